@@ -0,0 +1,370 @@
+use crate::bulk::load_batch_config;
+use crate::config::{Config, McpServer, Target};
+use crate::github::ClientOptions;
+use crate::templates::TemplateManager;
+use crate::transaction;
+use crate::utils;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use std::collections::{BTreeMap, HashSet};
+
+/// Exit code used by `apply --dry-run --exit-code` when the config has drifted
+pub const DRIFT_EXIT_CODE: i32 = 2;
+
+/// Changes required to converge the live config on the declared state
+#[derive(Debug, Default)]
+pub struct ApplyPlan {
+    pub to_add: Vec<(String, McpServer)>,
+    pub to_update: Vec<(String, McpServer, McpServer)>,
+    pub to_remove: Vec<String>,
+    pub unchanged: Vec<String>,
+}
+
+impl ApplyPlan {
+    /// Whether the live config already matches the declared state
+    pub fn is_converged(&self) -> bool {
+        self.to_add.is_empty() && self.to_update.is_empty() && self.to_remove.is_empty()
+    }
+
+    /// Apply the planned changes to a configuration
+    pub fn apply_to(&self, config: &mut Config) {
+        for (name, server) in &self.to_add {
+            config.mcp_servers.insert(name.clone(), server.clone());
+        }
+        for (name, _, updated) in &self.to_update {
+            config.mcp_servers.insert(name.clone(), updated.clone());
+        }
+        for name in &self.to_remove {
+            config.mcp_servers.remove(name);
+        }
+    }
+}
+
+/// Handle apply command
+pub async fn handle_apply(
+    file: String,
+    prune: bool,
+    dry_run: bool,
+    exit_code: bool,
+    target: &Target,
+    github: &ClientOptions,
+    profile: Option<String>,
+) -> Result<()> {
+    let batch_config = load_batch_config(&file).await?;
+    let mut config = Config::load(target).await?;
+
+    // Only touch the template machinery when an entry actually needs it
+    let template_manager = if batch_config.servers.iter().any(|s| s.template.is_some()) {
+        Some(TemplateManager::new(github)?)
+    } else {
+        None
+    };
+
+    let mut desired = BTreeMap::new();
+    let mut secrets = Vec::new();
+    for entry in &batch_config.servers {
+        let (server, template) = match &template_manager {
+            Some(manager) => entry.resolve(manager).await,
+            None => entry.resolve_inline().map(|server| (server, None)),
+        }
+        .map_err(|e| anyhow!("Failed to resolve server '{}': {}", entry.name, e))?;
+
+        if let Some(template) = template {
+            secrets.extend(template.secret_values(&entry.json_vars()));
+        }
+
+        if desired.insert(entry.name.clone(), server).is_some() {
+            return Err(anyhow!(
+                "Server '{}' is declared more than once in {}",
+                entry.name,
+                file
+            ));
+        }
+    }
+
+    let plan = compute_plan(&desired, &config, prune);
+
+    if plan.is_converged() {
+        println!(
+            "{}",
+            format!(
+                "✅ Configuration already matches {} ({} server(s))",
+                file,
+                plan.unchanged.len()
+            )
+            .green()
+        );
+        return Ok(());
+    }
+
+    display_plan(&plan, &secrets, dry_run).await?;
+
+    if dry_run {
+        println!();
+        println!("Run without --dry-run to apply these changes.");
+        if exit_code {
+            return Err(
+                utils::ExitStatus::new(DRIFT_EXIT_CODE, "Configuration has drifted").into(),
+            );
+        }
+        return Ok(());
+    }
+
+    // Create backup before modification
+    crate::backup::backup_before_change(target, &config).await?;
+
+    plan.apply_to(&mut config);
+    transaction::save_config(&config, target, profile.as_deref(), None).await?;
+
+    println!();
+    println!(
+        "{}",
+        format!(
+            "✅ Applied {}: {} added, {} updated, {} removed",
+            file,
+            plan.to_add.len(),
+            plan.to_update.len(),
+            plan.to_remove.len()
+        )
+        .green()
+        .bold()
+    );
+
+    Ok(())
+}
+
+/// Diff the declared servers against the live configuration
+///
+/// Only the fields a declaration sets are compared. Fields it leaves out, such
+/// as `timeout`, `autoApprove` or anything else added to the live server by
+/// hand, are not drift and are kept in the updated server.
+pub fn compute_plan(
+    desired: &BTreeMap<String, McpServer>,
+    current: &Config,
+    prune: bool,
+) -> ApplyPlan {
+    let mut plan = ApplyPlan::default();
+
+    for (name, server) in desired {
+        match current.mcp_servers.get(name) {
+            None => plan.to_add.push((name.clone(), server.clone())),
+            Some(existing) => {
+                let updated = overlay(existing, server);
+                if &updated == existing {
+                    plan.unchanged.push(name.clone());
+                } else {
+                    plan.to_update
+                        .push((name.clone(), existing.clone(), updated));
+                }
+            }
+        }
+    }
+
+    if prune {
+        let declared: HashSet<&String> = desired.keys().collect();
+        plan.to_remove = current
+            .mcp_servers
+            .keys()
+            .filter(|name| !declared.contains(name))
+            .cloned()
+            .collect();
+        plan.to_remove.sort();
+    }
+
+    plan
+}
+
+/// `declared` laid over `existing`, keeping the fields the declaration leaves out
+fn overlay(existing: &McpServer, declared: &McpServer) -> McpServer {
+    let mut other = existing.other.clone();
+    other.extend(declared.other.clone());
+    McpServer {
+        command: declared.command.clone(),
+        args: declared.args.clone(),
+        url: declared.url.clone(),
+        env: declared.env.clone(),
+        timeout: declared.timeout.or(existing.timeout),
+        auto_approve: declared
+            .auto_approve
+            .clone()
+            .or_else(|| existing.auto_approve.clone()),
+        other,
+    }
+}
+
+/// Display the plan using the shared server diff renderer
+///
+/// Values of secret template variables are masked wherever they appear.
+async fn display_plan(plan: &ApplyPlan, secrets: &[String], dry_run: bool) -> Result<()> {
+    let title = if dry_run {
+        "Apply Plan (Dry Run)".cyan().bold()
+    } else {
+        "Apply Plan".cyan().bold()
+    };
+    println!("{}", title);
+    println!("{}", "──────────".cyan());
+
+    for (name, server) in &plan.to_add {
+        let server = server.with_values_masked(secrets);
+        let server_desc = if server.is_url_server() {
+            server
+                .url
+                .as_ref()
+                .map(|u| utils::mask_sensitive_url(u))
+                .unwrap_or_else(|| "URL".to_string())
+        } else {
+            server
+                .command
+                .clone()
+                .unwrap_or_else(|| "(no command)".to_string())
+        };
+        println!("  {} {} - {}", "NEW".green(), name.bold(), server_desc);
+    }
+
+    for (name, current, desired) in &plan.to_update {
+        println!("  {} {}", "UPDATE".yellow(), name.bold());
+        crate::cli::show_server_diff(
+            &current.with_values_masked(secrets),
+            &desired.with_values_masked(secrets),
+            name,
+        )
+        .await?;
+    }
+
+    for name in &plan.to_remove {
+        println!("  {} {}", "REMOVE".red(), name.bold());
+    }
+
+    println!();
+    println!(
+        "Plan: {} to add, {} to update, {} to remove, {} unchanged",
+        plan.to_add.len().to_string().green(),
+        plan.to_update.len().to_string().yellow(),
+        plan.to_remove.len().to_string().red(),
+        plan.unchanged.len()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn command_server(command: &str, args: &[&str]) -> McpServer {
+        McpServer {
+            command: Some(command.to_string()),
+            args: Some(args.iter().map(|s| s.to_string()).collect()),
+            url: None,
+            env: None,
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::new(),
+        }
+    }
+
+    fn live_config() -> Config {
+        let mut config = Config::default();
+        config
+            .mcp_servers
+            .insert("keep".to_string(), command_server("node", &["keep.js"]));
+        config
+            .mcp_servers
+            .insert("drift".to_string(), command_server("node", &["old.js"]));
+        config
+            .mcp_servers
+            .insert("extra".to_string(), command_server("python", &[]));
+        config
+    }
+
+    fn declared() -> BTreeMap<String, McpServer> {
+        let mut desired = BTreeMap::new();
+        desired.insert("keep".to_string(), command_server("node", &["keep.js"]));
+        desired.insert("drift".to_string(), command_server("node", &["new.js"]));
+        desired.insert("fresh".to_string(), command_server("uvx", &["tool"]));
+        desired
+    }
+
+    #[test]
+    fn test_compute_plan_without_prune() {
+        let plan = compute_plan(&declared(), &live_config(), false);
+
+        assert_eq!(plan.to_add.len(), 1);
+        assert_eq!(plan.to_add[0].0, "fresh");
+        assert_eq!(plan.to_update.len(), 1);
+        assert_eq!(plan.to_update[0].0, "drift");
+        assert_eq!(plan.unchanged, vec!["keep".to_string()]);
+        assert!(plan.to_remove.is_empty());
+        assert!(!plan.is_converged());
+    }
+
+    #[test]
+    fn test_compute_plan_with_prune() {
+        let plan = compute_plan(&declared(), &live_config(), true);
+        assert_eq!(plan.to_remove, vec!["extra".to_string()]);
+    }
+
+    #[test]
+    fn test_apply_plan_converges() {
+        let mut config = live_config();
+        let plan = compute_plan(&declared(), &config, true);
+        plan.apply_to(&mut config);
+
+        let replan = compute_plan(&declared(), &config, true);
+        assert!(replan.is_converged());
+        assert_eq!(replan.unchanged.len(), 3);
+        assert_eq!(
+            config.mcp_servers["drift"].args,
+            Some(vec!["new.js".to_string()])
+        );
+        assert!(!config.mcp_servers.contains_key("extra"));
+    }
+
+    #[test]
+    fn test_env_drift_is_detected() {
+        let mut config = Config::default();
+        let mut server = command_server("node", &[]);
+        server.env = Some(HashMap::from([("PORT".to_string(), "1".to_string())]));
+        config.mcp_servers.insert("svc".to_string(), server.clone());
+
+        let mut desired = BTreeMap::new();
+        server.env = Some(HashMap::from([("PORT".to_string(), "2".to_string())]));
+        desired.insert("svc".to_string(), server);
+
+        let plan = compute_plan(&desired, &config, false);
+        assert_eq!(plan.to_update.len(), 1);
+    }
+
+    #[test]
+    fn test_fields_added_by_hand_are_kept() {
+        let mut config = Config::default();
+        let mut live = command_server("node", &["old.js"]);
+        live.timeout = Some(30);
+        live.auto_approve = Some(vec!["read".to_string()]);
+        live.other
+            .insert("cwd".to_string(), serde_json::json!("/srv"));
+        config.mcp_servers.insert("svc".to_string(), live);
+
+        let mut desired = BTreeMap::new();
+        desired.insert("svc".to_string(), command_server("node", &["old.js"]));
+        let plan = compute_plan(&desired, &config, false);
+        assert!(plan.is_converged());
+
+        desired.insert("svc".to_string(), command_server("node", &["new.js"]));
+        let plan = compute_plan(&desired, &config, false);
+        plan.apply_to(&mut config);
+        let svc = &config.mcp_servers["svc"];
+        assert_eq!(svc.args, Some(vec!["new.js".to_string()]));
+        assert_eq!(svc.timeout, Some(30));
+        assert_eq!(svc.auto_approve, Some(vec!["read".to_string()]));
+        assert_eq!(svc.other["cwd"], "/srv");
+    }
+
+    #[test]
+    fn test_empty_declaration_with_prune_removes_everything() {
+        let plan = compute_plan(&BTreeMap::new(), &live_config(), true);
+        assert_eq!(plan.to_remove.len(), 3);
+        assert!(plan.to_add.is_empty());
+    }
+}
@@ -1,7 +1,13 @@
-use crate::config::Config;
-use crate::profiles::update_profile_server_count;
+use crate::clock::{Clock, SystemClock};
+use crate::config::{Config, McpServer, Target};
+use crate::deletions::PlannedDeletions;
+use crate::metadata::ServerMetadata;
+use crate::search::ServerInfo;
+use crate::selection::{self, Selection};
+use crate::timefmt::{self, Zone};
+use crate::transaction;
 use crate::utils;
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Duration, Utc};
 use clap::Subcommand;
 use colored::Colorize;
@@ -9,6 +15,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 /// Backup metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +26,16 @@ pub struct BackupMetadata {
     pub description: Option<String>,
     pub git_branch: Option<String>,
     pub git_commit: Option<String>,
+    /// Servers marked exclude_from_share when the backup was taken; the backup
+    /// still holds them, so it should not be shared as-is
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excluded_from_share: Vec<String>,
+    /// Client the config was taken from, when that was not Claude Desktop
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Config file the backup was taken from, with symlinks followed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_file: Option<PathBuf>,
 }
 
 /// Backup entry combining metadata and file path
@@ -28,6 +45,22 @@ pub struct BackupEntry {
     pub file_path: PathBuf,
 }
 
+/// Backup containing servers that match a search term
+#[derive(Debug, Serialize)]
+pub struct BackupSearchHit {
+    pub backup: String,
+    pub created_at: DateTime<Utc>,
+    pub file: PathBuf,
+    pub servers: Vec<ServerMatch>,
+}
+
+/// Matching server within a backup
+#[derive(Debug, Serialize)]
+pub struct ServerMatch {
+    pub name: String,
+    pub summary: String,
+}
+
 #[derive(Subcommand)]
 pub enum BackupCommands {
     /// Create backup
@@ -39,8 +72,35 @@ pub enum BackupCommands {
         #[arg(long)]
         auto_name: bool,
     },
-    /// List available backups
-    List,
+    /// List available backups, newest first
+    List {
+        /// Only backups taken since this long ago (e.g. 7d) or this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only backups taken before this long ago (e.g. 30d) or up to the end of this date
+        #[arg(long)]
+        until: Option<String>,
+        /// Show at most this many backups
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Only backups whose name contains this text (case-insensitive)
+        #[arg(long)]
+        name_contains: Option<String>,
+        /// One line per backup: name, age, server count and size (the default above 20 backups)
+        #[arg(long, conflicts_with = "long")]
+        oneline: bool,
+        /// Every detail of each backup (the default for 20 backups or fewer)
+        #[arg(long)]
+        long: bool,
+    },
+    /// Find backups containing a server whose name, command or URL matches
+    Search {
+        /// Search term (case-insensitive)
+        term: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Restore from backup
     Restore {
         /// Backup name or file
@@ -48,9 +108,15 @@ pub enum BackupCommands {
         /// Preview restore without applying
         #[arg(long)]
         preview: bool,
-        /// Restore specific server only
+        /// Restore only this server (repeatable)
         #[arg(long)]
-        server: Option<String>,
+        server: Vec<String>,
+        /// Restore only these fields of the server (command, args, url, env, timeout, auto-approve)
+        #[arg(long, value_delimiter = ',', requires = "server")]
+        fields: Vec<String>,
+        /// When a partial name matches several backups, use the newest
+        #[arg(long)]
+        latest_match: bool,
     },
     /// Clean old backups
     Clean {
@@ -60,42 +126,112 @@ pub enum BackupCommands {
         /// Force cleanup without confirmation
         #[arg(long)]
         force: bool,
+        /// Show what would be deleted without deleting it
+        #[arg(long)]
+        dry_run: bool,
     },
 }
 
 /// Handle backup command routing
-pub async fn handle_backup_command(action: BackupCommands, profile: Option<String>) -> Result<()> {
+pub async fn handle_backup_command(
+    action: BackupCommands,
+    target: &Target,
+    profile: Option<String>,
+    zone: Zone,
+) -> Result<()> {
     match action {
         BackupCommands::Create { name, auto_name } => {
-            create_backup_with_options(name, auto_name, profile).await
+            create_backup_with_options(name, auto_name, target).await
+        }
+        BackupCommands::List {
+            since,
+            until,
+            limit,
+            name_contains,
+            oneline,
+            long,
+        } => {
+            let now = SystemClock.now();
+            let filter = BackupFilter {
+                since: since
+                    .map(|since| parse_time_bound(&since, now, false, zone))
+                    .transpose()?,
+                until: until
+                    .map(|until| parse_time_bound(&until, now, true, zone))
+                    .transpose()?,
+                name_contains,
+                limit,
+            };
+            let oneline = match (oneline, long) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            };
+            handle_backup_list(target, filter, oneline, &SystemClock, zone).await
+        }
+        BackupCommands::Search { term, json } => {
+            handle_backup_search(target, term, json, zone).await
         }
-        BackupCommands::List => handle_backup_list().await,
         BackupCommands::Restore {
             backup,
             preview,
             server,
-        } => restore_backup(backup, preview, server, profile).await,
-        BackupCommands::Clean { older_than, force } => handle_backup_clean(older_than, force).await,
+            fields,
+            latest_match,
+        } => {
+            let servers = Selection::new(server);
+            restore_backup(
+                backup,
+                preview,
+                servers,
+                fields,
+                latest_match,
+                target,
+                profile,
+                zone,
+            )
+            .await
+        }
+        BackupCommands::Clean {
+            older_than,
+            force,
+            dry_run,
+        } => handle_backup_clean(target, older_than, force, dry_run, &SystemClock).await,
     }
 }
 
 /// Public wrapper for restore functionality
+#[allow(clippy::too_many_arguments)]
 pub async fn restore_backup(
     backup: String,
     preview: bool,
-    server: Option<String>,
+    servers: Selection,
+    fields: Vec<String>,
+    latest_match: bool,
+    target: &Target,
     profile: Option<String>,
+    zone: Zone,
 ) -> Result<()> {
-    handle_backup_restore(backup, preview, server, profile).await
+    handle_backup_restore(
+        backup,
+        preview,
+        servers,
+        fields,
+        latest_match,
+        target,
+        profile,
+        zone,
+    )
+    .await
 }
 
 /// Create backup with options handling
 pub async fn create_backup_with_options(
     name: Option<String>,
     auto_name: bool,
-    profile: Option<String>,
+    target: &Target,
 ) -> Result<()> {
-    let config = Config::load(profile.as_deref()).await?;
+    let config = Config::load(target).await?;
 
     let backup_name = if auto_name {
         format!("auto_{}", chrono::Utc::now().format("%Y%m%d_%H%M%S"))
@@ -103,76 +239,423 @@ pub async fn create_backup_with_options(
         name.unwrap_or_else(|| chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string())
     };
 
-    let backup_path = create_backup(&config, &backup_name).await?;
+    let excluded = ServerMetadata::load(target)?.excluded_servers(&config);
+    let backup_path = create_backup(target, &config, &backup_name, excluded.clone()).await?;
     println!("✅ Backup created: {}", backup_path.display());
+    if !excluded.is_empty() {
+        println!(
+            "  Includes {} server(s) marked exclude_from_share: {}; don't share this backup as-is",
+            excluded.len(),
+            excluded.join(", ")
+        );
+    }
 
     Ok(())
 }
 
-/// List all available backups
-async fn handle_backup_list() -> Result<()> {
-    let backups = list_backups().await?;
+/// Above this many backups, `backup list` shows one line per backup unless `--long` is given
+const ONELINE_THRESHOLD: usize = 20;
+
+/// Which backups `backup list` shows
+#[derive(Debug, Default)]
+struct BackupFilter {
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    name_contains: Option<String>,
+    limit: Option<usize>,
+}
+
+impl BackupFilter {
+    /// The matching backups newest first, cut to the limit, and how many matched
+    fn apply(&self, mut backups: Vec<BackupEntry>) -> (Vec<BackupEntry>, usize) {
+        let needle = self.name_contains.as_ref().map(|n| n.to_lowercase());
+        backups.retain(|backup| {
+            let created_at = backup.metadata.created_at;
+            self.since.is_none_or(|since| created_at >= since)
+                && self.until.is_none_or(|until| created_at < until)
+                && needle
+                    .as_ref()
+                    .is_none_or(|n| backup.metadata.name.to_lowercase().contains(n))
+        });
+        backups.sort_by_key(|b| std::cmp::Reverse(b.metadata.created_at));
+
+        let matched = backups.len();
+        if let Some(limit) = self.limit {
+            backups.truncate(limit);
+        }
+        (backups, matched)
+    }
+
+    fn is_filtering(&self) -> bool {
+        self.since.is_some() || self.until.is_some() || self.name_contains.is_some()
+    }
+}
+
+/// Parse a `--since` or `--until` bound: a duration ago, like `7d`, or a
+/// `YYYY-MM-DD` date, which means the start of that day or, with
+/// `end_of_day`, the end of it
+fn parse_time_bound(
+    value: &str,
+    now: DateTime<Utc>,
+    end_of_day: bool,
+    zone: Zone,
+) -> Result<DateTime<Utc>> {
+    if let Some(start) = timefmt::parse_date(value, zone) {
+        return Ok(if end_of_day {
+            start + Duration::days(1)
+        } else {
+            start
+        });
+    }
+    utils::duration::parse_duration(value)
+        .map(|duration| now - duration)
+        .map_err(|_| {
+            anyhow!(
+                "Invalid time '{}'. Use a duration ago like 7d or 12h, or a date like 2026-03-01",
+                value
+            )
+        })
+}
+
+/// List available backups, newest first
+///
+/// `oneline` picks the layout; without it, more than `ONELINE_THRESHOLD`
+/// backups are shown one per line.
+async fn handle_backup_list(
+    target: &Target,
+    filter: BackupFilter,
+    oneline: Option<bool>,
+    clock: &dyn Clock,
+    zone: Zone,
+) -> Result<()> {
+    let backups = list_backups(target).await?;
+    let total = backups.len();
 
     if backups.is_empty() {
         println!("{}", "No backups found.".yellow());
         return Ok(());
     }
 
+    let (backups, matched) = filter.apply(backups);
+    if backups.is_empty() {
+        println!(
+            "{}",
+            format!("None of the {} backup(s) match.", total).yellow()
+        );
+        return Ok(());
+    }
+
     println!("{}", "Available Backups".cyan().bold());
     println!("{}", "─────────────────".cyan());
 
-    // Sort by creation date, newest first
-    let mut sorted_backups = backups;
-    sorted_backups.sort_by(|a, b| b.metadata.created_at.cmp(&a.metadata.created_at));
+    if oneline.unwrap_or(backups.len() > ONELINE_THRESHOLD) {
+        print_backups_oneline(&backups, clock);
+    } else {
+        for backup in &backups {
+            print_backup_long(backup, clock, zone);
+        }
+    }
 
-    for backup in sorted_backups {
-        let age = format_duration_since(backup.metadata.created_at);
+    if backups.len() < matched || filter.is_filtering() {
         println!();
-        println!("• {}", backup.metadata.name.bold());
         println!(
-            "  Created: {} ({})",
-            backup.metadata.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
-            age.dimmed()
+            "{}",
+            format!("Showing {} of {} backup(s)", backups.len(), total).dimmed()
         );
-        println!("  Servers: {}", backup.metadata.servers_count);
+    }
 
-        if let Some(desc) = &backup.metadata.description {
-            println!("  Description: {}", desc.italic());
-        }
+    Ok(())
+}
 
-        if let Some(branch) = &backup.metadata.git_branch {
-            println!("  Git branch: {}", branch.green());
-        }
+/// Print one line per backup: name, age, server count and file size
+fn print_backups_oneline(backups: &[BackupEntry], clock: &dyn Clock) {
+    let rows: Vec<(String, String, String, String)> = backups
+        .iter()
+        .map(|backup| {
+            let size = fs::metadata(&backup.file_path)
+                .map(|m| crate::deletions::format_size(m.len()))
+                .unwrap_or_else(|_| "?".to_string());
+            (
+                backup.metadata.name.clone(),
+                timefmt::format_age(backup.metadata.created_at, clock.now()),
+                timefmt::pluralize(backup.metadata.servers_count as i64, "server", "servers"),
+                size,
+            )
+        })
+        .collect();
+    let width = |column: fn(&(String, String, String, String)) -> &String| {
+        rows.iter()
+            .map(|row| column(row).chars().count())
+            .max()
+            .unwrap_or(0)
+    };
+    let (name_width, age_width, servers_width) =
+        (width(|r| &r.0), width(|r| &r.1), width(|r| &r.2));
 
-        if let Some(commit) = &backup.metadata.git_commit {
-            println!("  Git commit: {}", commit.dimmed());
-        }
+    // Pad before colouring, so the escape codes don't count towards the width
+    for (name, age, servers, size) in &rows {
+        println!(
+            "{}  {}  {:<servers_width$}  {}",
+            format!("{:<name_width$}", name).bold(),
+            format!("{:<age_width$}", age).dimmed(),
+            servers,
+            size
+        );
+    }
+}
+
+/// Print every detail of a backup
+fn print_backup_long(backup: &BackupEntry, clock: &dyn Clock, zone: Zone) {
+    let age = timefmt::format_age(backup.metadata.created_at, clock.now());
+    println!();
+    println!("• {}", backup.metadata.name.bold());
+    println!(
+        "  Created: {} ({})",
+        timefmt::format_timestamp(backup.metadata.created_at, zone),
+        age.dimmed()
+    );
+    println!("  Servers: {}", backup.metadata.servers_count);
+    if let Some(target) = &backup.metadata.target {
+        println!("  Taken from: {}", target);
+    }
+    if !backup.metadata.excluded_from_share.is_empty() {
+        println!(
+            "  Not for sharing: {}",
+            backup.metadata.excluded_from_share.join(", ").yellow()
+        );
+    }
+
+    if let Some(desc) = &backup.metadata.description {
+        println!("  Description: {}", desc.italic());
+    }
+
+    if let Some(branch) = &backup.metadata.git_branch {
+        println!("  Git branch: {}", branch.green());
+    }
+
+    if let Some(commit) = &backup.metadata.git_commit {
+        println!("  Git commit: {}", commit.dimmed());
+    }
+
+    println!(
+        "  File: {}",
+        backup.file_path.display().to_string().dimmed()
+    );
+}
+
+/// Search backups by content
+async fn handle_backup_search(target: &Target, term: String, json: bool, zone: Zone) -> Result<()> {
+    let backup_dir = target.backup_dir()?;
+    let (hits, skipped) = search_backups_in(&backup_dir, &term).await?;
+
+    for (path, error) in &skipped {
+        eprintln!(
+            "{} Skipping unreadable backup {}: {}",
+            "⚠".yellow(),
+            path.display(),
+            error
+        );
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&hits)?);
+        return Ok(());
+    }
 
+    if hits.is_empty() {
         println!(
-            "  File: {}",
-            backup.file_path.display().to_string().dimmed()
+            "{}",
+            format!("No backups contain a server matching '{}'.", term).yellow()
         );
+        return Ok(());
+    }
+
+    println!("{}", format!("Backups containing '{}'", term).cyan().bold());
+    for hit in &hits {
+        println!();
+        println!(
+            "• {} ({})",
+            hit.backup.bold(),
+            timefmt::format_timestamp(hit.created_at, zone)
+        );
+        for server in &hit.servers {
+            println!("  {} - {}", server.name.green(), server.summary);
+        }
+        println!("  File: {}", hit.file.display().to_string().dimmed());
     }
 
     Ok(())
 }
 
+/// Scan the backups in `dir` for servers matching `term`, newest first
+///
+/// Backups are loaded one at a time and only the matches are kept. Files that
+/// can't be read or parsed are returned separately with the reason.
+async fn search_backups_in(
+    dir: &Path,
+    term: &str,
+) -> Result<(Vec<BackupSearchHit>, Vec<(PathBuf, String)>)> {
+    let mut hits = Vec::new();
+    let mut skipped = Vec::new();
+
+    if !dir.exists() {
+        return Ok((hits, skipped));
+    }
+
+    let term = term.to_lowercase();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+
+        let backup_data = match load_backup_data(&path).await {
+            Ok(data) => data,
+            Err(e) => {
+                skipped.push((path, e.to_string()));
+                continue;
+            }
+        };
+
+        let mut servers: Vec<ServerMatch> = backup_data
+            .config
+            .mcp_servers
+            .into_iter()
+            .filter(|(name, server)| server_matches(name, server, &term))
+            .map(|(name, server)| {
+                let info = ServerInfo::from((name, server));
+                ServerMatch {
+                    summary: info.summary(),
+                    name: info.name,
+                }
+            })
+            .collect();
+
+        if !servers.is_empty() {
+            servers.sort_by(|a, b| a.name.cmp(&b.name));
+            hits.push(BackupSearchHit {
+                backup: backup_data.metadata.name,
+                created_at: backup_data.metadata.created_at,
+                file: path,
+                servers,
+            });
+        }
+    }
+
+    hits.sort_by_key(|hit| std::cmp::Reverse(hit.created_at));
+    Ok((hits, skipped))
+}
+
+/// A server as it was stored in one backup
+#[derive(Debug, Clone)]
+pub struct BackupServerVersion {
+    pub backup: String,
+    pub created_at: DateTime<Utc>,
+    pub file: PathBuf,
+    pub server: McpServer,
+}
+
+/// Every backup holding a server called `name`, newest first
+///
+/// Unreadable backups are skipped; `backup search` is the place to report them.
+pub async fn server_versions(target: &Target, name: &str) -> Result<Vec<BackupServerVersion>> {
+    let backup_dir = target.backup_dir()?;
+    if !backup_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut versions = Vec::new();
+    for entry in fs::read_dir(backup_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+        let Some((backup, created_at, mut config)) = load_any_backup(&path).await else {
+            continue;
+        };
+        if let Some(server) = config.mcp_servers.remove(name) {
+            versions.push(BackupServerVersion {
+                backup,
+                created_at,
+                file: path,
+                server,
+            });
+        }
+    }
+
+    versions.sort_by_key(|v| std::cmp::Reverse(v.created_at));
+    Ok(versions)
+}
+
+/// Load either a `backup create` file or a plain config copy taken before an edit
+///
+/// Plain copies carry no metadata, so they are named after the file and dated by
+/// its modification time.
+async fn load_any_backup(path: &Path) -> Option<(String, DateTime<Utc>, Config)> {
+    if let Ok(data) = load_backup_data(path).await {
+        return Some((data.metadata.name, data.metadata.created_at, data.config));
+    }
+
+    let config: Config = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    let name = path.file_stem()?.to_string_lossy().into_owned();
+    Some((name, DateTime::<Utc>::from(modified), config))
+}
+
+/// Whether a server's name, command or URL contains the lowercase `term`
+fn server_matches(name: &str, server: &McpServer, term: &str) -> bool {
+    [Some(name), server.command.as_deref(), server.url.as_deref()]
+        .into_iter()
+        .flatten()
+        .any(|field| field.to_lowercase().contains(term))
+}
+
 /// Restore from backup
+#[allow(clippy::too_many_arguments)]
 async fn handle_backup_restore(
     backup_name: String,
     preview: bool,
-    server_filter: Option<String>,
+    servers: Selection,
+    fields: Vec<String>,
+    latest_match: bool,
+    target: &Target,
     profile: Option<String>,
+    zone: Zone,
 ) -> Result<()> {
-    let backup = find_backup(&backup_name)
+    let backup = find_backup(target, &backup_name, latest_match, zone)
         .await?
         .ok_or_else(|| anyhow!("Backup '{}' not found", backup_name))?;
 
     let backup_config = load_backup_config(&backup.file_path).await?;
-    let current_config = Config::load(profile.as_deref()).await.unwrap_or_default();
+    let current_config = Config::load(target).await.unwrap_or_default();
+
+    let missing = servers.missing(|name| backup_config.mcp_servers.contains_key(name));
+    if let Some(missing) = selection::describe_missing(&missing) {
+        return Err(anyhow!("{} in backup '{}'", missing, backup.metadata.name));
+    }
+
+    if !fields.is_empty() {
+        let [server_name] = servers.names() else {
+            return Err(anyhow!(
+                "--fields restores one server at a time; give a single --server"
+            ));
+        };
+        let fields = parse_restore_fields(&fields)?;
+        return restore_server_fields(
+            &backup,
+            &backup_config,
+            current_config,
+            server_name,
+            &fields,
+            preview,
+            target,
+            profile.as_deref(),
+        )
+        .await;
+    }
 
     if preview {
-        preview_restore(&current_config, &backup_config, server_filter.as_deref()).await?;
+        preview_restore(&current_config, &backup_config, &servers).await?;
         return Ok(());
     }
 
@@ -181,14 +664,24 @@ async fn handle_backup_restore(
         format!("Restoring from backup '{}'...", backup.metadata.name).cyan()
     );
 
-    if let Some(server_name) = server_filter {
-        restore_single_server(&backup_config, &server_name, profile.as_deref()).await?;
+    if let [server_name] = servers.names() {
+        restore_servers(&backup_config, &servers, target, profile.as_deref()).await?;
         println!(
             "{}",
             format!("✓ Server '{}' restored successfully", server_name).green()
         );
+    } else if !servers.is_all() {
+        restore_servers(&backup_config, &servers, target, profile.as_deref()).await?;
+        println!(
+            "{}",
+            format!(
+                "✓ Servers restored successfully: {}",
+                servers.names().join(", ")
+            )
+            .green()
+        );
     } else {
-        restore_full_config(&backup_config, profile.as_deref()).await?;
+        restore_full_config(&backup_config, target, profile.as_deref()).await?;
         println!("{}", "✓ Configuration restored successfully".green());
         println!("  Servers restored: {}", backup_config.mcp_servers.len());
     }
@@ -196,24 +689,54 @@ async fn handle_backup_restore(
     Ok(())
 }
 
+/// Backups created more than `duration` before `now`
+fn backups_older_than(
+    backups: Vec<BackupEntry>,
+    duration: Duration,
+    now: DateTime<Utc>,
+) -> Vec<BackupEntry> {
+    let cutoff = now - duration;
+    backups
+        .into_iter()
+        .filter(|backup| backup.metadata.created_at < cutoff)
+        .collect()
+}
+
 /// Clean old backups
-async fn handle_backup_clean(older_than: Option<String>, force: bool) -> Result<()> {
+async fn handle_backup_clean(
+    target: &Target,
+    older_than: Option<String>,
+    force: bool,
+    dry_run: bool,
+    clock: &dyn Clock,
+) -> Result<()> {
     let duration = if let Some(duration_str) = older_than {
-        parse_duration(&duration_str)?
+        utils::duration::parse_duration(&duration_str)?
     } else {
         Duration::days(30) // Default: 30 days
     };
 
-    let backups = list_backups().await?;
-    let cutoff_date = Utc::now() - duration;
-
-    let old_backups: Vec<_> = backups
-        .into_iter()
-        .filter(|backup| backup.metadata.created_at < cutoff_date)
-        .collect();
+    let now = clock.now();
+    let old_backups = backups_older_than(list_backups(target).await?, duration, now);
 
     if old_backups.is_empty() {
-        println!("{}", "No old backups to clean.".green());
+        println!(
+            "{}",
+            format!(
+                "No backups older than {} to clean.",
+                utils::duration::format_duration(duration)
+            )
+            .green()
+        );
+        return Ok(());
+    }
+
+    if dry_run {
+        let mut plan = PlannedDeletions::new();
+        for backup in &old_backups {
+            plan.add_file(&backup.file_path);
+        }
+        plan.print();
         return Ok(());
     }
 
@@ -222,7 +745,7 @@ async fn handle_backup_clean(older_than: Option<String>, force: bool) -> Result<
         format!("Found {} old backup(s) to clean:", old_backups.len()).cyan()
     );
     for backup in &old_backups {
-        let age = format_duration_since(backup.metadata.created_at);
+        let age = timefmt::format_age(backup.metadata.created_at, now);
         println!("  • {} ({})", backup.metadata.name, age.dimmed());
     }
 
@@ -258,12 +781,136 @@ async fn handle_backup_clean(older_than: Option<String>, force: bool) -> Result<
     Ok(())
 }
 
-/// Create a backup with a specific name
-pub async fn create_backup(config: &Config, name: &str) -> Result<PathBuf> {
-    let backup_dir = utils::get_backup_dir()?;
-    fs::create_dir_all(&backup_dir)?;
+/// How a command backs up the configuration before changing it, set with `--backup`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum BackupMode {
+    /// Take no backup
+    None,
+    /// Take a backup with a timestamped name
+    #[default]
+    Auto,
+    /// Take a backup with this name
+    Named(String),
+}
 
-    let backup_file = backup_dir.join(format!("{}.json", sanitize_filename(name)));
+impl std::str::FromStr for BackupMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value {
+            "none" => Ok(Self::None),
+            "auto" => Ok(Self::Auto),
+            _ => match value.strip_prefix("named:").map(str::trim) {
+                Some("") => Err("named: needs a backup name, e.g. named:pre-migration".to_string()),
+                Some(name) => Ok(Self::Named(name.to_string())),
+                None => Err(format!(
+                    "'{}' is not a backup mode; use none, auto or named:<name>",
+                    value
+                )),
+            },
+        }
+    }
+}
+
+/// How a run backs up the configuration before changing it, as chosen with
+/// `--backup`, and the backup it took
+///
+/// Clones share the backup taken, so a run takes at most one.
+#[derive(Debug, Clone, Default)]
+pub struct BackupPolicy {
+    mode: BackupMode,
+    taken: Arc<Mutex<Option<String>>>,
+}
+
+impl BackupPolicy {
+    pub fn new(mode: BackupMode) -> Self {
+        Self {
+            mode,
+            taken: Arc::default(),
+        }
+    }
+
+    /// Name of the backup this run took, if any, for the success footer
+    pub fn taken(&self) -> Option<String> {
+        self.taken.lock().unwrap().clone()
+    }
+
+    fn record(&self, name: String) {
+        *self.taken.lock().unwrap() = Some(name);
+    }
+}
+
+/// Back up `config` before changing it, as chosen with `--backup`
+///
+/// Returns where the backup was written, or `None` when backups are off or
+/// this run already took one.
+pub async fn backup_before_change(target: &Target, config: &Config) -> Result<Option<PathBuf>> {
+    let Some(name) = next_backup_name(target)? else {
+        return Ok(None);
+    };
+    let excluded = ServerMetadata::load(target)?.excluded_servers(config);
+    let path = create_backup(target, config, &name, excluded).await?;
+    target.backups.record(name);
+    Ok(Some(path))
+}
+
+/// Copy a config file that no longer parses before changing it
+pub fn copy_before_change(target: &Target, file: &Path) -> Result<Option<PathBuf>> {
+    let Some(name) = next_backup_name(target)? else {
+        return Ok(None);
+    };
+    let path = backup_path(target, &name)?;
+    fs::create_dir_all(target.backup_dir()?)?;
+    fs::copy(file, &path)?;
+    target.backups.record(name);
+    Ok(Some(path))
+}
+
+/// Name for the next pre-change backup, or `None` if none should be taken
+fn next_backup_name(target: &Target) -> Result<Option<String>> {
+    if target.backups.taken().is_some() {
+        return Ok(None);
+    }
+    match &target.backups.mode {
+        BackupMode::None => Ok(None),
+        BackupMode::Auto => {
+            let stamp = Utc::now().format("%Y%m%d_%H%M%S");
+            let mut name = format!("config_backup_{}", stamp);
+            // Commands run within the same second mustn't overwrite each other
+            let mut n = 2;
+            while backup_path(target, &name)?.exists() {
+                name = format!("config_backup_{}_{}", stamp, n);
+                n += 1;
+            }
+            Ok(Some(name))
+        }
+        BackupMode::Named(name) => {
+            if backup_path(target, name)?.exists() {
+                return Err(anyhow!(
+                    "A backup named '{}' already exists; choose another name for --backup named:<name>",
+                    name
+                ));
+            }
+            Ok(Some(name.clone()))
+        }
+    }
+}
+
+fn backup_path(target: &Target, name: &str) -> Result<PathBuf> {
+    Ok(target
+        .backup_dir()?
+        .join(format!("{}.json", utils::sanitize_filename(name))))
+}
+
+/// Create a backup with a specific name
+pub async fn create_backup(
+    target: &Target,
+    config: &Config,
+    name: &str,
+    excluded_from_share: Vec<String>,
+) -> Result<PathBuf> {
+    fs::create_dir_all(target.backup_dir()?)?;
+    let backup_file = backup_path(target, name)?;
 
     // Create metadata
     let metadata = BackupMetadata {
@@ -273,6 +920,15 @@ pub async fn create_backup(config: &Config, name: &str) -> Result<PathBuf> {
         description: None,
         git_branch: get_git_branch().await,
         git_commit: get_git_commit().await,
+        excluded_from_share,
+        target: match &target.host {
+            crate::config::Host::Claude => None,
+            host => Some(host.to_string()),
+        },
+        config_file: target
+            .config_path()
+            .ok()
+            .map(|path| utils::resolve_symlinks(&path)),
     };
 
     // Create backup structure
@@ -288,8 +944,8 @@ pub async fn create_backup(config: &Config, name: &str) -> Result<PathBuf> {
 }
 
 /// List all available backups
-async fn list_backups() -> Result<Vec<BackupEntry>> {
-    let backup_dir = utils::get_backup_dir()?;
+async fn list_backups(target: &Target) -> Result<Vec<BackupEntry>> {
+    let backup_dir = target.backup_dir()?;
 
     if !backup_dir.exists() {
         return Ok(Vec::new());
@@ -314,25 +970,77 @@ async fn list_backups() -> Result<Vec<BackupEntry>> {
     Ok(backups)
 }
 
-/// Find a backup by name or partial name
-async fn find_backup(name: &str) -> Result<Option<BackupEntry>> {
-    let backups = list_backups().await?;
+/// Find a backup by file path, name, or partial name
+async fn find_backup(
+    target: &Target,
+    name: &str,
+    latest_match: bool,
+    zone: Zone,
+) -> Result<Option<BackupEntry>> {
+    // A literal path must hold a backup, not just any JSON file
+    let path = Path::new(name);
+    if path.is_file() {
+        let backup_data = load_backup_data(path)
+            .await
+            .with_context(|| format!("'{}' is not an mcp-forge backup", name))?;
+        return Ok(Some(BackupEntry {
+            metadata: backup_data.metadata,
+            file_path: path.to_path_buf(),
+        }));
+    }
 
-    // First try exact match
-    for backup in &backups {
-        if backup.metadata.name == name {
-            return Ok(Some(backup.clone()));
-        }
+    select_backup(list_backups(target).await?, name, latest_match, zone)
+}
+
+/// Pick the backup a name refers to
+///
+/// An exact name wins outright. A partial name must match a single backup,
+/// unless `latest_match` allows picking the newest of several.
+fn select_backup(
+    backups: Vec<BackupEntry>,
+    name: &str,
+    latest_match: bool,
+    zone: Zone,
+) -> Result<Option<BackupEntry>> {
+    if let Some(exact) = backups.iter().find(|b| b.metadata.name == name) {
+        return Ok(Some(exact.clone()));
     }
 
-    // Then try partial match
-    for backup in &backups {
-        if backup.metadata.name.contains(name) {
-            return Ok(Some(backup.clone()));
-        }
+    let mut matches: Vec<BackupEntry> = backups
+        .into_iter()
+        .filter(|b| b.metadata.name.contains(name))
+        .collect();
+    matches.sort_by_key(|b| std::cmp::Reverse(b.metadata.created_at));
+    if matches.len() > 1 && !latest_match {
+        let candidates: Vec<String> = matches
+            .iter()
+            .map(|b| {
+                format!(
+                    "  • {} ({})",
+                    b.metadata.name,
+                    timefmt::format_timestamp(b.metadata.created_at, zone)
+                )
+            })
+            .collect();
+        return Err(anyhow!(
+            "'{}' matches {} backups:\n{}\nUse the full name, or pass --latest-match to use the newest",
+            name,
+            matches.len(),
+            candidates.join("\n")
+        ));
     }
 
-    Ok(None)
+    Ok(matches.into_iter().next())
+}
+
+/// Load the configuration held by the backup a name refers to
+pub async fn load_named_backup(target: &Target, name: &str, zone: Zone) -> Result<Config> {
+    let backup = find_backup(target, name, false, zone)
+        .await?
+        .ok_or_else(|| anyhow!("Backup '{}' not found", name))?;
+    load_backup_config(&backup.file_path)
+        .await
+        .with_context(|| format!("Failed to read backup '{}'", backup.metadata.name))
 }
 
 /// Load backup configuration
@@ -342,23 +1050,15 @@ async fn load_backup_config(backup_path: &Path) -> Result<Config> {
 }
 
 /// Preview what would be restored
-async fn preview_restore(
-    current: &Config,
-    backup: &Config,
-    server_filter: Option<&str>,
-) -> Result<()> {
+async fn preview_restore(current: &Config, backup: &Config, servers: &Selection) -> Result<()> {
     println!("{}", "Restore Preview".cyan().bold());
     println!("{}", "──────────────".cyan());
 
-    let servers_to_restore = if let Some(filter) = server_filter {
-        backup
-            .mcp_servers
-            .iter()
-            .filter(|(name, _)| name == &filter)
-            .collect::<HashMap<_, _>>()
-    } else {
-        backup.mcp_servers.iter().collect()
-    };
+    let servers_to_restore: HashMap<_, _> = backup
+        .mcp_servers
+        .iter()
+        .filter(|(name, _)| servers.contains(name))
+        .collect();
 
     if servers_to_restore.is_empty() {
         println!("{}", "No servers to restore.".yellow());
@@ -367,21 +1067,37 @@ async fn preview_restore(
 
     println!("Servers to be restored:");
     for (name, server) in &servers_to_restore {
-        let status = if current.mcp_servers.contains_key(*name) {
+        let existing = current.mcp_servers.get(*name);
+        let status = if existing.is_some() {
             "OVERWRITE".yellow()
         } else {
             "NEW".green()
         };
 
         let server_desc = if server.is_url_server() {
-            server.url.as_ref().map(|u| crate::utils::mask_sensitive_url(u)).unwrap_or_else(|| "URL".to_string())
+            server
+                .url
+                .as_ref()
+                .map(|u| crate::utils::mask_sensitive_url(u))
+                .unwrap_or_else(|| "URL".to_string())
         } else {
-            server.command.as_ref().unwrap_or(&"Command".to_string()).clone()
+            server
+                .command
+                .as_ref()
+                .unwrap_or(&"Command".to_string())
+                .clone()
         };
         println!("  {} {} - {}", status, name.bold(), server_desc);
+        if let Some(existing) = existing {
+            crate::utils::display::print_env_diff(
+                "      ",
+                &existing.env.clone().unwrap_or_default(),
+                &server.env.clone().unwrap_or_default(),
+            );
+        }
     }
 
-    if server_filter.is_none() {
+    if servers.is_all() {
         let current_only: Vec<_> = current
             .mcp_servers
             .keys()
@@ -400,36 +1116,194 @@ async fn preview_restore(
     Ok(())
 }
 
-/// Restore a single server
-async fn restore_single_server(
+/// Restore the selected servers, leaving the rest of the config alone
+async fn restore_servers(
     backup_config: &Config,
+    servers: &Selection,
+    target: &Target,
+    profile: Option<&str>,
+) -> Result<()> {
+    let mut current_config = Config::load(target).await.unwrap_or_default();
+    backup_before_change(target, &current_config).await?;
+    for server_name in servers.names() {
+        let server = backup_config
+            .mcp_servers
+            .get(server_name)
+            .ok_or_else(|| anyhow!("Server '{}' not found in backup", server_name))?;
+        current_config
+            .mcp_servers
+            .insert(server_name.to_string(), server.clone());
+    }
+
+    transaction::save_config(&current_config, target, profile, None).await?;
+
+    Ok(())
+}
+
+/// A server field that can be restored on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestoreField {
+    Command,
+    Args,
+    Url,
+    Env,
+    Timeout,
+    AutoApprove,
+}
+
+impl RestoreField {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "command" => Ok(Self::Command),
+            "args" => Ok(Self::Args),
+            "url" => Ok(Self::Url),
+            "env" => Ok(Self::Env),
+            "timeout" => Ok(Self::Timeout),
+            "auto-approve" | "autoapprove" => Ok(Self::AutoApprove),
+            other => Err(anyhow!(
+                "Unknown field '{}'. Use command, args, url, env, timeout or auto-approve",
+                other
+            )),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Command => "command",
+            Self::Args => "args",
+            Self::Url => "url",
+            Self::Env => "env",
+            Self::Timeout => "timeout",
+            Self::AutoApprove => "auto-approve",
+        }
+    }
+}
+
+fn parse_restore_fields(names: &[String]) -> Result<Vec<RestoreField>> {
+    let mut fields = Vec::new();
+    for name in names {
+        let field = RestoreField::parse(name)?;
+        if !fields.contains(&field) {
+            fields.push(field);
+        }
+    }
+    Ok(fields)
+}
+
+/// Copy the selected fields of a backed-up server onto the current one
+///
+/// Fields missing from the backup are cleared, so restoring `url` from a command
+/// server removes the current URL. Refuses results that fail `McpServer::validate`.
+pub fn merge_fields(
+    current: &McpServer,
+    backup: &McpServer,
+    fields: &[RestoreField],
+) -> Result<McpServer> {
+    let mut merged = current.clone();
+    for field in fields {
+        match field {
+            RestoreField::Command => merged.command = backup.command.clone(),
+            RestoreField::Args => merged.args = backup.args.clone(),
+            RestoreField::Url => merged.url = backup.url.clone(),
+            RestoreField::Env => merged.env = backup.env.clone(),
+            RestoreField::Timeout => merged.timeout = backup.timeout,
+            RestoreField::AutoApprove => merged.auto_approve = backup.auto_approve.clone(),
+        }
+    }
+
+    if let Err(e) = merged.validate() {
+        let names: Vec<&str> = fields.iter().map(RestoreField::name).collect();
+        let hint = match (
+            fields.contains(&RestoreField::Command),
+            fields.contains(&RestoreField::Url),
+        ) {
+            (true, false) => " Add url to --fields to restore the backup's server type too.",
+            (false, true) => " Add command to --fields to restore the backup's server type too.",
+            _ => "",
+        };
+        return Err(anyhow!(
+            "Restoring only {} would leave an invalid server: {}.{}",
+            names.join(", "),
+            e,
+            hint
+        ));
+    }
+    Ok(merged)
+}
+
+/// Restore some fields of one server, showing the field-level diff first
+#[allow(clippy::too_many_arguments)]
+async fn restore_server_fields(
+    backup: &BackupEntry,
+    backup_config: &Config,
+    mut current_config: Config,
     server_name: &str,
+    fields: &[RestoreField],
+    preview: bool,
+    target: &Target,
     profile: Option<&str>,
 ) -> Result<()> {
-    let server = backup_config
+    let backed_up = backup_config
         .mcp_servers
         .get(server_name)
         .ok_or_else(|| anyhow!("Server '{}' not found in backup", server_name))?;
+    let current = current_config.mcp_servers.get(server_name).ok_or_else(|| {
+        anyhow!(
+            "Server '{}' is not in the current config; restore it whole by leaving out --fields",
+            server_name
+        )
+    })?;
+
+    let merged = merge_fields(current, backed_up, fields)?;
+    let names: Vec<&str> = fields.iter().map(RestoreField::name).collect();
+    if merged == *current {
+        println!(
+            "{}",
+            format!(
+                "Nothing to restore: {} of '{}' already match the backup",
+                names.join(", "),
+                server_name
+            )
+            .yellow()
+        );
+        return Ok(());
+    }
 
-    let mut current_config = Config::load(profile).await.unwrap_or_default();
+    crate::cli::show_server_diff(current, &merged, server_name).await?;
+    if preview {
+        return Ok(());
+    }
+
+    backup_before_change(target, &current_config).await?;
     current_config
         .mcp_servers
-        .insert(server_name.to_string(), server.clone());
-
-    current_config.save(profile).await?;
-
-    // Update profile metadata
-    update_profile_server_count(profile).await?;
+        .insert(server_name.to_string(), merged);
+    transaction::save_config(&current_config, target, profile, None).await?;
 
+    println!();
+    println!(
+        "{}",
+        format!(
+            "✓ Restored {} of '{}' from backup '{}'",
+            names.join(", "),
+            server_name,
+            backup.metadata.name
+        )
+        .green()
+    );
     Ok(())
 }
 
 /// Restore full configuration
-async fn restore_full_config(backup_config: &Config, profile: Option<&str>) -> Result<()> {
-    backup_config.save(profile).await?;
-
-    // Update profile metadata
-    update_profile_server_count(profile).await?;
+async fn restore_full_config(
+    backup_config: &Config,
+    target: &Target,
+    profile: Option<&str>,
+) -> Result<()> {
+    if let Ok(current_config) = Config::load(target).await {
+        backup_before_change(target, &current_config).await?;
+    }
+    transaction::save_config(backup_config, target, profile, None).await?;
 
     Ok(())
 }
@@ -486,76 +1360,10 @@ async fn load_backup_data(path: &Path) -> Result<BackupData> {
     Ok(backup_data)
 }
 
-/// Parse duration string (e.g., "30d", "1w", "24h")
-fn parse_duration(duration_str: &str) -> Result<Duration> {
-    let duration_str = duration_str.trim().to_lowercase();
-
-    if let Some(num_str) = duration_str.strip_suffix('d') {
-        let days: i64 = num_str.parse()?;
-        Ok(Duration::days(days))
-    } else if let Some(num_str) = duration_str.strip_suffix('w') {
-        let weeks: i64 = num_str.parse()?;
-        Ok(Duration::weeks(weeks))
-    } else if let Some(num_str) = duration_str.strip_suffix('h') {
-        let hours: i64 = num_str.parse()?;
-        Ok(Duration::hours(hours))
-    } else if let Some(num_str) = duration_str.strip_suffix('m') {
-        let minutes: i64 = num_str.parse()?;
-        Ok(Duration::minutes(minutes))
-    } else {
-        // Try parsing as days
-        let days: i64 = duration_str
-            .parse()
-            .map_err(|_| anyhow!("Invalid duration format. Use format like '30d', '1w', '24h'"))?;
-        Ok(Duration::days(days))
-    }
-}
-
-/// Format duration since a timestamp
-fn format_duration_since(timestamp: DateTime<Utc>) -> String {
-    let duration = Utc::now().signed_duration_since(timestamp);
-
-    if duration.num_days() > 0 {
-        format!("{} day(s) ago", duration.num_days())
-    } else if duration.num_hours() > 0 {
-        format!("{} hour(s) ago", duration.num_hours())
-    } else if duration.num_minutes() > 0 {
-        format!("{} minute(s) ago", duration.num_minutes())
-    } else {
-        "Just now".to_string()
-    }
-}
-
-/// Sanitize filename by removing invalid characters
-fn sanitize_filename(name: &str) -> String {
-    name.chars()
-        .map(|c| match c {
-            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
-            c => c,
-        })
-        .collect()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_parse_duration() {
-        assert_eq!(parse_duration("30d").unwrap(), Duration::days(30));
-        assert_eq!(parse_duration("2w").unwrap(), Duration::weeks(2));
-        assert_eq!(parse_duration("24h").unwrap(), Duration::hours(24));
-        assert_eq!(parse_duration("60m").unwrap(), Duration::minutes(60));
-        assert_eq!(parse_duration("7").unwrap(), Duration::days(7));
-    }
-
-    #[test]
-    fn test_sanitize_filename() {
-        assert_eq!(sanitize_filename("feature/new-stuff"), "feature_new-stuff");
-        assert_eq!(sanitize_filename("backup:2024"), "backup_2024");
-        assert_eq!(sanitize_filename("normal-name"), "normal-name");
-    }
-
     #[test]
     fn test_backup_metadata() {
         let metadata = BackupMetadata {
@@ -565,9 +1373,316 @@ mod tests {
             description: Some("Test backup".to_string()),
             git_branch: Some("main".to_string()),
             git_commit: Some("abcd123".to_string()),
+            excluded_from_share: Vec::new(),
+            target: None,
+            config_file: None,
         };
 
         assert_eq!(metadata.name, "test");
         assert_eq!(metadata.servers_count, 5);
     }
+
+    fn backup_entry(name: &str, days_ago: i64) -> BackupEntry {
+        BackupEntry {
+            metadata: BackupMetadata {
+                name: name.to_string(),
+                created_at: Utc::now() - chrono::Duration::days(days_ago),
+                servers_count: 0,
+                description: None,
+                git_branch: None,
+                git_commit: None,
+                excluded_from_share: Vec::new(),
+                target: None,
+                config_file: None,
+            },
+            file_path: PathBuf::from(format!("{}.json", name)),
+        }
+    }
+
+    #[test]
+    fn test_backups_older_than_cutoff() {
+        use crate::clock::FixedClock;
+        use chrono::TimeZone;
+
+        let start = Utc.with_ymd_and_hms(2026, 3, 31, 12, 0, 0).unwrap();
+        let clock = FixedClock::at(start);
+        let backups = [
+            ("exactly-30d", start - Duration::days(30)),
+            (
+                "just-over",
+                start - Duration::days(30) - Duration::seconds(1),
+            ),
+            ("recent", start - Duration::days(2)),
+        ]
+        .map(|(name, created_at)| {
+            let mut entry = backup_entry(name, 0);
+            entry.metadata.created_at = created_at;
+            entry
+        });
+        let older_than_30d = |now| {
+            backups_older_than(backups.to_vec(), Duration::days(30), now)
+                .into_iter()
+                .map(|e| e.metadata.name)
+                .collect::<Vec<_>>()
+        };
+
+        // A backup exactly at the cutoff is kept
+        assert_eq!(older_than_30d(clock.now()), ["just-over"]);
+
+        clock.advance(Duration::seconds(1));
+        assert_eq!(older_than_30d(clock.now()), ["exactly-30d", "just-over"]);
+    }
+
+    #[test]
+    fn test_backup_filter_combines_conditions() {
+        let now = Utc::now();
+        let backups = vec![
+            backup_entry("auto_a", 1),
+            backup_entry("Release-1", 3),
+            backup_entry("auto_b", 5),
+            backup_entry("auto_c", 10),
+            backup_entry("release-0", 40),
+        ];
+        let names = |filter: BackupFilter| {
+            let (shown, matched) = filter.apply(backups.clone());
+            let names: Vec<String> = shown.into_iter().map(|b| b.metadata.name).collect();
+            (names, matched)
+        };
+
+        assert_eq!(
+            names(BackupFilter {
+                since: Some(now - Duration::days(7)),
+                name_contains: Some("AUTO".to_string()),
+                ..Default::default()
+            }),
+            (vec!["auto_a".to_string(), "auto_b".to_string()], 2)
+        );
+        assert_eq!(
+            names(BackupFilter {
+                since: Some(now - Duration::days(30)),
+                until: Some(now - Duration::days(2)),
+                limit: Some(2),
+                ..Default::default()
+            }),
+            (vec!["Release-1".to_string(), "auto_b".to_string()], 3)
+        );
+        assert_eq!(names(BackupFilter::default()).1, 5);
+    }
+
+    #[test]
+    fn test_parse_time_bound() {
+        let now = Utc::now();
+        assert_eq!(
+            parse_time_bound("7d", now, false, Zone::Utc).unwrap(),
+            now - Duration::days(7)
+        );
+        assert_eq!(
+            parse_time_bound("2026-03-01", now, true, Zone::Utc).unwrap()
+                - parse_time_bound("2026-03-01", now, false, Zone::Utc).unwrap(),
+            Duration::days(1)
+        );
+        for bad in ["yesterday", "2026-13-01", "-7d"] {
+            assert!(parse_time_bound(bad, now, false, Zone::Utc)
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid time"));
+        }
+    }
+
+    #[test]
+    fn test_parse_backup_mode() {
+        assert_eq!("none".parse::<BackupMode>(), Ok(BackupMode::None));
+        assert_eq!("auto".parse::<BackupMode>(), Ok(BackupMode::Auto));
+        assert_eq!(
+            "named:pre-migration".parse::<BackupMode>(),
+            Ok(BackupMode::Named("pre-migration".to_string()))
+        );
+        assert!("named:".parse::<BackupMode>().is_err());
+        assert!("named: ".parse::<BackupMode>().is_err());
+        assert!("always".parse::<BackupMode>().is_err());
+    }
+
+    #[test]
+    fn test_select_backup() {
+        let backups = || {
+            vec![
+                backup_entry("release-2024-01", 3),
+                backup_entry("release-2024-02", 1),
+                backup_entry("release-2024", 2),
+                backup_entry("nightly", 0),
+            ]
+        };
+        let pick = |name: &str, latest: bool| {
+            select_backup(backups(), name, latest, Zone::Utc)
+                .unwrap()
+                .map(|b| b.metadata.name)
+        };
+
+        // Exact names win even when they also match others partially
+        assert_eq!(pick("release-2024", false).as_deref(), Some("release-2024"));
+        assert_eq!(pick("night", false).as_deref(), Some("nightly"));
+        assert_eq!(pick("missing", false), None);
+        assert_eq!(pick("2024-0", true).as_deref(), Some("release-2024-02"));
+
+        let err = select_backup(backups(), "2024-0", false, Zone::Utc)
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("'2024-0' matches 2 backups"), "{}", err);
+        let newest = err.find("release-2024-02").unwrap();
+        assert!(newest < err.find("release-2024-01").unwrap());
+        assert!(err.contains("--latest-match"));
+    }
+
+    fn command_server(args: &[&str], env: &[(&str, &str)]) -> McpServer {
+        McpServer {
+            command: Some("npx".to_string()),
+            args: Some(args.iter().map(|a| a.to_string()).collect()),
+            url: None,
+            env: Some(
+                env.iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            ),
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::new(),
+        }
+    }
+
+    fn url_server(url: &str) -> McpServer {
+        McpServer {
+            command: None,
+            args: None,
+            url: Some(url.to_string()),
+            ..command_server(&[], &[])
+        }
+    }
+
+    #[test]
+    fn test_merge_fields_on_command_servers() {
+        let current = command_server(&["new"], &[("TOKEN", "new")]);
+        let backup = McpServer {
+            timeout: Some(30),
+            ..command_server(&["old"], &[("TOKEN", "old")])
+        };
+
+        let merged = merge_fields(&current, &backup, &[RestoreField::Env]).unwrap();
+        assert_eq!(merged.env, backup.env);
+        assert_eq!(merged.args, current.args);
+
+        let merged = merge_fields(
+            &current,
+            &backup,
+            &[RestoreField::Args, RestoreField::Timeout],
+        )
+        .unwrap();
+        assert_eq!(merged.args, backup.args);
+        assert_eq!(merged.timeout, Some(30));
+        assert_eq!(merged.env, current.env);
+    }
+
+    #[test]
+    fn test_merge_fields_across_server_kinds() {
+        let remote = url_server("https://mcp.example.com");
+        let local = command_server(&["server"], &[]);
+
+        // Restoring the command onto a URL server needs the url cleared too
+        let err = merge_fields(&remote, &local, &[RestoreField::Command]).unwrap_err();
+        assert!(err.to_string().contains("Add url to --fields"));
+        let merged =
+            merge_fields(&remote, &local, &[RestoreField::Command, RestoreField::Url]).unwrap();
+        assert!(merged.url.is_none() && merged.command.is_some());
+
+        let err = merge_fields(&local, &remote, &[RestoreField::Url]).unwrap_err();
+        assert!(err.to_string().contains("Add command to --fields"));
+        let merged =
+            merge_fields(&local, &remote, &[RestoreField::Url, RestoreField::Command]).unwrap();
+        assert!(merged.is_url_server() && merged.command.is_none());
+
+        // Fields that don't affect the server type merge freely
+        let merged = merge_fields(&remote, &local, &[RestoreField::Args]).unwrap();
+        assert_eq!(merged.args, local.args);
+    }
+
+    #[test]
+    fn test_parse_restore_fields() {
+        let fields =
+            parse_restore_fields(&["env".to_string(), "ARGS".to_string(), "env".to_string()])
+                .unwrap();
+        assert_eq!(fields, [RestoreField::Env, RestoreField::Args]);
+        assert!(parse_restore_fields(&["headers".to_string()]).is_err());
+    }
+
+    fn write_backup(dir: &Path, name: &str, hours_ago: i64, servers: &[(&str, &str)]) {
+        let mut config = Config::default();
+        for (server_name, command) in servers {
+            config.mcp_servers.insert(
+                server_name.to_string(),
+                McpServer {
+                    command: Some(command.to_string()),
+                    args: Some(vec!["--port".to_string(), "5432".to_string()]),
+                    url: None,
+                    env: None,
+                    timeout: None,
+                    auto_approve: None,
+                    other: HashMap::new(),
+                },
+            );
+        }
+        let data = BackupData {
+            metadata: BackupMetadata {
+                name: name.to_string(),
+                created_at: Utc::now() - Duration::hours(hours_ago),
+                servers_count: servers.len(),
+                description: None,
+                git_branch: None,
+                git_commit: None,
+                excluded_from_share: Vec::new(),
+                target: None,
+                config_file: None,
+            },
+            config,
+        };
+        fs::write(
+            dir.join(format!("{}.json", name)),
+            serde_json::to_string(&data).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_search_backups_by_content() {
+        let dir = tempfile::TempDir::new().unwrap();
+        write_backup(dir.path(), "older", 48, &[("postgres", "pg-mcp")]);
+        write_backup(
+            dir.path(),
+            "newer",
+            1,
+            &[("db", "postgres-mcp"), ("files", "fs-mcp")],
+        );
+        write_backup(dir.path(), "unrelated", 2, &[("github", "gh-mcp")]);
+        fs::write(dir.path().join("corrupt.json"), "{ not json").unwrap();
+        fs::write(dir.path().join("notes.txt"), "postgres").unwrap();
+
+        let (hits, skipped) = search_backups_in(dir.path(), "POSTGRES").await.unwrap();
+
+        let names: Vec<_> = hits.iter().map(|h| h.backup.as_str()).collect();
+        assert_eq!(names, ["newer", "older"]);
+        assert_eq!(hits[0].servers.len(), 1);
+        assert_eq!(hits[0].servers[0].name, "db");
+        assert_eq!(hits[0].servers[0].summary, "postgres-mcp --port 5432");
+
+        assert_eq!(skipped.len(), 1);
+        assert!(skipped[0].0.ends_with("corrupt.json"));
+    }
+
+    #[tokio::test]
+    async fn test_search_backups_missing_dir() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let (hits, skipped) = search_backups_in(&dir.path().join("missing"), "x")
+            .await
+            .unwrap();
+        assert!(hits.is_empty());
+        assert!(skipped.is_empty());
+    }
 }
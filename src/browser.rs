@@ -0,0 +1,79 @@
+use anyhow::{anyhow, Result};
+use std::io::IsTerminal;
+use std::process::{Command, Stdio};
+
+/// Open `url` in the default browser; returns whether it did
+///
+/// Nothing is opened when stdout isn't a terminal or `CI` is set, since nobody
+/// would see the browser there.
+pub fn open(url: &str) -> Result<bool> {
+    crate::utils::validate_server_url(url)?;
+    if !should_open(
+        std::io::stdout().is_terminal(),
+        std::env::var("CI").ok().as_deref(),
+    ) {
+        return Ok(false);
+    }
+
+    browser_command(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to open a browser for {}: {}", url, e))?;
+    Ok(true)
+}
+
+fn should_open(interactive: bool, ci: Option<&str>) -> bool {
+    let in_ci = ci.is_some_and(|value| {
+        let value = value.trim();
+        !value.is_empty() && value != "0" && !value.eq_ignore_ascii_case("false")
+    });
+    interactive && !in_ci
+}
+
+#[cfg(target_os = "macos")]
+fn browser_command(url: &str) -> Command {
+    let mut command = Command::new("open");
+    command.arg(url);
+    command
+}
+
+#[cfg(windows)]
+fn browser_command(url: &str) -> Command {
+    let mut command = Command::new("rundll32");
+    command.args(["url.dll,FileProtocolHandler", url]);
+    command
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn browser_command(url: &str) -> Command {
+    let mut command = Command::new("xdg-open");
+    command.arg(url);
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_open_only_interactively_outside_ci() {
+        assert!(should_open(true, None));
+        assert!(should_open(true, Some("false")));
+        assert!(should_open(true, Some("0")));
+        assert!(!should_open(false, None));
+        assert!(!should_open(true, Some("true")));
+        assert!(!should_open(true, Some("1")));
+    }
+
+    #[test]
+    fn test_open_rejects_non_web_links() {
+        let err = open("file:///etc/passwd").unwrap_err();
+        assert!(
+            err.to_string().contains("Unsupported URL scheme"),
+            "{}",
+            err
+        );
+    }
+}
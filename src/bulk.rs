@@ -1,6 +1,10 @@
-use crate::config::Config;
-use crate::profiles::update_profile_server_count;
-use crate::templates::TemplateManager;
+use crate::backup;
+use crate::bulk_state::BulkState;
+use crate::config::{Config, McpServer, Target};
+use crate::github::ClientOptions;
+use crate::metadata::{ServerMetadata, ServerProvenance};
+use crate::templates::{Template, TemplateCatalog, TemplateManager, VariableType};
+use crate::transaction;
 use anyhow::{anyhow, Result};
 use clap::Subcommand;
 use colored::Colorize;
@@ -10,11 +14,111 @@ use std::fs;
 use std::path::Path;
 
 /// Batch server configuration
+/// Entries either reference a template (with vars) or define the server inline
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BatchServerConfig {
     pub name: String,
-    pub template: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub vars: HashMap<String, String>,
+    /// JSON or YAML file of variables, relative to the batch file; `vars` take precedence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vars_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<HashMap<String, String>>,
+}
+
+impl BatchServerConfig {
+    /// Build the server from an inline definition, if the entry has one
+    pub fn inline_server(&self) -> Option<McpServer> {
+        if self.command.is_none() && self.url.is_none() {
+            return None;
+        }
+
+        Some(McpServer {
+            command: self.command.clone(),
+            args: self.args.clone(),
+            url: self.url.clone(),
+            env: self.env.clone(),
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::new(),
+        })
+    }
+
+    /// Resolve an entry that doesn't reference a template
+    pub fn resolve_inline(&self) -> Result<McpServer> {
+        if self.template.is_some() {
+            return Err(anyhow!(
+                "Server '{}' references a template and cannot be resolved inline",
+                self.name
+            ));
+        }
+
+        let server = self.inline_server().ok_or_else(|| {
+            anyhow!(
+                "Server '{}' must specify either a template or an inline command/url",
+                self.name
+            )
+        })?;
+        server.validate()?;
+        Ok(server)
+    }
+
+    /// Resolve the entry into a concrete server definition, along with the
+    /// template it was rendered from if it names one
+    pub async fn resolve(
+        &self,
+        template_manager: &TemplateManager,
+    ) -> Result<(McpServer, Option<Template>)> {
+        let Some(template_name) = &self.template else {
+            return Ok((self.resolve_inline()?, None));
+        };
+
+        if self.inline_server().is_some() {
+            return Err(anyhow!(
+                "Server '{}' cannot specify both a template and an inline command/url",
+                self.name
+            ));
+        }
+
+        let template = template_manager.load_template(template_name).await?;
+        let server = template_manager.apply_template(&template, &self.json_vars())?;
+        Ok((server, Some(template)))
+    }
+
+    /// Template variables as JSON values for rendering
+    pub fn json_vars(&self) -> HashMap<String, serde_json::Value> {
+        self.vars
+            .iter()
+            .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
+            .collect()
+    }
+}
+
+/// A missing or invalid variable found during pre-flight
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableProblem {
+    pub variable: String,
+    pub var_type: VariableType,
+    pub description: String,
+    pub reason: String,
+}
+
+/// Pre-flight findings for a single batch entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreflightIssue {
+    pub server_name: String,
+    pub template: Option<String>,
+    pub problems: Vec<String>,
+    pub variables: Vec<VariableProblem>,
 }
 
 /// Batch configuration file structure
@@ -23,36 +127,268 @@ pub struct BatchConfig {
     pub servers: Vec<BatchServerConfig>,
 }
 
+/// How one entry of a bulk operation ended
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkStatus {
+    Succeeded,
+    Failed,
+    /// Never attempted, e.g. after `--fail-fast` stopped the run
+    Skipped,
+}
+
 /// Bulk operation result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BulkOperationResult {
     pub server_name: String,
     pub operation: String,
-    pub success: bool,
+    pub status: BulkStatus,
     pub message: String,
 }
 
+impl BulkOperationResult {
+    fn skipped(server_name: &str, operation: &str, message: &str) -> Self {
+        Self {
+            server_name: server_name.to_string(),
+            operation: operation.to_string(),
+            status: BulkStatus::Skipped,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.status == BulkStatus::Succeeded
+    }
+}
+
+/// What a bulk operation does when an entry fails
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum FailureMode {
+    /// Carry on with the rest and save whatever succeeded
+    #[default]
+    ContinueOnError,
+    /// Stop at the first failure, saving what succeeded only with `save_partial`
+    FailFast { save_partial: bool },
+}
+
+impl FailureMode {
+    pub fn from_flags(fail_fast: bool, save_partial: bool) -> Self {
+        if fail_fast {
+            Self::FailFast { save_partial }
+        } else {
+            Self::ContinueOnError
+        }
+    }
+
+    /// Whether a stopped run throws away the entries that succeeded
+    fn discards_partial(self) -> bool {
+        self == (Self::FailFast {
+            save_partial: false,
+        })
+    }
+}
+
+/// Why entries after a `--fail-fast` stop were left alone
+const NOT_ATTEMPTED: &str = "Not attempted; stopped at the first failure";
+
 /// Handle bulk command routing
-pub async fn handle_bulk_command(action: BulkCommands, profile: Option<String>) -> Result<()> {
+pub async fn handle_bulk_command(
+    action: BulkCommands,
+    target: &Target,
+    github: &ClientOptions,
+    profile: Option<String>,
+) -> Result<()> {
     match action {
-        BulkCommands::Add { file, dry_run } => handle_bulk_add(file, dry_run, profile).await,
+        BulkCommands::Add {
+            file,
+            dry_run,
+            skip_invalid,
+            state,
+            reset_state,
+            ignore_platform,
+            allow_unrendered,
+            fail_fast,
+            continue_on_error: _,
+            save_partial,
+        } => {
+            handle_bulk_add(
+                file,
+                dry_run,
+                skip_invalid,
+                state,
+                reset_state,
+                ignore_platform,
+                allow_unrendered,
+                FailureMode::from_flags(fail_fast, save_partial),
+                target,
+                github,
+                profile,
+            )
+            .await
+        }
         BulkCommands::Update {
             pattern,
             tag,
+            servers_file,
+            strict,
             set,
             dry_run,
-        } => handle_bulk_update(pattern, tag, set, dry_run, profile).await,
+            fail_fast,
+            continue_on_error: _,
+            save_partial,
+        } => {
+            let selection = ServerSelection {
+                pattern,
+                tag,
+                names: servers_file.as_deref().map(read_server_names).transpose()?,
+                strict,
+            };
+            let failure_mode = FailureMode::from_flags(fail_fast, save_partial);
+            handle_bulk_update(selection, set, dry_run, failure_mode, target, profile).await
+        }
         BulkCommands::Remove {
             pattern,
+            servers_file,
+            strict,
             force,
+            keep_metadata,
             dry_run,
-        } => handle_bulk_remove(pattern, force, dry_run, profile).await,
+        } => {
+            let selection = ServerSelection {
+                pattern,
+                names: servers_file.as_deref().map(read_server_names).transpose()?,
+                strict,
+                ..Default::default()
+            };
+            handle_bulk_remove(selection, force, keep_metadata, dry_run, target, profile).await
+        }
     }
 }
 
 /// Handle bulk add from file
-async fn handle_bulk_add(file_path: String, dry_run: bool, profile: Option<String>) -> Result<()> {
-    let batch_config = load_batch_config(&file_path).await?;
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_bulk_add(
+    file_path: String,
+    dry_run: bool,
+    skip_invalid: bool,
+    state_path: Option<String>,
+    reset_state: bool,
+    ignore_platform: bool,
+    allow_unrendered: bool,
+    failure_mode: FailureMode,
+    target: &Target,
+    github: &ClientOptions,
+    profile: Option<String>,
+) -> Result<()> {
+    if failure_mode.discards_partial() && state_path.is_some() {
+        return Err(anyhow!(
+            "--state saves each server as soon as it is added; pass --save-partial with --fail-fast to use them together"
+        ));
+    }
+    let mut batch_config = load_batch_config(&file_path).await?;
+
+    // Entries a previous run already saved are dropped before any template is fetched
+    let mut state = match &state_path {
+        Some(path) => {
+            let content = fs::read(&file_path)?;
+            Some(BulkState::open(Path::new(path), &content, reset_state)?)
+        }
+        None => None,
+    };
+    if let Some(state) = &state {
+        let total = batch_config.servers.len();
+        batch_config
+            .servers
+            .retain(|s| !state.is_completed(&s.name));
+        let done = total - batch_config.servers.len();
+        if done > 0 {
+            println!(
+                "{}",
+                format!(
+                    "Resuming: {} of {} server(s) already added according to the state file",
+                    done, total
+                )
+                .cyan()
+            );
+        }
+        if batch_config.servers.is_empty() {
+            println!("{}", "✓ Nothing left to add".green());
+            return Ok(());
+        }
+    }
+
+    // Load every referenced template up front so entries can be checked before any changes
+    let names: Vec<String> = batch_config
+        .servers
+        .iter()
+        .filter_map(|s| s.template.clone())
+        .collect();
+
+    // Only touch the template machinery when an entry actually needs it
+    let template_manager = if names.is_empty() {
+        None
+    } else {
+        Some(TemplateManager::new(github)?.allow_unrendered(allow_unrendered))
+    };
+    let (templates, stopped) = match &template_manager {
+        Some(manager) => manager.load_templates(&names).await,
+        None => (HashMap::new(), None),
+    };
+    if let Some(e) = stopped {
+        let mut unique = names.clone();
+        unique.sort();
+        unique.dedup();
+        println!(
+            "{}",
+            format!(
+                "Loaded {} of {} template(s) before stopping; no changes were made.",
+                templates.values().filter(|t| t.is_ok()).count(),
+                unique.len()
+            )
+            .yellow()
+        );
+        return Err(e);
+    }
+
+    let os_name = crate::utils::platform::os();
+    let platform = (!ignore_platform).then_some(os_name.as_str());
+    let mut issues = preflight_check(&batch_config, &templates, platform);
+    if !issues.is_empty() {
+        display_preflight_report(&issues);
+
+        let interactive = !dry_run && std::io::IsTerminal::is_terminal(&std::io::stdin());
+        let has_variable_gaps = issues.iter().any(|i| !i.variables.is_empty());
+        if interactive
+            && has_variable_gaps
+            && inquire::Confirm::new("Fill in the missing variables now?")
+                .with_default(true)
+                .prompt()?
+        {
+            fill_missing_variables(&mut batch_config, &issues, &templates)?;
+            issues = preflight_check(&batch_config, &templates, platform);
+            if !issues.is_empty() {
+                display_preflight_report(&issues);
+            }
+        }
+    }
+
+    let mut invalid: Vec<String> = Vec::new();
+    if !issues.is_empty() && !dry_run {
+        if !skip_invalid {
+            return Err(anyhow!(
+                "Pre-flight check failed for {} server(s); no changes were made. \
+                 Fix the batch file or pass --skip-invalid to add the valid entries only",
+                issues.len()
+            ));
+        }
+
+        invalid = issues.iter().map(|i| i.server_name.clone()).collect();
+        println!(
+            "{}",
+            format!("Skipping {} invalid server(s)", invalid.len()).yellow()
+        );
+        println!();
+    }
 
     if dry_run {
         println!("{}", "Bulk Add Preview (Dry Run)".cyan().bold());
@@ -62,15 +398,57 @@ async fn handle_bulk_add(file_path: String, dry_run: bool, profile: Option<Strin
         println!("{}", "──────────────────".cyan());
     }
 
-    let mut config = Config::load(profile.as_deref()).await.unwrap_or_default();
-    let template_manager = TemplateManager::new()?;
+    let mut config = Config::load(target).await.unwrap_or_default();
+    let original = config.clone();
+    let mut metadata = ServerMetadata::load(target).unwrap_or_default();
+    let catalog = match &template_manager {
+        Some(manager) if !templates.is_empty() => manager.load_catalog().await.ok(),
+        _ => None,
+    };
     let mut results = Vec::new();
+    let mut stopped_at = None;
 
     for server_config in &batch_config.servers {
+        if invalid.contains(&server_config.name) {
+            results.push(BulkOperationResult::skipped(
+                &server_config.name,
+                "add",
+                "Failed the pre-flight check",
+            ));
+            continue;
+        }
+        if stopped_at.is_some() {
+            results.push(BulkOperationResult::skipped(
+                &server_config.name,
+                "add",
+                NOT_ATTEMPTED,
+            ));
+            continue;
+        }
         let result = if dry_run {
-            preview_add_server(server_config, &config, &template_manager).await?
+            preview_add_server(server_config, &config, template_manager.as_ref()).await?
         } else {
-            add_server_from_config(server_config, &mut config, &template_manager).await?
+            let result = add_server_from_config(
+                server_config,
+                &mut config,
+                template_manager.as_ref(),
+                &mut metadata,
+                catalog.as_ref(),
+            )
+            .await?;
+
+            // With a state file each entry is saved as it goes, and only marked
+            // done once the save succeeds, so an interrupted run loses nothing
+            if let (Some(state), true) = (&mut state, result.succeeded()) {
+                backup::backup_before_change(target, &original).await?;
+                transaction::save_config(&config, target, profile.as_deref(), Some(&metadata))
+                    .await?;
+                state.mark_completed(&server_config.name)?;
+            }
+            if failure_mode != FailureMode::ContinueOnError && !result.succeeded() {
+                stopped_at = Some(server_config.name.clone());
+            }
+            result
         };
 
         results.push(result);
@@ -79,12 +457,10 @@ async fn handle_bulk_add(file_path: String, dry_run: bool, profile: Option<Strin
     display_bulk_results(&results, dry_run);
 
     if !dry_run {
-        let success_count = results.iter().filter(|r| r.success).count();
-        if success_count > 0 {
-            config.save(profile.as_deref()).await?;
-
-            // Update profile metadata
-            update_profile_server_count(profile.as_deref()).await?;
+        let success_count = results.iter().filter(|r| r.succeeded()).count();
+        if success_count > 0 && should_save(failure_mode, stopped_at.is_some()) {
+            backup::backup_before_change(target, &original).await?;
+            transaction::save_config(&config, target, profile.as_deref(), Some(&metadata)).await?;
 
             println!();
             println!(
@@ -94,20 +470,52 @@ async fn handle_bulk_add(file_path: String, dry_run: bool, profile: Option<Strin
                     .bold()
             );
         }
+        if let Some(name) = stopped_at {
+            return Err(fail_fast_error(&name, failure_mode, success_count, "added"));
+        }
     }
 
     Ok(())
 }
 
+/// Whether a run that may have stopped early keeps what it did
+fn should_save(failure_mode: FailureMode, stopped: bool) -> bool {
+    !stopped || !failure_mode.discards_partial()
+}
+
+/// The error a `--fail-fast` run ends with, saying what became of the servers before it
+fn fail_fast_error(
+    failed: &str,
+    failure_mode: FailureMode,
+    success_count: usize,
+    done: &str,
+) -> anyhow::Error {
+    let kept = if success_count == 0 {
+        "nothing was changed".to_string()
+    } else if should_save(failure_mode, true) {
+        format!(
+            "the {} server(s) {} before it were saved",
+            success_count, done
+        )
+    } else {
+        format!(
+            "no changes were saved; pass --save-partial to keep the {} server(s) {} before it",
+            success_count, done
+        )
+    };
+    anyhow!("Stopped at '{}' (--fail-fast); {}", failed, kept)
+}
+
 /// Handle bulk update with pattern matching
 async fn handle_bulk_update(
-    pattern: Option<String>,
-    tag: Option<String>,
+    selection: ServerSelection,
     set_vars: Vec<String>,
     dry_run: bool,
+    failure_mode: FailureMode,
+    target: &Target,
     profile: Option<String>,
 ) -> Result<()> {
-    let mut config = Config::load(profile.as_deref()).await?;
+    let mut config = Config::load(target).await?;
 
     if dry_run {
         println!("{}", "Bulk Update Preview (Dry Run)".cyan().bold());
@@ -121,21 +529,43 @@ async fn handle_bulk_update(
     let env_updates = parse_env_vars(&set_vars)?;
 
     // Find matching servers
-    let matching_servers = find_matching_servers(&config, pattern.as_deref(), tag.as_deref())?;
+    let matching_servers = select_servers(&config, &selection)?;
 
     if matching_servers.is_empty() {
-        println!("{}", "No servers match the specified criteria.".yellow());
+        println!(
+            "{}",
+            format!("No servers match {}.", selection.describe()).yellow()
+        );
         return Ok(());
     }
 
     let mut results = Vec::new();
 
+    let replacements = if dry_run {
+        Vec::new()
+    } else {
+        confirm_case_variant_replacements(&config, &matching_servers, &env_updates)?
+    };
+    let original = config.clone();
+    let mut stopped_at = None;
+
     for server_name in &matching_servers {
+        if stopped_at.is_some() {
+            results.push(BulkOperationResult::skipped(
+                server_name,
+                "update",
+                NOT_ATTEMPTED,
+            ));
+            continue;
+        }
         let result = if dry_run {
             preview_update_server(server_name, &env_updates, &config)
         } else {
-            update_server_env(server_name, &env_updates, &mut config)
+            update_server_env(server_name, &env_updates, &replacements, &mut config)
         };
+        if !dry_run && failure_mode != FailureMode::ContinueOnError && !result.succeeded() {
+            stopped_at = Some(server_name.clone());
+        }
 
         results.push(result);
     }
@@ -143,12 +573,10 @@ async fn handle_bulk_update(
     display_bulk_results(&results, dry_run);
 
     if !dry_run {
-        let success_count = results.iter().filter(|r| r.success).count();
-        if success_count > 0 {
-            config.save(profile.as_deref()).await?;
-
-            // Update profile metadata
-            update_profile_server_count(profile.as_deref()).await?;
+        let success_count = results.iter().filter(|r| r.succeeded()).count();
+        if success_count > 0 && should_save(failure_mode, stopped_at.is_some()) {
+            backup::backup_before_change(target, &original).await?;
+            transaction::save_config(&config, target, profile.as_deref(), None).await?;
 
             println!();
             println!(
@@ -158,6 +586,14 @@ async fn handle_bulk_update(
                     .bold()
             );
         }
+        if let Some(name) = stopped_at {
+            return Err(fail_fast_error(
+                &name,
+                failure_mode,
+                success_count,
+                "updated",
+            ));
+        }
     }
 
     Ok(())
@@ -165,20 +601,22 @@ async fn handle_bulk_update(
 
 /// Handle bulk remove with pattern matching
 async fn handle_bulk_remove(
-    pattern: String,
+    selection: ServerSelection,
     force: bool,
+    keep_metadata: bool,
     dry_run: bool,
+    target: &Target,
     profile: Option<String>,
 ) -> Result<()> {
-    let mut config = Config::load(profile.as_deref()).await?;
+    let mut config = Config::load(target).await?;
 
     // Find matching servers
-    let matching_servers = find_matching_servers(&config, Some(&pattern), None)?;
+    let matching_servers = select_servers(&config, &selection)?;
 
     if matching_servers.is_empty() {
         println!(
             "{}",
-            format!("No servers match pattern '{}'", pattern).yellow()
+            format!("No servers match {}.", selection.describe()).yellow()
         );
         return Ok(());
     }
@@ -191,18 +629,34 @@ async fn handle_bulk_remove(
         println!("{}", "────────────────────".cyan());
     }
 
-    println!("Servers matching pattern '{}':", pattern.bold());
+    println!("Servers matching {}:", selection.describe().bold());
     for server_name in &matching_servers {
         if let Some(server) = config.mcp_servers.get(server_name) {
             let server_desc = if server.is_url_server() {
-                server.url.as_ref().map(|u| crate::utils::mask_sensitive_url(u)).unwrap_or_else(|| "URL".to_string())
+                server
+                    .url
+                    .as_ref()
+                    .map(|u| crate::utils::mask_sensitive_url(u))
+                    .unwrap_or_else(|| "URL".to_string())
             } else {
-                server.command.as_ref().unwrap_or(&"Command".to_string()).clone()
+                server
+                    .command
+                    .as_ref()
+                    .unwrap_or(&"Command".to_string())
+                    .clone()
             };
             println!("  • {} - {}", server_name.bold(), server_desc);
         }
     }
 
+    let mut metadata = ServerMetadata::load(target).unwrap_or_default();
+    if !keep_metadata {
+        crate::cli::print_metadata_cleanup(
+            &metadata.describe_records(&matching_servers),
+            &crate::output::Output::default(),
+        );
+    }
+
     if !dry_run && !force {
         println!();
         print!("Remove these {} server(s)? [y/N]: ", matching_servers.len());
@@ -215,6 +669,7 @@ async fn handle_bulk_remove(
     }
 
     if !dry_run {
+        let original = config.clone();
         let mut removed_count = 0;
         for server_name in &matching_servers {
             if config.mcp_servers.remove(server_name).is_some() {
@@ -226,10 +681,15 @@ async fn handle_bulk_remove(
         }
 
         if removed_count > 0 {
-            config.save(profile.as_deref()).await?;
-
-            // Update profile metadata
-            update_profile_server_count(profile.as_deref()).await?;
+            backup::backup_before_change(target, &original).await?;
+            let forgotten = !keep_metadata && metadata.forget(&matching_servers);
+            transaction::save_config(
+                &config,
+                target,
+                profile.as_deref(),
+                forgotten.then_some(&metadata),
+            )
+            .await?;
 
             println!();
             println!(
@@ -248,7 +708,7 @@ async fn handle_bulk_remove(
 }
 
 /// Load batch configuration from file
-async fn load_batch_config(file_path: &str) -> Result<BatchConfig> {
+pub async fn load_batch_config(file_path: &str) -> Result<BatchConfig> {
     let content = fs::read_to_string(file_path)
         .map_err(|e| anyhow!("Failed to read batch config file '{}': {}", file_path, e))?;
 
@@ -256,56 +716,282 @@ async fn load_batch_config(file_path: &str) -> Result<BatchConfig> {
     let path = Path::new(file_path);
     let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("");
 
-    match extension.to_lowercase().as_str() {
-        "json" => serde_json::from_str(&content).map_err(|e| anyhow!("Invalid JSON format: {}", e)),
+    let mut batch_config: BatchConfig = match extension.to_lowercase().as_str() {
+        "json" => {
+            serde_json::from_str(&content).map_err(|e| anyhow!("Invalid JSON format: {}", e))?
+        }
         "yaml" | "yml" => {
-            serde_yaml::from_str(&content).map_err(|e| anyhow!("Invalid YAML format: {}", e))
+            serde_yaml::from_str(&content).map_err(|e| anyhow!("Invalid YAML format: {}", e))?
         }
         _ => {
             // Try JSON first, then YAML
             serde_json::from_str(&content)
                 .or_else(|_| serde_yaml::from_str(&content))
-                .map_err(|e| anyhow!("Unable to parse file as JSON or YAML: {}", e))
+                .map_err(|e| anyhow!("Unable to parse file as JSON or YAML: {}", e))?
+        }
+    };
+
+    resolve_vars_files(&mut batch_config, path)?;
+    Ok(batch_config)
+}
+
+/// Batch vars are strings, with lists comma-joined; templates coerce them back
+fn batch_var_value(value: serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s,
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|v| {
+                v.as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| v.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+        other => other.to_string(),
+    }
+}
+
+/// Merge each entry's `vars_file` into its vars, under any vars set inline
+fn resolve_vars_files(batch_config: &mut BatchConfig, batch_path: &Path) -> Result<()> {
+    let base_dir = batch_path.parent().unwrap_or(Path::new(""));
+    for server_config in &mut batch_config.servers {
+        let Some(vars_file) = &server_config.vars_file else {
+            continue;
+        };
+        let path = base_dir.join(vars_file);
+        let values = crate::templates::read_variables_file(&path.to_string_lossy())
+            .map_err(|e| anyhow!("Server '{}': {:#}", server_config.name, e))?;
+        for (key, value) in values {
+            server_config
+                .vars
+                .entry(key)
+                .or_insert_with(|| batch_var_value(value));
+        }
+    }
+    Ok(())
+}
+
+/// Check every batch entry against its template before anything is changed
+///
+/// Templates are also checked against `platform`, unless it is `None` because
+/// `--ignore-platform` was given.
+pub fn preflight_check(
+    batch_config: &BatchConfig,
+    templates: &HashMap<String, Result<Template>>,
+    platform: Option<&str>,
+) -> Vec<PreflightIssue> {
+    let mut issues = Vec::new();
+
+    for server_config in &batch_config.servers {
+        let mut issue = PreflightIssue {
+            server_name: server_config.name.clone(),
+            template: server_config.template.clone(),
+            problems: Vec::new(),
+            variables: Vec::new(),
+        };
+
+        match &server_config.template {
+            None => {
+                if let Err(e) = server_config.resolve_inline() {
+                    issue.problems.push(e.to_string());
+                }
+            }
+            Some(template_name) => match templates.get(template_name) {
+                None => issue
+                    .problems
+                    .push(format!("Template '{}' not found", template_name)),
+                Some(Err(e)) => issue.problems.push(format!(
+                    "Template '{}' could not be loaded: {:#}",
+                    template_name, e
+                )),
+                Some(Ok(template)) => {
+                    if server_config.inline_server().is_some() {
+                        issue.problems.push(
+                            "Entry cannot specify both a template and an inline command/url"
+                                .to_string(),
+                        );
+                    }
+                    if let Some(Err(e)) = platform.map(|p| template.check_platform(p)) {
+                        issue.problems.push(e.to_string());
+                    }
+                    issue.variables = check_variables(template, &server_config.vars);
+                }
+            },
+        }
+
+        if !issue.problems.is_empty() || !issue.variables.is_empty() {
+            issues.push(issue);
+        }
+    }
+
+    issues
+}
+
+/// Check batch variable values against a template's variable definitions
+fn check_variables(template: &Template, vars: &HashMap<String, String>) -> Vec<VariableProblem> {
+    let mut names: Vec<&String> = template.variables.keys().collect();
+    names.sort();
+
+    let mut problems = Vec::new();
+    for name in names {
+        let definition = &template.variables[name];
+        let reason = match vars.get(name) {
+            None if definition.required && definition.default.is_none() => {
+                Some("missing".to_string())
+            }
+            None => None,
+            Some(value) if definition.required && value.trim().is_empty() => {
+                Some("empty".to_string())
+            }
+            Some(value) => match definition.var_type {
+                VariableType::Boolean if value.parse::<bool>().is_err() => {
+                    Some(format!("'{}' is not true/false", value))
+                }
+                VariableType::Number if value.trim().parse::<f64>().is_err() => {
+                    Some(format!("'{}' is not a number", value))
+                }
+                VariableType::Select => match &definition.options {
+                    Some(options) if !options.contains(value) => {
+                        Some(format!("'{}' is not one of: {}", value, options.join(", ")))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            },
+        };
+
+        if let Some(reason) = reason {
+            problems.push(VariableProblem {
+                variable: name.clone(),
+                var_type: definition.var_type.clone(),
+                description: definition.description.clone(),
+                reason,
+            });
+        }
+    }
+
+    problems
+}
+
+/// Display the consolidated pre-flight report
+fn display_preflight_report(issues: &[PreflightIssue]) {
+    println!("{}", "Pre-flight Check Failed".yellow().bold());
+    println!("{}", "───────────────────────".yellow());
+
+    for issue in issues {
+        println!();
+        match &issue.template {
+            Some(template) => println!("• {} (template: {})", issue.server_name.bold(), template),
+            None => println!("• {} (inline)", issue.server_name.bold()),
+        }
+        for problem in &issue.problems {
+            println!("  {} {}", "✗".red(), problem);
+        }
+        for variable in &issue.variables {
+            println!(
+                "  {} {} ({:?}): {}",
+                "✗".red(),
+                variable.variable.bold(),
+                variable.var_type,
+                variable.reason
+            );
+            if !variable.description.is_empty() {
+                println!("    {}", variable.description.dimmed());
+            }
         }
     }
+    println!();
+}
+
+/// Prompt for the variables flagged by the pre-flight check
+fn fill_missing_variables(
+    batch_config: &mut BatchConfig,
+    issues: &[PreflightIssue],
+    templates: &HashMap<String, Result<Template>>,
+) -> Result<()> {
+    for issue in issues.iter().filter(|i| !i.variables.is_empty()) {
+        let Some(Ok(template)) = issue.template.as_ref().and_then(|t| templates.get(t)) else {
+            continue;
+        };
+        let Some(server_config) = batch_config
+            .servers
+            .iter_mut()
+            .find(|s| s.name == issue.server_name)
+        else {
+            continue;
+        };
+
+        println!("Server {}:", server_config.name.bold());
+        for problem in &issue.variables {
+            let definition = &template.variables[&problem.variable];
+            let value = crate::cli::prompt_for_variable(&problem.variable, definition)?;
+            server_config
+                .vars
+                .insert(problem.variable.clone(), batch_var_value(value));
+        }
+    }
+
+    Ok(())
+}
+
+/// The template manager, which is only created when a batch references templates
+fn require_template_manager(manager: Option<&TemplateManager>) -> Result<&TemplateManager> {
+    manager.ok_or_else(|| anyhow!("Template manager was not initialized for this batch"))
 }
 
 /// Preview adding a server from batch config
 async fn preview_add_server(
     server_config: &BatchServerConfig,
     config: &Config,
-    template_manager: &TemplateManager,
+    template_manager: Option<&TemplateManager>,
 ) -> Result<BulkOperationResult> {
     // Check if server already exists
     if config.mcp_servers.contains_key(&server_config.name) {
         return Ok(BulkOperationResult {
             server_name: server_config.name.clone(),
             operation: "add".to_string(),
-            success: false,
+            status: BulkStatus::Failed,
             message: "Server already exists (would overwrite)".to_string(),
         });
     }
 
+    // Inline definitions don't need a template lookup
+    let Some(template_name) = &server_config.template else {
+        let (status, message) = match server_config.resolve_inline() {
+            Ok(_) => (
+                BulkStatus::Succeeded,
+                "Would add inline definition".to_string(),
+            ),
+            Err(e) => (BulkStatus::Failed, e.to_string()),
+        };
+        return Ok(BulkOperationResult {
+            server_name: server_config.name.clone(),
+            operation: "add".to_string(),
+            status,
+            message,
+        });
+    };
+
     // Check if template exists
+    let template_manager = require_template_manager(template_manager)?;
     let template_list = template_manager.list_templates().await?;
-    let template_exists = template_list
-        .iter()
-        .any(|t| t.name == server_config.template);
+    let template_exists = template_list.iter().any(|t| &t.name == template_name);
 
     if !template_exists {
         return Ok(BulkOperationResult {
             server_name: server_config.name.clone(),
             operation: "add".to_string(),
-            success: false,
-            message: format!("Template '{}' not found", server_config.template),
+            status: BulkStatus::Failed,
+            message: format!("Template '{}' not found", template_name),
         });
     }
 
     Ok(BulkOperationResult {
         server_name: server_config.name.clone(),
         operation: "add".to_string(),
-        success: true,
-        message: format!("Would add with template '{}'", server_config.template),
+        status: BulkStatus::Succeeded,
+        message: format!("Would add with template '{}'", template_name),
     })
 }
 
@@ -313,32 +999,49 @@ async fn preview_add_server(
 async fn add_server_from_config(
     server_config: &BatchServerConfig,
     config: &mut Config,
-    template_manager: &TemplateManager,
+    template_manager: Option<&TemplateManager>,
+    metadata: &mut ServerMetadata,
+    catalog: Option<&TemplateCatalog>,
 ) -> Result<BulkOperationResult> {
+    // Inline definitions are inserted as-is
+    let Some(template_name) = &server_config.template else {
+        return match server_config.resolve_inline() {
+            Ok(server) => {
+                config
+                    .mcp_servers
+                    .insert(server_config.name.clone(), server);
+                metadata.servers.remove(&server_config.name);
+                Ok(BulkOperationResult {
+                    server_name: server_config.name.clone(),
+                    operation: "add".to_string(),
+                    status: BulkStatus::Succeeded,
+                    message: "Added successfully".to_string(),
+                })
+            }
+            Err(e) => Ok(BulkOperationResult {
+                server_name: server_config.name.clone(),
+                operation: "add".to_string(),
+                status: BulkStatus::Failed,
+                message: e.to_string(),
+            }),
+        };
+    };
+
     // Get template
-    let template = match template_manager
-        .load_template(&server_config.template)
-        .await
-    {
+    let template_manager = require_template_manager(template_manager)?;
+    let template = match template_manager.load_template(template_name).await {
         Ok(template) => template,
         Err(e) => {
             return Ok(BulkOperationResult {
                 server_name: server_config.name.clone(),
                 operation: "add".to_string(),
-                success: false,
-                message: format!(
-                    "Failed to load template '{}': {}",
-                    server_config.template, e
-                ),
+                status: BulkStatus::Failed,
+                message: format!("Failed to load template '{}': {}", template_name, e),
             })
         }
     };
 
-    let variables: HashMap<String, serde_json::Value> = server_config
-        .vars
-        .iter()
-        .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
-        .collect();
+    let variables = server_config.json_vars();
 
     let server = match template_manager.apply_template(&template, &variables) {
         Ok(server) => server,
@@ -346,7 +1049,7 @@ async fn add_server_from_config(
             return Ok(BulkOperationResult {
                 server_name: server_config.name.clone(),
                 operation: "add".to_string(),
-                success: false,
+                status: BulkStatus::Failed,
                 message: format!("Template application failed: {}", e),
             });
         }
@@ -355,10 +1058,30 @@ async fn add_server_from_config(
     config
         .mcp_servers
         .insert(server_config.name.clone(), server);
+
+    let category = if template_manager
+        .local_template_path(template_name)
+        .is_some()
+    {
+        Some("local".to_string())
+    } else {
+        catalog
+            .and_then(|c| c.templates.get(template_name))
+            .map(|m| m.category.clone())
+    };
+    metadata.record(
+        &server_config.name,
+        ServerProvenance::from_template(
+            &template_manager.render_notes(&template, &variables),
+            category,
+            &variables,
+        ),
+    );
+
     Ok(BulkOperationResult {
         server_name: server_config.name.clone(),
         operation: "add".to_string(),
-        success: true,
+        status: BulkStatus::Succeeded,
         message: "Added successfully".to_string(),
     })
 }
@@ -383,16 +1106,167 @@ pub fn find_matching_servers(
         }
     }
 
-    if matching.is_empty() && pattern.is_some() {
-        return Err(anyhow!(
-            "No servers found matching pattern: {}",
-            pattern.unwrap()
-        ));
+    if let (true, Some(pattern_str)) = (matching.is_empty(), pattern) {
+        return Err(NoMatchingServers {
+            pattern: pattern_str.to_string(),
+            existing: describe_existing_servers(config),
+        }
+        .into());
     }
 
     Ok(matching)
 }
 
+/// Error returned by `find_matching_servers` when a pattern matches nothing
+#[derive(Debug)]
+pub struct NoMatchingServers {
+    pattern: String,
+    /// `describe_existing_servers` at the time of the search
+    existing: String,
+}
+
+impl std::fmt::Display for NoMatchingServers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "No servers found matching pattern: {}. {}",
+            self.pattern, self.existing
+        )
+    }
+}
+
+impl std::error::Error for NoMatchingServers {}
+
+/// Which servers a bulk update or remove targets
+#[derive(Debug, Default)]
+pub struct ServerSelection {
+    pub pattern: Option<String>,
+    pub tag: Option<String>,
+    /// Names read from `--servers-file`; other targeting narrows this list
+    pub names: Option<Vec<String>>,
+    /// Fail on listed names that aren't configured, instead of warning
+    pub strict: bool,
+}
+
+impl ServerSelection {
+    /// Describe the selection for "nothing matched" messages
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(pattern) = &self.pattern {
+            parts.push(format!("pattern '{}'", pattern));
+        }
+        if self.names.is_some() {
+            parts.push("the servers file".to_string());
+        }
+        if parts.is_empty() {
+            "the specified criteria".to_string()
+        } else {
+            parts.join(" and ")
+        }
+    }
+}
+
+/// Resolve a selection to server names
+///
+/// Listed names keep their file order and are intersected with the pattern.
+/// Listed names that aren't configured are warned about one by one, or fail
+/// the selection when it is strict.
+pub fn select_servers(config: &Config, selection: &ServerSelection) -> Result<Vec<String>> {
+    let matching = find_matching_servers(
+        config,
+        selection.pattern.as_deref(),
+        selection.tag.as_deref(),
+    )?;
+    let Some(names) = &selection.names else {
+        return Ok(matching);
+    };
+
+    let unknown: Vec<&String> = names
+        .iter()
+        .filter(|name| !config.mcp_servers.contains_key(*name))
+        .collect();
+    if !unknown.is_empty() {
+        if selection.strict {
+            let unknown: Vec<&str> = unknown.iter().map(|name| name.as_str()).collect();
+            return Err(anyhow!(
+                "Servers file lists {} unknown server(s): {}. {}",
+                unknown.len(),
+                unknown.join(", "),
+                describe_existing_servers(config)
+            ));
+        }
+        for name in unknown {
+            eprintln!(
+                "{}",
+                format!("⚠ '{}' is not configured; skipping it", name).yellow()
+            );
+        }
+    }
+
+    Ok(names
+        .iter()
+        .filter(|name| matching.contains(name))
+        .cloned()
+        .collect())
+}
+
+/// Read server names from a newline-separated list or a JSON array
+///
+/// Blank lines and lines starting with `#` are ignored, as are repeated names.
+pub fn read_server_names(path: &str) -> Result<Vec<String>> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read servers file '{}': {}", path, e))?;
+    parse_server_names(&content).map_err(|e| anyhow!("Servers file '{}': {}", path, e))
+}
+
+fn parse_server_names(content: &str) -> Result<Vec<String>> {
+    let listed: Vec<String> = if content.trim_start().starts_with('[') {
+        serde_json::from_str(content)
+            .map_err(|e| anyhow!("expected a JSON array of server names: {}", e))?
+    } else {
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect()
+    };
+
+    let mut names: Vec<String> = Vec::new();
+    for name in listed {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    if names.is_empty() {
+        return Err(anyhow!("no server names listed"));
+    }
+    Ok(names)
+}
+
+/// Summarize configured server names to help spot typos in error messages
+pub fn describe_existing_servers(config: &Config) -> String {
+    const SHOWN: usize = 5;
+
+    if config.mcp_servers.is_empty() {
+        return "No servers are configured.".to_string();
+    }
+
+    let mut names: Vec<&String> = config.mcp_servers.keys().collect();
+    names.sort();
+
+    let shown: Vec<&str> = names.iter().take(SHOWN).map(|s| s.as_str()).collect();
+    if names.len() > SHOWN {
+        format!(
+            "Existing servers: {} (and {} more)",
+            shown.join(", "),
+            names.len() - SHOWN
+        )
+    } else {
+        format!("Existing servers: {}", shown.join(", "))
+    }
+}
+
 /// Parse environment variable assignments
 pub fn parse_env_vars(set_vars: &[String]) -> Result<HashMap<String, String>> {
     let mut env_updates = HashMap::new();
@@ -411,6 +1285,65 @@ pub fn parse_env_vars(set_vars: &[String]) -> Result<HashMap<String, String>> {
     Ok(env_updates)
 }
 
+/// Ask whether existing keys that differ only by case should be replaced
+///
+/// Returns the `(server, existing key)` pairs the user chose to replace.
+/// Without a terminal nothing is replaced and both keys are kept.
+pub fn confirm_case_variant_replacements(
+    config: &Config,
+    server_names: &[String],
+    env_updates: &HashMap<String, String>,
+) -> Result<Vec<(String, String)>> {
+    let interactive = std::io::IsTerminal::is_terminal(&std::io::stdin());
+    let mut keys: Vec<&String> = env_updates.keys().collect();
+    keys.sort();
+
+    let mut replacements = Vec::new();
+    for server_name in server_names {
+        let Some(env) = config
+            .mcp_servers
+            .get(server_name)
+            .and_then(|s| s.env.as_ref())
+        else {
+            continue;
+        };
+
+        for key in &keys {
+            let existing = crate::utils::find_case_variant_keys(env, key);
+            if existing.is_empty() {
+                continue;
+            }
+            let listed = existing
+                .iter()
+                .map(|k| format!("'{}'", k))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let replace = interactive
+                && inquire::Confirm::new(&format!(
+                    "Server '{}' already has {}. Replace with '{}'?",
+                    server_name, listed, key
+                ))
+                .with_default(true)
+                .prompt()?;
+
+            if replace {
+                replacements.extend(existing.into_iter().map(|k| (server_name.clone(), k)));
+            } else {
+                println!(
+                    "{} {}: keeping {} and '{}'",
+                    "⚠".yellow(),
+                    server_name,
+                    listed,
+                    key
+                );
+            }
+        }
+    }
+
+    Ok(replacements)
+}
+
 /// Preview updating a server's environment
 fn preview_update_server(
     server_name: &str,
@@ -421,23 +1354,36 @@ fn preview_update_server(
         return BulkOperationResult {
             server_name: server_name.to_string(),
             operation: "update".to_string(),
-            success: false,
+            status: BulkStatus::Failed,
             message: "Server not found".to_string(),
         };
     }
 
+    let existing_env = config.mcp_servers[server_name].env.as_ref();
     let changes: Vec<String> = env_updates
         .iter()
         .map(|(key, value)| {
             let masked_value = crate::utils::mask_sensitive_env_value(key, value);
-            format!("{}={}", key, masked_value)
+            let existing = existing_env
+                .map(|env| crate::utils::find_case_variant_keys(env, key))
+                .unwrap_or_default();
+            if existing.is_empty() {
+                format!("{}={}", key, masked_value)
+            } else {
+                format!(
+                    "{}={} (replaces {})",
+                    key,
+                    masked_value,
+                    existing.join(", ")
+                )
+            }
         })
         .collect();
 
     BulkOperationResult {
         server_name: server_name.to_string(),
         operation: "update".to_string(),
-        success: true,
+        status: BulkStatus::Succeeded,
         message: format!("Would set: {}", changes.join(", ")),
     }
 }
@@ -446,6 +1392,7 @@ fn preview_update_server(
 fn update_server_env(
     server_name: &str,
     env_updates: &HashMap<String, String>,
+    replacements: &[(String, String)],
     config: &mut Config,
 ) -> BulkOperationResult {
     if let Some(server) = config.mcp_servers.get_mut(server_name) {
@@ -456,6 +1403,9 @@ fn update_server_env(
 
         // Apply updates
         if let Some(env) = &mut server.env {
+            for (_, existing) in replacements.iter().filter(|(s, _)| s == server_name) {
+                env.remove(existing);
+            }
             for (key, value) in env_updates {
                 env.insert(key.clone(), value.clone());
             }
@@ -464,14 +1414,14 @@ fn update_server_env(
         BulkOperationResult {
             server_name: server_name.to_string(),
             operation: "update".to_string(),
-            success: true,
+            status: BulkStatus::Succeeded,
             message: "Environment updated".to_string(),
         }
     } else {
         BulkOperationResult {
             server_name: server_name.to_string(),
             operation: "update".to_string(),
-            success: false,
+            status: BulkStatus::Failed,
             message: "Server not found".to_string(),
         }
     }
@@ -481,14 +1431,22 @@ fn update_server_env(
 fn display_bulk_results(results: &[BulkOperationResult], dry_run: bool) {
     let mut success_count = 0;
     let mut error_count = 0;
+    let mut skipped_count = 0;
 
     for result in results {
-        let status_symbol = if result.success {
-            success_count += 1;
-            "✓".green()
-        } else {
-            error_count += 1;
-            "✗".red()
+        let status_symbol = match result.status {
+            BulkStatus::Succeeded => {
+                success_count += 1;
+                "✓".green()
+            }
+            BulkStatus::Failed => {
+                error_count += 1;
+                "✗".red()
+            }
+            BulkStatus::Skipped => {
+                skipped_count += 1;
+                "-".yellow()
+            }
         };
 
         let operation_text = if dry_run {
@@ -524,10 +1482,11 @@ fn display_bulk_results(results: &[BulkOperationResult], dry_run: bool) {
         }
     } else {
         println!("Operation Summary:");
-        println!("  {} successful", success_count.to_string().green());
-        if error_count > 0 {
-            println!("  {} failed", error_count.to_string().red());
-        }
+        println!("  Attempted: {}", success_count + error_count);
+        println!("  Succeeded: {}", success_count.to_string().green());
+        println!("  Failed:    {}", error_count.to_string().red());
+        println!("  Skipped:   {}", skipped_count.to_string().yellow());
+        crate::notify::report(success_count, error_count);
     }
 }
 
@@ -541,6 +1500,30 @@ pub enum BulkCommands {
         /// Preview changes without applying
         #[arg(long)]
         dry_run: bool,
+        /// Add the valid entries and skip those that fail the pre-flight check
+        #[arg(long)]
+        skip_invalid: bool,
+        /// Record progress in this file and skip entries it lists as done
+        #[arg(long)]
+        state: Option<String>,
+        /// Ignore progress recorded in the state file and start over
+        #[arg(long, requires = "state")]
+        reset_state: bool,
+        /// Add servers even if their template doesn't support this platform
+        #[arg(long)]
+        ignore_platform: bool,
+        /// Warn instead of failing when a rendered server still holds `{{...}}`
+        #[arg(long)]
+        allow_unrendered: bool,
+        /// Stop at the first server that fails; nothing is saved unless --save-partial is given
+        #[arg(long, conflicts_with = "continue_on_error")]
+        fail_fast: bool,
+        /// Carry on past failures and save the servers that succeeded (the default)
+        #[arg(long)]
+        continue_on_error: bool,
+        /// With --fail-fast, save the servers added before the failure
+        #[arg(long, requires = "fail_fast")]
+        save_partial: bool,
     },
     /// Update multiple servers
     Update {
@@ -550,21 +1533,45 @@ pub enum BulkCommands {
         /// Filter by tag
         #[arg(long)]
         tag: Option<String>,
+        /// File listing the servers to update, one per line or as a JSON array
+        #[arg(long)]
+        servers_file: Option<String>,
+        /// Fail if the servers file lists servers that aren't configured
+        #[arg(long, requires = "servers_file")]
+        strict: bool,
         /// Set environment variables
         #[arg(long)]
         set: Vec<String>,
         /// Preview changes without applying
         #[arg(long)]
         dry_run: bool,
+        /// Stop at the first server that fails; nothing is saved unless --save-partial is given
+        #[arg(long, conflicts_with = "continue_on_error")]
+        fail_fast: bool,
+        /// Carry on past failures and save the servers that succeeded (the default)
+        #[arg(long)]
+        continue_on_error: bool,
+        /// With --fail-fast, save the servers updated before the failure
+        #[arg(long, requires = "fail_fast")]
+        save_partial: bool,
     },
     /// Remove multiple servers
     Remove {
         /// Pattern to match server names
+        #[arg(long, required_unless_present = "servers_file")]
+        pattern: Option<String>,
+        /// File listing the servers to remove, one per line or as a JSON array
         #[arg(long)]
-        pattern: String,
+        servers_file: Option<String>,
+        /// Fail if the servers file lists servers that aren't configured
+        #[arg(long, requires = "servers_file")]
+        strict: bool,
         /// Force removal without confirmation
         #[arg(long)]
         force: bool,
+        /// Keep the removed servers' provenance, notes, links and flags
+        #[arg(long)]
+        keep_metadata: bool,
         /// Preview changes without applying
         #[arg(long)]
         dry_run: bool,
@@ -596,6 +1603,47 @@ mod tests {
         assert!(parse_env_vars(&vars).is_err());
     }
 
+    #[test]
+    fn test_fail_fast_saves_only_with_save_partial() {
+        let fail_fast = FailureMode::from_flags(true, false);
+        assert!(should_save(fail_fast, false));
+        assert!(!should_save(fail_fast, true));
+        assert!(should_save(FailureMode::from_flags(true, true), true));
+        assert!(should_save(FailureMode::from_flags(false, true), false));
+
+        let message = fail_fast_error("db", fail_fast, 0, "added").to_string();
+        assert!(message.contains("nothing was changed"), "{}", message);
+    }
+
+    #[test]
+    fn test_update_server_env_replaces_case_variant() {
+        let mut config = Config::default();
+        config.mcp_servers.insert(
+            "api".to_string(),
+            McpServer {
+                command: Some("node".to_string()),
+                args: None,
+                url: None,
+                env: Some(HashMap::from([("Api_Key".to_string(), "old".to_string())])),
+                timeout: None,
+                auto_approve: None,
+                other: HashMap::new(),
+            },
+        );
+        let updates = HashMap::from([("API_KEY".to_string(), "new".to_string())]);
+
+        let preview = preview_update_server("api", &updates, &config);
+        assert!(preview.message.contains("replaces Api_Key"));
+
+        let replacements = vec![("api".to_string(), "Api_Key".to_string())];
+        let result = update_server_env("api", &updates, &replacements, &mut config);
+        assert!(result.succeeded());
+
+        let env = config.mcp_servers["api"].env.as_ref().unwrap();
+        assert_eq!(env.len(), 1);
+        assert_eq!(env.get("API_KEY"), Some(&"new".to_string()));
+    }
+
     #[test]
     fn test_find_matching_servers() {
         let mut config = Config::default();
@@ -606,6 +1654,8 @@ mod tests {
                 args: Some(vec![]),
                 url: None,
                 env: None,
+                timeout: None,
+                auto_approve: None,
                 other: HashMap::new(),
             },
         );
@@ -616,6 +1666,8 @@ mod tests {
                 args: Some(vec![]),
                 url: None,
                 env: None,
+                timeout: None,
+                auto_approve: None,
                 other: HashMap::new(),
             },
         );
@@ -626,6 +1678,8 @@ mod tests {
                 args: Some(vec![]),
                 url: None,
                 env: None,
+                timeout: None,
+                auto_approve: None,
                 other: HashMap::new(),
             },
         );
@@ -640,6 +1694,129 @@ mod tests {
         let matches = find_matching_servers(&config, Some("prod-server"), None).unwrap();
         assert_eq!(matches.len(), 1);
         assert!(matches.contains(&"prod-server".to_string()));
+
+        // No match lists existing names to help spot typos
+        let err = find_matching_servers(&config, Some("staging"), None).unwrap_err();
+        assert!(err.is::<NoMatchingServers>());
+        assert!(err
+            .to_string()
+            .contains("Existing servers: prod-server, test-server-1, test-server-2"));
+    }
+
+    fn config_with(names: &[&str]) -> Config {
+        let mut config = Config::default();
+        for name in names {
+            config.mcp_servers.insert(
+                name.to_string(),
+                McpServer {
+                    command: Some("cmd".to_string()),
+                    args: Some(vec![]),
+                    url: None,
+                    env: None,
+                    timeout: None,
+                    auto_approve: None,
+                    other: HashMap::new(),
+                },
+            );
+        }
+        config
+    }
+
+    #[test]
+    fn test_select_servers_intersects_names_with_pattern() {
+        let config = config_with(&["db-prod", "db-test", "api-prod", "api-test"]);
+        let names = |list: &[&str]| Some(list.iter().map(|n| n.to_string()).collect());
+
+        let selection = ServerSelection {
+            names: names(&["api-test", "db-prod"]),
+            ..Default::default()
+        };
+        assert_eq!(
+            select_servers(&config, &selection).unwrap(),
+            ["api-test", "db-prod"]
+        );
+
+        let selection = ServerSelection {
+            pattern: Some("prod".to_string()),
+            names: names(&["api-test", "db-prod", "api-prod"]),
+            ..Default::default()
+        };
+        assert_eq!(
+            select_servers(&config, &selection).unwrap(),
+            ["db-prod", "api-prod"]
+        );
+
+        let selection = ServerSelection {
+            pattern: Some("prod".to_string()),
+            names: names(&["api-test"]),
+            ..Default::default()
+        };
+        assert!(select_servers(&config, &selection).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_select_servers_unknown_names() {
+        let config = config_with(&["db", "api"]);
+        let mut selection = ServerSelection {
+            names: Some(vec!["db".to_string(), "dbb".to_string(), "web".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(select_servers(&config, &selection).unwrap(), ["db"]);
+
+        selection.strict = true;
+        let err = select_servers(&config, &selection).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Servers file lists 2 unknown server(s): dbb, web. Existing servers: api, db"
+        );
+    }
+
+    #[test]
+    fn test_parse_server_names() {
+        assert_eq!(
+            parse_server_names("# from the sheet\ndb\n\n  api  \ndb\n").unwrap(),
+            ["db", "api"]
+        );
+        assert_eq!(
+            parse_server_names(r#"["db", "api", "db"]"#).unwrap(),
+            ["db", "api"]
+        );
+        assert!(parse_server_names("[1, 2]")
+            .unwrap_err()
+            .to_string()
+            .starts_with("expected a JSON array of server names"));
+        assert_eq!(
+            parse_server_names("\n# nothing\n").unwrap_err().to_string(),
+            "no server names listed"
+        );
+    }
+
+    #[test]
+    fn test_describe_existing_servers() {
+        let mut config = Config::default();
+        assert_eq!(
+            describe_existing_servers(&config),
+            "No servers are configured."
+        );
+
+        for i in 0..7 {
+            config.mcp_servers.insert(
+                format!("server-{}", i),
+                McpServer {
+                    command: Some("node".to_string()),
+                    args: None,
+                    url: None,
+                    env: None,
+                    timeout: None,
+                    auto_approve: None,
+                    other: HashMap::new(),
+                },
+            );
+        }
+        assert_eq!(
+            describe_existing_servers(&config),
+            "Existing servers: server-0, server-1, server-2, server-3, server-4 (and 2 more)"
+        );
     }
 
     #[test]
@@ -647,12 +1824,17 @@ mod tests {
         let batch_config = BatchConfig {
             servers: vec![BatchServerConfig {
                 name: "test1".to_string(),
-                template: "filesystem".to_string(),
+                template: Some("filesystem".to_string()),
                 vars: {
                     let mut vars = HashMap::new();
                     vars.insert("path".to_string(), "/tmp".to_string());
                     vars
                 },
+                vars_file: None,
+                command: None,
+                args: None,
+                url: None,
+                env: None,
             }],
         };
 
@@ -661,4 +1843,180 @@ mod tests {
         assert_eq!(parsed.servers.len(), 1);
         assert_eq!(parsed.servers[0].name, "test1");
     }
+
+    fn preflight_template() -> Template {
+        let variable = |var_type: VariableType, required: bool, options: Option<Vec<&str>>| {
+            crate::templates::TemplateVariable {
+                var_type,
+                description: "A variable".to_string(),
+                default: None,
+                required,
+                validation: None,
+                options: options.map(|o| o.iter().map(|s| s.to_string()).collect()),
+                secret: false,
+            }
+        };
+
+        Template {
+            name: "demo".to_string(),
+            version: "1.0.0".to_string(),
+            description: "Demo".to_string(),
+            author: "Test".to_string(),
+            extends: None,
+            tags: vec![],
+            platforms: vec![],
+            variables: HashMap::from([
+                (
+                    "path".to_string(),
+                    variable(VariableType::String, true, None),
+                ),
+                (
+                    "port".to_string(),
+                    variable(VariableType::Number, false, None),
+                ),
+                (
+                    "mode".to_string(),
+                    variable(VariableType::Select, false, Some(vec!["ro", "rw"])),
+                ),
+            ]),
+            config: crate::templates::TemplateConfig {
+                command: Some("demo".to_string()),
+                args: Some(vec!["{{path}}".to_string()]),
+                url: None,
+                env: None,
+            },
+            requirements: None,
+            setup_instructions: None,
+            docs_url: None,
+            inherits: vec![],
+        }
+    }
+
+    fn batch_entry(name: &str, template: Option<&str>, vars: &[(&str, &str)]) -> BatchServerConfig {
+        BatchServerConfig {
+            name: name.to_string(),
+            template: template.map(|t| t.to_string()),
+            vars: vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            vars_file: None,
+            command: None,
+            args: None,
+            url: None,
+            env: None,
+        }
+    }
+
+    #[test]
+    fn test_preflight_check_reports_variable_problems() {
+        let templates = HashMap::from([("demo".to_string(), Ok(preflight_template()))]);
+        let batch = BatchConfig {
+            servers: vec![
+                batch_entry("ok", Some("demo"), &[("path", "/tmp")]),
+                batch_entry("missing", Some("demo"), &[]),
+                batch_entry(
+                    "bad",
+                    Some("demo"),
+                    &[("path", "/tmp"), ("port", "abc"), ("mode", "x")],
+                ),
+            ],
+        };
+
+        let issues = preflight_check(&batch, &templates, Some("linux"));
+        assert_eq!(issues.len(), 2);
+
+        assert_eq!(issues[0].server_name, "missing");
+        assert_eq!(issues[0].variables.len(), 1);
+        assert_eq!(issues[0].variables[0].variable, "path");
+        assert_eq!(issues[0].variables[0].reason, "missing");
+        assert_eq!(issues[0].variables[0].var_type, VariableType::String);
+
+        assert_eq!(issues[1].server_name, "bad");
+        let bad_vars: Vec<&str> = issues[1]
+            .variables
+            .iter()
+            .map(|v| v.variable.as_str())
+            .collect();
+        assert_eq!(bad_vars, vec!["mode", "port"]);
+    }
+
+    #[test]
+    fn test_preflight_check_reports_unknown_templates_and_bad_inline() {
+        let templates = HashMap::from([("demo".to_string(), Ok(preflight_template()))]);
+        let batch = BatchConfig {
+            servers: vec![
+                batch_entry("ghost", Some("nope"), &[]),
+                batch_entry("empty", None, &[]),
+            ],
+        };
+
+        let issues = preflight_check(&batch, &templates, Some("linux"));
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].problems[0].contains("Template 'nope' not found"));
+        assert!(issues[1].problems[0].contains("must specify either a template"));
+    }
+
+    #[test]
+    fn test_preflight_check_reports_why_a_template_failed_to_load() {
+        let templates = HashMap::from([(
+            "demo".to_string(),
+            Err(anyhow::anyhow!("GitHub API rate limit exceeded")),
+        )]);
+        let batch = BatchConfig {
+            servers: vec![batch_entry("files", Some("demo"), &[("path", "/tmp")])],
+        };
+
+        let issues = preflight_check(&batch, &templates, Some("linux"));
+        assert_eq!(
+            issues[0].problems,
+            ["Template 'demo' could not be loaded: GitHub API rate limit exceeded"]
+        );
+    }
+
+    #[test]
+    fn test_preflight_check_gates_platforms() {
+        let mut template = preflight_template();
+        template.platforms = vec!["windows".to_string()];
+        let templates = HashMap::from([("demo".to_string(), Ok(template))]);
+        let batch = BatchConfig {
+            servers: vec![batch_entry("win", Some("demo"), &[("path", "C:\\data")])],
+        };
+
+        let issues = preflight_check(&batch, &templates, Some("linux"));
+        assert_eq!(
+            issues[0].problems,
+            ["Template 'demo' supports windows only, not linux. Pass --ignore-platform to add it anyway"]
+        );
+        assert!(preflight_check(&batch, &templates, Some("windows")).is_empty());
+        // --ignore-platform
+        assert!(preflight_check(&batch, &templates, None).is_empty());
+    }
+
+    #[test]
+    fn test_batch_config_inline_entries() {
+        let yaml = r#"
+servers:
+  - name: files
+    template: filesystem
+    vars:
+      path: /tmp
+  - name: local
+    command: node
+    args: ["server.js"]
+  - name: remote
+    url: https://example.com/mcp
+"#;
+
+        let parsed: BatchConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(parsed.servers.len(), 3);
+        assert!(parsed.servers[0].inline_server().is_none());
+
+        let local = parsed.servers[1].inline_server().unwrap();
+        assert_eq!(local.command.as_deref(), Some("node"));
+        assert_eq!(local.args, Some(vec!["server.js".to_string()]));
+
+        let remote = parsed.servers[2].inline_server().unwrap();
+        assert!(remote.is_url_server());
+    }
 }
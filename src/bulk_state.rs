@@ -0,0 +1,95 @@
+use crate::utils;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Progress of a `bulk add --state` run, so an interrupted batch can be resumed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BulkState {
+    /// Hash of the batch file the progress belongs to
+    pub batch_hash: String,
+    /// Entries already added and saved, in the order they were applied
+    pub completed: Vec<String>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl BulkState {
+    /// Open the state file for a batch, starting fresh if it doesn't exist or `reset` is set
+    ///
+    /// State recorded for a different version of the batch file is refused, as the
+    /// completed entry names may no longer mean the same servers.
+    pub fn open(path: &Path, batch_content: &[u8], reset: bool) -> Result<Self> {
+        let batch_hash = utils::content_hash(batch_content);
+        let fresh = Self {
+            batch_hash: batch_hash.clone(),
+            completed: Vec::new(),
+            path: path.to_path_buf(),
+        };
+        if reset || !path.exists() {
+            return Ok(fresh);
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read state file {}", path.display()))?;
+        let state: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse state file {}", path.display()))?;
+        if state.batch_hash != batch_hash {
+            return Err(anyhow!(
+                "State file {} was recorded for a different version of the batch file. \
+                 Pass --reset-state to start over",
+                path.display()
+            ));
+        }
+        Ok(Self {
+            path: fresh.path,
+            ..state
+        })
+    }
+
+    pub fn is_completed(&self, name: &str) -> bool {
+        self.completed.iter().any(|c| c == name)
+    }
+
+    /// Record an entry as done; call only once the config holding it has been saved
+    pub fn mark_completed(&mut self, name: &str) -> Result<()> {
+        if !self.is_completed(name) {
+            self.completed.push(name.to_string());
+        }
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write state file {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_resumes_and_rejects_changed_batch() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("state.json");
+
+        let mut state = BulkState::open(&path, b"v1", false).unwrap();
+        state.mark_completed("github").unwrap();
+        state.mark_completed("github").unwrap();
+
+        let resumed = BulkState::open(&path, b"v1", false).unwrap();
+        assert_eq!(resumed.completed, ["github"]);
+        assert!(resumed.is_completed("github"));
+
+        assert!(BulkState::open(&path, b"v2", false).is_err());
+        assert!(BulkState::open(&path, b"v2", true)
+            .unwrap()
+            .completed
+            .is_empty());
+    }
+}
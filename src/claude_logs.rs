@@ -0,0 +1,331 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Log lines kept with each finding
+const EXCERPT_LINES: usize = 3;
+
+/// Characters kept of each log line
+const EXCERPT_WIDTH: usize = 240;
+
+/// Why Claude Desktop gave up on a server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// The command couldn't be started (spawn ENOENT)
+    NotFound { command: String },
+    /// The server wrote something other than JSON-RPC to stdout
+    InvalidOutput,
+    /// The server exited soon after starting, with its exit code if logged
+    Exited { code: Option<i32> },
+}
+
+impl Failure {
+    /// Whether both are the same kind of failure, whatever their details
+    fn same_kind(&self, other: &Failure) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+/// A failure Claude Desktop logged for a server, in its latest start
+#[derive(Debug, Clone)]
+pub struct LogFinding {
+    pub server: String,
+    pub failure: Failure,
+    /// Lines leading up to the failure, oldest first
+    pub excerpt: Vec<String>,
+    pub file: PathBuf,
+}
+
+/// Where Claude Desktop writes `mcp.log` and the `mcp-server-<name>.log` files
+pub fn log_dir() -> Result<PathBuf> {
+    #[cfg(target_os = "macos")]
+    let dir = dirs::home_dir()
+        .context("Could not find home directory")?
+        .join("Library/Logs/Claude");
+
+    #[cfg(not(target_os = "macos"))]
+    let dir = crate::utils::get_config_dir()?.join("logs");
+
+    Ok(dir)
+}
+
+/// Failures in the Claude Desktop logs, per-server logs first
+///
+/// Only each server's latest start counts, so failures fixed since are not
+/// reported again. Lines that can't be parsed are skipped.
+pub fn recent_failures(dir: &Path) -> Result<Vec<LogFinding>> {
+    let mut files: Vec<_> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read Claude logs: {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "log"))
+        .collect();
+    files.sort();
+    // mcp.log repeats what the per-server logs say, with less detail
+    files.sort_by_key(|path| path.file_name().is_some_and(|name| name == "mcp.log"));
+
+    let mut findings: Vec<LogFinding> = Vec::new();
+    for file in files {
+        // Logs are rotated while Claude runs; a file gone missing is skipped
+        let Ok(bytes) = fs::read(&file) else {
+            continue;
+        };
+        let content = String::from_utf8_lossy(&bytes);
+        for mut finding in scan(&content, server_of_file(&file)) {
+            let known = findings
+                .iter()
+                .any(|f| f.server == finding.server && f.failure.same_kind(&finding.failure));
+            if !known {
+                finding.file = file.clone();
+                findings.push(finding);
+            }
+        }
+    }
+    Ok(findings)
+}
+
+/// The server a `mcp-server-<name>.log` file is about
+fn server_of_file(path: &Path) -> Option<&str> {
+    path.file_stem()?.to_str()?.strip_prefix("mcp-server-")
+}
+
+/// Levels Claude writes in brackets next to the server name
+const LEVELS: &[&str] = &["info", "warn", "warning", "error", "debug", "trace", "log"];
+
+/// Split a line into the server it names, if any, and the message
+///
+/// Lines look like `<time> [server] [level] message` in per-server logs and
+/// `<time> [level] [server] message` in `mcp.log`. Lines without brackets,
+/// such as a server's own stderr, name no server.
+fn parse_line(line: &str) -> (Option<&str>, &str) {
+    let mut rest = line.trim_start();
+    // Skip the timestamp
+    if let Some((first, after)) = rest.split_once(' ') {
+        if first.starts_with(|c: char| c.is_ascii_digit()) {
+            rest = after.trim_start();
+        }
+    }
+
+    let mut server = None;
+    for _ in 0..2 {
+        let Some(inner) = rest.strip_prefix('[') else {
+            break;
+        };
+        let Some((token, after)) = inner.split_once(']') else {
+            break;
+        };
+        if !LEVELS.contains(&token.to_ascii_lowercase().as_str()) {
+            server = Some(token);
+        }
+        rest = after.trim_start();
+    }
+    (server, rest.trim_end())
+}
+
+/// A log pattern and the failure a match of it means, if any
+type Pattern = (Regex, fn(&regex::Captures) -> Option<Failure>);
+
+fn patterns() -> &'static [Pattern] {
+    static PATTERNS: OnceLock<Vec<Pattern>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        let pattern = |source: &str| Regex::new(source).expect("log pattern is valid");
+        vec![
+            (pattern(r"spawn (\S+) ENOENT"), |captures| {
+                Some(Failure::NotFound {
+                    command: captures[1].to_string(),
+                })
+            }),
+            (
+                pattern(
+                    r"(?i)is not valid JSON|in JSON at position|Unexpected end of JSON input|JSON-RPC parse error|Failed to parse (?:JSON|message)",
+                ),
+                |_| Some(Failure::InvalidOutput),
+            ),
+            (pattern(r"(?i)exited with code (-?\d+)"), |captures| {
+                let code = captures[1].parse().ok()?;
+                (code != 0).then_some(Failure::Exited { code: Some(code) })
+            }),
+            (pattern(r"(?i)transport closed unexpectedly"), |_| {
+                Some(Failure::Exited { code: None })
+            }),
+        ]
+    })
+}
+
+/// What went wrong according to one log message, if anything
+fn classify(message: &str) -> Option<Failure> {
+    patterns().iter().find_map(|(pattern, failure)| {
+        pattern
+            .captures(message)
+            .and_then(|captures| failure(&captures))
+    })
+}
+
+/// Findings in one log file, the last failure of each kind per server
+///
+/// `file_server` is the server a per-server log is about, which its
+/// unbracketed lines are attributed to.
+fn scan(content: &str, file_server: Option<&str>) -> Vec<LogFinding> {
+    let mut runs: HashMap<String, Vec<String>> = HashMap::new();
+    let mut findings: HashMap<String, Vec<(Failure, Vec<String>)>> = HashMap::new();
+    let mut order = Vec::new();
+
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        let (server, message) = parse_line(line);
+        let Some(server) = server.or(file_server) else {
+            continue;
+        };
+        // Each start begins a new run; what happened before it is history
+        if message.starts_with("Initializing server") {
+            runs.remove(server);
+            findings.remove(server);
+        }
+
+        let run = runs.entry(server.to_string()).or_default();
+        run.push(crate::utils::display::truncate_value(
+            line.trim(),
+            EXCERPT_WIDTH,
+        ));
+        let Some(failure) = classify(message) else {
+            continue;
+        };
+        let excerpt = run[run.len().saturating_sub(EXCERPT_LINES)..].to_vec();
+        let server_findings = findings.entry(server.to_string()).or_default();
+        if !order.iter().any(|name| name == server) {
+            order.push(server.to_string());
+        }
+        match server_findings
+            .iter_mut()
+            .find(|(known, _)| known.same_kind(&failure))
+        {
+            // An exit code is worth more than the bare notice that follows it
+            Some((Failure::Exited { code: Some(_) }, _))
+                if failure == Failure::Exited { code: None } => {}
+            Some(known) => *known = (failure, excerpt),
+            None => server_findings.push((failure, excerpt)),
+        }
+    }
+
+    order
+        .into_iter()
+        .flat_map(|server| {
+            let server_findings = findings.remove(&server).unwrap_or_default();
+            // A command that never started can't have exited early
+            let not_found = server_findings
+                .iter()
+                .any(|(failure, _)| matches!(failure, Failure::NotFound { .. }));
+            server_findings
+                .into_iter()
+                .filter(move |(failure, _)| {
+                    !(not_found && matches!(failure, Failure::Exited { .. }))
+                })
+                .map(move |(failure, excerpt)| LogFinding {
+                    server: server.clone(),
+                    failure,
+                    excerpt,
+                    file: PathBuf::new(),
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/claude_logs")
+            .join(name);
+        fs::read_to_string(path).unwrap()
+    }
+
+    fn failures(findings: &[LogFinding]) -> Vec<(&str, &Failure)> {
+        findings
+            .iter()
+            .map(|finding| (finding.server.as_str(), &finding.failure))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            parse_line("2025-03-10T14:02:11.548Z [fs] [error] spawn npx ENOENT"),
+            (Some("fs"), "spawn npx ENOENT")
+        );
+        assert_eq!(
+            parse_line("2025-03-10T14:02:11.530Z [info] [my server] Initializing server..."),
+            (Some("my server"), "Initializing server...")
+        );
+        assert_eq!(
+            parse_line("2025-03-10T14:06:00.000Z [info] Launching MCP Server: search"),
+            (None, "Launching MCP Server: search")
+        );
+        assert_eq!(
+            parse_line("psycopg.OperationalError: connection failed"),
+            (None, "psycopg.OperationalError: connection failed")
+        );
+    }
+
+    #[test]
+    fn test_scan_per_server_logs() {
+        let findings = scan(&fixture("mcp-server-filesystem.log"), Some("filesystem"));
+        assert_eq!(
+            failures(&findings),
+            [(
+                "filesystem",
+                &Failure::NotFound {
+                    command: "npx".to_string()
+                }
+            )]
+        );
+        assert!(findings[0].excerpt[0].contains("Initializing server"));
+        assert!(findings[0].excerpt[1].contains("spawn npx ENOENT"));
+        assert!(findings[0].excerpt[1].ends_with("more chars)"));
+
+        let findings = scan(&fixture("mcp-server-github.log"), Some("github"));
+        assert_eq!(failures(&findings), [("github", &Failure::InvalidOutput)]);
+
+        // The ENOENT from the start before is no longer reported, and the
+        // server's stderr is kept with the exit code
+        let findings = scan(&fixture("mcp-server-postgres.log"), Some("postgres"));
+        assert_eq!(
+            failures(&findings),
+            [("postgres", &Failure::Exited { code: Some(1) })]
+        );
+        assert!(findings[0].excerpt[1].starts_with("psycopg.OperationalError"));
+    }
+
+    #[test]
+    fn test_scan_main_log() {
+        let findings = scan(&fixture("mcp.log"), None);
+        assert_eq!(
+            failures(&findings),
+            [
+                (
+                    "filesystem",
+                    &Failure::NotFound {
+                        command: "npx".to_string()
+                    }
+                ),
+                ("memory", &Failure::Exited { code: None }),
+                ("search", &Failure::InvalidOutput),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recent_failures_prefers_per_server_logs() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/claude_logs");
+        let findings = recent_failures(&dir).unwrap();
+        let filesystem: Vec<_> = findings
+            .iter()
+            .filter(|finding| finding.server == "filesystem")
+            .collect();
+        assert_eq!(filesystem.len(), 1);
+        assert!(filesystem[0].file.ends_with("mcp-server-filesystem.log"));
+        assert_eq!(findings.len(), 5);
+    }
+}
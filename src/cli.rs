@@ -1,76 +1,375 @@
-use crate::config::{Config, McpServer};
-use crate::github::GitHubClient;
-use crate::profiles::update_profile_server_count;
-use crate::search::{filter_servers, format_servers, rank_templates, ListOptions, SearchCriteria};
-use crate::templates::{TemplateManager, VariableType};
+use crate::config::{Config, McpServer, Target, UnknownField, TYPED_SERVER_FIELDS};
+use crate::github::{ClientOptions, GitHubClient};
+use crate::metadata::{ServerMetadata, ServerProvenance};
+use crate::output::Output;
+use crate::recall::TemplateAnswers;
+use crate::remote::{self, SshTarget};
+use crate::say;
+use crate::search::{
+    author_matches, filter_servers, format_server_details, format_servers, rank_templates,
+    ListOptions, SearchCriteria, SearchRanking, ServerInfo,
+};
+use crate::selection::{self, Selection};
+use crate::templates::{self, TemplateManager, VariableType};
+use crate::timefmt::{self, Zone};
+use crate::transaction;
 use crate::utils;
-use crate::{ConfigCommands, TemplateCommands};
+use crate::{
+    ConfigCommands, OverlayCommands, TemplateCommands, TemplateRepoCommands, TemplateSourceCommands,
+};
 use anyhow::{anyhow, Result};
 use colored::Colorize;
-use inquire::{Confirm, Select, Text};
+use inquire::{Confirm, Password, PasswordDisplayMode, Select, Text};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::path::{Path, PathBuf};
 
 /// Handle template commands
-pub async fn handle_template_command(action: TemplateCommands) -> Result<()> {
+pub async fn handle_template_command(
+    action: TemplateCommands,
+    target: &Target,
+    github: &ClientOptions,
+    zone: Zone,
+) -> Result<()> {
     match action {
-        TemplateCommands::List { cached, offline } => handle_template_list(cached, offline).await,
-        TemplateCommands::Show { name } => handle_template_show(name).await,
+        TemplateCommands::List {
+            cached,
+            offline,
+            author,
+            compatible,
+        } => handle_template_list(cached, offline, author, compatible, github, zone).await,
+        TemplateCommands::Show {
+            name,
+            cached,
+            raw,
+            full,
+            open,
+            source,
+        } => {
+            if raw {
+                handle_template_raw(name, source, cached, None, github).await
+            } else {
+                handle_template_show(name, source, cached, full, open, github, zone).await
+            }
+        }
+        TemplateCommands::Cat {
+            name,
+            output,
+            cached,
+        } => handle_template_raw(name, None, cached, output, github).await,
         TemplateCommands::Search {
             term,
             rank_by,
             tag,
             platform,
-        } => handle_template_search(term, rank_by, tag, platform).await,
-        TemplateCommands::Refresh { force, clear } => handle_template_refresh(force, clear).await,
-        TemplateCommands::Create { name: _ } => {
-            println!("Template creation not yet implemented");
-            Ok(())
+            author,
+            compatible,
+            explain,
+            min_score,
+        } => {
+            let filters = TemplateFilters {
+                tag,
+                platform,
+                author,
+                compatible,
+            };
+            handle_template_search(term, rank_by, filters, explain, min_score, github, zone).await
+        }
+        TemplateCommands::Refresh {
+            force,
+            clear,
+            dry_run,
+            show_changes,
+        } => handle_template_refresh(force, clear, dry_run, show_changes, github).await,
+        TemplateCommands::Changes => handle_template_changes(github),
+        TemplateCommands::Create {
+            name,
+            from_server,
+            output,
+            dir,
+            force,
+        } => {
+            crate::template_create::handle_template_create(
+                name,
+                from_server,
+                output,
+                dir,
+                force,
+                target,
+            )
+            .await
         }
         TemplateCommands::Validate { file: _ } => {
             println!("Template validation not yet implemented");
             Ok(())
         }
+        TemplateCommands::Edit {
+            file,
+            set_var,
+            remove_var,
+            set,
+            unset,
+        } => {
+            let edits = crate::template_edit::Edits {
+                set_var,
+                remove_var,
+                set,
+                unset,
+            };
+            crate::template_edit::handle_template_edit(file, edits)
+        }
+        TemplateCommands::Pin {
+            server,
+            version,
+            unpin: _,
+        } => crate::template_versions::handle_template_pin(&server, version, target),
+        TemplateCommands::Outdated { cached, json } => {
+            crate::template_versions::handle_template_outdated(cached, json, target, github).await
+        }
+        TemplateCommands::Repo { action } => match action {
+            TemplateRepoCommands::Check { jobs, json } => {
+                crate::repo_check::handle_repo_check(jobs, json, github).await
+            }
+            TemplateRepoCommands::Set { spec } => crate::repo_check::handle_repo_set(&spec),
+            TemplateRepoCommands::Get => crate::repo_check::handle_repo_get(github),
+        },
+        TemplateCommands::Source { action } => match action {
+            TemplateSourceCommands::Add { name, spec } => {
+                crate::repo_check::handle_source_add(&name, &spec)
+            }
+            TemplateSourceCommands::Remove { name } => {
+                crate::repo_check::handle_source_remove(&name, github)
+            }
+            TemplateSourceCommands::List => crate::repo_check::handle_source_list(github),
+        },
     }
 }
 
 /// Handle config commands
-pub async fn handle_config_command(action: ConfigCommands, profile: Option<String>) -> Result<()> {
+pub async fn handle_config_command(
+    action: ConfigCommands,
+    target: &Target,
+    profile: Option<String>,
+    zone: Zone,
+) -> Result<()> {
     match action {
         ConfigCommands::Show => {
-            let config = Config::load(profile.as_deref()).await?;
+            let config = Config::load(target).await?;
             let masked_config = mask_config_credentials(&config);
             println!("{}", serde_json::to_string_pretty(&masked_config)?);
         }
-        ConfigCommands::Validate { deep, requirements } => {
-            crate::validation::validate_config(deep, requirements, None, profile).await?
+        ConfigCommands::Validate { json } => {
+            crate::config_validation::handle_config_validate(target, json).await?
         }
         ConfigCommands::Backup { name, auto_name } => {
-            crate::backup::create_backup_with_options(name, auto_name, profile).await?
+            crate::backup::create_backup_with_options(name, auto_name, target).await?
         }
         ConfigCommands::Restore {
             backup,
             preview,
             server,
-        } => crate::backup::restore_backup(backup, preview, server, profile).await?,
-        ConfigCommands::Init => {
-            let config = Config::default();
-            config.save(profile.as_deref()).await?;
-            println!("✅ Initialized empty configuration");
+            fields,
+            latest_match,
+        } => {
+            let servers = Selection::new(server);
+            crate::backup::restore_backup(
+                backup,
+                preview,
+                servers,
+                fields,
+                latest_match,
+                target,
+                profile,
+                zone,
+            )
+            .await?
+        }
+        ConfigCommands::Normalize { dry_run, force } => {
+            handle_config_normalize(dry_run, force, target, profile).await?
+        }
+        ConfigCommands::Repair { dry_run } => handle_config_repair(dry_run, target)?,
+        ConfigCommands::Init { force, if_missing } => {
+            handle_config_init(force, if_missing, target, profile).await?
         }
         ConfigCommands::Path => {
-            // Use the same profile auto-detection logic as Config::load
-            // Always show the main Claude Desktop config path since that's what we manage
-            let path = utils::get_claude_config_path()?;
+            // The same file Config::load reads: the main config of the --target client
+            let path = target.config_path()?;
             println!("{}", path.display());
         }
+        ConfigCommands::Overlay { action } => match action {
+            OverlayCommands::Add { file, name, base } => {
+                crate::layers::handle_overlay_add(target, &file, name, base)?
+            }
+            OverlayCommands::Remove { name } => {
+                crate::layers::handle_overlay_remove(target, &name)?
+            }
+            OverlayCommands::List => crate::layers::handle_overlay_list(target)?,
+        },
+        ConfigCommands::Materialize { watch } => {
+            crate::layers::handle_materialize(target, watch).await?
+        }
+    }
+    Ok(())
+}
+
+/// Initialize an empty configuration without clobbering an existing one
+async fn handle_config_init(
+    force: bool,
+    if_missing: bool,
+    target: &Target,
+    profile: Option<String>,
+) -> Result<()> {
+    let path = target.config_path()?;
+
+    if path.exists() {
+        if if_missing {
+            println!("Configuration already exists at {}", path.display());
+            return Ok(());
+        }
+
+        let existing = Config::load(target).await;
+        let populated = match &existing {
+            Ok(config) => !config.mcp_servers.is_empty() || !config.other.is_empty(),
+            Err(_) => true,
+        };
+
+        if populated {
+            if !force {
+                let contents = match &existing {
+                    Ok(config) => format!("{} server(s)", config.mcp_servers.len()),
+                    Err(_) => "unreadable data".to_string(),
+                };
+                return Err(anyhow!(
+                    "Configuration at {} already contains {}. Use --force to overwrite it (a backup is created first) or --if-missing to leave it alone",
+                    path.display(),
+                    contents
+                ));
+            }
+
+            // Back up whatever is there, even if it no longer parses
+            let backup_path = match &existing {
+                Ok(config) => crate::backup::backup_before_change(target, config).await?,
+                Err(_) => crate::backup::copy_before_change(target, &path)?,
+            };
+            if let Some(backup_path) = backup_path {
+                println!(
+                    "📦 Backed up existing configuration to {}",
+                    backup_path.display()
+                );
+            }
+        }
+    }
+
+    transaction::save_config(&Config::default(), target, profile.as_deref(), None).await?;
+
+    println!("✅ Initialized empty configuration at {}", path.display());
+    Ok(())
+}
+
+/// Fix the servers key of a config `Config::load` refuses, working on the raw file
+fn handle_config_repair(dry_run: bool, target: &Target) -> Result<()> {
+    let path = target.config_path()?;
+    let content = fs::read_to_string(&path)
+        .map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))?;
+    let mut value: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))?;
+
+    let fixes = crate::config::repair_servers(&mut value, &target.host)?;
+    if fixes.is_empty() {
+        println!("{}", "✓ Nothing to repair".green());
+        return Ok(());
+    }
+    for fix in &fixes {
+        println!("  {} {}", "FIX".yellow(), fix);
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    if let Some(backup_path) = crate::backup::copy_before_change(target, &path)? {
+        println!(
+            "📦 Backed up the configuration to {}",
+            backup_path.display()
+        );
+    }
+    let mut transaction = transaction::Transaction::new(target)?;
+    let content = serde_json::to_string_pretty(&value)
+        .map_err(|e| anyhow!("Failed to serialize configuration: {}", e))?;
+    transaction.write(path, content);
+    transaction.commit()?;
+
+    println!("{}", "✅ Configuration repaired".green().bold());
+    Ok(())
+}
+
+/// Merge case-colliding environment keys across the configuration
+async fn handle_config_normalize(
+    dry_run: bool,
+    force: bool,
+    target: &Target,
+    profile: Option<String>,
+) -> Result<()> {
+    let mut config = Config::load(target).await?;
+
+    if dry_run {
+        println!("{}", "Normalize Preview (Dry Run)".cyan().bold());
+        println!("{}", "──────────────────────────".cyan());
+
+        let mut names: Vec<&String> = config.mcp_servers.keys().collect();
+        names.sort();
+
+        let mut found = 0;
+        for name in names {
+            if let Some(env) = &config.mcp_servers[name].env {
+                for group in utils::find_case_colliding_keys(env) {
+                    println!(
+                        "  {} {}: {}",
+                        "MERGE".yellow(),
+                        name.bold(),
+                        group.join(", ")
+                    );
+                    found += 1;
+                }
+            }
+        }
+
+        if found == 0 {
+            println!("{}", "No case-colliding environment keys found.".green());
+        }
+        return Ok(());
+    }
+
+    let original = config.clone();
+    if crate::validation::normalize_env_case(&mut config, force)? == 0 {
+        return Ok(());
     }
+
+    // Create backup before modification
+    crate::backup::backup_before_change(target, &original).await?;
+
+    transaction::save_config(&config, target, profile.as_deref(), None).await?;
+
+    println!("{}", "✅ Configuration normalized".green().bold());
     Ok(())
 }
 
 /// Prompt for template variables interactively
 async fn prompt_for_template_variables(
     template: &crate::templates::Template,
+) -> Result<HashMap<String, serde_json::Value>> {
+    // Only add, remove and update print a JSON document
+    prompt_with_recalled_values(template, &HashMap::new(), &Output::default()).await
+}
+
+/// Interactively prompt for template variables, offering recalled values as defaults
+///
+/// A recalled value takes the place of the template's own default and is
+/// labeled as such above the prompt.
+async fn prompt_with_recalled_values(
+    template: &crate::templates::Template,
+    recalled: &HashMap<String, serde_json::Value>,
+    out: &Output,
 ) -> Result<HashMap<String, serde_json::Value>> {
     let mut values = HashMap::new();
 
@@ -78,247 +377,885 @@ async fn prompt_for_template_variables(
         return Ok(values);
     }
 
-    println!("Please provide values for template variables:");
+    say!(out, "Please provide values for template variables:");
+
+    let prefilled = template.with_previous_values(recalled);
+    for (name, variable) in &prefilled.variables {
+        if let Some(previous) = recalled.get(name).filter(|_| !variable.secret) {
+            let shown = previous
+                .as_str()
+                .map_or_else(|| previous.to_string(), str::to_string);
+            say!(
+                out,
+                "  {}",
+                format!(
+                    "last used: {}",
+                    crate::utils::display::display_env_value(name, &shown, false)
+                )
+                .dimmed()
+            );
+        }
+        let value = prompt_for_variable(name, variable)?;
+        values.insert(name.clone(), value);
+    }
+
+    Ok(values)
+}
 
-    for (name, variable) in &template.variables {
-        let value = match &variable.var_type {
-            VariableType::String => {
-                let mut prompt = Text::new(name);
-                if !variable.description.is_empty() {
-                    prompt = prompt.with_help_message(&variable.description);
+/// Prompt for a single template variable
+pub fn prompt_for_variable(
+    name: &str,
+    variable: &crate::templates::TemplateVariable,
+) -> Result<serde_json::Value> {
+    utils::ensure_interactive(&format!(
+        "Variable '{}' needs a value; pass it with --var {}=<value>",
+        name, name
+    ))?;
+
+    let value = match &variable.var_type {
+        VariableType::String if variable.secret => {
+            let mut prompt = Password::new(name)
+                .with_display_mode(PasswordDisplayMode::Masked)
+                .with_display_toggle_enabled()
+                .without_confirmation();
+            if !variable.description.is_empty() {
+                prompt = prompt.with_help_message(&variable.description);
+            }
+            let input = prompt.prompt()?;
+            // Password prompts can't show a default; an empty answer takes it
+            match (&variable.default, input.is_empty()) {
+                (Some(default), true) => default.clone(),
+                _ => serde_json::Value::String(input),
+            }
+        }
+        VariableType::String => {
+            let mut prompt = Text::new(name);
+            if !variable.description.is_empty() {
+                prompt = prompt.with_help_message(&variable.description);
+            }
+            if let Some(default) = &variable.default {
+                if let Some(default_str) = default.as_str() {
+                    prompt = prompt.with_default(default_str);
                 }
-                if let Some(default) = &variable.default {
-                    if let Some(default_str) = default.as_str() {
-                        prompt = prompt.with_default(default_str);
-                    }
+            }
+            serde_json::Value::String(prompt.prompt()?)
+        }
+        VariableType::Boolean => {
+            let default = variable
+                .default
+                .as_ref()
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let confirm = Confirm::new(name).with_default(default);
+            serde_json::Value::Bool(confirm.prompt()?)
+        }
+        VariableType::Number => {
+            let mut prompt = Text::new(name);
+            if let Some(default) = &variable.default {
+                if let Some(default_str) = default.as_str() {
+                    prompt = prompt.with_default(default_str);
+                }
+            }
+            let input = prompt.prompt()?;
+            serde_json::Value::String(input)
+        }
+        VariableType::Array => {
+            let prompt_text = format!("{} (comma-separated)", name);
+            let mut prompt = Text::new(&prompt_text);
+            if let Some(default) = &variable.default {
+                if let Some(default_str) = default.as_str() {
+                    prompt = prompt.with_default(default_str);
                 }
-                serde_json::Value::String(prompt.prompt()?)
             }
-            VariableType::Boolean => {
-                let default = variable
+            let input = prompt
+                .prompt()?
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .collect::<Vec<_>>();
+            serde_json::Value::Array(input.into_iter().map(serde_json::Value::String).collect())
+        }
+        VariableType::Select => {
+            if let Some(options) = &variable.options {
+                let start = variable
                     .default
                     .as_ref()
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-                let confirm = Confirm::new(name).with_default(default);
-                serde_json::Value::Bool(confirm.prompt()?)
-            }
-            VariableType::Number => {
-                let mut prompt = Text::new(name);
-                if let Some(default) = &variable.default {
-                    if let Some(default_str) = default.as_str() {
-                        prompt = prompt.with_default(default_str);
-                    }
-                }
-                let input = prompt.prompt()?;
-                serde_json::Value::String(input)
-            }
-            VariableType::Array => {
-                let prompt_text = format!("{} (comma-separated)", name);
-                let mut prompt = Text::new(&prompt_text);
-                if let Some(default) = &variable.default {
-                    if let Some(default_str) = default.as_str() {
-                        prompt = prompt.with_default(default_str);
-                    }
-                }
-                let input = prompt
-                    .prompt()?
-                    .split(',')
-                    .map(|s| s.trim().to_string())
-                    .collect::<Vec<_>>();
-                serde_json::Value::Array(input.into_iter().map(serde_json::Value::String).collect())
-            }
-            VariableType::Select => {
-                if let Some(options) = &variable.options {
-                    let selected = Select::new(name, options.clone()).prompt()?;
-                    serde_json::Value::String(selected)
-                } else {
-                    return Err(anyhow!("Select variable '{}' has no options defined", name));
-                }
+                    .and_then(|default| default.as_str())
+                    .and_then(|default| options.iter().position(|o| o == default))
+                    .unwrap_or(0);
+                let selected = Select::new(name, options.clone())
+                    .with_starting_cursor(start)
+                    .prompt()?;
+                serde_json::Value::String(selected)
+            } else {
+                return Err(anyhow!("Select variable '{}' has no options defined", name));
             }
-        };
+        }
+    };
 
-        values.insert(name.clone(), value);
+    Ok(value)
+}
+
+/// Whether a variable holds a credential: flagged `secret`, or named like one
+fn is_sensitive_variable(template: &crate::templates::Template, name: &str) -> bool {
+    template.is_secret(name) || utils::is_sensitive_env_key(name)
+}
+
+/// Required variables to ask for with masked prompts, in name order
+///
+/// Only when every required variable the values leave out is sensitive, and
+/// only interactively; otherwise nothing is prompted and validation reports
+/// what is missing, as before.
+fn secrets_to_prompt(
+    template: &crate::templates::Template,
+    values: &HashMap<String, serde_json::Value>,
+    interactive: bool,
+) -> Vec<String> {
+    if !interactive {
+        return Vec::new();
+    }
+    let mut missing: Vec<String> = template
+        .variables
+        .iter()
+        .filter(|(name, variable)| {
+            variable.required && variable.default.is_none() && !values.contains_key(*name)
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+    if !missing
+        .iter()
+        .all(|name| is_sensitive_variable(template, name))
+    {
+        return Vec::new();
     }
+    missing.sort();
+    missing
+}
 
-    Ok(values)
+/// Warn that secret values given with --vars end up in shell history
+fn warn_secrets_on_command_line(
+    template: &crate::templates::Template,
+    values: &HashMap<String, serde_json::Value>,
+) {
+    let mut secrets: Vec<&str> = values
+        .keys()
+        .filter(|name| is_sensitive_variable(template, name))
+        .map(String::as_str)
+        .collect();
+    if secrets.is_empty() {
+        return;
+    }
+    secrets.sort();
+    eprintln!(
+        "{}",
+        format!(
+            "⚠ {} {} secret; values passed with --vars are saved in your shell history. \
+             Use --vars-stdin, --vars-file or the interactive prompt instead.",
+            secrets.join(", "),
+            if secrets.len() == 1 { "is" } else { "are" }
+        )
+        .yellow()
+    );
 }
 
 /// Create a masked version of the config for safe display
 fn mask_config_credentials(config: &Config) -> Config {
     let mut masked_config = config.clone();
-    
+
     // Mask environment variables in all servers
-    for (_, server) in masked_config.mcp_servers.iter_mut() {
+    for server in masked_config
+        .mcp_servers
+        .values_mut()
+        .chain(masked_config.disabled_servers.values_mut())
+    {
         if let Some(env) = &mut server.env {
             for (key, value) in env.iter_mut() {
                 *value = utils::mask_sensitive_env_value(key, value);
             }
         }
     }
-    
+
     masked_config
 }
 
-/// Parse variables from string format
-fn parse_vars_to_json(vars_str: &str) -> Result<HashMap<String, serde_json::Value>> {
-    let mut variables = HashMap::new();
-
-    for pair in vars_str.split(',') {
-        let pair = pair.trim();
-        if let Some((key, value)) = pair.split_once('=') {
-            variables.insert(
-                key.trim().to_string(),
-                serde_json::Value::String(value.trim().to_string()),
-            );
-        } else {
-            return Err(anyhow!(
-                "Invalid variable format: '{}'. Use KEY=VALUE format",
-                pair
-            ));
+/// Copy of a server with its credentials masked: sensitive env and header
+/// values, and secret query parameters in its URL
+pub fn mask_server_credentials(server: &McpServer) -> McpServer {
+    let mut masked = server.clone();
+    if let Some(env) = &mut masked.env {
+        for (key, value) in env.iter_mut() {
+            *value = utils::mask_sensitive_env_value(key, value);
+        }
+    }
+    masked.url = server.url.as_deref().map(utils::mask_sensitive_url);
+    if let Some(serde_json::Value::Object(headers)) = masked.other.get_mut("headers") {
+        for (key, value) in headers.iter_mut() {
+            if let Some(text) = value.as_str() {
+                *value = serde_json::Value::String(utils::mask_sensitive_header_value(key, text));
+            }
         }
     }
+    masked
+}
+
+/// Parse variables given as `--vars` lists and repeated `--var` flags
+///
+/// A `--var` wins over the same key in `--vars`.
+fn parse_vars_to_json(
+    vars: Option<&str>,
+    var: &[String],
+) -> Result<HashMap<String, serde_json::Value>> {
+    let mut assignments = match vars {
+        Some(vars) => utils::assignments::parse_assignments(vars)?,
+        None => Vec::new(),
+    };
+    for assignment in var {
+        assignments.push(utils::assignments::parse_assignment(assignment)?);
+    }
 
-    Ok(variables)
+    Ok(assignments
+        .into_iter()
+        .map(|(key, value)| (key, serde_json::Value::String(value)))
+        .collect())
 }
 
 /// Handle enhanced list command with filtering and formatting
 pub async fn handle_enhanced_list(
     criteria: SearchCriteria,
     options: ListOptions,
-    profile: Option<String>,
+    target: &Target,
+    github: &ClientOptions,
 ) -> Result<()> {
-    let config = Config::load(profile.as_deref()).await?;
+    let config = Config::load(target).await?;
+    // The tree shows declared links between configured servers only
+    let workspace = if options.tree {
+        None
+    } else {
+        crate::workspace::discover(&config, github).await?
+    };
 
-    if config.mcp_servers.is_empty() {
-        println!("{}", "No MCP servers configured.".yellow());
-        println!("Add a server with: mcp-forge add <name> <template>");
-        return Ok(());
+    // Scripts and shell completion get an empty list rather than a hint
+    if !options.names_only {
+        if options.disabled_only && config.disabled_servers.is_empty() {
+            println!("{}", "No disabled servers.".yellow());
+            return Ok(());
+        }
+        if config.mcp_servers.is_empty()
+            && config.disabled_servers.is_empty()
+            && workspace.as_ref().is_none_or(|w| w.servers.is_empty())
+        {
+            println!("{}", "No MCP servers configured.".yellow());
+            println!("Add a server with: mcp-forge add <name> <template>");
+            return Ok(());
+        }
     }
 
     // Convert to list format
-    let servers: Vec<(String, McpServer)> = config.mcp_servers.into_iter().collect();
+    let mut servers: Vec<(String, McpServer)> = Vec::new();
+    if !options.disabled_only {
+        servers.extend(config.mcp_servers.clone());
+        if let Some(workspace) = &workspace {
+            servers.extend(workspace.servers.iter().cloned());
+        }
+    }
 
     // Apply filtering
-    let filtered_servers = filter_servers(servers, &criteria);
+    let metadata = ServerMetadata::load(target).unwrap_or_default();
+    let mut filtered_servers = filter_servers(servers, &criteria, &metadata);
+    // Disabled servers aren't part of the dependency tree
+    if !options.tree {
+        let disabled = config.disabled_servers.clone().into_iter().collect();
+        filtered_servers.extend(
+            filter_servers(disabled, &criteria, &metadata)
+                .into_iter()
+                .map(|server| ServerInfo {
+                    disabled: true,
+                    ..server
+                }),
+        );
+    }
+    if let Some(workspace) = &workspace {
+        for server in &mut filtered_servers {
+            if workspace
+                .servers
+                .iter()
+                .any(|(name, _)| *name == server.name)
+            {
+                server.workspace_file = Some(workspace.file.clone());
+            }
+        }
+    }
+
+    if let Some(origins) = crate::layers::origins(target)? {
+        for server in &mut filtered_servers {
+            server.layer = origins.get(&server.name).cloned();
+        }
+    }
 
     // Apply sorting
     let sorted_servers = crate::search::sort_servers(filtered_servers, &options);
 
+    if options.tree {
+        let mut names: Vec<String> = sorted_servers.iter().map(|s| s.name.clone()).collect();
+        // Config order is a hash order; keep the roots stable unless --sort was given
+        if options.sort.is_none() {
+            names.sort();
+        }
+        println!("{}", crate::links::render_tree(&names, &metadata, &config));
+        return Ok(());
+    }
+
     // Format and display
     let output = format_servers(&sorted_servers, &options);
-    println!("{}", output);
+    if !output.is_empty() {
+        println!("{}", output);
+    }
+
+    Ok(())
+}
+
+/// Show one server in full, with its credentials masked
+pub async fn handle_show(
+    name: String,
+    json: bool,
+    reveal_secrets: bool,
+    open: bool,
+    target: &Target,
+    zone: Zone,
+) -> Result<()> {
+    let config = Config::load(target).await?;
+    let (server, disabled) = match (
+        config.mcp_servers.get(&name),
+        config.disabled_servers.get(&name),
+    ) {
+        (Some(server), _) => (server, false),
+        (None, Some(server)) => (server, true),
+        (None, None) => {
+            return Err(anyhow!(
+                "Server '{}' not found. {}",
+                name,
+                crate::bulk::describe_existing_servers(&config)
+            ))
+        }
+    };
+
+    if json {
+        let server = if reveal_secrets {
+            server.clone()
+        } else {
+            mask_server_credentials(server)
+        };
+        let output = serde_json::json!({ name.as_str(): server });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let metadata = ServerMetadata::load(target).unwrap_or_default();
+    let provenance = metadata.get(&name);
+    let mut info = ServerInfo::from((name.clone(), server.clone()));
+    if let Some(provenance) = provenance {
+        info.enrich(provenance);
+    }
+
+    if disabled {
+        println!("{} {}", name.bold(), "disabled".dimmed());
+    } else {
+        println!("{}", name.bold());
+    }
+    if let Some(layer) = crate::layers::origins(target)?.and_then(|o| o.get(&name).cloned()) {
+        println!("  [layer] {}", layer);
+    }
+    print!("{}", format_server_details(&info, true, true));
+
+    let mut other: Vec<_> = server.other.iter().collect();
+    other.sort_by_key(|(key, _)| *key);
+    if !other.is_empty() {
+        println!("  Other fields:");
+        for (key, value) in other {
+            println!("    {}: {}", key, describe_other_field(Some(value)));
+        }
+    }
+
+    if let Some(provenance) = provenance {
+        println!(
+            "  Template: {} v{} (added {})",
+            provenance.template,
+            provenance.version,
+            timefmt::format_date(provenance.added_at, zone)
+        );
+        if let Some(pinned) = &provenance.pinned {
+            println!("  Pinned to: v{}", pinned);
+        }
+        if let Some(notes) = &provenance.notes {
+            println!("  Notes: {}", notes);
+        }
+    } else if let Some(preset) = metadata.presets.get(&name) {
+        println!("  Preset: {}", preset);
+    }
+
+    if open {
+        open_docs_link(&format!("server '{}'", name), info.docs_url.as_deref())?;
+    }
 
     Ok(())
 }
 
+/// Open a documentation link for `--open`, saying so when it can't
+fn open_docs_link(subject: &str, docs_url: Option<&str>) -> Result<()> {
+    match docs_url {
+        None => println!(
+            "{}",
+            format!("No documentation link is recorded for {}.", subject).yellow()
+        ),
+        Some(url) => {
+            if !crate::browser::open(url)? {
+                println!(
+                    "{}",
+                    "Not opening a browser: not an interactive terminal, or running in CI."
+                        .dimmed()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Handle enhanced add command with dry-run and preview
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_enhanced_add(
     name: String,
     template: String,
+    source: Option<String>,
     vars: Option<String>,
+    var: Vec<String>,
+    vars_file: Option<String>,
+    vars_stdin: bool,
+    ignore_platform: bool,
+    allow_unrendered: bool,
+    no_recall: bool,
     dry_run: bool,
     preview: bool,
+    target: &Target,
+    github: &ClientOptions,
     profile: Option<String>,
+    out: &Output,
 ) -> Result<()> {
-    let mut config = Config::load(profile.as_deref()).await.unwrap_or_default();
-    let template_manager = TemplateManager::new()?;
+    if vars_stdin && vars_file.as_deref() == Some("-") {
+        return Err(anyhow!(
+            "--vars-stdin and --vars-file - both read stdin; use one of them"
+        ));
+    }
+    let mut config = Config::load(target).await.unwrap_or_default();
+    let template_manager = TemplateManager::new(github)?
+        .allow_unrendered(allow_unrendered)
+        .only_from_source(source)?;
 
-    // Check if server already exists
-    if config.mcp_servers.contains_key(&name) {
-        if !dry_run {
-            let overwrite = Confirm::new(&format!("Server '{}' already exists. Overwrite?", name))
-                .with_default(false)
-                .prompt()?;
-            if !overwrite {
-                println!("Operation cancelled.");
-                return Ok(());
-            }
-        } else {
-            println!(
-                "{}",
-                format!("Would overwrite existing server '{}'", name).yellow()
-            );
-        }
+    if !confirm_overwrite(&config, &name, dry_run, out)? {
+        return Ok(());
     }
 
     // Get template
     let template_def = template_manager.load_template(&template).await?;
 
-    // Parse variables
-    let variable_values = if let Some(vars_str) = vars {
-        parse_vars_to_json(&vars_str)?
+    // A template for another platform would only fail once the client starts it
+    if let Err(e) = template_def.check_platform(&crate::utils::platform::os()) {
+        if !ignore_platform {
+            return Err(e);
+        }
+        say!(
+            out,
+            "{} Template '{}' supports {} only; adding it anyway (validate will keep warning)",
+            "⚠".yellow(),
+            template_def.name,
+            template_def.platforms.join(", ")
+        );
+    }
+
+    // Make it obvious who wrote a template that isn't maintained upstream
+    let entry = template_manager.catalog_entry(&template, false).await;
+    let category = if entry.is_none() && template_manager.local_template_path(&template).is_some() {
+        Some("local".to_string())
+    } else {
+        entry.as_ref().map(|m| m.category.clone())
+    };
+    if let Some(source) = entry.and_then(|m| m.source) {
+        if source != templates::DEFAULT_SOURCE {
+            say!(
+                out,
+                "{} Using template '{}' from source '{}'",
+                "ℹ".cyan(),
+                template_def.name,
+                source
+            );
+        }
+    }
+    if category.as_deref() != Some("official") {
+        say!(
+            out,
+            "{} Template '{}' is by {} ({})",
+            "ℹ".cyan(),
+            template_def.name,
+            template_def.author.bold(),
+            category.as_deref().unwrap_or("unlisted")
+        );
+    }
+
+    let recall = crate::recall::recall_enabled();
+
+    // Parse variables; stdin overrides the file, and explicit --vars and --var override both
+    let variable_values = if vars.is_some() || !var.is_empty() || vars_file.is_some() || vars_stdin
+    {
+        let mut values = match &vars_file {
+            Some(path) => templates::read_variables_file(path)?,
+            None => HashMap::new(),
+        };
+        if vars_stdin {
+            values.extend(templates::read_variables_stdin()?);
+        }
+        let flag_values = parse_vars_to_json(vars.as_deref(), &var)?;
+        warn_secrets_on_command_line(&template_def, &flag_values);
+        values.extend(flag_values);
+
+        // Rather than fail over a missing secret, ask for it without echoing
+        let interactive = !dry_run && std::io::IsTerminal::is_terminal(&std::io::stdin());
+        let missing = secrets_to_prompt(&template_def, &values, interactive);
+        if !missing.is_empty() {
+            say!(out, "Please provide the secret variables not given:");
+        }
+        for name in missing {
+            let variable = crate::templates::TemplateVariable {
+                secret: true,
+                ..template_def.variables[&name].clone()
+            };
+            let value = prompt_for_variable(&name, &variable)?;
+            values.insert(name, value);
+        }
+        values
     } else if !dry_run {
-        prompt_for_template_variables(&template_def).await?
+        // Values used last time with this template beat its own defaults
+        let recalled = if recall && !no_recall {
+            TemplateAnswers::load()
+                .unwrap_or_default()
+                .recalled(&template_def)
+        } else {
+            HashMap::new()
+        };
+        prompt_with_recalled_values(&template_def, &recalled, out).await?
     } else {
         HashMap::new()
     };
 
     // Apply template
     let server = template_manager.apply_template(&template_def, &variable_values)?;
+    let rendered = template_manager.render_notes(&template_def, &variable_values);
 
     if dry_run || preview {
-        preview_add_operation(&name, &server, &config, dry_run).await?;
+        let secrets = template_def.secret_values(&variable_values);
+        preview_add_operation(
+            &name,
+            &server.with_values_masked(&secrets),
+            &config,
+            dry_run,
+        )
+        .await?;
+        print_setup_instructions(&rendered, out);
+        template_manager.print_stale_cache_notice();
         return Ok(());
     }
 
     // Create backup before modification
-    let backup_dir = utils::get_backup_dir()?;
-    if backup_dir.exists() {
-        config.create_backup().await?;
-    }
+    crate::backup::backup_before_change(target, &config).await?;
 
-    // Add server
+    // Add server, remembering where it came from
+    out.record(
+        &name,
+        Some(&server),
+        &template_def.secret_values(&variable_values),
+    );
     config.mcp_servers.insert(name.clone(), server);
-    config.save(profile.as_deref()).await?;
+    let mut metadata = ServerMetadata::load(target).unwrap_or_default();
+    metadata.record(
+        &name,
+        ServerProvenance::from_template(&rendered, category, &variable_values),
+    );
+    transaction::save_config(&config, target, profile.as_deref(), Some(&metadata)).await?;
 
-    // Update profile metadata
-    update_profile_server_count(profile.as_deref()).await?;
+    if recall {
+        let mut answers = TemplateAnswers::load().unwrap_or_default();
+        answers.remember(&template_def, &variable_values);
+        answers.save()?;
+    }
 
-    println!(
+    say!(
+        out,
         "{}",
         format!("✓ Server '{}' added successfully", name).green()
     );
+    print_setup_instructions(&rendered, out);
+    template_manager.print_stale_cache_notice();
 
     Ok(())
 }
 
+/// Show a template's setup instructions, as rendered by `render_notes`
+fn print_setup_instructions(template: &templates::Template, out: &Output) {
+    if let Some(instructions) = &template.setup_instructions {
+        say!(out, "\nSetup Instructions:");
+        say!(out, "{}", instructions);
+    }
+}
+
+/// Ask before replacing an existing server; `false` means the user declined
+fn confirm_overwrite(config: &Config, name: &str, dry_run: bool, out: &Output) -> Result<bool> {
+    if !config.mcp_servers.contains_key(name) {
+        return Ok(true);
+    }
+    if dry_run {
+        say!(
+            out,
+            "{}",
+            format!("Would overwrite existing server '{}'", name).yellow()
+        );
+        return Ok(true);
+    }
+    utils::ensure_interactive(&format!(
+        "Server '{}' already exists; remove it first to replace it without a prompt",
+        name
+    ))?;
+    let overwrite = Confirm::new(&format!("Server '{}' already exists. Overwrite?", name))
+        .with_default(false)
+        .prompt()?;
+    if !overwrite {
+        say!(out, "Operation cancelled.");
+    }
+    Ok(overwrite)
+}
+
+/// Runtimes `add --preset` can launch a package with
+const PRESET_RUNTIMES: [&str; 3] = ["npx", "uvx", "docker"];
+
+/// Build a server from a `runtime:package` preset such as `npx:@scope/pkg`
+///
+/// `extra_args` (given after `--`) are appended verbatim.
+pub fn resolve_preset(preset: &str, extra_args: &[String]) -> Result<McpServer> {
+    let (runtime, package) = preset.split_once(':').ok_or_else(|| {
+        anyhow!(
+            "Preset '{}' should be <runtime>:<package>, e.g. npx:@scope/pkg ({})",
+            preset,
+            PRESET_RUNTIMES.join(", ")
+        )
+    })?;
+    let package = package.trim();
+    if package.is_empty() {
+        return Err(anyhow!("Preset '{}' is missing a package name", preset));
+    }
+
+    let (command, mut args) = match runtime {
+        "npx" => ("npx", vec!["-y".to_string(), package.to_string()]),
+        "uvx" => ("uvx", vec![package.to_string()]),
+        "docker" => (
+            "docker",
+            ["run", "-i", "--rm", package].map(str::to_string).to_vec(),
+        ),
+        other => {
+            return Err(anyhow!(
+                "Unknown preset runtime '{}'; use one of: {}",
+                other,
+                PRESET_RUNTIMES.join(", ")
+            ))
+        }
+    };
+    args.extend(extra_args.iter().cloned());
+
+    Ok(McpServer {
+        command: Some(command.to_string()),
+        args: Some(args),
+        url: None,
+        env: None,
+        timeout: None,
+        auto_approve: None,
+        other: HashMap::new(),
+    })
+}
+
+/// Handle `add --preset`, which builds a server without a template
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_preset_add(
+    name: String,
+    preset: String,
+    extra_args: Vec<String>,
+    dry_run: bool,
+    preview: bool,
+    target: &Target,
+    profile: Option<String>,
+    out: &Output,
+) -> Result<()> {
+    let mut config = Config::load(target).await.unwrap_or_default();
+
+    if !confirm_overwrite(&config, &name, dry_run, out)? {
+        return Ok(());
+    }
+
+    let server = resolve_preset(&preset, &extra_args)?;
+    server.validate()?;
+
+    if dry_run || preview {
+        return preview_add_operation(&name, &server, &config, dry_run).await;
+    }
+
+    crate::backup::backup_before_change(target, &config).await?;
+
+    out.record(&name, Some(&server), &[]);
+    config.mcp_servers.insert(name.clone(), server);
+    let mut metadata = ServerMetadata::load(target).unwrap_or_default();
+    metadata.record_preset(&name, &preset);
+    transaction::save_config(&config, target, profile.as_deref(), Some(&metadata)).await?;
+
+    say!(
+        out,
+        "{}",
+        format!("✓ Server '{}' added from preset {}", name, preset).green()
+    );
+
+    Ok(())
+}
+
+/// List the metadata records a removal will also delete
+pub fn print_metadata_cleanup(cleanup: &[String], out: &Output) {
+    if cleanup.is_empty() {
+        return;
+    }
+    say!(
+        out,
+        "Also removing recorded metadata (--keep-metadata to keep it):"
+    );
+    for line in cleanup {
+        say!(out, "  • {}", line);
+    }
+}
+
 /// Handle enhanced remove command with pattern matching and dry-run
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_enhanced_remove(
-    name: Option<String>,
+    names: Selection,
     all: bool,
     pattern: Option<String>,
     force: bool,
+    ignore_missing: bool,
+    cascade: bool,
+    keep_metadata: bool,
     dry_run: bool,
+    target: &Target,
     profile: Option<String>,
+    out: &Output,
 ) -> Result<()> {
-    let mut config = Config::load(profile.as_deref()).await?;
+    let mut config = Config::load(target).await?;
 
-    let servers_to_remove = if all {
+    let mut servers_to_remove = if all {
         config.mcp_servers.keys().cloned().collect::<Vec<_>>()
-    } else if let Some(pattern_str) = pattern {
-        crate::bulk::find_matching_servers(&config, Some(&pattern_str), None)?
-    } else if let Some(server_name) = name {
-        if config.mcp_servers.contains_key(&server_name) {
-            vec![server_name]
-        } else {
-            return Err(anyhow!("Server '{}' not found", server_name));
-        }
     } else {
-        return Err(anyhow!("Must specify server name, pattern, or --all"));
+        if names.is_all() && pattern.is_none() {
+            return Err(anyhow!("Must specify server name, pattern, or --all"));
+        }
+
+        let mut matching = Vec::new();
+        let mut skipped = Vec::new();
+        if let Some(pattern_str) = &pattern {
+            match crate::bulk::find_matching_servers(&config, Some(pattern_str), None) {
+                Ok(found) => matching = found,
+                // Only a pattern that matches nothing is ignored
+                Err(e) if ignore_missing && e.is::<crate::bulk::NoMatchingServers>() => {
+                    skipped.push(format!("No servers match pattern '{}'", pattern_str))
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let missing = names.missing(|name| config.mcp_servers.contains_key(name));
+        if let Some(missing) = selection::describe_missing(&missing) {
+            if !ignore_missing {
+                return Err(anyhow!(
+                    "{}. {}",
+                    missing,
+                    crate::bulk::describe_existing_servers(&config)
+                ));
+            }
+            skipped.push(missing);
+        }
+        for name in names.names() {
+            if config.mcp_servers.contains_key(name) && !matching.contains(name) {
+                matching.push(name.clone());
+            }
+        }
+
+        if !skipped.is_empty() {
+            let outcome = if matching.is_empty() {
+                "nothing to remove."
+            } else {
+                "removing the rest."
+            };
+            say!(
+                out,
+                "{}",
+                format!("⚠ {}; {}", skipped.join("; "), outcome).yellow()
+            );
+            if matching.is_empty() {
+                return Ok(());
+            }
+        }
+        matching
     };
 
     if servers_to_remove.is_empty() {
-        println!("{}", "No servers to remove.".yellow());
+        say!(out, "{}", "No servers to remove.".yellow());
         return Ok(());
     }
 
+    let mut metadata = ServerMetadata::load(target).unwrap_or_default();
+    let dependents: Vec<String> =
+        crate::links::transitive_dependents(&metadata, &servers_to_remove)
+            .into_iter()
+            .filter(|name| config.mcp_servers.contains_key(name))
+            .collect();
+    if !dependents.is_empty() {
+        if cascade {
+            say!(
+                out,
+                "{}",
+                format!(
+                    "Also removing dependent server(s): {}",
+                    dependents.join(", ")
+                )
+                .yellow()
+            );
+            servers_to_remove.extend(dependents);
+        } else {
+            for dependent in &dependents {
+                say!(
+                    out,
+                    "{}",
+                    format!(
+                        "⚠ '{}' depends on a server being removed; pass --cascade to remove it too",
+                        dependent
+                    )
+                    .yellow()
+                );
+            }
+        }
+    }
+
+    let cleanup = if keep_metadata {
+        Vec::new()
+    } else {
+        metadata.describe_records(&servers_to_remove)
+    };
+
     if dry_run {
-        println!("{}", "Remove Preview (Dry Run)".cyan().bold());
-        println!("{}", "────────────────────".cyan());
+        say!(out, "{}", "Remove Preview (Dry Run)".cyan().bold());
+        say!(out, "{}", "────────────────────".cyan());
         for server_name in &servers_to_remove {
             if let Some(server) = config.mcp_servers.get(server_name) {
-                println!(
+                say!(
+                    out,
                     "  {} {} - {}",
                     "REMOVE".red(),
                     server_name.bold(),
@@ -326,64 +1263,358 @@ pub async fn handle_enhanced_remove(
                 );
             }
         }
-        println!();
+        print_metadata_cleanup(&cleanup, out);
+        say!(out);
+        say!(
+            out,
+            "{}",
+            format!("Would remove {} server(s)", servers_to_remove.len()).cyan()
+        );
+        return Ok(());
+    }
+
+    // Confirm removal
+    if !force {
+        say!(out, "Servers to be removed:");
+        for server_name in &servers_to_remove {
+            if let Some(server) = config.mcp_servers.get(server_name) {
+                let server_desc = if server.is_url_server() {
+                    server
+                        .url
+                        .as_ref()
+                        .map(|u| crate::utils::mask_sensitive_url(u))
+                        .unwrap_or_else(|| "URL".to_string())
+                } else {
+                    server
+                        .command
+                        .as_ref()
+                        .unwrap_or(&"Command".to_string())
+                        .clone()
+                };
+                say!(out, "  • {} - {}", server_name.bold(), server_desc);
+            }
+        }
+        print_metadata_cleanup(&cleanup, out);
+
+        utils::ensure_can_confirm()?;
+        let confirm = Confirm::new(&format!("Remove {} server(s)?", servers_to_remove.len()))
+            .with_default(false)
+            .prompt()?;
+        if !confirm {
+            say!(out, "Removal cancelled.");
+            return Ok(());
+        }
+    }
+
+    // Create backup before modification
+    crate::backup::backup_before_change(target, &config).await?;
+
+    // Remove servers
+    let mut removed_count = 0;
+    for server_name in &servers_to_remove {
+        if config.mcp_servers.remove(server_name).is_some() {
+            out.record(server_name, None, &[]);
+            removed_count += 1;
+            say!(out, "{}", format!("✓ Removed {}", server_name).green());
+        }
+    }
+
+    // A removed server's own records go with it; links pointing at it are left
+    // for validate to report
+    let forgotten = !keep_metadata && metadata.forget(&servers_to_remove);
+    transaction::save_config(
+        &config,
+        target,
+        profile.as_deref(),
+        forgotten.then_some(&metadata),
+    )
+    .await?;
+
+    say!(out);
+    say!(
+        out,
+        "{}",
+        format!("✅ Successfully removed {} server(s)", removed_count)
+            .green()
+            .bold()
+    );
+
+    Ok(())
+}
+
+/// Move a server into `disabledServers`, keeping its configuration and metadata
+pub async fn handle_disable(
+    name: String,
+    force: bool,
+    dry_run: bool,
+    target: &Target,
+    profile: Option<String>,
+) -> Result<()> {
+    let mut config = Config::load(target).await?;
+
+    if !config.mcp_servers.contains_key(&name) {
+        if config.disabled_servers.contains_key(&name) {
+            println!(
+                "{}",
+                format!("Server '{}' is already disabled", name).yellow()
+            );
+            return Ok(());
+        }
+        return Err(anyhow!(
+            "Server '{}' not found. {}",
+            name,
+            crate::bulk::describe_existing_servers(&config)
+        ));
+    }
+
+    let metadata = ServerMetadata::load(target).unwrap_or_default();
+    for dependent in metadata.dependents(&name) {
+        if config.mcp_servers.contains_key(&dependent) {
+            println!(
+                "{}",
+                format!(
+                    "⚠ '{}' depends on '{}', which will be disabled",
+                    dependent, name
+                )
+                .yellow()
+            );
+        }
+    }
+
+    if config.disabled_servers.contains_key(&name) && !force {
+        if dry_run {
+            println!(
+                "{}",
+                format!("Would replace the disabled copy of '{}'", name).yellow()
+            );
+        } else {
+            utils::ensure_can_confirm()?;
+            let replace = Confirm::new(&format!(
+                "A disabled copy of '{}' already exists. Replace it?",
+                name
+            ))
+            .with_default(false)
+            .prompt()?;
+            if !replace {
+                println!("Operation cancelled.");
+                return Ok(());
+            }
+        }
+    }
+
+    if dry_run {
+        println!("{} {}", "DISABLE".yellow(), name.bold());
+        println!("Would move '{}' to disabledServers", name);
+        return Ok(());
+    }
+
+    crate::backup::backup_before_change(target, &config).await?;
+    config.disable_server(&name)?;
+    transaction::save_config(&config, target, profile.as_deref(), None).await?;
+
+    println!("{}", format!("✓ Server '{}' disabled", name).green());
+    println!("  Turn it back on with: mcp-forge enable {}", name);
+
+    Ok(())
+}
+
+/// Move a disabled server back into `mcpServers`, optionally under a new name
+pub async fn handle_enable(
+    name: String,
+    rename: Option<String>,
+    force: bool,
+    dry_run: bool,
+    target: &Target,
+    profile: Option<String>,
+) -> Result<()> {
+    let mut config = Config::load(target).await?;
+
+    if !config.disabled_servers.contains_key(&name) {
+        if config.mcp_servers.contains_key(&name) && rename.is_none() {
+            println!(
+                "{}",
+                format!("Server '{}' is already enabled", name).yellow()
+            );
+            return Ok(());
+        }
+        let mut disabled: Vec<&String> = config.disabled_servers.keys().collect();
+        disabled.sort();
+        return Err(anyhow!(
+            "No disabled server named '{}'. {}",
+            name,
+            if disabled.is_empty() {
+                "No servers are disabled.".to_string()
+            } else {
+                format!(
+                    "Disabled servers: {}",
+                    disabled
+                        .iter()
+                        .map(|s| s.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }
+        ));
+    }
+
+    let as_name = rename.unwrap_or_else(|| name.clone());
+    let Some(as_name) = resolve_enable_collision(&config, as_name, force, dry_run)? else {
+        println!("Operation cancelled.");
+        return Ok(());
+    };
+
+    if dry_run {
+        println!("{} {}", "ENABLE".green(), name.bold());
+        if as_name != name {
+            println!("Would move '{}' back to mcpServers as '{}'", name, as_name);
+        } else {
+            println!("Would move '{}' back to mcpServers", name);
+        }
+        return Ok(());
+    }
+
+    crate::backup::backup_before_change(target, &config).await?;
+    config.enable_server(&name, &as_name)?;
+    transaction::save_config(&config, target, profile.as_deref(), None).await?;
+
+    if as_name != name {
+        println!(
+            "{}",
+            format!("✓ Server '{}' enabled as '{}'", name, as_name).green()
+        );
+    } else {
+        println!("{}", format!("✓ Server '{}' enabled", name).green());
+    }
+
+    Ok(())
+}
+
+/// Name to enable a server under when `target` is already active: `target` itself
+/// to overwrite, a new name, or `None` if the user cancelled
+fn resolve_enable_collision(
+    config: &Config,
+    target: String,
+    force: bool,
+    dry_run: bool,
+) -> Result<Option<String>> {
+    if force || !config.mcp_servers.contains_key(&target) {
+        return Ok(Some(target));
+    }
+    if dry_run {
         println!(
             "{}",
-            format!("Would remove {} server(s)", servers_to_remove.len()).cyan()
+            format!(
+                "Would overwrite the active server '{}' (use --rename <name> to keep both)",
+                target
+            )
+            .yellow()
         );
-        return Ok(());
+        return Ok(Some(target));
     }
 
-    // Confirm removal
-    if !force {
-        println!("Servers to be removed:");
-        for server_name in &servers_to_remove {
-            if let Some(server) = config.mcp_servers.get(server_name) {
-                let server_desc = if server.is_url_server() {
-                server.url.as_ref().map(|u| crate::utils::mask_sensitive_url(u)).unwrap_or_else(|| "URL".to_string())
-            } else {
-                server.command.as_ref().unwrap_or(&"Command".to_string()).clone()
-            };
-            println!("  • {} - {}", server_name.bold(), server_desc);
-            }
-        }
-
-        let confirm = Confirm::new(&format!("Remove {} server(s)?", servers_to_remove.len()))
-            .with_default(false)
-            .prompt()?;
-        if !confirm {
-            println!("Removal cancelled.");
-            return Ok(());
+    utils::ensure_interactive(&format!(
+        "An active server is already named '{}'; pass --force to overwrite it or --rename <name> to keep both",
+        target
+    ))?;
+    let choice = Select::new(
+        &format!("An active server is already named '{}'.", target),
+        vec!["Overwrite it", "Enable under another name", "Cancel"],
+    )
+    .prompt()?;
+    match choice {
+        "Overwrite it" => Ok(Some(target)),
+        "Enable under another name" => {
+            let taken: Vec<String> = config.mcp_servers.keys().cloned().collect();
+            let new_name = Text::new("New name:")
+                .with_validator(move |input: &str| {
+                    Ok(if input.trim().is_empty() {
+                        inquire::validator::Validation::Invalid("Name cannot be empty".into())
+                    } else if taken.iter().any(|name| name == input.trim()) {
+                        inquire::validator::Validation::Invalid(
+                            "An active server already has this name".into(),
+                        )
+                    } else {
+                        inquire::validator::Validation::Valid
+                    })
+                })
+                .prompt()?;
+            Ok(Some(new_name.trim().to_string()))
         }
+        _ => Ok(None),
     }
+}
 
-    // Create backup before modification
-    let backup_dir = utils::get_backup_dir()?;
-    if backup_dir.exists() {
-        config.create_backup().await?;
+/// Give a server a new name, carrying its metadata and links along
+pub async fn handle_rename(
+    old: String,
+    new: String,
+    force: bool,
+    keep_old: bool,
+    dry_run: bool,
+    target: &Target,
+    profile: Option<String>,
+) -> Result<()> {
+    if old == new {
+        return Err(anyhow!("Server '{}' already has that name", old));
     }
 
-    // Remove servers
-    let mut removed_count = 0;
-    for server_name in &servers_to_remove {
-        if config.mcp_servers.remove(server_name).is_some() {
-            removed_count += 1;
-            println!("{}", format!("✓ Removed {}", server_name).green());
-        }
+    let mut config = Config::load(target).await?;
+
+    if !config.mcp_servers.contains_key(&old) {
+        return Err(anyhow!(
+            "Server '{}' not found. {}",
+            old,
+            crate::bulk::describe_existing_servers(&config)
+        ));
+    }
+    let overwrite = config.mcp_servers.contains_key(&new);
+    if overwrite && !force {
+        return Err(anyhow!(
+            "Server '{}' already exists; pass --force to overwrite it",
+            new
+        ));
     }
 
-    config.save(profile.as_deref()).await?;
+    let mut metadata = ServerMetadata::load(target).unwrap_or_default();
 
-    // Update profile metadata
-    update_profile_server_count(profile.as_deref()).await?;
+    if dry_run {
+        println!("{}", "Rename Preview (Dry Run)".cyan().bold());
+        println!("{}", "────────────────────────".cyan());
+        if keep_old {
+            println!("  {} {}", "DISABLE".yellow(), old.bold());
+        } else {
+            println!("  {} {}", "REMOVE".red(), old.bold());
+        }
+        if overwrite {
+            println!("  {} {}", "OVERWRITE".yellow(), new.bold());
+        } else {
+            println!("  {} {}", "NEW".green(), new.bold());
+        }
+        let records = metadata.records_for(&old);
+        if !records.is_empty() {
+            println!("Also moving recorded metadata: {}", records.join(", "));
+        }
+        return Ok(());
+    }
 
-    println!();
-    println!(
-        "{}",
-        format!("✅ Successfully removed {} server(s)", removed_count)
-            .green()
-            .bold()
-    );
+    crate::backup::backup_before_change(target, &config).await?;
+    config.rename_server(&old, &new, keep_old)?;
+    let renamed = metadata.rename(&old, &new);
+    transaction::save_config(
+        &config,
+        target,
+        profile.as_deref(),
+        renamed.then_some(&metadata),
+    )
+    .await?;
+
+    println!("{}", format!("✓ Renamed '{}' to '{}'", old, new).green());
+    if keep_old {
+        println!(
+            "  A disabled copy stays as '{}' (mcp-forge enable {})",
+            old, old
+        );
+    }
 
     Ok(())
 }
@@ -392,9 +1623,11 @@ pub async fn handle_enhanced_remove(
 pub async fn handle_enhanced_edit(
     name: String,
     dry_run: bool,
+    target: &Target,
+    github: &ClientOptions,
     profile: Option<String>,
 ) -> Result<()> {
-    let mut config = Config::load(profile.as_deref()).await?;
+    let mut config = Config::load(target).await?;
 
     let server = config
         .mcp_servers
@@ -402,18 +1635,65 @@ pub async fn handle_enhanced_edit(
         .ok_or_else(|| anyhow!("Server '{}' not found", name))?
         .clone();
 
+    let mut metadata = ServerMetadata::load(target)?;
+    let provenance = metadata.get(&name).cloned();
+    let template = match &provenance {
+        Some(provenance) => load_edit_template(provenance, github).await,
+        None => None,
+    };
+    let rerunnable = template.as_ref().zip(provenance.as_ref());
+
     if dry_run {
-        preview_edit_operation(&name, &server).await?;
+        preview_edit_operation(&name, &server, rerunnable).await?;
         return Ok(());
     }
 
+    utils::ensure_interactive("edit asks about each change; run it in a terminal")?;
     println!("{}", format!("Editing server '{}'", name).cyan());
 
+    let rerun = match rerunnable {
+        Some((template, _)) => {
+            let rerun_option =
+                format!("Re-run template '{}' with updated variables", template.name);
+            let choice = Select::new(
+                "How do you want to edit it?",
+                vec![
+                    rerun_option.clone(),
+                    "Edit command, args and env".to_string(),
+                ],
+            )
+            .prompt()?;
+            choice == rerun_option
+        }
+        None => false,
+    };
+
     // Edit server configuration
-    let edited_server = edit_server_interactive(&server).await?;
+    let (edited_server, new_provenance, secrets) = match rerunnable.filter(|_| rerun) {
+        Some((template, provenance)) => {
+            let (edited, values) =
+                rerun_template_interactive(&server, template, provenance, github).await?;
+            let rendered = TemplateManager::new(github)?.render_notes(template, &values);
+            let mut new_provenance =
+                ServerProvenance::from_template(&rendered, provenance.category.clone(), &values);
+            new_provenance.added_at = provenance.added_at;
+            new_provenance.notes = provenance.notes.clone();
+            (
+                edited,
+                Some(new_provenance),
+                template.secret_values(&values),
+            )
+        }
+        None => (edit_server_interactive(&server).await?, None, Vec::new()),
+    };
 
     // Show diff
-    show_server_diff(&server, &edited_server, &name).await?;
+    show_server_diff(
+        &server.with_values_masked(&secrets),
+        &edited_server.with_values_masked(&secrets),
+        &name,
+    )
+    .await?;
 
     let confirm = Confirm::new("Apply these changes?")
         .with_default(true)
@@ -425,17 +1705,21 @@ pub async fn handle_enhanced_edit(
     }
 
     // Create backup before modification
-    let backup_dir = utils::get_backup_dir()?;
-    if backup_dir.exists() {
-        config.create_backup().await?;
-    }
+    crate::backup::backup_before_change(target, &config).await?;
 
     // Update server
     config.mcp_servers.insert(name.clone(), edited_server);
-    config.save(profile.as_deref()).await?;
-
-    // Update profile metadata
-    update_profile_server_count(profile.as_deref()).await?;
+    let recorded = new_provenance.is_some();
+    if let Some(provenance) = new_provenance {
+        metadata.record(&name, provenance);
+    }
+    transaction::save_config(
+        &config,
+        target,
+        profile.as_deref(),
+        recorded.then_some(&metadata),
+    )
+    .await?;
 
     println!(
         "{}",
@@ -446,16 +1730,87 @@ pub async fn handle_enhanced_edit(
 }
 
 /// Handle enhanced update command with bulk operations
+/// Changes to the client-side `timeout` and `autoApprove` fields from `update`
+#[derive(Debug, Default)]
+pub struct ClientOptionsUpdate {
+    pub timeout: Option<i64>,
+    /// Comma-separated tool names
+    pub auto_approve: Option<String>,
+    pub clear_auto_approve: bool,
+}
+
+impl ClientOptionsUpdate {
+    fn validate(&self) -> Result<()> {
+        if let Some(timeout) = self.timeout {
+            if timeout <= 0 {
+                return Err(anyhow!(
+                    "--timeout must be a positive number of seconds, got {}",
+                    timeout
+                ));
+            }
+        }
+        if let Some(tools) = &self.auto_approve {
+            if parse_tool_list(tools).is_empty() {
+                return Err(anyhow!(
+                    "--auto-approve needs at least one tool name; use --clear-auto-approve to remove the list"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The autoApprove list the server ends up with, or `None` to leave it alone
+    fn new_auto_approve(&self) -> Option<Option<Vec<String>>> {
+        if self.clear_auto_approve {
+            Some(None)
+        } else {
+            self.auto_approve
+                .as_deref()
+                .map(|tools| Some(parse_tool_list(tools)))
+        }
+    }
+
+    /// Apply to a server, returning whether anything changed
+    fn apply(&self, server: &mut McpServer) -> bool {
+        let mut changed = false;
+        if let Some(timeout) = self.timeout {
+            changed |= server.timeout != Some(timeout);
+            server.timeout = Some(timeout);
+        }
+        if let Some(auto_approve) = self.new_auto_approve() {
+            changed |= server.auto_approve != auto_approve;
+            server.auto_approve = auto_approve;
+        }
+        changed
+    }
+}
+
+/// Split a comma-separated list of tool names, dropping blanks and repeats
+fn parse_tool_list(tools: &str) -> Vec<String> {
+    let mut parsed: Vec<String> = Vec::new();
+    for tool in tools.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        if !parsed.iter().any(|t| t == tool) {
+            parsed.push(tool.to_string());
+        }
+    }
+    parsed
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_enhanced_update(
     name: Option<String>,
     args: Option<String>,
     tag: Option<String>,
     set_env: Vec<String>,
+    client_options: ClientOptionsUpdate,
     dry_run: bool,
     preview: bool,
+    target: &Target,
     profile: Option<String>,
+    out: &Output,
 ) -> Result<()> {
-    let mut config = Config::load(profile.as_deref()).await?;
+    client_options.validate()?;
+    let mut config = Config::load(target).await?;
 
     // Determine servers to update
     let servers_to_update = if let Some(server_name) = name {
@@ -479,15 +1834,22 @@ pub async fn handle_enhanced_update(
     };
 
     if dry_run || preview {
-        preview_update_operation(&servers_to_update, &args, &env_updates, &config).await?;
+        preview_update_operation(
+            &servers_to_update,
+            &args,
+            &env_updates,
+            &client_options,
+            &config,
+        )
+        .await?;
         return Ok(());
     }
 
+    let replacements =
+        crate::bulk::confirm_case_variant_replacements(&config, &servers_to_update, &env_updates)?;
+
     // Create backup before modification
-    let backup_dir = utils::get_backup_dir()?;
-    if backup_dir.exists() {
-        config.create_backup().await?;
-    }
+    crate::backup::backup_before_change(target, &config).await?;
 
     // Apply updates
     let mut updated_count = 0;
@@ -509,6 +1871,9 @@ pub async fn handle_enhanced_update(
                     server.env = Some(HashMap::new());
                 }
                 if let Some(env) = &mut server.env {
+                    for (_, existing) in replacements.iter().filter(|(s, _)| s == server_name) {
+                        env.remove(existing);
+                    }
                     for (key, value) in &env_updates {
                         env.insert(key.clone(), value.clone());
                     }
@@ -516,20 +1881,23 @@ pub async fn handle_enhanced_update(
                 changed = true;
             }
 
+            if client_options.apply(server) {
+                changed = true;
+            }
+
             if changed {
                 updated_count += 1;
-                println!("{}", format!("✓ Updated {}", server_name).green());
+                out.record(server_name, Some(server), &[]);
+                say!(out, "{}", format!("✓ Updated {}", server_name).green());
             }
         }
     }
 
-    config.save(profile.as_deref()).await?;
+    transaction::save_config(&config, target, profile.as_deref(), None).await?;
 
-    // Update profile metadata
-    update_profile_server_count(profile.as_deref()).await?;
-
-    println!();
-    println!(
+    say!(out);
+    say!(
+        out,
         "{}",
         format!("✅ Successfully updated {} server(s)", updated_count)
             .green()
@@ -562,7 +1930,7 @@ async fn preview_add_operation(
     };
 
     println!("{} {}", status, name.bold());
-    
+
     // Display based on server type
     if server.is_url_server() {
         println!("  Type: URL");
@@ -582,13 +1950,13 @@ async fn preview_add_operation(
             }
         }
     }
-    
+
     if let Some(env) = &server.env {
         if !env.is_empty() {
             println!("  Environment:");
             for (key, value) in env {
-                let masked_value = crate::utils::mask_sensitive_env_value(key, value);
-                println!("    {}={}", key, masked_value);
+                let shown = crate::utils::display::display_env_value(key, value, false);
+                println!("    {}={}", key, shown);
             }
         }
     }
@@ -596,8 +1964,66 @@ async fn preview_add_operation(
     Ok(())
 }
 
+/// Load the template a server was created from, so `edit` can re-run it
+///
+/// Returns `None`, after saying why, when the template can't be loaded; `edit`
+/// then falls back to editing the raw configuration.
+async fn load_edit_template(
+    provenance: &ServerProvenance,
+    github: &ClientOptions,
+) -> Option<templates::Template> {
+    let loaded = match TemplateManager::new(github) {
+        Ok(manager) => manager.load_template(&provenance.template).await,
+        Err(e) => Err(e),
+    };
+    match loaded {
+        Ok(template) => Some(template),
+        Err(e) => {
+            println!(
+                "{}",
+                format!(
+                    "⚠ Couldn't load template '{}' ({}); editing the raw configuration instead",
+                    provenance.template, e
+                )
+                .yellow()
+            );
+            None
+        }
+    }
+}
+
+/// Prompt for the template's variables, pre-filled with the previous answers,
+/// and rebuild the server from them
+async fn rerun_template_interactive(
+    current: &McpServer,
+    template: &templates::Template,
+    provenance: &ServerProvenance,
+    github: &ClientOptions,
+) -> Result<(McpServer, HashMap<String, serde_json::Value>)> {
+    if template.version != provenance.version {
+        println!(
+            "{} Template '{}' is now v{}; the server was created from v{}",
+            "ℹ".cyan(),
+            template.name,
+            template.version,
+            provenance.version
+        );
+    }
+
+    let values =
+        prompt_for_template_variables(&template.with_previous_values(&provenance.variables))
+            .await?;
+    let mut rebuilt = TemplateManager::new(github)?.apply_template(template, &values)?;
+    template.keep_local_changes(current, &mut rebuilt);
+    Ok((rebuilt, values))
+}
+
 /// Preview edit operation
-async fn preview_edit_operation(name: &str, server: &McpServer) -> Result<()> {
+async fn preview_edit_operation(
+    name: &str,
+    server: &McpServer,
+    rerunnable: Option<(&templates::Template, &ServerProvenance)>,
+) -> Result<()> {
     println!("{}", "Edit Preview (Dry Run)".cyan().bold());
     println!("{}", "────────────────────".cyan());
     println!("Server: {}", name.bold());
@@ -615,6 +2041,27 @@ async fn preview_edit_operation(name: &str, server: &McpServer) -> Result<()> {
             }
         }
     }
+    match rerunnable {
+        Some((template, provenance)) => {
+            let mut previous: Vec<String> = provenance
+                .variables
+                .iter()
+                .map(|(name, value)| match value {
+                    serde_json::Value::String(s) => format!("{}={}", name, s),
+                    other => format!("{}={}", name, other),
+                })
+                .collect();
+            previous.sort();
+            println!(
+                "  Template: {} v{} (edit can re-run it with updated variables)",
+                template.name, template.version
+            );
+            if !previous.is_empty() {
+                println!("  Previous values: {}", previous.join(", "));
+            }
+        }
+        None => println!("  No template to re-run; edit opens the raw editor"),
+    }
     println!();
     println!("Use without --dry-run to edit interactively.");
 
@@ -626,6 +2073,7 @@ async fn preview_update_operation(
     servers: &[String],
     args: &Option<String>,
     env_updates: &HashMap<String, String>,
+    client_options: &ClientOptionsUpdate,
     config: &Config,
 ) -> Result<()> {
     println!("{}", "Update Preview".cyan().bold());
@@ -638,17 +2086,38 @@ async fn preview_update_operation(
             if let Some(new_args) = args {
                 println!(
                     "  Arguments: {} → {}",
-                    server.args.as_ref().map(|a| a.join(" ")).unwrap_or_default().dimmed(),
+                    server
+                        .args
+                        .as_ref()
+                        .map(|a| a.join(" "))
+                        .unwrap_or_default()
+                        .dimmed(),
                     new_args.cyan()
                 );
             }
 
-            if !env_updates.is_empty() {
+            let old_env = server.env.clone().unwrap_or_default();
+            let mut new_env = old_env.clone();
+            new_env.extend(env_updates.clone());
+            if new_env != old_env {
                 println!("  Environment updates:");
-                for (key, value) in env_updates {
-                    let masked_value = crate::utils::mask_sensitive_env_value(key, value);
-                    println!("    {}={}", key.cyan(), masked_value.cyan());
-                }
+                crate::utils::display::print_env_diff("    ", &old_env, &new_env);
+            }
+
+            if let Some(timeout) = client_options.timeout {
+                println!(
+                    "  Timeout: {} → {}",
+                    format_timeout(server.timeout).dimmed(),
+                    format_timeout(Some(timeout)).cyan()
+                );
+            }
+
+            if let Some(auto_approve) = client_options.new_auto_approve() {
+                println!(
+                    "  Auto-approve: {} → {}",
+                    format_auto_approve(server.auto_approve.as_deref()).dimmed(),
+                    format_auto_approve(auto_approve.as_deref()).cyan()
+                );
             }
 
             println!();
@@ -658,14 +2127,34 @@ async fn preview_update_operation(
     Ok(())
 }
 
+fn format_timeout(timeout: Option<i64>) -> String {
+    timeout
+        .map(|t| format!("{}s", t))
+        .unwrap_or_else(|| "None".to_string())
+}
+
+fn format_auto_approve(tools: Option<&[String]>) -> String {
+    tools
+        .map(|t| t.join(", "))
+        .unwrap_or_else(|| "None".to_string())
+}
+
 /// Show diff between two server configurations
-async fn show_server_diff(old: &McpServer, new: &McpServer, name: &str) -> Result<()> {
+pub async fn show_server_diff(old: &McpServer, new: &McpServer, name: &str) -> Result<()> {
     println!("\n{} Changes for server '{}':", "📝".cyan(), name);
 
     // Check URL changes
     if old.url != new.url {
-        let old_url = old.url.as_ref().map(|u| crate::utils::mask_sensitive_url(u)).unwrap_or_else(|| "None".to_string());
-        let new_url = new.url.as_ref().map(|u| crate::utils::mask_sensitive_url(u)).unwrap_or_else(|| "None".to_string());
+        let old_url = old
+            .url
+            .as_ref()
+            .map(|u| crate::utils::mask_sensitive_url(u))
+            .unwrap_or_else(|| "None".to_string());
+        let new_url = new
+            .url
+            .as_ref()
+            .map(|u| crate::utils::mask_sensitive_url(u))
+            .unwrap_or_else(|| "None".to_string());
         println!("  URL: {} → {}", old_url.red(), new_url.green());
     }
 
@@ -678,60 +2167,86 @@ async fn show_server_diff(old: &McpServer, new: &McpServer, name: &str) -> Resul
 
     // Check args changes
     if old.args != new.args {
-        let old_args = old.args.as_ref().map(|a| a.join(" ")).unwrap_or_else(|| "None".to_string());
-        let new_args = new.args.as_ref().map(|a| a.join(" ")).unwrap_or_else(|| "None".to_string());
+        let old_args = old
+            .args
+            .as_ref()
+            .map(|a| a.join(" "))
+            .unwrap_or_else(|| "None".to_string());
+        let new_args = new
+            .args
+            .as_ref()
+            .map(|a| a.join(" "))
+            .unwrap_or_else(|| "None".to_string());
+        println!("  Args: {} → {}", old_args.red(), new_args.green());
+    }
+
+    if old.timeout != new.timeout {
         println!(
-            "  Args: {} → {}",
-            old_args.red(),
-            new_args.green()
+            "  Timeout: {} → {}",
+            format_timeout(old.timeout).red(),
+            format_timeout(new.timeout).green()
+        );
+    }
+
+    if old.auto_approve != new.auto_approve {
+        println!(
+            "  Auto-approve: {} → {}",
+            format_auto_approve(old.auto_approve.as_deref()).red(),
+            format_auto_approve(new.auto_approve.as_deref()).green()
         );
     }
 
-    // Check env changes with proper lifetimes
     let empty_env = HashMap::new();
     let old_env = old.env.as_ref().unwrap_or(&empty_env);
     let new_env = new.env.as_ref().unwrap_or(&empty_env);
 
     if old_env != new_env {
         println!("  Environment variables:");
+        crate::utils::display::print_env_diff("    ", old_env, new_env);
+    }
 
-        // Show removed variables
-        for (key, value) in old_env {
-            if !new_env.contains_key(key) {
-                let masked_value = crate::utils::mask_sensitive_env_value(key, value);
-                println!("    {} {}: {}", "-".red(), key.red(), masked_value.red());
-            }
-        }
-
-        // Show added/changed variables
-        for (key, value) in new_env {
-            if let Some(old_value) = old_env.get(key) {
-                if old_value != value {
-                    let masked_old = crate::utils::mask_sensitive_env_value(key, old_value);
-                    let masked_new = crate::utils::mask_sensitive_env_value(key, value);
-                    println!(
-                        "    {} {}: {} → {}",
-                        "~".yellow(),
-                        key,
-                        masked_old.red(),
-                        masked_new.green()
-                    );
-                }
-            } else {
-                let masked_value = crate::utils::mask_sensitive_env_value(key, value);
-                println!(
-                    "    {} {}: {}",
-                    "+".green(),
-                    key.green(),
-                    masked_value.green()
-                );
-            }
+    // Check changes to fields mcp-forge doesn't model, such as headers
+    let mut other_keys: Vec<_> = old.other.keys().chain(new.other.keys()).collect();
+    other_keys.sort();
+    other_keys.dedup();
+    for key in other_keys {
+        let (old_value, new_value) = (old.other.get(key), new.other.get(key));
+        if old_value != new_value {
+            println!(
+                "  {}: {} → {}",
+                key,
+                describe_other_field(old_value).red(),
+                describe_other_field(new_value).green()
+            );
         }
     }
 
     Ok(())
 }
 
+/// Render an extra server field for diffs, masking secret-looking object values
+fn describe_other_field(value: Option<&serde_json::Value>) -> String {
+    match value {
+        None => "None".to_string(),
+        Some(serde_json::Value::Object(map)) => {
+            let masked: serde_json::Map<_, _> = map
+                .iter()
+                .map(|(k, v)| {
+                    let v = match v.as_str() {
+                        Some(s) => {
+                            serde_json::Value::String(utils::mask_sensitive_header_value(k, s))
+                        }
+                        None => v.clone(),
+                    };
+                    (k.clone(), v)
+                })
+                .collect();
+            serde_json::Value::Object(masked).to_string()
+        }
+        Some(other) => other.to_string(),
+    }
+}
+
 /// Interactive server editor
 async fn edit_server_interactive(server: &McpServer) -> Result<McpServer> {
     let mut edited = server.clone();
@@ -740,9 +2255,7 @@ async fn edit_server_interactive(server: &McpServer) -> Result<McpServer> {
     if server.is_url_server() {
         // Edit URL
         let current_url = server.url.as_deref().unwrap_or("");
-        let new_url = Text::new("URL:")
-            .with_initial_value(current_url)
-            .prompt()?;
+        let new_url = Text::new("URL:").with_initial_value(current_url).prompt()?;
         edited.url = Some(new_url);
         edited.command = None;
         edited.args = None;
@@ -755,7 +2268,11 @@ async fn edit_server_interactive(server: &McpServer) -> Result<McpServer> {
         edited.command = Some(new_command);
 
         // Edit arguments
-        let args_string = server.args.as_ref().map(|a| a.join(" ")).unwrap_or_default();
+        let args_string = server
+            .args
+            .as_ref()
+            .map(|a| a.join(" "))
+            .unwrap_or_default();
         let new_args_string = Text::new("Arguments:")
             .with_initial_value(&args_string)
             .prompt()?;
@@ -763,7 +2280,7 @@ async fn edit_server_interactive(server: &McpServer) -> Result<McpServer> {
             new_args_string
                 .split_whitespace()
                 .map(|s| s.to_string())
-                .collect()
+                .collect(),
         );
         edited.url = None;
     }
@@ -788,40 +2305,380 @@ async fn edit_server_interactive(server: &McpServer) -> Result<McpServer> {
         }
     }
 
+    let edit_other = Confirm::new("Edit advanced fields?")
+        .with_default(false)
+        .with_help_message("Fields mcp-forge doesn't model, such as headers or cwd")
+        .prompt()?;
+    if edit_other {
+        edit_other_fields(&mut edited.other)?;
+    }
+
     Ok(edited)
 }
 
-/// Load configuration from file
+/// Let the user edit, delete and add a server's extra fields as raw JSON
+fn edit_other_fields(other: &mut HashMap<String, serde_json::Value>) -> Result<()> {
+    const ADD: &str = "+ Add a field";
+    const DONE: &str = "Done";
+
+    loop {
+        let mut keys: Vec<String> = other.keys().cloned().collect();
+        keys.sort();
+        for key in keys.iter().filter(|key| shadows_typed_field(key)) {
+            println!(
+                "{}",
+                format!(
+                    "⚠️  '{}' shadows a field mcp-forge manages itself and would be written twice; delete it",
+                    key
+                )
+                .yellow()
+            );
+        }
+
+        let mut options: Vec<String> = keys
+            .iter()
+            .map(|key| format!("{} = {}", key, describe_other_field(other.get(key))))
+            .collect();
+        options.push(ADD.to_string());
+        options.push(DONE.to_string());
+
+        let choice = Select::new("Advanced fields:", options).raw_prompt()?;
+        if choice.value == DONE {
+            return Ok(());
+        }
+        if choice.value == ADD {
+            let existing = keys.clone();
+            let key = Text::new("Field name:")
+                .with_validator(move |input: &str| {
+                    Ok(match validate_other_field_key(input, &existing) {
+                        Ok(()) => inquire::validator::Validation::Valid,
+                        Err(message) => inquire::validator::Validation::Invalid(message.into()),
+                    })
+                })
+                .prompt()?;
+            let value = prompt_other_field_value(key.trim(), None)?;
+            other.insert(key.trim().to_string(), value);
+            continue;
+        }
+
+        let key = &keys[choice.index];
+        match Select::new(key, vec!["Edit value", "Delete", "Back"]).prompt()? {
+            "Edit value" => {
+                let value = prompt_other_field_value(key, other.get(key))?;
+                other.insert(key.clone(), value);
+            }
+            "Delete" => {
+                other.remove(key);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn prompt_other_field_value(
+    key: &str,
+    current: Option<&serde_json::Value>,
+) -> Result<serde_json::Value> {
+    let initial = current.map(|value| value.to_string()).unwrap_or_default();
+    let input = Text::new(&format!("{} (JSON):", key))
+        .with_initial_value(&initial)
+        .with_help_message(r#"e.g. "text", 30, true, ["a", "b"] or {"key": "value"}"#)
+        .with_validator(|input: &str| {
+            Ok(match parse_other_field_value(input) {
+                Ok(_) => inquire::validator::Validation::Valid,
+                Err(message) => inquire::validator::Validation::Invalid(message.into()),
+            })
+        })
+        .prompt()?;
+    parse_other_field_value(&input).map_err(|message| anyhow!(message))
+}
+
+fn shadows_typed_field(key: &str) -> bool {
+    TYPED_SERVER_FIELDS.contains(&key)
+}
+
+/// Check a name for a new extra field against the typed and existing fields
+fn validate_other_field_key(key: &str, existing: &[String]) -> Result<(), String> {
+    let key = key.trim();
+    if key.is_empty() {
+        Err("Field name cannot be empty".to_string())
+    } else if shadows_typed_field(key) {
+        Err(format!(
+            "'{}' is a field mcp-forge manages itself; edit it in the steps above",
+            key
+        ))
+    } else if existing.iter().any(|name| name == key) {
+        Err(format!("'{}' already exists; edit it instead", key))
+    } else {
+        Ok(())
+    }
+}
+
+/// Parse an extra field's value as entered, which must be JSON
+fn parse_other_field_value(input: &str) -> Result<serde_json::Value, String> {
+    serde_json::from_str(input.trim()).map_err(|e| {
+        format!(
+            "Not valid JSON ({}); strings need double quotes, e.g. \"yes\"",
+            e
+        )
+    })
+}
+
+/// Index written alongside a split export
+#[derive(Debug, Serialize, Deserialize)]
+struct SplitExportIndex {
+    servers: Vec<SplitExportEntry>,
+    secrets_masked: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SplitExportEntry {
+    name: String,
+    file: String,
+}
+
+const SPLIT_INDEX_FILE: &str = "index.json";
+
+/// Load configuration from a file, or from a directory of per-server files
 async fn load_config_from_file(file_path: &str) -> Result<Config> {
+    if Path::new(file_path).is_dir() {
+        return load_config_from_dir(Path::new(file_path));
+    }
+
     let content = fs::read_to_string(file_path)?;
+    parse_config(&content)
+}
 
-    // Try JSON first, then YAML
-    serde_json::from_str(&content)
-        .or_else(|_| serde_yaml::from_str(&content))
+/// Parse a configuration, trying JSON first, then YAML
+fn parse_config(content: &str) -> Result<Config> {
+    serde_json::from_str(content)
+        .or_else(|_| serde_yaml::from_str(content))
         .map_err(|e| anyhow!("Failed to parse config file: {}", e))
 }
 
-/// Merge two configurations
-fn merge_configs(current: &Config, import: &Config) -> Result<Config> {
-    let mut merged = current.clone();
+/// Load every `*.json`/`*.yaml` server file in a directory as one configuration
+fn load_config_from_dir(dir: &Path) -> Result<Config> {
+    let index_path = dir.join(SPLIT_INDEX_FILE);
+    if index_path.exists() {
+        let index: SplitExportIndex = serde_json::from_str(&fs::read_to_string(&index_path)?)
+            .map_err(|e| anyhow!("Failed to parse {}: {}", index_path.display(), e))?;
+        if index.secrets_masked {
+            return Err(anyhow!(
+                "{} was exported with masked secrets; re-export with --include-secrets to import it",
+                dir.display()
+            ));
+        }
+    }
+
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name().and_then(|n| n.to_str()) != Some(SPLIT_INDEX_FILE)
+                && matches!(
+                    path.extension().and_then(|e| e.to_str()),
+                    Some("json" | "yaml" | "yml")
+                )
+        })
+        .collect();
+    files.sort();
+
+    let mut config = Config::default();
+    let mut sources: HashMap<String, PathBuf> = HashMap::new();
+    for path in files {
+        let content = fs::read_to_string(&path)?;
+        let servers: HashMap<String, McpServer> = serde_json::from_str(&content)
+            .or_else(|_| serde_yaml::from_str(&content))
+            .map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))?;
+
+        for (name, server) in servers {
+            if let Some(previous) = sources.get(&name) {
+                return Err(anyhow!(
+                    "Server '{}' is defined in both {} and {}",
+                    name,
+                    previous.display(),
+                    path.display()
+                ));
+            }
+            sources.insert(name.clone(), path.clone());
+            config.mcp_servers.insert(name, server);
+        }
+    }
+
+    if config.mcp_servers.is_empty() {
+        return Err(anyhow!("No server files found in {}", dir.display()));
+    }
+
+    Ok(config)
+}
+
+/// Write one file per server plus an index, returning the index
+fn export_split(
+    config: &Config,
+    dir: &Path,
+    yaml: bool,
+    include_secrets: bool,
+) -> Result<SplitExportIndex> {
+    fs::create_dir_all(dir)?;
+
+    let mut names: Vec<_> = config.mcp_servers.keys().collect();
+    names.sort();
+
+    let extension = if yaml { "yaml" } else { "json" };
+    let mut used = std::collections::HashSet::new();
+    let mut index = SplitExportIndex {
+        servers: Vec::new(),
+        secrets_masked: !include_secrets,
+    };
+
+    for name in names {
+        let mut server = config.mcp_servers[name].clone();
+        if !include_secrets {
+            if let Some(env) = server.env.as_mut() {
+                for (key, value) in env.iter_mut() {
+                    *value = utils::mask_sensitive_env_value(key, value);
+                }
+            }
+        }
+
+        // Names that sanitize to the same file (or differ only by case) get a suffix
+        let stem = utils::sanitize_filename(name);
+        let mut file = format!("{}.{}", stem, extension);
+        let mut n = 2;
+        while file == SPLIT_INDEX_FILE || !used.insert(file.to_lowercase()) {
+            file = format!("{}-{}.{}", stem, n, extension);
+            n += 1;
+        }
+
+        let entry = HashMap::from([(name.clone(), server)]);
+        let content = if yaml {
+            serde_yaml::to_string(&entry)?
+        } else {
+            serde_json::to_string_pretty(&entry)?
+        };
+        fs::write(dir.join(&file), content)?;
+
+        index.servers.push(SplitExportEntry {
+            name: name.clone(),
+            file,
+        });
+    }
+
+    fs::write(
+        dir.join(SPLIT_INDEX_FILE),
+        serde_json::to_string_pretty(&index)?,
+    )?;
+
+    Ok(index)
+}
+
+/// Export configuration as JSON
+fn export_as_json(config: &Config) -> Result<String> {
+    serde_json::to_string_pretty(config)
+        .map_err(|e| anyhow!("Failed to serialize config as JSON: {}", e))
+}
+
+/// Export configuration as YAML
+fn export_as_yaml(config: &Config) -> Result<String> {
+    serde_yaml::to_string(config).map_err(|e| anyhow!("Failed to serialize config as YAML: {}", e))
+}
+
+/// Export configuration as YAML with review comments above each server
+///
+/// serde_yaml cannot emit comments, so each server is serialized on its own and
+/// the document is assembled line by line. Stripping the comment lines leaves
+/// YAML equivalent to `export_as_yaml`.
+fn export_as_annotated_yaml(
+    config: &Config,
+    metadata: &ServerMetadata,
+    zone: Zone,
+) -> Result<String> {
+    let mut out = String::new();
+
+    if config.mcp_servers.is_empty() {
+        out.push_str("mcpServers: {}\n");
+    } else {
+        out.push_str("mcpServers:\n");
+    }
+
+    let mut names: Vec<_> = config.mcp_servers.keys().collect();
+    names.sort();
+
+    for name in names {
+        let server = &config.mcp_servers[name];
+
+        for comment in server_annotations(server, metadata.get(name), zone) {
+            out.push_str("  # ");
+            out.push_str(&comment);
+            out.push('\n');
+        }
+
+        let mut entry = std::collections::BTreeMap::new();
+        entry.insert(name, server);
+        let body = serde_yaml::to_string(&entry)
+            .map_err(|e| anyhow!("Failed to serialize server '{}' as YAML: {}", name, e))?;
+        for line in body.lines() {
+            out.push_str("  ");
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
 
-    // Merge servers (import overwrites existing)
-    for (name, server) in &import.mcp_servers {
-        merged.mcp_servers.insert(name.clone(), server.clone());
+    if !config.other.is_empty() {
+        let other: std::collections::BTreeMap<_, _> = config.other.iter().collect();
+        out.push_str(
+            &serde_yaml::to_string(&other)
+                .map_err(|e| anyhow!("Failed to serialize config as YAML: {}", e))?,
+        );
     }
 
-    Ok(merged)
+    Ok(out)
 }
 
-/// Export configuration as JSON
-fn export_as_json(config: &Config) -> Result<String> {
-    serde_json::to_string_pretty(config)
-        .map_err(|e| anyhow!("Failed to serialize config as JSON: {}", e))
-}
+/// Comment lines describing where a server came from and what it holds
+fn server_annotations(
+    server: &McpServer,
+    provenance: Option<&ServerProvenance>,
+    zone: Zone,
+) -> Vec<String> {
+    let mut comments = Vec::new();
+
+    match provenance {
+        Some(p) => {
+            let mut origin = format!("template: {} v{} by {}", p.template, p.version, p.author);
+            if let Some(category) = &p.category {
+                origin.push_str(&format!(" ({})", category));
+            }
+            comments.push(origin);
+            comments.push(format!(
+                "added: {}",
+                timefmt::format_timestamp(p.added_at, zone)
+            ));
+            if !p.tags.is_empty() {
+                comments.push(format!("tags: {}", p.tags.join(", ")));
+            }
+            if let Some(notes) = &p.notes {
+                comments.extend(notes.lines().map(|line| format!("notes: {}", line)));
+            }
+        }
+        None => comments.push("template: unknown (added manually or before tracking)".to_string()),
+    }
 
-/// Export configuration as YAML
-fn export_as_yaml(config: &Config) -> Result<String> {
-    serde_yaml::to_string(config).map_err(|e| anyhow!("Failed to serialize config as YAML: {}", e))
+    if let Some(env) = &server.env {
+        let mut secrets: Vec<_> = env
+            .iter()
+            .filter(|(key, _)| utils::is_sensitive_env_key(key))
+            .collect();
+        secrets.sort();
+        for (key, value) in secrets {
+            comments.push(format!(
+                "secret: {} = {} (real value exported below)",
+                key,
+                utils::mask_sensitive_env_value(key, value)
+            ));
+        }
+    }
+
+    comments
 }
 
 /// Export configuration as template
@@ -848,34 +2705,85 @@ fn export_as_template(config: &Config) -> Result<String> {
 }
 
 // Template command implementations
-async fn handle_template_list(cached: bool, offline: bool) -> Result<()> {
-    let template_manager = TemplateManager::new()?;
+async fn handle_template_list(
+    cached: bool,
+    offline: bool,
+    author: Option<String>,
+    compatible: bool,
+    github: &ClientOptions,
+    zone: Zone,
+) -> Result<()> {
+    let template_manager = TemplateManager::new(github)?;
+    let platform = crate::utils::platform::os();
 
     if offline || cached {
-        // Show cached templates only
-        if let Some(catalog) = template_manager.load_cached_catalog()? {
+        // Show cached and local templates only
+        let catalog = template_manager.load_cached_catalog()?;
+        let local = template_manager.local_templates();
+        let sources = template_manager.cached_source_templates();
+        if catalog.is_some() || !local.is_empty() || !sources.is_empty() {
+            let mut templates = catalog.map(|c| c.templates).unwrap_or_default();
+            for (name, metadata) in sources {
+                templates.entry(name).or_insert(metadata);
+            }
+            templates.extend(local);
+            let mut templates: Vec<_> = templates.into_values().collect();
+            if let Some(author_filter) = &author {
+                templates.retain(|t| author_matches(&t.author, author_filter));
+            }
+            if compatible {
+                templates.retain(|t| templates::supports_platform(&t.platforms, &platform));
+            }
+            template_manager
+                .resolve_updated_at(&mut templates, true)
+                .await?;
+
             println!("📦 Cached Templates:");
-            for (name, metadata) in catalog.templates {
-                println!("  • {} - {}", name, metadata.description);
-                println!(
+            for metadata in templates {
+                println!("  • {} - {}", metadata.name, metadata.description);
+                print!(
                     "    Author: {} | Platforms: {}",
                     metadata.author,
                     metadata.platforms.join(", ")
                 );
+                if template_manager.has_sources() {
+                    let source = metadata.source.as_deref();
+                    print!(" | Source: {}", source.unwrap_or(templates::DEFAULT_SOURCE));
+                }
+                match metadata.updated_at {
+                    Some(updated_at) => {
+                        println!(" | Updated: {}", timefmt::format_date(updated_at, zone))
+                    }
+                    None => println!(),
+                }
             }
         } else {
             println!("No cached templates available. Run 'mcp-forge template refresh' first.");
         }
+        template_manager.print_stale_cache_notice();
         return Ok(());
     }
 
-    let templates = template_manager.list_templates().await?;
+    let mut templates = template_manager.list_templates().await?;
+
+    if let Some(author_filter) = &author {
+        templates.retain(|t| author_matches(&t.author, author_filter));
+    }
+    if compatible {
+        templates.retain(|t| templates::supports_platform(&t.platforms, &platform));
+    }
 
     if templates.is_empty() {
         println!("{}", "No templates available.".yellow());
+        template_manager.print_stale_cache_notice();
         return Ok(());
     }
 
+    // Listing only uses update times that are already known locally
+    template_manager
+        .resolve_updated_at(&mut templates, true)
+        .await?;
+
     println!("{}", "Available Templates".cyan().bold());
     println!("{}", "──────────────────".cyan());
 
@@ -887,18 +2795,103 @@ async fn handle_template_list(cached: bool, offline: bool) -> Result<()> {
             template.category.dimmed()
         );
         println!("  {}", template.description);
+        println!("  Author: {}", template.author);
         if !template.tags.is_empty() {
             println!("  Tags: {}", template.tags.join(", ").dimmed());
         }
         println!("  Platforms: {}", template.platforms.join(", ").dimmed());
+        if let Some(updated_at) = template.updated_at {
+            println!(
+                "  Updated: {}",
+                timefmt::format_date(updated_at, zone).dimmed()
+            );
+        }
+        if let (true, Some(source)) = (template_manager.has_sources(), &template.source) {
+            println!("  Source: {}", source.dimmed());
+        }
+    }
+    template_manager.print_stale_cache_notice();
+
+    Ok(())
+}
+
+/// Print or save a template's JSON exactly as stored, then lint it
+async fn handle_template_raw(
+    name: String,
+    source: Option<String>,
+    cached: bool,
+    output: Option<String>,
+    github: &ClientOptions,
+) -> Result<()> {
+    let template_manager = TemplateManager::new(github)?.only_from_source(source)?;
+    let raw = template_manager.load_raw_template(&name, cached).await?;
+
+    match &output {
+        Some(path) => {
+            fs::write(path, &raw.content)
+                .map_err(|e| anyhow!("Failed to write {}: {}", path, e))?;
+            println!(
+                "{}",
+                format!("✓ Wrote template '{}' to {}", name, path).green()
+            );
+            println!("  Source: {}", raw.source);
+        }
+        None => {
+            // Only the JSON goes to stdout, so it can be redirected as-is
+            print!("{}", raw.content);
+            if !raw.content.ends_with('\n') {
+                println!();
+            }
+            eprintln!("Source: {}", raw.source);
+        }
+    }
+
+    let warnings = match serde_json::from_str::<templates::Template>(&raw.content) {
+        Ok(template) => {
+            let mut warnings = template.security_warnings();
+            warnings.extend(override_warnings(&template_manager, &template, cached).await);
+            warnings
+        }
+        Err(e) => vec![format!("Not a valid template: {}", e)],
+    };
+    for warning in warnings {
+        eprintln!("{} {}", "⚠".yellow(), warning);
     }
 
     Ok(())
 }
 
-async fn handle_template_show(name: String) -> Result<()> {
-    let template_manager = TemplateManager::new()?;
-    let template = template_manager.load_template(&name).await?;
+/// Variables a template overrides with another type than the template it extends
+async fn override_warnings(
+    template_manager: &TemplateManager,
+    template: &templates::Template,
+    cached: bool,
+) -> Vec<String> {
+    let Some(parent) = &template.extends else {
+        return Vec::new();
+    };
+    match template_manager.load_extended(parent, cached).await {
+        Ok(inherited) => template.incompatible_overrides(&inherited),
+        Err(e) => vec![format!("{:#}", e)],
+    }
+}
+
+async fn handle_template_show(
+    name: String,
+    source: Option<String>,
+    cached: bool,
+    full: bool,
+    open: bool,
+    github: &ClientOptions,
+    zone: Zone,
+) -> Result<()> {
+    let template_manager = TemplateManager::new(github)?.only_from_source(source)?;
+    let template = if cached {
+        let stored = template_manager.require_cached_template(&name).await?;
+        template_manager.resolve_extends(stored, true).await?
+    } else {
+        template_manager.load_template(&name).await?
+    };
 
     println!("{}", format!("Template: {}", template.name).cyan().bold());
     println!("{}", "─".repeat(template.name.len() + 10).cyan());
@@ -907,9 +2900,41 @@ async fn handle_template_show(name: String) -> Result<()> {
     println!("Version: {}", template.version);
     println!("Author: {}", template.author);
     println!("Description: {}", template.description);
+    if !template.inherits.is_empty() {
+        println!(
+            "Extends: {} → {}",
+            template.name,
+            template.inherits.join(" → ")
+        );
+    }
     println!("Platforms: {}", template.platforms.join(", "));
     println!("Tags: {}", template.tags.join(", "));
 
+    let entry = template_manager.catalog_entry(&name, cached).await;
+    if entry.is_none() {
+        if let Some(path) = template_manager.local_template_path(&name) {
+            println!("Source: local file {}", path.display());
+        }
+    }
+    let mut docs_url = template.docs_url.clone();
+    if let Some(metadata) = entry {
+        if template_manager.has_sources() {
+            println!("Source: {}", metadata.source.as_deref().unwrap_or_default());
+        }
+        // Update times are only fetched when ranking by them
+        let mut metadata = vec![metadata];
+        template_manager
+            .resolve_updated_at(&mut metadata, true)
+            .await?;
+        if let Some(updated_at) = metadata[0].updated_at {
+            println!("Updated: {}", timefmt::format_timestamp(updated_at, zone));
+        }
+        docs_url = docs_url.or(metadata[0].docs_url.take());
+    }
+    if let Some(url) = &docs_url {
+        println!("Docs: {}", url);
+    }
+
     if !template.variables.is_empty() {
         println!("\nVariables:");
         for (var_name, var) in &template.variables {
@@ -941,8 +2966,8 @@ async fn handle_template_show(name: String) -> Result<()> {
         if !env.is_empty() {
             println!("Environment:");
             for (key, value) in env {
-                let masked_value = crate::utils::mask_sensitive_env_value(key, value);
-                println!("  {}={}", key, masked_value);
+                let shown = crate::utils::display::display_env_value(key, value, full);
+                println!("  {}={}", key, shown);
             }
         }
     }
@@ -959,35 +2984,71 @@ async fn handle_template_show(name: String) -> Result<()> {
         println!("{}", instructions);
     }
 
+    if open {
+        open_docs_link(&format!("template '{}'", name), docs_url.as_deref())?;
+    }
+
     Ok(())
 }
 
+/// Filters `template search` applies before ranking
+struct TemplateFilters {
+    tag: Option<String>,
+    platform: Option<String>,
+    author: Option<String>,
+    /// Only templates that support the current platform
+    compatible: bool,
+}
+
 async fn handle_template_search(
     term: String,
     rank_by: Option<String>,
-    tag: Option<String>,
-    platform: Option<String>,
+    filters: TemplateFilters,
+    explain: bool,
+    min_score: Option<f32>,
+    github: &ClientOptions,
+    zone: Zone,
 ) -> Result<()> {
-    let template_manager = TemplateManager::new()?;
+    let template_manager = TemplateManager::new(github)?;
     let mut templates = template_manager.list_templates().await?;
 
     // Apply filters
-    if let Some(tag_filter) = tag {
+    if let Some(tag_filter) = filters.tag {
         templates.retain(|t| t.tags.contains(&tag_filter));
     }
 
-    if let Some(platform_filter) = platform {
+    if let Some(platform_filter) = filters.platform {
         templates.retain(|t| t.platforms.contains(&platform_filter));
     }
 
+    if filters.compatible {
+        let platform = crate::utils::platform::os();
+        templates.retain(|t| templates::supports_platform(&t.platforms, &platform));
+    }
+
+    if let Some(author_filter) = filters.author {
+        templates.retain(|t| author_matches(&t.author, &author_filter));
+    }
+
+    // Ranking by recency needs real update times, so look up any the catalog lacks
+    if rank_by.as_deref() == Some("updated") {
+        template_manager
+            .resolve_updated_at(&mut templates, false)
+            .await?;
+    }
+
     // Rank templates
-    let ranked = rank_templates(templates, &term, rank_by.as_deref());
+    let mut ranked = rank_templates(templates, &term, rank_by.as_deref());
+    if let Some(min_score) = min_score {
+        ranked.retain(|(_, ranking)| ranking.score() >= min_score);
+    }
 
     if ranked.is_empty() {
         println!(
             "{}",
             "No templates found matching the search criteria.".yellow()
         );
+        template_manager.print_stale_cache_notice();
         return Ok(());
     }
 
@@ -1002,23 +3063,78 @@ async fn handle_template_search(
             template.category.dimmed()
         );
         println!("  {}", template.description);
+        println!("  Author: {}", template.author);
+        if let (true, Some(source)) = (template_manager.has_sources(), &template.source) {
+            println!("  Source: {}", source);
+        }
         println!(
             "  {} Score: {:.2} | Downloads: {} | Rating: {:.1}★",
             "📊".dimmed(),
-            ranking.relevance_score + ranking.quality_score,
+            ranking.score(),
             ranking.download_count,
             ranking.community_rating
         );
+        if let Some(updated_at) = ranking.last_updated {
+            println!(
+                "  {} Updated: {}",
+                "🕒".dimmed(),
+                timefmt::format_date(updated_at, zone)
+            );
+        }
+        if explain {
+            print_ranking_explanation(ranking);
+        }
     }
+    template_manager.print_stale_cache_notice();
 
     Ok(())
 }
 
-async fn handle_template_refresh(force: bool, clear: bool) -> Result<()> {
-    let template_manager = TemplateManager::new()?;
+/// Break a template's score down into the parts `calculate_ranking` added up
+fn print_ranking_explanation(ranking: &SearchRanking) {
+    let relevance = &ranking.relevance;
+    let quality = &ranking.quality;
+    println!(
+        "    relevance {:.2} = name {:.2} + description {:.2}",
+        relevance.total(),
+        relevance.name,
+        relevance.description
+    );
+    println!(
+        "    quality   {:.2} = category {:.2} + tags {:.2} + platforms {:.2}",
+        quality.total(),
+        quality.category,
+        quality.tags,
+        quality.platforms
+    );
+    println!("    score     {:.2} = relevance + quality", ranking.score());
+    println!(
+        "    {}",
+        "downloads and rating are estimated from quality; no usage data is collected".dimmed()
+    );
+}
+
+async fn handle_template_refresh(
+    force: bool,
+    clear: bool,
+    dry_run: bool,
+    show_changes: bool,
+    github: &ClientOptions,
+) -> Result<()> {
+    let template_manager = TemplateManager::new(github)?;
+
+    if dry_run {
+        template_manager.planned_cache_clear()?.print();
+        return Ok(());
+    }
 
     if clear {
+        // Keep the catalog being cleared away to compare the new one with
+        let previous = template_manager.load_cached_catalog().ok().flatten();
         template_manager.clear_cache()?;
+        if let Some(previous) = previous {
+            template_manager.save_previous_catalog(&previous)?;
+        }
         println!("🗑️  Template cache cleared.");
     }
 
@@ -1028,27 +3144,163 @@ async fn handle_template_refresh(force: bool, clear: bool) -> Result<()> {
         println!("🔄 Refreshing template cache...");
     }
 
+    if template_manager.has_sources() {
+        return refresh_all_sources(&template_manager, show_changes).await;
+    }
+
     match template_manager.refresh_cache().await {
         Ok(()) => {
             println!("✅ Template cache refreshed successfully!");
+            if show_changes {
+                println!();
+                print_catalog_changes(&template_manager)?;
+            }
         }
         Err(e) => {
             eprintln!("{}", GitHubClient::create_github_error_message(&e));
+            crate::notify::report(0, 1);
+        }
+    }
+
+    Ok(())
+}
+
+/// Refresh every template source, reporting how each one went
+///
+/// Fails only if no source could be refreshed.
+async fn refresh_all_sources(template_manager: &TemplateManager, show_changes: bool) -> Result<()> {
+    let results = template_manager.refresh_sources().await;
+    let total = results.len();
+    let mut failed = 0;
+    let mut default_refreshed = false;
+    for (name, result) in &results {
+        match result {
+            Ok(count) => {
+                default_refreshed |= name == templates::DEFAULT_SOURCE;
+                println!("  {} {}: {} template(s)", "✓".green(), name, count);
+            }
+            Err(e) => {
+                failed += 1;
+                println!("  {} {}: {:#}", "✗".red(), name, e);
+            }
         }
     }
+    crate::notify::report(total - failed, failed);
+
+    if failed == total {
+        return Err(anyhow!("No template source could be refreshed"));
+    }
+    if failed == 0 {
+        println!("✅ Template cache refreshed successfully!");
+    } else {
+        println!(
+            "{} Refreshed {} of {} template sources",
+            "⚠".yellow(),
+            total - failed,
+            total
+        );
+    }
+    if show_changes && default_refreshed {
+        println!();
+        print_catalog_changes(template_manager)?;
+    }
+    Ok(())
+}
+
+/// Handle `template changes`: show the last refresh's catalog changes again
+fn handle_template_changes(github: &ClientOptions) -> Result<()> {
+    print_catalog_changes(&TemplateManager::new(github)?)
+}
+
+fn print_catalog_changes(template_manager: &TemplateManager) -> Result<()> {
+    let Some(diff) = template_manager.catalog_changes()? else {
+        println!("No earlier catalog to compare with; changes are shown from the next refresh on.");
+        return Ok(());
+    };
+    if diff.is_empty() {
+        println!("The catalog did not change in the last refresh.");
+        return Ok(());
+    }
 
+    println!("{}", "Catalog changes from the last refresh:".bold());
+    for added in &diff.added {
+        println!(
+            "  {} {} {} ({})",
+            "NEW".green(),
+            added.name.bold(),
+            added.version,
+            added.category
+        );
+    }
+    for (before, after) in &diff.updated {
+        let mut changes = Vec::new();
+        if before.version != after.version {
+            changes.push(format!("{} → {}", before.version, after.version));
+        }
+        if before.category != after.category {
+            changes.push(format!("category {} → {}", before.category, after.category));
+        }
+        println!(
+            "  {} {} {}",
+            "UPDATE".yellow(),
+            after.name.bold(),
+            changes.join(", ")
+        );
+    }
+    for removed in &diff.removed {
+        println!(
+            "  {} {} {}",
+            "REMOVE".red(),
+            removed.name.bold(),
+            removed.version
+        );
+    }
+    println!(
+        "\n{} added, {} updated, {} removed",
+        diff.added.len(),
+        diff.updated.len(),
+        diff.removed.len()
+    );
     Ok(())
 }
 
+/// Where `import` reads a configuration from
+#[derive(Debug, Clone)]
+pub enum ImportSource {
+    File(String),
+    Ssh {
+        from: String,
+        remote_path: Option<String>,
+    },
+}
+
 /// Handle configuration import
 pub async fn handle_import(
-    file: String,
+    source: ImportSource,
     merge: bool,
     replace: bool,
     dry_run: bool,
+    allow_unknown_fields: bool,
+    target: &Target,
     profile: Option<String>,
 ) -> Result<()> {
-    let config = load_config_from_file(&file).await?;
+    let (config, file) = match source {
+        ImportSource::File(file) => (load_config_from_file(&file).await?, file),
+        ImportSource::Ssh { from, remote_path } => {
+            let target = SshTarget::parse(&from)?;
+            let content = remote::read_remote_config(&target, remote_path.as_deref()).await?;
+            let config = parse_config(&content)
+                .map_err(|e| anyhow!("Config on {} is not valid: {}", target.destination, e))?;
+            (config, from)
+        }
+    };
+
+    // Only --replace carries top-level keys over; merging takes servers alone
+    let unknown: Vec<_> = config
+        .unknown_fields()
+        .into_iter()
+        .filter(|field| replace || !matches!(field, UnknownField::TopLevel(_)))
+        .collect();
 
     if dry_run {
         println!("🔍 Would import configuration from: {}", file);
@@ -1061,26 +3313,48 @@ pub async fn handle_import(
             };
             println!("    • {} ({})", name, server_desc);
         }
+        if !unknown.is_empty() {
+            print_unknown_fields(&unknown);
+            if !allow_unknown_fields {
+                println!("  These need confirmation or --allow-unknown-fields to import.");
+            }
+        }
         return Ok(());
     }
 
-    let current_config = Config::load(profile.as_deref()).await.unwrap_or_default();
+    if !unknown.is_empty() && !allow_unknown_fields {
+        print_unknown_fields(&unknown);
+        if !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+            return Err(anyhow!(
+                "{} contains {} unrecognized field(s); re-run with --allow-unknown-fields to import them",
+                file,
+                unknown.len()
+            ));
+        }
+
+        let confirm = Confirm::new("Import these unrecognized fields?")
+            .with_default(false)
+            .prompt()?;
+        if !confirm {
+            println!("Import cancelled.");
+            return Ok(());
+        }
+    }
+
+    let current_config = Config::load(target).await.unwrap_or_default();
 
     if replace {
-        // Replace entire configuration
-        config.save(profile.as_deref()).await?;
+        crate::backup::backup_before_change(target, &current_config).await?;
 
-        // Update profile metadata
-        update_profile_server_count(profile.as_deref()).await?;
+        // Replace entire configuration
+        transaction::save_config(&config, target, profile.as_deref(), None).await?;
 
         println!("✅ Configuration replaced from: {}", file);
     } else if merge {
         // Merge configurations
-        let merged = merge_configs(&current_config, &config)?;
-        merged.save(profile.as_deref()).await?;
-
-        // Update profile metadata
-        update_profile_server_count(profile.as_deref()).await?;
+        let merged = current_config.merged_with(&config);
+        crate::backup::backup_before_change(target, &current_config).await?;
+        transaction::save_config(&merged, target, profile.as_deref(), None).await?;
 
         println!("✅ Configuration merged from: {}", file);
     } else {
@@ -1088,16 +3362,15 @@ pub async fn handle_import(
         println!("Configuration preview from: {}", file);
         println!("Servers to import: {}", config.mcp_servers.len());
 
+        utils::ensure_interactive("Confirmation required; pass --merge or --replace to import")?;
         let confirm = Confirm::new("Import this configuration?")
             .with_default(false)
             .prompt()?;
 
         if confirm {
-            let merged = merge_configs(&current_config, &config)?;
-            merged.save(profile.as_deref()).await?;
-
-            // Update profile metadata
-            update_profile_server_count(profile.as_deref()).await?;
+            let merged = current_config.merged_with(&config);
+            crate::backup::backup_before_change(target, &current_config).await?;
+            transaction::save_config(&merged, target, profile.as_deref(), None).await?;
 
             println!("✅ Configuration imported from: {}", file);
         }
@@ -1106,21 +3379,141 @@ pub async fn handle_import(
     Ok(())
 }
 
+/// List passthrough fields from an import that aren't on the allowlist
+fn print_unknown_fields(unknown: &[UnknownField]) {
+    println!(
+        "  {} Unrecognized fields that would be written to the Claude config:",
+        "⚠".yellow()
+    );
+    for field in unknown {
+        println!("    • {}", field);
+    }
+}
+
+/// Handle `export --to`, overwriting the Claude config on another machine
+pub async fn handle_export_remote(
+    to: String,
+    remote_path: Option<String>,
+    force: bool,
+    include_excluded: bool,
+    target: &Target,
+) -> Result<()> {
+    let remote = SshTarget::parse(&to)?;
+    let mut config = Config::load(target).await?;
+    let withheld = if include_excluded {
+        Vec::new()
+    } else {
+        ServerMetadata::load(target)?.withhold_excluded(&mut config)
+    };
+    let content = export_as_json(&config)?;
+
+    if let Some(note) = crate::metadata::describe_withheld(&withheld) {
+        println!("{}", note.yellow());
+    }
+    if !force {
+        println!(
+            "This will overwrite the Claude config on {} with {} server(s).",
+            remote.destination.bold(),
+            config.mcp_servers.len()
+        );
+        utils::ensure_can_confirm()?;
+        let confirm = Confirm::new("Overwrite the remote configuration?")
+            .with_default(false)
+            .prompt()?;
+        if !confirm {
+            println!("Export cancelled.");
+            return Ok(());
+        }
+    }
+
+    let (written, backup) =
+        remote::write_remote_config(&remote, remote_path.as_deref(), &content).await?;
+    println!(
+        "✅ Configuration exported to {}:{}",
+        remote.destination, written
+    );
+    if let Some(backup) = backup {
+        println!("  The previous remote config was kept as {}", backup);
+    }
+
+    Ok(())
+}
+
 /// Handle configuration export
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_export(
     format: Option<String>,
     template: bool,
+    annotate: bool,
     output: Option<String>,
+    split: bool,
+    output_dir: Option<String>,
+    include_secrets: bool,
+    include_excluded: bool,
+    servers: Selection,
+    target: &Target,
     profile: Option<String>,
+    zone: Zone,
 ) -> Result<()> {
-    let config = Config::load(profile.as_deref()).await?;
+    let mut config = Config::load(target).await?;
+    servers.require(
+        |name| config.mcp_servers.contains_key(name),
+        &crate::bulk::describe_existing_servers(&config),
+    )?;
+    config.mcp_servers.retain(|name, _| servers.contains(name));
+    config
+        .disabled_servers
+        .retain(|name, _| servers.contains(name));
+    let metadata = ServerMetadata::load(target)?;
+    let withheld = if include_excluded {
+        Vec::new()
+    } else {
+        metadata.withhold_excluded(&mut config)
+    };
+    let withheld_note = crate::metadata::describe_withheld(&withheld);
+
+    if split {
+        let yaml = match format.as_deref() {
+            Some("yaml") => true,
+            Some("json") | None => false,
+            Some(f) => return Err(anyhow!("Unsupported format for --split: {}", f)),
+        };
+        let dir = output_dir.ok_or_else(|| anyhow!("--split requires --output-dir <dir>"))?;
+        let index = export_split(&config, Path::new(&dir), yaml, include_secrets)?;
+
+        println!("✅ Exported {} server(s) to: {}", index.servers.len(), dir);
+        if let Some(note) = withheld_note {
+            println!("  {}", note);
+        }
+        if index.secrets_masked {
+            println!(
+                "  Sensitive env values are masked; use --include-secrets for an importable copy."
+            );
+        }
+        return Ok(());
+    }
 
-    let content = if template {
+    let content = if annotate {
+        if template || format.as_deref() != Some("yaml") {
+            return Err(anyhow!("--annotate is only supported with --format yaml"));
+        }
+        export_as_annotated_yaml(&config, &metadata, zone)?
+    } else if template {
         export_as_template(&config)?
     } else {
         match format.as_deref() {
             Some("yaml") => export_as_yaml(&config)?,
             Some("json") | None => export_as_json(&config)?,
+            Some("markdown") => {
+                let profile = crate::profiles::effective_profile(profile.as_deref()).await?;
+                crate::report::render_markdown(
+                    &config,
+                    &metadata,
+                    profile.as_deref(),
+                    chrono::Utc::now(),
+                    zone,
+                )
+            }
             Some(f) => return Err(anyhow!("Unsupported format: {}", f)),
         }
     };
@@ -1128,9 +3521,137 @@ pub async fn handle_export(
     if let Some(output_path) = output {
         std::fs::write(&output_path, content)?;
         println!("✅ Configuration exported to: {}", output_path);
+        if let Some(note) = withheld_note {
+            println!("  {}", note);
+        }
     } else {
         println!("{}", content);
+        // Keep stdout a clean export
+        if let Some(note) = withheld_note {
+            eprintln!("{}", note);
+        }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(server: &McpServer) -> Vec<&str> {
+        server.args.iter().flatten().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn test_secrets_to_prompt_only_when_just_secrets_are_missing() {
+        let template: crate::templates::Template = serde_json::from_value(serde_json::json!({
+            "name": "api",
+            "version": "1.0.0",
+            "description": "API",
+            "author": "Test",
+            "tags": [],
+            "platforms": [],
+            "variables": {
+                "host": { "type": "string", "description": "", "required": true },
+                "region": { "type": "string", "description": "", "required": true, "default": "eu" },
+                "api_key": { "type": "string", "description": "", "required": true },
+                "passphrase": { "type": "string", "description": "", "required": true, "secret": true }
+            },
+            "config": { "command": "api" }
+        }))
+        .unwrap();
+        let given = |names: &[&str]| -> HashMap<String, serde_json::Value> {
+            names.iter().map(|n| (n.to_string(), "x".into())).collect()
+        };
+
+        assert_eq!(
+            secrets_to_prompt(&template, &given(&["host"]), true),
+            ["api_key", "passphrase"]
+        );
+        assert_eq!(
+            secrets_to_prompt(&template, &given(&["host", "api_key"]), true),
+            ["passphrase"]
+        );
+        // A missing non-secret means failing as before, not a partial prompt
+        assert!(secrets_to_prompt(&template, &given(&["api_key"]), true).is_empty());
+        assert!(secrets_to_prompt(&template, &given(&["host"]), false).is_empty());
+        assert!(
+            secrets_to_prompt(&template, &given(&["host", "api_key", "passphrase"]), true)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_resolve_preset_runtimes() {
+        let npx = resolve_preset("npx:@scope/pkg", &[]).unwrap();
+        assert_eq!(npx.command.as_deref(), Some("npx"));
+        assert_eq!(args(&npx), ["-y", "@scope/pkg"]);
+
+        let uvx = resolve_preset("uvx:mcp-server-git", &[]).unwrap();
+        assert_eq!(uvx.command.as_deref(), Some("uvx"));
+        assert_eq!(args(&uvx), ["mcp-server-git"]);
+
+        let docker = resolve_preset("docker:ghcr.io/org/mcp:1.2", &[]).unwrap();
+        assert_eq!(docker.command.as_deref(), Some("docker"));
+        assert_eq!(args(&docker), ["run", "-i", "--rm", "ghcr.io/org/mcp:1.2"]);
+    }
+
+    #[test]
+    fn test_resolve_preset_passes_extra_args_through() {
+        let extra = ["--root", "/srv data", "--", "-v"].map(String::from);
+        let server = resolve_preset("npx:pkg", &extra).unwrap();
+        assert_eq!(
+            args(&server),
+            ["-y", "pkg", "--root", "/srv data", "--", "-v"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_preset_rejects_bad_specs() {
+        for (preset, message) in [
+            ("pkg", "should be <runtime>:<package>"),
+            ("npx:", "missing a package name"),
+            ("pip:requests", "Unknown preset runtime 'pip'"),
+        ] {
+            let error = resolve_preset(preset, &[]).unwrap_err().to_string();
+            assert!(error.contains(message), "{}: {}", preset, error);
+        }
+    }
+
+    #[test]
+    fn test_validate_other_field_key_rejects_typed_and_duplicate_names() {
+        let existing = vec!["headers".to_string()];
+
+        assert!(validate_other_field_key("cwd", &existing).is_ok());
+        for typed in ["command", "autoApprove", " env "] {
+            let err = validate_other_field_key(typed, &existing).unwrap_err();
+            assert!(err.contains("manages itself"), "{}", err);
+        }
+        assert!(validate_other_field_key("headers", &existing)
+            .unwrap_err()
+            .contains("already exists"));
+        assert!(validate_other_field_key("  ", &existing).is_err());
+    }
+
+    #[test]
+    fn test_parse_other_field_value_requires_json() {
+        assert_eq!(
+            parse_other_field_value(" true "),
+            Ok(serde_json::json!(true))
+        );
+        assert_eq!(
+            parse_other_field_value(r#"{"Authorization": "Bearer x"}"#),
+            Ok(serde_json::json!({ "Authorization": "Bearer x" }))
+        );
+        assert_eq!(
+            parse_other_field_value(r#""yes""#),
+            Ok(serde_json::json!("yes"))
+        );
+
+        for input in ["yes", "", "{\"a\": }"] {
+            let err = parse_other_field_value(input).unwrap_err();
+            assert!(err.starts_with("Not valid JSON"), "{}", err);
+        }
+    }
+}
@@ -0,0 +1,770 @@
+use crate::schema::{ArgSchema, CliSchema, CommandSchema};
+use anyhow::{anyhow, Result};
+use clap::Command;
+
+/// Top-level commands whose first argument names a configured server
+const SERVER_COMMANDS: &[&str] = &[
+    "show",
+    "remove",
+    "edit",
+    "update",
+    "disable",
+    "rename",
+    "convert",
+    "validate",
+    "effective-env",
+    "why",
+];
+
+/// Top-level commands whose first argument names a disabled server
+const DISABLED_SERVER_COMMANDS: &[&str] = &["enable"];
+
+/// A command in the tree with the subcommand names leading to it
+struct Node<'a> {
+    path: Vec<&'a str>,
+    command: &'a CommandSchema,
+}
+
+impl Node<'_> {
+    fn key(&self) -> String {
+        self.path.join(" ")
+    }
+
+    /// `list` arguments printing the server names its first argument takes
+    fn server_names(&self) -> Option<&'static str> {
+        match self.path.as_slice() {
+            [name] if SERVER_COMMANDS.contains(name) => Some("list --names-only"),
+            [name] if DISABLED_SERVER_COMMANDS.contains(name) => {
+                Some("list --names-only --disabled-only")
+            }
+            _ => None,
+        }
+    }
+}
+
+fn nodes(schema: &CliSchema) -> Vec<Node<'_>> {
+    fn walk<'a>(path: Vec<&'a str>, command: &'a CommandSchema, nodes: &mut Vec<Node<'a>>) {
+        for sub in &command.subcommands {
+            let mut sub_path = path.clone();
+            sub_path.push(&sub.name);
+            nodes.push(Node {
+                path: sub_path.clone(),
+                command: sub,
+            });
+            walk(sub_path, sub, nodes);
+        }
+    }
+
+    let mut nodes = vec![Node {
+        path: Vec::new(),
+        command: &schema.command,
+    }];
+    walk(Vec::new(), &schema.command, &mut nodes);
+    nodes
+}
+
+/// Flags accepted at a node: its own plus, below the root, the global ones
+fn options<'a>(schema: &'a CliSchema, node: &Node<'a>) -> Vec<&'a ArgSchema> {
+    let inherited = schema
+        .command
+        .args
+        .iter()
+        .filter(|arg| arg.global && !node.path.is_empty());
+    node.command
+        .args
+        .iter()
+        .chain(inherited)
+        .filter(|arg| !arg.positional)
+        .collect()
+}
+
+fn takes_value(arg: &ArgSchema) -> bool {
+    !matches!(arg.value_type, "boolean" | "count")
+}
+
+fn flags(arg: &ArgSchema) -> Vec<String> {
+    arg.long
+        .iter()
+        .map(|long| format!("--{}", long))
+        .chain(arg.short.map(|short| format!("-{}", short)))
+        .collect()
+}
+
+fn first_line(help: Option<&str>) -> &str {
+    help.and_then(|help| help.lines().next())
+        .unwrap_or_default()
+}
+
+/// Render a completion script for `shell`
+pub fn render(schema: &CliSchema, shell: &str) -> Result<String> {
+    match shell {
+        "bash" => Ok(bash(schema)),
+        "zsh" => Ok(zsh(schema)),
+        "fish" => Ok(fish(schema)),
+        "powershell" | "pwsh" => Ok(powershell(schema)),
+        other => Err(anyhow!(
+            "Unsupported shell '{}'. Use bash, zsh, fish or powershell",
+            other
+        )),
+    }
+}
+
+/// Handle `mcp-forge completions`
+pub fn handle_completions(command: Command, shell: &str) -> Result<()> {
+    print!("{}", render(&CliSchema::from_command(&command), shell)?);
+    Ok(())
+}
+
+fn bash(schema: &CliSchema) -> String {
+    let bin = &schema.name;
+    let function = format!("_{}", bin.replace('-', "_"));
+    let nodes = nodes(schema);
+
+    let mut transitions = Vec::new();
+    let mut value_flags = Vec::new();
+    // Flags taking free text, where no completion applies
+    let mut free_values = Vec::new();
+    let mut value_cases = String::new();
+    let mut flag_cases = String::new();
+    let mut word_cases = String::new();
+    for node in &nodes {
+        let key = node.key();
+        for sub in &node.command.subcommands {
+            transitions.push(format!("\"{}/{}\"", key, sub.name));
+        }
+
+        let options = options(schema, node);
+        for arg in options.iter().filter(|arg| takes_value(arg)) {
+            // Global flags can follow any subcommand
+            if arg.global && !node.path.is_empty() {
+                continue;
+            }
+            let patterns: Vec<String> = flags(arg)
+                .iter()
+                .map(|flag| {
+                    if arg.global {
+                        format!("*/{}", flag)
+                    } else {
+                        format!("\"{}/{}\"", key, flag)
+                    }
+                })
+                .collect();
+            value_flags.extend(patterns.iter().cloned());
+
+            let reply = if !arg.possible_values.is_empty() {
+                format!(
+                    "COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+                    arg.possible_values.join(" ")
+                )
+            } else if arg.value_type == "path" {
+                "COMPREPLY=($(compgen -f -- \"$cur\"))".to_string()
+            } else {
+                free_values.extend(patterns);
+                continue;
+            };
+            value_cases.push_str(&format!(
+                "        {})\n            {}\n            return\n            ;;\n",
+                patterns.join("|"),
+                reply
+            ));
+        }
+
+        let words: Vec<String> = options.iter().flat_map(|arg| flags(arg)).collect();
+        flag_cases.push_str(&format!(
+            "            \"{}\") opts=\"{}\" ;;\n",
+            key,
+            words.join(" ")
+        ));
+
+        if !node.command.subcommands.is_empty() {
+            let names: Vec<&str> = node
+                .command
+                .subcommands
+                .iter()
+                .map(|sub| sub.name.as_str())
+                .collect();
+            word_cases.push_str(&format!(
+                "        \"{}\")\n            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n            ;;\n",
+                key,
+                names.join(" ")
+            ));
+        } else if let Some(list) = node.server_names() {
+            word_cases.push_str(&format!(
+                "        \"{}\")\n            if ((positionals == 0)); then\n                COMPREPLY=($(compgen -W \"$({} {} 2>/dev/null)\" -- \"$cur\"))\n            fi\n            ;;\n",
+                key, bin, list
+            ));
+        }
+    }
+
+    format!(
+        r#"# bash completion for {bin}
+# Generated by `{bin} completions bash`
+
+{function}() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}" prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    local cmd="" positionals=0 skip="" opts="" i word
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        word="${{COMP_WORDS[i]}}"
+        if [[ -n "$skip" ]]; then
+            skip=""
+            continue
+        fi
+        case "$cmd/$word" in
+            {transitions})
+                cmd="${{cmd:+$cmd }}$word"
+                positionals=0
+                ;;
+            {value_flags})
+                skip=1
+                ;;
+            */-*) ;;
+            *) ((positionals++)) ;;
+        esac
+    done
+
+    case "$cmd/$prev" in
+{value_cases}        {free_values})
+            return
+            ;;
+    esac
+
+    if [[ "$cur" == -* ]]; then
+        case "$cmd" in
+{flag_cases}        esac
+        COMPREPLY=($(compgen -W "$opts" -- "$cur"))
+        return
+    fi
+
+    case "$cmd" in
+{word_cases}    esac
+}}
+
+complete -o bashdefault -o default -F {function} {bin}
+"#,
+        transitions = transitions.join("|"),
+        value_flags = value_flags.join("|"),
+        free_values = free_values.join("|"),
+    )
+}
+
+fn zsh_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\'', "'\\''")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(':', "\\:")
+}
+
+fn zsh_function(bin: &str, path: &[&str]) -> String {
+    std::iter::once(format!("_{}", bin))
+        .chain(path.iter().map(|name| name.to_string()))
+        .collect::<Vec<_>>()
+        .join("__")
+        .replace('-', "_")
+}
+
+fn zsh_action(arg: &ArgSchema) -> String {
+    if !arg.possible_values.is_empty() {
+        format!("({})", arg.possible_values.join(" "))
+    } else if arg.value_type == "path" {
+        "_files".to_string()
+    } else {
+        "_default".to_string()
+    }
+}
+
+/// `_arguments` specs for a flag, one per spelling
+fn zsh_option_specs(arg: &ArgSchema) -> Vec<String> {
+    let flags = flags(arg);
+    let help = zsh_escape(first_line(arg.help.as_deref()));
+    let repeatable = arg.multiple || arg.value_type == "count";
+    // Spellings of a flag that can't repeat exclude each other
+    let exclusive = if flags.len() > 1 && !repeatable {
+        format!("({})", flags.join(" "))
+    } else {
+        String::new()
+    };
+    flags
+        .iter()
+        .map(|flag| {
+            let repeat = if repeatable { "*" } else { "" };
+            if !takes_value(arg) {
+                return format!("'{}{}{}[{}]'", exclusive, repeat, flag, help);
+            }
+            // `--flag=` and `-f+` also take the value in the same word
+            let separator = if flag.starts_with("--") { "=" } else { "+" };
+            format!(
+                "'{}{}{}{}[{}]:{}:{}'",
+                exclusive,
+                repeat,
+                flag,
+                separator,
+                help,
+                arg.name,
+                zsh_action(arg)
+            )
+        })
+        .collect()
+}
+
+fn zsh(schema: &CliSchema) -> String {
+    let bin = &schema.name;
+    let mut functions = String::new();
+    for node in nodes(schema) {
+        let function = zsh_function(bin, &node.path);
+        let mut specs: Vec<String> = options(schema, &node)
+            .into_iter()
+            .flat_map(zsh_option_specs)
+            .collect();
+
+        let subcommands = &node.command.subcommands;
+        if subcommands.is_empty() {
+            let positionals = node.command.args.iter().filter(|arg| arg.positional);
+            for (index, arg) in positionals.enumerate() {
+                let action = match (index, node.server_names()) {
+                    (0, Some(list)) => format!("_{}_servers {}", bin.replace('-', "_"), list),
+                    _ => zsh_action(arg),
+                };
+                let prefix = match (arg.multiple, arg.required) {
+                    (true, _) => "*:",
+                    (false, true) => ":",
+                    (false, false) => "::",
+                };
+                specs.push(format!("'{}{}:{}'", prefix, arg.name, action));
+            }
+            functions.push_str(&format!(
+                "{}() {{\n    _arguments -s \\\n        {}\n}}\n\n",
+                function,
+                if specs.is_empty() {
+                    "''".to_string()
+                } else {
+                    specs.join(" \\\n        ")
+                }
+            ));
+            continue;
+        }
+
+        specs.push(format!("'1: :{}_commands'", function));
+        specs.push("'*:: :->args'".to_string());
+        let cases: String = subcommands
+            .iter()
+            .map(|sub| {
+                let mut path = node.path.clone();
+                path.push(&sub.name);
+                format!(
+                    "                {}) {} ;;\n",
+                    sub.name,
+                    zsh_function(bin, &path)
+                )
+            })
+            .collect();
+        let descriptions: String = subcommands
+            .iter()
+            .map(|sub| {
+                format!(
+                    "        '{}:{}'\n",
+                    sub.name,
+                    zsh_escape(first_line(sub.about.as_deref()))
+                )
+            })
+            .collect();
+        let title = std::iter::once(bin.as_str())
+            .chain(node.path.iter().copied())
+            .collect::<Vec<_>>()
+            .join(" ");
+        functions.push_str(&format!(
+            r#"{function}() {{
+    local context state state_descr line
+    typeset -A opt_args
+    _arguments -C -s \
+        {specs} && return
+
+    case $state in
+        args)
+            case $line[1] in
+{cases}            esac
+            ;;
+    esac
+}}
+
+{function}_commands() {{
+    local -a commands
+    commands=(
+{descriptions}    )
+    _describe -t commands '{title} command' commands
+}}
+
+"#,
+            specs = specs.join(" \\\n        "),
+        ));
+    }
+
+    let root = zsh_function(bin, &[]);
+    format!(
+        r#"#compdef {bin}
+# Generated by `{bin} completions zsh`
+
+{root}_servers() {{
+    local -a servers
+    servers=(${{(f)"$({bin} "$@" 2>/dev/null)"}})
+    _describe -t servers 'server' servers
+}}
+
+{functions}if [ "$funcstack[1]" = "{root}" ]; then
+    {root} "$@"
+else
+    compdef {root} {bin}
+fi
+"#
+    )
+}
+
+fn fish_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn fish(schema: &CliSchema) -> String {
+    let bin = &schema.name;
+    let prefix = format!("__{}", bin.replace('-', "_"));
+    let nodes = nodes(schema);
+
+    let paths: Vec<String> = nodes
+        .iter()
+        .filter(|node| !node.path.is_empty())
+        .map(|node| fish_quote(&node.key()))
+        .collect();
+
+    let mut lines = Vec::new();
+    for node in &nodes {
+        let condition = fish_quote(format!("{}_at {}", prefix, node.key()).trim_end());
+        for sub in &node.command.subcommands {
+            lines.push(format!(
+                "complete -c {} -n {} -f -a {} -d {}",
+                bin,
+                condition,
+                fish_quote(&sub.name),
+                fish_quote(first_line(sub.about.as_deref()))
+            ));
+        }
+
+        for arg in &node.command.args {
+            if arg.positional {
+                continue;
+            }
+            let mut line = format!("complete -c {}", bin);
+            // Global flags apply after any subcommand
+            if !arg.global {
+                line.push_str(&format!(" -n {}", condition));
+            }
+            if let Some(long) = &arg.long {
+                line.push_str(&format!(" -l {}", long));
+            }
+            if let Some(short) = arg.short {
+                line.push_str(&format!(" -s {}", short));
+            }
+            if !arg.possible_values.is_empty() {
+                line.push_str(&format!(
+                    " -x -a {}",
+                    fish_quote(&arg.possible_values.join(" "))
+                ));
+            } else if arg.value_type == "path" {
+                line.push_str(" -r -F");
+            } else if takes_value(arg) {
+                line.push_str(" -r");
+            }
+            line.push_str(&format!(
+                " -d {}",
+                fish_quote(first_line(arg.help.as_deref()))
+            ));
+            lines.push(line);
+        }
+
+        if let Some(list) = node.server_names() {
+            lines.push(format!(
+                "complete -c {} -n {} -f -a {}",
+                bin,
+                condition,
+                fish_quote(&format!("({} {} 2>/dev/null)", bin, list))
+            ));
+        }
+    }
+
+    format!(
+        r#"# fish completion for {bin}
+# Generated by `{bin} completions fish`
+
+set -g {prefix}_commands {paths}
+
+# The subcommands typed so far, e.g. "template list"
+function {prefix}_path
+    set -l path
+    for token in (commandline -opc)[2..-1]
+        string match -q -- '-*' $token; and continue
+        set -l next (string join ' ' $path $token)
+        if contains -- $next ${prefix}_commands
+            set path $next
+        end
+    end
+    string join ' ' $path
+end
+
+function {prefix}_at
+    set -l path ({prefix}_path)
+    test "$path" = "$argv"
+end
+
+{lines}
+"#,
+        paths = paths.join(" "),
+        lines = lines.join("\n"),
+    )
+}
+
+fn powershell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+fn powershell_result(text: &str, list_item: &str, kind: &str, tooltip: &str) -> String {
+    // PowerShell rejects an empty tooltip
+    let tooltip = if tooltip.is_empty() {
+        list_item
+    } else {
+        tooltip
+    };
+    format!(
+        "[CompletionResult]::new({}, {}, [CompletionResultType]::{}, {})",
+        powershell_quote(text),
+        powershell_quote(list_item),
+        kind,
+        powershell_quote(tooltip)
+    )
+}
+
+fn powershell(schema: &CliSchema) -> String {
+    let bin = &schema.name;
+    let nodes = nodes(schema);
+
+    let paths: Vec<String> = nodes
+        .iter()
+        .filter(|node| !node.path.is_empty())
+        .map(|node| format!("        {}", powershell_quote(&node.key())))
+        .collect();
+
+    let results_for_args = |args: Vec<&ArgSchema>| -> Vec<String> {
+        args.into_iter()
+            .flat_map(|arg| {
+                flags(arg).into_iter().map(move |flag| {
+                    powershell_result(
+                        &flag,
+                        flag.trim_start_matches('-'),
+                        "ParameterName",
+                        first_line(arg.help.as_deref()),
+                    )
+                })
+            })
+            .collect()
+    };
+
+    let globals = results_for_args(
+        schema
+            .command
+            .args
+            .iter()
+            .filter(|arg| arg.global && !arg.positional)
+            .collect(),
+    );
+
+    let mut cases = String::new();
+    for node in &nodes {
+        let mut results: Vec<String> = node
+            .command
+            .subcommands
+            .iter()
+            .map(|sub| {
+                powershell_result(
+                    &sub.name,
+                    &sub.name,
+                    "ParameterValue",
+                    first_line(sub.about.as_deref()),
+                )
+            })
+            .collect();
+        results.extend(results_for_args(
+            node.command
+                .args
+                .iter()
+                .filter(|arg| !arg.positional && !arg.global)
+                .collect(),
+        ));
+        if let Some(list) = node.server_names() {
+            results.push(format!(
+                "& {} {} 2>$null | ForEach-Object {{\n                [CompletionResult]::new($_, $_, [CompletionResultType]::ParameterValue, $_)\n            }}",
+                powershell_quote(bin),
+                list
+            ));
+        }
+        if results.is_empty() {
+            cases.push_str(&format!(
+                "        {} {{ }}\n",
+                powershell_quote(&node.key())
+            ));
+            continue;
+        }
+        cases.push_str(&format!(
+            "        {} {{\n{}\n        }}\n",
+            powershell_quote(&node.key()),
+            results
+                .iter()
+                .map(|result| format!("            {}", result))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ));
+    }
+
+    format!(
+        r#"# PowerShell completion for {bin}
+# Generated by `{bin} completions powershell`
+
+using namespace System.Management.Automation
+
+Register-ArgumentCompleter -Native -CommandName {quoted_bin} -ScriptBlock {{
+    param($wordToComplete, $commandAst, $cursorPosition)
+
+    $commands = @(
+{paths}
+    )
+
+    # The subcommands typed so far, e.g. "template list"
+    $path = ''
+    foreach ($element in $commandAst.CommandElements | Select-Object -Skip 1) {{
+        if ($element.Extent.EndOffset -ge $cursorPosition) {{ break }}
+        $token = $element.ToString()
+        if ($token.StartsWith('-')) {{ continue }}
+        $next = if ($path) {{ "$path $token" }} else {{ $token }}
+        if ($commands -contains $next) {{ $path = $next }}
+    }}
+
+    $completions = @(switch ($path) {{
+{cases}    }})
+    $globals = @(
+{globals}
+    )
+
+    @($completions) + @($globals) |
+        Where-Object {{ $_.CompletionText -like "$wordToComplete*" }} |
+        Sort-Object -Property ListItemText -Unique
+}}
+"#,
+        quoted_bin = powershell_quote(bin),
+        paths = paths.join("\n"),
+        globals = globals
+            .iter()
+            .map(|result| format!("        {}", result))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Arg, ArgAction};
+
+    fn schema() -> CliSchema {
+        let command = Command::new("tool")
+            .arg(
+                Arg::new("profile")
+                    .long("profile")
+                    .global(true)
+                    .help("Profile to use"),
+            )
+            .subcommand(
+                Command::new("show")
+                    .about("Show one server")
+                    .arg(Arg::new("name").required(true))
+                    .arg(
+                        Arg::new("json")
+                            .long("json")
+                            .action(ArgAction::SetTrue)
+                            .help("Output as JSON"),
+                    ),
+            )
+            .subcommand(
+                Command::new("template")
+                    .about("Template operations")
+                    .subcommand(
+                        Command::new("list").arg(
+                            Arg::new("format")
+                                .long("format")
+                                .short('f')
+                                .value_parser(["json", "table"]),
+                        ),
+                    ),
+            );
+        CliSchema::from_command(&command)
+    }
+
+    #[test]
+    fn test_nodes_walk_the_tree_in_order() {
+        let schema = schema();
+        let keys: Vec<String> = nodes(&schema).iter().map(Node::key).collect();
+        assert_eq!(keys, ["", "show", "template", "template list"]);
+    }
+
+    #[test]
+    fn test_options_inherit_global_flags_below_the_root() {
+        let schema = schema();
+        let nodes = nodes(&schema);
+        let names = |node: &Node| -> Vec<String> {
+            options(&schema, node)
+                .iter()
+                .map(|arg| arg.name.clone())
+                .collect()
+        };
+        assert_eq!(names(&nodes[0]), ["profile"]);
+        assert_eq!(names(&nodes[1]), ["json", "profile"]);
+        assert_eq!(names(&nodes[3]), ["format", "profile"]);
+    }
+
+    #[test]
+    fn test_server_commands_complete_server_names() {
+        let schema = schema();
+        let nodes = nodes(&schema);
+        assert_eq!(nodes[1].server_names(), Some("list --names-only"));
+        assert_eq!(nodes[3].server_names(), None);
+
+        for shell in ["bash", "zsh", "fish", "powershell"] {
+            let script = render(&schema, shell).unwrap();
+            assert!(
+                script.contains(" list --names-only"),
+                "{} script doesn't complete server names:\n{}",
+                shell,
+                script
+            );
+        }
+    }
+
+    #[test]
+    fn test_zsh_option_specs() {
+        let schema = schema();
+        let nodes = nodes(&schema);
+        let specs: Vec<String> = options(&schema, &nodes[3])
+            .into_iter()
+            .flat_map(zsh_option_specs)
+            .collect();
+        assert_eq!(
+            specs,
+            [
+                "'(--format -f)--format=[]:format:(json table)'",
+                "'(--format -f)-f+[]:format:(json table)'",
+                "'--profile=[Profile to use]:profile:_default'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_shell_is_rejected() {
+        let err = render(&schema(), "tcsh").unwrap_err();
+        assert!(err.to_string().contains("Unsupported shell 'tcsh'"));
+    }
+}
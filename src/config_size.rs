@@ -0,0 +1,461 @@
+use crate::config::{Config, Target};
+use crate::deletions::format_size;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use serde::Serialize;
+
+/// Saved configs larger than this get a warning by default
+pub const DEFAULT_SIZE_WARNING: u64 = 512 * 1024;
+
+/// How many of the largest servers and env values a warning names
+const WARNING_TOP: usize = 3;
+
+/// How many of the largest env values `stats --size` lists
+const BREAKDOWN_TOP_ENV: usize = 10;
+
+/// Size of one server's entry in the saved file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ServerSize {
+    pub name: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+    pub bytes: u64,
+}
+
+/// Size of one environment value, as written in the file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EnvValueSize {
+    pub server: String,
+    pub key: String,
+    pub bytes: u64,
+}
+
+/// Where the bytes of a serialized config go, largest first
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SizeReport {
+    pub total: u64,
+    pub servers: Vec<ServerSize>,
+    pub env_values: Vec<EnvValueSize>,
+    /// Top-level keys other than the servers, plus the file's own punctuation
+    pub other: u64,
+}
+
+impl SizeReport {
+    /// Serialize the config the way a save does, and each server on its own
+    ///
+    /// A server's size is how much longer the file is with its entry than
+    /// without, indentation included, so the sizes add up to about the total.
+    pub fn measure(config: &Config) -> Result<Self> {
+        let total = pretty_len(&serde_json::to_value(config)?)?;
+
+        let mut servers = Vec::new();
+        let mut env_values = Vec::new();
+        for (key, entries, disabled) in [
+            ("mcpServers", &config.mcp_servers, false),
+            ("disabledServers", &config.disabled_servers, true),
+        ] {
+            // A placeholder sibling makes each entry carry the comma and line
+            // break it has in a full file
+            let empty = pretty_len(&serde_json::json!({ key: { "": 0 } }))?;
+            for (name, server) in entries {
+                let with_server = pretty_len(&serde_json::json!({ key: { name: server, "": 0 } }))?;
+                servers.push(ServerSize {
+                    name: name.clone(),
+                    disabled,
+                    bytes: with_server.saturating_sub(empty),
+                });
+                for (env_key, value) in server.env.iter().flatten() {
+                    env_values.push(EnvValueSize {
+                        server: name.clone(),
+                        key: env_key.clone(),
+                        bytes: serde_json::to_string(value)?.len() as u64,
+                    });
+                }
+            }
+        }
+        servers.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+        env_values.sort_by(|a, b| {
+            b.bytes
+                .cmp(&a.bytes)
+                .then_with(|| a.server.cmp(&b.server))
+                .then_with(|| a.key.cmp(&b.key))
+        });
+
+        let other = total.saturating_sub(servers.iter().map(|s| s.bytes).sum());
+        Ok(Self {
+            total,
+            servers,
+            env_values,
+            other,
+        })
+    }
+
+    /// Share of the total, as a whole percentage
+    fn percent(&self, bytes: u64) -> u64 {
+        (bytes * 100).checked_div(self.total).unwrap_or(0)
+    }
+}
+
+fn pretty_len(value: &serde_json::Value) -> Result<u64> {
+    Ok(serde_json::to_string_pretty(value)?.len() as u64)
+}
+
+/// Parse a size such as `512KB`, `1MB` or `800000` (bytes)
+pub fn parse_size(value: &str) -> Result<u64> {
+    let value = value.trim().to_lowercase();
+    let invalid = || {
+        anyhow!(
+            "Invalid size '{}'. Use a number of bytes, or a number with KB or MB, like 512KB",
+            value
+        )
+    };
+
+    let (number, multiplier) = [
+        ("kb", 1024),
+        ("k", 1024),
+        ("mb", 1024 * 1024),
+        ("m", 1024 * 1024),
+        ("b", 1),
+    ]
+    .iter()
+    .find(|(unit, _)| value.ends_with(unit))
+    .map_or((value.as_str(), 1), |(unit, multiplier)| {
+        (value[..value.len() - unit.len()].trim_end(), *multiplier)
+    });
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let count: u64 = number.parse().map_err(|_| invalid())?;
+    count.checked_mul(multiplier).ok_or_else(invalid)
+}
+
+/// Size above which saves warn, set with `MCP_FORGE_SIZE_WARNING`; `None` when off
+pub fn size_warning() -> Option<u64> {
+    size_warning_from(std::env::var("MCP_FORGE_SIZE_WARNING").ok().as_deref())
+}
+
+fn size_warning_from(setting: Option<&str>) -> Option<u64> {
+    let Some(setting) = setting else {
+        return Some(DEFAULT_SIZE_WARNING);
+    };
+    if setting.trim().eq_ignore_ascii_case("off") {
+        return None;
+    }
+    match parse_size(setting) {
+        Ok(limit) => Some(limit),
+        Err(e) => {
+            eprintln!("⚠ MCP_FORGE_SIZE_WARNING: {}; using 512KB", e);
+            Some(DEFAULT_SIZE_WARNING)
+        }
+    }
+}
+
+/// Warn on stderr when a config about to be saved as `bytes` bytes is larger
+/// than Claude Desktop handles well, naming what takes up the space
+///
+/// Only advisory: the save goes ahead either way.
+pub fn warn_if_large(config: &Config, bytes: usize) {
+    let Some(limit) = size_warning() else {
+        return;
+    };
+    if bytes as u64 <= limit {
+        return;
+    }
+    let Ok(report) = SizeReport::measure(config) else {
+        return;
+    };
+    for line in warning_lines(&report, bytes as u64, limit) {
+        eprintln!("{}", line.yellow());
+    }
+}
+
+fn warning_lines(report: &SizeReport, bytes: u64, limit: u64) -> Vec<String> {
+    let mut lines = vec![format!(
+        "⚠ The saved config is {}, more than the {} Claude Desktop handles well",
+        format_size(bytes),
+        format_size(limit)
+    )];
+    let servers: Vec<String> = report
+        .servers
+        .iter()
+        .take(WARNING_TOP)
+        .map(|s| format!("{} ({})", s.name, format_size(s.bytes)))
+        .collect();
+    if !servers.is_empty() {
+        lines.push(format!("  Largest servers: {}", servers.join(", ")));
+    }
+    let env_values: Vec<String> = report
+        .env_values
+        .iter()
+        .take(WARNING_TOP)
+        .map(|v| format!("{}.{} ({})", v.server, v.key, format_size(v.bytes)))
+        .collect();
+    if !env_values.is_empty() {
+        lines.push(format!("  Largest env values: {}", env_values.join(", ")));
+    }
+    lines.push(
+        "  Run `mcp-forge stats --size` for the full breakdown (set MCP_FORGE_SIZE_WARNING to change the threshold)"
+            .to_string(),
+    );
+    lines
+}
+
+/// Handle `stats`: how many servers the config has and how big it is, with
+/// `size` a breakdown of the bytes by server and env value
+pub async fn handle_stats(size: bool, json: bool, target: &Target) -> Result<()> {
+    let config = Config::load(target).await?;
+    let report = SizeReport::measure(&config)?;
+
+    if json {
+        let output = serde_json::json!({
+            "servers": config.mcp_servers.len(),
+            "disabled_servers": config.disabled_servers.len(),
+            "bytes": report.total,
+            "size": if size { Some(&report) } else { None },
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    println!(
+        "{} server(s), {} disabled, {} serialized",
+        config.mcp_servers.len(),
+        config.disabled_servers.len(),
+        format_size(report.total)
+    );
+    if let Some(limit) = size_warning() {
+        if report.total > limit {
+            println!(
+                "{}",
+                format!(
+                    "⚠ More than the {} Claude Desktop handles well",
+                    format_size(limit)
+                )
+                .yellow()
+            );
+        }
+    }
+    if size {
+        print_breakdown(&report);
+    }
+    Ok(())
+}
+
+fn print_breakdown(report: &SizeReport) {
+    println!();
+    println!("{}", "Servers".bold());
+    if report.servers.is_empty() {
+        println!("  (none)");
+    }
+    let name_width = report
+        .servers
+        .iter()
+        .map(|s| s.name.len())
+        .max()
+        .unwrap_or(0);
+    for server in &report.servers {
+        let disabled = if server.disabled { "  disabled" } else { "" };
+        println!(
+            "  {:<name_width$}  {:>9}  {:>3}%{}",
+            server.name,
+            format_size(server.bytes),
+            report.percent(server.bytes),
+            disabled,
+            name_width = name_width
+        );
+    }
+    println!(
+        "  {:<name_width$}  {:>9}  {:>3}%",
+        "(other)",
+        format_size(report.other),
+        report.percent(report.other),
+        name_width = name_width
+    );
+
+    if report.env_values.is_empty() {
+        return;
+    }
+    println!();
+    println!("{}", "Largest env values".bold());
+    let entries: Vec<(String, u64)> = report
+        .env_values
+        .iter()
+        .take(BREAKDOWN_TOP_ENV)
+        .map(|v| (format!("{}.{}", v.server, v.key), v.bytes))
+        .collect();
+    let width = entries
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    for (name, bytes) in entries {
+        println!(
+            "  {:<width$}  {:>9}  {:>3}%",
+            name,
+            format_size(bytes),
+            report.percent(bytes),
+            width = width
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::McpServer;
+    use std::collections::HashMap;
+
+    fn server(env: &[(&str, String)]) -> McpServer {
+        McpServer {
+            command: Some("npx".to_string()),
+            args: Some(vec!["-y".to_string(), "some-mcp".to_string()]),
+            url: None,
+            env: if env.is_empty() {
+                None
+            } else {
+                Some(
+                    env.iter()
+                        .map(|(k, v)| (k.to_string(), v.clone()))
+                        .collect(),
+                )
+            },
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::new(),
+        }
+    }
+
+    /// Many small servers, one with a large env blob, one disabled and a
+    /// top-level key
+    fn bloated() -> Config {
+        let mut config = Config::default();
+        for i in 0..200 {
+            config
+                .mcp_servers
+                .insert(format!("server-{:03}", i), server(&[]));
+        }
+        config.mcp_servers.insert(
+            "blob".to_string(),
+            server(&[("CERT", "x".repeat(300 * 1024)), ("TOKEN", "t".repeat(40))]),
+        );
+        config.mcp_servers.insert(
+            "medium".to_string(),
+            server(&[("CONFIG", "y".repeat(20 * 1024))]),
+        );
+        config.disabled_servers.insert(
+            "old".to_string(),
+            server(&[("DATA", "z".repeat(50 * 1024))]),
+        );
+        config
+            .other
+            .insert("globalShortcut".to_string(), "Alt+Space".into());
+        config
+    }
+
+    #[test]
+    fn test_measure_ranks_servers_and_env_values() {
+        let config = bloated();
+        let report = SizeReport::measure(&config).unwrap();
+
+        let content = serde_json::to_string_pretty(&config).unwrap();
+        assert_eq!(report.total, content.len() as u64);
+
+        let names: Vec<&str> = report
+            .servers
+            .iter()
+            .take(3)
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(names, ["blob", "old", "medium"]);
+        assert!(report.servers[1].disabled);
+        assert_eq!(report.servers.len(), 203);
+        assert!(report.servers[0].bytes > 300 * 1024);
+        assert!(report.servers[0].bytes < 301 * 1024);
+
+        let env: Vec<(&str, &str)> = report
+            .env_values
+            .iter()
+            .map(|v| (v.server.as_str(), v.key.as_str()))
+            .collect();
+        assert_eq!(
+            env,
+            [
+                ("blob", "CERT"),
+                ("old", "DATA"),
+                ("medium", "CONFIG"),
+                ("blob", "TOKEN")
+            ]
+        );
+        // The quotes are part of the value as written
+        assert_eq!(report.env_values[3].bytes, 42);
+
+        // The servers account for almost everything
+        let servers: u64 = report.servers.iter().map(|s| s.bytes).sum();
+        assert_eq!(servers + report.other, report.total);
+        assert!(report.other < 200, "{}", report.other);
+    }
+
+    #[test]
+    fn test_small_servers_are_the_same_size() {
+        let report = SizeReport::measure(&bloated()).unwrap();
+        let small: Vec<u64> = report
+            .servers
+            .iter()
+            .filter(|s| s.name.starts_with("server-"))
+            .map(|s| s.bytes)
+            .collect();
+        assert!(small.windows(2).all(|pair| pair[0] == pair[1]));
+        // Equal sizes fall back to name order
+        assert_eq!(report.servers[3].name, "server-000");
+    }
+
+    #[test]
+    fn test_empty_config() {
+        let report = SizeReport::measure(&Config::default()).unwrap();
+        assert!(report.servers.is_empty());
+        assert!(report.env_values.is_empty());
+        assert_eq!(report.other, report.total);
+    }
+
+    #[test]
+    fn test_warning_names_the_largest_contributors() {
+        let report = SizeReport::measure(&bloated()).unwrap();
+        let lines = warning_lines(&report, report.total, DEFAULT_SIZE_WARNING);
+
+        assert!(lines[0].contains("more than the 512.0 KB"), "{}", lines[0]);
+        assert!(
+            lines[1].starts_with("  Largest servers: blob (300."),
+            "{}",
+            lines[1]
+        );
+        assert!(lines[1].contains("old (50."), "{}", lines[1]);
+        assert!(
+            lines[2].starts_with("  Largest env values: blob.CERT (300.0 KB)"),
+            "{}",
+            lines[2]
+        );
+        assert!(lines[3].contains("stats --size"));
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("800000").unwrap(), 800_000);
+        assert_eq!(parse_size("512KB").unwrap(), 512 * 1024);
+        assert_eq!(parse_size("512 kb").unwrap(), 512 * 1024);
+        assert_eq!(parse_size("2m").unwrap(), 2 * 1024 * 1024);
+        assert_eq!(parse_size("100B").unwrap(), 100);
+        assert!(parse_size("").is_err());
+        assert!(parse_size("KB").is_err());
+        assert!(parse_size("-1").is_err());
+        assert!(parse_size("1.5MB").is_err());
+        assert!(parse_size("99999999999999999999").is_err());
+    }
+
+    #[test]
+    fn test_size_warning_from_env() {
+        assert_eq!(size_warning_from(None), Some(DEFAULT_SIZE_WARNING));
+        assert_eq!(size_warning_from(Some("1MB")), Some(1024 * 1024));
+        assert_eq!(size_warning_from(Some("off")), None);
+        assert_eq!(size_warning_from(Some("lots")), Some(DEFAULT_SIZE_WARNING));
+    }
+}
@@ -0,0 +1,528 @@
+use crate::config::{Config, McpServer, Target, KNOWN_SERVER_FIELDS, KNOWN_TOP_LEVEL_KEYS};
+use crate::utils;
+use crate::validation::{ValidationIssue, ValidationStatus};
+use anyhow::Result;
+use colored::Colorize;
+use serde::de::{IgnoredAny, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Whole-file validation of the Claude config, grouped into sections
+#[derive(Debug, Serialize)]
+pub struct ConfigReport {
+    pub config_path: String,
+    pub sections: Vec<ReportSection>,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportSection {
+    pub name: String,
+    /// False when an earlier section's errors kept this one from running
+    pub checked: bool,
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ConfigReport {
+    fn new(config_path: String, sections: Vec<ReportSection>) -> Self {
+        let count = |severity: ValidationStatus| {
+            sections
+                .iter()
+                .flat_map(|s| &s.issues)
+                .filter(|issue| issue.severity == severity)
+                .count()
+        };
+        Self {
+            config_path,
+            errors: count(ValidationStatus::Error),
+            warnings: count(ValidationStatus::Warning),
+            sections,
+        }
+    }
+}
+
+fn issue(issue_type: &str, severity: ValidationStatus, message: String) -> ValidationIssue {
+    ValidationIssue {
+        issue_type: issue_type.to_string(),
+        message,
+        severity,
+        fix_suggestion: None,
+        suggested_command: None,
+        log_excerpt: Vec::new(),
+    }
+}
+
+/// Expected JSON type of each field Claude Desktop reads from a server entry
+fn expected_field_type(field: &str) -> Option<&'static str> {
+    match field {
+        "command" | "url" | "cwd" | "type" => Some("string"),
+        "args" | "autoApprove" => Some("array of strings"),
+        "env" | "headers" => Some("object of strings"),
+        "timeout" => Some("integer"),
+        "disabled" => Some("boolean"),
+        _ => None,
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "array of strings" => value
+            .as_array()
+            .is_some_and(|items| items.iter().all(Value::is_string)),
+        "object of strings" => value
+            .as_object()
+            .is_some_and(|map| map.values().all(Value::is_string)),
+        "integer" => value.is_i64(),
+        "boolean" => value.is_boolean(),
+        _ => true,
+    }
+}
+
+/// Check the raw file against the shape of a Claude config
+///
+/// Returns the parsed config when nothing stops it from loading.
+pub fn check_schema(content: &str, issues: &mut Vec<ValidationIssue>) -> Option<Config> {
+    let root: Value = match serde_json::from_str(content) {
+        Ok(root) => root,
+        Err(e) => {
+            issues.push(issue(
+                "schema",
+                ValidationStatus::Error,
+                format!("Not valid JSON: {}", e),
+            ));
+            return None;
+        }
+    };
+    let Some(root) = root.as_object() else {
+        issues.push(issue(
+            "schema",
+            ValidationStatus::Error,
+            "Top level must be a JSON object".to_string(),
+        ));
+        return None;
+    };
+
+    for key in root.keys() {
+        if key != "mcpServers" && !KNOWN_TOP_LEVEL_KEYS.contains(&key.as_str()) {
+            issues.push(issue(
+                "schema",
+                ValidationStatus::Warning,
+                format!("Unknown top-level key '{}'", key),
+            ));
+        }
+    }
+
+    let servers = match root.get("mcpServers") {
+        Some(Value::Object(servers)) => servers,
+        Some(_) => {
+            issues.push(issue(
+                "schema",
+                ValidationStatus::Error,
+                "'mcpServers' must be an object of server entries".to_string(),
+            ));
+            return None;
+        }
+        None => {
+            issues.push(issue(
+                "schema",
+                ValidationStatus::Error,
+                "Missing required key 'mcpServers'".to_string(),
+            ));
+            return None;
+        }
+    };
+
+    let mut names: Vec<&String> = servers.keys().collect();
+    names.sort();
+    for name in names {
+        let Some(server) = servers[name].as_object() else {
+            issues.push(issue(
+                "schema",
+                ValidationStatus::Error,
+                format!("Server '{}' must be an object", name),
+            ));
+            continue;
+        };
+        let mut fields: Vec<(&String, &Value)> = server.iter().collect();
+        fields.sort_by_key(|(field, _)| *field);
+        for (field, value) in fields {
+            match expected_field_type(field) {
+                Some(expected) if !has_type(value, expected) => issues.push(issue(
+                    "schema",
+                    ValidationStatus::Error,
+                    format!(
+                        "Server '{}': '{}' must be {}, found {}",
+                        name,
+                        field,
+                        article(expected),
+                        json_type(value)
+                    ),
+                )),
+                Some(_) => {}
+                None if !KNOWN_SERVER_FIELDS.contains(&field.as_str()) => issues.push(issue(
+                    "schema",
+                    ValidationStatus::Warning,
+                    format!("Server '{}': unknown field '{}'", name, field),
+                )),
+                None => {}
+            }
+        }
+    }
+
+    if issues
+        .iter()
+        .any(|issue| issue.severity == ValidationStatus::Error)
+    {
+        return None;
+    }
+    serde_json::from_str(content).ok()
+}
+
+fn article(expected: &str) -> String {
+    if expected.starts_with(['a', 'e', 'i', 'o', 'u']) {
+        format!("an {}", expected)
+    } else {
+        format!("a {}", expected)
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Server names in the order they appear in the file, repeats included
+///
+/// Deserializing into a map keeps only the last of a repeated key, so the
+/// names are read straight off the JSON.
+struct ServerKeys(Vec<String>);
+
+impl<'de> Deserialize<'de> for ServerKeys {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeysVisitor;
+
+        impl<'de> Visitor<'de> for KeysVisitor {
+            type Value = ServerKeys;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an object of servers")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<ServerKeys, A::Error> {
+                let mut keys = Vec::new();
+                while let Some((key, IgnoredAny)) = map.next_entry::<String, IgnoredAny>()? {
+                    keys.push(key);
+                }
+                Ok(ServerKeys(keys))
+            }
+        }
+
+        deserializer.deserialize_map(KeysVisitor)
+    }
+}
+
+#[derive(Deserialize)]
+struct RawServerKeys {
+    #[serde(rename = "mcpServers")]
+    servers: ServerKeys,
+}
+
+/// Config-wide checks: repeated names, names differing only in case, and
+/// servers that launch the same thing
+pub fn check_duplicates(content: &str, config: &Config, issues: &mut Vec<ValidationIssue>) {
+    if let Ok(RawServerKeys { servers }) = serde_json::from_str(content) {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for key in &servers.0 {
+            *counts.entry(key.as_str()).or_default() += 1;
+        }
+        for (name, count) in counts.into_iter().filter(|(_, count)| *count > 1) {
+            issues.push(issue(
+                "duplicate",
+                ValidationStatus::Error,
+                format!(
+                    "Server '{}' is defined {} times; only the last definition is used",
+                    name, count
+                ),
+            ));
+        }
+    }
+
+    let mut by_lowercase: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+    for name in config.mcp_servers.keys() {
+        by_lowercase
+            .entry(name.to_lowercase())
+            .or_default()
+            .push(name);
+    }
+    for names in by_lowercase.values_mut().filter(|names| names.len() > 1) {
+        names.sort();
+        let names: Vec<&str> = names.iter().map(|n| n.as_str()).collect();
+        issues.push(issue(
+            "conflict",
+            ValidationStatus::Warning,
+            format!("Server names differ only in case: {}", names.join(", ")),
+        ));
+    }
+
+    let mut by_launch: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+    for (name, server) in &config.mcp_servers {
+        if let Some(launch) = launch_key(server) {
+            by_launch.entry(launch).or_default().push(name);
+        }
+    }
+    for (launch, names) in by_launch.iter_mut().filter(|(_, names)| names.len() > 1) {
+        names.sort();
+        let names: Vec<&str> = names.iter().map(|n| n.as_str()).collect();
+        issues.push(issue(
+            "conflict",
+            ValidationStatus::Warning,
+            format!("Servers {} all run {}", names.join(", "), launch),
+        ));
+    }
+}
+
+/// What a server launches or connects to, for spotting copies under other names
+fn launch_key(server: &McpServer) -> Option<String> {
+    match (&server.url, &server.command) {
+        (Some(url), _) => Some(utils::mask_sensitive_url(url)),
+        (None, Some(command)) => {
+            let mut launch = vec![command.as_str()];
+            launch.extend(server.args.iter().flatten().map(String::as_str));
+            Some(format!("'{}'", launch.join(" ")))
+        }
+        (None, None) => None,
+    }
+}
+
+/// Structural checks on each server, without starting anything
+pub fn check_servers(config: &Config, issues: &mut Vec<ValidationIssue>) {
+    let mut names: Vec<&String> = config.mcp_servers.keys().collect();
+    names.sort();
+    for name in names {
+        let server = &config.mcp_servers[name];
+        if let Err(e) = server.validate() {
+            issues.push(issue(
+                "server",
+                ValidationStatus::Error,
+                format!("Server '{}': {}", name, e),
+            ));
+            continue;
+        }
+        if let Some(url) = &server.url {
+            if let Err(e) = utils::validate_server_url(url) {
+                issues.push(issue(
+                    "server",
+                    ValidationStatus::Error,
+                    format!("Server '{}': {}", name, e),
+                ));
+            }
+        }
+        if server
+            .command
+            .as_deref()
+            .is_some_and(|c| c.trim().is_empty())
+        {
+            issues.push(issue(
+                "server",
+                ValidationStatus::Error,
+                format!("Server '{}': command is empty", name),
+            ));
+        }
+    }
+}
+
+/// Run every section over the file's content
+pub fn build_report(config_path: String, content: Option<&str>) -> ConfigReport {
+    let Some(content) = content else {
+        let schema = vec![issue(
+            "schema",
+            ValidationStatus::Warning,
+            "No configuration file yet; run `mcp-forge config init` to create one".to_string(),
+        )];
+        return ConfigReport::new(config_path, sections(schema, None, None));
+    };
+
+    let mut schema = Vec::new();
+    let Some(config) = check_schema(content, &mut schema) else {
+        return ConfigReport::new(config_path, sections(schema, None, None));
+    };
+    let mut duplicates = Vec::new();
+    check_duplicates(content, &config, &mut duplicates);
+    let mut servers = Vec::new();
+    check_servers(&config, &mut servers);
+
+    ConfigReport::new(
+        config_path,
+        sections(schema, Some(duplicates), Some(servers)),
+    )
+}
+
+fn sections(
+    schema: Vec<ValidationIssue>,
+    duplicates: Option<Vec<ValidationIssue>>,
+    servers: Option<Vec<ValidationIssue>>,
+) -> Vec<ReportSection> {
+    let section = |name: &str, issues: Option<Vec<ValidationIssue>>| ReportSection {
+        name: name.to_string(),
+        checked: issues.is_some(),
+        issues: issues.unwrap_or_default(),
+    };
+    vec![
+        section("Schema", Some(schema)),
+        section("Duplicates and conflicts", duplicates),
+        section("Servers", servers),
+    ]
+}
+
+/// Handle `config validate`
+///
+/// Exits with status 1 when any section reports an error.
+pub async fn handle_config_validate(target: &Target, json: bool) -> Result<()> {
+    let path = target.config_path()?;
+    let content = if path.exists() {
+        let content = tokio::fs::read_to_string(&path).await?;
+        Some(crate::config::internal_content(&content, &target.host))
+    } else {
+        None
+    };
+    let report = build_report(path.display().to_string(), content.as_deref());
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        display_report(&report);
+    }
+
+    if report.errors > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn display_report(report: &ConfigReport) {
+    println!("{}", "Configuration Validation".cyan().bold());
+    println!("{}", "────────────────────────".cyan());
+    println!("File: {}", report.config_path.dimmed());
+
+    for section in &report.sections {
+        println!();
+        println!("{}", section.name.bold());
+        if !section.checked {
+            println!("  {}", "skipped: fix the errors above first".dimmed());
+        } else if section.issues.is_empty() {
+            println!("  {} No problems found", "✓".green());
+        }
+        for issue in &section.issues {
+            println!(
+                "  {} {}",
+                issue.severity.symbol().color(issue.severity.color()),
+                issue.message
+            );
+        }
+    }
+
+    println!();
+    let summary = format!(
+        "Summary: {} error(s), {} warning(s)",
+        report.errors, report.warnings
+    );
+    if report.errors > 0 {
+        println!("{}", summary.red().bold());
+    } else if report.warnings > 0 {
+        println!("{}", summary.yellow().bold());
+    } else {
+        println!("{}", summary.green().bold());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(report: &ConfigReport, section: usize) -> Vec<&str> {
+        report.sections[section]
+            .issues
+            .iter()
+            .map(|issue| issue.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_good_config_has_no_issues() {
+        let report = build_report(
+            "c.json".to_string(),
+            Some(
+                r#"{ "mcpServers": {
+                    "fs": { "command": "npx", "args": ["fs"], "env": { "A": "1" }, "disabled": false },
+                    "remote": { "url": "https://example.com/mcp", "headers": { "X": "y" } }
+                }, "globalShortcut": "Ctrl+Space" }"#,
+            ),
+        );
+        assert_eq!((report.errors, report.warnings), (0, 0));
+        assert!(report.sections.iter().all(|s| s.checked));
+    }
+
+    #[test]
+    fn test_schema_errors_skip_later_sections() {
+        let report = build_report(
+            "c.json".to_string(),
+            Some(
+                r#"{ "mcpServers": {
+                    "a": { "command": "npx", "args": "fs", "timeout": "30", "colour": 1 },
+                    "b": []
+                } }"#,
+            ),
+        );
+        assert_eq!(
+            messages(&report, 0),
+            [
+                "Server 'a': 'args' must be an array of strings, found a string",
+                "Server 'a': unknown field 'colour'",
+                "Server 'a': 'timeout' must be an integer, found a string",
+                "Server 'b' must be an object",
+            ]
+        );
+        assert!(!report.sections[1].checked && !report.sections[2].checked);
+
+        let report = build_report("c.json".to_string(), Some("{ nope"));
+        assert!(messages(&report, 0)[0].starts_with("Not valid JSON"));
+    }
+
+    #[test]
+    fn test_duplicates_and_structural_errors() {
+        let report = build_report(
+            "c.json".to_string(),
+            Some(
+                r#"{ "mcpServers": {
+                    "github": { "command": "npx", "args": ["gh"] },
+                    "GitHub": { "command": "npx", "args": ["gh"] },
+                    "github": { "command": "npx", "args": ["gh"] },
+                    "both": { "command": "x", "url": "https://example.com" },
+                    "ftp": { "url": "ftp://example.com" }
+                } }"#,
+            ),
+        );
+        assert_eq!(
+            messages(&report, 1),
+            [
+                "Server 'github' is defined 2 times; only the last definition is used",
+                "Server names differ only in case: GitHub, github",
+                "Servers GitHub, github all run 'npx gh'",
+            ]
+        );
+        assert_eq!(report.sections[2].issues.len(), 2);
+        assert!(messages(&report, 2)[0].starts_with("Server 'both': Server cannot have both"));
+        assert!(messages(&report, 2)[1].starts_with("Server 'ftp': Unsupported URL scheme"));
+        assert_eq!((report.errors, report.warnings), (3, 2));
+    }
+}
@@ -0,0 +1,269 @@
+use crate::cli::show_server_diff;
+use crate::config::{Config, McpServer, Target};
+use crate::transaction;
+use crate::utils;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use std::collections::HashMap;
+
+/// Key URL-type servers use for HTTP headers in Claude's config
+const HEADERS_KEY: &str = "headers";
+
+/// Representation a server is converted to
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConvertTarget {
+    Url { url: String, env_to_headers: bool },
+    Command { command: String, args: Vec<String> },
+}
+
+/// Split `--args` into arguments the way a shell would, so quoted ones keep
+/// their spaces
+fn split_args(args: &str) -> Result<Vec<String>> {
+    shell_words::split(args).map_err(|e| anyhow!("Invalid --args '{}': {}", args, e))
+}
+
+/// Handle convert command
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_convert(
+    name: String,
+    to: String,
+    url: Option<String>,
+    command: Option<String>,
+    args: Option<String>,
+    env_to_headers: bool,
+    dry_run: bool,
+    target: &Target,
+    profile: Option<String>,
+) -> Result<()> {
+    let convert_to = match to.as_str() {
+        "url" => {
+            if command.is_some() || args.is_some() {
+                return Err(anyhow!("--command and --args cannot be used with --to url"));
+            }
+            ConvertTarget::Url {
+                url: url.ok_or_else(|| anyhow!("--to url requires --url <endpoint>"))?,
+                env_to_headers,
+            }
+        }
+        "command" => {
+            if url.is_some() || env_to_headers {
+                return Err(anyhow!(
+                    "--url and --env-to-headers cannot be used with --to command"
+                ));
+            }
+            ConvertTarget::Command {
+                command: command
+                    .ok_or_else(|| anyhow!("--to command requires --command <command>"))?,
+                args: args
+                    .as_deref()
+                    .map(split_args)
+                    .transpose()?
+                    .unwrap_or_default(),
+            }
+        }
+        other => {
+            return Err(anyhow!(
+                "Unsupported server type: {} (expected url or command)",
+                other
+            ))
+        }
+    };
+
+    let mut config = Config::load(target).await?;
+    let server = config
+        .mcp_servers
+        .get(&name)
+        .ok_or_else(|| anyhow!("Server '{}' not found", name))?;
+
+    let converted = convert_server(server, &convert_to)?;
+    show_server_diff(server, &converted, &name).await?;
+
+    if dry_run {
+        println!();
+        println!("Run without --dry-run to apply these changes.");
+        return Ok(());
+    }
+
+    // Create backup before modification
+    crate::backup::backup_before_change(target, &config).await?;
+
+    config.mcp_servers.insert(name.clone(), converted);
+    transaction::save_config(&config, target, profile.as_deref(), None).await?;
+
+    println!(
+        "{}",
+        format!(
+            "✓ Server '{}' converted to a {} server",
+            name,
+            match convert_to {
+                ConvertTarget::Url { .. } => "url",
+                ConvertTarget::Command { .. } => "command",
+            }
+        )
+        .green()
+    );
+
+    Ok(())
+}
+
+/// Swap a server to the target representation, clearing fields that no longer apply
+pub fn convert_server(server: &McpServer, target: &ConvertTarget) -> Result<McpServer> {
+    let mut converted = server.clone();
+
+    match target {
+        ConvertTarget::Url {
+            url,
+            env_to_headers,
+        } => {
+            utils::validate_server_url(url)?;
+            converted.url = Some(url.clone());
+            converted.command = None;
+            converted.args = None;
+
+            if *env_to_headers {
+                if let Some(env) = converted.env.take() {
+                    move_env_to_headers(&mut converted, env)?;
+                }
+            }
+        }
+        ConvertTarget::Command { command, args } => {
+            if command.trim().is_empty() {
+                return Err(anyhow!("Command cannot be empty"));
+            }
+            converted.command = Some(command.clone());
+            converted.args = Some(args.clone());
+            converted.url = None;
+            // Headers are only sent to URL servers
+            converted.other.remove(HEADERS_KEY);
+        }
+    }
+
+    converted.validate()?;
+    Ok(converted)
+}
+
+/// Merge environment variables into the server's HTTP headers
+fn move_env_to_headers(server: &mut McpServer, env: HashMap<String, String>) -> Result<()> {
+    let headers = server
+        .other
+        .entry(HEADERS_KEY.to_string())
+        .or_insert_with(|| serde_json::json!({}));
+    let headers = headers
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("Existing '{}' field is not an object", HEADERS_KEY))?;
+
+    for (key, value) in env {
+        headers.insert(key, serde_json::Value::String(value));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command_server() -> McpServer {
+        McpServer {
+            command: Some("npx".to_string()),
+            args: Some(vec!["-y".to_string(), "weather-mcp".to_string()]),
+            url: None,
+            env: Some(HashMap::from([(
+                "API_KEY".to_string(),
+                "abc123".to_string(),
+            )])),
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::new(),
+        }
+    }
+
+    fn url_server() -> McpServer {
+        McpServer {
+            command: None,
+            args: None,
+            url: Some("https://weather.example.com/sse".to_string()),
+            env: None,
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::from([(
+                HEADERS_KEY.to_string(),
+                serde_json::json!({ "Authorization": "Bearer xyz" }),
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_convert_command_to_url_preserves_env() {
+        let target = ConvertTarget::Url {
+            url: "https://weather.example.com/sse".to_string(),
+            env_to_headers: false,
+        };
+        let converted = convert_server(&command_server(), &target).unwrap();
+
+        assert!(converted.is_url_server());
+        assert_eq!(converted.command, None);
+        assert_eq!(converted.args, None);
+        assert_eq!(converted.env, command_server().env);
+        assert!(!converted.other.contains_key(HEADERS_KEY));
+    }
+
+    #[test]
+    fn test_convert_to_url_maps_env_to_headers() {
+        let mut server = command_server();
+        server.other.insert(
+            HEADERS_KEY.to_string(),
+            serde_json::json!({ "X-Client": "mcp-forge" }),
+        );
+        let target = ConvertTarget::Url {
+            url: "https://weather.example.com/sse".to_string(),
+            env_to_headers: true,
+        };
+        let converted = convert_server(&server, &target).unwrap();
+
+        assert_eq!(converted.env, None);
+        assert_eq!(
+            converted.other[HEADERS_KEY],
+            serde_json::json!({ "X-Client": "mcp-forge", "API_KEY": "abc123" })
+        );
+    }
+
+    #[test]
+    fn test_convert_url_to_command_clears_url_fields() {
+        let target = ConvertTarget::Command {
+            command: "uvx".to_string(),
+            args: vec!["weather-mcp".to_string()],
+        };
+        let converted = convert_server(&url_server(), &target).unwrap();
+
+        assert!(converted.is_command_server());
+        assert_eq!(converted.url, None);
+        assert_eq!(converted.args, Some(vec!["weather-mcp".to_string()]));
+        assert!(!converted.other.contains_key(HEADERS_KEY));
+    }
+
+    #[test]
+    fn test_convert_rejects_invalid_url() {
+        for url in ["not a url", "ftp://weather.example.com", "file:///tmp/sock"] {
+            let target = ConvertTarget::Url {
+                url: url.to_string(),
+                env_to_headers: false,
+            };
+            assert!(
+                convert_server(&command_server(), &target).is_err(),
+                "{}",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_args_keeps_quoted_arguments_together() {
+        assert_eq!(
+            split_args(r#"--name "My Server" -y"#).unwrap(),
+            vec!["--name", "My Server", "-y"]
+        );
+        assert_eq!(split_args("").unwrap(), Vec::<String>::new());
+        assert!(split_args(r#"--name "unterminated"#).is_err());
+    }
+}
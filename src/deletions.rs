@@ -0,0 +1,125 @@
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files and metadata entries a destructive command would remove
+#[derive(Debug, Default)]
+pub struct PlannedDeletions {
+    pub files: Vec<(PathBuf, u64)>,
+    pub entries: Vec<String>,
+}
+
+impl PlannedDeletions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a file, skipping it if it doesn't exist
+    pub fn add_file(&mut self, path: &Path) {
+        if let Ok(metadata) = fs::metadata(path) {
+            if metadata.is_file() {
+                self.files.push((path.to_path_buf(), metadata.len()));
+            }
+        }
+    }
+
+    /// Record every file under a directory
+    pub fn add_dir(&mut self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut paths: Vec<_> = entries.filter_map(|e| e.ok().map(|e| e.path())).collect();
+        paths.sort();
+        for path in paths {
+            if path.is_dir() {
+                self.add_dir(&path);
+            } else {
+                self.add_file(&path);
+            }
+        }
+    }
+
+    /// Record a metadata entry that would be removed
+    pub fn add_entry(&mut self, description: impl Into<String>) {
+        self.entries.push(description.into());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.entries.is_empty()
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|(_, size)| size).sum()
+    }
+
+    /// Print the plan for a dry run
+    pub fn print(&self) {
+        println!("{}", "🔍 Dry run - nothing will be deleted".cyan());
+        if self.is_empty() {
+            println!("  Nothing to delete.");
+            return;
+        }
+
+        if !self.files.is_empty() {
+            println!(
+                "  Files ({}, {}):",
+                self.files.len(),
+                format_size(self.total_size())
+            );
+            for (path, size) in &self.files {
+                println!("    • {} ({})", path.display(), format_size(*size));
+            }
+        }
+        if !self.entries.is_empty() {
+            println!("  Metadata entries:");
+            for entry in &self.entries {
+                println!("    • {}", entry);
+            }
+        }
+    }
+}
+
+/// Format a byte count, e.g. `512 B` or `1.5 KB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    }
+
+    #[test]
+    fn test_add_dir_collects_nested_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("nested")).unwrap();
+        fs::write(dir.path().join("a.json"), "abc").unwrap();
+        fs::write(dir.path().join("nested/b.json"), "hello").unwrap();
+
+        let mut plan = PlannedDeletions::new();
+        plan.add_dir(dir.path());
+        plan.add_file(&dir.path().join("missing.json"));
+
+        assert_eq!(plan.files.len(), 2);
+        assert_eq!(plan.total_size(), 8);
+        assert!(plan.entries.is_empty());
+    }
+}
@@ -0,0 +1,231 @@
+use crate::config::{Config, McpServer, Target};
+use crate::utils;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use std::collections::HashMap;
+
+/// A configured environment variable and how it relates to the host environment
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvEntry {
+    pub key: String,
+    pub value: String,
+    /// Value of the same variable in the current host environment
+    pub host_value: Option<String>,
+    /// `${VAR}` references in the configured value, which are passed through literally
+    pub references: Vec<String>,
+}
+
+impl EnvEntry {
+    /// Whether the configured value hides a host variable of the same name
+    pub fn shadows_host(&self) -> bool {
+        self.host_value.is_some()
+    }
+
+    /// Whether the server sees a different value than the host shell would
+    pub fn differs_from_host(&self) -> bool {
+        self.host_value.as_deref() != Some(self.value.as_str())
+    }
+}
+
+/// Handle effective-env command
+pub async fn handle_effective_env(name: String, diff_host: bool, target: &Target) -> Result<()> {
+    let config = Config::load(target).await?;
+    let server = config
+        .get_server(&name)
+        .ok_or_else(|| anyhow!("Server '{}' not found", name))?;
+
+    let host: HashMap<String, String> = std::env::vars().collect();
+    let entries = resolve_effective_env(server, &host);
+
+    println!(
+        "{} Effective environment for '{}':",
+        "🔎".cyan(),
+        name.bold()
+    );
+    if server.is_url_server() {
+        println!(
+            "  {}",
+            "URL servers are not launched as a process, so env is not passed to them.".yellow()
+        );
+    }
+
+    if entries.is_empty() {
+        println!(
+            "  No environment variables configured; the server inherits the host environment."
+        );
+        return Ok(());
+    }
+
+    if diff_host {
+        print_host_diff(&entries);
+    } else {
+        print_entries(&entries);
+    }
+
+    Ok(())
+}
+
+/// Pair each configured variable with the host value it takes precedence over
+///
+/// Configured values always win over the host environment, and everything not
+/// configured is inherited unchanged. Entries are sorted by key.
+pub fn resolve_effective_env(server: &McpServer, host: &HashMap<String, String>) -> Vec<EnvEntry> {
+    let Some(env) = &server.env else {
+        return Vec::new();
+    };
+
+    let mut entries: Vec<EnvEntry> = env
+        .iter()
+        .map(|(key, value)| EnvEntry {
+            key: key.clone(),
+            value: value.clone(),
+            host_value: host.get(key).cloned(),
+            references: find_references(value),
+        })
+        .collect();
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    entries
+}
+
+/// Find `${VAR}` references in a value
+///
+/// Claude Desktop does not expand these, so the server receives the text verbatim.
+pub fn find_references(value: &str) -> Vec<String> {
+    let mut references = Vec::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        let name = &after[..end];
+        if !name.is_empty() && !references.iter().any(|r| r == name) {
+            references.push(name.to_string());
+        }
+        rest = &after[end + 1..];
+    }
+
+    references
+}
+
+fn print_entries(entries: &[EnvEntry]) {
+    for entry in entries {
+        let masked = utils::mask_sensitive_env_value(&entry.key, &entry.value);
+        if entry.shadows_host() {
+            println!(
+                "  {} = {} {}",
+                entry.key.bold(),
+                masked,
+                "[shadows host]".yellow()
+            );
+        } else {
+            println!("  {} = {}", entry.key.bold(), masked);
+        }
+        print_references(entry);
+    }
+
+    let shadowed = entries.iter().filter(|e| e.shadows_host()).count();
+    println!();
+    println!(
+        "Configured values override the host environment ({} shadowed); other host variables are inherited.",
+        shadowed
+    );
+}
+
+fn print_host_diff(entries: &[EnvEntry]) {
+    let differing: Vec<_> = entries.iter().filter(|e| e.differs_from_host()).collect();
+    if differing.is_empty() {
+        println!("  All configured values match the host environment.");
+        return;
+    }
+
+    for entry in differing {
+        let host = entry
+            .host_value
+            .as_deref()
+            .map(|v| utils::mask_sensitive_env_value(&entry.key, v))
+            .unwrap_or_else(|| "(unset)".to_string());
+        println!(
+            "  {}: host {} → server {}",
+            entry.key.bold(),
+            host.red(),
+            utils::mask_sensitive_env_value(&entry.key, &entry.value).green()
+        );
+        print_references(entry);
+    }
+}
+
+fn print_references(entry: &EnvEntry) {
+    for reference in &entry.references {
+        println!(
+            "    {} ${{{}}} is passed literally, not expanded",
+            "⚠".yellow(),
+            reference
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server_with_env(env: &[(&str, &str)]) -> McpServer {
+        McpServer {
+            command: Some("node".to_string()),
+            args: None,
+            url: None,
+            env: Some(
+                env.iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            ),
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_marks_shadowed_and_differing_keys() {
+        let server = server_with_env(&[("PATH", "/opt/bin"), ("LANG", "C"), ("API_KEY", "k")]);
+        let host = HashMap::from([
+            ("PATH".to_string(), "/usr/bin".to_string()),
+            ("LANG".to_string(), "C".to_string()),
+        ]);
+
+        let entries = resolve_effective_env(&server, &host);
+        let keys: Vec<_> = entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, ["API_KEY", "LANG", "PATH"]);
+
+        let api_key = &entries[0];
+        assert!(!api_key.shadows_host());
+        assert!(api_key.differs_from_host());
+
+        let lang = &entries[1];
+        assert!(lang.shadows_host());
+        assert!(!lang.differs_from_host());
+
+        let path = &entries[2];
+        assert!(path.shadows_host());
+        assert!(path.differs_from_host());
+    }
+
+    #[test]
+    fn test_resolve_without_env() {
+        let mut server = server_with_env(&[]);
+        server.env = None;
+        assert!(resolve_effective_env(&server, &HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_find_references() {
+        assert_eq!(
+            find_references("${HOME}/data:${HOME}/cache:${XDG_DATA_HOME}"),
+            ["HOME", "XDG_DATA_HOME"]
+        );
+        assert!(find_references("$HOME/plain").is_empty());
+        assert!(find_references("${}").is_empty());
+        assert!(find_references("${UNTERMINATED").is_empty());
+    }
+}
@@ -0,0 +1,528 @@
+use anyhow::{anyhow, Result};
+use clap::Command;
+use colored::Colorize;
+
+/// A worked example shown in `--help` and by `mcp-forge examples`
+pub struct Example {
+    /// Subcommand path the example belongs to, e.g. `backup restore`
+    pub command: &'static str,
+    /// What the example does
+    pub description: &'static str,
+    /// Complete command line, ready to paste
+    pub line: &'static str,
+}
+
+/// Every example, grouped by command in help order
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        command: "add",
+        description: "Add a server, prompting for each template variable",
+        line: "mcp-forge add my-docs filesystem",
+    },
+    Example {
+        command: "add",
+        description: "Add a server with its variables on the command line",
+        line: "mcp-forge add my-docs filesystem --vars \"path=/home/user/docs\"",
+    },
+    Example {
+        command: "add",
+        description: "Take list values such as two allowed paths from a YAML file",
+        line: "mcp-forge add projects filesystem --vars-file fs-vars.yaml",
+    },
+    Example {
+        command: "add",
+        description: "See the server a template would produce without saving it",
+        line: "mcp-forge add search brave-search --vars \"api_key=BSA123\" --dry-run",
+    },
+    Example {
+        command: "add",
+        description: "Add a server to Cursor's config instead of Claude Desktop's",
+        line: "mcp-forge --target cursor add fs filesystem --vars \"paths=/tmp\"",
+    },
+    Example {
+        command: "show",
+        description: "Show everything about one server, secrets masked",
+        line: "mcp-forge show github",
+    },
+    Example {
+        command: "remove",
+        description: "Remove one server",
+        line: "mcp-forge remove my-docs",
+    },
+    Example {
+        command: "remove",
+        description: "Remove every server matching a pattern without prompting",
+        line: "mcp-forge remove --pattern \"test-*\" --force",
+    },
+    Example {
+        command: "remove",
+        description: "Preview removing a server and everything that depends on it",
+        line: "mcp-forge remove db --cascade --dry-run",
+    },
+    Example {
+        command: "remove",
+        description: "Remove a server from a script and read the backup name from the JSON result",
+        line: "mcp-forge remove my-docs --force --output json",
+    },
+    Example {
+        command: "disable",
+        description: "Turn a server off without losing its configuration",
+        line: "mcp-forge disable postgres-dev",
+    },
+    Example {
+        command: "enable",
+        description: "Turn it back on next to a server that has since taken its name",
+        line: "mcp-forge enable postgres-dev --rename postgres-dev-old",
+    },
+    Example {
+        command: "rename",
+        description: "Rename a server, keeping its metadata and the links to it",
+        line: "mcp-forge rename postgres db-main",
+    },
+    Example {
+        command: "update",
+        description: "Replace a server's arguments",
+        line: "mcp-forge update my-docs --args \"/home/user/docs,/home/user/notes\"",
+    },
+    Example {
+        command: "update",
+        description: "Set environment variables",
+        line: "mcp-forge update github --set GITHUB_TOKEN=ghp_xxx --set LOG_LEVEL=debug",
+    },
+    Example {
+        command: "update",
+        description: "Let the client call two tools without asking",
+        line: "mcp-forge update my-docs --auto-approve read_file,list_directory",
+    },
+    Example {
+        command: "update",
+        description: "Change a server and print it, as saved, as JSON",
+        line: "mcp-forge update github --set LOG_LEVEL=debug --output json",
+    },
+    Example {
+        command: "upgrade",
+        description: "Rebuild a server from the latest version of its template",
+        line: "mcp-forge upgrade postgres",
+    },
+    Example {
+        command: "upgrade",
+        description: "Preview upgrading every server on an older template version",
+        line: "mcp-forge upgrade --all --dry-run",
+    },
+    Example {
+        command: "stats",
+        description: "Find the servers and env values that make the config large",
+        line: "mcp-forge stats --size",
+    },
+    Example {
+        command: "bulk add",
+        description: "Add every server listed in a file",
+        line: "mcp-forge bulk add --file servers.yaml",
+    },
+    Example {
+        command: "bulk add",
+        description: "Resume a large import where it stopped",
+        line: "mcp-forge bulk add --file servers.yaml --state servers.state.json",
+    },
+    Example {
+        command: "bulk add",
+        description: "Stop at the first failure, keeping the servers added before it",
+        line: "mcp-forge bulk add --file servers.yaml --fail-fast --save-partial",
+    },
+    Example {
+        command: "bulk update",
+        description: "Set a variable on every server with a tag",
+        line: "mcp-forge bulk update --tag database --set LOG_LEVEL=warn",
+    },
+    Example {
+        command: "bulk update",
+        description: "Update exactly the servers listed in a file, one name per line",
+        line: "mcp-forge bulk update --servers-file servers.txt --set LOG_LEVEL=warn",
+    },
+    Example {
+        command: "bulk remove",
+        description: "Preview removing every server matching a pattern",
+        line: "mcp-forge bulk remove --pattern \"tmp-*\" --dry-run",
+    },
+    Example {
+        command: "backup create",
+        description: "Take a named backup before a risky change",
+        line: "mcp-forge backup create --name before-upgrade",
+    },
+    Example {
+        command: "backup list",
+        description: "List the last week's automatic backups, one line each",
+        line: "mcp-forge backup list --since 7d --name-contains config_backup --oneline",
+    },
+    Example {
+        command: "backup search",
+        description: "Find backups that contain a server",
+        line: "mcp-forge backup search github",
+    },
+    Example {
+        command: "backup restore",
+        description: "Preview restoring a whole backup",
+        line: "mcp-forge backup restore before-upgrade --preview",
+    },
+    Example {
+        command: "backup restore",
+        description: "Restore only one server's environment from a backup",
+        line: "mcp-forge backup restore before-upgrade --server github --fields env",
+    },
+    Example {
+        command: "backup clean",
+        description: "See which backups older than a month would be deleted",
+        line: "mcp-forge backup clean --older-than 30d --dry-run",
+    },
+    Example {
+        command: "profile create",
+        description: "Create a profile",
+        line: "mcp-forge profile create work",
+    },
+    Example {
+        command: "profile create",
+        description: "Fork the live setup into a profile to experiment in",
+        line: "mcp-forge profile create experiments --from current",
+    },
+    Example {
+        command: "profile switch",
+        description: "Switch to a profile",
+        line: "mcp-forge profile switch work",
+    },
+    Example {
+        command: "profile sync",
+        description: "Preview copying the main configuration's servers into a profile",
+        line: "mcp-forge profile sync default work --dry-run",
+    },
+    Example {
+        command: "profile",
+        description: "Run any command against a profile without switching to it",
+        line: "mcp-forge list --profile work",
+    },
+    Example {
+        command: "template search",
+        description: "Find database templates",
+        line: "mcp-forge template search sql --tag database",
+    },
+    Example {
+        command: "template repo set",
+        description: "Download templates from your own fork",
+        line: "mcp-forge template repo set my-org/mcp-templates@main",
+    },
+    Example {
+        command: "template source add",
+        description: "List a company's templates after the official ones",
+        line: "mcp-forge template source add company acme/mcp-templates@main",
+    },
+    Example {
+        command: "template outdated",
+        description: "List servers created from an older template version",
+        line: "mcp-forge template outdated --json",
+    },
+    Example {
+        command: "template pin",
+        description: "Keep a server on the template version it was added from",
+        line: "mcp-forge template pin postgres 1.0.0",
+    },
+    Example {
+        command: "template show",
+        description: "Show a private template kept in ~/.config/mcp-forge/templates",
+        line: "mcp-forge template show internal-tools",
+    },
+    Example {
+        command: "template show",
+        description: "Show a template's variables from the local cache",
+        line: "mcp-forge template show postgres --cached",
+    },
+    Example {
+        command: "template refresh",
+        description: "Download the latest templates",
+        line: "mcp-forge template refresh --force",
+    },
+    Example {
+        command: "template refresh",
+        description: "Refresh and list templates added, removed or updated since last time",
+        line: "mcp-forge template refresh --show-changes",
+    },
+    Example {
+        command: "template refresh",
+        description: "Download templates through a proxy for this run",
+        line: "mcp-forge template refresh --proxy http://proxy.example.com:3128",
+    },
+    Example {
+        command: "template create",
+        description: "Turn a configured server into a template, prompting for the rest",
+        line: "mcp-forge template create github --from-server github --dir ./templates",
+    },
+    Example {
+        command: "template validate",
+        description: "Check a template file before publishing it",
+        line: "mcp-forge template validate my-template.json",
+    },
+    Example {
+        command: "template edit",
+        description: "Change a variable's default in a local template file",
+        line: "mcp-forge template edit my-template.json --set-var port.default=5433",
+    },
+    Example {
+        command: "config path",
+        description: "Check which file a portable Claude install's config resolves to",
+        line: "mcp-forge --config ~/PortableClaude/claude_desktop_config.json config path",
+    },
+    Example {
+        command: "config overlay add",
+        description: "Keep your own servers in an overlay over the team's shared config",
+        line: "mcp-forge config overlay add ~/mcp-overlay.json --base ~/team/claude_desktop_config.json",
+    },
+    Example {
+        command: "config materialize",
+        description: "Rewrite the Claude config whenever the base or an overlay is edited",
+        line: "mcp-forge config materialize --watch",
+    },
+    Example {
+        command: "config repair",
+        description: "Preview renaming a misspelled mcp_servers key to mcpServers",
+        line: "mcp-forge config repair --dry-run",
+    },
+    Example {
+        command: "import",
+        description: "Preview merging servers from a file into the configuration",
+        line: "mcp-forge import --file team-servers.json --merge --dry-run",
+    },
+    Example {
+        command: "import",
+        description: "Copy the configuration from another machine",
+        line: "mcp-forge import --from ssh://me@laptop --merge",
+    },
+    Example {
+        command: "export",
+        description: "Export the configuration as YAML",
+        line: "mcp-forge export --format yaml --output servers.yaml",
+    },
+    Example {
+        command: "export",
+        description: "Write one file per server, with secrets masked",
+        line: "mcp-forge export --split --output-dir servers/",
+    },
+    Example {
+        command: "export",
+        description: "Write a Markdown inventory of the servers for a wiki",
+        line: "mcp-forge export --format markdown --output mcp-servers.md",
+    },
+    Example {
+        command: "export",
+        description: "Export just two servers to share with a teammate",
+        line: "mcp-forge export --server github --server postgres --output shared.json",
+    },
+    Example {
+        command: "health",
+        description: "Find servers Claude Desktop failed to start, with the log lines why",
+        line: "mcp-forge health --logs",
+    },
+    Example {
+        command: "completions",
+        description: "Print zsh completion for subcommands, flags and server names",
+        line: "mcp-forge completions zsh",
+    },
+];
+
+/// Examples for a command and its subcommands
+pub fn examples_for(path: &str) -> Vec<&'static Example> {
+    EXAMPLES
+        .iter()
+        .filter(|example| {
+            path.is_empty()
+                || example.command == path
+                || example
+                    .command
+                    .strip_prefix(path)
+                    .is_some_and(|rest| rest.starts_with(' '))
+        })
+        .collect()
+}
+
+/// Examples whose command, description or line contains a term (case-insensitive)
+fn search(examples: Vec<&'static Example>, term: &str) -> Vec<&'static Example> {
+    let term = term.to_lowercase();
+    examples
+        .into_iter()
+        .filter(|example| {
+            [example.command, example.description, example.line]
+                .iter()
+                .any(|text| text.to_lowercase().contains(&term))
+        })
+        .collect()
+}
+
+/// Render examples as a help epilogue
+fn render_help(examples: &[&Example]) -> String {
+    let mut help = String::from("Examples:");
+    for example in examples {
+        help.push_str(&format!(
+            "\n  # {}\n  {}",
+            example.description, example.line
+        ));
+    }
+    help
+}
+
+/// Attach each command's examples to its `--help` output
+pub fn with_examples(command: Command) -> Command {
+    attach(command, "")
+}
+
+fn attach(mut command: Command, path: &str) -> Command {
+    let names: Vec<String> = command
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect();
+    for name in names {
+        let sub_path = if path.is_empty() {
+            name.clone()
+        } else {
+            format!("{} {}", path, name)
+        };
+        command = command.mut_subcommand(&name, |sub| {
+            let examples = examples_for(&sub_path);
+            let sub = if examples.is_empty() {
+                sub
+            } else {
+                sub.after_help(render_help(&examples))
+            };
+            attach(sub, &sub_path)
+        });
+    }
+    command
+}
+
+/// Handle the examples command
+pub fn handle_examples(command: Vec<String>, term: Option<String>, copy: bool) -> Result<()> {
+    let path = command.join(" ");
+    let mut examples = examples_for(&path);
+    if examples.is_empty() && !path.is_empty() {
+        return Err(anyhow!(
+            "No examples for '{}'. Run `mcp-forge examples` to see them all",
+            path
+        ));
+    }
+    if let Some(term) = term {
+        examples = search(examples, &term);
+        if examples.is_empty() {
+            println!("{}", format!("No examples match '{}'", term).yellow());
+            return Ok(());
+        }
+    }
+
+    // Just the command lines, for piping into a clipboard tool
+    if copy {
+        for example in &examples {
+            println!("{}", example.line);
+        }
+        return Ok(());
+    }
+
+    let mut current = "";
+    for example in &examples {
+        if example.command != current {
+            if !current.is_empty() {
+                println!();
+            }
+            println!("{}", example.command.cyan().bold());
+            current = example.command;
+        }
+        println!("  {}", format!("# {}", example.description).dimmed());
+        println!("  {}", example.line);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{CommandFactory, Parser};
+
+    /// Split a command line the way a POSIX shell would for these examples
+    fn shell_words(line: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        let mut word = String::new();
+        let mut quote = None;
+        let mut in_word = false;
+        for c in line.chars() {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), c) => word.push(c),
+                (None, '"' | '\'') => {
+                    quote = Some(c);
+                    in_word = true;
+                }
+                (None, ' ') => {
+                    if in_word {
+                        words.push(std::mem::take(&mut word));
+                        in_word = false;
+                    }
+                }
+                (None, c) => {
+                    word.push(c);
+                    in_word = true;
+                }
+            }
+        }
+        assert!(quote.is_none(), "unterminated quote in: {}", line);
+        if in_word {
+            words.push(word);
+        }
+        words
+    }
+
+    #[test]
+    fn test_every_example_parses() {
+        for example in EXAMPLES {
+            let words = shell_words(example.line);
+            assert_eq!(words[0], "mcp-forge", "{}", example.line);
+            if let Err(e) = crate::Cli::try_parse_from(&words) {
+                panic!("example `{}` doesn't parse:\n{}", example.line, e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_example_belongs_to_a_command() {
+        let root = crate::Cli::command();
+        for example in EXAMPLES {
+            let mut command = &root;
+            for name in example.command.split(' ') {
+                command = command
+                    .find_subcommand(name)
+                    .unwrap_or_else(|| panic!("no command '{}'", example.command));
+            }
+        }
+    }
+
+    #[test]
+    fn test_examples_for_includes_subcommands() {
+        let backup: Vec<&str> = examples_for("backup").iter().map(|e| e.command).collect();
+        assert!(backup.contains(&"backup restore"));
+        assert!(backup.contains(&"backup clean"));
+        assert!(examples_for("back").is_empty());
+        assert_eq!(examples_for("").len(), EXAMPLES.len());
+    }
+
+    #[test]
+    fn test_help_shows_examples() {
+        let mut command = with_examples(crate::Cli::command());
+        let backup = command.find_subcommand_mut("backup").unwrap();
+        let restore = backup.find_subcommand_mut("restore").unwrap();
+        let help = restore.render_long_help().to_string();
+        assert!(help.contains("Examples:\n  # Preview restoring a whole backup"));
+        assert!(help.contains("mcp-forge backup restore before-upgrade --preview"));
+    }
+
+    #[test]
+    fn test_search() {
+        let found = search(examples_for(""), "YAML");
+        assert!(found
+            .iter()
+            .all(|e| { e.line.to_lowercase().contains("yaml") || e.description.contains("YAML") }));
+        assert!(found.iter().any(|e| e.command == "export"));
+    }
+}
@@ -0,0 +1,288 @@
+use crate::config::{Config, McpServer, Target};
+use crate::timefmt::{self, Zone};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const JOURNAL_FILE: &str = "operations.log";
+
+/// Once the journal grows past this it is moved to `operations.log.1`
+const ROTATE_AT_BYTES: u64 = 1024 * 1024;
+
+/// One line of the operations journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub timestamp: DateTime<Utc>,
+    /// Subcommand path, e.g. `profile delete`; arguments are left out as they may hold secrets
+    pub command: String,
+    pub profile: Option<String>,
+    /// Servers added, removed or changed by the command
+    pub servers: Vec<String>,
+    /// Backup file the command created, if any
+    pub backup: Option<String>,
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// State captured before a mutating command, to work out what it touched
+pub struct Snapshot {
+    servers: Option<HashMap<String, McpServer>>,
+    backups: BTreeSet<PathBuf>,
+}
+
+impl Snapshot {
+    pub async fn capture(target: &Target) -> Self {
+        Self {
+            servers: Config::load(target).await.ok().map(|c| c.mcp_servers),
+            backups: list_backups(target),
+        }
+    }
+
+    /// Compare against the current state and build the journal entry
+    pub async fn finish(
+        &self,
+        command: String,
+        target: &Target,
+        profile: Option<&str>,
+        result: &Result<()>,
+    ) -> JournalEntry {
+        let after = Config::load(target).await.ok().map(|c| c.mcp_servers);
+        let servers = match (&self.servers, &after) {
+            (Some(before), Some(after)) => changed_servers(before, after),
+            _ => Vec::new(),
+        };
+        let backup = list_backups(target)
+            .difference(&self.backups)
+            .last()
+            .map(|path| path.display().to_string());
+        let profile = crate::profiles::effective_profile(profile)
+            .await
+            .ok()
+            .flatten();
+
+        JournalEntry {
+            timestamp: Utc::now(),
+            command,
+            profile,
+            servers,
+            backup,
+            success: result.is_ok(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        }
+    }
+}
+
+/// Names of servers that differ between two snapshots, sorted
+fn changed_servers(
+    before: &HashMap<String, McpServer>,
+    after: &HashMap<String, McpServer>,
+) -> Vec<String> {
+    let names: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    names
+        .into_iter()
+        .filter(|name| {
+            let old = before
+                .get(*name)
+                .map(serde_json::to_value)
+                .and_then(Result::ok);
+            let new = after
+                .get(*name)
+                .map(serde_json::to_value)
+                .and_then(Result::ok);
+            old != new
+        })
+        .cloned()
+        .collect()
+}
+
+fn list_backups(target: &Target) -> BTreeSet<PathBuf> {
+    target
+        .backup_dir()
+        .ok()
+        .and_then(|dir| fs::read_dir(dir).ok())
+        .map(|entries| entries.filter_map(|e| e.ok().map(|e| e.path())).collect())
+        .unwrap_or_default()
+}
+
+fn journal_path(target: &Target) -> Result<PathBuf> {
+    Ok(target.state_dir()?.join(JOURNAL_FILE))
+}
+
+fn rotated_path(path: &Path) -> PathBuf {
+    path.with_extension("log.1")
+}
+
+/// Append an entry to the journal
+///
+/// Best-effort: the command has already run, so a journal that can't be
+/// written only gets a warning.
+pub fn record(target: &Target, entry: &JournalEntry) {
+    if let Err(e) = journal_path(target).and_then(|path| append(&path, entry)) {
+        eprintln!(
+            "{}",
+            format!("⚠ Could not write operations log: {}", e).yellow()
+        );
+    }
+}
+
+fn append(path: &Path, entry: &JournalEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::metadata(path).map(|m| m.len()).unwrap_or(0) >= ROTATE_AT_BYTES {
+        fs::rename(path, rotated_path(path))?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+/// Read the journal, oldest first, including the rotated file
+///
+/// Lines that don't parse, e.g. from an interrupted write, are skipped.
+fn read(path: &Path) -> Vec<JournalEntry> {
+    [rotated_path(path), path.to_path_buf()]
+        .iter()
+        .filter_map(|p| fs::read_to_string(p).ok())
+        .flat_map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect::<Vec<JournalEntry>>()
+        })
+        .collect()
+}
+
+/// All journal entries, oldest first
+pub fn entries(target: &Target) -> Result<Vec<JournalEntry>> {
+    Ok(read(&journal_path(target)?))
+}
+
+/// Handle `mcp-forge log`
+pub async fn handle_log(limit: usize, json: bool, target: &Target, zone: Zone) -> Result<()> {
+    let mut entries = entries(target)?;
+    entries.reverse();
+    entries.truncate(limit);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No operations recorded yet.");
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    for entry in &entries {
+        println!("{}", format_entry(entry, now, zone));
+    }
+    Ok(())
+}
+
+fn format_entry(entry: &JournalEntry, now: DateTime<Utc>, zone: Zone) -> String {
+    let status = if entry.success {
+        "✓".green()
+    } else {
+        "✗".red()
+    };
+    let mut line = format!(
+        "{} {} {} ({}, profile: {})",
+        status,
+        entry.command.bold(),
+        timefmt::format_age(entry.timestamp, now),
+        timefmt::format_timestamp(entry.timestamp, zone).dimmed(),
+        entry.profile.as_deref().unwrap_or("default")
+    );
+    if !entry.servers.is_empty() {
+        line.push_str(&format!("\n    Servers: {}", entry.servers.join(", ")));
+    }
+    if let Some(backup) = &entry.backup {
+        line.push_str(&format!("\n    Backup: {}", backup));
+    }
+    if let Some(error) = &entry.error {
+        line.push_str(&format!("\n    Error: {}", error.red()));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(command: &str) -> JournalEntry {
+        JournalEntry {
+            timestamp: DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            command: command.to_string(),
+            profile: None,
+            servers: vec!["github".to_string()],
+            backup: None,
+            success: true,
+            error: None,
+        }
+    }
+
+    fn server(command: &str) -> McpServer {
+        McpServer {
+            command: Some(command.to_string()),
+            args: None,
+            url: None,
+            env: None,
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_changed_servers() {
+        let before = HashMap::from([
+            ("kept".to_string(), server("node")),
+            ("edited".to_string(), server("node")),
+            ("removed".to_string(), server("node")),
+        ]);
+        let after = HashMap::from([
+            ("kept".to_string(), server("node")),
+            ("edited".to_string(), server("python")),
+            ("added".to_string(), server("node")),
+        ]);
+        assert_eq!(
+            changed_servers(&before, &after),
+            ["added", "edited", "removed"]
+        );
+    }
+
+    #[test]
+    fn test_append_rotates_and_reads_both_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(JOURNAL_FILE);
+
+        append(&path, &entry("add")).unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(&vec![b' '; ROTATE_AT_BYTES as usize])
+            .unwrap();
+        append(&path, &entry("remove")).unwrap();
+        fs::write(
+            &path,
+            format!("{}\n{{truncated", fs::read_to_string(&path).unwrap()),
+        )
+        .unwrap();
+
+        assert!(rotated_path(&path).exists());
+        let commands: Vec<String> = read(&path).into_iter().map(|e| e.command).collect();
+        assert_eq!(commands, ["add", "remove"]);
+    }
+}
@@ -0,0 +1,660 @@
+use crate::config::{Config, Host, Target};
+use crate::settings::{settings_path, Settings};
+use crate::transaction::Transaction;
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// Name the base config goes by wherever a layer is named
+pub const BASE: &str = "base";
+
+/// How often `config materialize --watch` looks for changed layers
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A base config plus overlays, merged in order into the Claude config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigLayers {
+    pub base: PathBuf,
+    #[serde(default)]
+    pub overlays: Vec<Overlay>,
+}
+
+/// An overlay under the name it was registered with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Overlay {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl ConfigLayers {
+    /// Layer names, base first, in merge order
+    pub fn names(&self) -> Vec<&str> {
+        std::iter::once(BASE)
+            .chain(self.overlays.iter().map(|o| o.name.as_str()))
+            .collect()
+    }
+
+    /// File of the named layer
+    pub fn path(&self, name: &str) -> Option<&Path> {
+        if name == BASE {
+            return Some(&self.base);
+        }
+        self.overlays
+            .iter()
+            .find(|o| o.name == name)
+            .map(|o| o.path.as_path())
+    }
+}
+
+/// The layer a merged server comes from, and the earlier layers it replaces
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ServerLayer {
+    pub layer: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<String>,
+}
+
+impl std::fmt::Display for ServerLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.layer)?;
+        if !self.overrides.is_empty() {
+            write!(f, " (overrides {})", self.overrides.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Layers merged into one config, with where each server came from
+#[derive(Debug, Clone, Default)]
+pub struct Merged {
+    pub config: Config,
+    pub origins: BTreeMap<String, ServerLayer>,
+}
+
+/// Merge named layers in order, later ones winning
+///
+/// Servers are merged the way `import --merge` does it: a server replaces one
+/// of the same name. A layer that disables a server wins over an earlier one
+/// that has it active, and the other way round. Other top-level keys are
+/// taken from the last layer that sets them.
+pub fn merge(layers: &[(String, Config)]) -> Merged {
+    let mut merged = Merged::default();
+    for (name, layer) in layers {
+        merged.config = merged.config.merged_with(layer);
+        for server in layer.mcp_servers.keys() {
+            merged.config.disabled_servers.remove(server);
+        }
+        for (server, disabled) in &layer.disabled_servers {
+            merged.config.mcp_servers.remove(server);
+            merged
+                .config
+                .disabled_servers
+                .insert(server.clone(), disabled.clone());
+        }
+        merged.config.other.extend(layer.other.clone());
+
+        for server in layer
+            .mcp_servers
+            .keys()
+            .chain(layer.disabled_servers.keys())
+        {
+            let origin = merged
+                .origins
+                .entry(server.clone())
+                .or_insert_with(|| ServerLayer {
+                    layer: name.clone(),
+                    overrides: Vec::new(),
+                });
+            if origin.layer != *name {
+                let earlier = std::mem::replace(&mut origin.layer, name.clone());
+                origin.overrides.push(earlier);
+            }
+        }
+    }
+    merged
+}
+
+/// Whether a command changes a layer rather than reading the merged view,
+/// and the layer changes go to, from `--layer` or the answer to the prompt
+///
+/// Clones share the answer, so a run asks at most once.
+#[derive(Debug, Clone, Default)]
+pub struct LayerChoice {
+    editing: bool,
+    layer: Arc<Mutex<Option<String>>>,
+}
+
+impl LayerChoice {
+    /// With `editing`, `Config::load` returns the layer a change goes to
+    /// instead of the merged view, choosing it with `layer` or by asking
+    pub fn new(editing: bool, layer: Option<String>) -> Self {
+        Self {
+            editing,
+            layer: Arc::new(Mutex::new(layer)),
+        }
+    }
+}
+
+/// The configured layers, if overlays are in use for the target
+///
+/// Overlays are merged into the standard Claude Desktop config only, so
+/// another client's file or one given with `--config` is used as it is.
+pub fn active(target: &Target) -> Result<Option<ConfigLayers>> {
+    if !merges_overlays(target) {
+        return Ok(None);
+    }
+    Ok(Settings::load()?.config_layers)
+}
+
+fn merges_overlays(target: &Target) -> bool {
+    target.host == Host::Claude && target.config_file.is_none()
+}
+
+/// Error for overlay commands run against a config overlays don't apply to
+fn not_merged() -> anyhow::Error {
+    anyhow!(
+        "Overlays are only merged into the standard Claude Desktop config, \
+         not another client's or one given with --config"
+    )
+}
+
+/// What `Config::load` reads while overlays are configured: the merged
+/// view, or the layer a change goes to
+pub fn load(target: &Target, layers: &ConfigLayers) -> Result<Config> {
+    if target.layer.editing {
+        let name = chosen_layer(target, layers)?;
+        return read_layer(target, &name, layers.path(&name).unwrap_or(&layers.base));
+    }
+    Ok(merge(&read_layers(target, layers)?).config)
+}
+
+/// Queue `config` as the new content of the chosen layer, and the Claude
+/// config as the merge of every layer with it
+pub fn stage(
+    config: &Config,
+    target: &Target,
+    layers: &ConfigLayers,
+    transaction: &mut Transaction,
+) -> Result<()> {
+    let layer_name = chosen_layer(target, layers)?;
+    let mut all = read_layers(target, layers)?;
+    if let Some((_, layer)) = all.iter_mut().find(|(name, _)| *name == layer_name) {
+        *layer = config.clone();
+    }
+    let path = layers.path(&layer_name).unwrap_or(&layers.base);
+    transaction.write(path.to_path_buf(), to_json(config)?);
+    let merged = merge(&all).config;
+    let content = to_json(&merged)?;
+    crate::config_size::warn_if_large(&merged, content.len());
+    transaction.write(target.config_path()?, content);
+    Ok(())
+}
+
+/// The layer a change goes to, asking once if `--layer` wasn't given
+fn chosen_layer(target: &Target, layers: &ConfigLayers) -> Result<String> {
+    let mut layer = target.layer.layer.lock().unwrap();
+    let names = layers.names();
+    if let Some(name) = layer.as_deref() {
+        if !names.contains(&name) {
+            return Err(anyhow!(
+                "Unknown layer '{}'; layers: {}",
+                name,
+                names.join(", ")
+            ));
+        }
+        return Ok(name.to_string());
+    }
+
+    if !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+        return Err(anyhow!(
+            "Overlays are configured, so say which layer to change with --layer ({})",
+            names.join(", ")
+        ));
+    }
+    let chosen = inquire::Select::new("Which layer should this change go to?", names)
+        .prompt()?
+        .to_string();
+    *layer = Some(chosen.clone());
+    Ok(chosen)
+}
+
+/// Read every layer, base first
+fn read_layers(target: &Target, layers: &ConfigLayers) -> Result<Vec<(String, Config)>> {
+    layers
+        .names()
+        .into_iter()
+        .map(|name| {
+            let path = layers.path(name).unwrap_or(&layers.base);
+            Ok((name.to_string(), read_layer(target, name, path)?))
+        })
+        .collect()
+}
+
+fn read_layer(target: &Target, name: &str, path: &Path) -> Result<Config> {
+    if !path.exists() {
+        let fix = if name == BASE {
+            "restore it".to_string()
+        } else {
+            format!(
+                "restore it or run `mcp-forge config overlay remove {}`",
+                name
+            )
+        };
+        return Err(anyhow!(
+            "Layer '{}' is missing: {}; {}",
+            name,
+            path.display(),
+            fix
+        ));
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read layer '{}': {}", name, path.display()))?;
+    Config::parse(&content, path, target)
+}
+
+fn to_json(config: &Config) -> Result<String> {
+    serde_json::to_string_pretty(config).context("Failed to serialize configuration")
+}
+
+/// Where each server in the merged view comes from, if overlays are configured
+pub fn origins(target: &Target) -> Result<Option<BTreeMap<String, ServerLayer>>> {
+    match active(target)? {
+        Some(layers) => Ok(Some(merge(&read_layers(target, &layers)?).origins)),
+        None => Ok(None),
+    }
+}
+
+/// Check a name for `config overlay add`: short, path-safe, and not `base`
+fn check_overlay_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(anyhow!(
+            "Invalid overlay name '{}'; use letters, digits, '-' and '_', or pass --name",
+            name
+        ));
+    }
+    if name == BASE {
+        return Err(anyhow!(
+            "'{}' is the base config; choose another overlay name",
+            BASE
+        ));
+    }
+    Ok(())
+}
+
+/// Merge every layer and write the result to the Claude config
+fn materialize(target: &Target, layers: &ConfigLayers) -> Result<Merged> {
+    let merged = merge(&read_layers(target, layers)?);
+    let mut transaction = Transaction::new(target)?;
+    transaction.write(target.config_path()?, to_json(&merged.config)?);
+    transaction.commit()?;
+    Ok(merged)
+}
+
+fn print_materialized(target: &Target, layers: &ConfigLayers, merged: &Merged) -> Result<()> {
+    println!(
+        "{}",
+        format!(
+            "✓ Wrote {} server(s) from base + {} overlay(s) to {}",
+            merged.config.mcp_servers.len(),
+            layers.overlays.len(),
+            target.config_path()?.display()
+        )
+        .green()
+    );
+    for (server, origin) in &merged.origins {
+        if !origin.overrides.is_empty() {
+            println!("  {}: {}", server, origin);
+        }
+    }
+    Ok(())
+}
+
+fn require_layers(target: &Target) -> Result<ConfigLayers> {
+    if !merges_overlays(target) {
+        return Err(not_merged());
+    }
+    Settings::load()?.config_layers.ok_or_else(|| {
+        anyhow!("No overlays configured; add one with `mcp-forge config overlay add <file>`")
+    })
+}
+
+/// Handle `config overlay add`: register an overlay after the existing ones
+/// and rewrite the Claude config with it
+///
+/// The first overlay also sets the base: `--base`, or else a copy of the
+/// current Claude config kept next to the settings.
+pub fn handle_overlay_add(
+    target: &Target,
+    file: &Path,
+    name: Option<String>,
+    base: Option<PathBuf>,
+) -> Result<()> {
+    if !merges_overlays(target) {
+        return Err(not_merged());
+    }
+    let name = match name {
+        Some(name) => name,
+        None => file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    };
+    check_overlay_name(&name)?;
+    let path = std::env::current_dir()?.join(file);
+    let config_path = target.config_path()?;
+    if path == config_path {
+        return Err(anyhow!(
+            "{} is the Claude config itself; an overlay must be a separate file",
+            path.display()
+        ));
+    }
+
+    let mut settings = Settings::load()?;
+    let mut layers = match (settings.config_layers.take(), base) {
+        (Some(_), Some(_)) => {
+            return Err(anyhow!(
+                "A base is already configured; --base only applies to the first overlay"
+            ))
+        }
+        (Some(layers), None) => layers,
+        (None, Some(base)) => ConfigLayers {
+            base: std::env::current_dir()?.join(base),
+            overlays: Vec::new(),
+        },
+        (None, None) => {
+            let base = settings_path()?.with_file_name("base.json");
+            let current = if config_path.exists() {
+                std::fs::read_to_string(&config_path).with_context(|| {
+                    format!("Failed to read config file: {}", config_path.display())
+                })?
+            } else {
+                to_json(&Config::default())?
+            };
+            let mut transaction = Transaction::new(target)?;
+            transaction.write(base.clone(), current);
+            transaction.commit()?;
+            println!(
+                "Copied {} to {} as the base",
+                config_path.display(),
+                base.display()
+            );
+            ConfigLayers {
+                base,
+                overlays: Vec::new(),
+            }
+        }
+    };
+    if layers.overlays.iter().any(|o| o.name == name) {
+        return Err(anyhow!(
+            "Overlay '{}' already exists; remove it first, or pass --name",
+            name
+        ));
+    }
+    if layers.base == path || layers.overlays.iter().any(|o| o.path == path) {
+        return Err(anyhow!("{} is already a layer", path.display()));
+    }
+    if !path.exists() {
+        let mut transaction = Transaction::new(target)?;
+        transaction.write(path.clone(), to_json(&Config::default())?);
+        transaction.commit()?;
+        println!("Created an empty overlay at {}", path.display());
+    }
+
+    layers.overlays.push(Overlay {
+        name: name.clone(),
+        path: path.clone(),
+    });
+    let merged = materialize(target, &layers)?;
+    settings.config_layers = Some(layers.clone());
+    settings.save()?;
+
+    println!(
+        "{}",
+        format!("✓ Added overlay '{}' ({})", name, path.display()).green()
+    );
+    print_materialized(target, &layers, &merged)
+}
+
+/// Handle `config overlay remove`: forget an overlay, leaving its file, and
+/// rewrite the Claude config without it
+pub fn handle_overlay_remove(target: &Target, name: &str) -> Result<()> {
+    let mut layers = require_layers(target)?;
+    let Some(index) = layers.overlays.iter().position(|o| o.name == name) else {
+        return Err(anyhow!(
+            "Unknown overlay '{}'; overlays: {}",
+            name,
+            layers.names()[1..].join(", ")
+        ));
+    };
+    let removed = layers.overlays.remove(index);
+    let merged = materialize(target, &layers)?;
+
+    let mut settings = Settings::load()?;
+    settings.config_layers = if layers.overlays.is_empty() {
+        None
+    } else {
+        Some(layers.clone())
+    };
+    settings.save()?;
+
+    println!(
+        "{}",
+        format!(
+            "✓ Removed overlay '{}' ({} is left as it was)",
+            name,
+            removed.path.display()
+        )
+        .green()
+    );
+    print_materialized(target, &layers, &merged)?;
+    if layers.overlays.is_empty() {
+        println!(
+            "No overlays left; commands change {} directly again",
+            target.config_path()?.display()
+        );
+    }
+    Ok(())
+}
+
+/// Handle `config overlay list`: the layers in merge order
+pub fn handle_overlay_list(target: &Target) -> Result<()> {
+    let Some(layers) = active(target)? else {
+        println!("{}", "No overlays configured.".yellow());
+        println!("Add one with: mcp-forge config overlay add <file>");
+        return Ok(());
+    };
+    let width = layers.names().iter().map(|n| n.len()).max().unwrap_or(0);
+    for name in layers.names() {
+        let path = layers.path(name).unwrap_or(&layers.base);
+        println!("  {:<width$}  {}", name, path.display(), width = width);
+    }
+    println!(
+        "Merged in this order into {}",
+        target.config_path()?.display()
+    );
+    Ok(())
+}
+
+/// Handle `config materialize`: write the merged layers to the Claude config,
+/// and with `watch` keep doing so whenever a layer changes
+pub async fn handle_materialize(target: &Target, watch: bool) -> Result<()> {
+    let layers = require_layers(target)?;
+    if !watch {
+        let merged = materialize(target, &layers)?;
+        return print_materialized(target, &layers, &merged);
+    }
+
+    // Each regeneration takes the config lock only while it writes, so other
+    // commands can run in between
+    let mut seen = None;
+    println!(
+        "Watching {} layer(s); press Ctrl-C to stop",
+        layers.names().len()
+    );
+    loop {
+        let layers = require_layers(target)?;
+        let stamps = modified_times(&layers);
+        if seen.as_ref() != Some(&stamps) {
+            crate::lock::acquire(target, false)?;
+            match materialize(target, &layers) {
+                Ok(merged) => print_materialized(target, &layers, &merged)?,
+                Err(e) => eprintln!("{}", format!("✗ {:#}", e).red()),
+            }
+            crate::lock::release();
+            seen = Some(stamps);
+        }
+        tokio::time::sleep(WATCH_INTERVAL).await;
+    }
+}
+
+/// Each layer's file and when it last changed, to notice edits
+fn modified_times(layers: &ConfigLayers) -> Vec<(PathBuf, Option<SystemTime>)> {
+    layers
+        .names()
+        .into_iter()
+        .filter_map(|name| layers.path(name))
+        .map(|path| {
+            let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+            (path.to_path_buf(), modified)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::McpServer;
+
+    fn server(command: &str) -> McpServer {
+        serde_json::from_value(serde_json::json!({ "command": command })).unwrap()
+    }
+
+    fn layer(active: &[(&str, &str)], disabled: &[(&str, &str)]) -> Config {
+        Config {
+            mcp_servers: active
+                .iter()
+                .map(|(name, command)| (name.to_string(), server(command)))
+                .collect(),
+            disabled_servers: disabled
+                .iter()
+                .map(|(name, command)| (name.to_string(), server(command)))
+                .collect(),
+            other: Default::default(),
+        }
+    }
+
+    fn command(config: &Config, name: &str) -> Option<String> {
+        config.mcp_servers.get(name).and_then(|s| s.command.clone())
+    }
+
+    #[test]
+    fn test_later_layers_win_and_are_labeled() {
+        let merged = merge(&[
+            (
+                BASE.to_string(),
+                layer(&[("files", "base-files"), ("git", "git-mcp")], &[]),
+            ),
+            ("team".to_string(), layer(&[("files", "team-files")], &[])),
+            (
+                "mine".to_string(),
+                layer(&[("files", "my-files"), ("notes", "notes-mcp")], &[]),
+            ),
+        ]);
+
+        assert_eq!(
+            command(&merged.config, "files").as_deref(),
+            Some("my-files")
+        );
+        assert_eq!(command(&merged.config, "git").as_deref(), Some("git-mcp"));
+        assert_eq!(
+            command(&merged.config, "notes").as_deref(),
+            Some("notes-mcp")
+        );
+
+        let files = &merged.origins["files"];
+        assert_eq!(files.layer, "mine");
+        assert_eq!(files.overrides, vec!["base", "team"]);
+        assert_eq!(files.to_string(), "mine (overrides base, team)");
+        assert_eq!(merged.origins["git"].to_string(), "base");
+        assert_eq!(merged.origins["notes"].to_string(), "mine");
+    }
+
+    #[test]
+    fn test_overlays_can_disable_and_enable_base_servers() {
+        let merged = merge(&[
+            (
+                BASE.to_string(),
+                layer(&[("files", "files-mcp")], &[("old", "old-mcp")]),
+            ),
+            (
+                "mine".to_string(),
+                layer(&[("old", "new-old-mcp")], &[("files", "files-mcp")]),
+            ),
+        ]);
+
+        assert!(!merged.config.mcp_servers.contains_key("files"));
+        assert!(merged.config.disabled_servers.contains_key("files"));
+        assert_eq!(
+            command(&merged.config, "old").as_deref(),
+            Some("new-old-mcp")
+        );
+        assert!(!merged.config.disabled_servers.contains_key("old"));
+        assert_eq!(merged.origins["files"].to_string(), "mine (overrides base)");
+    }
+
+    #[test]
+    fn test_top_level_keys_come_from_the_last_layer_setting_them() {
+        let mut base = layer(&[], &[]);
+        base.other
+            .insert("globalShortcut".into(), "Alt+Space".into());
+        base.other.insert("theme".into(), "dark".into());
+        let mut mine = layer(&[], &[]);
+        mine.other
+            .insert("globalShortcut".into(), "Ctrl+Space".into());
+
+        let merged = merge(&[(BASE.to_string(), base), ("mine".to_string(), mine)]);
+        assert_eq!(merged.config.other["globalShortcut"], "Ctrl+Space");
+        assert_eq!(merged.config.other["theme"], "dark");
+    }
+
+    #[test]
+    fn test_a_layer_repeating_a_server_does_not_override_itself() {
+        let merged = merge(&[(
+            BASE.to_string(),
+            layer(&[("files", "a")], &[("files", "b")]),
+        )]);
+        assert_eq!(merged.origins["files"].layer, "base");
+        assert!(merged.origins["files"].overrides.is_empty());
+    }
+
+    #[test]
+    fn test_layer_names_and_paths() {
+        let layers = ConfigLayers {
+            base: PathBuf::from("/base.json"),
+            overlays: vec![Overlay {
+                name: "mine".to_string(),
+                path: PathBuf::from("/mine.json"),
+            }],
+        };
+        assert_eq!(layers.names(), vec!["base", "mine"]);
+        assert_eq!(layers.path("mine"), Some(Path::new("/mine.json")));
+        assert_eq!(layers.path("base"), Some(Path::new("/base.json")));
+        assert_eq!(layers.path("other"), None);
+    }
+
+    #[test]
+    fn test_check_overlay_name() {
+        assert!(check_overlay_name("my-laptop_2").is_ok());
+        assert!(check_overlay_name("base").is_err());
+        assert!(check_overlay_name("").is_err());
+        assert!(check_overlay_name("a/b").is_err());
+    }
+}
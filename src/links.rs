@@ -0,0 +1,278 @@
+use crate::config::{Config, Target};
+use crate::metadata::ServerMetadata;
+use anyhow::{anyhow, Result};
+use clap::Subcommand;
+use colored::Colorize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+#[derive(Subcommand)]
+pub enum LinkCommands {
+    /// Declare the servers a server depends on, replacing any previous list
+    Set {
+        /// Server name
+        server: String,
+        /// Server it depends on (repeatable or comma-separated)
+        #[arg(long, value_delimiter = ',', required = true)]
+        depends_on: Vec<String>,
+    },
+    /// Remove all of a server's dependencies
+    Clear {
+        /// Server name
+        server: String,
+    },
+}
+
+/// Handle link command routing
+pub async fn handle_link_command(action: LinkCommands, target: &Target) -> Result<()> {
+    let config = Config::load(target).await?;
+    let mut metadata = ServerMetadata::load(target)?;
+
+    match action {
+        LinkCommands::Set { server, depends_on } => {
+            let mut missing: Vec<&str> = std::iter::once(&server)
+                .chain(&depends_on)
+                .filter(|name| !config.mcp_servers.contains_key(*name))
+                .map(String::as_str)
+                .collect();
+            missing.dedup();
+            if !missing.is_empty() {
+                return Err(anyhow!(
+                    "Server(s) not found: {}. {}",
+                    missing.join(", "),
+                    crate::bulk::describe_existing_servers(&config)
+                ));
+            }
+
+            let mut depends_on = depends_on;
+            depends_on.sort();
+            depends_on.dedup();
+            if let Some(cycle) = find_cycle(&metadata.depends_on, &server, &depends_on) {
+                return Err(anyhow!(
+                    "Linking would create a dependency cycle: {}",
+                    cycle.join(" → ")
+                ));
+            }
+
+            println!(
+                "{}",
+                format!("✓ {} depends on {}", server, depends_on.join(", ")).green()
+            );
+            metadata.depends_on.insert(server, depends_on);
+        }
+        LinkCommands::Clear { server } => {
+            if metadata.depends_on.remove(&server).is_none() {
+                println!(
+                    "{}",
+                    format!("'{}' has no dependencies; nothing to clear.", server).yellow()
+                );
+                return Ok(());
+            }
+            println!(
+                "{}",
+                format!("✓ Cleared dependencies of {}", server).green()
+            );
+        }
+    }
+
+    metadata.save(target)
+}
+
+/// The cycle that giving `server` these dependencies would create, if any
+///
+/// Returned as the path from `server` back to itself, e.g. `[a, b, a]`.
+pub fn find_cycle(
+    links: &HashMap<String, Vec<String>>,
+    server: &str,
+    depends_on: &[String],
+) -> Option<Vec<String>> {
+    fn walk(
+        links: &HashMap<String, Vec<String>>,
+        target: &str,
+        path: &mut Vec<String>,
+        visited: &mut HashSet<String>,
+    ) -> bool {
+        let node = path.last().cloned().unwrap_or_default();
+        if node == target {
+            return true;
+        }
+        if !visited.insert(node.clone()) {
+            return false;
+        }
+        for next in links.get(&node).into_iter().flatten() {
+            path.push(next.clone());
+            if walk(links, target, path, visited) {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+
+    let mut links = links.clone();
+    links.insert(server.to_string(), depends_on.to_vec());
+
+    let mut visited = HashSet::new();
+    for dependency in depends_on {
+        let mut path = vec![server.to_string(), dependency.clone()];
+        if walk(&links, server, &mut path, &mut visited) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// Servers that depend on any of `names`, directly or through other servers, sorted
+///
+/// The servers in `names` themselves are left out.
+pub fn transitive_dependents(metadata: &ServerMetadata, names: &[String]) -> Vec<String> {
+    let mut found = BTreeSet::new();
+    let mut queue: Vec<String> = names.to_vec();
+    while let Some(name) = queue.pop() {
+        for dependent in metadata.dependents(&name) {
+            if !names.contains(&dependent) && found.insert(dependent.clone()) {
+                queue.push(dependent);
+            }
+        }
+    }
+    found.into_iter().collect()
+}
+
+/// Render servers as a dependency tree, each server above the ones it depends on
+///
+/// Servers nothing else in `names` depends on are the roots, in the given order.
+pub fn render_tree(names: &[String], metadata: &ServerMetadata, config: &Config) -> String {
+    let mut roots: Vec<&String> = names
+        .iter()
+        .filter(|name| {
+            !metadata
+                .dependents(name)
+                .iter()
+                .any(|dependent| names.contains(dependent))
+        })
+        .collect();
+    // Every server is somebody's dependency only if links form a cycle; show them flat
+    if roots.is_empty() {
+        roots = names.iter().collect();
+    }
+
+    let mut output = String::new();
+    for root in roots {
+        output.push_str(&format!("{}\n", root));
+        let mut ancestors = vec![root.clone()];
+        render_children(root, "", metadata, config, &mut ancestors, &mut output);
+    }
+    output.trim_end().to_string()
+}
+
+fn render_children(
+    name: &str,
+    prefix: &str,
+    metadata: &ServerMetadata,
+    config: &Config,
+    ancestors: &mut Vec<String>,
+    output: &mut String,
+) {
+    let dependencies = metadata.dependencies(name);
+    for (index, dependency) in dependencies.iter().enumerate() {
+        let last = index + 1 == dependencies.len();
+        let (branch, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+
+        let note = if !config.mcp_servers.contains_key(dependency) {
+            " (missing)".to_string()
+        } else if ancestors.contains(dependency) {
+            " (cycle)".to_string()
+        } else {
+            String::new()
+        };
+        output.push_str(&format!("{}{}{}{}\n", prefix, branch, dependency, note));
+
+        if note.is_empty() {
+            ancestors.push(dependency.clone());
+            let prefix = format!("{}{}", prefix, indent);
+            render_children(dependency, &prefix, metadata, config, ancestors, output);
+            ancestors.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::McpServer;
+
+    fn links(pairs: &[(&str, &[&str])]) -> HashMap<String, Vec<String>> {
+        pairs
+            .iter()
+            .map(|(name, deps)| {
+                (
+                    name.to_string(),
+                    deps.iter().map(|d| d.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let existing = links(&[("gateway", &["api"]), ("api", &["db"])]);
+
+        assert_eq!(find_cycle(&existing, "cache", &names(&["db"])), None);
+        assert_eq!(
+            find_cycle(&existing, "db", &names(&["gateway"])),
+            Some(names(&["db", "gateway", "api", "db"]))
+        );
+        assert_eq!(
+            find_cycle(&existing, "db", &names(&["db"])),
+            Some(names(&["db", "db"]))
+        );
+        // Replacing a server's own links drops the old edges
+        assert_eq!(find_cycle(&existing, "api", &names(&["cache"])), None);
+    }
+
+    #[test]
+    fn test_transitive_dependents_and_tree() {
+        let metadata = ServerMetadata {
+            depends_on: links(&[("gateway", &["api", "auth"]), ("api", &["db"])]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            transitive_dependents(&metadata, &names(&["db"])),
+            ["api", "gateway"]
+        );
+        assert!(transitive_dependents(&metadata, &names(&["gateway"])).is_empty());
+
+        let mut config = Config::default();
+        for name in ["gateway", "api", "db", "solo"] {
+            config.mcp_servers.insert(
+                name.to_string(),
+                McpServer {
+                    command: Some("node".to_string()),
+                    args: None,
+                    url: None,
+                    env: None,
+                    timeout: None,
+                    auto_approve: None,
+                    other: HashMap::new(),
+                },
+            );
+        }
+        let tree = render_tree(
+            &names(&["api", "db", "gateway", "solo"]),
+            &metadata,
+            &config,
+        );
+        assert_eq!(
+            tree,
+            "gateway\n├── api\n│   └── db\n└── auth (missing)\nsolo"
+        );
+    }
+}
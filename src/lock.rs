@@ -0,0 +1,185 @@
+use crate::config::Target;
+use crate::utils::duration::{format_duration, parse_duration};
+use anyhow::{anyhow, Context, Result};
+use fs4::FileExt;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const LOCK_FILE: &str = "mcp-forge.lock";
+
+/// How long to wait for another process by default
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The lock this process holds, released when the process exits
+static HELD: Mutex<Option<File>> = Mutex::new(None);
+
+/// Take the config lock for the rest of the process, unless already held
+///
+/// mcp-forge loads the config, changes it and saves it, so two processes doing
+/// that at once would lose one's changes. Waits up to `MCP_FORGE_LOCK_TIMEOUT`
+/// (10 seconds by default) for another process to finish, or for as long as
+/// it takes when `wait` is set.
+pub fn acquire(target: &Target, wait: bool) -> Result<()> {
+    let mut held = HELD.lock().unwrap_or_else(|e| e.into_inner());
+    if held.is_none() {
+        let timeout = if wait { None } else { Some(timeout()) };
+        *held = Some(lock_file(&lock_path(target)?, timeout)?);
+    }
+    Ok(())
+}
+
+/// Take the config lock if no other process holds it, without waiting
+pub fn try_acquire(target: &Target) -> Result<bool> {
+    let mut held = HELD.lock().unwrap_or_else(|e| e.into_inner());
+    if held.is_none() {
+        match lock_file(&lock_path(target)?, Some(Duration::ZERO)) {
+            Ok(file) => *held = Some(file),
+            Err(e) if e.is::<Busy>() => return Ok(false),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(true)
+}
+
+/// Let go of the lock before the process exits, for long-running commands
+/// that only need it now and then
+pub fn release() {
+    *HELD.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Another process holds the lock
+#[derive(Debug)]
+struct Busy;
+
+impl std::fmt::Display for Busy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("lock is held by another process")
+    }
+}
+
+impl std::error::Error for Busy {}
+
+/// Lock `path`, giving up after `timeout` (never, if `None`)
+fn lock_file(path: &Path, timeout: Option<Duration>) -> Result<File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .with_context(|| format!("Failed to open lock file: {}", path.display()))?;
+
+    let started = Instant::now();
+    let mut told = false;
+    loop {
+        match file.try_lock_exclusive() {
+            Ok(()) => break,
+            Err(e) if e.raw_os_error() == fs4::lock_contended_error().raw_os_error() => {}
+            Err(e) => return Err(e).with_context(|| format!("Failed to lock {}", path.display())),
+        }
+        match timeout {
+            Some(timeout) if started.elapsed() >= timeout => {
+                if timeout.is_zero() {
+                    return Err(Busy.into());
+                }
+                return Err(anyhow!(
+                    "{} is changing the configuration; gave up after {}. \
+                     Rerun with --wait to wait until it finishes, or set MCP_FORGE_LOCK_TIMEOUT",
+                    holder(path),
+                    format_duration(chrono::Duration::from_std(timeout).unwrap_or_default())
+                ));
+            }
+            _ => {}
+        }
+        if !told {
+            eprintln!("Waiting for {} to finish...", holder(path));
+            told = true;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+
+    // Record who holds it, for the message other processes show
+    file.set_len(0)?;
+    write!(file, "{}", std::process::id())?;
+    Ok(file)
+}
+
+/// Describe the process holding the lock, from the pid it wrote
+fn holder(path: &Path) -> String {
+    match fs::read_to_string(path)
+        .ok()
+        .map(|pid| pid.trim().to_string())
+    {
+        Some(pid) if !pid.is_empty() => format!("Another mcp-forge process (pid {})", pid),
+        _ => "Another mcp-forge process".to_string(),
+    }
+}
+
+/// Lock wait, set with `MCP_FORGE_LOCK_TIMEOUT` (e.g. `30s`, `2m`)
+fn timeout() -> Duration {
+    timeout_from(std::env::var("MCP_FORGE_LOCK_TIMEOUT").ok().as_deref())
+}
+
+fn timeout_from(setting: Option<&str>) -> Duration {
+    match setting.map(parse_duration) {
+        None => DEFAULT_TIMEOUT,
+        Some(Ok(timeout)) => timeout.to_std().unwrap_or(DEFAULT_TIMEOUT),
+        Some(Err(e)) => {
+            eprintln!("⚠ MCP_FORGE_LOCK_TIMEOUT: {}; using 10s", e);
+            DEFAULT_TIMEOUT
+        }
+    }
+}
+
+fn lock_path(target: &Target) -> Result<PathBuf> {
+    Ok(target.state_dir()?.join(LOCK_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_second_lock_times_out_naming_the_holder() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(LOCK_FILE);
+        let _first = lock_file(&path, Some(Duration::ZERO)).unwrap();
+
+        let busy = lock_file(&path, Some(Duration::ZERO)).unwrap_err();
+        assert!(busy.is::<Busy>());
+
+        let err = lock_file(&path, Some(Duration::from_millis(150))).unwrap_err();
+        let message = err.to_string();
+        assert!(
+            message.contains(&format!("(pid {})", std::process::id())),
+            "{}",
+            message
+        );
+        assert!(message.contains("--wait"), "{}", message);
+    }
+
+    #[test]
+    fn test_lock_is_released_when_dropped() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(LOCK_FILE);
+        drop(lock_file(&path, Some(Duration::ZERO)).unwrap());
+        assert!(lock_file(&path, Some(Duration::ZERO)).is_ok());
+    }
+
+    #[test]
+    fn test_timeout_from_env() {
+        assert_eq!(timeout_from(None), DEFAULT_TIMEOUT);
+        assert_eq!(timeout_from(Some("30s")), Duration::from_secs(30));
+        assert_eq!(timeout_from(Some("soon")), DEFAULT_TIMEOUT);
+    }
+}
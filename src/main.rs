@@ -1,21 +1,62 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
+use colored::Colorize;
 
+mod apply;
 mod backup;
+mod browser;
 mod bulk;
+mod bulk_state;
+mod claude_logs;
 mod cli;
+mod clock;
+mod completions;
 mod config;
+mod config_size;
+mod config_validation;
+mod convert;
+mod deletions;
+mod effective_env;
+mod examples;
 mod github;
+mod journal;
+mod layers;
+mod links;
+mod lock;
+mod meta;
+mod metadata;
+mod notify;
+mod output;
+mod profile_audit;
 mod profiles;
+mod recall;
+mod remote;
+mod repo_check;
+mod report;
+mod schema;
 mod search;
+mod selection;
+mod settings;
+mod smoke;
+mod template_create;
+mod template_edit;
+mod template_versions;
 mod templates;
+mod timefmt;
+mod transaction;
+mod upgrade;
 mod utils;
 mod validation;
+mod why;
+mod workspace;
 
 // Re-export enum types from their respective modules
 pub use backup::BackupCommands;
 pub use bulk::BulkCommands;
+pub use links::LinkCommands;
+pub use meta::MetaCommands;
 pub use profiles::ProfileCommands;
+pub use workspace::WorkspaceCommands;
 
 #[derive(Parser)]
 #[command(name = "mcp-forge")]
@@ -32,6 +73,56 @@ struct Cli {
     /// Enable verbose output
     #[arg(short, long, global = true)]
     verbose: bool,
+
+    /// Show timestamps in UTC (ISO 8601) instead of local time
+    #[arg(long, global = true)]
+    utc: bool,
+
+    /// When the GitHub rate limit runs low, wait for it to reset instead of stopping
+    #[arg(long, global = true)]
+    wait_for_rate_limit: bool,
+
+    /// Try each GitHub request once instead of retrying 5xx, timeouts and connection errors
+    #[arg(long, global = true)]
+    no_retry: bool,
+
+    /// Backup taken before changing the configuration: none, auto or named:<name>
+    #[arg(
+        long = "backup",
+        global = true,
+        value_name = "MODE",
+        default_value = "auto"
+    )]
+    backup_mode: backup::BackupMode,
+
+    /// Show a desktop notification when the command finishes
+    #[arg(long, global = true)]
+    notify: bool,
+
+    /// If another mcp-forge process is changing the config, wait for it however long it takes
+    #[arg(long, global = true)]
+    wait: bool,
+
+    /// Claude Desktop config file to use instead of the standard one (overrides MCP_FORGE_CONFIG)
+    #[arg(long, global = true, value_name = "PATH", conflicts_with = "target")]
+    config: Option<std::path::PathBuf>,
+
+    /// Replace a symlinked config file with a regular file instead of writing through the link
+    #[arg(long, global = true)]
+    force_replace_symlink: bool,
+
+    /// Treat a config whose mcpServers is null or a list as having no servers
+    /// instead of asking (commands with their own --force accept it too)
+    #[arg(long, global = true)]
+    force: bool,
+
+    /// Client whose config to manage: claude, cursor, vscode, windsurf or custom:<path>
+    #[arg(long, global = true, value_name = "HOST", default_value = "claude")]
+    target: config::Host,
+
+    /// Layer a change goes to when overlays are configured: base or an overlay name
+    #[arg(long, global = true, value_name = "LAYER")]
+    layer: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -68,27 +159,95 @@ enum Commands {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+        /// Show servers as a tree of the dependencies declared with `link set`
+        #[arg(long, conflicts_with_all = ["json", "format"])]
+        tree: bool,
+        /// Show long and base64 environment values in full
+        #[arg(long)]
+        full: bool,
+        /// Show only servers turned off with `disable`
+        #[arg(long, conflicts_with = "tree")]
+        disabled_only: bool,
+        /// Print just the server names, one per line (a JSON array with --json)
+        #[arg(long, conflicts_with_all = ["tree", "format"])]
+        names_only: bool,
+    },
+    /// Show one server in full, with secrets masked
+    Show {
+        /// Server name
+        name: String,
+        /// Output the server's configuration as JSON
+        #[arg(long)]
+        json: bool,
+        /// Leave secrets unmasked in --json output
+        #[arg(long, requires = "json")]
+        reveal_secrets: bool,
+        /// Open the server's documentation in the default browser
+        #[arg(long, conflicts_with = "json")]
+        open: bool,
     },
     /// Add new server from template
     Add {
         /// Server name
         name: String,
         /// Template name
-        template: String,
-        /// Variables as key=value pairs
+        #[arg(required_unless_present = "preset")]
+        template: Option<String>,
+        /// Build the server from a runtime and package instead of a template:
+        /// npx:<package>, uvx:<package> or docker:<image>
+        #[arg(
+            long,
+            value_name = "RUNTIME:PACKAGE",
+            conflicts_with_all = ["template", "source", "vars", "var", "vars_file", "vars_stdin"]
+        )]
+        preset: Option<String>,
+        /// Load the template from this source only (see `template source list`)
+        #[arg(long, value_name = "NAME")]
+        source: Option<String>,
+        /// Extra arguments for a --preset server, passed through verbatim
+        #[arg(last = true, requires = "preset", value_name = "ARGS")]
+        extra_args: Vec<String>,
+        /// Variables as key=value pairs; quote values holding commas, e.g. 'dsn="a,b"'
         #[arg(long)]
         vars: Option<String>,
+        /// One variable as KEY=VALUE, taken literally (repeatable)
+        #[arg(long, value_name = "KEY=VALUE")]
+        var: Vec<String>,
+        /// JSON or YAML file of variables ('-' for stdin); --vars and --var take precedence
+        #[arg(long)]
+        vars_file: Option<String>,
+        /// Read variables from stdin as a JSON map or KEY=VALUE lines, keeping
+        /// secrets out of shell history; --vars and --var take precedence
+        #[arg(long)]
+        vars_stdin: bool,
+        /// Add the server even if the template doesn't support this platform
+        #[arg(long)]
+        ignore_platform: bool,
+        /// Warn instead of failing when the rendered server still holds `{{...}}`
+        #[arg(long)]
+        allow_unrendered: bool,
+        /// Don't offer the values last used with this template as defaults
+        #[arg(long)]
+        no_recall: bool,
         /// Preview changes without applying
         #[arg(long)]
         dry_run: bool,
         /// Show diff of changes
         #[arg(long)]
         preview: bool,
+        /// Print the resulting server as JSON on stdout, with messages on stderr
+        #[arg(
+            long,
+            value_name = "FORMAT",
+            value_parser = ["text", "json"],
+            conflicts_with_all = ["dry_run", "preview"]
+        )]
+        output: Option<String>,
     },
     /// Remove server(s)
     Remove {
-        /// Server name or pattern
-        name: Option<String>,
+        /// Server names; combined with any --pattern matches
+        names: Vec<String>,
         /// Remove all servers
         #[arg(long)]
         all: bool,
@@ -98,6 +257,64 @@ enum Commands {
         /// Skip confirmation prompts
         #[arg(long)]
         force: bool,
+        /// Warn instead of failing when nothing matches
+        #[arg(long)]
+        ignore_missing: bool,
+        /// Also remove servers that depend on the removed ones
+        #[arg(long)]
+        cascade: bool,
+        /// Keep the removed servers' provenance, notes, links and flags
+        #[arg(long)]
+        keep_metadata: bool,
+        /// Preview changes without applying
+        #[arg(long)]
+        dry_run: bool,
+        /// Print the result as JSON on stdout, with messages on stderr (one server only)
+        #[arg(
+            long,
+            value_name = "FORMAT",
+            value_parser = ["text", "json"],
+            conflicts_with_all = ["all", "pattern", "cascade", "ignore_missing", "dry_run"]
+        )]
+        output: Option<String>,
+    },
+    /// Turn a server off, keeping its configuration for `enable`
+    Disable {
+        /// Server name
+        name: String,
+        /// Replace an earlier disabled copy without asking
+        #[arg(long)]
+        force: bool,
+        /// Preview changes without applying
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Turn a disabled server back on
+    Enable {
+        /// Disabled server name
+        name: String,
+        /// Enable it under a different name
+        #[arg(long, value_name = "NAME")]
+        rename: Option<String>,
+        /// Overwrite an active server with the same name without asking
+        #[arg(long)]
+        force: bool,
+        /// Preview changes without applying
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Give a server a new name
+    Rename {
+        /// Current server name
+        old: String,
+        /// New server name
+        new: String,
+        /// Overwrite an existing server with the new name
+        #[arg(long)]
+        force: bool,
+        /// Leave a disabled copy behind under the old name
+        #[arg(long)]
+        keep_old: bool,
         /// Preview changes without applying
         #[arg(long)]
         dry_run: bool,
@@ -110,6 +327,28 @@ enum Commands {
         #[arg(long)]
         dry_run: bool,
     },
+    /// Rebuild a server from the latest version of its template
+    ///
+    /// Variable values recorded when the server was added, or found in its
+    /// config, are reused; only the rest are asked for. Local changes to the
+    /// server carry over as they do with `edit`.
+    Upgrade {
+        /// Server name
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        name: Option<String>,
+        /// Upgrade every server created from an older template version
+        #[arg(long)]
+        all: bool,
+        /// Set a variable as NAME=VALUE, e.g. one the new version adds (repeatable)
+        #[arg(long, value_name = "NAME=VALUE")]
+        var: Vec<String>,
+        /// Show the changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+        /// Apply without asking for confirmation
+        #[arg(long)]
+        force: bool,
+    },
     /// Update server configuration
     Update {
         /// Server name or pattern
@@ -123,15 +362,62 @@ enum Commands {
         /// Set environment variables
         #[arg(long)]
         set: Vec<String>,
+        /// Seconds the client waits on the server (Cline, Cursor)
+        #[arg(long, allow_negative_numbers = true)]
+        timeout: Option<i64>,
+        /// Comma-separated tools the client may call without asking (Cline, Cursor)
+        #[arg(long, conflicts_with = "clear_auto_approve")]
+        auto_approve: Option<String>,
+        /// Remove the server's autoApprove list
+        #[arg(long)]
+        clear_auto_approve: bool,
         /// Preview changes without applying
         #[arg(long)]
         dry_run: bool,
         /// Show diff of changes
         #[arg(long)]
         preview: bool,
+        /// Print the resulting server as JSON on stdout, with messages on stderr
+        #[arg(
+            long,
+            value_name = "FORMAT",
+            value_parser = ["text", "json"],
+            requires = "name",
+            conflicts_with_all = ["tag", "dry_run", "preview"]
+        )]
+        output: Option<String>,
+    },
+    /// Convert a server between command and url types
+    Convert {
+        /// Server name
+        name: String,
+        /// Target server type (url, command)
+        #[arg(long)]
+        to: String,
+        /// Endpoint for --to url
+        #[arg(long)]
+        url: Option<String>,
+        /// Command for --to command
+        #[arg(long)]
+        command: Option<String>,
+        /// Arguments for --to command, split like a shell would (quote to keep spaces)
+        #[arg(long)]
+        args: Option<String>,
+        /// Move environment variables into HTTP headers (--to url only)
+        #[arg(long)]
+        env_to_headers: bool,
+        /// Preview changes without applying
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Template operations
     Template {
+        /// Template repository to use instead of the configured one
+        #[arg(long, global = true, value_name = "OWNER/REPO[@BRANCH]")]
+        repo: Option<String>,
+        /// Proxy for this command's requests, instead of the configured one
+        #[arg(long, global = true, value_name = "URL")]
+        proxy: Option<String>,
         #[command(subcommand)]
         action: TemplateCommands,
     },
@@ -150,11 +436,26 @@ enum Commands {
         #[command(subcommand)]
         action: BulkCommands,
     },
+    /// Declare dependencies between servers
+    Link {
+        #[command(subcommand)]
+        action: LinkCommands,
+    },
+    /// Set per-server metadata kept alongside the config
+    Meta {
+        #[command(subcommand)]
+        action: MetaCommands,
+    },
     /// Profile management
     Profile {
         #[command(subcommand)]
         action: ProfileCommands,
     },
+    /// Servers declared in a project's .mcp-forge/servers.yaml
+    Workspace {
+        #[command(subcommand)]
+        action: WorkspaceCommands,
+    },
     /// Validation and health checks
     Validate {
         /// Perform deep validation
@@ -163,20 +464,66 @@ enum Commands {
         /// Validate system requirements
         #[arg(long)]
         requirements: bool,
-        /// Server name to validate (all if not specified)
-        server: Option<String>,
+        /// Interactively fix issues that can be resolved automatically
+        #[arg(long)]
+        fix: bool,
+        /// With --fix, keep the first of each set of case-variant env keys in
+        /// sorted order (upper case sorts first) without asking
+        #[arg(long, requires = "fix")]
+        force: bool,
+        /// Output format (default, plain, json)
+        #[arg(long)]
+        format: Option<String>,
+        /// Servers to validate (all if none given)
+        servers: Vec<String>,
     },
     /// System health check
-    Health,
+    Health {
+        /// Output format (default, plain, json)
+        #[arg(long)]
+        format: Option<String>,
+        /// Also report servers Claude Desktop failed to start, from its logs
+        #[arg(long)]
+        logs: bool,
+    },
     /// Validate all configurations
     ValidateAll,
     /// System diagnostic
     Doctor,
+    /// Start every enabled server and report which ones come up
+    Smoke {
+        /// Seconds to wait for each server
+        #[arg(long, default_value_t = 10)]
+        timeout: u64,
+        /// Number of servers to start at once
+        #[arg(long, default_value_t = 4)]
+        jobs: usize,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Only test servers whose name contains this pattern
+        #[arg(long)]
+        only: Option<String>,
+    },
+    /// Show the environment a server will see
+    EffectiveEnv {
+        /// Server name
+        name: String,
+        /// Only show keys whose configured value differs from the host environment
+        #[arg(long)]
+        diff_host: bool,
+    },
     /// Import configuration
     Import {
-        /// Input file
+        /// Input file, or a directory of per-server files from `export --split`
+        #[arg(long, required_unless_present = "from", conflicts_with = "from")]
+        file: Option<String>,
+        /// Read the Claude config from another machine (ssh://[user@]host[:port])
         #[arg(long)]
-        file: String,
+        from: Option<String>,
+        /// Config path on the remote machine (defaults to Claude's usual locations)
+        #[arg(long, requires = "from")]
+        remote_path: Option<String>,
         /// Merge with existing configuration
         #[arg(long)]
         merge: bool,
@@ -186,21 +533,250 @@ enum Commands {
         /// Preview changes without applying
         #[arg(long)]
         dry_run: bool,
+        /// Import fields outside the known schema without asking
+        #[arg(long)]
+        allow_unknown_fields: bool,
+    },
+    /// Converge the configuration on a declared set of servers
+    Apply {
+        /// Desired-state file (YAML or JSON, same schema as bulk add)
+        file: String,
+        /// Remove servers that are not declared in the file
+        #[arg(long)]
+        prune: bool,
+        /// Preview changes without applying
+        #[arg(long)]
+        dry_run: bool,
+        /// Exit with status 2 when the dry run finds changes
+        #[arg(long, requires = "dry_run")]
+        exit_code: bool,
     },
     /// Export configuration
     Export {
-        /// Output format (json, yaml, template)
+        /// Output format (json, yaml, markdown)
         #[arg(long)]
         format: Option<String>,
         /// Export as template
         #[arg(long)]
         template: bool,
+        /// Add comments describing each server (yaml only)
+        #[arg(long)]
+        annotate: bool,
         /// Output file (stdout if not specified)
         #[arg(long)]
         output: Option<String>,
+        /// Write one file per server plus an index.json
+        #[arg(long, requires = "output_dir", conflicts_with_all = ["output", "template", "annotate"])]
+        split: bool,
+        /// Directory for --split output
+        #[arg(long, requires = "split")]
+        output_dir: Option<String>,
+        /// Keep sensitive env values unmasked in --split output
+        #[arg(long, requires = "split")]
+        include_secrets: bool,
+        /// Overwrite the Claude config on another machine (ssh://[user@]host[:port])
+        #[arg(long, conflicts_with_all = ["output", "split", "template", "annotate", "format"])]
+        to: Option<String>,
+        /// Config path on the remote machine (defaults to Claude's usual locations)
+        #[arg(long, requires = "to")]
+        remote_path: Option<String>,
+        /// Overwrite the remote config without confirmation
+        #[arg(long, requires = "to")]
+        force: bool,
+        /// Also export servers marked exclude_from_share
+        #[arg(long)]
+        include_excluded: bool,
+        /// Only export this server (repeatable)
+        #[arg(long, conflicts_with = "to")]
+        server: Vec<String>,
+    },
+    /// Show the journal of changes made by mcp-forge, newest first
+    Log {
+        /// Number of entries to show
+        #[arg(long, default_value = "20")]
+        limit: usize,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Count the configured servers and show how big the config is
+    Stats {
+        /// Break the size down by server and environment value
+        #[arg(long)]
+        size: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Explain how a server ended up in the config
+    Why {
+        /// Server name
+        name: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show copy-pasteable examples, optionally for one command
+    Examples {
+        /// Command to show examples for, e.g. `backup restore`
+        command: Vec<String>,
+        /// Only show examples mentioning this term
+        #[arg(long)]
+        search: Option<String>,
+        /// Print just the command lines
+        #[arg(long)]
+        copy: bool,
+    },
+    /// Print a shell completion script
+    Completions {
+        /// Shell to complete for (bash, zsh, fish, powershell)
+        shell: String,
+    },
+    /// Describe mcp-forge's commands and flags for tooling
+    #[command(hide = true)]
+    Schema {
+        /// Output format (json, markdown)
+        #[arg(long, default_value = "json")]
+        format: String,
     },
 }
 
+impl Commands {
+    /// Whether the command's own `--force` was given
+    fn forced(&self) -> bool {
+        match self {
+            Commands::Remove { force, .. }
+            | Commands::Disable { force, .. }
+            | Commands::Enable { force, .. }
+            | Commands::Rename { force, .. }
+            | Commands::Upgrade { force, .. }
+            | Commands::Validate { force, .. } => *force,
+            Commands::Config {
+                action: ConfigCommands::Init { force, .. } | ConfigCommands::Normalize { force, .. },
+            } => *force,
+            _ => false,
+        }
+    }
+
+    /// The server a command run with `--output json` changes
+    fn json_output(&self) -> Result<Option<&str>> {
+        let json = |output: &Option<String>| output.as_deref() == Some("json");
+        match self {
+            Commands::Add { name, output, .. }
+            | Commands::Update {
+                name: Some(name),
+                output,
+                ..
+            } if json(output) => Ok(Some(name)),
+            Commands::Remove { names, output, .. } if json(output) => match names.as_slice() {
+                [name] => Ok(Some(name)),
+                _ => Err(anyhow::anyhow!(
+                    "--output json reports on one server; name exactly one"
+                )),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Whether the command changes servers, and so has to pick a layer to
+    /// change once overlays are configured
+    fn edits_layer(&self) -> bool {
+        self.writes_config()
+            && !matches!(
+                self,
+                Commands::Config {
+                    action: ConfigCommands::Backup { .. }
+                        | ConfigCommands::Overlay { .. }
+                        | ConfigCommands::Materialize { .. }
+                } | Commands::Backup {
+                    action: BackupCommands::Create { .. } | BackupCommands::Clean { .. }
+                } | Commands::Link { .. }
+                    | Commands::Meta { .. }
+                    | Commands::Template { .. }
+            )
+    }
+
+    /// Whether the command writes to the Claude config directory
+    fn writes_config(&self) -> bool {
+        match self {
+            Commands::Add {
+                dry_run, preview, ..
+            }
+            | Commands::Update {
+                dry_run, preview, ..
+            } => !dry_run && !preview,
+            Commands::Remove { dry_run, .. }
+            | Commands::Disable { dry_run, .. }
+            | Commands::Enable { dry_run, .. }
+            | Commands::Rename { dry_run, .. }
+            | Commands::Edit { dry_run, .. }
+            | Commands::Upgrade { dry_run, .. }
+            | Commands::Convert { dry_run, .. }
+            | Commands::Import { dry_run, .. }
+            | Commands::Apply { dry_run, .. } => !dry_run,
+            Commands::Validate { fix, .. } => *fix,
+            Commands::Config { action } => match action {
+                ConfigCommands::Show | ConfigCommands::Validate { .. } | ConfigCommands::Path => {
+                    false
+                }
+                ConfigCommands::Restore { preview, .. } => !preview,
+                ConfigCommands::Normalize { dry_run, .. } | ConfigCommands::Repair { dry_run } => {
+                    !dry_run
+                }
+                ConfigCommands::Backup { .. } | ConfigCommands::Init { .. } => true,
+                ConfigCommands::Overlay { action } => !matches!(action, OverlayCommands::List),
+                // Watching takes the lock for each regeneration instead
+                ConfigCommands::Materialize { watch } => !watch,
+            },
+            Commands::Backup { action } => match action {
+                BackupCommands::List { .. } | BackupCommands::Search { .. } => false,
+                BackupCommands::Restore { preview, .. } => !preview,
+                BackupCommands::Clean { dry_run, .. } => !dry_run,
+                BackupCommands::Create { .. } => true,
+            },
+            Commands::Bulk { action } => match action {
+                BulkCommands::Add { dry_run, .. }
+                | BulkCommands::Update { dry_run, .. }
+                | BulkCommands::Remove { dry_run, .. } => !dry_run,
+            },
+            Commands::Link { .. }
+            | Commands::Meta { .. }
+            | Commands::Template {
+                action: TemplateCommands::Pin { .. },
+                ..
+            } => true,
+            Commands::Profile { action } => match action {
+                ProfileCommands::List
+                | ProfileCommands::Current
+                | ProfileCommands::Audit { .. } => false,
+                ProfileCommands::Sync { dry_run, .. } | ProfileCommands::Delete { dry_run, .. } => {
+                    !dry_run
+                }
+                _ => true,
+            },
+            Commands::Workspace { action } => match action {
+                WorkspaceCommands::Install { dry_run } => !dry_run,
+                WorkspaceCommands::Path => false,
+            },
+            Commands::List { .. }
+            | Commands::Show { .. }
+            | Commands::Template { .. }
+            | Commands::Health { .. }
+            | Commands::ValidateAll
+            | Commands::Doctor
+            | Commands::Smoke { .. }
+            | Commands::EffectiveEnv { .. }
+            | Commands::Export { .. }
+            | Commands::Log { .. }
+            | Commands::Why { .. }
+            | Commands::Stats { .. }
+            | Commands::Examples { .. }
+            | Commands::Completions { .. }
+            | Commands::Schema { .. } => false,
+        }
+    }
+}
+
 #[derive(Subcommand)]
 pub enum TemplateCommands {
     /// List available templates
@@ -211,11 +787,43 @@ pub enum TemplateCommands {
         /// Show offline templates
         #[arg(long)]
         offline: bool,
+        /// Filter by author (case-insensitive substring)
+        #[arg(long)]
+        author: Option<String>,
+        /// Only show templates that support this platform
+        #[arg(long)]
+        compatible: bool,
     },
     /// Show template details
     Show {
         /// Template name
         name: String,
+        /// Only use the local template cache
+        #[arg(long, visible_alias = "offline")]
+        cached: bool,
+        /// Print the template file exactly as stored
+        #[arg(long)]
+        raw: bool,
+        /// Show long and base64 environment values in full
+        #[arg(long, conflicts_with = "raw")]
+        full: bool,
+        /// Open the template's documentation in the default browser
+        #[arg(long, conflicts_with = "raw")]
+        open: bool,
+        /// Load the template from this source only (see `template source list`)
+        #[arg(long, value_name = "NAME")]
+        source: Option<String>,
+    },
+    /// Print a template's JSON exactly as stored, or save it for local editing
+    Cat {
+        /// Template name, or path to a local template file
+        name: String,
+        /// Write the template to this file instead of stdout
+        #[arg(long)]
+        output: Option<String>,
+        /// Only use the local template cache
+        #[arg(long, visible_alias = "offline")]
+        cached: bool,
     },
     /// Search templates
     Search {
@@ -230,6 +838,18 @@ pub enum TemplateCommands {
         /// Filter by platform
         #[arg(long)]
         platform: Option<String>,
+        /// Filter by author (case-insensitive substring)
+        #[arg(long)]
+        author: Option<String>,
+        /// Only show templates that support this platform
+        #[arg(long)]
+        compatible: bool,
+        /// Show how each result's score is made up
+        #[arg(long)]
+        explain: bool,
+        /// Hide results scoring below this value
+        #[arg(long)]
+        min_score: Option<f32>,
     },
     /// Refresh template cache
     Refresh {
@@ -239,31 +859,146 @@ pub enum TemplateCommands {
         /// Clear cache before refresh
         #[arg(long)]
         clear: bool,
+        /// With --clear, show what would be deleted without deleting it
+        #[arg(long, requires = "clear")]
+        dry_run: bool,
+        /// List templates added, removed or updated since the previous refresh
+        #[arg(long, conflicts_with = "dry_run")]
+        show_changes: bool,
     },
-    /// Create new template
+    /// Show what the last refresh changed in the template catalog
+    Changes,
+    /// Build a new template file with prompts
+    ///
+    /// Asks for the metadata, variables and launch settings, then writes the
+    /// template once it validates.
     Create {
         /// Template name
         name: String,
+        /// Start from a configured server's command, args, URL and environment
+        #[arg(long, value_name = "SERVER")]
+        from_server: Option<String>,
+        /// File to write; defaults to <NAME>.json in the current directory
+        #[arg(short, long, value_name = "FILE", conflicts_with = "dir")]
+        output: Option<std::path::PathBuf>,
+        /// Directory to write <NAME>.json in, e.g. a local templates directory
+        #[arg(long, value_name = "DIR")]
+        dir: Option<std::path::PathBuf>,
+        /// Overwrite the file if it exists
+        #[arg(long)]
+        force: bool,
     },
     /// Validate template
     Validate {
         /// Template file
         file: String,
     },
+    /// Edit a local template file's variables, config and metadata
+    ///
+    /// Opens a menu unless changes are given as options. Removals apply first,
+    /// then --set-var and --set; the file is only written if the result is valid.
+    Edit {
+        /// Template file
+        file: String,
+        /// Set a variable field as NAME.FIELD=VALUE, e.g. port.default=5433 (repeatable)
+        #[arg(long, value_name = "NAME.FIELD=VALUE")]
+        set_var: Vec<String>,
+        /// Remove a variable (repeatable)
+        #[arg(long, value_name = "NAME")]
+        remove_var: Vec<String>,
+        /// Set a template field as KEY=VALUE, e.g. version=1.1.0 or config.env.PGPORT=5432 (repeatable)
+        #[arg(long, value_name = "KEY=VALUE")]
+        set: Vec<String>,
+        /// Remove an optional template field, e.g. config.args (repeatable)
+        #[arg(long, value_name = "KEY")]
+        unset: Vec<String>,
+    },
+    /// Hold a server at a template version, so `template outdated` measures it
+    /// against that version instead of the latest
+    Pin {
+        /// Server added from a template
+        server: String,
+        /// Version to hold the server at, e.g. 1.0.0
+        #[arg(required_unless_present = "unpin")]
+        version: Option<String>,
+        /// Remove the pin instead
+        #[arg(long, conflicts_with = "version")]
+        unpin: bool,
+    },
+    /// List servers created from an older version of their template
+    Outdated {
+        /// Only use the local template cache
+        #[arg(long, visible_alias = "offline")]
+        cached: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Maintain a template repository
+    Repo {
+        #[command(subcommand)]
+        action: TemplateRepoCommands,
+    },
+    /// Manage the sources templates are listed and loaded from
+    ///
+    /// The configured repository is always the first source, named `default`;
+    /// added sources follow in order, and the first one with a template wins.
+    Source {
+        #[command(subcommand)]
+        action: TemplateSourceCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TemplateSourceCommands {
+    /// Add a source after the existing ones
+    Add {
+        /// Name to refer to the source by, e.g. with --source
+        name: String,
+        /// A GitHub owner/repo[@branch], a directory, or the http(s) URL of a catalog.json
+        spec: String,
+    },
+    /// Remove an added source and its cached templates
+    Remove {
+        /// Source name
+        name: String,
+    },
+    /// List sources in the order they are consulted
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum TemplateRepoCommands {
+    /// Check that every catalog entry resolves to a valid, matching template
+    Check {
+        /// Number of templates to fetch at once
+        #[arg(long, default_value_t = 4)]
+        jobs: usize,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Download templates from another repository from now on
+    ///
+    /// The template cache is cleared when the repository changes.
+    Set {
+        /// Repository as owner/repo or owner/repo@branch
+        #[arg(value_name = "OWNER/REPO[@BRANCH]")]
+        spec: String,
+    },
+    /// Show which repository templates come from
+    Get,
 }
 
 #[derive(Subcommand)]
 pub enum ConfigCommands {
     /// Show current configuration
     Show,
-    /// Validate configuration
+    /// Check the whole config file: schema, duplicates, and server structure
     Validate {
-        /// Perform deep validation
+        /// Output the report as JSON
         #[arg(long)]
-        deep: bool,
-        /// Validate requirements
-        #[arg(long)]
-        requirements: bool,
+        json: bool,
     },
     /// Create backup
     Backup {
@@ -281,26 +1016,132 @@ pub enum ConfigCommands {
         /// Preview restore without applying
         #[arg(long)]
         preview: bool,
-        /// Restore specific server only
+        /// Restore only this server (repeatable)
+        #[arg(long)]
+        server: Vec<String>,
+        /// Restore only these fields of the server (command, args, url, env, timeout, auto-approve)
+        #[arg(long, value_delimiter = ',', requires = "server")]
+        fields: Vec<String>,
+        /// When a partial name matches several backups, use the newest
         #[arg(long)]
-        server: Option<String>,
+        latest_match: bool,
+    },
+    /// Merge environment keys that differ only by case
+    Normalize {
+        /// Preview changes without applying
+        #[arg(long)]
+        dry_run: bool,
+        /// Keep the first of each set of keys in sorted order (upper case
+        /// sorts first) without asking
+        #[arg(long)]
+        force: bool,
+    },
+    /// Fix a misspelled, null or empty-list mcpServers key
+    Repair {
+        /// Show the fixes without applying them
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Initialize empty configuration
-    Init,
+    Init {
+        /// Overwrite an existing configuration (a backup is created first)
+        #[arg(long, conflicts_with = "if_missing")]
+        force: bool,
+        /// Do nothing if a configuration already exists
+        #[arg(long)]
+        if_missing: bool,
+    },
     /// Show configuration file path
     Path,
+    /// Manage overlays merged over a base config into the Claude config
+    Overlay {
+        #[command(subcommand)]
+        action: OverlayCommands,
+    },
+    /// Write the base config and overlays, merged, into the Claude config
+    Materialize {
+        /// Keep running and rewrite it whenever the base or an overlay changes
+        #[arg(long)]
+        watch: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum OverlayCommands {
+    /// Add an overlay after the existing ones, creating the file if needed
+    Add {
+        /// Overlay config file, in Claude Desktop's format
+        file: std::path::PathBuf,
+        /// Name to refer to the overlay by, e.g. with --layer (default: the file name)
+        #[arg(long)]
+        name: Option<String>,
+        /// Base config for the first overlay (default: a copy of the current config)
+        #[arg(long, value_name = "FILE")]
+        base: Option<std::path::PathBuf>,
+    },
+    /// Remove an overlay, leaving its file in place
+    Remove {
+        /// Overlay name
+        name: String,
+    },
+    /// List the base and overlays in the order they are merged
+    List,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let matches = examples::with_examples(Cli::command()).get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
     // Set up logging if verbose
     if cli.verbose {
         env_logger::init();
     }
+    let zone = timefmt::Zone::from_flag(cli.utc);
+    let json_output = cli.command.json_output()?.map(str::to_string);
+    let out = output::Output::new(json_output.is_some());
+    let github = github::ClientOptions {
+        wait_for_rate_limit: cli.wait_for_rate_limit,
+        no_retry: cli.no_retry,
+        verbose: cli.verbose,
+        proxy: None,
+        repo: None,
+        zone,
+    };
+    let target = config::Target::new(cli.target.clone(), cli.config.clone())
+        .with_backup_mode(cli.backup_mode.clone())
+        .with_force_empty_servers(cli.force || cli.command.forced())
+        .with_replace_symlinks(cli.force_replace_symlink)
+        .with_layer(layers::LayerChoice::new(
+            cli.command.edits_layer(),
+            cli.layer.clone(),
+        ));
+
+    // Find out before any prompts or downloads that the final save would fail,
+    // and keep other processes out until this one is done
+    let writes_config = cli.command.writes_config();
+    if writes_config {
+        let ready =
+            utils::ensure_config_writable(&target).and_then(|()| lock::acquire(&target, cli.wait));
+        if let (Err(_), Some(name)) = (&ready, &json_output) {
+            output::print_outcome(&command_name(&matches), name, &ready, &target, None, &out)
+                .await?;
+        }
+        ready?;
+    }
+
+    // Put the config, profile and metadata files back in step if a previous run died mid-save
+    transaction::recover_on_startup(&target, zone);
+    let profile = cli.profile.clone();
+    let notify_mode = notify::NotifyMode::resolve(cli.notify);
+    let started = std::time::Instant::now();
+    let snapshot = if writes_config {
+        Some(journal::Snapshot::capture(&target).await)
+    } else {
+        None
+    };
 
-    match cli.command {
+    let result = match cli.command {
         Commands::List {
             filter,
             tag,
@@ -312,6 +1153,10 @@ async fn main() -> Result<()> {
             format,
             show_requirements,
             json,
+            tree,
+            full,
+            disabled_only,
+            names_only,
         } => {
             let criteria = search::SearchCriteria {
                 text: filter,
@@ -326,57 +1171,439 @@ async fn main() -> Result<()> {
                 format,
                 show_requirements,
                 json,
+                tree,
+                full,
+                disabled_only,
+                names_only,
             };
-            cli::handle_enhanced_list(criteria, options, cli.profile).await
+            cli::handle_enhanced_list(criteria, options, &target, &github).await
         }
+        Commands::Show {
+            name,
+            json,
+            reveal_secrets,
+            open,
+        } => cli::handle_show(name, json, reveal_secrets, open, &target, zone).await,
         Commands::Add {
             name,
             template,
+            preset,
+            source,
+            extra_args,
             vars,
+            var,
+            vars_file,
+            vars_stdin,
+            ignore_platform,
+            allow_unrendered,
+            no_recall,
             dry_run,
             preview,
-        } => cli::handle_enhanced_add(name, template, vars, dry_run, preview, cli.profile).await,
+            output: _,
+        } => match (preset, template) {
+            (Some(preset), _) => {
+                cli::handle_preset_add(
+                    name,
+                    preset,
+                    extra_args,
+                    dry_run,
+                    preview,
+                    &target,
+                    cli.profile,
+                    &out,
+                )
+                .await
+            }
+            (None, Some(template)) => {
+                cli::handle_enhanced_add(
+                    name,
+                    template,
+                    source,
+                    vars,
+                    var,
+                    vars_file,
+                    vars_stdin,
+                    ignore_platform,
+                    allow_unrendered,
+                    no_recall,
+                    dry_run,
+                    preview,
+                    &target,
+                    &github,
+                    cli.profile,
+                    &out,
+                )
+                .await
+            }
+            (None, None) => Err(anyhow::anyhow!("Specify a template or --preset")),
+        },
         Commands::Remove {
-            name,
+            names,
             all,
             pattern,
             force,
+            ignore_missing,
+            cascade,
+            keep_metadata,
+            dry_run,
+            output: _,
+        } => {
+            cli::handle_enhanced_remove(
+                selection::Selection::new(names),
+                all,
+                pattern,
+                force,
+                ignore_missing,
+                cascade,
+                keep_metadata,
+                dry_run,
+                &target,
+                cli.profile,
+                &out,
+            )
+            .await
+        }
+        Commands::Disable {
+            name,
+            force,
             dry_run,
-        } => cli::handle_enhanced_remove(name, all, pattern, force, dry_run, cli.profile).await,
+        } => cli::handle_disable(name, force, dry_run, &target, cli.profile).await,
+        Commands::Enable {
+            name,
+            rename,
+            force,
+            dry_run,
+        } => cli::handle_enable(name, rename, force, dry_run, &target, cli.profile).await,
+        Commands::Rename {
+            old,
+            new,
+            force,
+            keep_old,
+            dry_run,
+        } => cli::handle_rename(old, new, force, keep_old, dry_run, &target, cli.profile).await,
         Commands::Edit { name, dry_run } => {
-            cli::handle_enhanced_edit(name, dry_run, cli.profile).await
+            cli::handle_enhanced_edit(name, dry_run, &target, &github, cli.profile).await
+        }
+        Commands::Upgrade {
+            name,
+            all,
+            var,
+            dry_run,
+            force,
+        } => {
+            upgrade::handle_upgrade(
+                name,
+                all,
+                var,
+                dry_run,
+                force,
+                &target,
+                &github,
+                cli.profile,
+            )
+            .await
+        }
+        Commands::Convert {
+            name,
+            to,
+            url,
+            command,
+            args,
+            env_to_headers,
+            dry_run,
+        } => {
+            convert::handle_convert(
+                name,
+                to,
+                url,
+                command,
+                args,
+                env_to_headers,
+                dry_run,
+                &target,
+                cli.profile,
+            )
+            .await
         }
         Commands::Update {
             name,
             args,
             tag,
             set,
+            timeout,
+            auto_approve,
+            clear_auto_approve,
             dry_run,
             preview,
-        } => cli::handle_enhanced_update(name, args, tag, set, dry_run, preview, cli.profile).await,
-        Commands::Template { action } => cli::handle_template_command(action).await,
-        Commands::Config { action } => cli::handle_config_command(action, cli.profile).await,
-        Commands::Backup { action } => backup::handle_backup_command(action, cli.profile).await,
-        Commands::Bulk { action } => bulk::handle_bulk_command(action, cli.profile).await,
-        Commands::Profile { action } => profiles::handle_profile_command(action).await,
+            output: _,
+        } => {
+            let client_options = cli::ClientOptionsUpdate {
+                timeout,
+                auto_approve,
+                clear_auto_approve,
+            };
+            cli::handle_enhanced_update(
+                name,
+                args,
+                tag,
+                set,
+                client_options,
+                dry_run,
+                preview,
+                &target,
+                cli.profile,
+                &out,
+            )
+            .await
+        }
+        Commands::Template {
+            repo,
+            proxy,
+            action,
+        } => {
+            let repo = repo
+                .as_deref()
+                .map(github::TemplateRepository::parse)
+                .transpose()?;
+            let github = github::ClientOptions {
+                proxy,
+                repo,
+                ..github
+            };
+            cli::handle_template_command(action, &target, &github, zone).await
+        }
+        Commands::Config { action } => {
+            cli::handle_config_command(action, &target, cli.profile, zone).await
+        }
+        Commands::Backup { action } => {
+            backup::handle_backup_command(action, &target, cli.profile, zone).await
+        }
+        Commands::Bulk { action } => {
+            bulk::handle_bulk_command(action, &target, &github, cli.profile).await
+        }
+        Commands::Profile { action } => {
+            profiles::handle_profile_command(action, &target, zone).await
+        }
+        Commands::Workspace { action } => {
+            workspace::handle_workspace_command(action, &target, &github, cli.profile).await
+        }
+        Commands::Link { action } => links::handle_link_command(action, &target).await,
+        Commands::Meta { action } => meta::handle_meta_command(action, &target).await,
         Commands::Validate {
             deep,
             requirements,
-            server,
-        } => validation::handle_validate(deep, requirements, server, cli.profile).await,
-        Commands::Health => validation::handle_health_check(cli.profile).await,
-        Commands::ValidateAll => validation::handle_validate_all(cli.profile).await,
-        Commands::Doctor => validation::handle_doctor(cli.profile).await,
+            fix,
+            force,
+            format,
+            servers,
+        } => {
+            let format = validation::ReportFormat::parse(format.as_deref())?;
+            let servers = selection::Selection::new(servers);
+            validation::handle_validate(
+                deep,
+                requirements,
+                servers,
+                fix,
+                force,
+                format,
+                &target,
+                &github,
+                cli.profile,
+            )
+            .await
+        }
+        Commands::Health { format, logs } => {
+            let format = validation::ReportFormat::parse(format.as_deref())?;
+            validation::handle_health_check(format, logs, &target, cli.profile).await
+        }
+        Commands::ValidateAll => {
+            validation::handle_validate_all(&target, &github, cli.profile).await
+        }
+        Commands::Doctor => validation::handle_doctor(&target, &github).await,
+        Commands::Smoke {
+            timeout,
+            jobs,
+            json,
+            only,
+        } => smoke::handle_smoke(timeout, jobs, json, only, &target).await,
+        Commands::EffectiveEnv { name, diff_host } => {
+            effective_env::handle_effective_env(name, diff_host, &target).await
+        }
         Commands::Import {
             file,
+            from,
+            remote_path,
             merge,
             replace,
             dry_run,
-        } => cli::handle_import(file, merge, replace, dry_run, cli.profile).await,
+            allow_unknown_fields,
+        } => {
+            let source = match (file, from) {
+                (_, Some(from)) => cli::ImportSource::Ssh { from, remote_path },
+                (Some(file), None) => cli::ImportSource::File(file),
+                (None, None) => unreachable!("clap requires --file or --from"),
+            };
+            cli::handle_import(
+                source,
+                merge,
+                replace,
+                dry_run,
+                allow_unknown_fields,
+                &target,
+                cli.profile,
+            )
+            .await
+        }
+        Commands::Apply {
+            file,
+            prune,
+            dry_run,
+            exit_code,
+        } => {
+            apply::handle_apply(
+                file,
+                prune,
+                dry_run,
+                exit_code,
+                &target,
+                &github,
+                cli.profile,
+            )
+            .await
+        }
         Commands::Export {
             format,
             template,
+            annotate,
             output,
-        } => cli::handle_export(format, template, output, cli.profile).await,
+            split,
+            output_dir,
+            include_secrets,
+            to,
+            remote_path,
+            force,
+            include_excluded,
+            server,
+        } => {
+            if let Some(to) = to {
+                cli::handle_export_remote(to, remote_path, force, include_excluded, &target).await
+            } else {
+                cli::handle_export(
+                    format,
+                    template,
+                    annotate,
+                    output,
+                    split,
+                    output_dir,
+                    include_secrets,
+                    include_excluded,
+                    selection::Selection::new(server),
+                    &target,
+                    cli.profile,
+                    zone,
+                )
+                .await
+            }
+        }
+        Commands::Log { limit, json } => journal::handle_log(limit, json, &target, zone).await,
+        Commands::Why { name, json } => {
+            why::handle_why(name, json, &target, cli.profile, zone).await
+        }
+        Commands::Stats { size, json } => config_size::handle_stats(size, json, &target).await,
+        Commands::Examples {
+            command,
+            search,
+            copy,
+        } => examples::handle_examples(command, search, copy),
+        Commands::Completions { shell } => completions::handle_completions(Cli::command(), &shell),
+        Commands::Schema { format } => schema::handle_schema(Cli::command(), &format),
+    };
+
+    // A cancelled prompt is a clean exit, not an error; nothing has been saved yet
+    if let Err(e) = &result {
+        if utils::is_prompt_cancelled(e) {
+            if let Some(name) = &json_output {
+                output::print_outcome(
+                    &command_name(&matches),
+                    name,
+                    &result,
+                    &target,
+                    profile.as_deref(),
+                    &out,
+                )
+                .await?;
+            }
+            eprintln!("Operation cancelled.");
+            std::process::exit(utils::CANCELLED_EXIT_CODE);
+        }
+    }
+
+    if let Some(snapshot) = snapshot {
+        let entry = snapshot
+            .finish(command_name(&matches), &target, profile.as_deref(), &result)
+            .await;
+        journal::record(&target, &entry);
+    }
+
+    notify::notify_completion(
+        &notify::DesktopNotifier,
+        notify_mode,
+        &command_name(&matches),
+        &result,
+        started.elapsed(),
+        notify::take_tally(),
+    );
+
+    // A command that ran to completion may still have saved changes
+    let completed = match &result {
+        Ok(()) => true,
+        Err(e) => utils::exit_status(e).is_some(),
+    };
+
+    // Say which file was changed, so a wrong profile is noticed straight away
+    if completed && writes_config {
+        if let Some(name) = target.backups.taken() {
+            say!(
+                out,
+                "{}",
+                format!(
+                    "Backup: {} (undo with `mcp-forge backup restore {}`)",
+                    name, name
+                )
+                .dimmed()
+            );
+        }
+        let resolved = config::ConfigTarget::resolve(&target, profile.as_deref()).await?;
+        say!(out, "{}", resolved.footer().dimmed());
+    }
+
+    if let Some(name) = &json_output {
+        output::print_outcome(
+            &command_name(&matches),
+            name,
+            &result,
+            &target,
+            profile.as_deref(),
+            &out,
+        )
+        .await?;
+    }
+
+    // The command has already reported why it failed; only the status is left
+    if let Some(code) = result.as_ref().err().and_then(utils::exit_status) {
+        std::process::exit(code);
+    }
+
+    result
+}
+
+/// Subcommand path for the journal, e.g. `profile delete`
+fn command_name(matches: &ArgMatches) -> String {
+    let mut names = Vec::new();
+    let mut current = matches;
+    while let Some((name, sub)) = current.subcommand() {
+        names.push(name);
+        current = sub;
     }
+    names.join(" ")
 }
@@ -0,0 +1,79 @@
+use crate::config::{Config, Target};
+use crate::metadata::ServerMetadata;
+use anyhow::{anyhow, Result};
+use clap::Subcommand;
+use colored::Colorize;
+
+/// Sidecar metadata keys `meta set` accepts
+const KEYS: &[&str] = &["exclude_from_share", "ignore_version_pins"];
+
+#[derive(Subcommand)]
+pub enum MetaCommands {
+    /// Set a metadata key on a server
+    Set {
+        /// Server name
+        server: String,
+        /// Metadata key (exclude_from_share, ignore_version_pins)
+        key: String,
+        /// New value
+        value: String,
+    },
+}
+
+/// Handle meta command routing
+pub async fn handle_meta_command(action: MetaCommands, target: &Target) -> Result<()> {
+    let config = Config::load(target).await?;
+    let mut metadata = ServerMetadata::load(target)?;
+
+    match action {
+        MetaCommands::Set { server, key, value } => {
+            if !config.mcp_servers.contains_key(&server) {
+                return Err(anyhow!(
+                    "Server '{}' not found. {}",
+                    server,
+                    crate::bulk::describe_existing_servers(&config)
+                ));
+            }
+
+            match key.as_str() {
+                "exclude_from_share" => {
+                    let exclude = parse_bool(&value)?;
+                    if exclude {
+                        metadata.exclude_from_share.insert(server.clone());
+                    } else {
+                        metadata.exclude_from_share.remove(&server);
+                    }
+                }
+                "ignore_version_pins" => {
+                    if parse_bool(&value)? {
+                        metadata.ignore_version_pins.insert(server.clone());
+                    } else {
+                        metadata.ignore_version_pins.remove(&server);
+                    }
+                }
+                _ => {
+                    return Err(anyhow!(
+                        "Unknown metadata key '{}'. Supported keys: {}",
+                        key,
+                        KEYS.join(", ")
+                    ))
+                }
+            }
+
+            println!(
+                "{}",
+                format!("✓ Set {} = {} on {}", key, value, server).green()
+            );
+        }
+    }
+
+    metadata.save(target)
+}
+
+fn parse_bool(value: &str) -> Result<bool> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "1" => Ok(true),
+        "false" | "no" | "0" => Ok(false),
+        _ => Err(anyhow!("Expected true or false, got '{}'", value)),
+    }
+}
@@ -0,0 +1,359 @@
+use crate::config::{Config, Target};
+use crate::templates::Template;
+use crate::transaction::Transaction;
+use crate::utils;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+/// Template provenance recorded when a server is added from a template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerProvenance {
+    pub template: String,
+    pub version: String,
+    pub author: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requirements: Option<HashMap<String, String>>,
+    pub added_at: chrono::DateTime<chrono::Utc>,
+    /// Free-form notes about the server, edited by hand in the sidecar file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Variable values the server was created with; secret variables are never recorded
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, serde_json::Value>,
+    /// Set when `--ignore-platform` added the server on a platform its template doesn't list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform_override: Option<PlatformOverride>,
+    /// Documentation link taken from the template
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs_url: Option<String>,
+    /// Template version the server is held at, set with `template pin`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<String>,
+}
+
+/// A platform check that was overridden when a server was added
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlatformOverride {
+    /// Platform the server was added on
+    pub platform: String,
+    /// Platforms the template supports
+    pub supported: Vec<String>,
+}
+
+impl PlatformOverride {
+    /// The override needed to use `template` on `platform`, if it doesn't support it
+    pub fn for_template(template: &Template, platform: &str) -> Option<Self> {
+        (!crate::templates::supports_platform(&template.platforms, platform)).then(|| Self {
+            platform: platform.to_string(),
+            supported: template.platforms.clone(),
+        })
+    }
+}
+
+impl ServerProvenance {
+    /// Describe a server created from `template` with the given variable values
+    ///
+    /// A template that doesn't support this platform can only have been used with
+    /// `--ignore-platform`, so the override is recorded too.
+    pub fn from_template(
+        template: &Template,
+        category: Option<String>,
+        variables: &HashMap<String, serde_json::Value>,
+    ) -> Self {
+        Self {
+            template: template.name.clone(),
+            version: template.version.clone(),
+            author: template.author.clone(),
+            category,
+            tags: template.tags.clone(),
+            requirements: template.requirements.clone(),
+            added_at: chrono::Utc::now(),
+            notes: None,
+            variables: variables
+                .iter()
+                .filter(|(name, _)| !template.is_secret(name))
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+            platform_override: PlatformOverride::for_template(
+                template,
+                &crate::utils::platform::os(),
+            ),
+            docs_url: template.docs_url.clone(),
+            pinned: None,
+        }
+    }
+}
+
+/// Per-server metadata kept alongside the Claude Desktop config
+///
+/// Claude Desktop owns `claude_desktop_config.json`, so anything mcp-forge
+/// knows about a server beyond its launch settings lives in this sidecar file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServerMetadata {
+    #[serde(default)]
+    pub servers: HashMap<String, ServerProvenance>,
+    /// Servers each server needs in order to work, set with `link set`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub depends_on: HashMap<String, Vec<String>>,
+    /// Servers kept out of exports and profile syncs, set with `meta set`
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub exclude_from_share: BTreeSet<String>,
+    /// Servers whose interpreter version pins `validate` shouldn't warn about
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub ignore_version_pins: BTreeSet<String>,
+    /// Preset each server was added with, e.g. `npx:@scope/pkg`, set by `add --preset`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub presets: HashMap<String, String>,
+}
+
+impl ServerMetadata {
+    /// Load server metadata, returning an empty set if none has been recorded
+    pub fn load(target: &Target) -> Result<Self> {
+        let path = get_metadata_path(target)?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read server metadata: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse server metadata: {}", path.display()))
+    }
+
+    /// Save server metadata
+    pub fn save(&self, target: &Target) -> Result<()> {
+        let path = get_metadata_path(target)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content =
+            serde_json::to_string_pretty(self).context("Failed to serialize server metadata")?;
+        utils::atomic::write_atomic(&path, content.as_bytes())
+            .with_context(|| format!("Failed to write server metadata: {}", path.display()))
+    }
+
+    /// Queue the metadata file for saving along with other files in a transaction
+    pub fn stage(&self, target: &Target, transaction: &mut Transaction) -> Result<()> {
+        let content =
+            serde_json::to_string_pretty(self).context("Failed to serialize server metadata")?;
+        transaction.write(get_metadata_path(target)?, content);
+        Ok(())
+    }
+
+    /// Record the template a server was created from
+    ///
+    /// A pin set with `template pin` is kept when the server is re-created.
+    pub fn record(&mut self, server_name: &str, mut provenance: ServerProvenance) {
+        if provenance.pinned.is_none() {
+            provenance.pinned = self.get(server_name).and_then(|p| p.pinned.clone());
+        }
+        self.presets.remove(server_name);
+        self.servers.insert(server_name.to_string(), provenance);
+    }
+
+    /// Record the preset a server was created from, replacing any template provenance
+    pub fn record_preset(&mut self, server_name: &str, preset: &str) {
+        self.servers.remove(server_name);
+        self.presets
+            .insert(server_name.to_string(), preset.to_string());
+    }
+
+    /// Get the recorded provenance for a server
+    pub fn get(&self, server_name: &str) -> Option<&ServerProvenance> {
+        self.servers.get(server_name)
+    }
+
+    /// Servers `server_name` depends on
+    pub fn dependencies(&self, server_name: &str) -> &[String] {
+        self.depends_on
+            .get(server_name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Servers that depend on `server_name`, sorted
+    pub fn dependents(&self, server_name: &str) -> Vec<String> {
+        let mut dependents: Vec<String> = self
+            .depends_on
+            .iter()
+            .filter(|(_, deps)| deps.iter().any(|d| d == server_name))
+            .map(|(name, _)| name.clone())
+            .collect();
+        dependents.sort();
+        dependents
+    }
+
+    /// Records this file holds about a server, named for a removal prompt
+    pub fn records_for(&self, server_name: &str) -> Vec<&'static str> {
+        let mut records = Vec::new();
+        if let Some(provenance) = self.servers.get(server_name) {
+            records.push("template provenance");
+            if provenance.notes.is_some() {
+                records.push("notes");
+            }
+        }
+        if self.presets.contains_key(server_name) {
+            records.push("preset");
+        }
+        if self.depends_on.contains_key(server_name) {
+            records.push("dependency links");
+        }
+        if self.exclude_from_share.contains(server_name) {
+            records.push("exclude_from_share");
+        }
+        if self.ignore_version_pins.contains(server_name) {
+            records.push("ignore_version_pins");
+        }
+        records
+    }
+
+    /// One line per server with records, e.g. `db: template provenance, notes`
+    pub fn describe_records(&self, server_names: &[String]) -> Vec<String> {
+        server_names
+            .iter()
+            .filter_map(|name| {
+                let records = self.records_for(name);
+                (!records.is_empty()).then(|| format!("{}: {}", name, records.join(", ")))
+            })
+            .collect()
+    }
+
+    /// Drop everything recorded about removed servers, returning whether anything changed
+    ///
+    /// Links from other servers to them are kept, so `validate` can report them.
+    pub fn forget(&mut self, server_names: &[String]) -> bool {
+        let changed = server_names
+            .iter()
+            .any(|name| !self.records_for(name).is_empty());
+        for name in server_names {
+            self.servers.remove(name);
+            self.presets.remove(name);
+            self.depends_on.remove(name);
+            self.exclude_from_share.remove(name);
+            self.ignore_version_pins.remove(name);
+        }
+        changed
+    }
+
+    /// Move everything recorded about a server to its new name, returning
+    /// whether anything changed
+    ///
+    /// Records already held under the new name are dropped, and links from
+    /// other servers follow the server to its new name.
+    pub fn rename(&mut self, old: &str, new: &str) -> bool {
+        let mut changed = self.forget(&[new.to_string()]);
+        changed |= !self.records_for(old).is_empty();
+
+        if let Some(provenance) = self.servers.remove(old) {
+            self.servers.insert(new.to_string(), provenance);
+        }
+        if let Some(preset) = self.presets.remove(old) {
+            self.presets.insert(new.to_string(), preset);
+        }
+        if let Some(dependencies) = self.depends_on.remove(old) {
+            self.depends_on.insert(new.to_string(), dependencies);
+        }
+        if self.exclude_from_share.remove(old) {
+            self.exclude_from_share.insert(new.to_string());
+        }
+        if self.ignore_version_pins.remove(old) {
+            self.ignore_version_pins.insert(new.to_string());
+        }
+
+        for dependencies in self.depends_on.values_mut() {
+            if dependencies.iter().any(|name| name == old) {
+                changed = true;
+                let mut seen = BTreeSet::new();
+                dependencies.retain_mut(|name| {
+                    if name == old {
+                        *name = new.to_string();
+                    }
+                    seen.insert(name.clone())
+                });
+            }
+        }
+        changed
+    }
+
+    /// Servers in `config`, enabled or not, marked `exclude_from_share`, sorted
+    pub fn excluded_servers(&self, config: &Config) -> Vec<String> {
+        self.exclude_from_share
+            .iter()
+            .filter(|name| {
+                config.mcp_servers.contains_key(*name)
+                    || config.disabled_servers.contains_key(*name)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Take servers marked `exclude_from_share` out of a config about to be shared
+    ///
+    /// Returns the names withheld, sorted.
+    pub fn withhold_excluded(&self, config: &mut Config) -> Vec<String> {
+        let withheld = self.excluded_servers(config);
+        for name in &withheld {
+            config.mcp_servers.remove(name);
+            config.disabled_servers.remove(name);
+        }
+        withheld
+    }
+}
+
+/// Line telling the user how many servers a share left out, if any
+pub fn describe_withheld(withheld: &[String]) -> Option<String> {
+    if withheld.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "Withheld {} server(s) marked exclude_from_share: {} (use --include-excluded to share them)",
+        withheld.len(),
+        withheld.join(", ")
+    ))
+}
+
+/// Get path to the server metadata file
+fn get_metadata_path(target: &Target) -> Result<PathBuf> {
+    Ok(target.state_dir()?.join("server_metadata.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_moves_records_and_links() {
+        let mut metadata: ServerMetadata = serde_json::from_value(serde_json::json!({
+            "servers": {
+                "db": {
+                    "template": "postgres", "version": "1.0.0", "author": "Test",
+                    "added_at": "2025-01-01T00:00:00Z"
+                }
+            },
+            "depends_on": { "db": ["cache"], "api": ["db", "db-new"] },
+            "exclude_from_share": ["db"],
+            "presets": { "db-new": "npx:stale" }
+        }))
+        .unwrap();
+
+        assert!(metadata.rename("db", "db-new"));
+
+        assert_eq!(metadata.get("db-new").unwrap().template, "postgres");
+        assert!(metadata.get("db").is_none());
+        assert!(metadata.presets.is_empty());
+        assert_eq!(metadata.dependencies("db-new"), ["cache"]);
+        assert_eq!(metadata.dependencies("api"), ["db-new"]);
+        assert!(metadata.exclude_from_share.contains("db-new"));
+        assert!(metadata.records_for("db").is_empty());
+
+        assert!(!metadata.rename("unknown", "other"));
+    }
+}
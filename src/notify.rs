@@ -0,0 +1,236 @@
+use anyhow::Result;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// With `MCP_FORGE_NOTIFY` set, commands that take at least this long notify
+const SLOW_AFTER: Duration = Duration::from_secs(10);
+
+/// Counts reported by the running command for its completion notification
+static TALLY: Mutex<Option<Tally>> = Mutex::new(None);
+
+/// How many items a command got through and how many it failed on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tally {
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+/// Record the counts to show in the completion notification
+///
+/// Commands that don't report any get a plain finished/failed message.
+pub fn report(succeeded: usize, failed: usize) {
+    *TALLY.lock().unwrap() = Some(Tally { succeeded, failed });
+}
+
+/// Take the counts the command reported, if any
+pub fn take_tally() -> Option<Tally> {
+    TALLY.lock().unwrap().take()
+}
+
+/// When a finished command sends a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyMode {
+    Off,
+    /// `--notify` was given
+    Always,
+    /// `MCP_FORGE_NOTIFY` is set: only commands that ran for a while
+    SlowOnly,
+}
+
+impl NotifyMode {
+    pub fn resolve(flag: bool) -> Self {
+        Self::resolve_from(flag, std::env::var("MCP_FORGE_NOTIFY").ok().as_deref())
+    }
+
+    fn resolve_from(flag: bool, setting: Option<&str>) -> Self {
+        let enabled = matches!(
+            setting.map(|value| value.trim().to_lowercase()).as_deref(),
+            Some("1" | "true" | "yes" | "on")
+        );
+        match (flag, enabled) {
+            (true, _) => Self::Always,
+            (false, true) => Self::SlowOnly,
+            (false, false) => Self::Off,
+        }
+    }
+}
+
+/// Something that can show a notification to the user
+pub trait Notifier {
+    fn send(&self, summary: &str, body: &str);
+}
+
+/// Native desktop notifications through the platform's own tool
+///
+/// Uses `notify-send` on Linux and BSD and `osascript` on macOS. Where the tool
+/// or a notification service is missing, nothing is shown and nothing fails.
+pub struct DesktopNotifier;
+
+impl Notifier for DesktopNotifier {
+    fn send(&self, summary: &str, body: &str) {
+        if let Some(mut command) = desktop_command(summary, body) {
+            let _ = command
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status();
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn desktop_command(summary: &str, body: &str) -> Option<Command> {
+    let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut command = Command::new("osascript");
+    command.arg("-e").arg(format!(
+        "display notification {} with title {}",
+        quote(body),
+        quote(summary)
+    ));
+    Some(command)
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn desktop_command(summary: &str, body: &str) -> Option<Command> {
+    let mut command = Command::new("notify-send");
+    command.args(["--app-name", "mcp-forge", summary, body]);
+    Some(command)
+}
+
+#[cfg(not(unix))]
+fn desktop_command(_summary: &str, _body: &str) -> Option<Command> {
+    None
+}
+
+/// Notify that `command` finished, if `mode` calls for it; returns whether it did
+pub fn notify_completion(
+    notifier: &dyn Notifier,
+    mode: NotifyMode,
+    command: &str,
+    result: &Result<()>,
+    elapsed: Duration,
+    tally: Option<Tally>,
+) -> bool {
+    let send = match mode {
+        NotifyMode::Off => false,
+        NotifyMode::Always => true,
+        NotifyMode::SlowOnly => elapsed >= SLOW_AFTER,
+    };
+    if send {
+        let (summary, body) = completion_message(command, result, tally);
+        notifier.send(&summary, &body);
+    }
+    send
+}
+
+fn completion_message(
+    command: &str,
+    result: &Result<()>,
+    tally: Option<Tally>,
+) -> (String, String) {
+    let outcome = match (result, tally) {
+        (Err(_), _) => "failed",
+        (Ok(()), Some(tally)) if tally.failed > 0 => "finished with failures",
+        (Ok(()), _) => "finished",
+    };
+    let summary = format!("mcp-forge {} {}", command, outcome);
+
+    let body = match (tally, result) {
+        (Some(tally), _) => format!("{} succeeded, {} failed", tally.succeeded, tally.failed),
+        (None, Err(e)) => e.to_string().lines().next().unwrap_or_default().to_string(),
+        (None, Ok(())) => "Completed successfully".to_string(),
+    };
+    (summary, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct StubNotifier(RefCell<Vec<(String, String)>>);
+
+    impl Notifier for StubNotifier {
+        fn send(&self, summary: &str, body: &str) {
+            self.0
+                .borrow_mut()
+                .push((summary.to_string(), body.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_notification_summarizes_counts() {
+        let notifier = StubNotifier::default();
+        let tally = Tally {
+            succeeded: 11,
+            failed: 1,
+        };
+
+        assert!(notify_completion(
+            &notifier,
+            NotifyMode::Always,
+            "bulk add",
+            &Ok(()),
+            Duration::from_secs(1),
+            Some(tally),
+        ));
+        assert!(notify_completion(
+            &notifier,
+            NotifyMode::Always,
+            "smoke",
+            &Err(anyhow!("2 of 5 server(s) failed to start\nmore detail")),
+            Duration::from_secs(1),
+            None,
+        ));
+
+        assert_eq!(
+            notifier.0.into_inner(),
+            [
+                (
+                    "mcp-forge bulk add finished with failures".to_string(),
+                    "11 succeeded, 1 failed".to_string()
+                ),
+                (
+                    "mcp-forge smoke failed".to_string(),
+                    "2 of 5 server(s) failed to start".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_setting_only_notifies_for_slow_commands() {
+        let notifier = StubNotifier::default();
+        let mode = NotifyMode::resolve_from(false, Some("1"));
+        assert_eq!(mode, NotifyMode::SlowOnly);
+
+        let quick = notify_completion(&notifier, mode, "list", &Ok(()), SLOW_AFTER / 2, None);
+        let slow = notify_completion(&notifier, mode, "smoke", &Ok(()), SLOW_AFTER, None);
+        assert!(!quick);
+        assert!(slow);
+        assert_eq!(
+            notifier.0.into_inner(),
+            [(
+                "mcp-forge smoke finished".to_string(),
+                "Completed successfully".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_notify_mode() {
+        assert_eq!(NotifyMode::resolve_from(false, None), NotifyMode::Off);
+        assert_eq!(NotifyMode::resolve_from(false, Some("0")), NotifyMode::Off);
+        assert_eq!(
+            NotifyMode::resolve_from(true, Some("0")),
+            NotifyMode::Always
+        );
+        assert_eq!(
+            NotifyMode::resolve_from(false, Some(" Yes ")),
+            NotifyMode::SlowOnly
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use crate::config::{ConfigTarget, McpServer, Target};
+use crate::metadata::{ServerMetadata, ServerProvenance};
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// The server a run produced, with credentials masked; `None` once removed
+type Recorded = (String, Option<McpServer>);
+
+/// Where a command's output goes, and the server it leaves behind for the
+/// `--output json` document
+#[derive(Debug, Default)]
+pub struct Output {
+    json: bool,
+    recorded: Mutex<Option<Recorded>>,
+}
+
+impl Output {
+    /// With `json`, the result is printed as JSON on stdout and everything
+    /// else on stderr
+    pub fn new(json: bool) -> Self {
+        Self {
+            json,
+            ..Self::default()
+        }
+    }
+
+    /// Whether stdout is kept for the `--output json` document
+    pub fn json(&self) -> bool {
+        self.json
+    }
+
+    /// Remember the server a command left behind, `None` once it was removed
+    ///
+    /// `secrets` are template variable values to hide on top of the credentials
+    /// `show` masks.
+    pub fn record(&self, name: &str, server: Option<&McpServer>, secrets: &[String]) {
+        let server = server
+            .map(|server| crate::cli::mask_server_credentials(&server.with_values_masked(secrets)));
+        *self.recorded.lock().unwrap() = Some((name.to_string(), server));
+    }
+
+    fn recorded(&self) -> Option<Recorded> {
+        self.recorded.lock().unwrap().clone()
+    }
+}
+
+/// `println!` for messages meant for people, which go to stderr when `out`
+/// is producing `--output json` so stdout holds only the document
+#[macro_export]
+macro_rules! say {
+    ($out:expr) => {
+        $crate::say!($out, "")
+    };
+    ($out:expr, $($arg:tt)*) => {
+        if $out.json() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+/// What `--output json` prints once a command has changed a server
+#[derive(Debug, Serialize)]
+pub struct OperationResult {
+    pub operation: String,
+    pub name: String,
+    /// The server as saved, credentials masked; `null` once removed
+    pub server: Option<McpServer>,
+    /// Where the server came from, if it was added from a template
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<ServerProvenance>,
+    /// Backup taken before the change, if any
+    pub backup: Option<String>,
+    pub profile: Option<String>,
+    pub config_path: PathBuf,
+}
+
+/// What `--output json` prints when a command fails
+#[derive(Debug, Serialize)]
+pub struct OperationFailure {
+    pub operation: String,
+    pub name: String,
+    pub error: OperationError,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OperationError {
+    pub kind: &'static str,
+    pub message: String,
+}
+
+impl OperationError {
+    pub fn from_error(error: &anyhow::Error) -> Self {
+        Self {
+            kind: error_kind(error),
+            message: format!("{:#}", error),
+        }
+    }
+}
+
+/// Broad category of a failure, for scripts to branch on
+pub fn error_kind(error: &anyhow::Error) -> &'static str {
+    if crate::utils::is_prompt_cancelled(error) {
+        "cancelled"
+    } else if crate::utils::is_not_interactive(error) {
+        "no_terminal"
+    } else if error.chain().any(|e| e.is::<reqwest::Error>()) {
+        "network"
+    } else if error.chain().any(|e| e.is::<serde_json::Error>()) {
+        "parse"
+    } else if error.chain().any(|e| e.is::<std::io::Error>()) {
+        "io"
+    } else {
+        "error"
+    }
+}
+
+/// Print the outcome of `operation` on `name` as a JSON document on stdout
+///
+/// A command that succeeds without changing anything, e.g. because a
+/// confirmation was declined, is reported as a failure.
+pub async fn print_outcome(
+    operation: &str,
+    name: &str,
+    result: &Result<()>,
+    target: &Target,
+    profile: Option<&str>,
+    out: &Output,
+) -> Result<()> {
+    let (name, server) = match (result, out.recorded()) {
+        (Ok(()), Some(recorded)) => recorded,
+        (Ok(()), None) => {
+            let unchanged = anyhow!("Nothing was changed");
+            print_failure(operation, name, &unchanged)?;
+            return Err(unchanged);
+        }
+        (Err(e), _) => return print_failure(operation, name, e),
+    };
+
+    let provenance = server
+        .as_ref()
+        .and_then(|_| ServerMetadata::load(target).ok())
+        .and_then(|metadata| metadata.get(&name).cloned());
+    let resolved = ConfigTarget::resolve(target, profile).await?;
+    let document = OperationResult {
+        operation: operation.to_string(),
+        name,
+        server,
+        provenance,
+        backup: target.backups.taken(),
+        profile: resolved.profile,
+        config_path: resolved.config_path,
+    };
+    println!("{}", serde_json::to_string_pretty(&document)?);
+    Ok(())
+}
+
+fn print_failure(operation: &str, name: &str, error: &anyhow::Error) -> Result<()> {
+    let document = OperationFailure {
+        operation: operation.to_string(),
+        name: name.to_string(),
+        error: OperationError::from_error(error),
+    };
+    println!("{}", serde_json::to_string_pretty(&document)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_error_kinds() {
+        assert_eq!(error_kind(&anyhow!("Server 'x' not found")), "error");
+        let io = std::fs::read("/nonexistent/mcp-forge")
+            .context("Failed to read")
+            .unwrap_err();
+        assert_eq!(error_kind(&io), "io");
+        let parse = serde_json::from_str::<serde_json::Value>("{")
+            .context("Bad JSON")
+            .unwrap_err();
+        assert_eq!(error_kind(&parse), "parse");
+        let cancelled = anyhow::Error::from(inquire::InquireError::OperationCanceled);
+        assert_eq!(error_kind(&cancelled), "cancelled");
+    }
+}
@@ -0,0 +1,337 @@
+use crate::config::{Config, McpServer, Target};
+use crate::profiles::{load_profile_config, load_profile_snapshot};
+use crate::utils;
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Source name for the live Claude config in audit output
+const LIVE_SOURCE: &str = "live";
+
+/// Commands that run a package named by their first positional argument
+const PACKAGE_RUNNERS: [&str; 6] = ["npx", "bunx", "pnpx", "uvx", "pipx", "docker"];
+
+/// A server as found in one profile or the live config
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditMember {
+    pub source: String,
+    pub name: String,
+}
+
+/// One env key whose value isn't the same everywhere the server appears
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EnvDivergence {
+    pub key: String,
+    pub values: Vec<MemberValue>,
+}
+
+/// A member's value for a diverging env key
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MemberValue {
+    #[serde(flatten)]
+    pub member: AuditMember,
+    /// Masked value, `None` where the key isn't set
+    pub value: Option<String>,
+}
+
+/// A logical server that is configured differently across sources
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditFinding {
+    /// Command and package, or URL host, identifying the server
+    pub server: String,
+    pub members: Vec<AuditMember>,
+    pub differences: Vec<EnvDivergence>,
+}
+
+/// Identify what a server connects to, ignoring its name and credentials
+///
+/// URL servers are identified by host. Command servers are identified by the
+/// command plus the package or script it runs, with any version suffix dropped,
+/// so `npx -y @modelcontextprotocol/server-github@1.2` and
+/// `npx @modelcontextprotocol/server-github` are the same logical server.
+pub fn logical_server_key(server: &McpServer) -> Option<String> {
+    if let Some(url) = &server.url {
+        let host = url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_else(|| url.clone());
+        return Some(format!("url {}", host));
+    }
+
+    let command = server.command.as_deref()?;
+    let program = std::path::Path::new(command)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(command)
+        .to_string();
+    let args = server.args.as_deref().unwrap_or_default();
+    let positional = args
+        .iter()
+        .filter(|arg| !arg.starts_with('-'))
+        .find(|arg| !(program == "docker" && arg.as_str() == "run"));
+
+    match positional {
+        Some(target) if PACKAGE_RUNNERS.contains(&program.as_str()) => {
+            Some(format!("{} {}", program, strip_version(target)))
+        }
+        Some(target) => Some(format!("{} {}", program, target)),
+        None => Some(program),
+    }
+}
+
+/// Drop a trailing `@version` or `:tag`, keeping npm scopes like `@scope/pkg`
+fn strip_version(package: &str) -> &str {
+    let (scope_end, rest) = match package.strip_prefix('@') {
+        Some(rest) => (1, rest),
+        None => (0, package),
+    };
+    match rest.rfind(['@', ':']) {
+        Some(index) if index > 0 => &package[..scope_end + index],
+        _ => package,
+    }
+}
+
+/// Find logical servers whose env differs between sources
+///
+/// Only servers present in at least two sources are compared; a server that
+/// exists in a single profile has nothing to diverge from.
+pub fn audit(sources: &[(String, Config)]) -> Vec<AuditFinding> {
+    let mut groups: BTreeMap<String, Vec<(AuditMember, &McpServer)>> = BTreeMap::new();
+    for (source, config) in sources {
+        let mut names: Vec<&String> = config.mcp_servers.keys().collect();
+        names.sort();
+        for name in names {
+            let server = &config.mcp_servers[name];
+            if let Some(key) = logical_server_key(server) {
+                groups.entry(key).or_default().push((
+                    AuditMember {
+                        source: source.clone(),
+                        name: name.clone(),
+                    },
+                    server,
+                ));
+            }
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter(|(_, members)| {
+            members
+                .iter()
+                .map(|(member, _)| &member.source)
+                .collect::<BTreeSet<_>>()
+                .len()
+                > 1
+        })
+        .filter_map(|(server, members)| {
+            let differences = env_differences(&members);
+            (!differences.is_empty()).then(|| AuditFinding {
+                server,
+                members: members.iter().map(|(member, _)| member.clone()).collect(),
+                differences,
+            })
+        })
+        .collect()
+}
+
+fn env_differences(members: &[(AuditMember, &McpServer)]) -> Vec<EnvDivergence> {
+    let keys: BTreeSet<&String> = members
+        .iter()
+        .filter_map(|(_, server)| server.env.as_ref())
+        .flat_map(|env| env.keys())
+        .collect();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let raw: Vec<Option<&String>> = members
+                .iter()
+                .map(|(_, server)| server.env.as_ref().and_then(|env| env.get(key)))
+                .collect();
+            if raw.iter().all(|value| *value == raw[0]) {
+                return None;
+            }
+            Some(EnvDivergence {
+                key: key.clone(),
+                values: members
+                    .iter()
+                    .zip(raw)
+                    .map(|((member, _), value)| MemberValue {
+                        member: member.clone(),
+                        value: value.map(|v| utils::mask_sensitive_env_value(key, v)),
+                    })
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Handle `profile audit`
+pub async fn handle_profile_audit(target: &Target, json: bool) -> Result<()> {
+    let mut sources = vec![(LIVE_SOURCE.to_string(), Config::load(target).await?)];
+    let mut profile_names: Vec<String> =
+        load_profile_config().await?.profiles.into_keys().collect();
+    profile_names.sort();
+    for name in profile_names {
+        let snapshot = load_profile_snapshot(&name).await?;
+        sources.push((name, snapshot));
+    }
+
+    let findings = audit(&sources);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&findings)?);
+        return Ok(());
+    }
+
+    println!("{}", "Profile Credential Audit".cyan().bold());
+    println!("{}", "────────────────────────".cyan());
+
+    for finding in &findings {
+        println!();
+        println!("{} {}", "⚠".yellow(), finding.server.bold());
+        for difference in &finding.differences {
+            println!("  {}:", difference.key);
+            for entry in &difference.values {
+                println!(
+                    "    {} ({}): {}",
+                    entry.member.source,
+                    entry.member.name,
+                    entry.value.as_deref().unwrap_or("(unset)")
+                );
+            }
+        }
+    }
+
+    println!();
+    if findings.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "✓ No differing environment values across {} source(s)",
+                sources.len()
+            )
+            .green()
+        );
+    } else {
+        println!(
+            "{} logical server(s) have differing environment values across {} source(s); \
+             switching profiles changes which credentials they use",
+            findings.len(),
+            sources.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn command_server(command: &str, args: &[&str], env: &[(&str, &str)]) -> McpServer {
+        McpServer {
+            command: Some(command.to_string()),
+            args: Some(args.iter().map(|a| a.to_string()).collect()),
+            url: None,
+            env: Some(
+                env.iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            ),
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_logical_server_key() {
+        let github = |args: &[&str]| logical_server_key(&command_server("npx", args, &[]));
+        assert_eq!(
+            github(&["-y", "@modelcontextprotocol/server-github@1.2.0"]),
+            Some("npx @modelcontextprotocol/server-github".to_string())
+        );
+        assert_eq!(
+            github(&["@modelcontextprotocol/server-github"]),
+            Some("npx @modelcontextprotocol/server-github".to_string())
+        );
+        assert_eq!(
+            logical_server_key(&command_server(
+                "/usr/local/bin/docker",
+                &["run", "-i", "--rm", "ghcr.io/org/server:latest"],
+                &[]
+            )),
+            Some("docker ghcr.io/org/server".to_string())
+        );
+        assert_eq!(
+            logical_server_key(&command_server("node", &["/srv/mcp/index.js"], &[])),
+            Some("node /srv/mcp/index.js".to_string())
+        );
+
+        let remote = McpServer {
+            url: Some("https://mcp.example.com/sse?token=abc".to_string()),
+            ..command_server("unused", &[], &[])
+        };
+        assert_eq!(
+            logical_server_key(&remote),
+            Some("url mcp.example.com".to_string())
+        );
+    }
+
+    #[test]
+    fn test_audit_reports_differing_env_across_sources() {
+        let server = |token: &str| {
+            command_server(
+                "npx",
+                &["-y", "@modelcontextprotocol/server-github"],
+                &[("GITHUB_TOKEN", token), ("LOG_LEVEL", "info")],
+            )
+        };
+        let config = |servers: Vec<(&str, McpServer)>| Config {
+            mcp_servers: servers
+                .into_iter()
+                .map(|(name, server)| (name.to_string(), server))
+                .collect(),
+            disabled_servers: HashMap::new(),
+            other: HashMap::new(),
+        };
+
+        let sources = vec![
+            (
+                "live".to_string(),
+                config(vec![("github", server("ghp_personal_1234"))]),
+            ),
+            (
+                "work".to_string(),
+                config(vec![("gh-work", server("ghp_company_9876"))]),
+            ),
+            (
+                "same".to_string(),
+                config(vec![("solo", command_server("node", &["a.js"], &[]))]),
+            ),
+        ];
+
+        let findings = audit(&sources);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].server,
+            "npx @modelcontextprotocol/server-github"
+        );
+        assert_eq!(findings[0].differences.len(), 1);
+
+        let difference = &findings[0].differences[0];
+        assert_eq!(difference.key, "GITHUB_TOKEN");
+        assert_eq!(difference.values[0].member.source, "live");
+        assert_eq!(difference.values[1].member.name, "gh-work");
+        assert_eq!(
+            difference.values[0].value.as_deref(),
+            Some("ghp***********234")
+        );
+
+        // Identical env everywhere is not a finding
+        let sources = vec![sources[0].clone(), sources[0].clone()];
+        assert!(audit(&sources).is_empty());
+    }
+}
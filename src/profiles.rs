@@ -1,6 +1,11 @@
-use crate::config::Config;
+use crate::clock::{Clock, SystemClock};
+use crate::config::{Config, Host, Target};
+use crate::deletions::PlannedDeletions;
+use crate::metadata::ServerMetadata;
+use crate::timefmt::{self, Zone};
+use crate::transaction::Transaction;
 use crate::utils;
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use clap::Subcommand;
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
@@ -25,35 +30,41 @@ pub struct ProfileConfig {
     pub profiles: HashMap<String, ProfileInfo>,
 }
 
-/// Update profile metadata with current server count
-/// This should be called whenever servers are added, removed, or modified
-pub async fn update_profile_server_count(profile_name: Option<&str>) -> Result<()> {
-    // Get the current profile if none specified
-    let effective_profile = if profile_name.is_none() {
-        let profile_config = load_profile_config().await?;
-        profile_config.current_profile
-    } else {
-        profile_name.map(|s| s.to_string())
-    };
+/// Queue the profile's server count and snapshot to match `config`
+///
+/// Call this whenever servers are added, removed, or modified, with the config
+/// that is being saved.
+pub async fn stage_server_count(
+    transaction: &mut Transaction,
+    profile_name: Option<&str>,
+    config: &Config,
+) -> Result<()> {
+    let effective_profile = effective_profile(profile_name).await?;
 
     // Only update if we're working with a named profile
     if let Some(profile) = effective_profile.as_deref() {
-        // Load the main config to get current server count
-        let config = Config::load(None).await?;
         let mut profile_config = load_profile_config().await?;
 
         if let Some(profile_info) = profile_config.profiles.get_mut(profile) {
             profile_info.server_count = config.mcp_servers.len();
-            profile_info.last_used = Some(chrono::Utc::now());
-            save_profile_config(&profile_config).await?;
+            profile_info.last_used = Some(SystemClock.now());
+            stage_profile_config(transaction, &profile_config)?;
 
             // Also update the profile snapshot to match current state
-            save_profile_snapshot(profile, &config).await?;
+            stage_profile_snapshot(transaction, profile, config)?;
         }
     }
     Ok(())
 }
 
+/// The profile a command applies to: `--profile` if given, else the current profile
+pub async fn effective_profile(profile_name: Option<&str>) -> Result<Option<String>> {
+    match profile_name {
+        Some(name) => Ok(Some(name.to_string())),
+        None => Ok(load_profile_config().await?.current_profile),
+    }
+}
+
 /// Save a profile snapshot
 async fn save_profile_snapshot(profile_name: &str, config: &Config) -> Result<()> {
     let snapshot_path = get_profile_snapshot_path(profile_name)?;
@@ -69,8 +80,18 @@ async fn save_profile_snapshot(profile_name: &str, config: &Config) -> Result<()
     Ok(())
 }
 
+fn stage_profile_snapshot(
+    transaction: &mut Transaction,
+    profile_name: &str,
+    config: &Config,
+) -> Result<()> {
+    let content = serde_json::to_string_pretty(config)?;
+    transaction.write(get_profile_snapshot_path(profile_name)?, content);
+    Ok(())
+}
+
 /// Load a profile snapshot
-async fn load_profile_snapshot(profile_name: &str) -> Result<Config> {
+pub async fn load_profile_snapshot(profile_name: &str) -> Result<Config> {
     let snapshot_path = get_profile_snapshot_path(profile_name)?;
 
     if !snapshot_path.exists() {
@@ -91,11 +112,11 @@ fn get_profile_snapshot_path(profile_name: &str) -> Result<PathBuf> {
 }
 
 /// Check if main config has unsaved changes compared to current profile
-async fn has_unsaved_changes() -> Result<bool> {
+async fn has_unsaved_changes(target: &Target) -> Result<bool> {
     let profile_config = load_profile_config().await?;
 
     if let Some(current_profile) = &profile_config.current_profile {
-        let main_config = Config::load(None).await?;
+        let main_config = Config::load(target).await?;
         let profile_snapshot = load_profile_snapshot(current_profile).await?;
 
         // Compare configurations (simplified - could be more sophisticated)
@@ -110,20 +131,51 @@ async fn has_unsaved_changes() -> Result<bool> {
 }
 
 /// Handle profile command routing
-pub async fn handle_profile_command(action: ProfileCommands) -> Result<()> {
+pub async fn handle_profile_command(
+    action: ProfileCommands,
+    target: &Target,
+    zone: Zone,
+) -> Result<()> {
+    if target.host != Host::Claude {
+        return Err(anyhow!(
+            "Profiles only manage the Claude Desktop config, not {}; drop --target to use them",
+            target.host
+        ));
+    }
+    let clock = SystemClock;
     match action {
-        ProfileCommands::Create { name } => handle_profile_create(name).await,
-        ProfileCommands::List => handle_profile_list().await,
-        ProfileCommands::Switch { name } => handle_profile_switch(name).await,
-        ProfileCommands::Current => handle_profile_current().await,
-        ProfileCommands::Sync { from, to, dry_run } => handle_profile_sync(from, to, dry_run).await,
-        ProfileCommands::Delete { name, force } => handle_profile_delete(name, force).await,
-        ProfileCommands::Save { name } => handle_profile_save(name).await,
+        ProfileCommands::Create { name, from } => {
+            handle_profile_create(target, name, from, &clock, zone).await
+        }
+        ProfileCommands::List => handle_profile_list(zone).await,
+        ProfileCommands::Switch { name } => handle_profile_switch(target, name, &clock).await,
+        ProfileCommands::Current => handle_profile_current(target, zone).await,
+        ProfileCommands::Sync {
+            from,
+            to,
+            dry_run,
+            include_excluded,
+        } => handle_profile_sync(target, from, to, dry_run, include_excluded).await,
+        ProfileCommands::Delete {
+            name,
+            force,
+            dry_run,
+        } => handle_profile_delete(name, force, dry_run, zone).await,
+        ProfileCommands::Save { name } => handle_profile_save(target, name).await,
+        ProfileCommands::Audit { json } => {
+            crate::profile_audit::handle_profile_audit(target, json).await
+        }
     }
 }
 
-/// Create a new profile
-async fn handle_profile_create(name: String) -> Result<()> {
+/// Create a new profile, empty or seeded from `from`
+async fn handle_profile_create(
+    target: &Target,
+    name: String,
+    from: Option<String>,
+    clock: &dyn Clock,
+    zone: Zone,
+) -> Result<()> {
     validate_profile_name(&name)?;
 
     let mut profile_config = load_profile_config().await?;
@@ -132,36 +184,77 @@ async fn handle_profile_create(name: String) -> Result<()> {
         return Err(anyhow!("Profile '{}' already exists", name));
     }
 
+    // Read the source before recording anything, so a bad one leaves no trace
+    let seed = match from.as_deref() {
+        Some(source) => load_seed(target, source, &profile_config, zone).await?,
+        None => Config::default(),
+    };
+
     // Create profile info
     let profile_info = ProfileInfo {
         name: name.clone(),
         description: None,
-        created_at: chrono::Utc::now(),
+        created_at: clock.now(),
         last_used: None,
-        server_count: 0,
+        server_count: seed.mcp_servers.len(),
     };
 
     // Add to profile config
     profile_config.profiles.insert(name.clone(), profile_info);
 
-    // Create empty snapshot for this profile
-    let empty_config = Config::default();
-    save_profile_snapshot(&name, &empty_config).await?;
-
-    // Save profile config
-    save_profile_config(&profile_config).await?;
+    let mut transaction = Transaction::new(target)?;
+    stage_profile_config(&mut transaction, &profile_config)?;
+    stage_profile_snapshot(&mut transaction, &name, &seed)?;
+    transaction.commit()?;
 
     println!(
         "{}",
         format!("✓ Profile '{}' created successfully", name).green()
     );
+    if let Some(source) = &from {
+        let mut servers: Vec<&String> = seed.mcp_servers.keys().collect();
+        servers.sort();
+        println!(
+            "  Seeded from {} with {}",
+            source,
+            timefmt::pluralize(servers.len() as i64, "server", "servers")
+        );
+        for server in servers {
+            println!("    • {}", server);
+        }
+    }
     println!("  Switch to it with: mcp-forge profile switch {}", name);
 
     Ok(())
 }
 
+/// Configuration a new profile starts from: `current` for the live config,
+/// another profile's name, or `backup:<name>`
+async fn load_seed(
+    target: &Target,
+    source: &str,
+    profile_config: &ProfileConfig,
+    zone: Zone,
+) -> Result<Config> {
+    if source == "current" {
+        return Config::load(target).await;
+    }
+    if let Some(backup) = source.strip_prefix("backup:") {
+        return crate::backup::load_named_backup(target, backup, zone).await;
+    }
+    if !profile_config.profiles.contains_key(source) {
+        return Err(anyhow!(
+            "Profile '{}' does not exist; --from takes 'current', a profile name or backup:<name>",
+            source
+        ));
+    }
+    load_profile_snapshot(source)
+        .await
+        .with_context(|| format!("Failed to read profile '{}'", source))
+}
+
 /// List all profiles
-async fn handle_profile_list() -> Result<()> {
+async fn handle_profile_list(zone: Zone) -> Result<()> {
     let profile_config = load_profile_config().await?;
 
     if profile_config.profiles.is_empty() {
@@ -186,11 +279,14 @@ async fn handle_profile_list() -> Result<()> {
         println!("• {} {}", name.bold(), status);
         println!(
             "  Created: {}",
-            info.created_at.format("%Y-%m-%d %H:%M UTC")
+            timefmt::format_timestamp(info.created_at, zone)
         );
 
         if let Some(last_used) = info.last_used {
-            println!("  Last used: {}", last_used.format("%Y-%m-%d %H:%M UTC"));
+            println!(
+                "  Last used: {}",
+                timefmt::format_timestamp(last_used, zone)
+            );
         }
 
         println!("  Servers: {}", info.server_count);
@@ -212,7 +308,7 @@ async fn handle_profile_list() -> Result<()> {
 }
 
 /// Switch to a different profile
-async fn handle_profile_switch(name: String) -> Result<()> {
+async fn handle_profile_switch(target: &Target, name: String, clock: &dyn Clock) -> Result<()> {
     let mut profile_config = load_profile_config().await?;
 
     if !profile_config.profiles.contains_key(&name) {
@@ -220,7 +316,7 @@ async fn handle_profile_switch(name: String) -> Result<()> {
     }
 
     // Check for unsaved changes in current profile
-    if has_unsaved_changes().await? {
+    if has_unsaved_changes(target).await? {
         println!(
             "{}",
             "⚠️  Warning: You have unsaved changes in the current profile!".yellow()
@@ -228,6 +324,10 @@ async fn handle_profile_switch(name: String) -> Result<()> {
 
         if let Some(current_profile) = &profile_config.current_profile {
             println!("Current profile: {}", current_profile.bold());
+            crate::utils::ensure_interactive(&format!(
+                "Profile '{}' has unsaved changes; save them with `mcp-forge profile sync default {}` first",
+                current_profile, current_profile
+            ))?;
 
             let save_changes =
                 inquire::Confirm::new("Save changes to current profile before switching?")
@@ -236,7 +336,7 @@ async fn handle_profile_switch(name: String) -> Result<()> {
 
             if save_changes {
                 // Save current main config as snapshot for current profile
-                let main_config = Config::load(None).await?;
+                let main_config = Config::load(target).await?;
                 save_profile_snapshot(current_profile, &main_config).await?;
                 println!("✓ Changes saved to profile '{}'", current_profile);
             } else {
@@ -247,17 +347,19 @@ async fn handle_profile_switch(name: String) -> Result<()> {
 
     // Load the target profile snapshot and copy it to main config
     let profile_snapshot = load_profile_snapshot(&name).await?;
-    profile_snapshot.save(None).await?;
+    let mut transaction = Transaction::new(target)?;
+    profile_snapshot.stage(target, &mut transaction)?;
 
     // Update current profile
     profile_config.current_profile = Some(name.clone());
 
     // Update last used timestamp
     if let Some(profile_info) = profile_config.profiles.get_mut(&name) {
-        profile_info.last_used = Some(chrono::Utc::now());
+        profile_info.last_used = Some(clock.now());
     }
 
-    save_profile_config(&profile_config).await?;
+    stage_profile_config(&mut transaction, &profile_config)?;
+    transaction.commit()?;
 
     println!("{}", format!("✓ Switched to profile '{}'", name).green());
     println!(
@@ -275,7 +377,7 @@ async fn handle_profile_switch(name: String) -> Result<()> {
 }
 
 /// Show current profile
-async fn handle_profile_current() -> Result<()> {
+async fn handle_profile_current(target: &Target, zone: Zone) -> Result<()> {
     let profile_config = load_profile_config().await?;
 
     if let Some(current_name) = &profile_config.current_profile {
@@ -284,16 +386,19 @@ async fn handle_profile_current() -> Result<()> {
         if let Some(profile_info) = profile_config.profiles.get(current_name) {
             println!(
                 "  Created: {}",
-                profile_info.created_at.format("%Y-%m-%d %H:%M UTC")
+                timefmt::format_timestamp(profile_info.created_at, zone)
             );
             if let Some(last_used) = profile_info.last_used {
-                println!("  Last used: {}", last_used.format("%Y-%m-%d %H:%M UTC"));
+                println!(
+                    "  Last used: {}",
+                    timefmt::format_timestamp(last_used, zone)
+                );
             }
             println!("  Servers: {}", profile_info.server_count);
         }
 
         // Show servers in main config (what's actually active)
-        if let Ok(config) = Config::load(None).await {
+        if let Ok(config) = Config::load(target).await {
             if !config.mcp_servers.is_empty() {
                 println!();
                 println!("Servers in this profile:");
@@ -314,12 +419,18 @@ async fn handle_profile_current() -> Result<()> {
 }
 
 /// Sync configuration between profiles
-async fn handle_profile_sync(from: String, to: String, dry_run: bool) -> Result<()> {
+async fn handle_profile_sync(
+    target: &Target,
+    from: String,
+    to: String,
+    dry_run: bool,
+    include_excluded: bool,
+) -> Result<()> {
     let profile_config = load_profile_config().await?;
 
     // Handle special case for "default" profile (main Claude config)
     let (source_config, from_display_name) = if from == "default" {
-        (Config::load(None).await?, "default".to_string())
+        (Config::load(target).await?, "default".to_string())
     } else {
         // Validate source profile exists
         if !profile_config.profiles.contains_key(&from) {
@@ -335,8 +446,35 @@ async fn handle_profile_sync(from: String, to: String, dry_run: bool) -> Result<
 
     let target_config = load_profile_snapshot(&to).await?;
 
+    let mut source_config = source_config;
+    let withheld = if include_excluded {
+        Vec::new()
+    } else {
+        ServerMetadata::load(target)?.withhold_excluded(&mut source_config)
+    };
+    // A withheld server the target already has keeps the target's own copy
+    for name in &withheld {
+        if let Some(server) = target_config.mcp_servers.get(name) {
+            source_config
+                .mcp_servers
+                .insert(name.clone(), server.clone());
+        }
+        if let Some(server) = target_config.disabled_servers.get(name) {
+            source_config
+                .disabled_servers
+                .insert(name.clone(), server.clone());
+        }
+    }
+
     if dry_run {
-        preview_profile_sync(&source_config, &target_config, &from_display_name, &to).await?;
+        preview_profile_sync(
+            &source_config,
+            &target_config,
+            &from_display_name,
+            &to,
+            &withheld,
+        )
+        .await?;
         return Ok(());
     }
 
@@ -350,19 +488,35 @@ async fn handle_profile_sync(from: String, to: String, dry_run: bool) -> Result<
     );
 
     // Save source config as snapshot for target profile
-    save_profile_snapshot(&to, &source_config).await?;
+    let mut transaction = Transaction::new(target)?;
+    stage_profile_snapshot(&mut transaction, &to, &source_config)?;
 
     // Update profile metadata with new server count
-    update_profile_server_count(Some(&to)).await?;
+    let mut profile_config = profile_config;
+    if let Some(profile_info) = profile_config.profiles.get_mut(&to) {
+        profile_info.server_count = source_config.mcp_servers.len();
+        stage_profile_config(&mut transaction, &profile_config)?;
+    }
+    transaction.commit()?;
 
     println!("{}", "✓ Configuration synced successfully".green());
-    println!("  Servers copied: {}", source_config.mcp_servers.len());
+    println!(
+        "  Servers copied: {}",
+        source_config
+            .mcp_servers
+            .keys()
+            .filter(|name| !withheld.contains(name))
+            .count()
+    );
+    if let Some(note) = crate::metadata::describe_withheld(&withheld) {
+        println!("  {}", note);
+    }
 
     Ok(())
 }
 
 /// Delete a profile
-async fn handle_profile_delete(name: String, force: bool) -> Result<()> {
+async fn handle_profile_delete(name: String, force: bool, dry_run: bool, zone: Zone) -> Result<()> {
     let mut profile_config = load_profile_config().await?;
 
     if !profile_config.profiles.contains_key(&name) {
@@ -370,18 +524,37 @@ async fn handle_profile_delete(name: String, force: bool) -> Result<()> {
     }
 
     // Check if it's the current profile
-    if profile_config.current_profile.as_ref() == Some(&name) {
+    let is_current = profile_config.current_profile.as_ref() == Some(&name);
+    if is_current {
         if !force {
             return Err(anyhow!("Cannot delete current profile '{}'. Switch to another profile first or use --force", name));
         }
         profile_config.current_profile = None;
     }
 
+    if dry_run {
+        let mut plan = PlannedDeletions::new();
+        plan.add_file(&get_profile_snapshot_path(&name)?);
+        plan.add_entry(format!(
+            "profile '{}' in {}",
+            name,
+            get_profiles_config_path()?.display()
+        ));
+        if is_current {
+            plan.add_entry("current profile setting");
+        }
+        plan.print();
+        return Ok(());
+    }
+
     if !force {
         println!("Are you sure you want to delete profile '{}'?", name.red());
         if let Some(profile_info) = profile_config.profiles.get(&name) {
             println!("  Servers: {}", profile_info.server_count);
-            println!("  Created: {}", profile_info.created_at.format("%Y-%m-%d"));
+            println!(
+                "  Created: {}",
+                timefmt::format_date(profile_info.created_at, zone)
+            );
         }
         println!();
         print!("This action cannot be undone. Continue? [y/N]: ");
@@ -413,7 +586,7 @@ async fn handle_profile_delete(name: String, force: bool) -> Result<()> {
 }
 
 /// Save current configuration to profile
-async fn handle_profile_save(name: Option<String>) -> Result<()> {
+async fn handle_profile_save(target: &Target, name: Option<String>) -> Result<()> {
     let profile_config = load_profile_config().await?;
 
     // Determine which profile to save to
@@ -433,13 +606,12 @@ async fn handle_profile_save(name: Option<String>) -> Result<()> {
     };
 
     // Load current main config
-    let main_config = Config::load(None).await?;
-
-    // Save as snapshot
-    save_profile_snapshot(&target_profile, &main_config).await?;
+    let main_config = Config::load(target).await?;
 
-    // Update profile metadata
-    update_profile_server_count(Some(&target_profile)).await?;
+    // Save as snapshot, with the profile's server count
+    let mut transaction = Transaction::new(target)?;
+    stage_server_count(&mut transaction, Some(&target_profile), &main_config).await?;
+    transaction.commit()?;
 
     println!(
         "{}",
@@ -466,6 +638,7 @@ async fn preview_profile_sync(
     target: &Config,
     from_name: &str,
     to_name: &str,
+    withheld: &[String],
 ) -> Result<()> {
     println!("{}", "Profile Sync Preview".cyan().bold());
     println!("{}", "───────────────────".cyan());
@@ -485,7 +658,7 @@ async fn preview_profile_sync(
     let mut new_servers = Vec::new();
     let mut overwritten_servers = Vec::new();
 
-    for name in source.mcp_servers.keys() {
+    for name in source.mcp_servers.keys().filter(|n| !withheld.contains(n)) {
         if target.mcp_servers.contains_key(name) {
             overwritten_servers.push(name);
         } else {
@@ -524,13 +697,18 @@ async fn preview_profile_sync(
         println!();
     }
 
+    if let Some(note) = crate::metadata::describe_withheld(withheld) {
+        println!("{}", note.yellow());
+        println!();
+    }
+
     println!("Run without --dry-run to apply these changes.");
 
     Ok(())
 }
 
 /// Load profile configuration
-async fn load_profile_config() -> Result<ProfileConfig> {
+pub async fn load_profile_config() -> Result<ProfileConfig> {
     let profile_path = get_profiles_config_path()?;
 
     if !profile_path.exists() {
@@ -557,6 +735,12 @@ async fn save_profile_config(config: &ProfileConfig) -> Result<()> {
     Ok(())
 }
 
+fn stage_profile_config(transaction: &mut Transaction, config: &ProfileConfig) -> Result<()> {
+    let content = serde_json::to_string_pretty(config)?;
+    transaction.write(get_profiles_config_path()?, content);
+    Ok(())
+}
+
 /// Get path to profiles configuration file
 fn get_profiles_config_path() -> Result<PathBuf> {
     let config_dir = utils::get_config_dir()?;
@@ -600,6 +784,10 @@ pub enum ProfileCommands {
     Create {
         /// Profile name
         name: String,
+        /// Start with the servers of `current` (the live config), another
+        /// profile, or `backup:<name>`
+        #[arg(long, value_name = "SOURCE")]
+        from: Option<String>,
     },
     /// List available profiles
     List,
@@ -619,6 +807,9 @@ pub enum ProfileCommands {
         /// Preview changes without applying
         #[arg(long)]
         dry_run: bool,
+        /// Also copy servers marked exclude_from_share
+        #[arg(long)]
+        include_excluded: bool,
     },
     /// Delete profile
     Delete {
@@ -627,12 +818,21 @@ pub enum ProfileCommands {
         /// Force deletion without confirmation
         #[arg(long)]
         force: bool,
+        /// Show what would be deleted without deleting it
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Save current configuration to profile
     Save {
         /// Profile name (defaults to current profile)
         name: Option<String>,
     },
+    /// Report servers whose credentials differ between profiles
+    Audit {
+        /// Output findings as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[cfg(test)]
@@ -0,0 +1,154 @@
+use crate::templates::Template;
+use crate::utils;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Variable values last used with each template, offered as prompt defaults
+///
+/// Secret variables are never recorded.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateAnswers {
+    #[serde(default)]
+    pub templates: HashMap<String, HashMap<String, serde_json::Value>>,
+}
+
+impl TemplateAnswers {
+    /// Load recorded answers, returning an empty set if none have been recorded
+    pub fn load() -> Result<Self> {
+        let path = get_answers_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read template answers: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse template answers: {}", path.display()))
+    }
+
+    /// Save recorded answers
+    pub fn save(&self) -> Result<()> {
+        let path = get_answers_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content =
+            serde_json::to_string_pretty(self).context("Failed to serialize template answers")?;
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write template answers: {}", path.display()))
+    }
+
+    /// Values last used with `template`, secret ones left out
+    pub fn recalled(&self, template: &Template) -> HashMap<String, serde_json::Value> {
+        self.templates
+            .get(&template.name)
+            .into_iter()
+            .flatten()
+            .filter(|(name, _)| template.variables.contains_key(*name) && !template.is_secret(name))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
+
+    /// Record the non-secret values a server was added with
+    ///
+    /// Values for variables not given this time are kept from earlier.
+    pub fn remember(&mut self, template: &Template, values: &HashMap<String, serde_json::Value>) {
+        let answers = self.templates.entry(template.name.clone()).or_default();
+        for (name, value) in values {
+            if !template.is_secret(name) {
+                answers.insert(name.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Whether answers are recorded and offered again, turned off with
+/// `MCP_FORGE_RECALL=0`
+pub fn recall_enabled() -> bool {
+    recall_enabled_from(std::env::var("MCP_FORGE_RECALL").ok().as_deref())
+}
+
+fn recall_enabled_from(setting: Option<&str>) -> bool {
+    !matches!(
+        setting.map(|value| value.trim().to_lowercase()).as_deref(),
+        Some("0" | "false" | "no" | "off")
+    )
+}
+
+/// Get path to the template answers file
+fn get_answers_path() -> Result<PathBuf> {
+    Ok(utils::get_config_dir()?.join("template_answers.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn template() -> Template {
+        serde_json::from_value(json!({
+            "name": "postgres",
+            "version": "1.0.0",
+            "description": "Postgres",
+            "author": "Test",
+            "tags": [],
+            "platforms": ["linux", "macos", "windows"],
+            "variables": {
+                "host": { "type": "string", "description": "Host", "default": "db" },
+                "port": { "type": "number", "description": "Port" },
+                "password": { "type": "string", "description": "Password", "secret": true }
+            },
+            "config": { "command": "pg-mcp", "args": [] }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_remember_skips_secrets_and_keeps_earlier_answers() {
+        let template = template();
+        let mut answers = TemplateAnswers::default();
+        answers.remember(
+            &template,
+            &HashMap::from([
+                ("host".to_string(), json!("localhost")),
+                ("port".to_string(), json!(5432)),
+                ("password".to_string(), json!("hunter2")),
+            ]),
+        );
+        answers.remember(
+            &template,
+            &HashMap::from([("host".to_string(), json!("db.internal"))]),
+        );
+
+        let recalled = answers.recalled(&template);
+        assert_eq!(recalled.len(), 2);
+        assert_eq!(recalled["host"], json!("db.internal"));
+        assert_eq!(recalled["port"], json!(5432));
+    }
+
+    #[test]
+    fn test_recalled_ignores_variables_the_template_no_longer_has() {
+        let template = template();
+        let answers: TemplateAnswers = serde_json::from_value(json!({
+            "templates": {
+                "postgres": { "host": "localhost", "schema": "public", "password": "leaked" }
+            }
+        }))
+        .unwrap();
+
+        let recalled = answers.recalled(&template);
+        assert_eq!(recalled.keys().collect::<Vec<_>>(), vec!["host"]);
+    }
+
+    #[test]
+    fn test_recall_setting() {
+        assert!(recall_enabled_from(None));
+        assert!(recall_enabled_from(Some("1")));
+        assert!(!recall_enabled_from(Some("0")));
+        assert!(!recall_enabled_from(Some(" Off ")));
+    }
+}
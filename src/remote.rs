@@ -0,0 +1,210 @@
+use anyhow::{anyhow, Context, Result};
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Where Claude Desktop keeps its config on macOS and Linux, relative to $HOME
+const DEFAULT_REMOTE_PATHS: [&str; 2] = [
+    "Library/Application Support/Claude/claude_desktop_config.json",
+    ".config/claude/claude_desktop_config.json",
+];
+
+/// Exit status ssh uses for its own failures, as opposed to the remote command's
+const SSH_ERROR_STATUS: i32 = 255;
+
+/// A machine reached with the system `ssh` client, from an `ssh://user@host[:port]` URL
+#[derive(Debug, Clone, PartialEq)]
+pub struct SshTarget {
+    pub destination: String,
+    pub port: Option<u16>,
+}
+
+impl SshTarget {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let url =
+            url::Url::parse(spec).map_err(|e| anyhow!("Invalid SSH target '{}': {}", spec, e))?;
+        if url.scheme() != "ssh" {
+            return Err(anyhow!(
+                "Unsupported remote '{}': expected ssh://[user@]host[:port]",
+                spec
+            ));
+        }
+        let host = url
+            .host_str()
+            .filter(|h| !h.is_empty())
+            .ok_or_else(|| anyhow!("SSH target '{}' has no host", spec))?;
+        if !matches!(url.path(), "" | "/") {
+            return Err(anyhow!(
+                "SSH target '{}' should not include a path; use --remote-path instead",
+                spec
+            ));
+        }
+
+        let destination = if url.username().is_empty() {
+            host.to_string()
+        } else {
+            format!("{}@{}", url.username(), host)
+        };
+        Ok(Self {
+            destination,
+            port: url.port(),
+        })
+    }
+
+    /// Run a shell command on the remote machine, feeding it `input` on stdin
+    ///
+    /// `action` describes the command in errors from the remote side, which are
+    /// reported separately from ssh's own connection and authentication failures.
+    async fn run(&self, script: &str, input: Option<&str>, action: &str) -> Result<String> {
+        let mut command = Command::new("ssh");
+        if let Some(port) = self.port {
+            command.arg("-p").arg(port.to_string());
+        }
+        let mut child = command
+            // Keep a destination starting with `-` from being read as an option
+            .arg("--")
+            .arg(&self.destination)
+            .arg(script)
+            .stdin(if input.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run ssh; is an OpenSSH client installed and on PATH?")?;
+
+        if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+            // If the remote side exits early the write fails; its stderr explains why
+            let _ = stdin.write_all(input.as_bytes()).await;
+        }
+
+        let output = child.wait_with_output().await?;
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        match output.status.code() {
+            Some(0) => Ok(String::from_utf8_lossy(&output.stdout).into_owned()),
+            Some(SSH_ERROR_STATUS) => Err(anyhow!(
+                "SSH connection to {} failed: {}",
+                self.destination,
+                if stderr.is_empty() {
+                    "no details from ssh"
+                } else {
+                    &stderr
+                }
+            )),
+            _ => Err(anyhow!(
+                "Could not {} the Claude config on {}: {}",
+                action,
+                self.destination,
+                stderr
+            )),
+        }
+    }
+}
+
+/// Read the Claude config from a remote machine
+pub async fn read_remote_config(target: &SshTarget, remote_path: Option<&str>) -> Result<String> {
+    let script = format!("{} || exit 1\ncat \"$p\"", locate_script(remote_path));
+    target.run(&script, None, "read").await
+}
+
+/// Overwrite the Claude config on a remote machine, keeping the old file as `.bak`
+///
+/// The new content goes to a temporary file that is moved over the config, so
+/// a dropped connection can't leave it half written.
+///
+/// Returns the remote path written and the backup path, if there was a file to back up.
+pub async fn write_remote_config(
+    target: &SshTarget,
+    remote_path: Option<&str>,
+    content: &str,
+) -> Result<(String, Option<String>)> {
+    let script = format!(
+        "{} || exit 1\n\
+         b=; if [ -f \"$p\" ]; then cp \"$p\" \"$p.bak\" || exit 1; b=\"$p.bak\"; fi\n\
+         mkdir -p \"$(dirname \"$p\")\" || exit 1\n\
+         cat > \"$p.tmp\" && mv \"$p.tmp\" \"$p\" || {{ rm -f \"$p.tmp\"; exit 1; }}\n\
+         printf '%s\\n%s\\n' \"$p\" \"$b\"",
+        locate_script(remote_path)
+    );
+    let output = target.run(&script, Some(content), "write").await?;
+
+    let mut lines = output.lines();
+    let path = lines.next().unwrap_or_default().to_string();
+    let backup = lines
+        .next()
+        .filter(|b| !b.is_empty())
+        .map(|b| b.to_string());
+    Ok((path, backup))
+}
+
+/// Shell snippet that sets `$p` to the config path, failing if there is none
+fn locate_script(remote_path: Option<&str>) -> String {
+    match remote_path {
+        Some(path) => match path.strip_prefix("~/") {
+            Some(rest) => format!("p=\"$HOME\"/{}", shell_quote(rest)),
+            None => format!("p={}", shell_quote(path)),
+        },
+        None => {
+            let candidates: Vec<String> = DEFAULT_REMOTE_PATHS
+                .iter()
+                .map(|path| format!("\"$HOME\"/{}", shell_quote(path)))
+                .collect();
+            format!(
+                "p=; for c in {}; do if [ -f \"$c\" ]; then p=\"$c\"; break; fi; done; \
+                 [ -n \"$p\" ] || {{ echo 'no Claude config found in the default locations; pass --remote-path' >&2; false; }}",
+                candidates.join(" ")
+            )
+        }
+    }
+}
+
+/// Quote a string for a POSIX shell
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ssh_target() {
+        assert_eq!(
+            SshTarget::parse("ssh://me@laptop").unwrap(),
+            SshTarget {
+                destination: "me@laptop".to_string(),
+                port: None,
+            }
+        );
+        assert_eq!(
+            SshTarget::parse("ssh://build.local:2222").unwrap(),
+            SshTarget {
+                destination: "build.local".to_string(),
+                port: Some(2222),
+            }
+        );
+        assert!(SshTarget::parse("me@laptop").is_err());
+        assert!(SshTarget::parse("https://laptop").is_err());
+        assert!(SshTarget::parse("ssh://me@laptop/etc/config.json").is_err());
+    }
+
+    #[test]
+    fn test_locate_script_expands_home() {
+        assert_eq!(
+            locate_script(Some("~/claude/config.json")),
+            "p=\"$HOME\"/'claude/config.json'"
+        );
+        assert_eq!(
+            locate_script(Some("/etc/it's.json")),
+            r"p='/etc/it'\''s.json'"
+        );
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("plain"), "'plain'");
+        assert_eq!(shell_quote("it's here"), r"'it'\''s here'");
+    }
+}
@@ -0,0 +1,499 @@
+use crate::github::{ClientOptions, GitHubClient, RepoSource, TemplateRepository};
+use crate::settings::{settings_path, Settings};
+use crate::templates::{
+    self, NamedSource, Template, TemplateCatalog, TemplateManager, TemplateMetadata, TemplateSource,
+};
+use crate::utils;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Result of checking one catalog entry against the file it points at
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryCheck {
+    pub name: String,
+    pub version: String,
+    pub path: String,
+    pub passed: bool,
+    pub problems: Vec<String>,
+    /// Checks that could not be run, e.g. an unsupported checksum algorithm
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+impl EntryCheck {
+    fn new(key: &str, entry: &TemplateMetadata) -> Self {
+        Self {
+            name: key.to_string(),
+            version: entry.version.clone(),
+            path: entry.path.clone(),
+            passed: true,
+            problems: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    fn fail(&mut self, problem: String) {
+        self.passed = false;
+        self.problems.push(problem);
+    }
+}
+
+/// Catalog keys sharing each path that more than one entry points at
+pub fn duplicate_paths(catalog: &TemplateCatalog) -> HashMap<String, Vec<String>> {
+    let mut by_path: HashMap<String, Vec<String>> = HashMap::new();
+    for (key, entry) in &catalog.templates {
+        by_path
+            .entry(entry.path.clone())
+            .or_default()
+            .push(key.clone());
+    }
+    by_path.retain(|_, keys| keys.len() > 1);
+    for keys in by_path.values_mut() {
+        keys.sort();
+    }
+    by_path
+}
+
+/// Check a fetched template file against its catalog entry
+fn check_content(entry: &TemplateMetadata, content: &str, check: &mut EntryCheck) {
+    if let Some(expected) = &entry.checksum {
+        match expected.split_once(':') {
+            Some(("fnv1a64", _)) => {
+                let actual = utils::content_hash(content.as_bytes());
+                if !actual.eq_ignore_ascii_case(expected) {
+                    check.fail(format!(
+                        "checksum mismatch: catalog has {}, file hashes to {}",
+                        expected, actual
+                    ));
+                }
+            }
+            Some((algorithm, _)) => check.notes.push(format!(
+                "checksum not verified: unsupported algorithm '{}'",
+                algorithm
+            )),
+            None => check.fail(format!(
+                "malformed checksum '{}'; expected algorithm:hex",
+                expected
+            )),
+        }
+    }
+
+    let template: Template = match serde_json::from_str(content) {
+        Ok(template) => template,
+        Err(e) => {
+            check.fail(format!("does not parse as a template: {}", e));
+            return;
+        }
+    };
+    if template.name != entry.name {
+        check.fail(format!(
+            "name mismatch: catalog has '{}', template has '{}'",
+            entry.name, template.name
+        ));
+    }
+    if template.version != entry.version {
+        check.fail(format!(
+            "version mismatch: catalog has {}, template has {}",
+            entry.version, template.version
+        ));
+    }
+    for problem in templates::validate_template(&template) {
+        check.fail(problem);
+    }
+}
+
+/// Fetch every catalog entry's file, at most `jobs` at a time, and check it
+///
+/// Results are sorted by catalog key.
+pub async fn check_catalog(
+    client: Arc<GitHubClient>,
+    catalog: &TemplateCatalog,
+    jobs: usize,
+) -> Result<Vec<EntryCheck>> {
+    let duplicates = duplicate_paths(catalog);
+    let semaphore = Arc::new(Semaphore::new(jobs.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for (key, entry) in &catalog.templates {
+        let mut check = EntryCheck::new(key, entry);
+        if let Some(keys) = duplicates.get(&entry.path) {
+            let others: Vec<&str> = keys
+                .iter()
+                .filter(|other| *other != key)
+                .map(String::as_str)
+                .collect();
+            check.fail(format!("path is shared with {}", others.join(", ")));
+        }
+
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        let entry = entry.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            if let Err(e) = client.pace().await {
+                check.fail(e.to_string());
+                return check;
+            }
+            match client.fetch_file(&entry.path).await {
+                Ok(content) => check_content(&entry, &content, &mut check),
+                Err(e) => check.fail(format!("path does not resolve: {}", e)),
+            }
+            check
+        });
+    }
+
+    let mut checks = Vec::new();
+    while let Some(check) = tasks.join_next().await {
+        checks.push(check?);
+    }
+    checks.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(checks)
+}
+
+/// Handle `template repo check`
+pub async fn handle_repo_check(jobs: usize, json: bool, github: &ClientOptions) -> Result<()> {
+    let client = Arc::new(GitHubClient::for_repo(
+        TemplateRepository::configured(github)?,
+        github,
+    )?);
+
+    if !json {
+        println!(
+            "{}",
+            format!(
+                "Checking template catalog of {}...",
+                client.repo().describe()
+            )
+            .cyan()
+        );
+    }
+    let catalog = client.fetch_template_catalog().await?;
+    let checks = check_catalog(client, &catalog, jobs).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&checks)?);
+    } else {
+        display_checks(&checks);
+    }
+
+    let failed = checks.iter().filter(|c| !c.passed).count();
+    if failed > 0 {
+        return Err(anyhow!(
+            "{} of {} catalog entry(ies) failed the check",
+            failed,
+            checks.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Handle `template repo set`: save the repository templates come from
+pub fn handle_repo_set(spec: &str) -> Result<()> {
+    let repo = TemplateRepository::parse(spec)?;
+    let mut settings = Settings::load()?;
+    // The default repository is left unsaved, so a later change of default applies
+    settings.template_repo =
+        Some(repo.describe()).filter(|s| *s != TemplateRepository::default().describe());
+    settings.save()?;
+
+    println!(
+        "{}",
+        format!("✓ Templates now come from {}", repo.describe()).green()
+    );
+    println!("  Run `mcp-forge template refresh` to download its catalog");
+    Ok(())
+}
+
+/// Handle `template repo get`: show the repository in use and why
+pub fn handle_repo_get(github: &ClientOptions) -> Result<()> {
+    let (repo, source) = TemplateRepository::configured_with_source(github)?;
+    let source = match source {
+        RepoSource::Flag => "--repo".to_string(),
+        RepoSource::Settings => format!("set in {}", settings_path()?.display()),
+        RepoSource::Default => "default".to_string(),
+    };
+    println!("{} ({})", repo.describe(), source.dimmed());
+    Ok(())
+}
+
+/// Handle `template source add`: save a source to consult after the others
+pub fn handle_source_add(name: &str, spec: &str) -> Result<()> {
+    templates::check_source_name(name)?;
+    let source = TemplateSource::parse(spec)?;
+    let mut settings = Settings::load()?;
+    if settings.template_sources.iter().any(|s| s.name == name) {
+        return Err(anyhow!(
+            "Template source '{}' already exists; remove it first to change it",
+            name
+        ));
+    }
+    settings.template_sources.push(NamedSource {
+        name: name.to_string(),
+        source: source.clone(),
+    });
+    settings.save()?;
+
+    println!(
+        "{}",
+        format!("✓ Added template source '{}' ({})", name, source).green()
+    );
+    if !matches!(source, TemplateSource::Dir(_)) {
+        println!("  Run `mcp-forge template refresh` to download its catalog");
+    }
+    Ok(())
+}
+
+/// Handle `template source remove`: forget an added source and its cache
+pub fn handle_source_remove(name: &str, github: &ClientOptions) -> Result<()> {
+    if name == templates::DEFAULT_SOURCE {
+        return Err(anyhow!(
+            "The default source can't be removed; use `mcp-forge template repo set` to change it"
+        ));
+    }
+    let mut settings = Settings::load()?;
+    let Some(index) = settings
+        .template_sources
+        .iter()
+        .position(|s| s.name == name)
+    else {
+        return Err(anyhow!("Unknown template source '{}'", name));
+    };
+    settings.template_sources.remove(index);
+    settings.save()?;
+    TemplateManager::new(github)?.clear_source_cache(name)?;
+
+    println!(
+        "{}",
+        format!("✓ Removed template source '{}'", name).green()
+    );
+    Ok(())
+}
+
+/// Handle `template source list`: show every source in the order consulted
+pub fn handle_source_list(github: &ClientOptions) -> Result<()> {
+    let mut sources = vec![NamedSource {
+        name: templates::DEFAULT_SOURCE.to_string(),
+        source: TemplateSource::Github(TemplateRepository::configured(github)?),
+    }];
+    sources.extend(Settings::load()?.template_sources);
+
+    let name_width = sources.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    for source in &sources {
+        println!(
+            "  {:<name_width$}  {}",
+            source.name,
+            source.source,
+            name_width = name_width
+        );
+    }
+    Ok(())
+}
+
+fn display_checks(checks: &[EntryCheck]) {
+    if checks.is_empty() {
+        println!("{}", "The catalog has no entries.".yellow());
+        return;
+    }
+
+    let name_width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    let version_width = checks.iter().map(|c| c.version.len()).max().unwrap_or(0);
+
+    println!();
+    for check in checks {
+        let symbol = if check.passed {
+            "✓".green()
+        } else {
+            "✗".red()
+        };
+        println!(
+            "  {} {:<name_width$}  {:<version_width$}  {}",
+            symbol,
+            check.name,
+            check.version,
+            check.path,
+            name_width = name_width,
+            version_width = version_width
+        );
+        for problem in &check.problems {
+            println!("      {}", problem.red());
+        }
+        for note in &check.notes {
+            println!("      {}", note.dimmed());
+        }
+    }
+
+    let mut problems: BTreeMap<&str, usize> = BTreeMap::new();
+    for problem in checks.iter().flat_map(|c| &c.problems) {
+        let kind = problem.split(':').next().unwrap_or(problem);
+        *problems.entry(kind).or_default() += 1;
+    }
+    let passed = checks.iter().filter(|c| c.passed).count();
+    println!();
+    println!(
+        "Summary: {} passed, {} failed",
+        passed.to_string().green(),
+        (checks.len() - passed).to_string().red()
+    );
+    for (kind, count) in problems {
+        println!("  {} × {}", count, kind);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serve repository files through a fake contents API, routed by request path
+    ///
+    /// Paths missing from `files` get a 404.
+    async fn mock_repo(files: Vec<(&'static str, String)>) -> String {
+        let files: HashMap<&str, String> = files.into_iter().collect();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let path = request
+                    .split_whitespace()
+                    .nth(1)
+                    .and_then(|target| target.split("/contents/").nth(1))
+                    .and_then(|rest| rest.split('?').next())
+                    .unwrap_or_default();
+
+                let (status, body) = match files.get(path) {
+                    Some(content) => (
+                        200,
+                        serde_json::json!({ "content": content, "encoding": "utf-8" }).to_string(),
+                    ),
+                    None => (404, r#"{"message":"Not Found"}"#.to_string()),
+                };
+                let response = format!(
+                    "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    fn template(name: &str, version: &str, config: &str) -> String {
+        format!(
+            r#"{{"name":"{}","version":"{}","description":"d","author":"a","tags":[],"platforms":[],"variables":{{}},"config":{}}}"#,
+            name, version, config
+        )
+    }
+
+    fn entry(name: &str, version: &str, path: &str, checksum: Option<String>) -> String {
+        serde_json::json!({
+            "name": name,
+            "version": version,
+            "description": "d",
+            "author": "a",
+            "tags": [],
+            "platforms": [],
+            "category": "official",
+            "path": path,
+            "checksum": checksum,
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_check_catalog_reports_each_failure() {
+        let good = template("good", "1.0.0", r#"{"command":"node"}"#);
+        let catalog = format!(
+            r#"{{"version":"1","last_updated":"now","templates":{{
+                "good": {},
+                "stale": {},
+                "broken": {},
+                "missing": {},
+                "twin": {},
+                "tampered": {},
+                "future": {}
+            }}}}"#,
+            entry(
+                "good",
+                "1.0.0",
+                "templates/good.json",
+                Some(utils::content_hash(good.as_bytes()))
+            ),
+            entry("stale", "2.0.0", "templates/stale.json", None),
+            entry("broken", "1.0.0", "templates/broken.json", None),
+            entry("missing", "1.0.0", "templates/missing.json", None),
+            entry("twin", "1.0.0", "templates/good.json", None),
+            entry(
+                "tampered",
+                "1.0.0",
+                "templates/tampered.json",
+                Some("fnv1a64:0000000000000000".to_string())
+            ),
+            entry(
+                "future",
+                "1.0.0",
+                "templates/future.json",
+                Some("sha256:abc".to_string())
+            ),
+        );
+        let base_url = mock_repo(vec![
+            ("catalog.json", catalog),
+            ("templates/good.json", good),
+            (
+                "templates/stale.json",
+                template("stale", "1.0.0", r#"{"command":"node"}"#),
+            ),
+            (
+                "templates/broken.json",
+                template("broken", "1.0.0", r#"{"command":"node","url":"http://x"}"#),
+            ),
+            (
+                "templates/tampered.json",
+                template("tampered", "1.0.0", r#"{"command":"node"}"#),
+            ),
+            (
+                "templates/future.json",
+                template("future", "1.0.0", r#"{"command":"node"}"#),
+            ),
+        ])
+        .await;
+
+        let client = Arc::new(GitHubClient::with_base_url(&base_url));
+        let catalog = client.fetch_template_catalog().await.unwrap();
+        let checks = check_catalog(client, &catalog, 2).await.unwrap();
+        let by_name: HashMap<&str, &EntryCheck> =
+            checks.iter().map(|c| (c.name.as_str(), c)).collect();
+
+        assert_eq!(
+            checks.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(),
+            ["broken", "future", "good", "missing", "stale", "tampered", "twin"]
+        );
+        assert_eq!(by_name["good"].problems, ["path is shared with twin"]);
+        assert_eq!(by_name["twin"].problems.len(), 2);
+        assert!(by_name["twin"].problems[1].starts_with("name mismatch"));
+        assert_eq!(
+            by_name["stale"].problems,
+            ["version mismatch: catalog has 2.0.0, template has 1.0.0"]
+        );
+        assert!(by_name["broken"].problems[0].starts_with("invalid config"));
+        assert!(by_name["missing"].problems[0].contains("not found in repository"));
+        assert!(by_name["tampered"].problems[0].starts_with("checksum mismatch"));
+
+        let future = by_name["future"];
+        assert!(future.passed);
+        assert_eq!(
+            future.notes,
+            ["checksum not verified: unsupported algorithm 'sha256'"]
+        );
+    }
+}
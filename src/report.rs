@@ -0,0 +1,191 @@
+use crate::config::{Config, McpServer};
+use crate::metadata::ServerMetadata;
+use crate::timefmt::{self, Zone};
+use crate::utils;
+use chrono::{DateTime, Utc};
+
+/// Render the config as a Markdown inventory for wikis and hand-overs
+///
+/// Servers are listed by name so the same config always renders the same
+/// document, apart from the generation time. Secrets are masked.
+pub fn render_markdown(
+    config: &Config,
+    metadata: &ServerMetadata,
+    profile: Option<&str>,
+    generated_at: DateTime<Utc>,
+    zone: Zone,
+) -> String {
+    let mut names: Vec<&String> = config.mcp_servers.keys().collect();
+    names.sort();
+
+    let mut out = String::from("# MCP server inventory\n\n");
+    out.push_str(&format!(
+        "- Generated: {}\n",
+        timefmt::format_timestamp(generated_at, zone)
+    ));
+    out.push_str(&format!("- Profile: {}\n", profile.unwrap_or("default")));
+    out.push_str(&format!("- Servers: {}\n", names.len()));
+    if !config.disabled_servers.is_empty() {
+        let mut disabled: Vec<&String> = config.disabled_servers.keys().collect();
+        disabled.sort();
+        out.push_str(&format!(
+            "- Disabled: {}\n",
+            disabled
+                .iter()
+                .map(|name| code(name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    if names.is_empty() {
+        out.push_str("\nNo servers are configured.\n");
+        return out;
+    }
+
+    out.push_str("\n## Summary\n\n");
+    out.push_str("| Name | Type | Runtime | Tags |\n");
+    out.push_str("| --- | --- | --- | --- |\n");
+    for name in &names {
+        let server = &config.mcp_servers[*name];
+        let tags = metadata
+            .get(name)
+            .map(|p| p.tags.join(", "))
+            .filter(|tags| !tags.is_empty())
+            .unwrap_or_else(|| "-".to_string());
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            cell(name),
+            server.server_type(),
+            cell(&crate::validation::runtime_label(server)),
+            cell(&tags)
+        ));
+    }
+
+    for name in names {
+        out.push_str(&format!("\n## {}\n\n", name));
+        server_section(&mut out, name, &config.mcp_servers[name], metadata, zone);
+    }
+
+    out
+}
+
+fn server_section(
+    out: &mut String,
+    name: &str,
+    server: &McpServer,
+    metadata: &ServerMetadata,
+    zone: Zone,
+) {
+    if let Some(url) = &server.url {
+        out.push_str(&format!(
+            "- URL: {}\n",
+            code(&utils::mask_sensitive_url(url))
+        ));
+    }
+    if let Some(command) = &server.command {
+        out.push_str(&format!("- Command: {}\n", code(command)));
+    }
+    if let Some(args) = server.args.as_ref().filter(|args| !args.is_empty()) {
+        let args: Vec<String> = args.iter().map(|arg| code(arg)).collect();
+        out.push_str(&format!("- Args: {}\n", args.join(" ")));
+    }
+    if let Some(env) = server.env.as_ref().filter(|env| !env.is_empty()) {
+        out.push_str("- Environment:\n");
+        let mut keys: Vec<&String> = env.keys().collect();
+        keys.sort();
+        for key in keys {
+            let value = utils::mask_sensitive_env_value(key, &env[key]);
+            out.push_str(&format!("  - {} = {}\n", code(key), code(&value)));
+        }
+    }
+
+    let provenance = metadata.get(name);
+    if let Some(requirements) = provenance.and_then(|p| p.requirements.as_ref()) {
+        let mut requirements: Vec<_> = requirements.iter().collect();
+        requirements.sort();
+        let list: Vec<String> = requirements
+            .into_iter()
+            .map(|(name, version)| format!("{} {}", name, version))
+            .collect();
+        out.push_str(&format!("- Requirements: {}\n", list.join(", ")));
+    }
+    if let Some(notes) = provenance.and_then(|p| p.notes.as_deref()) {
+        out.push_str("- Notes:\n");
+        for line in notes.lines() {
+            out.push_str(&format!("  > {}\n", line));
+        }
+    }
+    match (provenance, metadata.presets.get(name)) {
+        (Some(p), _) => out.push_str(&format!(
+            "- Added from template {} v{}, {}\n",
+            code(&p.template),
+            p.version,
+            timefmt::format_date(p.added_at, zone)
+        )),
+        (None, Some(preset)) => {
+            out.push_str(&format!("- Added from preset {}\n", code(preset)));
+        }
+        (None, None) => out.push_str("- Added manually or before tracking\n"),
+    }
+    if let Some(docs_url) = provenance.and_then(|p| p.docs_url.as_deref()) {
+        out.push_str(&format!("- Docs: <{}>\n", docs_url));
+    }
+}
+
+/// Inline code, with a longer fence when the text holds backticks
+fn code(text: &str) -> String {
+    let fence = if text.contains('`') { "``" } else { "`" };
+    let pad = if text.starts_with('`') || text.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+    format!("{0}{1}{2}{1}{0}", fence, pad, text)
+}
+
+/// Text safe to put in a table cell
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render_markdown_masks_secrets_and_sorts_servers() {
+        let config: Config = serde_json::from_str(
+            r#"{"mcpServers": {
+                "web": {"url": "https://api.example.com/mcp?api_key=abcdef123456"},
+                "db": {"command": "npx", "args": ["db-mcp"], "env": {"DB_PASSWORD": "hunter2hunter2", "DB_HOST": "db"}}
+            }}"#,
+        )
+        .unwrap();
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        let markdown = render_markdown(
+            &config,
+            &ServerMetadata::default(),
+            Some("work"),
+            at,
+            Zone::Utc,
+        );
+
+        let header = "- Generated: 2026-03-01T12:00:00Z\n- Profile: work\n- Servers: 2\n";
+        assert!(markdown.contains(header), "{}", markdown);
+        assert!(markdown.contains("| db | command | Node.js | - |\n| web | url | remote | - |"));
+        assert!(markdown.find("## db").unwrap() < markdown.find("## web").unwrap());
+        assert!(!markdown.contains("hunter2hunter2"));
+        assert!(!markdown.contains("abcdef123456"));
+        assert!(markdown.contains("  - `DB_HOST` = `db`\n"));
+    }
+
+    #[test]
+    fn test_code_and_cell_escaping() {
+        assert_eq!(code("npx"), "`npx`");
+        assert_eq!(code("a`b"), "``a`b``");
+        assert_eq!(code("`a"), "`` `a ``");
+        assert_eq!(cell("a|b\nc"), "a\\|b c");
+    }
+}
@@ -0,0 +1,274 @@
+use anyhow::{anyhow, Result};
+use clap::{Arg, ArgAction, Command};
+use serde::Serialize;
+use std::any::TypeId;
+use std::path::PathBuf;
+
+/// Description of the whole command-line interface, for wrapper generators
+#[derive(Debug, Serialize)]
+pub struct CliSchema {
+    pub name: String,
+    pub version: String,
+    pub command: CommandSchema,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommandSchema {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub about: Option<String>,
+    pub args: Vec<ArgSchema>,
+    pub subcommands: Vec<CommandSchema>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArgSchema {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub long: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub short: Option<char>,
+    pub positional: bool,
+    /// `boolean`, `count`, `string`, `integer`, `number` or `path`
+    #[serde(rename = "type")]
+    pub value_type: &'static str,
+    pub multiple: bool,
+    pub required: bool,
+    pub global: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub default: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub possible_values: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+}
+
+impl CliSchema {
+    /// Walk a clap command tree, leaving out hidden commands and arguments
+    pub fn from_command(command: &Command) -> Self {
+        Self {
+            name: command.get_name().to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            command: CommandSchema::from_command(command),
+        }
+    }
+}
+
+impl CommandSchema {
+    fn from_command(command: &Command) -> Self {
+        Self {
+            name: command.get_name().to_string(),
+            about: command.get_about().map(|about| about.to_string()),
+            args: command
+                .get_arguments()
+                .filter(|arg| !arg.is_hide_set())
+                .map(ArgSchema::from_arg)
+                .collect(),
+            subcommands: command
+                .get_subcommands()
+                .filter(|sub| !sub.is_hide_set())
+                .map(CommandSchema::from_command)
+                .collect(),
+        }
+    }
+}
+
+impl ArgSchema {
+    fn from_arg(arg: &Arg) -> Self {
+        let value_type = value_type(arg);
+        Self {
+            name: arg.get_id().to_string(),
+            long: arg.get_long().map(str::to_string),
+            short: arg.get_short(),
+            positional: arg.is_positional(),
+            value_type,
+            multiple: matches!(arg.get_action(), ArgAction::Append),
+            required: arg.is_required_set(),
+            global: arg.is_global_set(),
+            default: arg
+                .get_default_values()
+                .iter()
+                .map(|v| v.to_string_lossy().into_owned())
+                .collect(),
+            // Flags report true/false here, which says nothing their type doesn't
+            possible_values: if matches!(value_type, "boolean" | "count") {
+                Vec::new()
+            } else {
+                arg.get_possible_values()
+                    .iter()
+                    .filter(|v| !v.is_hide_set())
+                    .map(|v| v.get_name().to_string())
+                    .collect()
+            },
+            help: arg.get_help().map(|help| help.to_string()),
+        }
+    }
+
+    /// How the argument appears on the command line, e.g. `--limit <LIMIT>` or `[NAME]`
+    fn usage(&self) -> String {
+        let value = self.name.to_uppercase();
+        if self.positional {
+            return if self.required {
+                format!("<{}>", value)
+            } else {
+                format!("[{}]", value)
+            };
+        }
+
+        let flag = match (&self.long, self.short) {
+            (Some(long), Some(short)) => format!("-{}, --{}", short, long),
+            (Some(long), None) => format!("--{}", long),
+            (None, Some(short)) => format!("-{}", short),
+            (None, None) => self.name.clone(),
+        };
+        match self.value_type {
+            "boolean" | "count" => flag,
+            _ => format!("{} <{}>", flag, value),
+        }
+    }
+}
+
+fn value_type(arg: &Arg) -> &'static str {
+    match arg.get_action() {
+        ArgAction::SetTrue | ArgAction::SetFalse => return "boolean",
+        ArgAction::Count => return "count",
+        _ => {}
+    }
+
+    let id = arg.get_value_parser().type_id();
+    if id == TypeId::of::<bool>() {
+        "boolean"
+    } else if [
+        TypeId::of::<u8>(),
+        TypeId::of::<u16>(),
+        TypeId::of::<u32>(),
+        TypeId::of::<u64>(),
+        TypeId::of::<usize>(),
+        TypeId::of::<i32>(),
+        TypeId::of::<i64>(),
+    ]
+    .iter()
+    .any(|t| id == *t)
+    {
+        "integer"
+    } else if id == TypeId::of::<f32>() || id == TypeId::of::<f64>() {
+        "number"
+    } else if id == TypeId::of::<PathBuf>() {
+        "path"
+    } else {
+        "string"
+    }
+}
+
+/// Render the schema as a Markdown command reference
+pub fn render_markdown(schema: &CliSchema) -> String {
+    let mut output = format!(
+        "# {} command reference\n\nGenerated by `{} schema --format markdown` for version {}.\n",
+        schema.name, schema.name, schema.version
+    );
+    render_command_markdown(&schema.command, &schema.name, &mut output);
+    output
+}
+
+fn render_command_markdown(command: &CommandSchema, path: &str, output: &mut String) {
+    output.push_str(&format!("\n## `{}`\n", path));
+    if let Some(about) = &command.about {
+        output.push_str(&format!("\n{}\n", about));
+    }
+
+    if !command.args.is_empty() {
+        output.push_str("\n| Argument | Type | Default | Description |\n");
+        output.push_str("| --- | --- | --- | --- |\n");
+        for arg in &command.args {
+            let mut description = arg.help.clone().unwrap_or_default();
+            if !arg.possible_values.is_empty() {
+                description.push_str(&format!(" (one of: {})", arg.possible_values.join(", ")));
+            }
+            if arg.multiple {
+                description.push_str(" (repeatable)");
+            }
+            if arg.global {
+                description.push_str(" (global)");
+            }
+            output.push_str(&format!(
+                "| `{}` | {} | {} | {} |\n",
+                arg.usage(),
+                arg.value_type,
+                arg.default.join(", "),
+                description.trim().replace('|', "\\|")
+            ));
+        }
+    }
+
+    for sub in &command.subcommands {
+        render_command_markdown(sub, &format!("{} {}", path, sub.name), output);
+    }
+}
+
+/// Handle `mcp-forge schema`
+pub fn handle_schema(command: Command, format: &str) -> Result<()> {
+    let schema = CliSchema::from_command(&command);
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&schema)?),
+        "markdown" | "md" => print!("{}", render_markdown(&schema)),
+        other => {
+            return Err(anyhow!(
+                "Unsupported schema format '{}'. Use json or markdown",
+                other
+            ))
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Command {
+        Command::new("tool")
+            .about("A tool")
+            .arg(
+                Arg::new("verbose")
+                    .long("verbose")
+                    .short('v')
+                    .action(ArgAction::SetTrue),
+            )
+            .subcommand(
+                Command::new("run")
+                    .about("Run it")
+                    .arg(Arg::new("name").required(true))
+                    .arg(
+                        Arg::new("jobs")
+                            .long("jobs")
+                            .value_parser(clap::value_parser!(usize))
+                            .default_value("4"),
+                    )
+                    .arg(Arg::new("secret").long("secret").hide(true)),
+            )
+            .subcommand(Command::new("internal").hide(true))
+    }
+
+    #[test]
+    fn test_schema_walks_command_tree() {
+        let schema = CliSchema::from_command(&sample());
+        assert_eq!(schema.command.args[0].value_type, "boolean");
+        assert_eq!(schema.command.subcommands.len(), 1);
+
+        let run = &schema.command.subcommands[0];
+        assert_eq!(run.args.len(), 2);
+        assert!(run.args[0].positional && run.args[0].required);
+        assert_eq!(run.args[1].value_type, "integer");
+        assert_eq!(run.args[1].default, ["4"]);
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let markdown = render_markdown(&CliSchema::from_command(&sample()));
+        assert!(markdown.contains("\n## `tool run`\n\nRun it\n"));
+        assert!(markdown.contains("| `<NAME>` | string |  |  |"));
+        assert!(markdown.contains("| `--jobs <JOBS>` | integer | 4 |  |"));
+        assert!(markdown.contains("| `-v, --verbose` | boolean |  |  |"));
+        assert!(!markdown.contains("internal"));
+    }
+}
@@ -1,7 +1,10 @@
 use crate::config::McpServer;
+use crate::metadata::{ServerMetadata, ServerProvenance};
 use chrono::{DateTime, Utc};
+use colored::Colorize;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// Search criteria for filtering servers and templates
 #[derive(Debug, Clone)]
@@ -21,27 +24,70 @@ pub struct ListOptions {
     pub format: Option<String>,
     pub show_requirements: bool,
     pub json: bool,
+    /// Show servers as a dependency tree instead
+    pub tree: bool,
+    /// Show long and base64 environment values in full
+    pub full: bool,
+    /// Show only servers turned off with `disable`
+    pub disabled_only: bool,
+    /// Print just the names, for scripts and shell completion
+    pub names_only: bool,
 }
 
 /// Search ranking for templates
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct SearchRanking {
-    pub relevance_score: f32,
+    pub relevance: RelevanceScore,
+    pub quality: QualityScore,
     pub download_count: u32,
-    pub last_updated: DateTime<Utc>,
-    pub quality_score: f32,
+    pub last_updated: Option<DateTime<Utc>>,
     pub community_rating: f32,
 }
 
-impl Default for SearchRanking {
-    fn default() -> Self {
-        Self {
-            relevance_score: 0.0,
-            download_count: 0,
-            last_updated: Utc::now(),
-            quality_score: 0.0,
-            community_rating: 0.0,
-        }
+/// How well a template matches the search term
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelevanceScore {
+    /// 1.0 exact, 0.8 prefix, 0.6 substring match on the name
+    pub name: f32,
+    /// 0.4 when the description contains the term
+    pub description: f32,
+}
+
+/// Catalog-derived quality signals for a template
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QualityScore {
+    /// 1.0 official, 0.7 community, 0.4 experimental, 0.5 otherwise
+    pub category: f32,
+    /// 0.1 per popular tag, 0.05 per other tag
+    pub tags: f32,
+    /// 0.2 for all three platforms, 0.1 for two
+    pub platforms: f32,
+}
+
+impl RelevanceScore {
+    pub fn total(&self) -> f32 {
+        self.name + self.description
+    }
+}
+
+impl QualityScore {
+    pub fn total(&self) -> f32 {
+        self.category + self.tags + self.platforms
+    }
+}
+
+impl SearchRanking {
+    pub fn relevance_score(&self) -> f32 {
+        self.relevance.total()
+    }
+
+    pub fn quality_score(&self) -> f32 {
+        self.quality.total()
+    }
+
+    /// Combined score used for the default ordering
+    pub fn score(&self) -> f32 {
+        self.relevance_score() + self.quality_score()
     }
 }
 
@@ -53,11 +99,27 @@ pub struct ServerInfo {
     pub args: Vec<String>,
     pub url: Option<String>,
     pub env: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_approve: Option<Vec<String>>,
     pub template: Option<String>,
     pub tags: Vec<String>,
     pub platform: String,
     pub author: Option<String>,
     pub requirements: Option<HashMap<String, String>>,
+    /// Workspace file that declares the server, if it isn't in the config
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_file: Option<PathBuf>,
+    /// Layer the server comes from, when overlays are configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layer: Option<crate::layers::ServerLayer>,
+    /// Turned off with `disable`
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+    /// Documentation link recorded from the server's template
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub docs_url: Option<String>,
 }
 
 impl From<(String, McpServer)> for ServerInfo {
@@ -68,23 +130,58 @@ impl From<(String, McpServer)> for ServerInfo {
             args: server.args.unwrap_or_default(),
             url: server.url,
             env: server.env,
+            timeout: server.timeout,
+            auto_approve: server.auto_approve,
             template: None, // Will be enriched if available
             tags: vec![],   // Will be enriched if available
-            platform: get_current_platform(),
+            platform: crate::utils::platform::os(),
             author: None,       // Will be enriched if available
             requirements: None, // Will be enriched if available
+            workspace_file: None,
+            layer: None,
+            disabled: false,
+            docs_url: None,
+        }
+    }
+}
+
+impl ServerInfo {
+    /// One-line description of what the server runs or connects to
+    pub fn summary(&self) -> String {
+        if let Some(url) = &self.url {
+            format!("URL {}", crate::utils::mask_sensitive_url(url))
+        } else if self.args.is_empty() {
+            self.command.clone()
+        } else {
+            format!("{} {}", self.command, self.args.join(" "))
         }
     }
+
+    /// Fill in template details recorded when the server was added
+    pub fn enrich(&mut self, provenance: &ServerProvenance) {
+        self.template = Some(provenance.template.clone());
+        self.tags = provenance.tags.clone();
+        self.author = Some(provenance.author.clone());
+        self.requirements = provenance.requirements.clone();
+        self.docs_url = provenance.docs_url.clone();
+    }
 }
 
 /// Filter servers based on search criteria
 pub fn filter_servers(
     servers: Vec<(String, McpServer)>,
     criteria: &SearchCriteria,
+    metadata: &ServerMetadata,
 ) -> Vec<ServerInfo> {
     let mut filtered: Vec<ServerInfo> = servers
         .into_iter()
-        .map(ServerInfo::from)
+        .map(|entry| {
+            let mut info = ServerInfo::from(entry);
+            if let Some(provenance) = metadata.get(&info.name) {
+                info.enrich(provenance);
+            }
+            info
+        })
         .filter(|server| matches_criteria(server, criteria))
         .collect();
 
@@ -115,7 +212,11 @@ fn matches_criteria(server: &ServerInfo, criteria: &SearchCriteria) -> bool {
 
     // Check author filter
     if let Some(author) = &criteria.author {
-        if server.author.as_ref() != Some(author) {
+        if !server
+            .author
+            .as_deref()
+            .is_some_and(|a| author_matches(a, author))
+        {
             return false;
         }
     }
@@ -139,6 +240,11 @@ fn matches_criteria(server: &ServerInfo, criteria: &SearchCriteria) -> bool {
     true
 }
 
+/// Case-insensitive substring match used by every `--author` filter
+pub fn author_matches(author: &str, filter: &str) -> bool {
+    author.to_lowercase().contains(&filter.to_lowercase())
+}
+
 /// Sort servers based on specified field
 pub fn sort_servers(mut servers: Vec<ServerInfo>, options: &ListOptions) -> Vec<ServerInfo> {
     if let Some(sort_field) = &options.sort {
@@ -173,6 +279,19 @@ pub fn sort_servers(mut servers: Vec<ServerInfo>, options: &ListOptions) -> Vec<
 
 /// Format servers for output
 pub fn format_servers(servers: &[ServerInfo], options: &ListOptions) -> String {
+    if options.names_only {
+        let mut names: Vec<&str> = servers.iter().map(|server| server.name.as_str()).collect();
+        // Scripts and shell completion want a stable order
+        if options.sort.is_none() {
+            names.sort_unstable();
+        }
+        return if options.json {
+            serde_json::to_string(&names).unwrap_or_else(|_| "[]".to_string())
+        } else {
+            names.join("\n")
+        };
+    }
+
     if options.json {
         return serde_json::to_string_pretty(servers).unwrap_or_else(|_| "[]".to_string());
     }
@@ -199,11 +318,21 @@ fn format_as_table(servers: &[ServerInfo], options: &ListOptions) -> String {
 
     // Rows
     for server in servers {
-        let name = truncate_string(&server.name, 19);
+        let name = match (&server.workspace_file, server.disabled) {
+            (Some(_), _) => truncate_string(&format!("{} [workspace]", server.name), 19),
+            (None, true) => truncate_string(&format!("{} [disabled]", server.name), 19),
+            (None, false) => truncate_string(&server.name, 19),
+        };
         let (type_cmd, details) = if let Some(url) = &server.url {
-            ("URL".to_string(), truncate_string(&crate::utils::mask_sensitive_url(url), 19))
+            (
+                "URL".to_string(),
+                truncate_string(&crate::utils::mask_sensitive_url(url), 19),
+            )
         } else {
-            (truncate_string(&server.command, 19), truncate_string(&server.args.join(" "), 19))
+            (
+                truncate_string(&server.command, 19),
+                truncate_string(&server.args.join(" "), 19),
+            )
         };
 
         output.push_str(&format!(
@@ -242,50 +371,88 @@ fn format_as_default(servers: &[ServerInfo], options: &ListOptions) -> String {
     output.push_str("─────────────────────\n");
 
     for server in servers {
-        output.push_str(&format!("• {}\n", server.name));
-        
-        // Display URL or Command based on server type
-        if let Some(url) = &server.url {
-            let masked_url = crate::utils::mask_sensitive_url(url);
-            output.push_str(&format!("  URL: {}\n", masked_url));
-        } else if !server.command.is_empty() {
-            output.push_str(&format!("  Command: {}\n", server.command));
-            if !server.args.is_empty() {
-                output.push_str(&format!("  Args: {}\n", server.args.join(" ")));
-            }
+        if server.disabled {
+            output.push_str(&format!("• {} {}\n", server.name, "disabled".dimmed()));
+        } else {
+            output.push_str(&format!("• {}\n", server.name));
         }
-
-        if let Some(env) = &server.env {
-            if !env.is_empty() {
-                output.push_str("  Environment:\n");
-                for (key, value) in env {
-                    let masked_value = crate::utils::mask_sensitive_env_value(key, value);
-                    output.push_str(&format!("    {}={}\n", key, masked_value));
-                }
-            }
+        if let Some(file) = &server.workspace_file {
+            output.push_str(&format!("  [workspace] {}\n", file.display()));
         }
-
-        if !server.tags.is_empty() {
-            output.push_str(&format!("  Tags: {}\n", server.tags.join(", ")));
+        if let Some(layer) = &server.layer {
+            output.push_str(&format!("  [layer] {}\n", layer));
         }
 
-        if let Some(author) = &server.author {
-            output.push_str(&format!("  Author: {}\n", author));
+        output.push_str(&format_server_details(
+            server,
+            options.full,
+            options.show_requirements,
+        ));
+        output.push('\n');
+    }
+
+    output.push_str(&format!("Total: {} server(s)\n", servers.len()));
+    output
+}
+
+/// Detail lines for one server, indented under its name: what it runs, its
+/// environment (sensitive values masked) and what its template recorded
+pub fn format_server_details(server: &ServerInfo, full: bool, show_requirements: bool) -> String {
+    let mut output = String::new();
+
+    // Display URL or Command based on server type
+    if let Some(url) = &server.url {
+        let masked_url = crate::utils::mask_sensitive_url(url);
+        output.push_str(&format!("  URL: {}\n", masked_url));
+    } else if !server.command.is_empty() {
+        output.push_str(&format!("  Command: {}\n", server.command));
+        if !server.args.is_empty() {
+            output.push_str(&format!("  Args: {}\n", server.args.join(" ")));
         }
+    }
 
-        if options.show_requirements {
-            if let Some(requirements) = &server.requirements {
-                output.push_str("  Requirements:\n");
-                for (req, version) in requirements {
-                    output.push_str(&format!("    • {}: {}\n", req, version));
-                }
+    if let Some(env) = &server.env {
+        if !env.is_empty() {
+            output.push_str("  Environment:\n");
+            let mut keys: Vec<&String> = env.keys().collect();
+            keys.sort();
+            for key in keys {
+                let value = &env[key];
+                let shown = crate::utils::display::display_env_value(key, value, full);
+                output.push_str(&format!("    {}={}\n", key, shown));
             }
         }
+    }
 
-        output.push('\n');
+    if let Some(timeout) = server.timeout {
+        output.push_str(&format!("  Timeout: {}s\n", timeout));
+    }
+
+    if let Some(tools) = &server.auto_approve {
+        output.push_str(&format!("  Auto-approve: {}\n", tools.join(", ")));
+    }
+
+    if !server.tags.is_empty() {
+        output.push_str(&format!("  Tags: {}\n", server.tags.join(", ")));
+    }
+
+    if let Some(author) = &server.author {
+        output.push_str(&format!("  Author: {}\n", author));
+    }
+
+    if let Some(docs_url) = &server.docs_url {
+        output.push_str(&format!("  Docs: {}\n", docs_url));
+    }
+
+    if show_requirements {
+        if let Some(requirements) = &server.requirements {
+            output.push_str("  Requirements:\n");
+            for (req, version) in requirements {
+                output.push_str(&format!("    • {}: {}\n", req, version));
+            }
+        }
     }
 
-    output.push_str(&format!("Total: {} server(s)\n", servers.len()));
     output
 }
 
@@ -330,12 +497,17 @@ pub fn calculate_ranking(
         0.0
     };
 
-    ranking.relevance_score = name_match + description_match;
+    ranking.relevance = RelevanceScore {
+        name: name_match,
+        description: description_match,
+    };
 
     // Creative ranking factors based on template characteristics
     if let Some(meta) = metadata {
         // Official templates get higher quality score
-        ranking.quality_score = match meta.category.as_str() {
+        ranking.last_updated = meta.updated_at;
+
+        ranking.quality.category = match meta.category.as_str() {
             "official" => 1.0,
             "community" => 0.7,
             "experimental" => 0.4,
@@ -355,7 +527,7 @@ pub fn calculate_ranking(
                 }
             })
             .sum();
-        ranking.quality_score += tag_bonus;
+        ranking.quality.tags = tag_bonus;
 
         // Cross-platform templates get higher score
         let platform_bonus = match meta.platforms.len() {
@@ -363,7 +535,7 @@ pub fn calculate_ranking(
             2 => 0.1, // Two platforms
             _ => 0.0, // Single platform
         };
-        ranking.quality_score += platform_bonus;
+        ranking.quality.platforms = platform_bonus;
 
         // Simulate download count based on template characteristics
         ranking.download_count = match meta.category.as_str() {
@@ -376,14 +548,14 @@ pub fn calculate_ranking(
                     "github" => 6000,
                     _ => 1000,
                 };
-                base + (ranking.quality_score * 1000.0) as u32
+                base + (ranking.quality_score() * 1000.0) as u32
             }
-            "community" => (ranking.quality_score * 2000.0) as u32 + 100,
-            _ => (ranking.quality_score * 500.0) as u32 + 10,
+            "community" => (ranking.quality_score() * 2000.0) as u32 + 100,
+            _ => (ranking.quality_score() * 500.0) as u32 + 10,
         };
 
         // Simulate community rating
-        ranking.community_rating = ranking.quality_score * 5.0; // Scale to 0-5 stars
+        ranking.community_rating = ranking.quality_score() * 5.0; // Scale to 0-5 stars
     }
 
     ranking
@@ -402,13 +574,13 @@ pub fn rank_templates(
             (template, ranking)
         })
         // Filter out templates with zero relevance (no match to search term)
-        .filter(|(_, ranking)| ranking.relevance_score > 0.0)
+        .filter(|(_, ranking)| ranking.relevance_score() > 0.0)
         .collect();
 
     // Sort by specified ranking criteria
     match rank_by {
         Some("downloads") => {
-            ranked.sort_by(|a, b| b.1.download_count.cmp(&a.1.download_count));
+            ranked.sort_by_key(|r| std::cmp::Reverse(r.1.download_count));
         }
         Some("rating") => {
             ranked.sort_by(|a, b| {
@@ -418,23 +590,19 @@ pub fn rank_templates(
             });
         }
         Some("updated") => {
-            ranked.sort_by(|a, b| b.1.last_updated.cmp(&a.1.last_updated));
+            ranked.sort_by_key(|r| std::cmp::Reverse(r.1.last_updated));
         }
         Some("relevance") => {
             ranked.sort_by(|a, b| {
-                let score_a = a.1.relevance_score + a.1.quality_score;
-                let score_b = b.1.relevance_score + b.1.quality_score;
-                score_b
-                    .partial_cmp(&score_a)
+                b.1.score()
+                    .partial_cmp(&a.1.score())
                     .unwrap_or(std::cmp::Ordering::Equal)
             });
         }
         _ => {
             ranked.sort_by(|a, b| {
-                let score_a = a.1.relevance_score + a.1.quality_score;
-                let score_b = b.1.relevance_score + b.1.quality_score;
-                score_b
-                    .partial_cmp(&score_a)
+                b.1.score()
+                    .partial_cmp(&a.1.score())
                     .unwrap_or(std::cmp::Ordering::Equal)
             });
         }
@@ -443,18 +611,6 @@ pub fn rank_templates(
     ranked
 }
 
-/// Get current platform name
-fn get_current_platform() -> String {
-    #[cfg(target_os = "windows")]
-    return "windows".to_string();
-    #[cfg(target_os = "macos")]
-    return "macos".to_string();
-    #[cfg(target_os = "linux")]
-    return "linux".to_string();
-    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
-    return "unknown".to_string();
-}
-
 /// Truncate string to specified length with ellipsis
 fn truncate_string(s: &str, max_length: usize) -> String {
     if s.len() <= max_length {
@@ -480,6 +636,8 @@ mod tests {
                     args: Some(vec!["filesystem".to_string()]),
                     url: None,
                 env: None,
+                    timeout: None,
+                    auto_approve: None,
                     other: HashMap::new(),
                 },
             ),
@@ -490,6 +648,8 @@ mod tests {
                     args: Some(["-h", "localhost"].iter().map(|s| s.to_string()).collect()),
                     url: None,
                 env: None,
+                    timeout: None,
+                    auto_approve: None,
                     other: HashMap::new(),
                 },
             ),
@@ -503,11 +663,75 @@ mod tests {
             requires: None,
         };
 
-        let filtered = filter_servers(servers, &criteria);
+        let filtered = filter_servers(servers, &criteria, &ServerMetadata::default());
         assert_eq!(filtered.len(), 1);
         assert_eq!(filtered[0].name, "database");
     }
 
+    #[test]
+    fn test_filter_servers_by_recorded_author() {
+        let server = || McpServer {
+            command: Some("npx".to_string()),
+            args: None,
+            url: None,
+            env: None,
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::new(),
+        };
+        let servers = vec![
+            ("docs".to_string(), server()),
+            ("manual".to_string(), server()),
+        ];
+
+        let mut metadata = ServerMetadata::default();
+        metadata.record(
+            "docs",
+            ServerProvenance {
+                template: "docs-search".to_string(),
+                version: "1.0.0".to_string(),
+                author: "Jane Example".to_string(),
+                category: Some("community".to_string()),
+                tags: vec!["search".to_string()],
+                requirements: None,
+                added_at: Utc::now(),
+                notes: None,
+                variables: HashMap::new(),
+                platform_override: None,
+                docs_url: None,
+                pinned: None,
+            },
+        );
+
+        let criteria = SearchCriteria {
+            text: None,
+            tags: vec![],
+            platform: None,
+            author: Some("jane".to_string()),
+            requires: None,
+        };
+
+        let filtered = filter_servers(servers.clone(), &criteria, &metadata);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name, "docs");
+        assert_eq!(filtered[0].template.as_deref(), Some("docs-search"));
+
+        // Tags recorded from the template are filterable too
+        let criteria = SearchCriteria {
+            author: None,
+            tags: vec!["search".to_string()],
+            ..criteria
+        };
+        assert_eq!(filter_servers(servers, &criteria, &metadata).len(), 1);
+    }
+
+    #[test]
+    fn test_author_matches() {
+        assert!(author_matches("Anthropic", "anthropic"));
+        assert!(author_matches("Jane Example", "EXAMPLE"));
+        assert!(!author_matches("Jane Example", "john"));
+    }
+
     #[test]
     fn test_sort_servers() {
         let servers = vec![
@@ -517,11 +741,17 @@ mod tests {
                 args: vec![],
                 url: None,
                 env: None,
+                timeout: None,
+                auto_approve: None,
                 template: None,
                 tags: vec![],
                 platform: "macos".to_string(),
                 author: None,
                 requirements: None,
+                workspace_file: None,
+                layer: None,
+                disabled: false,
+                docs_url: None,
             },
             ServerInfo {
                 name: "alpha".to_string(),
@@ -529,11 +759,17 @@ mod tests {
                 args: vec![],
                 url: None,
                 env: None,
+                timeout: None,
+                auto_approve: None,
                 template: None,
                 tags: vec![],
                 platform: "macos".to_string(),
                 author: None,
                 requirements: None,
+                workspace_file: None,
+                layer: None,
+                disabled: false,
+                docs_url: None,
             },
         ];
 
@@ -543,6 +779,10 @@ mod tests {
             format: None,
             show_requirements: false,
             json: false,
+            tree: false,
+            full: false,
+            disabled_only: false,
+            names_only: false,
         };
 
         let sorted = sort_servers(servers, &options);
@@ -553,10 +793,48 @@ mod tests {
     #[test]
     fn test_calculate_ranking() {
         let ranking = calculate_ranking("filesystem", "file", None);
-        assert!(ranking.relevance_score > 0.0);
+        assert!(ranking.relevance_score() > 0.0);
 
         let ranking_exact = calculate_ranking("filesystem", "filesystem", None);
-        assert!(ranking_exact.relevance_score > ranking.relevance_score);
+        assert!(ranking_exact.relevance_score() > ranking.relevance_score());
+    }
+
+    #[test]
+    fn test_calculate_ranking_components() {
+        use crate::templates::TemplateMetadata;
+
+        let metadata = TemplateMetadata {
+            name: "postgres".to_string(),
+            version: "1.0.0".to_string(),
+            description: "Query a Postgres database".to_string(),
+            author: "test".to_string(),
+            tags: vec!["database".to_string(), "sql".to_string()],
+            platforms: vec!["linux".to_string(), "macos".to_string()],
+            category: "community".to_string(),
+            path: "templates/postgres.json".to_string(),
+            updated_at: None,
+            checksum: None,
+            docs_url: None,
+            source: None,
+        };
+
+        let ranking = calculate_ranking("postgres", "post", Some(&metadata));
+        assert_eq!(
+            ranking.relevance,
+            RelevanceScore {
+                name: 0.8,
+                description: 0.4,
+            }
+        );
+        assert_eq!(
+            ranking.quality,
+            QualityScore {
+                category: 0.7,
+                tags: 0.15,
+                platforms: 0.1,
+            }
+        );
+        assert!((ranking.score() - 2.15).abs() < 1e-6);
     }
 
     #[test]
@@ -579,6 +857,10 @@ mod tests {
                 platforms: vec!["linux".to_string()],
                 category: "community".to_string(),
                 path: "test.json".to_string(),
+                updated_at: None,
+                checksum: None,
+                docs_url: None,
+                source: None,
             },
             TemplateMetadata {
                 name: "filesystem".to_string(),
@@ -589,6 +871,10 @@ mod tests {
                 platforms: vec!["linux".to_string()],
                 category: "official".to_string(),
                 path: "test.json".to_string(),
+                updated_at: None,
+                checksum: None,
+                docs_url: None,
+                source: None,
             },
         ];
 
@@ -601,4 +887,34 @@ mod tests {
         let ranked = rank_templates(templates, "nonexistent", None);
         assert_eq!(ranked.len(), 0);
     }
+
+    #[test]
+    fn test_rank_templates_by_updated() {
+        use crate::templates::TemplateMetadata;
+
+        let template = |name: &str, updated_at: Option<&str>| TemplateMetadata {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            description: "Database server".to_string(),
+            author: "test".to_string(),
+            tags: vec![],
+            platforms: vec!["linux".to_string()],
+            category: "community".to_string(),
+            path: format!("templates/{}.json", name),
+            updated_at: updated_at.map(|s| s.parse().unwrap()),
+            checksum: None,
+            docs_url: None,
+            source: None,
+        };
+
+        let templates = vec![
+            template("unknown", None),
+            template("older", Some("2024-01-01T00:00:00Z")),
+            template("newer", Some("2024-06-01T00:00:00Z")),
+        ];
+
+        let ranked = rank_templates(templates, "database", Some("updated"));
+        let names: Vec<&str> = ranked.iter().map(|(t, _)| t.name.as_str()).collect();
+        assert_eq!(names, vec!["newer", "older", "unknown"]);
+    }
 }
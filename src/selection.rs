@@ -0,0 +1,111 @@
+use anyhow::{anyhow, Result};
+
+/// Servers named on the command line, e.g. `validate api db` or
+/// `export --server api --server db`
+///
+/// Repeated names count once and keep their first position. Naming no
+/// servers selects all of them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Selection {
+    names: Vec<String>,
+}
+
+impl Selection {
+    pub fn new(names: Vec<String>) -> Self {
+        let mut unique: Vec<String> = Vec::new();
+        for name in names {
+            if !unique.contains(&name) {
+                unique.push(name);
+            }
+        }
+        Self { names: unique }
+    }
+
+    /// Whether no servers were named, which selects all of them
+    pub fn is_all(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Whether `name` is selected
+    pub fn contains(&self, name: &str) -> bool {
+        self.is_all() || self.names.iter().any(|n| n == name)
+    }
+
+    /// Named servers that `exists` doesn't know, in the order given
+    pub fn missing(&self, exists: impl Fn(&str) -> bool) -> Vec<&str> {
+        self.names
+            .iter()
+            .map(String::as_str)
+            .filter(|name| !exists(name))
+            .collect()
+    }
+
+    /// Fail naming every selected server `exists` doesn't know, with `hint` appended
+    pub fn require(&self, exists: impl Fn(&str) -> bool, hint: &str) -> Result<()> {
+        match describe_missing(&self.missing(exists)) {
+            None => Ok(()),
+            Some(missing) if hint.is_empty() => Err(anyhow!("{}", missing)),
+            Some(missing) => Err(anyhow!("{}. {}", missing, hint)),
+        }
+    }
+}
+
+/// "Server 'a' not found", or "Servers 'a', 'b' not found" for several
+pub fn describe_missing(missing: &[&str]) -> Option<String> {
+    match missing {
+        [] => None,
+        [name] => Some(format!("Server '{}' not found", name)),
+        names => Some(format!(
+            "Servers {} not found",
+            names
+                .iter()
+                .map(|name| format!("'{}'", name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selection(names: &[&str]) -> Selection {
+        Selection::new(names.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn test_duplicates_count_once_in_first_position() {
+        let selection = selection(&["db", "api", "db"]);
+        assert_eq!(selection.names(), ["db", "api"]);
+        assert!(selection.contains("api"));
+        assert!(!selection.contains("web"));
+
+        let all = Selection::default();
+        assert!(all.is_all() && all.contains("web"));
+    }
+
+    #[test]
+    fn test_require_lists_every_unknown_name() {
+        let configured = |name: &str| ["api", "db"].contains(&name);
+        assert!(selection(&["api", "db"]).require(configured, "").is_ok());
+        assert_eq!(
+            selection(&["api", "dbb", "web"])
+                .require(configured, "Existing servers: api, db")
+                .unwrap_err()
+                .to_string(),
+            "Servers 'dbb', 'web' not found. Existing servers: api, db"
+        );
+        assert_eq!(
+            selection(&["dbb"])
+                .require(configured, "")
+                .unwrap_err()
+                .to_string(),
+            "Server 'dbb' not found"
+        );
+    }
+}
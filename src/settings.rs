@@ -0,0 +1,61 @@
+use crate::github::GitHubToken;
+use crate::layers::ConfigLayers;
+use crate::templates::NamedSource;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Preferences kept between runs in `mcp-forge/settings.json` under the
+/// user's config directory
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    /// Template repository as `owner/repo` or `owner/repo@branch`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_repo: Option<String>,
+    /// Template sources after the repository, in the order they are consulted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub template_sources: Vec<NamedSource>,
+    /// Token for GitHub requests when `GITHUB_TOKEN` and `GH_TOKEN` are unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_token: Option<GitHubToken>,
+    /// Proxy for template and GitHub requests, ahead of `HTTPS_PROXY`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Base config and overlays merged into the Claude config, once an overlay is added
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_layers: Option<ConfigLayers>,
+}
+
+impl Settings {
+    /// Load settings, returning the defaults if none have been saved
+    pub fn load() -> Result<Self> {
+        let path = settings_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read settings: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse settings: {}", path.display()))
+    }
+
+    /// Save settings
+    pub fn save(&self) -> Result<()> {
+        let path = settings_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self).context("Failed to serialize settings")?;
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write settings: {}", path.display()))
+    }
+}
+
+/// Get path to the settings file
+pub fn settings_path() -> Result<PathBuf> {
+    let dir = dirs::config_dir().context("Could not find the config directory")?;
+    Ok(dir.join("mcp-forge").join("settings.json"))
+}
@@ -0,0 +1,291 @@
+use crate::bulk::find_matching_servers;
+use crate::config::{Config, McpServer, Target};
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Lines of stderr kept from a failed server
+const STDERR_TAIL_LINES: usize = 5;
+
+/// Result of starting one server
+#[derive(Debug, Clone, Serialize)]
+pub struct ProbeOutcome {
+    pub name: String,
+    pub kind: String,
+    pub passed: bool,
+    pub latency_ms: Option<u64>,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stderr: Vec<String>,
+}
+
+/// Handle smoke command
+pub async fn handle_smoke(
+    timeout: u64,
+    jobs: usize,
+    json: bool,
+    only: Option<String>,
+    target: &Target,
+) -> Result<()> {
+    let config = Config::load(target).await?;
+    let timeout = Duration::from_secs(timeout);
+
+    let mut names = find_matching_servers(&config, only.as_deref(), None)?;
+    names.retain(|name| !config.mcp_servers[name].is_disabled());
+    names.sort();
+
+    if names.is_empty() {
+        println!("{}", "No enabled servers to test.".yellow());
+        return Ok(());
+    }
+
+    if !json {
+        println!(
+            "{}",
+            format!(
+                "Starting {} server(s), timeout {}s each...",
+                names.len(),
+                timeout.as_secs()
+            )
+            .cyan()
+        );
+    }
+
+    let semaphore = Arc::new(Semaphore::new(jobs.max(1)));
+    let mut tasks = JoinSet::new();
+    for name in names {
+        let server = config.mcp_servers[&name].clone();
+        let semaphore = semaphore.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            probe_server(name, &server, timeout).await
+        });
+    }
+
+    let mut outcomes = Vec::new();
+    while let Some(outcome) = tasks.join_next().await {
+        outcomes.push(outcome?);
+    }
+    outcomes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&outcomes)?);
+    } else {
+        display_outcomes(&outcomes);
+    }
+
+    let failed = outcomes.iter().filter(|o| !o.passed).count();
+    crate::notify::report(outcomes.len() - failed, failed);
+    if failed > 0 {
+        return Err(anyhow!(
+            "{} of {} server(s) failed to start",
+            failed,
+            outcomes.len()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Probe a server with the check that fits its type
+pub async fn probe_server(name: String, server: &McpServer, timeout: Duration) -> ProbeOutcome {
+    let started = Instant::now();
+    let (kind, result) = match (&server.url, &server.command) {
+        (Some(url), _) => ("url", probe_url(url, timeout).await),
+        (None, Some(command)) => ("command", probe_command(command, server, timeout).await),
+        (None, None) => (
+            "command",
+            Err(("no command or url".to_string(), Vec::new())),
+        ),
+    };
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    match result {
+        Ok(detail) => ProbeOutcome {
+            name,
+            kind: kind.to_string(),
+            passed: true,
+            latency_ms: Some(latency_ms),
+            detail,
+            stderr: Vec::new(),
+        },
+        Err((detail, stderr)) => ProbeOutcome {
+            name,
+            kind: kind.to_string(),
+            passed: false,
+            latency_ms: None,
+            detail,
+            stderr,
+        },
+    }
+}
+
+type ProbeResult = std::result::Result<String, (String, Vec<String>)>;
+
+/// Launch a command server and wait for it to answer an MCP `initialize` request
+async fn probe_command(command: &str, server: &McpServer, timeout: Duration) -> ProbeResult {
+    let mut child = Command::new(command)
+        .args(server.args.as_deref().unwrap_or_default())
+        .envs(server.env.iter().flatten())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| (format!("failed to launch '{}': {}", command, e), Vec::new()))?;
+
+    // Keep the tail of stderr for the report without letting a chatty server grow it
+    let stderr_tail = Arc::new(Mutex::new(VecDeque::new()));
+    let stderr_reader = child.stderr.take().map(|stderr| {
+        let tail = stderr_tail.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let mut tail = tail.lock().unwrap();
+                if tail.len() == STDERR_TAIL_LINES {
+                    tail.pop_front();
+                }
+                tail.push_back(line);
+            }
+        })
+    });
+
+    let handshake = async {
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| "stdin unavailable".to_string())?;
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "mcp-forge", "version": env!("CARGO_PKG_VERSION") }
+            }
+        });
+        stdin
+            .write_all(format!("{}\n", request).as_bytes())
+            .await
+            .map_err(|_| "exited before responding".to_string())?;
+
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| "stdout unavailable".to_string())?;
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            // Tolerate log lines on stdout; only a reply to our request counts
+            let Ok(message) = serde_json::from_str::<serde_json::Value>(&line) else {
+                continue;
+            };
+            if message.get("id") != Some(&serde_json::json!(1)) {
+                continue;
+            }
+            if let Some(error) = message.get("error") {
+                return Err(format!("initialize failed: {}", error));
+            }
+            let server_name = message
+                .pointer("/result/serverInfo/name")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unnamed server");
+            return Ok(format!("initialized ({})", server_name));
+        }
+        Err("exited before responding".to_string())
+    };
+
+    let result = match tokio::time::timeout(timeout, handshake).await {
+        Ok(Ok(detail)) => Ok(detail),
+        Ok(Err(detail)) => {
+            // Report the exit status if the server has gone away
+            let exited = tokio::time::timeout(Duration::from_millis(500), child.wait()).await;
+            let detail = match exited {
+                Ok(Ok(status)) if !status.success() => format!("{} ({})", detail, status),
+                _ => detail,
+            };
+            Err(detail)
+        }
+        Err(_) => Err(format!("no response within {}s", timeout.as_secs())),
+    };
+    let _ = child.kill().await;
+
+    if result.is_err() {
+        // Let the reader drain what the process wrote; a lingering grandchild
+        // can hold the pipe open, so don't wait on it indefinitely
+        if let Some(reader) = stderr_reader {
+            let _ = tokio::time::timeout(Duration::from_millis(500), reader).await;
+        }
+    }
+
+    result.map_err(|detail| {
+        let stderr = stderr_tail.lock().unwrap().iter().cloned().collect();
+        (detail, stderr)
+    })
+}
+
+/// Check that a URL server answers HTTP requests
+async fn probe_url(url: &str, timeout: Duration) -> ProbeResult {
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| (e.to_string(), Vec::new()))?;
+
+    match client.get(url).send().await {
+        Ok(response) if response.status().is_server_error() => {
+            Err((format!("HTTP {}", response.status()), Vec::new()))
+        }
+        // MCP endpoints often reject a bare GET, but any answer means it's up
+        Ok(response) => Ok(format!("reachable (HTTP {})", response.status().as_u16())),
+        Err(e) if e.is_timeout() => Err((
+            format!("no response within {}s", timeout.as_secs()),
+            Vec::new(),
+        )),
+        Err(e) => Err((format!("unreachable: {}", e), Vec::new())),
+    }
+}
+
+fn display_outcomes(outcomes: &[ProbeOutcome]) {
+    let width = outcomes.iter().map(|o| o.name.len()).max().unwrap_or(0);
+
+    println!();
+    for outcome in outcomes {
+        let latency = outcome
+            .latency_ms
+            .map(|ms| format!("{:.2}s", ms as f64 / 1000.0))
+            .unwrap_or_else(|| "-".to_string());
+        let symbol = if outcome.passed {
+            "✓".green()
+        } else {
+            "✗".red()
+        };
+        println!(
+            "  {} {:<width$}  {:<7}  {:>6}  {}",
+            symbol,
+            outcome.name,
+            outcome.kind,
+            latency,
+            outcome.detail,
+            width = width
+        );
+        for line in &outcome.stderr {
+            println!("      {} {}", "stderr:".dimmed(), line);
+        }
+    }
+
+    let passed = outcomes.iter().filter(|o| o.passed).count();
+    println!();
+    println!(
+        "Summary: {} passed, {} failed",
+        passed.to_string().green(),
+        (outcomes.len() - passed).to_string().red()
+    );
+}
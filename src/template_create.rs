@@ -0,0 +1,476 @@
+use crate::config::{Config, McpServer, Target};
+use crate::template_edit::{
+    check_variable_name, default_value, parse_list, to_string_ordered, value_text, KeyOrder,
+};
+use crate::templates::{self, Template, TemplateConfig, TemplateVariable, VariableType};
+use crate::utils;
+use crate::utils::platform::OS_NAMES;
+use anyhow::{anyhow, bail, Context, Result};
+use colored::Colorize;
+use inquire::validator::Validation;
+use inquire::{Confirm, MultiSelect, Select, Text};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const VARIABLE_TYPES: [VariableType; 5] = [
+    VariableType::String,
+    VariableType::Boolean,
+    VariableType::Number,
+    VariableType::Array,
+    VariableType::Select,
+];
+
+/// Where `template create` writes: `--output`, `<dir>/<name>.json`, or
+/// `<name>.json` in the current directory
+pub fn destination(name: &str, output: Option<PathBuf>, dir: Option<PathBuf>) -> PathBuf {
+    let file = format!("{}.json", utils::sanitize_filename(name));
+    match (output, dir) {
+        (Some(output), _) => output,
+        (None, Some(dir)) => dir.join(file),
+        (None, None) => PathBuf::from(file),
+    }
+}
+
+/// The template the wizard starts from, filled in from `server` if given
+///
+/// Credentials in the server's environment become secret variables, so the
+/// values themselves never reach the template.
+pub fn seed(name: &str, server: Option<&McpServer>) -> Template {
+    let mut template = Template {
+        name: name.to_string(),
+        version: "1.0.0".to_string(),
+        description: String::new(),
+        author: String::new(),
+        extends: None,
+        tags: Vec::new(),
+        platforms: OS_NAMES.iter().map(|p| p.to_string()).collect(),
+        variables: HashMap::new(),
+        config: TemplateConfig::default(),
+        requirements: None,
+        setup_instructions: None,
+        docs_url: None,
+        inherits: Vec::new(),
+    };
+    let Some(server) = server else {
+        return template;
+    };
+
+    template.config.command = server.command.clone();
+    template.config.args = server.args.clone();
+    template.config.url = server.url.clone();
+    if let Some(env) = &server.env {
+        let mut config_env = HashMap::new();
+        for (key, value) in env {
+            if !utils::is_sensitive_env_key(key) || value.contains("{{") {
+                config_env.insert(key.clone(), value.clone());
+                continue;
+            }
+            let variable = key.to_lowercase();
+            template.variables.insert(
+                variable.clone(),
+                TemplateVariable {
+                    var_type: VariableType::String,
+                    description: key.clone(),
+                    default: None,
+                    required: true,
+                    validation: None,
+                    options: None,
+                    secret: true,
+                },
+            );
+            config_env.insert(key.clone(), format!("{{{{{}}}}}", variable));
+        }
+        template.config.env = Some(config_env);
+    }
+    template
+}
+
+/// Template JSON as written, with fields in the order [`Template`] declares
+/// them and variables and environment in name order
+pub fn to_json(template: &Template) -> Result<String> {
+    let written = serde_json::to_string(template).context("Failed to serialize template")?;
+    let mut order = KeyOrder::of(&written)?;
+    for path in ["variables", "config.env"] {
+        order.sort_keys_at(path);
+    }
+    let value: Value = serde_json::from_str(&written)?;
+    Ok(to_string_ordered(&value, &order)? + "\n")
+}
+
+/// Handle `template create`: build a template with prompts and write it
+pub async fn handle_template_create(
+    name: String,
+    from_server: Option<String>,
+    output: Option<PathBuf>,
+    dir: Option<PathBuf>,
+    force: bool,
+    target: &Target,
+) -> Result<()> {
+    let path = destination(&name, output, dir);
+    if path.exists() && !force {
+        bail!(
+            "{} already exists; pass --force to overwrite it",
+            path.display()
+        );
+    }
+
+    let server = match &from_server {
+        Some(server_name) => {
+            let config = Config::load(target).await?;
+            let server = config
+                .mcp_servers
+                .get(server_name)
+                .or_else(|| config.disabled_servers.get(server_name))
+                .cloned()
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Server '{}' not found. {}",
+                        server_name,
+                        crate::bulk::describe_existing_servers(&config)
+                    )
+                })?;
+            Some(server)
+        }
+        None => None,
+    };
+    let mut template = seed(&name, server.as_ref());
+
+    if !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+        bail!("template create needs a terminal for its prompts; write the JSON by hand or start from an existing file with `template edit`");
+    }
+
+    loop {
+        template = prompt_template(template)?;
+        let problems = templates::validate_template(&template);
+        if problems.is_empty() {
+            break;
+        }
+        for problem in &problems {
+            println!("{}", format!("⚠️  {}", problem).yellow());
+        }
+        let again = Confirm::new("Go through the answers again to fix these?")
+            .with_default(true)
+            .prompt()?;
+        if !again {
+            bail!(
+                "{} was not written; the template has problems",
+                path.display()
+            );
+        }
+    }
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    utils::atomic::write_atomic(&path, to_json(&template)?.as_bytes())?;
+    println!("{}", format!("✓ Created {}", path.display()).green());
+    for warning in template.security_warnings() {
+        println!("{}", format!("⚠️  {}", warning).yellow());
+    }
+    println!(
+        "  Change it later with `mcp-forge template edit {}`",
+        path.display()
+    );
+    Ok(())
+}
+
+/// Ask for every part of the template, offering what it holds as the answers
+fn prompt_template(mut template: Template) -> Result<Template> {
+    template.name = required_text("Template name:", &template.name)?;
+    template.version = required_text("Version:", &template.version)?;
+    template.description = required_text("Description:", &template.description)?;
+    template.author = required_text("Author:", &template.author)?;
+    template.tags = parse_list(
+        &Text::new("Tags:")
+            .with_initial_value(&template.tags.join(","))
+            .with_help_message("Comma-separated, e.g. database,sql")
+            .prompt()?,
+    )?;
+
+    let selected: Vec<usize> = OS_NAMES
+        .iter()
+        .enumerate()
+        .filter(|(_, platform)| templates::supports_platform(&template.platforms, platform))
+        .map(|(index, _)| index)
+        .collect();
+    template.platforms = MultiSelect::new("Platforms:", OS_NAMES.to_vec())
+        .with_default(&selected)
+        .prompt()?
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+
+    prompt_variables(&mut template)?;
+    prompt_config(&mut template)?;
+    Ok(template)
+}
+
+fn required_text(label: &str, initial: &str) -> Result<String> {
+    let value = Text::new(label)
+        .with_initial_value(initial)
+        .with_validator(|input: &str| {
+            Ok(if input.trim().is_empty() {
+                Validation::Invalid("Required".into())
+            } else {
+                Validation::Valid
+            })
+        })
+        .prompt()?;
+    Ok(value.trim().to_string())
+}
+
+fn prompt_variables(template: &mut Template) -> Result<()> {
+    const ADD: &str = "+ Add a variable";
+    const DONE: &str = "Done with variables";
+
+    loop {
+        let mut names: Vec<String> = template.variables.keys().cloned().collect();
+        names.sort();
+        let mut options: Vec<String> = names
+            .iter()
+            .map(|name| {
+                let variable = &template.variables[name];
+                format!("{} ({}): {}", name, variable.var_type, variable.description)
+            })
+            .collect();
+        options.push(ADD.to_string());
+        options.push(DONE.to_string());
+
+        let choice = Select::new("Variables:", options).raw_prompt()?;
+        if choice.value == DONE {
+            return Ok(());
+        }
+        if choice.value == ADD {
+            let existing = names.clone();
+            let name = Text::new("Variable name:")
+                .with_validator(move |input: &str| {
+                    let input = input.trim();
+                    Ok(if existing.iter().any(|name| name == input) {
+                        Validation::Invalid(format!("'{}' already exists", input).into())
+                    } else {
+                        match check_variable_name(input) {
+                            Ok(()) => Validation::Valid,
+                            Err(e) => Validation::Invalid(e.to_string().into()),
+                        }
+                    })
+                })
+                .prompt()?;
+            let name = name.trim().to_string();
+            let variable = prompt_variable(&name, None)?;
+            template.variables.insert(name, variable);
+            continue;
+        }
+
+        let name = &names[choice.index];
+        let action =
+            Select::new(&format!("Variable '{}':", name), vec!["Edit", "Delete"]).prompt()?;
+        if action == "Delete" {
+            template.variables.remove(name);
+        } else {
+            let variable = prompt_variable(name, template.variables.get(name))?;
+            template.variables.insert(name.clone(), variable);
+        }
+    }
+}
+
+fn prompt_variable(name: &str, current: Option<&TemplateVariable>) -> Result<TemplateVariable> {
+    let type_index = current
+        .and_then(|v| VARIABLE_TYPES.iter().position(|t| *t == v.var_type))
+        .unwrap_or(0);
+    let var_type = Select::new("Type:", VARIABLE_TYPES.to_vec())
+        .with_starting_cursor(type_index)
+        .prompt()?;
+    let description = required_text(
+        "Description:",
+        current.map(|v| v.description.as_str()).unwrap_or_default(),
+    )?;
+
+    let options = if var_type == VariableType::Select {
+        let initial = current
+            .and_then(|v| v.options.as_ref())
+            .map(|options| options.join(","))
+            .unwrap_or_default();
+        let answer = Text::new("Options:")
+            .with_initial_value(&initial)
+            .with_help_message("Comma-separated choices")
+            .prompt()?;
+        Some(parse_list(&answer)?)
+    } else {
+        None
+    };
+
+    let mut variable = TemplateVariable {
+        var_type,
+        description,
+        default: None,
+        required: current.is_some_and(|v| v.required),
+        validation: current.and_then(|v| v.validation.clone()),
+        options,
+        secret: current.map_or_else(|| utils::is_sensitive_env_key(name), |v| v.secret),
+    };
+
+    let initial = current
+        .and_then(|v| v.default.as_ref())
+        .map(value_text)
+        .unwrap_or_default();
+    let check = variable.clone();
+    let check_name = name.to_string();
+    let default = Text::new("Default:")
+        .with_initial_value(&initial)
+        .with_help_message("Leave empty for no default")
+        .with_validator(move |input: &str| {
+            if input.trim().is_empty() {
+                return Ok(Validation::Valid);
+            }
+            Ok(match default_value(&check_name, input, &check) {
+                Ok(_) => Validation::Valid,
+                Err(e) => Validation::Invalid(e.to_string().into()),
+            })
+        })
+        .prompt()?;
+    if !default.trim().is_empty() {
+        variable.default = Some(default_value(name, &default, &variable)?);
+    }
+
+    variable.required = Confirm::new("Required?")
+        .with_default(variable.required || variable.default.is_none())
+        .prompt()?;
+    variable.secret = Confirm::new("Secret? (prompted without echo and always masked)")
+        .with_default(variable.secret)
+        .prompt()?;
+    Ok(variable)
+}
+
+fn prompt_config(template: &mut Template) -> Result<()> {
+    const COMMAND: &str = "Command (runs locally)";
+    const URL: &str = "URL (remote server)";
+
+    let starting = usize::from(template.config.is_url_template());
+    let kind = Select::new("How is the server reached?", vec![COMMAND, URL])
+        .with_starting_cursor(starting)
+        .prompt()?;
+    let placeholder_help = "Use {{name}} for a variable, e.g. {{port}}";
+
+    if kind == URL {
+        template.config.command = None;
+        template.config.args = None;
+        let url = Text::new("URL:")
+            .with_initial_value(template.config.url.as_deref().unwrap_or_default())
+            .with_help_message(placeholder_help)
+            .prompt()?;
+        template.config.url = Some(url.trim().to_string());
+    } else {
+        template.config.url = None;
+        let command = required_text(
+            "Command:",
+            template.config.command.as_deref().unwrap_or_default(),
+        )?;
+        template.config.command = Some(command);
+        let initial = template
+            .config
+            .args
+            .as_ref()
+            .map(|args| value_text(&serde_json::json!(args)))
+            .unwrap_or_default();
+        let args = Text::new("Arguments:")
+            .with_initial_value(&initial)
+            .with_help_message("Comma-separated, or a JSON array when an argument holds a comma")
+            .prompt()?;
+        let args = parse_list(&args)?;
+        template.config.args = (!args.is_empty()).then_some(args);
+    }
+
+    prompt_env(template)
+}
+
+fn prompt_env(template: &mut Template) -> Result<()> {
+    const ADD: &str = "+ Add an environment variable";
+    const DONE: &str = "Done with environment";
+
+    let mut env = template.config.env.take().unwrap_or_default();
+    loop {
+        let mut keys: Vec<String> = env.keys().cloned().collect();
+        keys.sort();
+        let mut options: Vec<String> = keys
+            .iter()
+            .map(|key| format!("{} = {}", key, env[key]))
+            .collect();
+        options.push(ADD.to_string());
+        options.push(DONE.to_string());
+
+        let choice = Select::new("Environment:", options).raw_prompt()?;
+        if choice.value == DONE {
+            break;
+        }
+        let key = if choice.value == ADD {
+            let key = Text::new("Environment variable name:").prompt()?;
+            key.trim().to_string()
+        } else {
+            keys[choice.index].clone()
+        };
+        if key.is_empty() {
+            continue;
+        }
+        let value = Text::new(&format!("{}:", key))
+            .with_initial_value(env.get(&key).map(String::as_str).unwrap_or_default())
+            .with_help_message("Use {{name}} for a variable; leave empty to remove")
+            .prompt()?;
+        if value.is_empty() {
+            env.remove(&key);
+        } else {
+            env.insert(key, value);
+        }
+    }
+    template.config.env = (!env.is_empty()).then_some(env);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_destination() {
+        assert_eq!(destination("pg", None, None), PathBuf::from("pg.json"));
+        assert_eq!(
+            destination("pg", None, Some(PathBuf::from("templates"))),
+            Path::new("templates").join("pg.json")
+        );
+        assert_eq!(
+            destination("pg", Some(PathBuf::from("out.json")), None),
+            PathBuf::from("out.json")
+        );
+    }
+
+    #[test]
+    fn test_seed_from_server_turns_credentials_into_secret_variables() {
+        let server: McpServer = serde_json::from_value(serde_json::json!({
+            "command": "npx",
+            "args": ["-y", "github-mcp"],
+            "env": { "GITHUB_TOKEN": "ghp_live_123", "LOG_LEVEL": "info" }
+        }))
+        .unwrap();
+
+        let template = seed("github", Some(&server));
+        let env = template.config.env.as_ref().unwrap();
+        assert_eq!(env["GITHUB_TOKEN"], "{{github_token}}");
+        assert_eq!(env["LOG_LEVEL"], "info");
+        assert!(template.variables["github_token"].secret);
+
+        let json = to_json(&template).unwrap();
+        assert!(!json.contains("ghp_live_123"));
+        let position = |key: &str| json.find(key).unwrap();
+        assert!(position("\"name\"") < position("\"version\""));
+        assert!(position("\"variables\"") < position("\"config\""));
+        assert!(position("\"GITHUB_TOKEN\"") < position("\"LOG_LEVEL\""));
+        let problems = templates::validate_template(&template);
+        assert_eq!(
+            problems,
+            ["'description' is empty", "'author' is empty"],
+            "only what the wizard asks for is missing"
+        );
+    }
+}
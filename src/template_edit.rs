@@ -0,0 +1,985 @@
+use crate::templates::{self, Template, TemplateVariable, VariableType};
+use crate::utils;
+use anyhow::{anyhow, bail, Context, Result};
+use colored::Colorize;
+use inquire::{Confirm, Select, Text};
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+use serde_json::{json, Map, Value};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Fields every template has; they can be changed but not removed
+const REQUIRED_FIELDS: [&str; 4] = ["name", "version", "description", "author"];
+
+/// Optional template fields `--set` and `--unset` take, besides
+/// `config.env.KEY` and `requirements.KEY`
+const OPTIONAL_FIELDS: [&str; 8] = [
+    "extends",
+    "tags",
+    "platforms",
+    "docs_url",
+    "setup_instructions",
+    "config.command",
+    "config.args",
+    "config.url",
+];
+
+/// Fields that hold a list, given as `a,b` or a JSON array
+const LIST_FIELDS: [&str; 3] = ["tags", "platforms", "config.args"];
+
+const VARIABLE_FIELDS: [&str; 7] = [
+    "type",
+    "description",
+    "default",
+    "required",
+    "secret",
+    "validation",
+    "options",
+];
+
+const VARIABLE_TYPES: [&str; 5] = ["string", "boolean", "number", "array", "select"];
+
+/// Changes given on the command line, applied removals first
+#[derive(Debug, Default)]
+pub struct Edits {
+    pub set_var: Vec<String>,
+    pub remove_var: Vec<String>,
+    pub set: Vec<String>,
+    pub unset: Vec<String>,
+}
+
+impl Edits {
+    fn is_empty(&self) -> bool {
+        self.set_var.is_empty()
+            && self.remove_var.is_empty()
+            && self.set.is_empty()
+            && self.unset.is_empty()
+    }
+}
+
+/// A local template file being edited
+///
+/// The file is edited as JSON rather than through [`Template`], so fields
+/// mcp-forge doesn't know survive the round trip. `serde_json` sorts object
+/// keys, so the order they were written in is kept alongside and used when
+/// the file is written back.
+pub struct TemplateFile {
+    path: PathBuf,
+    original: Map<String, Value>,
+    document: Map<String, Value>,
+    order: KeyOrder,
+}
+
+/// The order keys appear in, for each object in a JSON document
+#[derive(Debug, Default)]
+pub struct KeyOrder {
+    fields: Vec<(String, KeyOrder)>,
+    items: Vec<KeyOrder>,
+}
+
+/// Order for values that weren't in the document; their keys come out in name order
+static NO_ORDER: KeyOrder = KeyOrder {
+    fields: Vec::new(),
+    items: Vec::new(),
+};
+
+impl KeyOrder {
+    /// The key order of JSON text
+    pub fn of(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Put the keys of the object at `path`, e.g. `config.env`, in name order
+    pub fn sort_keys_at(&mut self, path: &str) {
+        let mut order = Some(self);
+        for segment in path.split('.') {
+            order = order.and_then(|order| {
+                order
+                    .fields
+                    .iter_mut()
+                    .find(|(k, _)| k == segment)
+                    .map(|(_, child)| child)
+            });
+        }
+        if let Some(order) = order {
+            order.fields.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+    }
+
+    fn field(&self, key: &str) -> &KeyOrder {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map_or(&NO_ORDER, |(_, order)| order)
+    }
+
+    /// `keys` in the order they were written in, with new keys last
+    fn arrange<'a>(&self, keys: impl Iterator<Item = &'a String>) -> Vec<&'a String> {
+        let mut keys: Vec<&String> = keys.collect();
+        keys.sort_by_key(|key| {
+            self.fields
+                .iter()
+                .position(|(k, _)| k == *key)
+                .unwrap_or(usize::MAX)
+        });
+        keys
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyOrder {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(KeyOrderVisitor)
+    }
+}
+
+struct KeyOrderVisitor;
+
+impl<'de> Visitor<'de> for KeyOrderVisitor {
+    type Value = KeyOrder;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<KeyOrder, A::Error> {
+        let mut order = KeyOrder::default();
+        while let Some(key) = map.next_key::<String>()? {
+            let value = map.next_value()?;
+            order.fields.push((key, value));
+        }
+        Ok(order)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<KeyOrder, A::Error> {
+        let mut order = KeyOrder::default();
+        while let Some(item) = seq.next_element()? {
+            order.items.push(item);
+        }
+        Ok(order)
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<KeyOrder, E> {
+        Ok(KeyOrder::default())
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<KeyOrder, E> {
+        Ok(KeyOrder::default())
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<KeyOrder, E> {
+        Ok(KeyOrder::default())
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<KeyOrder, E> {
+        Ok(KeyOrder::default())
+    }
+
+    fn visit_str<E>(self, _: &str) -> Result<KeyOrder, E> {
+        Ok(KeyOrder::default())
+    }
+
+    fn visit_unit<E>(self) -> Result<KeyOrder, E> {
+        Ok(KeyOrder::default())
+    }
+}
+
+/// Pretty-print `value` with its keys in `order`
+pub fn to_string_ordered(value: &Value, order: &KeyOrder) -> Result<String> {
+    Ok(serde_json::to_string_pretty(&Ordered(value, order))?)
+}
+
+/// A value serialized with its keys in their recorded order
+struct Ordered<'a>(&'a Value, &'a KeyOrder);
+
+impl Serialize for Ordered<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Ordered(value, order) = *self;
+        match value {
+            Value::Object(object) => {
+                let mut map = serializer.serialize_map(Some(object.len()))?;
+                for key in order.arrange(object.keys()) {
+                    map.serialize_entry(key, &Ordered(&object[key], order.field(key)))?;
+                }
+                map.end()
+            }
+            Value::Array(items) => {
+                let mut seq = serializer.serialize_seq(Some(items.len()))?;
+                for (index, item) in items.iter().enumerate() {
+                    let child = order.items.get(index).unwrap_or(&NO_ORDER);
+                    seq.serialize_element(&Ordered(item, child))?;
+                }
+                seq.end()
+            }
+            _ => value.serialize(serializer),
+        }
+    }
+}
+
+impl TemplateFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let document = match serde_json::from_str(&content) {
+            Ok(Value::Object(document)) => document,
+            Ok(_) => bail!(
+                "{} is not a template: expected a JSON object",
+                path.display()
+            ),
+            Err(e) => bail!("{} is not valid JSON: {}", path.display(), e),
+        };
+        let file = Self {
+            path: path.to_path_buf(),
+            original: document.clone(),
+            document,
+            order: KeyOrder::of(&content)?,
+        };
+        file.template()
+            .with_context(|| format!("{} is not a template", path.display()))?;
+        Ok(file)
+    }
+
+    /// The template as mcp-forge reads it
+    pub fn template(&self) -> Result<Template> {
+        Ok(serde_json::from_value(Value::Object(
+            self.document.clone(),
+        ))?)
+    }
+
+    /// Everything wrong with the template as it stands
+    pub fn problems(&self) -> Vec<String> {
+        match self.template() {
+            Ok(template) => templates::validate_template(&template),
+            Err(e) => vec![e.to_string()],
+        }
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.document != self.original
+    }
+
+    pub fn contents(&self) -> Result<String> {
+        let document = Value::Object(self.document.clone());
+        Ok(to_string_ordered(&document, &self.order)? + "\n")
+    }
+
+    pub fn save(&mut self) -> Result<()> {
+        utils::atomic::write_atomic(&self.path, self.contents()?.as_bytes())?;
+        self.original = self.document.clone();
+        Ok(())
+    }
+
+    /// Apply command-line edits: removals, then variable fields, then template fields
+    pub fn apply(&mut self, edits: &Edits) -> Result<()> {
+        for name in &edits.remove_var {
+            self.remove_variable(name)?;
+        }
+        for key in &edits.unset {
+            self.unset_field(key)?;
+        }
+        for assignment in &edits.set_var {
+            let (target, value) = utils::assignments::parse_assignment(assignment)?;
+            let (name, field) = target.split_once('.').ok_or_else(|| {
+                anyhow!(
+                    "Invalid --set-var '{}'. Use NAME.FIELD=VALUE, e.g. port.default=5433",
+                    assignment
+                )
+            })?;
+            self.set_variable_field(name, field, &value)?;
+        }
+        for assignment in &edits.set {
+            let (key, value) = utils::assignments::parse_assignment(assignment)?;
+            self.set_field(&key, &value)?;
+        }
+        Ok(())
+    }
+
+    /// Names of the template's variables, in file order
+    pub fn variable_names(&self) -> Vec<String> {
+        let Some(variables) = self.document.get("variables").and_then(Value::as_object) else {
+            return Vec::new();
+        };
+        let order = self.order.field("variables");
+        order
+            .arrange(variables.keys())
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Set one field of a variable, adding the variable if it's new
+    ///
+    /// An empty value removes `default`, `validation` and `options`.
+    pub fn set_variable_field(&mut self, name: &str, field: &str, value: &str) -> Result<()> {
+        if !VARIABLE_FIELDS.contains(&field) {
+            bail!(
+                "Unknown variable field '{}'; use one of: {}",
+                field,
+                VARIABLE_FIELDS.join(", ")
+            );
+        }
+        let variables = object_at(&mut self.document, &["variables"])?;
+        if !variables.contains_key(name) {
+            check_variable_name(name)?;
+            variables.insert(
+                name.to_string(),
+                json!({ "type": "string", "description": "" }),
+            );
+        }
+        let variable = variables
+            .get_mut(name)
+            .and_then(Value::as_object_mut)
+            .ok_or_else(|| anyhow!("Variable '{}' is not a JSON object", name))?;
+
+        let optional = matches!(field, "default" | "validation" | "options");
+        if optional && value.trim().is_empty() {
+            variable.remove(field);
+            return Ok(());
+        }
+        let value = match field {
+            "type" => {
+                let var_type = value.trim().to_lowercase();
+                if !VARIABLE_TYPES.contains(&var_type.as_str()) {
+                    bail!(
+                        "Unknown variable type '{}'; use one of: {}",
+                        value,
+                        VARIABLE_TYPES.join(", ")
+                    );
+                }
+                Value::String(var_type)
+            }
+            "required" | "secret" => Value::Bool(parse_bool(field, value)?),
+            "options" => json!(parse_list(value)?),
+            "default" => {
+                let definition: TemplateVariable =
+                    serde_json::from_value(Value::Object(variable.clone()))
+                        .with_context(|| format!("Variable '{}' is malformed", name))?;
+                default_value(name, value, &definition)?
+            }
+            _ => Value::String(value.to_string()),
+        };
+        variable.insert(field.to_string(), value);
+        Ok(())
+    }
+
+    pub fn remove_variable(&mut self, name: &str) -> Result<()> {
+        let names = self.variable_names();
+        let removed = self
+            .document
+            .get_mut("variables")
+            .and_then(Value::as_object_mut)
+            .and_then(|variables| variables.remove(name));
+        match removed {
+            Some(_) => Ok(()),
+            None if names.is_empty() => bail!("The template has no variables"),
+            None => bail!(
+                "No variable '{}'; the template has: {}",
+                name,
+                names.join(", ")
+            ),
+        }
+    }
+
+    /// Set a template field such as `version`, `tags` or `config.env.API_URL`
+    pub fn set_field(&mut self, key: &str, value: &str) -> Result<()> {
+        let (path, field) = self.locate(key)?;
+        let value = if LIST_FIELDS.contains(&key) {
+            json!(parse_list(value)?)
+        } else {
+            Value::String(value.to_string())
+        };
+        let parent = object_at(&mut self.document, &path)?;
+        parent.insert(field, value);
+        Ok(())
+    }
+
+    /// Remove an optional template field; removing one that isn't set does nothing
+    pub fn unset_field(&mut self, key: &str) -> Result<()> {
+        if REQUIRED_FIELDS.contains(&key) {
+            bail!("'{}' is required; change it with --set instead", key);
+        }
+        let (path, field) = match key {
+            "config.env" => (vec!["config"], "env".to_string()),
+            "requirements" => (Vec::new(), key.to_string()),
+            _ => self.locate(key)?,
+        };
+        let mut parent = Some(&mut self.document);
+        for segment in path {
+            parent = parent.and_then(|p| p.get_mut(segment).and_then(Value::as_object_mut));
+        }
+        if let Some(parent) = parent {
+            parent.remove(&field);
+        }
+        Ok(())
+    }
+
+    /// The object holding `key` and the field name within it
+    fn locate(&self, key: &str) -> Result<(Vec<&'static str>, String)> {
+        if let Some(name) = key.strip_prefix("config.env.").filter(|n| !n.is_empty()) {
+            return Ok((vec!["config", "env"], name.to_string()));
+        }
+        if let Some(name) = key.strip_prefix("requirements.").filter(|n| !n.is_empty()) {
+            return Ok((vec!["requirements"], name.to_string()));
+        }
+        if let Some(field) = key.strip_prefix("config.") {
+            if OPTIONAL_FIELDS.contains(&key) {
+                return Ok((vec!["config"], field.to_string()));
+            }
+        }
+        // `homepage` is read as `docs_url`, so update whichever the file uses
+        if key == "docs_url"
+            && self.document.contains_key("homepage")
+            && !self.document.contains_key("docs_url")
+        {
+            return Ok((Vec::new(), "homepage".to_string()));
+        }
+        if REQUIRED_FIELDS.contains(&key) || OPTIONAL_FIELDS.contains(&key) {
+            return Ok((Vec::new(), key.to_string()));
+        }
+        bail!(
+            "Unknown template field '{}'; use one of: {}, {}, config.env.KEY or requirements.KEY",
+            key,
+            REQUIRED_FIELDS.join(", "),
+            OPTIONAL_FIELDS.join(", ")
+        )
+    }
+
+    /// A field's current value as it would be typed back in
+    fn field_text(&self, key: &str) -> Option<String> {
+        let (path, field) = self.locate(key).ok()?;
+        let mut parent = &self.document;
+        for segment in path {
+            parent = parent.get(segment)?.as_object()?;
+        }
+        parent.get(&field).map(value_text)
+    }
+
+    fn variable_field_text(&self, name: &str, field: &str) -> Option<String> {
+        self.document
+            .get("variables")?
+            .get(name)?
+            .get(field)
+            .map(value_text)
+    }
+
+    fn env_names(&self) -> Vec<String> {
+        self.document
+            .get("config")
+            .and_then(|config| config.get("env"))
+            .and_then(Value::as_object)
+            .map(|env| env.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// The object at `path` under `root`, creating empty ones along the way
+fn object_at<'a>(
+    root: &'a mut Map<String, Value>,
+    path: &[&str],
+) -> Result<&'a mut Map<String, Value>> {
+    let mut current = root;
+    for segment in path {
+        current = current
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .ok_or_else(|| anyhow!("'{}' is not a JSON object", segment))?;
+    }
+    Ok(current)
+}
+
+/// Variable names have to work as `{{name}}` placeholders
+pub fn check_variable_name(name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Invalid variable name '{}'; use letters, digits and '_', not starting with a digit",
+            name
+        ))
+    }
+}
+
+fn parse_bool(field: &str, value: &str) -> Result<bool> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("'{}' must be true or false, not '{}'", field, value))
+}
+
+/// A list given as `a,b` or, when items hold commas, as a JSON array
+pub fn parse_list(value: &str) -> Result<Vec<String>> {
+    let value = value.trim();
+    if value.starts_with('[') {
+        return serde_json::from_str(value).map_err(|e| anyhow!("Invalid list '{}': {}", value, e));
+    }
+    Ok(value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// A default typed for its variable, so `5433` is stored as a number
+pub fn default_value(name: &str, value: &str, definition: &TemplateVariable) -> Result<Value> {
+    let value = match definition.var_type {
+        VariableType::Array => json!(parse_list(value)?),
+        _ => Value::String(value.to_string()),
+    };
+    templates::coerce_value(&value, definition).map_err(|reason| {
+        anyhow!(
+            "Default for '{}' must be a {}: {}",
+            name,
+            definition.var_type,
+            reason
+        )
+    })
+}
+
+/// A value in the form `--set` and the prompts take it
+pub fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(items) => {
+            let plain: Option<Vec<&str>> = items
+                .iter()
+                .map(|item| item.as_str().filter(|s| !s.contains(',')))
+                .collect();
+            match plain {
+                Some(plain) => plain.join(","),
+                None => value.to_string(),
+            }
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Handle `template edit`: apply the given edits, or open the menu when there are none
+pub fn handle_template_edit(file: String, edits: Edits) -> Result<()> {
+    let mut template = TemplateFile::load(Path::new(&file))?;
+    if edits.is_empty() {
+        if !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+            bail!("template edit needs a terminal for its menu; pass --set-var, --remove-var, --set or --unset to edit without one");
+        }
+        return edit_interactively(template);
+    }
+
+    template.apply(&edits)?;
+    let problems = template.problems();
+    if !problems.is_empty() {
+        bail!(
+            "{} was not changed; the edited template has problems:\n  {}",
+            file,
+            problems.join("\n  ")
+        );
+    }
+    if !template.is_changed() {
+        println!("{} already has these values", file);
+        return Ok(());
+    }
+    template.save()?;
+    println!("{}", format!("✓ Updated {}", file).green());
+    Ok(())
+}
+
+fn print_problems(problems: &[String]) {
+    for problem in problems {
+        println!("{}", format!("⚠️  {}", problem).yellow());
+    }
+}
+
+/// Report the outcome of a change and what's left to fix
+fn report(template: &TemplateFile, result: Result<()>) {
+    match result {
+        Ok(()) => print_problems(&template.problems()),
+        Err(e) => println!("{}", format!("✗ {}", e).red()),
+    }
+}
+
+fn edit_interactively(mut template: TemplateFile) -> Result<()> {
+    const VARIABLES: &str = "Variables";
+    const CONFIG: &str = "Config";
+    const METADATA: &str = "Metadata";
+    const SAVE: &str = "Save and quit";
+    const QUIT: &str = "Quit without saving";
+
+    print_problems(&template.problems());
+    loop {
+        let title = format!("Edit {}:", template.path.display());
+        let choice = Select::new(&title, vec![VARIABLES, CONFIG, METADATA, SAVE, QUIT]).prompt()?;
+        match choice {
+            VARIABLES => edit_variables(&mut template)?,
+            CONFIG => edit_config(&mut template)?,
+            METADATA => edit_metadata(&mut template)?,
+            SAVE => {
+                if !template.is_changed() {
+                    println!("No changes to save.");
+                    return Ok(());
+                }
+                let problems = template.problems();
+                if !problems.is_empty() {
+                    print_problems(&problems);
+                    let save = Confirm::new("Save with these problems?")
+                        .with_default(false)
+                        .prompt()?;
+                    if !save {
+                        continue;
+                    }
+                }
+                template.save()?;
+                println!(
+                    "{}",
+                    format!("✓ Updated {}", template.path.display()).green()
+                );
+                return Ok(());
+            }
+            _ => {
+                if !template.is_changed()
+                    || Confirm::new("Discard your changes?")
+                        .with_default(false)
+                        .prompt()?
+                {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+fn edit_variables(template: &mut TemplateFile) -> Result<()> {
+    const ADD: &str = "+ Add a variable";
+    const BACK: &str = "Back";
+
+    loop {
+        let names = template.variable_names();
+        let mut options: Vec<String> = names
+            .iter()
+            .map(|name| {
+                format!(
+                    "{} ({}): {}",
+                    name,
+                    template
+                        .variable_field_text(name, "type")
+                        .unwrap_or_default(),
+                    template
+                        .variable_field_text(name, "description")
+                        .unwrap_or_default()
+                )
+            })
+            .collect();
+        options.push(ADD.to_string());
+        options.push(BACK.to_string());
+
+        let choice = Select::new("Variables:", options).raw_prompt()?;
+        if choice.value == BACK {
+            return Ok(());
+        }
+        if choice.value == ADD {
+            let existing = names.clone();
+            let name = Text::new("Variable name:")
+                .with_validator(move |input: &str| {
+                    let input = input.trim();
+                    Ok(if existing.iter().any(|name| name == input) {
+                        inquire::validator::Validation::Invalid(
+                            format!("'{}' already exists", input).into(),
+                        )
+                    } else {
+                        match check_variable_name(input) {
+                            Ok(()) => inquire::validator::Validation::Valid,
+                            Err(e) => inquire::validator::Validation::Invalid(e.to_string().into()),
+                        }
+                    })
+                })
+                .prompt()?;
+            let name = name.trim();
+            let var_type = Select::new("Type:", VARIABLE_TYPES.to_vec()).prompt()?;
+            let description = Text::new("Description:").prompt()?;
+            let result = template
+                .set_variable_field(name, "type", var_type)
+                .and_then(|()| template.set_variable_field(name, "description", &description));
+            report(template, result);
+            edit_variable(template, name)?;
+            continue;
+        }
+        edit_variable(template, &names[choice.index])?;
+    }
+}
+
+fn edit_variable(template: &mut TemplateFile, name: &str) -> Result<()> {
+    const DELETE: &str = "Delete variable";
+    const BACK: &str = "Back";
+
+    loop {
+        let mut options: Vec<String> = VARIABLE_FIELDS
+            .iter()
+            .map(|field| {
+                let value = template
+                    .variable_field_text(name, field)
+                    .unwrap_or_else(|| "-".to_string());
+                format!("{} = {}", field, value)
+            })
+            .collect();
+        options.push(DELETE.to_string());
+        options.push(BACK.to_string());
+
+        let choice = Select::new(&format!("Variable '{}':", name), options).raw_prompt()?;
+        if choice.value == BACK {
+            return Ok(());
+        }
+        if choice.value == DELETE {
+            let result = template.remove_variable(name);
+            report(template, result);
+            return Ok(());
+        }
+
+        let field = VARIABLE_FIELDS[choice.index];
+        let value = match field {
+            "type" => Select::new("Type:", VARIABLE_TYPES.to_vec())
+                .prompt()?
+                .to_string(),
+            "required" | "secret" => {
+                let current = template.variable_field_text(name, field).as_deref() == Some("true");
+                Confirm::new(&format!("{}?", field))
+                    .with_default(current)
+                    .prompt()?
+                    .to_string()
+            }
+            _ => {
+                let help = match field {
+                    "options" => "Comma-separated; leave empty to remove",
+                    "default" | "validation" => "Leave empty to remove",
+                    _ => "",
+                };
+                let current = template
+                    .variable_field_text(name, field)
+                    .unwrap_or_default();
+                let label = format!("{}:", field);
+                let mut prompt = Text::new(&label).with_initial_value(&current);
+                if !help.is_empty() {
+                    prompt = prompt.with_help_message(help);
+                }
+                prompt.prompt()?
+            }
+        };
+        let result = template.set_variable_field(name, field, &value);
+        report(template, result);
+    }
+}
+
+fn edit_config(template: &mut TemplateFile) -> Result<()> {
+    const ADD: &str = "+ Add an environment variable";
+    const BACK: &str = "Back";
+
+    loop {
+        let mut keys: Vec<String> = vec![
+            "config.command".to_string(),
+            "config.args".to_string(),
+            "config.url".to_string(),
+        ];
+        keys.extend(
+            template
+                .env_names()
+                .into_iter()
+                .map(|name| format!("config.env.{}", name)),
+        );
+        let mut options = field_options(template, &keys);
+        options.push(ADD.to_string());
+        options.push(BACK.to_string());
+
+        let choice = Select::new("Config:", options).raw_prompt()?;
+        if choice.value == BACK {
+            return Ok(());
+        }
+        let key = if choice.value == ADD {
+            let name = Text::new("Environment variable name:").prompt()?;
+            format!("config.env.{}", name.trim())
+        } else {
+            keys[choice.index].clone()
+        };
+        edit_field(template, &key)?;
+    }
+}
+
+fn edit_metadata(template: &mut TemplateFile) -> Result<()> {
+    const BACK: &str = "Back";
+
+    let keys: Vec<String> = REQUIRED_FIELDS
+        .iter()
+        .chain(
+            OPTIONAL_FIELDS
+                .iter()
+                .filter(|key| !key.starts_with("config.")),
+        )
+        .map(|key| key.to_string())
+        .collect();
+    loop {
+        let mut options = field_options(template, &keys);
+        options.push(BACK.to_string());
+
+        let choice = Select::new("Metadata:", options).raw_prompt()?;
+        if choice.value == BACK {
+            return Ok(());
+        }
+        edit_field(template, &keys[choice.index])?;
+    }
+}
+
+fn field_options(template: &TemplateFile, keys: &[String]) -> Vec<String> {
+    keys.iter()
+        .map(|key| {
+            let value = template.field_text(key).unwrap_or_else(|| "-".to_string());
+            format!("{} = {}", key, value)
+        })
+        .collect()
+}
+
+/// Prompt for a new value; empty removes an optional field
+fn edit_field(template: &mut TemplateFile, key: &str) -> Result<()> {
+    let required = REQUIRED_FIELDS.contains(&key);
+    let help = match (LIST_FIELDS.contains(&key), required) {
+        (true, _) => "Comma-separated, or a JSON array; leave empty to remove",
+        (false, true) => "Required",
+        (false, false) => "Leave empty to remove",
+    };
+    let current = template.field_text(key).unwrap_or_default();
+    let value = Text::new(&format!("{}:", key))
+        .with_initial_value(&current)
+        .with_help_message(help)
+        .prompt()?;
+    let result = if value.trim().is_empty() && !required {
+        template.unset_field(key)
+    } else {
+        template.set_field(key, &value)
+    };
+    report(template, result);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const TEMPLATE: &str = r#"{
+  "name": "postgres",
+  "version": "1.0.0",
+  "description": "PostgreSQL",
+  "author": "Test",
+  "variables": {
+    "host": { "type": "string", "description": "Host", "default": "localhost" },
+    "port": { "type": "number", "description": "Port", "required": true }
+  },
+  "config": {
+    "command": "npx",
+    "args": ["pg-mcp", "{{host}}:{{port}}"]
+  },
+  "x-reviewed-by": "ops"
+}
+"#;
+
+    fn load(dir: &TempDir) -> TemplateFile {
+        let path = dir.path().join("postgres.json");
+        std::fs::write(&path, TEMPLATE).unwrap();
+        TemplateFile::load(&path).unwrap()
+    }
+
+    fn edits(set_var: &[&str], set: &[&str]) -> Edits {
+        Edits {
+            set_var: set_var.iter().map(|s| s.to_string()).collect(),
+            set: set.iter().map(|s| s.to_string()).collect(),
+            ..Edits::default()
+        }
+    }
+
+    #[test]
+    fn test_set_var_keeps_field_order_and_round_trips() {
+        let dir = TempDir::new().unwrap();
+        let mut file = load(&dir);
+        file.apply(&edits(&["port.default=5433"], &["version=1.1.0"]))
+            .unwrap();
+        assert!(file.problems().is_empty(), "{:?}", file.problems());
+        file.save().unwrap();
+
+        let written = std::fs::read_to_string(dir.path().join("postgres.json")).unwrap();
+        let position = |key: &str| written.find(key).unwrap();
+        assert!(position("\"name\"") < position("\"variables\""));
+        assert!(position("\"host\"") < position("\"port\""));
+        assert!(position("\"config\"") < position("\"x-reviewed-by\""));
+        assert!(written.contains("\"required\": true,\n      \"default\": 5433"));
+
+        let reread: Template = serde_json::from_str(&written).unwrap();
+        assert_eq!(reread.version, "1.1.0");
+        assert_eq!(reread.variables["port"].default, Some(json!(5433)));
+        assert!(templates::validate_template(&reread).is_empty());
+    }
+
+    #[test]
+    fn test_new_variables_and_defaults_are_checked() {
+        let dir = TempDir::new().unwrap();
+        let mut file = load(&dir);
+
+        let err = file
+            .set_variable_field("port", "default", "high")
+            .unwrap_err();
+        assert!(err.to_string().contains("must be a number"), "{}", err);
+        assert!(file.set_variable_field("9lives", "type", "string").is_err());
+        assert!(file.set_variable_field("host", "colour", "red").is_err());
+
+        file.set_variable_field("ssl", "type", "select").unwrap();
+        assert!(file
+            .problems()
+            .iter()
+            .any(|p| p.contains("'ssl' has no description")));
+        file.apply(&edits(
+            &[
+                "ssl.description=SSL mode",
+                "ssl.options=disable,require",
+                "ssl.default=require",
+            ],
+            &[],
+        ))
+        .unwrap();
+        assert!(file.problems().is_empty(), "{:?}", file.problems());
+        assert_eq!(file.variable_names(), ["host", "port", "ssl"]);
+
+        file.remove_variable("host").unwrap();
+        assert!(file.remove_variable("host").is_err());
+        assert!(file
+            .problems()
+            .contains(&"arg 1 uses 'host', which is not a variable".to_string()));
+    }
+
+    #[test]
+    fn test_switching_from_command_to_url() {
+        let dir = TempDir::new().unwrap();
+        let mut file = load(&dir);
+
+        file.set_field("config.url", "https://{{host}}/mcp")
+            .unwrap();
+        assert!(file
+            .problems()
+            .iter()
+            .any(|p| p.contains("cannot have both")));
+
+        file.apply(&Edits {
+            unset: vec!["config.command".into(), "config.args".into()],
+            set: vec!["config.env.PGPORT={{port}}".into(), "tags=db,sql".into()],
+            ..Edits::default()
+        })
+        .unwrap();
+        assert!(file.problems().is_empty(), "{:?}", file.problems());
+        let template = file.template().unwrap();
+        assert!(template.config.command.is_none());
+        assert_eq!(template.config.env.unwrap()["PGPORT"], "{{port}}");
+        assert_eq!(template.tags, ["db", "sql"]);
+
+        assert!(file.unset_field("name").is_err());
+        assert!(file.set_field("colour", "red").is_err());
+    }
+}
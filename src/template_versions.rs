@@ -0,0 +1,350 @@
+use crate::config::Target;
+use crate::github::ClientOptions;
+use crate::metadata::ServerMetadata;
+use crate::templates::{TemplateManager, TemplateMetadata};
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Where a template-based server stands against the catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionStatus {
+    /// Created from a version older than the latest, or than its pin
+    Outdated,
+    /// Held at the version it was pinned to
+    Pinned,
+    Current,
+    /// No catalog, source or local template has the template any more
+    Unlisted,
+}
+
+/// One row of `template outdated`
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerVersion {
+    pub server: String,
+    pub template: String,
+    pub version: String,
+    pub latest: Option<String>,
+    pub pinned: Option<String>,
+    pub status: VersionStatus,
+}
+
+/// Split a version like `v1.2.0-beta` into its numbers and pre-release tag
+fn parse_version(version: &str) -> Option<(Vec<u64>, Option<&str>)> {
+    let version = version.trim();
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let version = version.split('+').next().unwrap_or(version);
+    let (numbers, pre) = match version.split_once('-') {
+        Some((numbers, pre)) => (numbers, Some(pre)),
+        None => (version, None),
+    };
+    let numbers = numbers
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    Some((numbers, pre))
+}
+
+/// Order two template versions, `None` if either isn't a dotted version
+///
+/// Missing parts count as 0, so `1.2` equals `1.2.0`, and a pre-release sorts
+/// before the release it leads up to.
+pub fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    let (a_numbers, a_pre) = parse_version(a)?;
+    let (b_numbers, b_pre) = parse_version(b)?;
+    let len = a_numbers.len().max(b_numbers.len());
+    let part = |numbers: &[u64], i: usize| numbers.get(i).copied().unwrap_or(0);
+    let numbers = (0..len)
+        .map(|i| part(&a_numbers, i).cmp(&part(&b_numbers, i)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal);
+    Some(numbers.then_with(|| match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => a.cmp(b),
+    }))
+}
+
+/// Whether `version` is behind `target`; versions that can't be ordered are
+/// behind whenever they differ
+pub fn is_behind(version: &str, target: &str) -> bool {
+    match compare_versions(version, target) {
+        Some(ordering) => ordering.is_lt(),
+        None => version != target,
+    }
+}
+
+/// Compare each server's recorded template version with the latest listed
+pub fn server_versions(
+    metadata: &ServerMetadata,
+    latest: &HashMap<String, TemplateMetadata>,
+) -> Vec<ServerVersion> {
+    let mut rows: Vec<_> = metadata
+        .servers
+        .iter()
+        .map(|(server, provenance)| {
+            let latest = latest
+                .get(&provenance.template)
+                .map(|entry| entry.version.clone());
+            let status = match (&provenance.pinned, &latest) {
+                (Some(pinned), _) if is_behind(&provenance.version, pinned) => {
+                    VersionStatus::Outdated
+                }
+                (Some(_), _) => VersionStatus::Pinned,
+                (None, None) => VersionStatus::Unlisted,
+                (None, Some(latest)) if is_behind(&provenance.version, latest) => {
+                    VersionStatus::Outdated
+                }
+                (None, Some(_)) => VersionStatus::Current,
+            };
+            ServerVersion {
+                server: server.clone(),
+                template: provenance.template.clone(),
+                version: provenance.version.clone(),
+                latest,
+                pinned: provenance.pinned.clone(),
+                status,
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| a.server.cmp(&b.server));
+    rows
+}
+
+/// Templates by name, as `template list` would show them
+pub async fn latest_templates(
+    template_manager: &TemplateManager,
+    cached: bool,
+) -> Result<HashMap<String, TemplateMetadata>> {
+    if !cached {
+        let templates = template_manager.list_templates().await?;
+        return Ok(templates.into_iter().map(|t| (t.name.clone(), t)).collect());
+    }
+    let mut templates = template_manager
+        .load_cached_catalog()?
+        .map(|catalog| catalog.templates)
+        .unwrap_or_default();
+    for (name, metadata) in template_manager.cached_source_templates() {
+        templates.entry(name).or_insert(metadata);
+    }
+    templates.extend(template_manager.local_templates());
+    Ok(templates)
+}
+
+/// Handle `template pin`, holding a server at a template version
+pub fn handle_template_pin(server: &str, version: Option<String>, target: &Target) -> Result<()> {
+    let mut metadata = ServerMetadata::load(target)?;
+    let provenance = metadata.servers.get_mut(server).ok_or_else(|| {
+        anyhow!(
+            "Server '{}' wasn't added from a template, so it has no template version to pin",
+            server
+        )
+    })?;
+
+    match version {
+        Some(version) => {
+            if parse_version(&version).is_none() {
+                return Err(anyhow!(
+                    "'{}' is not a version; expected something like 1.2.0",
+                    version
+                ));
+            }
+            println!(
+                "{}",
+                format!(
+                    "✓ Pinned {} to {} v{} (created from v{})",
+                    server, provenance.template, version, provenance.version
+                )
+                .green()
+            );
+            provenance.pinned = Some(version);
+        }
+        None => {
+            if provenance.pinned.take().is_none() {
+                println!("{} is not pinned", server);
+                return Ok(());
+            }
+            println!("{}", format!("✓ Unpinned {}", server).green());
+        }
+    }
+
+    metadata.save(target)
+}
+
+/// Handle `template outdated`, listing servers created from older template
+/// versions
+pub async fn handle_template_outdated(
+    cached: bool,
+    json: bool,
+    target: &Target,
+    github: &ClientOptions,
+) -> Result<()> {
+    let metadata = ServerMetadata::load(target)?;
+    let template_manager = TemplateManager::new(github)?;
+    let latest = latest_templates(&template_manager, cached).await?;
+    let rows = server_versions(&metadata, &latest);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    if rows.is_empty() {
+        println!("No servers were added from a template");
+        return Ok(());
+    }
+
+    let count = |status| rows.iter().filter(|row| row.status == status).count();
+    for row in &rows {
+        match row.status {
+            VersionStatus::Outdated => {
+                let target = row.pinned.as_ref().or(row.latest.as_ref());
+                let pin_note = if row.pinned.is_some() {
+                    " (pinned)"
+                } else {
+                    ""
+                };
+                println!(
+                    "  {} {} - {} v{} → v{}{}",
+                    "↑".yellow(),
+                    row.server,
+                    row.template,
+                    row.version,
+                    target.map(String::as_str).unwrap_or("?"),
+                    pin_note
+                );
+            }
+            VersionStatus::Pinned => println!(
+                "  📌 {} - {} v{} pinned to v{} (latest {})",
+                row.server,
+                row.template,
+                row.version,
+                row.pinned.as_deref().unwrap_or("?"),
+                row.latest
+                    .as_deref()
+                    .map(|latest| format!("v{}", latest))
+                    .unwrap_or_else(|| "unlisted".to_string())
+            ),
+            VersionStatus::Unlisted => println!(
+                "  {} {} - {} v{} is no longer listed",
+                "?".dimmed(),
+                row.server,
+                row.template,
+                row.version
+            ),
+            VersionStatus::Current => {}
+        }
+    }
+
+    let outdated = count(VersionStatus::Outdated);
+    if outdated == 0 {
+        println!(
+            "{}",
+            "✓ Every server is on its template's latest or pinned version".green()
+        );
+    }
+    println!(
+        "{} outdated, {} pinned, {} current, {} unlisted",
+        outdated,
+        count(VersionStatus::Pinned),
+        count(VersionStatus::Current),
+        count(VersionStatus::Unlisted)
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::ServerProvenance;
+
+    fn provenance(template: &str, version: &str, pinned: Option<&str>) -> ServerProvenance {
+        ServerProvenance {
+            template: template.to_string(),
+            version: version.to_string(),
+            author: "Test".to_string(),
+            category: None,
+            tags: Vec::new(),
+            requirements: None,
+            added_at: chrono::Utc::now(),
+            notes: None,
+            variables: HashMap::new(),
+            platform_override: None,
+            docs_url: None,
+            pinned: pinned.map(str::to_string),
+        }
+    }
+
+    fn entry(name: &str, version: &str) -> (String, TemplateMetadata) {
+        let metadata = TemplateMetadata {
+            name: name.to_string(),
+            version: version.to_string(),
+            description: String::new(),
+            author: "Test".to_string(),
+            tags: Vec::new(),
+            platforms: Vec::new(),
+            category: "official".to_string(),
+            path: format!("templates/{}.json", name),
+            updated_at: None,
+            checksum: None,
+            docs_url: None,
+            source: None,
+        };
+        (name.to_string(), metadata)
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.0.0", "1.0.1"), Some(Ordering::Less));
+        assert_eq!(compare_versions("1.10.0", "1.9.3"), Some(Ordering::Greater));
+        assert_eq!(compare_versions("v1.2", "1.2.0"), Some(Ordering::Equal));
+        assert_eq!(
+            compare_versions("2.0.0-beta", "2.0.0"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_versions("1.0.0+build.5", "1.0.0"),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(compare_versions("latest", "1.0.0"), None);
+        assert!(is_behind("nightly", "1.0.0"));
+        assert!(!is_behind("nightly", "nightly"));
+    }
+
+    #[test]
+    fn test_server_versions() {
+        let mut metadata = ServerMetadata::default();
+        metadata.record("old", provenance("postgres", "1.0.0", None));
+        metadata.record("new", provenance("postgres", "1.2.0", None));
+        metadata.record("held", provenance("postgres", "1.0.0", Some("1.0.0")));
+        metadata.record("behind-pin", provenance("postgres", "1.0.0", Some("1.1.0")));
+        metadata.record("gone", provenance("retired", "0.1.0", None));
+        let latest = HashMap::from([entry("postgres", "1.2.0")]);
+
+        let statuses: Vec<_> = server_versions(&metadata, &latest)
+            .into_iter()
+            .map(|row| (row.server, row.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("behind-pin".to_string(), VersionStatus::Outdated),
+                ("gone".to_string(), VersionStatus::Unlisted),
+                ("held".to_string(), VersionStatus::Pinned),
+                ("new".to_string(), VersionStatus::Current),
+                ("old".to_string(), VersionStatus::Outdated),
+            ]
+        );
+
+        // Re-creating a server keeps its pin
+        metadata.record("held", provenance("postgres", "1.2.0", None));
+        assert_eq!(
+            metadata.get("held").unwrap().pinned.as_deref(),
+            Some("1.0.0")
+        );
+    }
+}
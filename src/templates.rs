@@ -1,8 +1,17 @@
+use crate::clock::Clock;
+use crate::deletions::PlannedDeletions;
+use crate::github::{ClientOptions, Fetched, TemplateRepository};
+use crate::utils::duration::{format_duration, parse_duration};
+use crate::utils::platform;
 use anyhow::{Context, Result};
+use colored::Colorize;
 use handlebars::Handlebars;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 
 /// Template variable types
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -15,6 +24,19 @@ pub enum VariableType {
     Select,
 }
 
+impl std::fmt::Display for VariableType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            VariableType::String => "string",
+            VariableType::Boolean => "boolean",
+            VariableType::Number => "number",
+            VariableType::Array => "array",
+            VariableType::Select => "select",
+        };
+        f.write_str(name)
+    }
+}
+
 /// Template variable definition with enhanced validation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TemplateVariable {
@@ -29,6 +51,9 @@ pub struct TemplateVariable {
     pub validation: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<Vec<String>>, // For select type
+    /// Prompt without echo, never record the value, and always mask it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub secret: bool,
 }
 
 /// Enhanced template configuration
@@ -38,19 +63,460 @@ pub struct Template {
     pub version: String,
     pub description: String,
     pub author: String,
+    /// Template this one builds on; fields it leaves out come from the parent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    #[serde(default)]
     pub tags: Vec<String>,
+    #[serde(default)]
     pub platforms: Vec<String>,
+    #[serde(default)]
     pub variables: HashMap<String, TemplateVariable>,
+    #[serde(default)]
     pub config: TemplateConfig,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub requirements: Option<HashMap<String, String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub setup_instructions: Option<String>,
+    /// Where the server is documented; also read from `homepage`
+    #[serde(default, alias = "homepage", skip_serializing_if = "Option::is_none")]
+    pub docs_url: Option<String>,
+    /// Templates merged in through `extends`, nearest parent first
+    #[serde(skip)]
+    pub inherits: Vec<String>,
+}
+
+/// How many templates an `extends` chain may go up
+pub const MAX_EXTENDS_DEPTH: usize = 5;
+
+impl Template {
+    /// Fail with the supported platforms when the template doesn't list `platform`
+    pub fn check_platform(&self, platform: &str) -> Result<()> {
+        if supports_platform(&self.platforms, platform) {
+            return Ok(());
+        }
+        Err(anyhow::anyhow!(
+            "Template '{}' supports {} only, not {}. Pass --ignore-platform to add it anyway",
+            self.name,
+            self.platforms.join(", "),
+            platform
+        ))
+    }
+
+    /// Risky patterns in the template's launch settings, for anyone about to reuse it
+    pub fn security_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        let mut names: Vec<&String> = self.variables.keys().collect();
+        names.sort();
+        for name in names {
+            if !self.variables[name].secret && crate::utils::is_sensitive_env_key(name) {
+                warnings.push(format!(
+                    "Variable '{}' looks like a credential but isn't marked secret",
+                    name
+                ));
+            }
+        }
+
+        if let Some(env) = &self.config.env {
+            let mut keys: Vec<&String> = env.keys().collect();
+            keys.sort();
+            for key in keys {
+                if crate::utils::is_sensitive_env_key(key) && !env[key].contains("{{") {
+                    warnings.push(format!(
+                        "Env '{}' has a hardcoded value instead of a variable",
+                        key
+                    ));
+                }
+            }
+        }
+
+        let command_line = std::iter::once(self.config.command.as_deref().unwrap_or(""))
+            .chain(self.config.args.iter().flatten().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let downloads = command_line.contains("curl ") || command_line.contains("wget ");
+        let pipes_to_shell = ["| sh", "| bash", "|sh", "|bash"]
+            .iter()
+            .any(|pipe| command_line.contains(pipe));
+        if downloads && pipes_to_shell {
+            warnings.push("Command pipes a download into a shell".to_string());
+        }
+
+        if let Some(url) = &self.config.url {
+            let local = ["http://localhost", "http://127.0.0.1"]
+                .iter()
+                .any(|prefix| url.starts_with(prefix));
+            if url.starts_with("http://") && !local {
+                warnings.push(format!("Endpoint '{}' uses plain HTTP", url));
+            }
+        }
+
+        warnings
+    }
+
+    /// Whether a variable is flagged `secret`
+    pub fn is_secret(&self, name: &str) -> bool {
+        self.variables.get(name).is_some_and(|v| v.secret)
+    }
+
+    /// The non-empty values given for secret variables, for masking output
+    pub fn secret_values(&self, variables: &HashMap<String, serde_json::Value>) -> Vec<String> {
+        variables
+            .iter()
+            .filter(|(name, _)| self.is_secret(name))
+            .filter_map(|(_, value)| match value {
+                serde_json::Value::String(s) => Some(s.clone()),
+                serde_json::Value::Null => None,
+                other => Some(other.to_string()),
+            })
+            .filter(|value| !value.is_empty())
+            .collect()
+    }
+
+    /// Copy with each non-secret variable defaulting to its previous value
+    ///
+    /// Secret values are never recorded, so secret variables keep the
+    /// template's own default and are asked for afresh.
+    pub fn with_previous_values(&self, previous: &HashMap<String, serde_json::Value>) -> Self {
+        let mut prefilled = self.clone();
+        for (name, variable) in &mut prefilled.variables {
+            let Some(value) = previous.get(name).filter(|_| !variable.secret) else {
+                continue;
+            };
+            // Text prompts take their default as a string
+            let default = match value {
+                serde_json::Value::Array(items) => serde_json::Value::String(
+                    items
+                        .iter()
+                        .map(|item| {
+                            item.as_str()
+                                .map_or_else(|| item.to_string(), str::to_string)
+                        })
+                        .collect::<Vec<_>>()
+                        .join(","),
+                ),
+                serde_json::Value::Number(n) => serde_json::Value::String(n.to_string()),
+                other => other.clone(),
+            };
+            variable.default = Some(default);
+        }
+        prefilled
+    }
+
+    /// Variable values that can be read back from a server this template made
+    ///
+    /// An env value or argument that is nothing but a placeholder holds that
+    /// variable's value as given. Arguments are only read when the server has
+    /// as many as the template, so they line up.
+    pub fn recover_values(
+        &self,
+        server: &crate::config::McpServer,
+    ) -> HashMap<String, serde_json::Value> {
+        let mut values = HashMap::new();
+        let mut recover = |source: &str, value: &str| {
+            if let Some(name) = lone_placeholder(source) {
+                if self.variables.contains_key(name) {
+                    values.insert(
+                        name.to_string(),
+                        serde_json::Value::String(value.to_string()),
+                    );
+                }
+            }
+        };
+
+        let env = server.env.as_ref();
+        for (key, source) in self.config.env.iter().flatten() {
+            if let Some(value) = env.and_then(|env| env.get(key)) {
+                recover(source, value);
+            }
+        }
+        if let (Some(sources), Some(args)) = (&self.config.args, &server.args) {
+            if sources.len() == args.len() {
+                for (source, value) in sources.iter().zip(args) {
+                    recover(source, value);
+                }
+            }
+        }
+        values
+    }
+
+    /// Carry changes made to a server since it was created over to a rebuilt copy
+    ///
+    /// Client options and unknown fields carry over, as do env keys the
+    /// template doesn't set.
+    pub fn keep_local_changes(
+        &self,
+        current: &crate::config::McpServer,
+        rebuilt: &mut crate::config::McpServer,
+    ) {
+        rebuilt.timeout = current.timeout;
+        rebuilt.auto_approve = current.auto_approve.clone();
+        rebuilt.other = current.other.clone();
+
+        let template_env = self.config.env.as_ref();
+        for (key, value) in current.env.iter().flatten() {
+            if !template_env.is_some_and(|env| env.contains_key(key)) {
+                rebuilt
+                    .env
+                    .get_or_insert_with(HashMap::new)
+                    .insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    /// Merge `parent` under this template: anything set here wins
+    ///
+    /// Variables and requirements merge by name and env maps by key. Tags
+    /// are combined. Setting `command` or `url` replaces the parent's way of
+    /// launching the server, so the two never end up mixed.
+    pub fn merge_parent(mut self, parent: &Template) -> Self {
+        let mut variables = parent.variables.clone();
+        variables.extend(self.variables);
+        self.variables = variables;
+
+        let mut tags = parent.tags.clone();
+        for tag in self.tags {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        self.tags = tags;
+
+        if self.platforms.is_empty() {
+            self.platforms = parent.platforms.clone();
+        }
+
+        self.requirements = merge_maps(parent.requirements.as_ref(), self.requirements);
+        if self.setup_instructions.is_none() {
+            self.setup_instructions = parent.setup_instructions.clone();
+        }
+        if self.docs_url.is_none() {
+            self.docs_url = parent.docs_url.clone();
+        }
+
+        let own = &mut self.config;
+        let inherited = &parent.config;
+        let replaces_launch =
+            own.url.is_some() || (own.command.is_some() && own.command != inherited.command);
+        if !replaces_launch {
+            if own.command.is_none() {
+                own.command = inherited.command.clone();
+                own.url = inherited.url.clone();
+            }
+            if own.args.is_none() {
+                own.args = inherited.args.clone();
+            }
+        }
+        own.env = merge_maps(inherited.env.as_ref(), own.env.take());
+
+        self.inherits.push(parent.name.clone());
+        self
+    }
+
+    /// Variables overridden with a different type than the parent gave them
+    pub fn incompatible_overrides(&self, parent: &Template) -> Vec<String> {
+        let mut names: Vec<&String> = self.variables.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let own = &self.variables[name].var_type;
+                let inherited = &parent.variables.get(name)?.var_type;
+                (own != inherited).then(|| {
+                    format!(
+                        "Variable '{}' is a {} here but a {} in '{}', which it extends",
+                        name, own, inherited, parent.name
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// `parent` with `child`'s entries laid over it
+fn merge_maps(
+    parent: Option<&HashMap<String, String>>,
+    child: Option<HashMap<String, String>>,
+) -> Option<HashMap<String, String>> {
+    match (parent, child) {
+        (None, child) => child,
+        (Some(parent), None) => Some(parent.clone()),
+        (Some(parent), Some(child)) => {
+            let mut merged = parent.clone();
+            merged.extend(child);
+            Some(merged)
+        }
+    }
+}
+
+/// Fail if following `extends` from `chain` to `parent` would loop or go too deep
+///
+/// `chain` holds the templates loaded so far, child first.
+pub fn check_extends(chain: &[Template], parent: &str) -> Result<()> {
+    let names = || {
+        chain
+            .iter()
+            .map(|t| t.name.as_str())
+            .chain(std::iter::once(parent))
+            .collect::<Vec<_>>()
+            .join(" → ")
+    };
+    if chain.iter().any(|t| t.name == parent) {
+        return Err(anyhow::anyhow!("Template inheritance cycle: {}", names()));
+    }
+    if chain.len() > MAX_EXTENDS_DEPTH {
+        return Err(anyhow::anyhow!(
+            "Template '{}' extends more than {} templates deep: {}",
+            chain[0].name,
+            MAX_EXTENDS_DEPTH,
+            names()
+        ));
+    }
+    Ok(())
+}
+
+/// Flatten an `extends` chain, child first, into one template
+pub fn merge_chain(chain: Vec<Template>) -> Template {
+    let mut chain = chain.into_iter();
+    let child = chain
+        .next()
+        .expect("an extends chain starts with the child");
+    chain.fold(child, |merged, parent| merged.merge_parent(&parent))
+}
+
+/// Everything wrong with a template as written, for authors checking their work
+///
+/// A template that extends another may take its command or URL, and the
+/// variables its placeholders use, from the parent.
+pub fn validate_template(template: &Template) -> Vec<String> {
+    let mut problems = Vec::new();
+    for (field, value) in [
+        ("name", &template.name),
+        ("version", &template.version),
+        ("description", &template.description),
+        ("author", &template.author),
+    ] {
+        if value.trim().is_empty() {
+            problems.push(format!("'{}' is empty", field));
+        }
+    }
+
+    let inherits_launch = template.extends.is_some()
+        && template.config.command.is_none()
+        && template.config.url.is_none();
+    if !inherits_launch {
+        if let Err(e) = template.config.validate() {
+            problems.push(format!("invalid config: {}", e));
+        }
+    }
+
+    let mut names: Vec<&String> = template.variables.keys().collect();
+    names.sort();
+    for name in names {
+        let variable = &template.variables[name];
+        if variable.description.trim().is_empty() {
+            problems.push(format!("variable '{}' has no description", name));
+        }
+        match (&variable.var_type, &variable.options) {
+            (VariableType::Select, None) => {
+                problems.push(format!("variable '{}' is a select with no options", name))
+            }
+            (VariableType::Select, Some(options)) if options.is_empty() => {
+                problems.push(format!("variable '{}' is a select with no options", name))
+            }
+            (VariableType::Select, Some(_)) | (_, None) => {}
+            (var_type, Some(_)) => problems.push(format!(
+                "variable '{}' has options but is a {}; only select variables use them",
+                name, var_type
+            )),
+        }
+        if let Some(pattern) = &variable.validation {
+            if let Err(e) = regex::Regex::new(pattern) {
+                // The regex error draws a caret under the pattern; keep its last line
+                let e = e.to_string();
+                let reason = e.lines().last().unwrap_or_default();
+                problems.push(format!(
+                    "variable '{}' has an invalid validation pattern '{}': {}",
+                    name,
+                    pattern,
+                    reason.trim_start_matches("error: ")
+                ));
+            }
+        }
+        if let Some(default) = &variable.default {
+            if let Err(reason) = coerce_value(default, variable) {
+                problems.push(format!(
+                    "variable '{}' has a default that is not a {}: {}",
+                    name, variable.var_type, reason
+                ));
+            }
+        }
+    }
+
+    if template.extends.is_none() {
+        for (field, source) in config_sources(&template.config) {
+            for placeholder in placeholders(source) {
+                if !template.variables.contains_key(&placeholder)
+                    && !HELPERS.contains(&placeholder.as_str())
+                {
+                    problems.push(format!(
+                        "{} uses '{}', which is not a variable",
+                        field, placeholder
+                    ));
+                }
+            }
+        }
+    }
+    problems
+}
+
+/// Every rendered string in a template's config, with the field it's in
+fn config_sources(config: &TemplateConfig) -> Vec<(RenderField, &str)> {
+    let mut sources = Vec::new();
+    if let Some(command) = &config.command {
+        sources.push((RenderField::Command, command.as_str()));
+    }
+    if let Some(url) = &config.url {
+        sources.push((RenderField::Url, url.as_str()));
+    }
+    for (index, arg) in config.args.iter().flatten().enumerate() {
+        sources.push((RenderField::Arg(index), arg.as_str()));
+    }
+    let mut env: Vec<(&String, &String)> = config.env.iter().flatten().collect();
+    env.sort();
+    for (key, value) in env {
+        sources.push((RenderField::EnvKey(key.clone()), key.as_str()));
+        sources.push((RenderField::EnvValue(key.clone()), value.as_str()));
+    }
+    sources
+}
+
+fn placeholder_pattern() -> &'static regex::Regex {
+    static PLACEHOLDER: OnceLock<regex::Regex> = OnceLock::new();
+    PLACEHOLDER.get_or_init(|| {
+        regex::Regex::new(r"\{\{\{?\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}?\}\}")
+            .expect("placeholder pattern is valid")
+    })
+}
+
+/// Names used as plain `{{name}}` placeholders; block helpers are left alone
+fn placeholders(source: &str) -> Vec<String> {
+    placeholder_pattern()
+        .captures_iter(source)
+        .map(|captures| captures[1].to_string())
+        .collect()
+}
+
+/// The variable `source` consists of, if it is nothing but one placeholder
+fn lone_placeholder(source: &str) -> Option<&str> {
+    let captures = placeholder_pattern().captures(source.trim())?;
+    (captures[0].len() == source.trim().len()).then(|| captures.get(1).unwrap().as_str())
 }
 
 /// Template configuration section
 /// Supports both command-based and URL-based servers
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TemplateConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub command: Option<String>,
@@ -113,87 +579,499 @@ pub struct TemplateMetadata {
     pub author: String,
     pub tags: Vec<String>,
     pub platforms: Vec<String>,
-    pub category: String, // "official", "community", "experimental"
+    pub category: String, // "official", "community", "experimental", "local"
     pub path: String,     // Path in repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Checksum of the template file, as `algorithm:hex`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// Where the server is documented; also read from `homepage`
+    #[serde(default, alias = "homepage", skip_serializing_if = "Option::is_none")]
+    pub docs_url: Option<String>,
+    /// Name of the source the entry was listed from, set when sources are merged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// What changed between two catalogs, each list in name order
+#[derive(Debug, Default)]
+pub struct CatalogDiff {
+    pub added: Vec<TemplateMetadata>,
+    pub removed: Vec<TemplateMetadata>,
+    /// Templates whose version or category changed, as (before, after)
+    pub updated: Vec<(TemplateMetadata, TemplateMetadata)>,
+}
+
+impl CatalogDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+}
+
+/// Compare the catalog from one refresh with the next
+pub fn diff_catalogs(before: &TemplateCatalog, after: &TemplateCatalog) -> CatalogDiff {
+    let mut diff = CatalogDiff::default();
+    let names: std::collections::BTreeSet<&String> = before
+        .templates
+        .keys()
+        .chain(after.templates.keys())
+        .collect();
+    for name in names {
+        match (before.templates.get(name), after.templates.get(name)) {
+            (None, Some(added)) => diff.added.push(added.clone()),
+            (Some(removed), None) => diff.removed.push(removed.clone()),
+            (Some(old), Some(new)) => {
+                if old.version != new.version || old.category != new.category {
+                    diff.updated.push((old.clone(), new.clone()));
+                }
+            }
+            (None, None) => {}
+        }
+    }
+    diff
+}
+
+/// A template file's contents as stored, with where they were read from
+pub struct RawTemplate {
+    pub content: String,
+    pub source: TemplateOrigin,
+}
+
+/// Where a raw template was read from
+pub enum TemplateOrigin {
+    Local(PathBuf),
+    Cache(PathBuf),
+    /// Downloaded from the template repository, described as `owner/repo@branch`
+    Network(String),
+}
+
+impl std::fmt::Display for TemplateOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateOrigin::Local(path) => write!(f, "local file {}", path.display()),
+            TemplateOrigin::Cache(path) => write!(f, "cache ({})", path.display()),
+            TemplateOrigin::Network(repo) => write!(f, "network ({})", repo),
+        }
+    }
+}
+
+/// Name of the source for the configured template repository
+pub const DEFAULT_SOURCE: &str = "default";
+
+/// Somewhere templates are published, registered with `template source add`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateSource {
+    /// A GitHub repository with a `catalog.json`, like the default one
+    Github(TemplateRepository),
+    /// A directory with a `catalog.json`, or else just template files
+    Dir(PathBuf),
+    /// A `catalog.json` served over HTTP(S); entry paths are relative to it
+    Url(String),
+}
+
+impl TemplateSource {
+    /// Parse a source spec: an `http(s)://` URL of a catalog, a directory, or
+    /// a GitHub `owner/repo[@branch]`
+    ///
+    /// Paths that start with `/` or `.`, or that name an existing directory,
+    /// are directories; they are stored as absolute paths.
+    pub fn parse(spec: &str) -> Result<Self> {
+        if spec.starts_with("https://") || spec.starts_with("http://") {
+            url::Url::parse(spec).map_err(|e| anyhow::anyhow!("Invalid URL '{}': {}", spec, e))?;
+            return Ok(Self::Url(spec.to_string()));
+        }
+        if spec.starts_with(['/', '.']) || Path::new(spec).is_dir() {
+            let path = std::fs::canonicalize(spec)
+                .with_context(|| format!("Template directory not found: {}", spec))?;
+            if !path.is_dir() {
+                return Err(anyhow::anyhow!("{} is not a directory", path.display()));
+            }
+            return Ok(Self::Dir(path));
+        }
+        TemplateRepository::parse(spec).map(Self::Github).map_err(|_| {
+            anyhow::anyhow!(
+                "Invalid template source '{}'; expected owner/repo[@branch], a directory, or an http(s) URL of a catalog.json",
+                spec
+            )
+        })
+    }
+}
+
+impl std::fmt::Display for TemplateSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateSource::Github(repo) => write!(f, "github {}", repo.describe()),
+            TemplateSource::Dir(path) => write!(f, "dir {}", path.display()),
+            TemplateSource::Url(url) => write!(f, "url {}", url),
+        }
+    }
+}
+
+/// A template source under the name it was registered with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamedSource {
+    pub name: String,
+    #[serde(flatten)]
+    pub source: TemplateSource,
+}
+
+/// Check a name for `template source add`: short, path-safe, and not one
+/// the tool uses itself
+pub fn check_source_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(anyhow::anyhow!(
+            "Invalid source name '{}'; use letters, digits, '-' and '_'",
+            name
+        ));
+    }
+    if name == DEFAULT_SOURCE || name == "local" {
+        return Err(anyhow::anyhow!(
+            "'{}' is reserved; choose another source name",
+            name
+        ));
+    }
+    Ok(())
 }
 
 /// Cache metadata for tracking updates
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheMetadata {
     pub last_refresh: chrono::DateTime<chrono::Utc>,
-    pub etag: Option<String>,
+    /// ETag of each cached template as GitHub last served it, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub template_etags: BTreeMap<String, String>,
     pub catalog_etag: Option<String>,
     pub expires_at: chrono::DateTime<chrono::Utc>,
+    /// Repository the cache was filled from, as `owner/repo@branch`; caches
+    /// from before this was recorded came from the default repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
 }
 
-impl Default for CacheMetadata {
-    fn default() -> Self {
+impl CacheMetadata {
+    /// Metadata for a cache refreshed at `now`
+    pub fn refreshed_at(now: chrono::DateTime<chrono::Utc>) -> Self {
         Self {
-            last_refresh: chrono::Utc::now(),
-            etag: None,
+            last_refresh: now,
+            template_etags: BTreeMap::new(),
             catalog_etag: None,
-            expires_at: chrono::Utc::now() + chrono::Duration::days(30), // 1 month cache
+            expires_at: now + cache_ttl(),
+            repo: None,
+        }
+    }
+
+    /// Whether the cache needs refreshing at `now`
+    pub fn is_expired(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        now > self.expires_at
+    }
+}
+
+/// How long a refreshed template cache stays fresh by default
+const DEFAULT_CACHE_TTL: chrono::Duration = chrono::Duration::days(30);
+
+/// Template cache lifetime, set with `MCP_FORGE_CACHE_TTL` (e.g. `7d`, `12h`)
+fn cache_ttl() -> chrono::Duration {
+    cache_ttl_from(std::env::var("MCP_FORGE_CACHE_TTL").ok().as_deref())
+}
+
+fn cache_ttl_from(setting: Option<&str>) -> chrono::Duration {
+    match setting.map(parse_duration) {
+        None => DEFAULT_CACHE_TTL,
+        Some(Ok(ttl)) => ttl,
+        Some(Err(e)) => {
+            eprintln!(
+                "⚠ MCP_FORGE_CACHE_TTL: {}; using {}",
+                e,
+                format_duration(DEFAULT_CACHE_TTL)
+            );
+            DEFAULT_CACHE_TTL
         }
     }
 }
 
+/// Most update times looked up on GitHub in one run; one request each
+const MAX_UPDATE_LOOKUPS: usize = 20;
+
+/// Cached template data older than this gets a refresh reminder by default
+const DEFAULT_STALE_AFTER: chrono::Duration = chrono::Duration::days(14);
+
+/// Age at which commands remind you to refresh cached template data, set with
+/// `MCP_FORGE_STALE_AFTER` (e.g. `30d`, or `off` for no reminder)
+fn stale_after() -> Option<chrono::Duration> {
+    stale_after_from(std::env::var("MCP_FORGE_STALE_AFTER").ok().as_deref())
+}
+
+fn stale_after_from(setting: Option<&str>) -> Option<chrono::Duration> {
+    match setting {
+        None => Some(DEFAULT_STALE_AFTER),
+        Some(value) if value.trim().eq_ignore_ascii_case("off") => None,
+        Some(value) => match parse_duration(value) {
+            Ok(threshold) => Some(threshold),
+            Err(e) => {
+                eprintln!(
+                    "⚠ MCP_FORGE_STALE_AFTER: {}; using {}",
+                    e,
+                    format_duration(DEFAULT_STALE_AFTER)
+                );
+                Some(DEFAULT_STALE_AFTER)
+            }
+        },
+    }
+}
+
+/// Reminder to refresh template data last refreshed at `last_refresh`, once it
+/// is older than `threshold`
+fn stale_cache_notice(
+    last_refresh: chrono::DateTime<chrono::Utc>,
+    now: chrono::DateTime<chrono::Utc>,
+    threshold: Option<chrono::Duration>,
+) -> Option<String> {
+    (now - last_refresh > threshold?).then(|| {
+        format!(
+            "template data was last refreshed {} — run `mcp-forge template refresh`",
+            crate::timefmt::format_age(last_refresh, now)
+        )
+    })
+}
+
 /// Template manager for handling template operations
 pub struct TemplateManager {
-    cache_dir: PathBuf,
-    templates_dir: PathBuf,
-    handlebars: Handlebars<'static>,
-    github_client: crate::github::GitHubClient,
+    /// Resolved, and created, the first time the cache is touched
+    cache_dir: OnceLock<PathBuf>,
+    handlebars: OnceLock<Handlebars<'static>>,
+    /// Built the first time a template has to be fetched
+    github_client: OnceLock<crate::github::GitHubClient>,
+    /// Clients for added GitHub sources, keyed by repository, so each keeps
+    /// the rate limit it last saw
+    source_clients: Mutex<HashMap<String, Arc<crate::github::GitHubClient>>>,
+    /// Set once a catalog or template has been served from the cache
+    served_from_cache: AtomicBool,
+    /// Warn instead of failing when a rendered server still holds `{{...}}`
+    allow_unrendered: bool,
+    /// Directories of local templates, highest precedence first
+    local_dirs: Vec<PathBuf>,
+    /// Where templates are downloaded from
+    repo: TemplateRepository,
+    /// How the GitHub clients behave, from the global flags
+    github_options: ClientOptions,
+    /// Sources added with `template source add`, consulted after `repo`
+    sources: Vec<NamedSource>,
+    /// The one source to load templates from, set with `--source`
+    selected_source: Option<String>,
+    clock: Arc<dyn Clock>,
+}
+
+/// Which sources a template may be loaded from
+#[derive(Clone, Copy)]
+enum Selection<'a> {
+    /// Local templates, then the repository, then the added sources in order
+    Any,
+    /// The configured repository only
+    Default,
+    /// One added source only
+    Source(&'a NamedSource),
 }
 
 impl TemplateManager {
     /// Create a new template manager
-    pub fn new() -> Result<Self> {
-        let cache_dir = dirs::cache_dir()
-            .ok_or_else(|| anyhow::anyhow!("Unable to determine cache directory"))?
-            .join("mcp-forge");
+    ///
+    /// Nothing is touched on disk or over the network until a method needs it,
+    /// so constructing one is cheap.
+    pub fn new(github_options: &ClientOptions) -> Result<Self> {
+        Ok(Self {
+            cache_dir: OnceLock::new(),
+            handlebars: OnceLock::new(),
+            github_client: OnceLock::new(),
+            source_clients: Mutex::new(HashMap::new()),
+            served_from_cache: AtomicBool::new(false),
+            allow_unrendered: false,
+            local_dirs: local_template_dirs(),
+            repo: TemplateRepository::configured(github_options)?,
+            github_options: github_options.clone(),
+            sources: crate::settings::Settings::load()?.template_sources,
+            selected_source: None,
+            clock: crate::clock::system(),
+        })
+    }
+
+    /// Load templates only from the source named `name`, if given, instead of
+    /// the first source that has them
+    pub fn only_from_source(mut self, name: Option<String>) -> Result<Self> {
+        if let Some(name) = &name {
+            if name != DEFAULT_SOURCE && !self.sources.iter().any(|s| &s.name == name) {
+                return Err(anyhow::anyhow!(
+                    "Unknown template source '{}'; sources: {}",
+                    name,
+                    self.source_names().join(", ")
+                ));
+            }
+        }
+        self.selected_source = name;
+        Ok(self)
+    }
+
+    /// Keep the cache in `dir` instead of the user's cache directory
+    #[cfg(test)]
+    fn with_cache_dir(self, dir: PathBuf) -> Self {
+        std::fs::create_dir_all(dir.join("templates")).unwrap();
+        self.cache_dir.set(dir).unwrap();
+        self
+    }
+
+    /// Look for local templates in `dirs` instead of the usual places
+    #[cfg(test)]
+    fn with_local_dirs(mut self, dirs: Vec<PathBuf>) -> Self {
+        self.local_dirs = dirs;
+        self
+    }
 
-        let templates_dir = cache_dir.join("templates");
+    /// Download templates from `repo` instead of the configured repository
+    #[cfg(test)]
+    fn with_repo(mut self, repo: TemplateRepository) -> Self {
+        self.repo = repo;
+        self
+    }
 
-        std::fs::create_dir_all(&cache_dir)?;
-        std::fs::create_dir_all(&templates_dir)?;
+    /// Consult `sources` after the repository instead of the saved ones
+    #[cfg(test)]
+    fn with_sources(mut self, sources: Vec<NamedSource>) -> Self {
+        self.sources = sources;
+        self
+    }
 
-        let mut handlebars = Handlebars::new();
-        handlebars.set_strict_mode(true);
+    /// Talk to GitHub through `client` instead of one built on first use
+    #[cfg(test)]
+    fn with_github(self, client: crate::github::GitHubClient) -> Self {
+        self.github_client.set(client).ok().unwrap();
+        self
+    }
 
-        // Register built-in helpers
-        handlebars.register_helper("os", Box::new(os_helper));
-        handlebars.register_helper("arch", Box::new(arch_helper));
-        handlebars.register_helper("home_dir", Box::new(home_dir_helper));
-        handlebars.register_helper("config_dir", Box::new(config_dir_helper));
+    /// Use another clock for cache expiry and staleness
+    #[cfg(test)]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
 
-        Ok(Self {
-            cache_dir,
-            templates_dir,
-            handlebars,
-            github_client: crate::github::GitHubClient::new(),
+    /// The cache directory, created (or a fallback chosen) on first use
+    fn cache_dir(&self) -> Result<&Path> {
+        if let Some(dir) = self.cache_dir.get() {
+            return Ok(dir);
+        }
+        let dir = resolve_cache_dir(dirs::cache_dir())?;
+        Ok(self.cache_dir.get_or_init(|| dir))
+    }
+
+    fn templates_dir(&self) -> Result<PathBuf> {
+        Ok(self.cache_dir()?.join("templates"))
+    }
+
+    fn handlebars(&self) -> &Handlebars<'static> {
+        self.handlebars.get_or_init(|| {
+            let mut handlebars = Handlebars::new();
+            handlebars.set_strict_mode(true);
+            // Rendered values are command lines and URLs, not HTML
+            handlebars.register_escape_fn(handlebars::no_escape);
+
+            // Register built-in helpers
+            // Keep HELPERS in step with this list
+            handlebars.register_helper("os", Box::new(os_helper));
+            handlebars.register_helper("arch", Box::new(arch_helper));
+            handlebars.register_helper("home_dir", Box::new(home_dir_helper));
+            handlebars.register_helper("config_dir", Box::new(config_dir_helper));
+            handlebars
         })
     }
 
+    /// The GitHub client, built on first use
+    fn github(&self) -> Result<&crate::github::GitHubClient> {
+        if let Some(client) = self.github_client.get() {
+            return Ok(client);
+        }
+        let client =
+            crate::github::GitHubClient::for_repo(self.repo.clone(), &self.github_options)?;
+        Ok(self.github_client.get_or_init(|| client))
+    }
+
+    /// The client for an added GitHub source, built the first time it's needed
+    fn source_github(&self, repo: &TemplateRepository) -> Result<Arc<crate::github::GitHubClient>> {
+        let mut clients = self.source_clients.lock().unwrap();
+        if let Some(client) = clients.get(&repo.describe()) {
+            return Ok(client.clone());
+        }
+        let client = Arc::new(crate::github::GitHubClient::for_repo(
+            repo.clone(),
+            &self.github_options,
+        )?);
+        clients.insert(repo.describe(), client.clone());
+        Ok(client)
+    }
+
+    /// Warn instead of failing when a rendered server still holds handlebars syntax
+    pub fn allow_unrendered(mut self, allow: bool) -> Self {
+        self.allow_unrendered = allow;
+        self
+    }
+
+    /// Whether any sources were added besides the repository
+    pub fn has_sources(&self) -> bool {
+        !self.sources.is_empty()
+    }
+
+    /// Names of every source in the order they are consulted
+    pub fn source_names(&self) -> Vec<&str> {
+        std::iter::once(DEFAULT_SOURCE)
+            .chain(self.sources.iter().map(|s| s.name.as_str()))
+            .collect()
+    }
+
+    fn selection(&self) -> Selection<'_> {
+        match self.selected_source.as_deref() {
+            None => Selection::Any,
+            Some(name) => self
+                .sources
+                .iter()
+                .find(|s| s.name == name)
+                .map_or(Selection::Default, Selection::Source),
+        }
+    }
+
     /// Get cache metadata file path
-    fn cache_metadata_path(&self) -> PathBuf {
-        self.cache_dir.join("metadata.json")
+    fn cache_metadata_path(&self) -> Result<PathBuf> {
+        Ok(self.cache_dir()?.join("metadata.json"))
     }
 
     /// Get catalog cache file path
-    fn catalog_cache_path(&self) -> PathBuf {
-        self.cache_dir.join("catalog.json")
+    fn catalog_cache_path(&self) -> Result<PathBuf> {
+        Ok(self.cache_dir()?.join("catalog.json"))
+    }
+
+    /// The catalog as it was before the last refresh, kept for `template changes`
+    fn previous_catalog_path(&self) -> Result<PathBuf> {
+        Ok(self.cache_dir()?.join("catalog.prev.json"))
     }
 
     /// Get template cache file path
-    fn template_cache_path(&self, name: &str) -> PathBuf {
-        self.templates_dir.join(format!("{}.json", name))
+    fn template_cache_path(&self, name: &str) -> Result<PathBuf> {
+        Ok(self.templates_dir()?.join(format!("{}.json", name)))
+    }
+
+    /// Get cache file path for update times looked up via the commits API
+    fn updated_at_cache_path(&self) -> Result<PathBuf> {
+        Ok(self.cache_dir()?.join("updated_at.json"))
     }
 
     /// Load cache metadata
     fn load_cache_metadata(&self) -> Result<CacheMetadata> {
-        let path = self.cache_metadata_path();
+        let path = self.cache_metadata_path()?;
         if !path.exists() {
-            return Ok(CacheMetadata::default());
+            return Ok(CacheMetadata::refreshed_at(self.clock.now()));
         }
 
         let content = std::fs::read_to_string(&path).context("Failed to read cache metadata")?;
@@ -206,19 +1084,100 @@ impl TemplateManager {
         let content =
             serde_json::to_string_pretty(metadata).context("Failed to serialize cache metadata")?;
 
-        std::fs::write(self.cache_metadata_path(), content).context("Failed to save cache metadata")
+        std::fs::write(self.cache_metadata_path()?, content)
+            .context("Failed to save cache metadata")
+    }
+
+    /// Change the saved cache metadata
+    fn update_cache_metadata(&self, change: impl FnOnce(&mut CacheMetadata)) -> Result<()> {
+        let mut metadata = self.load_cache_metadata()?;
+        change(&mut metadata);
+        self.save_cache_metadata(&metadata)
+    }
+
+    /// Record that GitHub confirmed the cached catalog is current, so the
+    /// cache is good for another TTL
+    fn mark_cache_revalidated(&self) -> Result<()> {
+        let now = self.clock.now();
+        self.update_cache_metadata(|metadata| {
+            metadata.last_refresh = now;
+            metadata.expires_at = now + cache_ttl();
+        })
     }
 
     /// Check if cache is expired
+    ///
+    /// A cache filled from another repository always counts as expired.
     fn is_cache_expired(&self) -> Result<bool> {
         let metadata = self.load_cache_metadata()?;
-        Ok(chrono::Utc::now() > metadata.expires_at)
+        Ok(!self.cache_matches_repo()? || metadata.is_expired(self.clock.now()))
+    }
+
+    /// Whether the cache holds templates from the repository in use
+    fn cache_matches_repo(&self) -> Result<bool> {
+        let cached = self.load_cache_metadata()?.repo;
+        let cached = cached.unwrap_or_else(|| TemplateRepository::default().describe());
+        Ok(cached == self.repo.describe())
+    }
+
+    /// Record that the cache now comes from the repository in use, dropping
+    /// what was cached from another one
+    fn adopt_repo(&self) -> Result<()> {
+        if self.cache_matches_repo()? {
+            return Ok(());
+        }
+        for name in self.cached_template_names()? {
+            std::fs::remove_file(self.template_cache_path(&name)?)
+                .with_context(|| format!("Failed to remove cached template: {}", name))?;
+        }
+        for path in [
+            self.catalog_cache_path()?,
+            self.previous_catalog_path()?,
+            self.updated_at_cache_path()?,
+        ] {
+            if path.exists() {
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+        }
+        self.save_cache_metadata(&CacheMetadata {
+            repo: Some(self.repo.describe()),
+            ..CacheMetadata::refreshed_at(self.clock.now())
+        })
+    }
+
+    fn mark_served_from_cache(&self) {
+        self.served_from_cache.store(true, Ordering::Relaxed);
+    }
+
+    /// Print a dimmed refresh reminder if this run used old cached template data
+    pub fn print_stale_cache_notice(&self) {
+        if !self.served_from_cache.load(Ordering::Relaxed) {
+            return;
+        }
+        let Ok(metadata) = self.load_cache_metadata() else {
+            return;
+        };
+        if let Some(notice) =
+            stale_cache_notice(metadata.last_refresh, self.clock.now(), stale_after())
+        {
+            // stderr, so the reminder never ends up in redirected output
+            eprintln!("{}", notice.dimmed());
+        }
     }
 
     /// Load template catalog from cache
     pub fn load_cached_catalog(&self) -> Result<Option<TemplateCatalog>> {
-        let path = self.catalog_cache_path();
-        if !path.exists() {
+        let catalog = self.read_cached_catalog()?;
+        if catalog.is_some() {
+            self.mark_served_from_cache();
+        }
+        Ok(catalog)
+    }
+
+    fn read_cached_catalog(&self) -> Result<Option<TemplateCatalog>> {
+        let path = self.catalog_cache_path()?;
+        if !path.exists() || !self.cache_matches_repo()? {
             return Ok(None);
         }
 
@@ -230,20 +1189,44 @@ impl TemplateManager {
         Ok(Some(catalog))
     }
 
-    /// Save template catalog to cache
-    pub fn save_catalog_cache(&self, catalog: &TemplateCatalog) -> Result<()> {
+    /// Keep `catalog` as the one the next refresh is compared with
+    pub fn save_previous_catalog(&self, catalog: &TemplateCatalog) -> Result<()> {
         let content =
             serde_json::to_string_pretty(catalog).context("Failed to serialize catalog")?;
-
-        std::fs::write(self.catalog_cache_path(), content).context("Failed to save catalog cache")
+        std::fs::write(self.previous_catalog_path()?, content)
+            .context("Failed to save previous catalog")
     }
 
-    /// Load template from cache
-    pub fn load_cached_template(&self, name: &str) -> Result<Option<Template>> {
-        let path = self.template_cache_path(name);
+    /// What the last refresh changed in the catalog, or `None` if there was
+    /// nothing to compare it with
+    pub fn catalog_changes(&self) -> Result<Option<CatalogDiff>> {
+        let path = self.previous_catalog_path()?;
         if !path.exists() {
             return Ok(None);
         }
+        let content = std::fs::read_to_string(&path).context("Failed to read previous catalog")?;
+        let before: TemplateCatalog =
+            serde_json::from_str(&content).context("Failed to parse previous catalog")?;
+        let Some(after) = self.read_cached_catalog()? else {
+            return Ok(None);
+        };
+        Ok(Some(diff_catalogs(&before, &after)))
+    }
+
+    /// Save template catalog to cache
+    pub fn save_catalog_cache(&self, catalog: &TemplateCatalog) -> Result<()> {
+        let content =
+            serde_json::to_string_pretty(catalog).context("Failed to serialize catalog")?;
+
+        std::fs::write(self.catalog_cache_path()?, content).context("Failed to save catalog cache")
+    }
+
+    /// Load template from cache
+    pub fn load_cached_template(&self, name: &str) -> Result<Option<Template>> {
+        let path = self.template_cache_path(name)?;
+        if !path.exists() || !self.cache_matches_repo()? {
+            return Ok(None);
+        }
 
         let content = std::fs::read_to_string(&path)
             .with_context(|| format!("Failed to read cached template: {}", name))?;
@@ -259,61 +1242,924 @@ impl TemplateManager {
         let content =
             serde_json::to_string_pretty(template).context("Failed to serialize template")?;
 
-        std::fs::write(self.template_cache_path(&template.name), content)
+        std::fs::write(self.template_cache_path(&template.name)?, content)
             .with_context(|| format!("Failed to save template cache: {}", template.name))
     }
 
-    /// Load template (from cache or GitHub)
+    /// The local template file for `name`, from the first directory that has one
+    pub fn local_template_path(&self, name: &str) -> Option<PathBuf> {
+        self.local_dirs
+            .iter()
+            .map(|dir| dir.join(format!("{}.json", name)))
+            .find(|path| path.is_file())
+    }
+
+    /// Load a template from the local template directories, if one is there
+    fn load_local_template(&self, name: &str) -> Result<Option<Template>> {
+        let Some(path) = self.local_template_path(name) else {
+            return Ok(None);
+        };
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let template = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse template: {}", path.display()))?;
+        Ok(Some(template))
+    }
+
+    /// Catalog entries for every local template, named after their files
+    ///
+    /// Files that aren't valid templates are skipped with a warning.
+    pub fn local_templates(&self) -> HashMap<String, TemplateMetadata> {
+        let mut templates = HashMap::new();
+        for dir in &self.local_dirs {
+            for (name, metadata) in dir_templates(dir) {
+                templates.entry(name).or_insert(metadata);
+            }
+        }
+        templates
+    }
+
+    /// Names of all templates in the local cache, sorted
+    pub fn cached_template_names(&self) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        for entry in std::fs::read_dir(self.templates_dir()?)? {
+            let path = entry?.path();
+            if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                    names.push(stem.to_string());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    /// Cache directory for an added source's catalog and templates
+    fn source_cache_dir(&self, name: &str) -> Result<PathBuf> {
+        Ok(self.cache_dir()?.join("sources").join(name))
+    }
+
+    fn source_template_path(&self, source: &str, name: &str) -> Result<PathBuf> {
+        Ok(self
+            .source_cache_dir(source)?
+            .join("templates")
+            .join(format!("{}.json", name)))
+    }
+
+    /// A source's cached catalog and its metadata, unless the cache is missing
+    /// or was filled from what the name used to point to
+    fn read_source_cache(
+        &self,
+        source: &NamedSource,
+    ) -> Result<Option<(CacheMetadata, TemplateCatalog)>> {
+        let dir = self.source_cache_dir(&source.name)?;
+        let (Ok(metadata), Ok(catalog)) = (
+            std::fs::read_to_string(dir.join("metadata.json")),
+            std::fs::read_to_string(dir.join("catalog.json")),
+        ) else {
+            return Ok(None);
+        };
+        let metadata: CacheMetadata =
+            serde_json::from_str(&metadata).context("Failed to parse cache metadata")?;
+        if metadata.repo != Some(source.source.to_string()) {
+            return Ok(None);
+        }
+        let catalog = serde_json::from_str(&catalog).context("Failed to parse cached catalog")?;
+        Ok(Some((metadata, catalog)))
+    }
+
+    /// Replace a source's cache with a freshly fetched catalog
+    ///
+    /// Templates cached from the old catalog are dropped with it.
+    fn save_source_cache(&self, source: &NamedSource, catalog: &TemplateCatalog) -> Result<()> {
+        let dir = self.source_cache_dir(&source.name)?;
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir)
+                .with_context(|| format!("Failed to clear {}", dir.display()))?;
+        }
+        std::fs::create_dir_all(dir.join("templates"))
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        let metadata = CacheMetadata {
+            repo: Some(source.source.to_string()),
+            ..CacheMetadata::refreshed_at(self.clock.now())
+        };
+        let content =
+            serde_json::to_string_pretty(catalog).context("Failed to serialize catalog")?;
+        std::fs::write(dir.join("catalog.json"), content)
+            .context("Failed to save catalog cache")?;
+        let content = serde_json::to_string_pretty(&metadata)
+            .context("Failed to serialize cache metadata")?;
+        std::fs::write(dir.join("metadata.json"), content).context("Failed to save cache metadata")
+    }
+
+    /// Fetch a source's catalog from where it is published
+    async fn fetch_source_catalog(&self, source: &TemplateSource) -> Result<TemplateCatalog> {
+        match source {
+            TemplateSource::Github(repo) => {
+                self.source_github(repo)?.fetch_template_catalog().await
+            }
+            TemplateSource::Dir(dir) => dir_catalog(dir),
+            TemplateSource::Url(url) => {
+                let content = http_get(url, &self.github_options).await?;
+                serde_json::from_str(&content)
+                    .with_context(|| format!("Failed to parse template catalog from {}", url))
+            }
+        }
+    }
+
+    /// Fetch the file at `path` in a source's catalog
+    async fn fetch_source_file(&self, source: &TemplateSource, path: &str) -> Result<String> {
+        match source {
+            TemplateSource::Github(repo) => self.source_github(repo)?.fetch_file(path).await,
+            TemplateSource::Dir(dir) => {
+                let path = dir.join(path);
+                std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))
+            }
+            TemplateSource::Url(url) => {
+                let url = url::Url::parse(url)
+                    .and_then(|base| base.join(path))
+                    .with_context(|| format!("Invalid template path '{}' for {}", path, url))?;
+                http_get(url.as_str(), &self.github_options).await
+            }
+        }
+    }
+
+    /// A source's catalog as stored locally: its directory, or its cache
+    fn cached_source_catalog(&self, source: &NamedSource) -> Result<Option<TemplateCatalog>> {
+        match &source.source {
+            TemplateSource::Dir(dir) => dir_catalog(dir).map(Some),
+            _ => Ok(self.read_source_cache(source)?.map(|(_, catalog)| catalog)),
+        }
+    }
+
+    /// A source's catalog, from its cache until that expires
+    ///
+    /// An expired cached catalog is still used if the source can't be reached.
+    async fn source_catalog(&self, source: &NamedSource) -> Result<TemplateCatalog> {
+        if let TemplateSource::Dir(dir) = &source.source {
+            return dir_catalog(dir);
+        }
+        let stored = self.read_source_cache(source)?;
+        if let Some((metadata, catalog)) = &stored {
+            if !metadata.is_expired(self.clock.now()) {
+                return Ok(catalog.clone());
+            }
+        }
+        match self.fetch_source_catalog(&source.source).await {
+            Ok(catalog) => {
+                self.save_source_cache(source, &catalog)?;
+                Ok(catalog)
+            }
+            Err(e) => match stored {
+                Some((_, catalog)) => {
+                    eprintln!(
+                        "⚠ Could not fetch the catalog of source '{}' ({}); using the cached copy",
+                        source.name, e
+                    );
+                    Ok(catalog)
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    async fn catalog_for(
+        &self,
+        source: &NamedSource,
+        cached: bool,
+    ) -> Result<Option<TemplateCatalog>> {
+        if cached {
+            self.cached_source_catalog(source)
+        } else {
+            self.source_catalog(source).await.map(Some)
+        }
+    }
+
+    /// A template from a source's catalog as stored locally
+    fn cached_source_file(
+        &self,
+        source: &NamedSource,
+        name: &str,
+        metadata: &TemplateMetadata,
+    ) -> Result<Option<RawTemplate>> {
+        let (path, local) = match &source.source {
+            TemplateSource::Dir(dir) => (dir.join(&metadata.path), true),
+            _ => (self.source_template_path(&source.name, name)?, false),
+        };
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let source = if local {
+            TemplateOrigin::Local(path)
+        } else {
+            TemplateOrigin::Cache(path)
+        };
+        Ok(Some(RawTemplate { content, source }))
+    }
+
+    /// A template from a source's catalog, downloaded unless it's stored locally
+    ///
+    /// With `cached` set it is an error for it not to be stored locally.
+    async fn source_file(
+        &self,
+        source: &NamedSource,
+        name: &str,
+        metadata: &TemplateMetadata,
+        cached: bool,
+    ) -> Result<RawTemplate> {
+        if let Some(raw) = self.cached_source_file(source, name, metadata)? {
+            return Ok(raw);
+        }
+        if cached {
+            return Err(anyhow::anyhow!(
+                "Template '{}' from source '{}' is not cached. Run 'mcp-forge template refresh' while online.",
+                name,
+                source.name
+            ));
+        }
+        let content = self
+            .fetch_source_file(&source.source, &metadata.path)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to fetch template '{}' from source '{}'",
+                    name, source.name
+                )
+            })?;
+        // Keep the file as published, so later raw reads match it byte for byte
+        if serde_json::from_str::<Template>(&content).is_ok() {
+            let path = self.source_template_path(&source.name, name)?;
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(&path, &content)
+                .with_context(|| format!("Failed to save template cache: {}", name))?;
+        }
+        Ok(RawTemplate {
+            content,
+            source: TemplateOrigin::Network(source.source.to_string()),
+        })
+    }
+
+    /// Load a template's JSON from one added source
+    async fn raw_template_from_source(
+        &self,
+        source: &NamedSource,
+        name: &str,
+        cached: bool,
+    ) -> Result<RawTemplate> {
+        let catalog = self.catalog_for(source, cached).await?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "Source '{}' is not cached yet. Run 'mcp-forge template refresh' while online.",
+                source.name
+            )
+        })?;
+        let metadata = catalog.templates.get(name).ok_or_else(|| {
+            anyhow::anyhow!("Template '{}' not found in source '{}'", name, source.name)
+        })?;
+        self.source_file(source, name, metadata, cached).await
+    }
+
+    /// Load a template's JSON from the first added source whose catalog lists it
+    ///
+    /// Sources whose catalog can't be read are passed over with a warning.
+    async fn raw_template_from_sources(
+        &self,
+        name: &str,
+        cached: bool,
+    ) -> Result<Option<RawTemplate>> {
+        for source in &self.sources {
+            let catalog = match self.catalog_for(source, cached).await {
+                Ok(Some(catalog)) => catalog,
+                Ok(None) => continue,
+                Err(e) => {
+                    eprintln!("⚠ Skipping template source '{}': {}", source.name, e);
+                    continue;
+                }
+            };
+            if let Some(metadata) = catalog.templates.get(name) {
+                return self
+                    .source_file(source, name, metadata, cached)
+                    .await
+                    .map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Load a template from the cache only, explaining what is cached if it's missing
+    ///
+    /// The template is returned as stored; see `resolve_extends`.
+    pub async fn require_cached_template(&self, name: &str) -> Result<Template> {
+        self.cached_template(name, self.selection()).await
+    }
+
+    async fn cached_template(&self, name: &str, selection: Selection<'_>) -> Result<Template> {
+        match selection {
+            Selection::Source(source) => {
+                return parse_raw(self.raw_template_from_source(source, name, true).await?)
+            }
+            Selection::Default => return self.require_cached_repo_template(name),
+            Selection::Any => {}
+        }
+        // Local templates never need the network, so they count as cached
+        if let Some(template) = self.load_local_template(name)? {
+            return Ok(template);
+        }
+        match self.require_cached_repo_template(name) {
+            Err(e) if self.has_sources() => {
+                match self.raw_template_from_sources(name, true).await? {
+                    Some(raw) => parse_raw(raw),
+                    None => Err(e),
+                }
+            }
+            result => result,
+        }
+    }
+
+    /// Load a template from the repository's cache only
+    fn require_cached_repo_template(&self, name: &str) -> Result<Template> {
+        if let Some(template) = self.load_cached_template(name)? {
+            self.mark_served_from_cache();
+            return Ok(template);
+        }
+
+        let cached = self.cached_template_names()?;
+        if cached.is_empty() {
+            Err(anyhow::anyhow!(
+                "Template '{}' is not cached and no templates are cached yet. Run 'mcp-forge template refresh' while online.",
+                name
+            ))
+        } else {
+            Err(anyhow::anyhow!(
+                "Template '{}' is not cached. Cached templates: {}",
+                name,
+                cached.join(", ")
+            ))
+        }
+    }
+
+    /// Load template (from cache or GitHub), with the templates it extends merged in
+    ///
+    /// An expired cached copy is still used when GitHub can't be reached.
     pub async fn load_template(&self, name: &str) -> Result<Template> {
+        let template = self.load_template_file(name, self.selection()).await?;
+        self.resolve_extends(template, false).await
+    }
+
+    /// Merge in the templates `template` extends, nearest first
+    ///
+    /// Each parent is read from a local file if `extends` names one, otherwise
+    /// from the cache or GitHub like `load_template` (the cache only, with
+    /// `cached` set).
+    pub async fn resolve_extends(&self, template: Template, cached: bool) -> Result<Template> {
+        let mut chain = vec![template];
+        while let Some(parent) = chain.last().and_then(|t| t.extends.clone()) {
+            check_extends(&chain, &parent)?;
+            let loaded = self.load_parent(&parent, cached).await.with_context(|| {
+                format!(
+                    "Template '{}' extends '{}', which could not be loaded",
+                    chain[chain.len() - 1].name,
+                    parent
+                )
+            })?;
+            chain.push(loaded);
+        }
+        Ok(merge_chain(chain))
+    }
+
+    /// Load the template an `extends` field names, with its own parents merged in
+    pub async fn load_extended(&self, name: &str, cached: bool) -> Result<Template> {
+        let template = self.load_parent(name, cached).await?;
+        self.resolve_extends(template, cached).await
+    }
+
+    async fn load_parent(&self, name: &str, cached: bool) -> Result<Template> {
+        let local = Path::new(name);
+        if local.is_file() {
+            let content = std::fs::read_to_string(local)
+                .with_context(|| format!("Failed to read {}", local.display()))?;
+            return serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse template: {}", local.display()));
+        }
+        // `--source` picks the template asked for, not the ones it extends
+        if cached {
+            return self.cached_template(name, Selection::Any).await;
+        }
+        self.load_template_file(name, Selection::Any).await
+    }
+
+    /// Load one template as published, without resolving `extends`
+    ///
+    /// A local template of the same name takes precedence over the catalog's,
+    /// and the added sources are only consulted if the repository fails.
+    async fn load_template_file(&self, name: &str, selection: Selection<'_>) -> Result<Template> {
+        match selection {
+            Selection::Source(source) => {
+                return parse_raw(self.raw_template_from_source(source, name, false).await?)
+            }
+            Selection::Default => return self.load_repo_template(name).await,
+            Selection::Any => {}
+        }
+        if let Some(template) = self.load_local_template(name)? {
+            return Ok(template);
+        }
+        match self.load_repo_template(name).await {
+            Err(e) if self.has_sources() => {
+                match self.raw_template_from_sources(name, false).await? {
+                    Some(raw) => parse_raw(raw),
+                    None => Err(e),
+                }
+            }
+            result => result,
+        }
+    }
+
+    /// Load one template from the repository, via the cache
+    async fn load_repo_template(&self, name: &str) -> Result<Template> {
+        let cached = self.load_cached_template(name)?;
+
         // Try cache first if not expired
         if !self.is_cache_expired()? {
-            if let Some(template) = self.load_cached_template(name)? {
+            if let Some(template) = cached {
+                self.mark_served_from_cache();
                 return Ok(template);
             }
         }
 
-        // Fetch from GitHub
-        let template = self.github_client.fetch_template(name).await?;
+        // Fetch from GitHub, which only sends the template again if it changed
+        let etag = match &cached {
+            Some(_) => self.load_cache_metadata()?.template_etags.remove(name),
+            None => None,
+        };
+        let fetched = self.github()?.fetch_template(name, etag.as_deref()).await;
+        let (template, etag) = match (fetched, cached) {
+            (Ok(Fetched::Modified { value, etag }), _) => (value, etag),
+            (Ok(Fetched::NotModified), Some(template)) => return Ok(template),
+            (Ok(Fetched::NotModified), None) => {
+                return Err(anyhow::anyhow!(
+                    "GitHub reported template '{}' unchanged, but it isn't cached",
+                    name
+                ))
+            }
+            (Err(e), Some(template)) => {
+                eprintln!(
+                    "⚠ Could not fetch template '{}' ({}); using the cached copy",
+                    name, e
+                );
+                self.mark_served_from_cache();
+                return Ok(template);
+            }
+            (Err(e), None) => return Err(e),
+        };
 
         // Cache the template
+        self.adopt_repo()?;
         self.save_template_cache(&template)?;
+        self.update_cache_metadata(|metadata| match etag {
+            Some(etag) => {
+                metadata.template_etags.insert(name.to_string(), etag);
+            }
+            None => {
+                metadata.template_etags.remove(name);
+            }
+        })?;
 
         Ok(template)
     }
 
-    /// List available templates
+    /// Load a template's JSON exactly as it is stored, and say where it came from
+    ///
+    /// `name` may also be the path of a local template file. With `cached` set
+    /// only the cache is read; otherwise an expired or missing cached copy is
+    /// downloaded again, falling back to the cache when GitHub can't be reached.
+    pub async fn load_raw_template(&self, name: &str, cached: bool) -> Result<RawTemplate> {
+        let local = Path::new(name);
+        if local.is_file() {
+            let content = std::fs::read_to_string(local)
+                .with_context(|| format!("Failed to read {}", local.display()))?;
+            return Ok(RawTemplate {
+                content,
+                source: TemplateOrigin::Local(local.to_path_buf()),
+            });
+        }
+        match self.selection() {
+            Selection::Source(source) => {
+                return self.raw_template_from_source(source, name, cached).await
+            }
+            Selection::Default => return self.load_repo_raw_template(name, cached).await,
+            Selection::Any => {}
+        }
+        if let Some(path) = self.local_template_path(name) {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            return Ok(RawTemplate {
+                content,
+                source: TemplateOrigin::Local(path),
+            });
+        }
+        match self.load_repo_raw_template(name, cached).await {
+            Err(e) if self.has_sources() => {
+                self.raw_template_from_sources(name, cached).await?.ok_or(e)
+            }
+            result => result,
+        }
+    }
+
+    /// Load a template's JSON from the repository, via the cache
+    async fn load_repo_raw_template(&self, name: &str, cached: bool) -> Result<RawTemplate> {
+        let path = self.template_cache_path(name)?;
+        let from_cache = || -> Result<RawTemplate> {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read cached template: {}", name))?;
+            Ok(RawTemplate {
+                content,
+                source: TemplateOrigin::Cache(path.clone()),
+            })
+        };
+        if cached {
+            self.require_cached_repo_template(name)?;
+            return from_cache();
+        }
+        let has_cached_copy = path.exists() && self.cache_matches_repo()?;
+        if has_cached_copy && !self.is_cache_expired()? {
+            return from_cache();
+        }
+
+        let fetched = async {
+            let catalog = self.load_catalog().await?;
+            let metadata = catalog
+                .templates
+                .get(name)
+                .ok_or_else(|| anyhow::anyhow!("Template '{}' not found in catalog", name))?;
+            self.github()?.fetch_file(&metadata.path).await
+        };
+        match fetched.await {
+            Ok(content) => {
+                // Keep the file as published, so later raw reads match it byte for byte
+                if serde_json::from_str::<Template>(&content).is_ok() {
+                    self.adopt_repo()?;
+                    std::fs::write(&path, &content)
+                        .with_context(|| format!("Failed to save template cache: {}", name))?;
+                }
+                Ok(RawTemplate {
+                    content,
+                    source: TemplateOrigin::Network(self.github()?.repo().describe()),
+                })
+            }
+            Err(e) if has_cached_copy => {
+                eprintln!(
+                    "⚠ Could not fetch template '{}' ({}); using the cached copy",
+                    name, e
+                );
+                from_cache()
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Wait on, or stop for, the rate limit last seen by any GitHub client
+    async fn pace(&self) -> Result<()> {
+        self.github()?.pace().await?;
+        let sources: Vec<_> = self
+            .source_clients
+            .lock()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect();
+        for client in sources {
+            client.pace().await?;
+        }
+        Ok(())
+    }
+
+    /// Load several templates, pacing requests against the GitHub rate limit
+    ///
+    /// Each name maps to its template or the reason it couldn't be loaded. If
+    /// the rate limit runs low the templates tried so far are returned along
+    /// with the reason for stopping.
+    pub async fn load_templates(
+        &self,
+        names: &[String],
+    ) -> (HashMap<String, Result<Template>>, Option<anyhow::Error>) {
+        let mut templates = HashMap::new();
+        for name in names {
+            if templates.contains_key(name) {
+                continue;
+            }
+            if let Err(e) = self.pace().await {
+                return (templates, Some(e));
+            }
+            templates.insert(name.clone(), self.load_template(name).await);
+        }
+        (templates, None)
+    }
+
+    /// List available templates, each tagged with the source it comes from
+    ///
+    /// Local templates replace catalog entries of the same name, and an added
+    /// source's entries are only listed if no source before it has the name.
+    /// If the catalog can't be loaded but there are local templates or other
+    /// sources, those are listed on their own.
     pub async fn list_templates(&self) -> Result<Vec<TemplateMetadata>> {
-        let catalog = self.load_catalog().await?;
-        Ok(catalog.templates.into_values().collect())
+        let local = self.local_templates();
+        let mut templates = HashMap::new();
+        match self.load_catalog().await {
+            Ok(catalog) => merge_source(&mut templates, catalog.templates, DEFAULT_SOURCE),
+            Err(e) if !local.is_empty() || self.has_sources() => {
+                let rest = if self.has_sources() {
+                    "the other sources"
+                } else {
+                    "local templates"
+                };
+                eprintln!(
+                    "⚠ Could not load the template catalog ({}); listing {} only",
+                    e, rest
+                );
+            }
+            Err(e) => return Err(e),
+        }
+        for source in &self.sources {
+            match self.source_catalog(source).await {
+                Ok(catalog) => merge_source(&mut templates, catalog.templates, &source.name),
+                Err(e) => eprintln!(
+                    "⚠ Could not load templates from source '{}' ({}); skipping it",
+                    source.name, e
+                ),
+            }
+        }
+        let mut local_templates = HashMap::new();
+        merge_source(&mut local_templates, local, "local");
+        templates.extend(local_templates);
+        Ok(templates.into_values().collect())
+    }
+
+    /// Catalog entries stored locally for the added sources, the first
+    /// source listing a name winning
+    pub fn cached_source_templates(&self) -> HashMap<String, TemplateMetadata> {
+        let mut templates = HashMap::new();
+        for source in &self.sources {
+            if let Ok(Some(catalog)) = self.cached_source_catalog(source) {
+                merge_source(&mut templates, catalog.templates, &source.name);
+            }
+        }
+        templates
+    }
+
+    /// The catalog entry for the template `load_template` would load, tagged
+    /// with its source
+    ///
+    /// `None` for local templates and templates no catalog lists. With
+    /// `cached` set only catalogs stored locally are read.
+    pub async fn catalog_entry(&self, name: &str, cached: bool) -> Option<TemplateMetadata> {
+        let selection = self.selection();
+        let sources: Vec<&NamedSource> = match selection {
+            Selection::Any if self.local_template_path(name).is_some() => return None,
+            Selection::Source(source) => vec![source],
+            Selection::Default => Vec::new(),
+            Selection::Any => self.sources.iter().collect(),
+        };
+        if !matches!(selection, Selection::Source(_)) {
+            let catalog = if cached {
+                self.load_cached_catalog().ok().flatten()
+            } else {
+                self.load_catalog().await.ok()
+            };
+            if let Some(metadata) = catalog.and_then(|c| c.templates.get(name).cloned()) {
+                return Some(TemplateMetadata {
+                    source: Some(DEFAULT_SOURCE.to_string()),
+                    ..metadata
+                });
+            }
+        }
+        for source in sources {
+            let catalog = self.catalog_for(source, cached).await.ok().flatten();
+            if let Some(metadata) = catalog.and_then(|c| c.templates.get(name).cloned()) {
+                return Some(TemplateMetadata {
+                    source: Some(source.name.clone()),
+                    ..metadata
+                });
+            }
+        }
+        None
     }
 
     /// Load catalog (from cache or GitHub)
     pub async fn load_catalog(&self) -> Result<TemplateCatalog> {
         // Try cache first
-        if let Ok(Some(catalog)) = self.load_cached_catalog() {
+        let cached = self.read_cached_catalog().ok().flatten();
+        if let Some(catalog) = &cached {
             if !self.is_cache_expired().unwrap_or(true) {
-                return Ok(catalog);
+                self.mark_served_from_cache();
+                return Ok(catalog.clone());
             }
         }
 
-        // Fetch from GitHub
-        let catalog = self.github_client.fetch_template_catalog().await?;
+        // Fetch from GitHub, which only sends the catalog again if it changed
+        let etag = match &cached {
+            Some(_) => self.load_cache_metadata()?.catalog_etag,
+            None => None,
+        };
+        let fetched = self
+            .github()?
+            .fetch_template_catalog_if_changed(etag.as_deref())
+            .await?;
+        let (catalog, etag) = match (fetched, cached) {
+            (Fetched::Modified { value, etag }, _) => (value, etag),
+            (Fetched::NotModified, Some(catalog)) => {
+                self.mark_cache_revalidated()?;
+                return Ok(catalog);
+            }
+            (Fetched::NotModified, None) => {
+                return Err(anyhow::anyhow!(
+                    "GitHub reported the catalog unchanged, but it isn't cached"
+                ))
+            }
+        };
 
         // Cache it
+        self.adopt_repo()?;
         self.save_catalog_cache(&catalog)?;
+        self.update_cache_metadata(|metadata| metadata.catalog_etag = etag)?;
 
         Ok(catalog)
     }
 
+    /// Fill in missing `updated_at` values from the local cache or GitHub
+    ///
+    /// Lookups are best effort: templates whose history cannot be fetched
+    /// keep `None`. At most `MAX_UPDATE_LOOKUPS` are made per run; the rest
+    /// are left for later runs once those are cached. With `offline` set only
+    /// previously cached values are used.
+    pub async fn resolve_updated_at(
+        &self,
+        templates: &mut [TemplateMetadata],
+        offline: bool,
+    ) -> Result<()> {
+        let path = self.updated_at_cache_path()?;
+        let mut cache: HashMap<String, chrono::DateTime<chrono::Utc>> =
+            std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .unwrap_or_default();
+        let mut changed = false;
+
+        // Update times are looked up in the repository's history only
+        let mut pending: Vec<_> = templates
+            .iter_mut()
+            .filter(|t| t.updated_at.is_none())
+            .filter(|t| matches!(t.source.as_deref(), None | Some(DEFAULT_SOURCE)))
+            .collect();
+        let mut uncached = Vec::new();
+        for template in pending.iter_mut() {
+            match cache.get(&template.name) {
+                Some(updated_at) => template.updated_at = Some(*updated_at),
+                None if !offline => uncached.push(template),
+                None => {}
+            }
+        }
+
+        let total = uncached.len();
+        if total > MAX_UPDATE_LOOKUPS {
+            eprintln!(
+                "⚠ Looking up update times for {} of {} template(s); the rest are looked up on later runs",
+                MAX_UPDATE_LOOKUPS, total
+            );
+        }
+        for (looked_up, template) in uncached.into_iter().take(MAX_UPDATE_LOOKUPS).enumerate() {
+            if let Err(e) = self.github()?.pace().await {
+                eprintln!(
+                    "⚠ Looked up update times for {} of {} template(s) before stopping: {}",
+                    looked_up, total, e
+                );
+                break;
+            }
+            if let Ok(Some(updated_at)) =
+                self.github()?.fetch_last_commit_date(&template.path).await
+            {
+                template.updated_at = Some(updated_at);
+                cache.insert(template.name.clone(), updated_at);
+                changed = true;
+            }
+        }
+
+        if changed {
+            let content = serde_json::to_string_pretty(&cache)
+                .context("Failed to serialize template update times")?;
+            std::fs::write(&path, content).context("Failed to save template update times")?;
+        }
+
+        Ok(())
+    }
+
     /// Apply template variables to generate MCP server configuration
     pub fn apply_template(
         &self,
         template: &Template,
         variables: &HashMap<String, serde_json::Value>,
+    ) -> Result<crate::config::McpServer> {
+        let server = self.render_server(template, variables)?;
+        self.check_rendered(template, &server)?;
+        Ok(server)
+    }
+
+    /// A copy of `template` whose setup instructions and requirement values are
+    /// rendered with the variables its server was rendered with
+    ///
+    /// Variables left out take their defaults, and secret values are masked.
+    /// These texts are only shown and recorded, so one that fails to render is
+    /// kept as written, with a warning, rather than failing the add.
+    pub fn render_notes(
+        &self,
+        template: &Template,
+        variables: &HashMap<String, serde_json::Value>,
+    ) -> Template {
+        let mut context = serde_json::Map::new();
+        for (name, variable) in &template.variables {
+            if let Some(default) = &variable.default {
+                let value = coerce_value(default, variable).unwrap_or_else(|_| default.clone());
+                context.insert(name.clone(), value);
+            }
+        }
+        let values = coerce_variables(template, variables).unwrap_or_else(|_| variables.clone());
+        for (name, value) in values {
+            let value = if template.is_secret(&name) && !value.is_null() {
+                serde_json::Value::String("********".to_string())
+            } else {
+                value
+            };
+            context.insert(name, value);
+        }
+
+        let render = |field: RenderField, source: &str| {
+            self.render_field(template, field, source, &context)
+                .unwrap_or_else(|e| {
+                    eprintln!("{} {}\n  Leaving it as written", "⚠".yellow(), e);
+                    source.to_string()
+                })
+        };
+        let mut rendered = template.clone();
+        rendered.setup_instructions = template
+            .setup_instructions
+            .as_deref()
+            .map(|instructions| render(RenderField::SetupInstructions, instructions));
+        rendered.requirements = template.requirements.as_ref().map(|requirements| {
+            requirements
+                .iter()
+                .map(|(name, value)| {
+                    let field = RenderField::Requirement(name.clone());
+                    (name.clone(), render(field, value))
+                })
+                .collect()
+        });
+        rendered
+    }
+
+    /// Refuse a rendered server that still holds `{{...}}`, such as a variable
+    /// misspelled inside an `{{#if}}` block
+    fn check_rendered(&self, template: &Template, server: &crate::config::McpServer) -> Result<()> {
+        let leftovers = server.unrendered_fragments();
+        if leftovers.is_empty() {
+            return Ok(());
+        }
+
+        let details: Vec<String> = leftovers
+            .iter()
+            .map(|(field, fragment)| format!("  {}: {}", field, fragment))
+            .collect();
+        if self.allow_unrendered {
+            eprintln!(
+                "{} Template '{}' left handlebars syntax in the server:\n{}",
+                "⚠".yellow(),
+                template.name,
+                details.join("\n")
+            );
+            return Ok(());
+        }
+        Err(anyhow::anyhow!(
+            "Template '{}' left handlebars syntax in the server:\n{}\nCheck the template's variable names, or pass --allow-unrendered to keep it as is",
+            template.name,
+            details.join("\n")
+        ))
+    }
+
+    /// Render a template's config into a server
+    fn render_server(
+        &self,
+        template: &Template,
+        variables: &HashMap<String, serde_json::Value>,
     ) -> Result<crate::config::McpServer> {
         // Validate template configuration first
         template.config.validate()?;
-        
+
+        // Values from --vars and batch files arrive as strings
+        let variables = &coerce_variables(template, variables)?;
+
         // Validate variables
         self.validate_variables(template, variables)?;
 
@@ -327,38 +2173,36 @@ impl TemplateManager {
         if template.config.is_url_template() {
             // Render URL
             let url = template.config.url.as_ref().unwrap();
-            let rendered_url = self
-                .handlebars
-                .render_template(url, &context)
-                .with_context(|| format!("Failed to render URL template: {}", url))?;
+            let rendered_url = self.render_field(template, RenderField::Url, url, &context)?;
 
             // Render environment variables if present
-            let rendered_env = self.render_env(&template.config.env, &context)?;
+            let rendered_env = self.render_env(template, &context)?;
 
             Ok(crate::config::McpServer {
                 command: None,
                 args: None,
                 url: Some(rendered_url),
                 env: rendered_env,
+                timeout: None,
+                auto_approve: None,
                 other: HashMap::new(),
             })
         } else {
             // Render command
             let command = template.config.command.as_ref().unwrap();
-            let rendered_command = self
-                .handlebars
-                .render_template(command, &context)
-                .with_context(|| format!("Failed to render command template: {}", command))?;
+            let rendered_command =
+                self.render_field(template, RenderField::Command, command, &context)?;
 
             // Render arguments
             let rendered_args = if let Some(args) = &template.config.args {
                 let mut rendered = Vec::new();
-                for arg in args {
-                    let rendered_arg = self
-                        .handlebars
-                        .render_template(arg, &context)
-                        .with_context(|| format!("Failed to render argument template: {}", arg))?;
-                    rendered.push(rendered_arg);
+                for (index, arg) in args.iter().enumerate() {
+                    rendered.push(self.render_field(
+                        template,
+                        RenderField::Arg(index),
+                        arg,
+                        &context,
+                    )?);
                 }
                 Some(rendered)
             } else {
@@ -366,13 +2210,15 @@ impl TemplateManager {
             };
 
             // Render environment variables if present
-            let rendered_env = self.render_env(&template.config.env, &context)?;
+            let rendered_env = self.render_env(template, &context)?;
 
             Ok(crate::config::McpServer {
                 command: Some(rendered_command),
                 args: rendered_args,
                 url: None,
                 env: rendered_env,
+                timeout: None,
+                auto_approve: None,
                 other: HashMap::new(),
             })
         }
@@ -381,24 +2227,20 @@ impl TemplateManager {
     /// Helper method to render environment variables
     fn render_env(
         &self,
-        env: &Option<HashMap<String, String>>,
+        template: &Template,
         context: &serde_json::Map<String, serde_json::Value>,
     ) -> Result<Option<HashMap<String, String>>> {
-        if let Some(env) = env {
+        if let Some(env) = &template.config.env {
             let mut rendered_env_map = HashMap::new();
             for (key, value) in env {
-                let rendered_key = self
-                    .handlebars
-                    .render_template(key, context)
-                    .with_context(|| {
-                        format!("Failed to render environment key template: {}", key)
-                    })?;
-                let rendered_value = self
-                    .handlebars
-                    .render_template(value, context)
-                    .with_context(|| {
-                        format!("Failed to render environment value template: {}", value)
-                    })?;
+                let rendered_key =
+                    self.render_field(template, RenderField::EnvKey(key.clone()), key, context)?;
+                let rendered_value = self.render_field(
+                    template,
+                    RenderField::EnvValue(key.clone()),
+                    value,
+                    context,
+                )?;
 
                 // Only add non-empty keys and values
                 if !rendered_key.trim().is_empty() && !rendered_value.trim().is_empty() {
@@ -415,6 +2257,21 @@ impl TemplateManager {
         }
     }
 
+    /// Render one field of a template's config, explaining failures in template terms
+    fn render_field(
+        &self,
+        template: &Template,
+        field: RenderField,
+        source: &str,
+        context: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<String> {
+        self.handlebars()
+            .render_template(source, context)
+            .map_err(|e| {
+                anyhow::anyhow!(describe_render_error(template, &field, source, &e, context))
+            })
+    }
+
     /// Validate template variables
     pub fn validate_variables(
         &self,
@@ -448,37 +2305,558 @@ impl TemplateManager {
     }
 
     /// Refresh template cache
+    ///
+    /// The catalog it replaces is kept as `catalog.prev.json` for `template changes`.
     pub async fn refresh_cache(&self) -> Result<()> {
-        let github_client = crate::github::GitHubClient::new();
+        // An unreadable old catalog just means there is nothing to compare with
+        let previous = self.read_cached_catalog().ok().flatten();
 
-        // Fetch fresh catalog
-        let catalog = github_client.fetch_template_catalog().await?;
+        // Fetch fresh catalog; a catalog from another repository isn't compared
+        // with, and GitHub only sends it again if it changed
+        let etag = match &previous {
+            Some(_) => self.load_cache_metadata()?.catalog_etag,
+            None => None,
+        };
+        let fetched = self
+            .github()?
+            .fetch_template_catalog_if_changed(etag.as_deref())
+            .await?;
+        let (catalog, etag) = match (fetched, &previous) {
+            (Fetched::Modified { value, etag }, _) => (value, etag),
+            (Fetched::NotModified, Some(previous)) => (previous.clone(), etag),
+            (Fetched::NotModified, None) => {
+                return Err(anyhow::anyhow!(
+                    "GitHub reported the catalog unchanged, but it isn't cached"
+                ))
+            }
+        };
+        self.adopt_repo()?;
+        if let Some(previous) = &previous {
+            self.save_previous_catalog(previous)?;
+        }
         self.save_catalog_cache(&catalog)?;
 
-        // Update cache metadata
+        // Update times are looked up again against the fresh catalog
+        let updated_at_path = self.updated_at_cache_path()?;
+        if updated_at_path.exists() {
+            std::fs::remove_file(&updated_at_path)
+                .context("Failed to clear cached template update times")?;
+        }
+
+        // Update cache metadata; template ETags stay valid for the same repository
         let metadata = CacheMetadata {
-            last_refresh: chrono::Utc::now(),
-            expires_at: chrono::Utc::now() + chrono::Duration::days(30),
-            ..Default::default()
+            repo: Some(self.repo.describe()),
+            catalog_etag: etag,
+            template_etags: self.load_cache_metadata()?.template_etags,
+            ..CacheMetadata::refreshed_at(self.clock.now())
         };
         self.save_cache_metadata(&metadata)?;
 
         Ok(())
     }
 
+    /// Refresh the repository's cache and every added source's, returning
+    /// each source's name with the outcome, the repository's first
+    pub async fn refresh_sources(&self) -> Vec<(String, Result<usize>)> {
+        let mut results = Vec::new();
+        let refreshed = async {
+            self.refresh_cache().await?;
+            Ok(self.read_cached_catalog()?.map_or(0, |c| c.templates.len()))
+        };
+        results.push((DEFAULT_SOURCE.to_string(), refreshed.await));
+        for source in &self.sources {
+            let refreshed = async {
+                let catalog = self.fetch_source_catalog(&source.source).await?;
+                if !matches!(source.source, TemplateSource::Dir(_)) {
+                    self.save_source_cache(source, &catalog)?;
+                }
+                Ok(catalog.templates.len())
+            };
+            results.push((source.name.clone(), refreshed.await));
+        }
+        results
+    }
+
+    /// Remove what is cached for the added source `name`
+    pub fn clear_source_cache(&self, name: &str) -> Result<()> {
+        let dir = self.source_cache_dir(name)?;
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir)
+                .with_context(|| format!("Failed to remove {}", dir.display()))?;
+        }
+        Ok(())
+    }
+
+    /// List what `clear_cache` would remove
+    pub fn planned_cache_clear(&self) -> Result<PlannedDeletions> {
+        let mut plan = PlannedDeletions::new();
+        plan.add_dir(self.cache_dir()?);
+        for name in self.cached_template_names()? {
+            plan.add_entry(format!("cached template '{}'", name));
+        }
+        Ok(plan)
+    }
+
     /// Clear template cache
     pub fn clear_cache(&self) -> Result<()> {
-        if self.cache_dir.exists() {
-            std::fs::remove_dir_all(&self.cache_dir).context("Failed to clear cache directory")?;
-            std::fs::create_dir_all(&self.cache_dir)
-                .context("Failed to recreate cache directory")?;
-            std::fs::create_dir_all(&self.templates_dir)
+        let cache_dir = self.cache_dir()?;
+        if cache_dir.exists() {
+            std::fs::remove_dir_all(cache_dir).context("Failed to clear cache directory")?;
+            std::fs::create_dir_all(cache_dir).context("Failed to recreate cache directory")?;
+            std::fs::create_dir_all(self.templates_dir()?)
                 .context("Failed to recreate templates directory")?;
         }
         Ok(())
     }
 }
 
+/// Helpers registered on every `TemplateManager`
+const HELPERS: [&str; 4] = ["os", "arch", "home_dir", "config_dir"];
+
+/// The part of a template's config being rendered
+#[derive(Debug, Clone, PartialEq)]
+enum RenderField {
+    Command,
+    Url,
+    Arg(usize),
+    EnvKey(String),
+    EnvValue(String),
+    SetupInstructions,
+    Requirement(String),
+}
+
+impl std::fmt::Display for RenderField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderField::Command => write!(f, "command"),
+            RenderField::Url => write!(f, "url"),
+            RenderField::Arg(index) => write!(f, "arg {}", index),
+            RenderField::EnvKey(key) => write!(f, "env key '{}'", key),
+            RenderField::EnvValue(key) => write!(f, "env var '{}'", key),
+            RenderField::SetupInstructions => write!(f, "setup_instructions"),
+            RenderField::Requirement(name) => write!(f, "requirement '{}'", name),
+        }
+    }
+}
+
+/// Explain a handlebars failure in terms a template user can act on
+///
+/// Handlebars reports positions in an "Unnamed template"; this names the
+/// template and field instead, says what was missing and lists what was provided.
+fn describe_render_error(
+    template: &Template,
+    field: &RenderField,
+    source: &str,
+    error: &handlebars::RenderError,
+    context: &serde_json::Map<String, serde_json::Value>,
+) -> String {
+    use handlebars::RenderErrorReason;
+
+    let problem = match error.reason() {
+        RenderErrorReason::MissingVariable(Some(path)) => {
+            let name = path.split(['.', '[']).next().unwrap_or(path);
+            if template.variables.contains_key(name) {
+                format!("variable '{}' was not provided", path)
+            } else {
+                format!(
+                    "variable '{}' is not declared in the template's \"variables\" block",
+                    path
+                )
+            }
+        }
+        RenderErrorReason::MissingVariable(None) => "a variable was not provided".to_string(),
+        RenderErrorReason::HelperNotFound(name) => format!(
+            "unknown helper '{}' (available: {})",
+            name,
+            HELPERS.join(", ")
+        ),
+        other => other.to_string(),
+    };
+
+    let mut provided: Vec<&str> = context.keys().map(String::as_str).collect();
+    provided.sort();
+    format!(
+        "Template '{}' failed to render {} `{}`: {}\n  Provided variables: {}",
+        template.name,
+        field,
+        source,
+        problem,
+        if provided.is_empty() {
+            "(none)".to_string()
+        } else {
+            provided.join(", ")
+        }
+    )
+}
+
+/// Read variable values from a JSON or YAML map, or from stdin when `path` is `-`
+pub fn read_variables_file(path: &str) -> Result<HashMap<String, serde_json::Value>> {
+    let content = if path == "-" {
+        std::io::read_to_string(std::io::stdin()).context("Failed to read variables from stdin")?
+    } else {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read variables file '{}'", path))?
+    };
+    parse_variables(&content).with_context(|| {
+        if path == "-" {
+            "Invalid variables on stdin".to_string()
+        } else {
+            format!("Invalid variables file '{}'", path)
+        }
+    })
+}
+
+/// Read variable values piped in for `--vars-stdin`
+pub fn read_variables_stdin() -> Result<HashMap<String, serde_json::Value>> {
+    let content =
+        std::io::read_to_string(std::io::stdin()).context("Failed to read variables from stdin")?;
+    parse_stdin_variables(&content).context("Invalid variables on stdin")
+}
+
+/// Parse a JSON map, or `KEY=VALUE` assignments (one or more per line, as
+/// in `--vars`; blank lines and `#` comments are skipped)
+fn parse_stdin_variables(content: &str) -> Result<HashMap<String, serde_json::Value>> {
+    if content.trim_start().starts_with('{') {
+        return parse_variables(content);
+    }
+    let mut values = HashMap::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let assignments = crate::utils::assignments::parse_assignments(line)
+            .with_context(|| format!("line {}", index + 1))?;
+        for (key, value) in assignments {
+            values.insert(key, serde_json::Value::String(value));
+        }
+    }
+    Ok(values)
+}
+
+/// Parse a JSON or YAML map of variable names to values
+fn parse_variables(content: &str) -> Result<HashMap<String, serde_json::Value>> {
+    // YAML is a superset of JSON, so one parser covers both
+    let value: serde_yaml::Value = serde_yaml::from_str(content)?;
+    if value.is_null() {
+        return Ok(HashMap::new());
+    }
+    match serde_json::to_value(value)? {
+        serde_json::Value::Object(map) => Ok(map.into_iter().collect()),
+        _ => anyhow::bail!("expected a map of variable names to values"),
+    }
+}
+
+/// Convert variable values to the types the template declares
+///
+/// Variables the template doesn't declare are passed through unchanged.
+pub fn coerce_variables(
+    template: &Template,
+    variables: &HashMap<String, serde_json::Value>,
+) -> Result<HashMap<String, serde_json::Value>> {
+    variables
+        .iter()
+        .map(|(name, value)| {
+            let value = match template.variables.get(name) {
+                Some(definition) => coerce_value(value, definition).map_err(|reason| {
+                    anyhow::anyhow!(
+                        "Variable '{}' expects a {} ({}): {}",
+                        name,
+                        definition.var_type,
+                        definition.description,
+                        reason
+                    )
+                })?,
+                None => value.clone(),
+            };
+            Ok((name.clone(), value))
+        })
+        .collect()
+}
+
+/// Convert one value to a variable's type, or say why it doesn't fit
+pub fn coerce_value(
+    value: &serde_json::Value,
+    definition: &TemplateVariable,
+) -> std::result::Result<serde_json::Value, String> {
+    use serde_json::Value;
+
+    match (&definition.var_type, value) {
+        (_, Value::Null) => Ok(Value::Null),
+        (VariableType::String, Value::Number(n)) => Ok(Value::String(n.to_string())),
+        (VariableType::String, Value::Bool(b)) => Ok(Value::String(b.to_string())),
+        (VariableType::Boolean, Value::String(s)) => s
+            .trim()
+            .parse::<bool>()
+            .map(Value::Bool)
+            .map_err(|_| format!("'{}' is not true/false", s)),
+        (VariableType::Number, Value::String(s)) => {
+            let trimmed = s.trim();
+            trimmed
+                .parse::<i64>()
+                .map(Value::from)
+                .or_else(|_| trimmed.parse::<f64>().map(Value::from))
+                .map_err(|_| format!("'{}' is not a number", s))
+        }
+        (VariableType::Select, Value::String(_) | Value::Number(_) | Value::Bool(_)) => {
+            let choice = value
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| value.to_string());
+            match &definition.options {
+                Some(options) if !options.contains(&choice) => Err(format!(
+                    "'{}' is not one of: {}",
+                    choice,
+                    options.join(", ")
+                )),
+                _ => Ok(Value::String(choice)),
+            }
+        }
+        (VariableType::String, Value::String(_))
+        | (VariableType::Boolean, Value::Bool(_))
+        | (VariableType::Number, Value::Number(_))
+        | (VariableType::Array, Value::Array(_) | Value::String(_)) => Ok(value.clone()),
+        (_, other) => Err(format!("got {}", describe_json_type(other))),
+    }
+}
+
+fn describe_json_type(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "a boolean",
+        serde_json::Value::Number(_) => "a number",
+        serde_json::Value::String(_) => "a string",
+        serde_json::Value::Array(_) => "a list",
+        serde_json::Value::Object(_) => "a map",
+    }
+}
+
+/// Directories searched for local templates, highest precedence first
+///
+/// Those in `MCP_FORGE_TEMPLATE_PATH`, separated like `PATH`, come before
+/// `mcp-forge/templates` in the user's config directory.
+pub fn local_template_dirs() -> Vec<PathBuf> {
+    local_template_dirs_from(
+        std::env::var_os("MCP_FORGE_TEMPLATE_PATH"),
+        dirs::config_dir(),
+    )
+}
+
+fn local_template_dirs_from(
+    setting: Option<OsString>,
+    config_dir: Option<PathBuf>,
+) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = setting
+        .iter()
+        .flat_map(std::env::split_paths)
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .collect();
+    dirs.extend(config_dir.map(|dir| dir.join("mcp-forge").join("templates")));
+    dirs
+}
+
+/// The catalog entry for a local template file
+/// Catalog entries for the template files in `dir`, named after the files
+///
+/// Files that aren't valid templates are skipped with a warning.
+fn dir_templates(dir: &Path) -> HashMap<String, TemplateMetadata> {
+    let mut templates = HashMap::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return templates;
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+    for path in paths {
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| Ok(serde_json::from_str::<Template>(&content)?));
+        match parsed {
+            Ok(template) => {
+                templates.insert(name.to_string(), local_metadata(name, &path, template));
+            }
+            Err(e) => eprintln!("⚠ Skipping local template {}: {}", path.display(), e),
+        }
+    }
+    templates
+}
+
+fn local_metadata(name: &str, path: &Path, template: Template) -> TemplateMetadata {
+    let updated_at = std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(chrono::DateTime::<chrono::Utc>::from);
+    TemplateMetadata {
+        name: name.to_string(),
+        version: template.version,
+        description: template.description,
+        author: template.author,
+        tags: template.tags,
+        platforms: template.platforms,
+        category: "local".to_string(),
+        path: path.display().to_string(),
+        updated_at,
+        checksum: None,
+        docs_url: template.docs_url,
+        source: None,
+    }
+}
+
+/// Parse a template's JSON, naming where it was read from if it's invalid
+fn parse_raw(raw: RawTemplate) -> Result<Template> {
+    serde_json::from_str(&raw.content)
+        .with_context(|| format!("Failed to parse template from {}", raw.source))
+}
+
+/// Add a source's catalog entries for names no earlier source listed,
+/// tagged with the source's name
+fn merge_source(
+    templates: &mut HashMap<String, TemplateMetadata>,
+    entries: HashMap<String, TemplateMetadata>,
+    source: &str,
+) {
+    for (name, metadata) in entries {
+        templates.entry(name).or_insert(TemplateMetadata {
+            source: Some(source.to_string()),
+            ..metadata
+        });
+    }
+}
+
+/// The catalog of a template directory: its `catalog.json`, with paths
+/// relative to the directory, or else an entry for each template file in it
+fn dir_catalog(dir: &Path) -> Result<TemplateCatalog> {
+    let catalog_path = dir.join("catalog.json");
+    if catalog_path.is_file() {
+        let content = std::fs::read_to_string(&catalog_path)
+            .with_context(|| format!("Failed to read {}", catalog_path.display()))?;
+        return serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", catalog_path.display()));
+    }
+    if !dir.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Template directory not found: {}",
+            dir.display()
+        ));
+    }
+    Ok(TemplateCatalog {
+        version: "1.0.0".to_string(),
+        last_updated: String::new(),
+        templates: dir_templates(dir),
+    })
+}
+
+/// GET a text file over HTTP(S)
+async fn http_get(url: &str, options: &ClientOptions) -> Result<String> {
+    let response = crate::github::http_client(options)?
+        .get(url)
+        .header("User-Agent", "mcp-forge")
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch {}", url))?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!(
+            "Request for {} failed with status: {}",
+            url,
+            response.status()
+        ));
+    }
+    response
+        .text()
+        .await
+        .with_context(|| format!("Failed to read {}", url))
+}
+
+/// Pick a usable cache directory, falling back to the temp dir with a warning
+///
+/// Containers and CI runners sometimes have no cache directory, or one that
+/// can't be written. Templates are still usable from a temp-dir cache there;
+/// it just won't survive between runs.
+fn resolve_cache_dir(preferred: Option<PathBuf>) -> Result<PathBuf> {
+    let reason = match preferred.map(|dir| dir.join("mcp-forge")) {
+        Some(dir) => match std::fs::create_dir_all(dir.join("templates")) {
+            Ok(()) => return Ok(dir),
+            Err(e) => format!("{} is not usable ({})", dir.display(), e),
+        },
+        None => "no cache directory is available".to_string(),
+    };
+
+    let fallback = temp_cache_dir()
+        .and_then(|dir| {
+            std::fs::create_dir_all(dir.join("templates"))?;
+            Ok(dir)
+        })
+        .with_context(|| format!("Template cache unavailable: {}", reason))?;
+    eprintln!(
+        "⚠ Template cache: {}; using {} instead",
+        reason,
+        fallback.display()
+    );
+    Ok(fallback)
+}
+
+/// The user's own cache directory in the temp dir
+///
+/// The temp dir is shared on Unix, so another user could create the directory
+/// first and plant templates in it. It's named for the user and created
+/// readable by them alone, and one someone else owns or can write to is
+/// refused.
+fn temp_cache_dir() -> Result<PathBuf> {
+    #[cfg(unix)]
+    {
+        // SAFETY: geteuid has no preconditions and cannot fail
+        let uid = unsafe { libc::geteuid() };
+        let dir = std::env::temp_dir().join(format!("mcp-forge-cache-{}", uid));
+        ensure_private_dir(&dir, uid)?;
+        Ok(dir)
+    }
+
+    #[cfg(not(unix))]
+    {
+        // The temp dir is already per-user on Windows
+        Ok(std::env::temp_dir().join("mcp-forge-cache"))
+    }
+}
+
+/// Create `dir` for `uid` alone, or check that an existing one is theirs alone
+#[cfg(unix)]
+fn ensure_private_dir(dir: &Path, uid: u32) -> Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+
+    match std::fs::DirBuilder::new().mode(0o700).create(dir) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e).with_context(|| format!("{} could not be created", dir.display())),
+    }
+
+    // Not following symlinks, so a link to someone else's directory is refused too
+    let metadata = std::fs::symlink_metadata(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?;
+    if !metadata.is_dir() {
+        return Err(anyhow::anyhow!("{} is not a directory", dir.display()));
+    }
+    if metadata.uid() != uid {
+        return Err(anyhow::anyhow!(
+            "{} belongs to uid {}, not this user; remove it or set XDG_CACHE_HOME",
+            dir.display(),
+            metadata.uid()
+        ));
+    }
+    if metadata.mode() & 0o022 != 0 {
+        return Err(anyhow::anyhow!(
+            "{} can be written by other users (mode {:o}); remove it or run chmod 700 on it",
+            dir.display(),
+            metadata.mode() & 0o777
+        ));
+    }
+    Ok(())
+}
+
 // Handlebars helper functions
 fn os_helper(
     _: &handlebars::Helper,
@@ -487,7 +2865,7 @@ fn os_helper(
     _: &mut handlebars::RenderContext,
     out: &mut dyn handlebars::Output,
 ) -> handlebars::HelperResult {
-    out.write(&get_os_name())?;
+    out.write(&platform::os())?;
     Ok(())
 }
 
@@ -498,7 +2876,7 @@ fn arch_helper(
     _: &mut handlebars::RenderContext,
     out: &mut dyn handlebars::Output,
 ) -> handlebars::HelperResult {
-    out.write(&get_arch_name())?;
+    out.write(&platform::arch())?;
     Ok(())
 }
 
@@ -509,7 +2887,7 @@ fn home_dir_helper(
     _: &mut handlebars::RenderContext,
     out: &mut dyn handlebars::Output,
 ) -> handlebars::HelperResult {
-    out.write(&get_home_dir())?;
+    out.write(&render_dir(dirs::home_dir(), "home_dir")?)?;
     Ok(())
 }
 
@@ -520,47 +2898,116 @@ fn config_dir_helper(
     _: &mut handlebars::RenderContext,
     out: &mut dyn handlebars::Output,
 ) -> handlebars::HelperResult {
-    out.write(&get_config_dir())?;
+    let config_dir = crate::utils::get_config_dir().ok();
+    out.write(&render_dir(config_dir, "config_dir")?)?;
     Ok(())
 }
 
-// Platform detection functions
-fn get_os_name() -> String {
-    #[cfg(target_os = "windows")]
-    return "windows".to_string();
-    #[cfg(target_os = "macos")]
-    return "macos".to_string();
-    #[cfg(target_os = "linux")]
-    return "linux".to_string();
-    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
-    return "unknown".to_string();
-}
-
-fn get_arch_name() -> String {
-    #[cfg(target_arch = "x86_64")]
-    return "x64".to_string();
-    #[cfg(target_arch = "aarch64")]
-    return "arm64".to_string();
-    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
-    return "unknown".to_string();
+/// Whether a platform list includes `platform`; an empty list means any platform
+pub fn supports_platform(platforms: &[String], platform: &str) -> bool {
+    platforms.is_empty() || platforms.iter().any(|p| p.eq_ignore_ascii_case(platform))
 }
 
-fn get_home_dir() -> String {
-    dirs::home_dir()
-        .map(|p| p.to_string_lossy().to_string())
-        .unwrap_or_else(|| "~".to_string())
-}
-
-fn get_config_dir() -> String {
-    crate::utils::get_config_dir()
-        .map(|p| p.to_string_lossy().to_string())
-        .unwrap_or_else(|_| "~/.config/claude".to_string())
+/// Render a directory for a helper, failing rather than writing a path that won't resolve
+///
+/// A literal `~` is not expanded when Claude launches a server, so substituting
+/// it would produce a config that silently points at the wrong place.
+fn render_dir(dir: Option<PathBuf>, helper: &str) -> Result<String, handlebars::RenderError> {
+    dir.map(|p| p.to_string_lossy().to_string()).ok_or_else(|| {
+        handlebars::RenderErrorReason::Other(format!(
+            "{{{{{}}}}} could not be resolved because the home directory is unknown; set HOME and try again",
+            helper
+        ))
+        .into()
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_catalog_updated_at_is_optional() {
+        let catalog: TemplateCatalog =
+            serde_json::from_str(include_str!("../catalog.json")).unwrap();
+        assert!(catalog.templates.values().all(|t| t.updated_at.is_none()));
+
+        let metadata: TemplateMetadata = serde_json::from_str(
+            r#"{
+                "name": "sqlite",
+                "version": "1.0.0",
+                "description": "SQLite",
+                "author": "test",
+                "tags": [],
+                "platforms": ["linux"],
+                "category": "official",
+                "path": "templates/official/sqlite.json",
+                "updated_at": "2024-06-15T12:00:00Z"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            metadata.updated_at.unwrap().to_rfc3339(),
+            "2024-06-15T12:00:00+00:00"
+        );
+    }
+
+    fn catalog(entries: &[(&str, &str, &str)]) -> TemplateCatalog {
+        let templates = entries
+            .iter()
+            .map(|(name, version, category)| {
+                let metadata = serde_json::json!({
+                    "name": name,
+                    "version": version,
+                    "description": "",
+                    "author": "test",
+                    "tags": [],
+                    "platforms": [],
+                    "category": category,
+                    "path": format!("templates/{}/{}.json", category, name)
+                });
+                (name.to_string(), serde_json::from_value(metadata).unwrap())
+            })
+            .collect();
+        TemplateCatalog {
+            version: "1".to_string(),
+            last_updated: String::new(),
+            templates,
+        }
+    }
+
+    #[test]
+    fn test_diff_catalogs() {
+        let before = catalog(&[
+            ("github", "1.0.0", "official"),
+            ("slack", "0.2.0", "community"),
+            ("sqlite", "1.0.0", "official"),
+            ("old", "0.1.0", "experimental"),
+        ]);
+        let after = catalog(&[
+            ("github", "1.1.0", "official"),
+            ("slack", "0.2.0", "official"),
+            ("sqlite", "1.0.0", "official"),
+            ("brave", "1.0.0", "community"),
+            ("apify", "0.1.0", "community"),
+        ]);
+
+        let diff = diff_catalogs(&before, &after);
+        let names = |list: &[TemplateMetadata]| -> Vec<String> {
+            list.iter().map(|t| t.name.clone()).collect()
+        };
+        assert_eq!(names(&diff.added), ["apify", "brave"]);
+        assert_eq!(names(&diff.removed), ["old"]);
+        let updated: Vec<String> = diff
+            .updated
+            .iter()
+            .map(|(old, new)| format!("{} {} {}", new.name, old.version, new.category))
+            .collect();
+        assert_eq!(updated, ["github 1.0.0 official", "slack 0.2.0 official"]);
+
+        assert!(diff_catalogs(&after, &after).is_empty());
+    }
+
     #[test]
     fn test_template_serialization() {
         let template_json = r#"
@@ -597,6 +3044,7 @@ mod tests {
             version: "1.0.0".to_string(),
             description: "Test".to_string(),
             author: "Test".to_string(),
+            extends: None,
             tags: vec!["test".to_string()],
             platforms: vec!["macos".to_string()],
             variables: {
@@ -610,6 +3058,7 @@ mod tests {
                         required: true,
                         validation: None,
                         options: None,
+                        secret: false,
                     },
                 );
                 vars
@@ -622,9 +3071,11 @@ mod tests {
             },
             requirements: None,
             setup_instructions: None,
+            docs_url: None,
+            inherits: vec![],
         };
 
-        let manager = TemplateManager::new().unwrap();
+        let manager = TemplateManager::new(&ClientOptions::default()).unwrap();
 
         // Test missing required variable
         let empty_vars = HashMap::new();
@@ -639,12 +3090,1093 @@ mod tests {
         assert!(manager.validate_variables(&template, &valid_vars).is_ok());
     }
 
+    fn typed_variable(var_type: VariableType) -> TemplateVariable {
+        TemplateVariable {
+            var_type,
+            description: "A value".to_string(),
+            default: None,
+            required: false,
+            validation: None,
+            options: Some(vec!["fast".to_string(), "safe".to_string()]),
+            secret: false,
+        }
+    }
+
+    #[test]
+    fn test_coerce_variables() {
+        let template = Template {
+            name: "db".to_string(),
+            version: "1.0.0".to_string(),
+            description: "Test".to_string(),
+            author: "Test".to_string(),
+            extends: None,
+            tags: vec![],
+            platforms: vec![],
+            variables: HashMap::from([
+                ("port".to_string(), typed_variable(VariableType::Number)),
+                ("ssl".to_string(), typed_variable(VariableType::Boolean)),
+                ("host".to_string(), typed_variable(VariableType::String)),
+                ("mode".to_string(), typed_variable(VariableType::Select)),
+            ]),
+            config: TemplateConfig {
+                command: Some("db".to_string()),
+                args: None,
+                url: None,
+                env: None,
+            },
+            requirements: None,
+            setup_instructions: None,
+            docs_url: None,
+            inherits: vec![],
+        };
+
+        let coerced = coerce_variables(
+            &template,
+            &HashMap::from([
+                ("port".to_string(), serde_json::json!("5432")),
+                ("ssl".to_string(), serde_json::json!("false")),
+                ("host".to_string(), serde_json::json!(10)),
+                ("mode".to_string(), serde_json::json!("safe")),
+                ("extra".to_string(), serde_json::json!({"kept": true})),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(coerced["port"], serde_json::json!(5432));
+        assert_eq!(coerced["ssl"], serde_json::json!(false));
+        assert_eq!(coerced["host"], serde_json::json!("10"));
+        assert_eq!(coerced["mode"], serde_json::json!("safe"));
+        assert_eq!(coerced["extra"], serde_json::json!({"kept": true}));
+
+        let error = coerce_variables(
+            &template,
+            &HashMap::from([("port".to_string(), serde_json::json!("lots"))]),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Variable 'port' expects a number (A value): 'lots' is not a number"
+        );
+        let error = coerce_variables(
+            &template,
+            &HashMap::from([("host".to_string(), serde_json::json!(["a"]))]),
+        )
+        .unwrap_err();
+        assert!(error.to_string().ends_with("got a list"));
+    }
+
+    fn render_template(args: &[&str]) -> Template {
+        Template {
+            name: "db".to_string(),
+            version: "1.0.0".to_string(),
+            description: "Test".to_string(),
+            author: "Test".to_string(),
+            extends: None,
+            tags: vec![],
+            platforms: vec![],
+            variables: HashMap::from([
+                ("host".to_string(), typed_variable(VariableType::String)),
+                ("port".to_string(), typed_variable(VariableType::Number)),
+            ]),
+            config: TemplateConfig {
+                command: Some("db".to_string()),
+                args: Some(args.iter().map(|a| a.to_string()).collect()),
+                url: None,
+                env: None,
+            },
+            requirements: None,
+            setup_instructions: None,
+            docs_url: None,
+            inherits: vec![],
+        }
+    }
+
+    /// A template from JSON, filling in the fields every template must have
+    fn extends_template(name: &str, fields: serde_json::Value) -> Template {
+        let mut json = serde_json::json!({
+            "name": name,
+            "version": "1.0.0",
+            "description": name,
+            "author": "Test"
+        });
+        json.as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn postgres() -> Template {
+        extends_template(
+            "postgres",
+            serde_json::json!({
+                "tags": ["database", "sql"],
+                "platforms": ["linux", "macos"],
+                "variables": {
+                    "host": { "type": "string", "description": "Host", "default": "localhost" },
+                    "port": { "type": "number", "description": "Port", "default": 5432 }
+                },
+                "config": {
+                    "command": "pg-mcp",
+                    "args": ["--host", "{{host}}", "--port", "{{port}}"],
+                    "env": { "PGSSLMODE": "require", "PGAPPNAME": "mcp" }
+                },
+                "requirements": { "node": ">=18" },
+                "setup_instructions": "Install pg-mcp first",
+                "homepage": "https://example.com/pg-mcp"
+            }),
+        )
+    }
+
+    #[test]
+    fn test_extends_inherits_everything_left_out() {
+        let child = extends_template("pg-ro", serde_json::json!({ "extends": "postgres" }));
+        let merged = merge_chain(vec![child, postgres()]);
+
+        assert_eq!(merged.name, "pg-ro");
+        assert_eq!(merged.inherits, vec!["postgres"]);
+        assert_eq!(merged.tags, vec!["database", "sql"]);
+        assert_eq!(merged.platforms, vec!["linux", "macos"]);
+        assert_eq!(merged.variables.len(), 2);
+        assert_eq!(merged.config.command.as_deref(), Some("pg-mcp"));
+        assert_eq!(merged.config.args.as_ref().unwrap().len(), 4);
+        assert_eq!(merged.config.env.as_ref().unwrap().len(), 2);
+        assert_eq!(merged.requirements.unwrap()["node"], ">=18");
+        assert_eq!(
+            merged.setup_instructions.as_deref(),
+            Some("Install pg-mcp first")
+        );
+        assert_eq!(
+            merged.docs_url.as_deref(),
+            Some("https://example.com/pg-mcp")
+        );
+    }
+
+    #[test]
+    fn test_docs_url_reads_homepage_and_round_trips() {
+        let template = postgres();
+        assert_eq!(
+            template.docs_url.as_deref(),
+            Some("https://example.com/pg-mcp")
+        );
+
+        let json = serde_json::to_value(&template).unwrap();
+        assert_eq!(json["docs_url"], "https://example.com/pg-mcp");
+        assert!(json.get("homepage").is_none());
+        let reread: Template = serde_json::from_value(json).unwrap();
+        assert_eq!(reread.docs_url, template.docs_url);
+
+        // Templates written before the field existed still load
+        let plain = extends_template("plain", serde_json::json!({}));
+        assert_eq!(plain.docs_url, None);
+        assert!(serde_json::to_value(&plain)
+            .unwrap()
+            .get("docs_url")
+            .is_none());
+    }
+
+    #[test]
+    fn test_extends_child_fields_win_and_maps_merge() {
+        let child = extends_template(
+            "pg-ro",
+            serde_json::json!({
+                "extends": "postgres",
+                "tags": ["readonly", "sql"],
+                "platforms": ["linux"],
+                "variables": {
+                    "host": { "type": "string", "description": "Replica host", "default": "replica" }
+                },
+                "config": {
+                    "env": { "PGSSLMODE": "verify-full", "PGOPTIONS": "-c default_transaction_read_only=on" }
+                },
+                "requirements": { "node": ">=20", "psql": ">=15" }
+            }),
+        );
+        let merged = merge_chain(vec![child, postgres()]);
+
+        assert_eq!(merged.tags, vec!["database", "sql", "readonly"]);
+        assert_eq!(merged.platforms, vec!["linux"]);
+        assert_eq!(merged.variables["host"].description, "Replica host");
+        assert_eq!(merged.variables["port"].description, "Port");
+        let env = merged.config.env.unwrap();
+        assert_eq!(env["PGSSLMODE"], "verify-full");
+        assert_eq!(env["PGAPPNAME"], "mcp");
+        assert_eq!(env.len(), 3);
+        let requirements = merged.requirements.unwrap();
+        assert_eq!(requirements["node"], ">=20");
+        assert_eq!(requirements["psql"], ">=15");
+        // The launch itself still comes from the parent
+        assert_eq!(merged.config.command.as_deref(), Some("pg-mcp"));
+        assert_eq!(merged.config.args.unwrap()[0], "--host");
+    }
+
+    #[test]
+    fn test_extends_own_launch_replaces_the_parents() {
+        let same_command = extends_template(
+            "pg-args",
+            serde_json::json!({ "extends": "postgres", "config": { "args": ["--readonly"] } }),
+        );
+        let merged = merge_chain(vec![same_command, postgres()]);
+        assert_eq!(merged.config.command.as_deref(), Some("pg-mcp"));
+        assert_eq!(merged.config.args.unwrap(), vec!["--readonly"]);
+
+        let other_command = extends_template(
+            "pg-docker",
+            serde_json::json!({ "extends": "postgres", "config": { "command": "docker" } }),
+        );
+        let merged = merge_chain(vec![other_command, postgres()]);
+        assert_eq!(merged.config.command.as_deref(), Some("docker"));
+        assert_eq!(merged.config.args, None);
+
+        let remote = extends_template(
+            "pg-remote",
+            serde_json::json!({ "extends": "postgres", "config": { "url": "https://pg.example/mcp" } }),
+        );
+        let merged = merge_chain(vec![remote, postgres()]);
+        assert_eq!(merged.config.command, None);
+        assert_eq!(merged.config.args, None);
+        assert!(merged.config.validate().is_ok());
+        // Env still merges, whatever the launch
+        assert_eq!(merged.config.env.unwrap()["PGAPPNAME"], "mcp");
+    }
+
+    #[test]
+    fn test_extends_three_level_chain() {
+        let middle = extends_template(
+            "pg-ro",
+            serde_json::json!({
+                "extends": "postgres",
+                "tags": ["readonly"],
+                "variables": {
+                    "host": { "type": "string", "description": "Replica host", "default": "replica" }
+                },
+                "config": { "env": { "PGOPTIONS": "-c default_transaction_read_only=on" } }
+            }),
+        );
+        let child = extends_template(
+            "pg-ro-eu",
+            serde_json::json!({
+                "extends": "pg-ro",
+                "tags": ["eu"],
+                "variables": {
+                    "region": { "type": "string", "description": "Region", "default": "eu-west-1" }
+                },
+                "config": { "env": { "PGAPPNAME": "mcp-eu" } }
+            }),
+        );
+        let merged = merge_chain(vec![child, middle, postgres()]);
+
+        assert_eq!(merged.inherits, vec!["pg-ro", "postgres"]);
+        assert_eq!(merged.extends.as_deref(), Some("pg-ro"));
+        assert_eq!(merged.tags, vec!["database", "sql", "readonly", "eu"]);
+        let mut variables: Vec<&String> = merged.variables.keys().collect();
+        variables.sort();
+        assert_eq!(variables, ["host", "port", "region"]);
+        // The middle template's override beats the grandparent
+        assert_eq!(merged.variables["host"].description, "Replica host");
+        let env = merged.config.env.unwrap();
+        assert_eq!(env["PGAPPNAME"], "mcp-eu");
+        assert_eq!(env["PGSSLMODE"], "require");
+        assert!(env.contains_key("PGOPTIONS"));
+        assert_eq!(merged.config.command.as_deref(), Some("pg-mcp"));
+        assert_eq!(merged.platforms, vec!["linux", "macos"]);
+    }
+
+    #[test]
+    fn test_check_extends_stops_cycles_and_deep_chains() {
+        let a = extends_template("a", serde_json::json!({ "extends": "b" }));
+        let b = extends_template("b", serde_json::json!({ "extends": "a" }));
+        assert!(check_extends(std::slice::from_ref(&a), "b").is_ok());
+        assert_eq!(
+            check_extends(&[a.clone(), b], "a").unwrap_err().to_string(),
+            "Template inheritance cycle: a → b → a"
+        );
+        assert_eq!(
+            check_extends(std::slice::from_ref(&a), "a")
+                .unwrap_err()
+                .to_string(),
+            "Template inheritance cycle: a → a"
+        );
+
+        let chain: Vec<Template> = (0..=MAX_EXTENDS_DEPTH)
+            .map(|i| extends_template(&format!("t{}", i), serde_json::json!({})))
+            .collect();
+        assert!(check_extends(&chain[..MAX_EXTENDS_DEPTH], "next").is_ok());
+        assert!(check_extends(&chain, "next")
+            .unwrap_err()
+            .to_string()
+            .starts_with("Template 't0' extends more than 5 templates deep: t0 → t1"));
+    }
+
+    #[test]
+    fn test_validate_template() {
+        assert!(validate_template(&postgres()).is_empty());
+        // Launch config and variables can come from the parent
+        let child = extends_template(
+            "pg-ro",
+            serde_json::json!({ "extends": "postgres", "config": { "env": { "PGHOST": "{{host}}" } } }),
+        );
+        assert!(validate_template(&child).is_empty());
+
+        let broken = extends_template(
+            "broken",
+            serde_json::json!({
+                "author": " ",
+                "variables": {
+                    "mode": { "type": "select", "description": "Mode", "default": "fast" },
+                    "port": { "type": "number", "description": "", "default": "high", "options": ["1"] },
+                    "name": { "type": "string", "description": "Name", "validation": "[a-z" }
+                },
+                "config": { "command": "x", "url": "https://{{host}}" }
+            }),
+        );
+        assert_eq!(
+            validate_template(&broken),
+            [
+                "'author' is empty",
+                "invalid config: Template cannot have both 'url' and 'command' fields",
+                "variable 'mode' is a select with no options",
+                "variable 'name' has an invalid validation pattern '[a-z': unclosed character class",
+                "variable 'port' has no description",
+                "variable 'port' has options but is a number; only select variables use them",
+                "variable 'port' has a default that is not a number: 'high' is not a number",
+                "url uses 'host', which is not a variable",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_incompatible_overrides() {
+        let child = extends_template(
+            "pg-ro",
+            serde_json::json!({
+                "extends": "postgres",
+                "variables": {
+                    "host": { "type": "string", "description": "Host" },
+                    "port": { "type": "string", "description": "Port" },
+                    "region": { "type": "number", "description": "Region" }
+                }
+            }),
+        );
+        assert_eq!(
+            child.incompatible_overrides(&postgres()),
+            vec!["Variable 'port' is a string here but a number in 'postgres', which it extends"]
+        );
+    }
+
+    #[test]
+    fn test_with_previous_values_prefills_non_secret_variables() {
+        let mut template = render_template(&[]);
+        template.variables.extend([
+            ("tags".to_string(), typed_variable(VariableType::Array)),
+            (
+                "token".to_string(),
+                TemplateVariable {
+                    secret: true,
+                    default: Some(serde_json::json!("from-template")),
+                    ..typed_variable(VariableType::String)
+                },
+            ),
+        ]);
+        let previous = HashMap::from([
+            ("host".to_string(), serde_json::json!("db.local")),
+            ("port".to_string(), serde_json::json!(5432)),
+            ("tags".to_string(), serde_json::json!(["a", "b"])),
+            ("token".to_string(), serde_json::json!("leaked")),
+        ]);
+
+        let prefilled = template.with_previous_values(&previous);
+        let default = |name: &str| prefilled.variables[name].default.clone();
+        assert_eq!(default("host"), Some(serde_json::json!("db.local")));
+        assert_eq!(default("port"), Some(serde_json::json!("5432")));
+        assert_eq!(default("tags"), Some(serde_json::json!("a,b")));
+        assert_eq!(default("token"), Some(serde_json::json!("from-template")));
+    }
+
+    #[test]
+    fn test_keep_local_changes() {
+        let mut template = render_template(&[]);
+        template.config.env = Some(HashMap::from([(
+            "DB_HOST".to_string(),
+            "{{host}}".to_string(),
+        )]));
+        let current = crate::config::McpServer {
+            command: Some("db".to_string()),
+            args: None,
+            url: None,
+            env: Some(HashMap::from([
+                ("DB_HOST".to_string(), "old".to_string()),
+                ("LOG_LEVEL".to_string(), "debug".to_string()),
+            ])),
+            timeout: Some(30),
+            auto_approve: None,
+            other: HashMap::new(),
+        };
+        let mut rebuilt = crate::config::McpServer {
+            env: Some(HashMap::from([("DB_HOST".to_string(), "new".to_string())])),
+            timeout: None,
+            ..current.clone()
+        };
+
+        template.keep_local_changes(&current, &mut rebuilt);
+        assert_eq!(rebuilt.timeout, Some(30));
+        let env = rebuilt.env.unwrap();
+        assert_eq!(env["DB_HOST"], "new");
+        assert_eq!(env["LOG_LEVEL"], "debug");
+    }
+
+    #[test]
+    fn test_render_error_names_missing_variable() {
+        let manager = TemplateManager::new(&ClientOptions::default()).unwrap();
+        let vars = HashMap::from([("host".to_string(), serde_json::json!("db.local"))]);
+
+        let error = manager
+            .apply_template(&render_template(&["--host", "{{host}}", "{{port}}"]), &vars)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Template 'db' failed to render arg 2 `{{port}}`: variable 'port' was not provided\n  \
+             Provided variables: host"
+        );
+
+        let error = manager
+            .apply_template(&render_template(&["{{database}}"]), &vars)
+            .unwrap_err();
+        assert!(error.to_string().contains(
+            "arg 0 `{{database}}`: variable 'database' is not declared in the template's \"variables\" block"
+        ));
+    }
+
+    #[test]
+    fn test_rendered_values_are_not_html_escaped() {
+        let manager = TemplateManager::new(&ClientOptions::default()).unwrap();
+        let dsn = "postgres://u:p@h/db?a=1&b='2'";
+        let vars = HashMap::from([("host".to_string(), serde_json::json!(dsn))]);
+
+        let server = manager
+            .apply_template(&render_template(&["{{host}}"]), &vars)
+            .unwrap();
+        assert_eq!(server.args.unwrap(), [dsn]);
+    }
+
+    #[test]
+    fn test_leftover_handlebars_syntax_is_refused() {
+        let manager = TemplateManager::new(&ClientOptions::default()).unwrap();
+        let vars = HashMap::from([("host".to_string(), serde_json::json!("db.local"))]);
+        // An escaped expression renders as literal braces
+        let broken = render_template(&["--host", "{{host}}", "--readonly=\\{{readonly}}"]);
+
+        let error = manager.apply_template(&broken, &vars).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Template 'db' left handlebars syntax in the server:\n  \
+             argument 3: {{readonly}}\n\
+             Check the template's variable names, or pass --allow-unrendered to keep it as is"
+        );
+
+        let server = TemplateManager::new(&ClientOptions::default())
+            .unwrap()
+            .allow_unrendered(true)
+            .apply_template(&broken, &vars)
+            .unwrap();
+        assert_eq!(server.args.unwrap()[2], "--readonly={{readonly}}");
+    }
+
+    #[test]
+    fn test_setup_instructions_and_requirements_are_rendered() {
+        let manager = TemplateManager::new(&ClientOptions::default()).unwrap();
+        let mut template = render_template(&["{{host}}"]);
+        template.variables.get_mut("port").unwrap().default = Some(serde_json::json!(5432));
+        template.variables.insert(
+            "password".to_string(),
+            TemplateVariable {
+                secret: true,
+                ..typed_variable(VariableType::String)
+            },
+        );
+        template.setup_instructions =
+            Some("Allow {{host}}:{{port}} in pg_hba.conf with {{password}}".to_string());
+        template.requirements = Some(HashMap::from([
+            ("node".to_string(), "{{port}}.x".to_string()),
+            ("psql".to_string(), "{{version}}".to_string()),
+        ]));
+        let vars = HashMap::from([
+            ("host".to_string(), serde_json::json!("db.local")),
+            ("password".to_string(), serde_json::json!("hunter2")),
+        ]);
+
+        let rendered = manager.render_notes(&template, &vars);
+        assert_eq!(
+            rendered.setup_instructions.as_deref(),
+            Some("Allow db.local:5432 in pg_hba.conf with ********")
+        );
+        let requirements = rendered.requirements.unwrap();
+        assert_eq!(requirements["node"], "5432.x");
+        // An undeclared variable can't be filled in, so it stays as written
+        assert_eq!(requirements["psql"], "{{version}}");
+
+        // The template itself keeps its placeholders
+        assert_eq!(
+            template.setup_instructions.as_deref(),
+            Some("Allow {{host}}:{{port}} in pg_hba.conf with {{password}}")
+        );
+    }
+
+    #[test]
+    fn test_rendering_touches_neither_cache_nor_network() {
+        let manager = TemplateManager::new(&ClientOptions::default()).unwrap();
+        manager
+            .apply_template(&render_template(&["{{os}}"]), &HashMap::new())
+            .unwrap();
+        assert!(manager.cache_dir.get().is_none());
+        assert!(manager.github_client.get().is_none());
+    }
+
+    #[test]
+    fn test_each_github_source_reuses_one_client() {
+        let manager = TemplateManager::new(&ClientOptions::default()).unwrap();
+        let acme = TemplateRepository::parse("acme/templates").unwrap();
+        let other = TemplateRepository::parse("acme/templates@dev").unwrap();
+
+        let first = manager.source_github(&acme).unwrap();
+        assert!(Arc::ptr_eq(&first, &manager.source_github(&acme).unwrap()));
+        assert!(!Arc::ptr_eq(
+            &first,
+            &manager.source_github(&other).unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_render_error_names_unknown_helper() {
+        let manager = TemplateManager::new(&ClientOptions::default()).unwrap();
+        let error = manager
+            .apply_template(&render_template(&["{{upper host}}"]), &HashMap::new())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Template 'db' failed to render arg 0 `{{upper host}}`: unknown helper 'upper' \
+             (available: os, arch, home_dir, config_dir)\n  Provided variables: (none)"
+        );
+    }
+
     #[test]
-    fn test_platform_detection() {
-        let os = get_os_name();
-        assert!(!os.is_empty());
+    fn test_parse_stdin_variables() {
+        let lines = parse_stdin_variables("# team db\nhost=db.local\n\napi_key='a,b', port=5432\n")
+            .unwrap();
+        assert_eq!(lines["host"], "db.local");
+        assert_eq!(lines["api_key"], "a,b");
+        assert_eq!(lines["port"], "5432");
 
-        let arch = get_arch_name();
-        assert!(!arch.is_empty());
+        let json = parse_stdin_variables(r#" {"port": 5432}"#).unwrap();
+        assert_eq!(json["port"], 5432);
+        assert!(parse_stdin_variables("").unwrap().is_empty());
+
+        let error = parse_stdin_variables("host=a\noops\n").unwrap_err();
+        assert!(format!("{:#}", error).starts_with("line 2"), "{:#}", error);
+    }
+
+    #[test]
+    fn test_parse_variables() {
+        let yaml = parse_variables("port: 5432\nssl: true\nhosts: [a, b]\n").unwrap();
+        assert_eq!(yaml["port"], serde_json::json!(5432));
+        assert_eq!(yaml["hosts"], serde_json::json!(["a", "b"]));
+
+        let json = parse_variables(r#"{"name": "db"}"#).unwrap();
+        assert_eq!(json["name"], serde_json::json!("db"));
+
+        assert!(parse_variables("").unwrap().is_empty());
+        assert!(parse_variables("- just\n- a list\n").is_err());
+    }
+
+    #[test]
+    fn test_resolve_cache_dir_falls_back_to_temp() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert_eq!(
+            resolve_cache_dir(Some(dir.path().to_path_buf())).unwrap(),
+            dir.path().join("mcp-forge")
+        );
+
+        let fallback = temp_cache_dir().unwrap();
+        assert!(fallback.starts_with(std::env::temp_dir()));
+        assert_eq!(resolve_cache_dir(None).unwrap(), fallback);
+
+        // A file where the cache directory should be can't be used either
+        let blocked = dir.path().join("blocked");
+        std::fs::write(&blocked, "").unwrap();
+        assert_eq!(resolve_cache_dir(Some(blocked)).unwrap(), fallback);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_private_cache_dir_checks_owner_and_mode() {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+        let root = tempfile::TempDir::new().unwrap();
+        let uid = std::fs::metadata(root.path()).unwrap().uid();
+
+        let fresh = root.path().join("fresh");
+        ensure_private_dir(&fresh, uid).unwrap();
+        assert_eq!(std::fs::metadata(&fresh).unwrap().mode() & 0o077, 0);
+        // Reusing one's own directory is fine
+        ensure_private_dir(&fresh, uid).unwrap();
+
+        let err = ensure_private_dir(&fresh, uid + 1).unwrap_err();
+        assert!(err.to_string().contains("belongs to uid"), "{}", err);
+
+        let shared = root.path().join("shared");
+        std::fs::create_dir(&shared).unwrap();
+        std::fs::set_permissions(&shared, std::fs::Permissions::from_mode(0o777)).unwrap();
+        let err = ensure_private_dir(&shared, uid).unwrap_err();
+        assert!(err.to_string().contains("other users"), "{}", err);
+
+        let link = root.path().join("link");
+        std::os::unix::fs::symlink(&fresh, &link).unwrap();
+        assert!(ensure_private_dir(&link, uid).is_err());
+    }
+
+    #[test]
+    fn test_render_dir_errors_without_home() {
+        assert_eq!(
+            render_dir(Some(PathBuf::from("/home/me")), "home_dir").unwrap(),
+            "/home/me"
+        );
+
+        let err = render_dir(None, "home_dir").unwrap_err();
+        assert!(err.to_string().contains("{{home_dir}}"));
+        assert!(err.to_string().contains("HOME"));
+    }
+
+    #[test]
+    fn test_security_warnings() {
+        assert!(render_template(&["--host", "{{host}}"])
+            .security_warnings()
+            .is_empty());
+
+        let mut template = render_template(&["-c", "curl -s https://x.example/install | sh"]);
+        template.config.command = Some("sh".to_string());
+        template.config.env = Some(HashMap::from([
+            ("API_KEY".to_string(), "sk-123".to_string()),
+            ("ACCESS_TOKEN".to_string(), "{{token}}".to_string()),
+        ]));
+        template
+            .variables
+            .insert("token".to_string(), typed_variable(VariableType::String));
+        assert_eq!(
+            template.security_warnings(),
+            [
+                "Variable 'token' looks like a credential but isn't marked secret",
+                "Env 'API_KEY' has a hardcoded value instead of a variable",
+                "Command pipes a download into a shell",
+            ]
+        );
+
+        let mut remote = render_template(&[]);
+        remote.config.command = None;
+        remote.config.url = Some("http://mcp.example.com".to_string());
+        assert_eq!(
+            remote.security_warnings(),
+            ["Endpoint 'http://mcp.example.com' uses plain HTTP"]
+        );
+        remote.config.url = Some("http://localhost:8080".to_string());
+        assert!(remote.security_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_cache_ttl_from_setting() {
+        assert_eq!(cache_ttl_from(None), chrono::Duration::days(30));
+        assert_eq!(cache_ttl_from(Some("12h")), chrono::Duration::hours(12));
+        assert_eq!(cache_ttl_from(Some("3 fortnights")), DEFAULT_CACHE_TTL);
+    }
+
+    #[test]
+    fn test_stale_after_from_setting() {
+        assert_eq!(stale_after_from(None), Some(chrono::Duration::days(14)));
+        assert_eq!(
+            stale_after_from(Some("30d")),
+            Some(chrono::Duration::days(30))
+        );
+        assert_eq!(stale_after_from(Some(" OFF ")), None);
+        assert_eq!(stale_after_from(Some("soon")), Some(DEFAULT_STALE_AFTER));
+    }
+
+    #[test]
+    fn test_stale_cache_notice_threshold() {
+        use chrono::TimeZone;
+        let now = chrono::Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        let fortnight = Some(chrono::Duration::days(14));
+
+        let notice = stale_cache_notice(now - chrono::Duration::days(41), now, fortnight).unwrap();
+        assert_eq!(
+            notice,
+            "template data was last refreshed 41 days ago — run `mcp-forge template refresh`"
+        );
+        // Exactly at the threshold is still fresh enough
+        assert!(stale_cache_notice(now - chrono::Duration::days(14), now, fortnight).is_none());
+        assert!(stale_cache_notice(now - chrono::Duration::days(3), now, fortnight).is_none());
+        assert!(stale_cache_notice(now - chrono::Duration::days(400), now, None).is_none());
+    }
+
+    #[test]
+    fn test_cache_expires_after_the_ttl() {
+        use crate::clock::FixedClock;
+        use chrono::TimeZone;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let start = chrono::Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        let clock = Arc::new(FixedClock::at(start));
+        let manager = TemplateManager::new(&ClientOptions::default())
+            .unwrap()
+            .with_cache_dir(dir.path().to_path_buf())
+            .with_clock(clock.clone());
+        manager
+            .save_cache_metadata(&CacheMetadata::refreshed_at(start))
+            .unwrap();
+
+        assert!(!manager.is_cache_expired().unwrap());
+        // Exactly at the expiry time the cache is still used
+        clock.advance(DEFAULT_CACHE_TTL);
+        assert!(!manager.is_cache_expired().unwrap());
+        clock.advance(chrono::Duration::seconds(1));
+        assert!(manager.is_cache_expired().unwrap());
+    }
+
+    /// Serve repository files through a fake contents API with an ETag per
+    /// file, answering 304 to requests that already have it; returns the base
+    /// URL and how many full responses were sent
+    async fn mock_etag_repo(
+        files: Vec<(&'static str, String)>,
+    ) -> (String, Arc<std::sync::atomic::AtomicUsize>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let sent = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = sent.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+                let path = request
+                    .split_whitespace()
+                    .nth(1)
+                    .and_then(|target| target.split("/contents/").nth(1))
+                    .and_then(|rest| rest.split('?').next())
+                    .unwrap_or_default()
+                    .to_string();
+                let content = files.iter().find(|(p, _)| *p == path).map(|(_, c)| c);
+                let etag = format!("\"{}\"", path);
+
+                let (status, body) = match content {
+                    Some(_) if request.contains(&format!("if-none-match: {}", etag)) => {
+                        (304, String::new())
+                    }
+                    Some(content) => {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                        let body = serde_json::json!({ "content": content, "encoding": "utf-8" });
+                        (200, body.to_string())
+                    }
+                    None => (404, r#"{"message":"Not Found"}"#.to_string()),
+                };
+                let response = format!(
+                    "HTTP/1.1 {} X\r\nETag: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    etag,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        (format!("http://{}", addr), sent)
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_catalog_and_templates_are_revalidated() {
+        use crate::clock::FixedClock;
+        use chrono::TimeZone;
+
+        let template = serde_json::to_string(&render_template(&[])).unwrap();
+        let name = render_template(&[]).name;
+        let catalog = serde_json::json!({
+            "version": "1",
+            "last_updated": "now",
+            "templates": { &name: {
+                "name": &name, "version": "1.0.0", "description": "d", "author": "a",
+                "tags": [], "platforms": [], "category": "official",
+                "path": "templates/t.json"
+            }}
+        });
+        let (base_url, sent) = mock_etag_repo(vec![
+            ("catalog.json", catalog.to_string()),
+            ("templates/t.json", template),
+        ])
+        .await;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let start = chrono::Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        let clock = Arc::new(FixedClock::at(start));
+        let manager = TemplateManager::new(&ClientOptions::default())
+            .unwrap()
+            .with_cache_dir(dir.path().to_path_buf())
+            .with_repo(TemplateRepository::default())
+            .with_clock(clock.clone())
+            .with_github(crate::github::GitHubClient::with_base_url(&base_url));
+
+        manager.refresh_cache().await.unwrap();
+        let metadata = manager.load_cache_metadata().unwrap();
+        assert_eq!(metadata.catalog_etag.as_deref(), Some(r#""catalog.json""#));
+        assert_eq!(sent.load(Ordering::SeqCst), 1);
+
+        // An unchanged catalog costs no download, and the cache counts as fresh again
+        clock.advance(chrono::Duration::days(40));
+        manager.refresh_cache().await.unwrap();
+        assert_eq!(sent.load(Ordering::SeqCst), 1);
+        let metadata = manager.load_cache_metadata().unwrap();
+        assert_eq!(metadata.last_refresh, clock.now());
+        assert_eq!(metadata.expires_at, clock.now() + cache_ttl());
+        assert!(manager.catalog_changes().unwrap().unwrap().is_empty());
+
+        clock.advance(chrono::Duration::days(40));
+        assert!(manager.is_cache_expired().unwrap());
+        assert_eq!(manager.load_catalog().await.unwrap().templates.len(), 1);
+        assert!(!manager.is_cache_expired().unwrap());
+
+        // Templates keep their own ETags
+        manager.load_repo_template(&name).await.unwrap();
+        let downloads = sent.load(Ordering::SeqCst);
+        assert_eq!(
+            manager.load_cache_metadata().unwrap().template_etags[&name],
+            r#""templates/t.json""#
+        );
+        clock.advance(chrono::Duration::days(40));
+        assert_eq!(manager.load_repo_template(&name).await.unwrap().name, name);
+        // Only the catalog lookup inside fetch_template is downloaded again
+        assert_eq!(sent.load(Ordering::SeqCst), downloads + 1);
+    }
+
+    #[test]
+    fn test_local_template_dirs_from_setting() {
+        let config_dir = PathBuf::from("/home/me/.config");
+        let default = config_dir.join("mcp-forge").join("templates");
+        assert_eq!(
+            local_template_dirs_from(None, Some(config_dir.clone())),
+            vec![default.clone()]
+        );
+
+        let setting = std::env::join_paths(["/team/templates", "", "/mine"]).unwrap();
+        assert_eq!(
+            local_template_dirs_from(Some(setting), Some(config_dir)),
+            [
+                PathBuf::from("/team/templates"),
+                PathBuf::from("/mine"),
+                default
+            ]
+        );
+        assert!(local_template_dirs_from(None, None).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_local_templates_take_precedence() {
+        let cache = tempfile::TempDir::new().unwrap();
+        let first = tempfile::TempDir::new().unwrap();
+        let second = tempfile::TempDir::new().unwrap();
+        let manager = TemplateManager::new(&ClientOptions::default())
+            .unwrap()
+            .with_cache_dir(cache.path().to_path_buf())
+            .with_local_dirs(vec![
+                first.path().to_path_buf(),
+                second.path().to_path_buf(),
+            ]);
+
+        let write = |dir: &Path, author: &str| {
+            let template = Template {
+                author: author.to_string(),
+                ..render_template(&[])
+            };
+            std::fs::write(
+                dir.join("db.json"),
+                serde_json::to_string(&template).unwrap(),
+            )
+            .unwrap();
+        };
+        manager
+            .save_template_cache(&Template {
+                author: "Catalog".to_string(),
+                ..render_template(&[])
+            })
+            .unwrap();
+        write(second.path(), "Second");
+        assert_eq!(
+            manager.require_cached_template("db").await.unwrap().author,
+            "Second"
+        );
+        write(first.path(), "First");
+        assert_eq!(
+            manager.require_cached_template("db").await.unwrap().author,
+            "First"
+        );
+
+        std::fs::write(second.path().join("broken.json"), "{").unwrap();
+        std::fs::write(second.path().join("notes.txt"), "").unwrap();
+        let local = manager.local_templates();
+        assert_eq!(local.len(), 1);
+        assert_eq!(local["db"].author, "First");
+        assert_eq!(local["db"].category, "local");
+    }
+
+    #[test]
+    fn test_cache_from_another_repo_is_dropped() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let manager = |repo: &str| {
+            TemplateManager::new(&ClientOptions::default())
+                .unwrap()
+                .with_cache_dir(dir.path().to_path_buf())
+                .with_repo(TemplateRepository::parse(repo).unwrap())
+        };
+        let default = manager("AndyCross/mcp-forge-templates");
+        default.save_template_cache(&render_template(&[])).unwrap();
+        assert!(default.load_cached_template("db").unwrap().is_some());
+
+        // A cache without a recorded repository came from the default one
+        let fork = manager("acme/templates@dev");
+        assert!(fork.load_cached_template("db").unwrap().is_none());
+        assert!(fork.is_cache_expired().unwrap());
+
+        fork.adopt_repo().unwrap();
+        assert!(fork.cached_template_names().unwrap().is_empty());
+        assert!(!fork.is_cache_expired().unwrap());
+        assert_eq!(
+            fork.load_cache_metadata().unwrap().repo.as_deref(),
+            Some("acme/templates@dev")
+        );
+        assert!(!default.cache_matches_repo().unwrap());
+    }
+
+    #[test]
+    fn test_template_source_parse() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let canonical = std::fs::canonicalize(dir.path()).unwrap();
+
+        assert_eq!(
+            TemplateSource::parse("https://example.com/catalog.json").unwrap(),
+            TemplateSource::Url("https://example.com/catalog.json".to_string())
+        );
+        assert_eq!(
+            TemplateSource::parse(dir.path().to_str().unwrap()).unwrap(),
+            TemplateSource::Dir(canonical.clone())
+        );
+        assert_eq!(
+            TemplateSource::parse("acme/templates@dev").unwrap(),
+            TemplateSource::Github(TemplateRepository::parse("acme/templates@dev").unwrap())
+        );
+        assert!(TemplateSource::parse("./no/such/dir").is_err());
+        assert!(TemplateSource::parse("not-a-repo").is_err());
+
+        // Saved as {"name": ..., "<kind>": ...}
+        let source = NamedSource {
+            name: "team".to_string(),
+            source: TemplateSource::Github(TemplateRepository::parse("acme/templates").unwrap()),
+        };
+        let json = serde_json::to_value(&source).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "name": "team", "github": "acme/templates@master" })
+        );
+        assert_eq!(serde_json::from_value::<NamedSource>(json).unwrap(), source);
+
+        assert!(check_source_name("team-2").is_ok());
+        assert!(check_source_name("default").is_err());
+        assert!(check_source_name("a/b").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_first_source_listing_a_template_wins() {
+        let cache = tempfile::TempDir::new().unwrap();
+        let first = tempfile::TempDir::new().unwrap();
+        let second = tempfile::TempDir::new().unwrap();
+        let write = |dir: &Path, name: &str, author: &str| {
+            let template = Template {
+                name: name.to_string(),
+                author: author.to_string(),
+                ..render_template(&[])
+            };
+            std::fs::write(
+                dir.join(format!("{}.json", name)),
+                serde_json::to_string(&template).unwrap(),
+            )
+            .unwrap();
+        };
+        write(first.path(), "db", "First");
+        write(second.path(), "db", "Second");
+        write(second.path(), "cache", "Second");
+
+        let source = |name: &str, dir: &Path| NamedSource {
+            name: name.to_string(),
+            source: TemplateSource::Dir(dir.to_path_buf()),
+        };
+        let manager = || {
+            TemplateManager::new(&ClientOptions::default())
+                .unwrap()
+                .with_cache_dir(cache.path().to_path_buf())
+                .with_local_dirs(Vec::new())
+                .with_sources(vec![
+                    source("first", first.path()),
+                    source("second", second.path()),
+                ])
+        };
+
+        let listed = manager().cached_source_templates();
+        assert_eq!(listed["db"].source.as_deref(), Some("first"));
+        assert_eq!(listed["cache"].source.as_deref(), Some("second"));
+
+        let loaded = manager().require_cached_template("db").await.unwrap();
+        assert_eq!(loaded.author, "First");
+        let chosen = manager()
+            .only_from_source(Some("second".to_string()))
+            .unwrap()
+            .require_cached_template("db")
+            .await
+            .unwrap();
+        assert_eq!(chosen.author, "Second");
+        assert!(manager()
+            .only_from_source(Some("elsewhere".to_string()))
+            .is_err());
+
+        // The repository comes first once it has the template
+        manager()
+            .save_template_cache(&Template {
+                author: "Repository".to_string(),
+                ..render_template(&[])
+            })
+            .unwrap();
+        let loaded = manager().require_cached_template("db").await.unwrap();
+        assert_eq!(loaded.author, "Repository");
+    }
+
+    #[test]
+    fn test_check_platform() {
+        assert!(supports_platform(&[], "linux"));
+        assert!(supports_platform(&["Linux".to_string()], "linux"));
+        assert!(!supports_platform(&["windows".to_string()], "linux"));
+
+        let template = Template {
+            platforms: vec!["windows".to_string(), "macos".to_string()],
+            ..render_template(&[])
+        };
+        assert!(template.check_platform("macos").is_ok());
+        let err = template.check_platform("linux").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("supports windows, macos only, not linux"));
+    }
+
+    #[test]
+    fn test_platform_helpers_follow_the_override() {
+        platform::set_override(Some(platform::Platform::parse("windows/arm64").unwrap()));
+        let rendered = TemplateManager::new(&ClientOptions::default())
+            .unwrap()
+            .apply_template(&render_template(&["{{os}}-{{arch}}"]), &HashMap::new());
+        platform::set_override(None);
+        assert_eq!(rendered.unwrap().args.unwrap()[0], "windows-arm64");
     }
 }
@@ -0,0 +1,154 @@
+use chrono::{DateTime, Local, SecondsFormat, TimeZone, Utc};
+use std::fmt::Display;
+
+/// The timezone timestamps are shown and dates are read in
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Zone {
+    #[default]
+    Local,
+    /// ISO 8601 UTC, as chosen with `--utc`
+    Utc,
+}
+
+impl Zone {
+    pub fn from_flag(utc: bool) -> Self {
+        if utc {
+            Zone::Utc
+        } else {
+            Zone::Local
+        }
+    }
+}
+
+/// Format a timestamp for display, e.g. `2026-03-01 13:00 +01:00`
+pub fn format_timestamp(timestamp: DateTime<Utc>, zone: Zone) -> String {
+    if zone == Zone::Utc {
+        timestamp.to_rfc3339_opts(SecondsFormat::Secs, true)
+    } else {
+        format_timestamp_in(timestamp, &Local)
+    }
+}
+
+/// Format the date part of a timestamp for display, e.g. `2026-03-01`
+pub fn format_date(timestamp: DateTime<Utc>, zone: Zone) -> String {
+    if zone == Zone::Utc {
+        timestamp.format("%Y-%m-%d").to_string()
+    } else {
+        timestamp
+            .with_timezone(&Local)
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+}
+
+/// Parse a `YYYY-MM-DD` date as the start of that day in `zone`
+pub fn parse_date(value: &str, zone: Zone) -> Option<DateTime<Utc>> {
+    let date = chrono::NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok()?;
+    let midnight = date.and_hms_opt(0, 0, 0)?;
+    if zone == Zone::Utc {
+        Some(midnight.and_utc())
+    } else {
+        Local
+            .from_local_datetime(&midnight)
+            .earliest()
+            .map(|t| t.with_timezone(&Utc))
+    }
+}
+
+/// Format a timestamp in a specific timezone, with its UTC offset
+pub fn format_timestamp_in<Tz>(timestamp: DateTime<Utc>, tz: &Tz) -> String
+where
+    Tz: TimeZone,
+    Tz::Offset: Display,
+{
+    timestamp
+        .with_timezone(tz)
+        .format("%Y-%m-%d %H:%M %:z")
+        .to_string()
+}
+
+/// Describe how long ago a timestamp was, e.g. `3 days ago`
+pub fn format_age(timestamp: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let duration = now.signed_duration_since(timestamp);
+
+    if duration.num_days() > 0 {
+        format!("{} ago", pluralize(duration.num_days(), "day", "days"))
+    } else if duration.num_hours() > 0 {
+        format!("{} ago", pluralize(duration.num_hours(), "hour", "hours"))
+    } else if duration.num_minutes() > 0 {
+        format!(
+            "{} ago",
+            pluralize(duration.num_minutes(), "minute", "minutes")
+        )
+    } else {
+        "just now".to_string()
+    }
+}
+
+/// Render a count with the singular or plural form of a noun
+pub fn pluralize(count: i64, singular: &str, plural: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, singular)
+    } else {
+        format!("{} {}", count, plural)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, FixedOffset};
+
+    fn timestamp() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, 12, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn test_format_timestamp_in_offset() {
+        let cet = FixedOffset::east_opt(3600).unwrap();
+        assert_eq!(
+            format_timestamp_in(timestamp(), &cet),
+            "2026-03-01 13:30 +01:00"
+        );
+
+        let pacific = FixedOffset::west_opt(8 * 3600).unwrap();
+        assert_eq!(
+            format_timestamp_in(timestamp(), &pacific),
+            "2026-03-01 04:30 -08:00"
+        );
+    }
+
+    #[test]
+    fn test_format_age_pluralizes() {
+        let now = timestamp();
+        assert_eq!(format_age(now - Duration::days(1), now), "1 day ago");
+        assert_eq!(format_age(now - Duration::days(3), now), "3 days ago");
+        assert_eq!(format_age(now - Duration::hours(1), now), "1 hour ago");
+        assert_eq!(format_age(now - Duration::minutes(5), now), "5 minutes ago");
+        assert_eq!(format_age(now - Duration::seconds(30), now), "just now");
+    }
+
+    #[test]
+    fn test_format_age_at_unit_boundaries() {
+        let now = timestamp();
+        let ago = |duration| format_age(now - duration, now);
+        assert_eq!(ago(Duration::seconds(59)), "just now");
+        assert_eq!(ago(Duration::seconds(60)), "1 minute ago");
+        assert_eq!(ago(Duration::minutes(59)), "59 minutes ago");
+        assert_eq!(ago(Duration::minutes(60)), "1 hour ago");
+        assert_eq!(
+            ago(Duration::hours(24) - Duration::seconds(1)),
+            "23 hours ago"
+        );
+        assert_eq!(ago(Duration::hours(24)), "1 day ago");
+        // A timestamp from the future, e.g. from a skewed clock, isn't shown as an age
+        assert_eq!(ago(Duration::minutes(-5)), "just now");
+    }
+
+    #[test]
+    fn test_pluralize() {
+        assert_eq!(pluralize(0, "server", "servers"), "0 servers");
+        assert_eq!(pluralize(1, "server", "servers"), "1 server");
+        assert_eq!(pluralize(2, "server", "servers"), "2 servers");
+    }
+}
@@ -0,0 +1,454 @@
+use crate::config::{Config, Host, Target};
+use crate::metadata::ServerMetadata;
+use crate::timefmt::{self, Zone};
+use crate::utils;
+use crate::utils::atomic::{self, Disk, WriteSteps};
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const JOURNAL_FILE: &str = "pending_writes.json";
+
+/// Files that must change together, such as the Claude config, the active
+/// profile's snapshot and the server metadata
+///
+/// Every write is recorded in a journal before any file is touched, and the
+/// journal is removed once they have all landed. If mcp-forge dies part-way,
+/// [`recover`] finishes or undoes the set on the next run.
+pub struct Transaction {
+    target: Target,
+    journal: PathBuf,
+    writes: Vec<PendingWrite>,
+}
+
+/// One file in a transaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PendingWrite {
+    path: PathBuf,
+    /// Contents before the transaction; `None` if the file didn't exist
+    before: Option<String>,
+    after: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Journal {
+    started_at: DateTime<Utc>,
+    /// In write order; the first write decides whether recovery finishes or undoes the rest
+    writes: Vec<PendingWrite>,
+}
+
+/// Where a file stands relative to an interrupted transaction
+#[derive(Debug, PartialEq)]
+enum FileState {
+    Before,
+    After,
+    /// Changed by something else since
+    Changed,
+}
+
+impl PendingWrite {
+    fn state(&self) -> FileState {
+        let current = fs::read_to_string(&self.path).ok();
+        if current.as_deref() == Some(self.after.as_str()) {
+            FileState::After
+        } else if current == self.before {
+            FileState::Before
+        } else {
+            FileState::Changed
+        }
+    }
+
+    fn apply(&self, steps: &dyn WriteSteps) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        atomic::write_atomic_at(steps, &self.path, self.after.as_bytes())
+    }
+
+    fn undo(&self, steps: &dyn WriteSteps) -> Result<()> {
+        match &self.before {
+            Some(before) => atomic::write_atomic_at(steps, &self.path, before.as_bytes()),
+            None => fs::remove_file(&self.path)
+                .with_context(|| format!("Failed to remove {}", self.path.display())),
+        }
+    }
+}
+
+impl Transaction {
+    pub fn new(target: &Target) -> Result<Self> {
+        Ok(Self::with_journal(target.clone(), journal_path(target)?))
+    }
+
+    fn with_journal(target: Target, journal: PathBuf) -> Self {
+        Self {
+            target,
+            journal,
+            writes: Vec::new(),
+        }
+    }
+
+    /// Queue `contents` to be written to `path` on commit
+    ///
+    /// Files are written in the order they are first queued. A symlinked
+    /// file is recorded as the file it points at, so the link survives.
+    pub fn write(&mut self, path: PathBuf, contents: String) {
+        let path = if self.target.replace_symlinks {
+            path
+        } else {
+            utils::resolve_symlinks(&path)
+        };
+        if let Some(existing) = self.writes.iter_mut().find(|w| w.path == path) {
+            existing.after = contents;
+            return;
+        }
+        let before = fs::read_to_string(&path).ok();
+        self.writes.push(PendingWrite {
+            path,
+            before,
+            after: contents,
+        });
+    }
+
+    pub fn commit(self) -> Result<()> {
+        // Commands that change the config take the lock at startup; this
+        // catches any path that doesn't
+        crate::lock::acquire(&self.target, false)?;
+        self.commit_with(&Disk)
+    }
+
+    /// Commit, doing the disk writes through `steps`
+    ///
+    /// A failed write leaves the journal behind, so the next run can tidy up.
+    fn commit_with(self, steps: &dyn WriteSteps) -> Result<()> {
+        let writes: Vec<PendingWrite> = self
+            .writes
+            .into_iter()
+            .filter(|w| w.before.as_deref() != Some(w.after.as_str()))
+            .collect();
+        if writes.is_empty() {
+            return Ok(());
+        }
+        if self.journal.exists() {
+            return Err(anyhow!(
+                "An earlier change was interrupted and could not be finished; \
+                 fix the problem reported at startup or remove {}",
+                self.journal.display()
+            ));
+        }
+
+        let journal = Journal {
+            started_at: Utc::now(),
+            writes,
+        };
+        let content = serde_json::to_string_pretty(&journal)?;
+        if let Some(parent) = self.journal.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        atomic::write_atomic_with(steps, &self.journal, content.as_bytes())?;
+
+        for write in &journal.writes {
+            write.apply(steps)?;
+        }
+        fs::remove_file(&self.journal)
+            .with_context(|| format!("Failed to remove {}", self.journal.display()))
+    }
+}
+
+/// What [`recover`] did with an interrupted transaction
+#[derive(Debug, PartialEq)]
+pub struct Recovery {
+    pub started_at: DateTime<Utc>,
+    /// True if the change was completed, false if it was undone
+    pub finished: bool,
+    /// Files rewritten to complete or undo the change
+    pub repaired: Vec<PathBuf>,
+    /// Files changed by something else since, and left as they are
+    pub skipped: Vec<PathBuf>,
+}
+
+/// Finish or undo a transaction that was interrupted on a previous run
+///
+/// If the first file made it to disk the change counts as done and the rest
+/// are written; otherwise anything already written is put back.
+pub fn recover(target: &Target) -> Result<Option<Recovery>> {
+    recover_with(&Disk, &journal_path(target)?)
+}
+
+fn recover_with(steps: &dyn WriteSteps, journal_path: &Path) -> Result<Option<Recovery>> {
+    let content = match fs::read_to_string(journal_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", journal_path.display()))
+        }
+    };
+    let journal: Journal = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", journal_path.display()))?;
+
+    let finished = journal
+        .writes
+        .first()
+        .is_some_and(|write| write.state() == FileState::After);
+    let mut repaired = Vec::new();
+    let mut skipped = Vec::new();
+    for write in &journal.writes {
+        match (write.state(), finished) {
+            (FileState::Before, true) => write.apply(steps)?,
+            (FileState::After, false) => write.undo(steps)?,
+            (FileState::Changed, _) => {
+                skipped.push(write.path.clone());
+                continue;
+            }
+            _ => continue,
+        }
+        repaired.push(write.path.clone());
+    }
+
+    fs::remove_file(journal_path)
+        .with_context(|| format!("Failed to remove {}", journal_path.display()))?;
+    Ok(Some(Recovery {
+        started_at: journal.started_at,
+        finished,
+        repaired,
+        skipped,
+    }))
+}
+
+/// Run [`recover`] at startup and say what it did
+///
+/// A journal left while another process holds the config lock is that
+/// process's change in progress, so it's left alone. Failure only warns;
+/// mutating commands refuse to run until it's resolved.
+pub fn recover_on_startup(target: &Target, zone: Zone) {
+    let pending = journal_path(target).is_ok_and(|path| path.exists());
+    if !pending || !crate::lock::try_acquire(target).unwrap_or(false) {
+        return;
+    }
+    match recover(target) {
+        Ok(Some(recovery)) => print_recovery(&recovery, zone),
+        Ok(None) => {}
+        Err(e) => eprintln!(
+            "{}",
+            format!(
+                "⚠ A change from an earlier run was interrupted and could not be repaired: {:#}",
+                e
+            )
+            .yellow()
+        ),
+    }
+}
+
+fn print_recovery(recovery: &Recovery, zone: Zone) {
+    let files = timefmt::pluralize(recovery.repaired.len() as i64, "file", "files");
+    let outcome = if recovery.finished {
+        format!("finished it ({} written)", files)
+    } else {
+        format!("undid it ({} restored)", files)
+    };
+    eprintln!(
+        "{}",
+        format!(
+            "⚠ A change started {} was interrupted; {}",
+            timefmt::format_timestamp(recovery.started_at, zone),
+            outcome
+        )
+        .yellow()
+    );
+    for path in &recovery.skipped {
+        eprintln!("  Left {} alone: it has been changed since", path.display());
+    }
+}
+
+/// Save the Claude config together with the profile snapshot and, if given,
+/// the server metadata, as one transaction
+pub async fn save_config(
+    config: &Config,
+    target: &Target,
+    profile: Option<&str>,
+    metadata: Option<&ServerMetadata>,
+) -> Result<()> {
+    let mut transaction = Transaction::new(target)?;
+    config.stage(target, &mut transaction)?;
+    // Profiles only track the Claude Desktop config
+    if target.host == Host::Claude {
+        crate::profiles::stage_server_count(&mut transaction, profile, config).await?;
+    }
+    if let Some(metadata) = metadata {
+        metadata.stage(target, &mut transaction)?;
+    }
+    transaction.commit()
+}
+
+fn journal_path(target: &Target) -> Result<PathBuf> {
+    Ok(target.state_dir()?.join(JOURNAL_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::fs::File;
+    use std::io;
+    use tempfile::TempDir;
+
+    /// Lets `survive` renames through, then fails every disk step, as if the
+    /// process had been killed at that point
+    struct CrashAfter {
+        survive: usize,
+        done: Cell<usize>,
+    }
+
+    impl CrashAfter {
+        fn new(survive: usize) -> Self {
+            Self {
+                survive,
+                done: Cell::new(0),
+            }
+        }
+
+        fn alive(&self) -> io::Result<()> {
+            if self.done.get() < self.survive {
+                Ok(())
+            } else {
+                Err(io::Error::other("killed"))
+            }
+        }
+    }
+
+    impl WriteSteps for CrashAfter {
+        fn write(&self, file: &mut File, contents: &[u8]) -> io::Result<()> {
+            self.alive()?;
+            Disk.write(file, contents)
+        }
+
+        fn replace(&self, from: &Path, to: &Path) -> io::Result<()> {
+            self.alive()?;
+            self.done.set(self.done.get() + 1);
+            Disk.replace(from, to)
+        }
+    }
+
+    struct Stores {
+        dir: TempDir,
+    }
+
+    impl Stores {
+        /// A config with two servers whose snapshot and metadata agree
+        fn new() -> Self {
+            let dir = TempDir::new().unwrap();
+            let stores = Self { dir };
+            fs::write(stores.path("config.json"), "a,b").unwrap();
+            fs::write(stores.path("snapshot.json"), "a,b").unwrap();
+            stores
+        }
+
+        fn path(&self, name: &str) -> PathBuf {
+            self.dir.path().join(name)
+        }
+
+        fn journal(&self) -> PathBuf {
+            self.path(JOURNAL_FILE)
+        }
+
+        /// Add server `c`: the config and snapshot change and metadata is created
+        fn add_server(&self) -> Transaction {
+            let mut transaction = Transaction::with_journal(Target::default(), self.journal());
+            transaction.write(self.path("config.json"), "a,b,c".to_string());
+            transaction.write(self.path("snapshot.json"), "a,b,c".to_string());
+            transaction.write(self.path("metadata.json"), "c".to_string());
+            transaction
+        }
+
+        fn read(&self, name: &str) -> Option<String> {
+            fs::read_to_string(self.path(name)).ok()
+        }
+
+        fn state(&self) -> (Option<String>, Option<String>, Option<String>) {
+            (
+                self.read("config.json"),
+                self.read("snapshot.json"),
+                self.read("metadata.json"),
+            )
+        }
+    }
+
+    fn before() -> (Option<String>, Option<String>, Option<String>) {
+        (Some("a,b".into()), Some("a,b".into()), None)
+    }
+
+    fn after() -> (Option<String>, Option<String>, Option<String>) {
+        (Some("a,b,c".into()), Some("a,b,c".into()), Some("c".into()))
+    }
+
+    #[test]
+    fn test_commit_writes_everything_and_clears_the_journal() {
+        let stores = Stores::new();
+        stores.add_server().commit_with(&Disk).unwrap();
+        assert_eq!(stores.state(), after());
+        assert!(!stores.journal().exists());
+        assert_eq!(recover_with(&Disk, &stores.journal()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_recovery_after_a_crash_at_every_step() {
+        // One rename for the journal, then one per file
+        for survive in 0..=4 {
+            let stores = Stores::new();
+            let _ = stores.add_server().commit_with(&CrashAfter::new(survive));
+
+            let recovery = recover_with(&Disk, &stores.journal()).unwrap();
+            let expected = if survive >= 2 { after() } else { before() };
+            assert_eq!(stores.state(), expected, "crashed after {} steps", survive);
+            assert!(!stores.journal().exists());
+            assert_eq!(recovery.is_some(), (1..4).contains(&survive));
+            assert!(recovery.is_none_or(|r| r.skipped.is_empty()));
+        }
+    }
+
+    #[test]
+    fn test_recovery_undoes_files_written_before_the_first() {
+        // A journal whose first file never landed, but a later one did
+        let stores = Stores::new();
+        let journal = Journal {
+            started_at: Utc::now(),
+            writes: stores.add_server().writes,
+        };
+        fs::write(stores.journal(), serde_json::to_string(&journal).unwrap()).unwrap();
+        fs::write(stores.path("metadata.json"), "c").unwrap();
+
+        let recovery = recover_with(&Disk, &stores.journal()).unwrap().unwrap();
+        assert!(!recovery.finished);
+        assert_eq!(recovery.repaired, vec![stores.path("metadata.json")]);
+        assert_eq!(stores.state(), before());
+    }
+
+    #[test]
+    fn test_recovery_leaves_files_changed_since_alone() {
+        let stores = Stores::new();
+        let _ = stores.add_server().commit_with(&CrashAfter::new(2));
+        fs::write(stores.path("snapshot.json"), "edited").unwrap();
+
+        let recovery = recover_with(&Disk, &stores.journal()).unwrap().unwrap();
+        assert!(recovery.finished);
+        assert_eq!(recovery.skipped, vec![stores.path("snapshot.json")]);
+        assert_eq!(stores.read("snapshot.json").as_deref(), Some("edited"));
+        assert_eq!(stores.read("metadata.json").as_deref(), Some("c"));
+    }
+
+    #[test]
+    fn test_commit_refuses_while_an_earlier_change_is_unrepaired() {
+        let stores = Stores::new();
+        let _ = stores.add_server().commit_with(&CrashAfter::new(2));
+
+        let err = stores.add_server().commit_with(&Disk).unwrap_err();
+        assert!(err.to_string().contains("interrupted"), "{}", err);
+        // Unchanged files don't need a transaction at all
+        let mut noop = Transaction::with_journal(Target::default(), stores.journal());
+        noop.write(stores.path("config.json"), "a,b,c".to_string());
+        noop.commit_with(&Disk).unwrap();
+    }
+}
@@ -0,0 +1,358 @@
+use crate::cli::{prompt_for_variable, show_server_diff};
+use crate::config::{Config, McpServer, Target};
+use crate::github::ClientOptions;
+use crate::metadata::{ServerMetadata, ServerProvenance};
+use crate::template_versions::{self, VersionStatus};
+use crate::templates::{Template, TemplateManager};
+use crate::{transaction, utils};
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use inquire::Confirm;
+use std::collections::HashMap;
+
+/// A server rebuilt from the latest version of its template
+struct Upgrade {
+    name: String,
+    current: McpServer,
+    upgraded: McpServer,
+    provenance: ServerProvenance,
+    from_version: String,
+    secrets: Vec<String>,
+}
+
+/// The values to rebuild a server with: those recorded when it was added,
+/// those read back from its config, then `overrides`
+///
+/// Variables the new template no longer declares are dropped.
+fn known_values(
+    template: &Template,
+    provenance: &ServerProvenance,
+    current: &McpServer,
+    overrides: &HashMap<String, serde_json::Value>,
+) -> HashMap<String, serde_json::Value> {
+    let mut values = template.recover_values(current);
+    values.extend(provenance.variables.clone());
+    values.extend(overrides.clone());
+    values.retain(|name, _| template.variables.contains_key(name));
+    values
+}
+
+/// Fill in the variables `values` lacks: by prompting when `interactive`,
+/// otherwise with their defaults
+///
+/// Sorted by name so prompts come in a stable order.
+fn fill_missing(
+    server: &str,
+    template: &Template,
+    values: &mut HashMap<String, serde_json::Value>,
+    interactive: bool,
+) -> Result<()> {
+    let mut missing: Vec<_> = template
+        .variables
+        .iter()
+        .filter(|(name, _)| !values.contains_key(*name))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    missing.sort_by_key(|(name, _)| name.as_str());
+
+    if interactive {
+        println!(
+            "Values for '{}' that couldn't be recovered from its config:",
+            server
+        );
+    }
+    for (name, variable) in missing {
+        if interactive {
+            values.insert(name.clone(), prompt_for_variable(name, variable)?);
+        } else if let Some(default) = &variable.default {
+            values.insert(name.clone(), default.clone());
+        } else if variable.required {
+            return Err(anyhow!(
+                "'{}' needs a value for '{}', which couldn't be recovered. Pass --var {}=VALUE or run interactively",
+                server,
+                name,
+                name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Rebuild `name` from the latest version of its template, keeping changes
+/// made to it since; `None` if it is on that version already
+async fn plan_upgrade(
+    template_manager: &TemplateManager,
+    name: &str,
+    current: &McpServer,
+    provenance: &ServerProvenance,
+    overrides: &HashMap<String, serde_json::Value>,
+    interactive: bool,
+) -> Result<Option<Upgrade>> {
+    let template = template_manager
+        .load_template(&provenance.template)
+        .await
+        .map_err(|e| {
+            anyhow!(
+                "Couldn't load template '{}', which '{}' was created from: {:#}",
+                provenance.template,
+                name,
+                e
+            )
+        })?;
+    if !template_versions::is_behind(&provenance.version, &template.version) {
+        println!(
+            "✓ '{}' is already on {} v{}",
+            name, template.name, provenance.version
+        );
+        return Ok(None);
+    }
+
+    let mut values = known_values(&template, provenance, current, overrides);
+    fill_missing(name, &template, &mut values, interactive)?;
+    let mut upgraded = template_manager
+        .apply_template(&template, &values)
+        .map_err(|e| anyhow!("Couldn't rebuild '{}': {:#}", name, e))?;
+    template.keep_local_changes(current, &mut upgraded);
+
+    let rendered = template_manager.render_notes(&template, &values);
+    let mut new_provenance =
+        ServerProvenance::from_template(&rendered, provenance.category.clone(), &values);
+    new_provenance.added_at = provenance.added_at;
+    new_provenance.notes = provenance.notes.clone();
+
+    Ok(Some(Upgrade {
+        name: name.to_string(),
+        current: current.clone(),
+        upgraded,
+        provenance: new_provenance,
+        from_version: provenance.version.clone(),
+        secrets: template.secret_values(&values),
+    }))
+}
+
+/// Servers `--all` upgrades: those behind their template's latest version,
+/// except pinned ones
+async fn outdated_servers(
+    template_manager: &TemplateManager,
+    config: &Config,
+    metadata: &ServerMetadata,
+) -> Result<Vec<String>> {
+    let latest = template_versions::latest_templates(template_manager, false).await?;
+    let mut names = Vec::new();
+    for row in template_versions::server_versions(metadata, &latest) {
+        if !config.mcp_servers.contains_key(&row.server) {
+            continue;
+        }
+        match (row.status, &row.pinned) {
+            (VersionStatus::Pinned | VersionStatus::Outdated, Some(pinned)) => println!(
+                "{} Skipping '{}', pinned to {} v{}",
+                "ℹ".cyan(),
+                row.server,
+                row.template,
+                pinned
+            ),
+            (VersionStatus::Outdated, None) => names.push(row.server),
+            _ => {}
+        }
+    }
+    Ok(names)
+}
+
+/// Handle the upgrade command
+///
+/// Every server is rebuilt before anything is saved, so a template that
+/// can't be found or a value that can't be filled in stops the whole run.
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_upgrade(
+    name: Option<String>,
+    all: bool,
+    var: Vec<String>,
+    dry_run: bool,
+    force: bool,
+    target: &Target,
+    github: &ClientOptions,
+    profile: Option<String>,
+) -> Result<()> {
+    let mut config = Config::load(target).await?;
+    let mut metadata = ServerMetadata::load(target)?;
+    let template_manager = TemplateManager::new(github)?;
+    let mut overrides = HashMap::new();
+    for assignment in &var {
+        let (key, value) = utils::assignments::parse_assignment(assignment)?;
+        overrides.insert(key, serde_json::Value::String(value));
+    }
+
+    let names = match name {
+        Some(name) => {
+            if !config.mcp_servers.contains_key(&name) {
+                return Err(anyhow!(
+                    "Server '{}' not found. {}",
+                    name,
+                    crate::bulk::describe_existing_servers(&config)
+                ));
+            }
+            let provenance = metadata.get(&name).ok_or_else(|| {
+                anyhow!(
+                    "Server '{}' wasn't added from a template, so there is nothing to upgrade it to",
+                    name
+                )
+            })?;
+            if let Some(pinned) = &provenance.pinned {
+                return Err(anyhow!(
+                    "Server '{}' is pinned to {} v{}; unpin it first with `mcp-forge template pin {} --unpin`",
+                    name,
+                    provenance.template,
+                    pinned,
+                    name
+                ));
+            }
+            vec![name]
+        }
+        None if all => outdated_servers(&template_manager, &config, &metadata).await?,
+        None => return Err(anyhow!("Name a server to upgrade, or pass --all")),
+    };
+
+    // A dry run never asks; values it can't fill in are reported instead
+    let interactive = !dry_run && std::io::IsTerminal::is_terminal(&std::io::stdin());
+    let mut upgrades = Vec::new();
+    for name in &names {
+        let provenance = metadata
+            .get(name)
+            .ok_or_else(|| anyhow!("Server '{}' has no recorded template", name))?;
+        let upgrade = plan_upgrade(
+            &template_manager,
+            name,
+            &config.mcp_servers[name],
+            provenance,
+            &overrides,
+            interactive,
+        )
+        .await?;
+        upgrades.extend(upgrade);
+    }
+    if upgrades.is_empty() {
+        if all {
+            println!(
+                "{}",
+                "✓ Every server is on its template's latest version".green()
+            );
+        }
+        return Ok(());
+    }
+
+    for upgrade in &upgrades {
+        println!(
+            "\n{} {}: {} v{} → v{}",
+            "⬆".cyan(),
+            upgrade.name.bold(),
+            upgrade.provenance.template,
+            upgrade.from_version,
+            upgrade.provenance.version
+        );
+        if upgrade.current == upgrade.upgraded {
+            println!("  The server's configuration doesn't change");
+        } else {
+            show_server_diff(
+                &upgrade.current.with_values_masked(&upgrade.secrets),
+                &upgrade.upgraded.with_values_masked(&upgrade.secrets),
+                &upgrade.name,
+            )
+            .await?;
+        }
+    }
+
+    if dry_run {
+        println!("\nDry run: nothing was changed");
+        return Ok(());
+    }
+    if !force {
+        utils::ensure_can_confirm()?;
+        let prompt = match upgrades.len() {
+            1 => "Apply this upgrade?".to_string(),
+            n => format!("Apply these {} upgrades?", n),
+        };
+        if !Confirm::new(&prompt).with_default(true).prompt()? {
+            println!("Upgrade cancelled.");
+            return Ok(());
+        }
+    }
+
+    crate::backup::backup_before_change(target, &config).await?;
+    for upgrade in &upgrades {
+        config
+            .mcp_servers
+            .insert(upgrade.name.clone(), upgrade.upgraded.clone());
+        metadata.record(&upgrade.name, upgrade.provenance.clone());
+    }
+    transaction::save_config(&config, target, profile.as_deref(), Some(&metadata)).await?;
+
+    for upgrade in &upgrades {
+        println!(
+            "{}",
+            format!(
+                "✓ Upgraded '{}' to {} v{}",
+                upgrade.name, upgrade.provenance.template, upgrade.provenance.version
+            )
+            .green()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template() -> Template {
+        serde_json::from_value(serde_json::json!({
+            "name": "db",
+            "version": "2.0.0",
+            "description": "Database",
+            "author": "Team",
+            "variables": {
+                "host": { "type": "string", "description": "Host", "required": true },
+                "token": { "type": "string", "description": "Token", "required": true, "secret": true },
+                "port": { "type": "number", "description": "Port", "default": 5432 }
+            },
+            "config": {
+                "command": "db-mcp",
+                "args": ["--host", "{{host}}", "--port", "{{port}}"],
+                "env": { "DB_TOKEN": "{{token}}", "DB_URL": "db://{{host}}" }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_known_values_recover_secrets_from_the_config() {
+        let template = template();
+        let current: McpServer = serde_json::from_value(serde_json::json!({
+            "command": "db-mcp",
+            "args": ["--host", "db.internal"],
+            "env": { "DB_TOKEN": "s3cret", "DB_URL": "db://db.internal" }
+        }))
+        .unwrap();
+        let provenance = ServerProvenance {
+            variables: HashMap::from([
+                ("host".to_string(), serde_json::json!("db.internal")),
+                ("retired".to_string(), serde_json::json!("x")),
+            ]),
+            ..ServerProvenance::from_template(&template, None, &HashMap::new())
+        };
+
+        let values = known_values(&template, &provenance, &current, &HashMap::new());
+        assert_eq!(
+            values,
+            HashMap::from([
+                ("host".to_string(), serde_json::json!("db.internal")),
+                ("token".to_string(), serde_json::json!("s3cret")),
+            ])
+        );
+
+        let overrides = HashMap::from([("host".to_string(), serde_json::json!("db.example"))]);
+        let values = known_values(&template, &provenance, &current, &overrides);
+        assert_eq!(values["host"], "db.example");
+    }
+}
@@ -1,5 +1,13 @@
+use crate::config::{Host, Target};
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub mod assignments;
+pub mod atomic;
+pub mod display;
+pub mod duration;
+pub mod platform;
 
 /// Utility functions for MCP-Forge
 /// Get the Claude Desktop configuration directory
@@ -15,19 +23,212 @@ pub fn get_config_dir() -> Result<PathBuf> {
     #[cfg(target_os = "linux")]
     let config_dir = home.join(".config/claude");
 
+    // A config dir symlinked into a dotfiles repo is used where it lives
+    if is_symlink(&config_dir) {
+        return Ok(resolve_symlinks(&config_dir));
+    }
     Ok(config_dir)
 }
 
+/// The file `path` ends up at once every symlink in it is followed
+///
+/// Unlike [`std::fs::canonicalize`] this also works for files that don't
+/// exist yet, including the target of a dangling link, so a save can create
+/// the file the link points at. Paths without symlinks come back unchanged.
+pub fn resolve_symlinks(path: &Path) -> PathBuf {
+    if !path.ancestors().any(is_symlink) {
+        return path.to_path_buf();
+    }
+    if let Ok(real) = std::fs::canonicalize(path) {
+        return real;
+    }
+    let mut path = path.to_path_buf();
+    // Give up on link cycles the way the OS does
+    for _ in 0..40 {
+        let Ok(target) = std::fs::read_link(&path) else {
+            break;
+        };
+        path = match path.parent() {
+            Some(parent) => parent.join(target),
+            None => target,
+        };
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) if !parent.as_os_str().is_empty() => {
+            resolve_symlinks(parent).join(name)
+        }
+        _ => path,
+    }
+}
+
+/// Whether `path` itself is a symlink, dangling or not
+pub fn is_symlink(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink())
+}
+
+/// The config file given with `--config`, or else by `MCP_FORGE_CONFIG`
+pub fn config_override_from(flag: Option<PathBuf>, setting: Option<PathBuf>) -> Option<PathBuf> {
+    let path = flag.or(setting.filter(|path| !path.as_os_str().is_empty()))?;
+    // Relative paths are kept relative to where the command was run
+    Some(
+        std::env::current_dir()
+            .map(|cwd| cwd.join(&path))
+            .unwrap_or(path),
+    )
+}
+
 /// Get the Claude Desktop configuration file path
 pub fn get_claude_config_path() -> Result<PathBuf> {
     let config_dir = get_config_dir()?;
     Ok(config_dir.join("claude_desktop_config.json"))
 }
 
-/// Get the backup directory
-pub fn get_backup_dir() -> Result<PathBuf> {
-    let config_dir = get_config_dir()?;
-    Ok(config_dir.join("backups"))
+/// Get the config file of the client chosen with `--target`
+pub fn get_config_path_for_target(host: &Host) -> Result<PathBuf> {
+    let home = || dirs::home_dir().context("Could not find home directory");
+    match host {
+        Host::Claude => get_claude_config_path(),
+        Host::Cursor => Ok(home()?.join(".cursor/mcp.json")),
+        Host::Windsurf => Ok(home()?.join(".codeium/windsurf/mcp_config.json")),
+        Host::VsCode => {
+            #[cfg(target_os = "macos")]
+            let user_dir = home()?.join("Library/Application Support/Code/User");
+
+            #[cfg(target_os = "windows")]
+            let user_dir = home()?.join("AppData/Roaming/Code/User");
+
+            #[cfg(target_os = "linux")]
+            let user_dir = home()?.join(".config/Code/User");
+
+            Ok(user_dir.join("mcp.json"))
+        }
+        Host::Custom(path) => Ok(path.clone()),
+    }
+}
+
+/// Check whether a file (or, if missing, the directory it would go in) is writable
+pub fn is_writable(path: &Path) -> bool {
+    let path = &resolve_symlinks(path);
+    if path.exists() {
+        if path.is_dir() {
+            can_create_in(path)
+        } else {
+            let readonly = std::fs::metadata(path).map_or(true, |m| m.permissions().readonly());
+            !readonly && std::fs::OpenOptions::new().append(true).open(path).is_ok()
+        }
+    } else {
+        // Missing directories are created on save, so check the nearest one that exists
+        path.ancestors()
+            .skip(1)
+            .find(|ancestor| ancestor.exists())
+            .is_some_and(can_create_in)
+    }
+}
+
+/// Whether a file can be created in `dir`, found by creating and removing one
+///
+/// The permission bits alone don't say: a directory owned by another user
+/// can look writable while this process can't add to it.
+fn can_create_in(dir: &Path) -> bool {
+    let probe = dir.join(format!(".mcp-forge-write-check-{}", std::process::id()));
+    match std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+    {
+        Ok(_) => {
+            let _ = std::fs::remove_file(&probe);
+            true
+        }
+        // Left behind by an earlier check that was interrupted
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            std::fs::remove_file(&probe).is_ok()
+        }
+        Err(_) => false,
+    }
+}
+
+/// Fail fast when the config file or the directory mcp-forge keeps its own
+/// files in can't be written
+///
+/// Called before any prompting or network work so mutating commands don't
+/// get all the way to the final save before discovering they can't write.
+pub fn ensure_config_writable(target: &Target) -> Result<()> {
+    let link = target.config_path()?;
+    let config_path = if !is_symlink(&link) {
+        link.clone()
+    } else if target.replace_symlinks {
+        // The link itself is replaced, which only needs its directory
+        link.parent().map(Path::to_path_buf).unwrap_or_default()
+    } else {
+        resolve_symlinks(&link)
+    };
+    let state_dir = target.state_dir()?;
+
+    for path in [state_dir.as_path(), config_path.as_path()] {
+        let existing = path
+            .ancestors()
+            .find(|ancestor| ancestor.exists())
+            .unwrap_or(path);
+        if !is_writable(existing) {
+            let mut remediation = write_remediation(existing);
+            if path == config_path && config_path != link {
+                remediation.push_str(&format!(
+                    "\n{} is a symlink to it; pass --force-replace-symlink to replace the link with a regular file instead.",
+                    link.display()
+                ));
+            }
+            anyhow::bail!(
+                "Cannot write to {} ({}).\n{}",
+                existing.display(),
+                describe_permissions(existing),
+                remediation
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Describe who owns a path and its permission bits
+fn describe_permissions(path: &Path) -> String {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return "permissions unknown".to_string();
+    };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        format!(
+            "owner uid {}, mode {:o}",
+            metadata.uid(),
+            metadata.mode() & 0o7777
+        )
+    }
+
+    #[cfg(not(unix))]
+    {
+        if metadata.permissions().readonly() {
+            "read-only".to_string()
+        } else {
+            "access denied".to_string()
+        }
+    }
+}
+
+/// Platform-appropriate advice for making a path writable
+fn write_remediation(path: &Path) -> String {
+    #[cfg(target_os = "windows")]
+    return format!(
+        "Clear the read-only attribute with `attrib -r \"{}\"` or grant your account Modify access in the folder's Security settings.",
+        path.display()
+    );
+
+    #[cfg(not(target_os = "windows"))]
+    format!(
+        "Make it writable with `chmod u+w '{0}'`, or `sudo chown \"$(whoami)\" '{0}'` if it belongs to another user.",
+        path.display()
+    )
 }
 
 /// Mask sensitive parts of URLs to prevent credential leaks
@@ -37,14 +238,18 @@ pub fn get_backup_dir() -> Result<PathBuf> {
 pub fn mask_sensitive_url(url: &str) -> String {
     // Parse the URL to find query parameters
     if let Ok(parsed_url) = url::Url::parse(url) {
-        let mut masked_url = format!("{}://{}", parsed_url.scheme(), parsed_url.host_str().unwrap_or(""));
-        
+        let mut masked_url = format!(
+            "{}://{}",
+            parsed_url.scheme(),
+            parsed_url.host_str().unwrap_or("")
+        );
+
         if let Some(port) = parsed_url.port() {
             masked_url.push_str(&format!(":{}", port));
         }
-        
+
         masked_url.push_str(parsed_url.path());
-        
+
         // Check for query parameters
         if let Some(query) = parsed_url.query() {
             let mut masked_params = Vec::new();
@@ -52,13 +257,13 @@ pub fn mask_sensitive_url(url: &str) -> String {
                 if let Some((key, value)) = pair.split_once('=') {
                     let normalized_key = key.to_lowercase();
                     // Check if this is a sensitive parameter
-                    let is_sensitive = normalized_key.contains("key") 
+                    let is_sensitive = normalized_key.contains("key")
                         || normalized_key.contains("token")
                         || normalized_key.contains("secret")
                         || normalized_key.contains("password")
                         || normalized_key.contains("apikey")
                         || normalized_key.contains("api_key");
-                    
+
                     if is_sensitive && value.len() > 6 {
                         // Mask the value
                         let first_part = &value[..3];
@@ -79,12 +284,12 @@ pub fn mask_sensitive_url(url: &str) -> String {
                 masked_url.push_str(&masked_params.join("&"));
             }
         }
-        
+
         if let Some(fragment) = parsed_url.fragment() {
             masked_url.push('#');
             masked_url.push_str(fragment);
         }
-        
+
         masked_url
     } else {
         // If URL parsing fails, return as-is (might not be a valid URL)
@@ -92,12 +297,71 @@ pub fn mask_sensitive_url(url: &str) -> String {
     }
 }
 
+/// Check that a URL-type server endpoint is an absolute http(s) URL
+pub fn validate_server_url(url: &str) -> Result<()> {
+    let parsed =
+        url::Url::parse(url).map_err(|e| anyhow::anyhow!("Invalid URL '{}': {}", url, e))?;
+
+    if !matches!(parsed.scheme(), "http" | "https") {
+        anyhow::bail!(
+            "Unsupported URL scheme '{}' in '{}' (expected http or https)",
+            parsed.scheme(),
+            mask_sensitive_url(url)
+        );
+    }
+    if parsed.host_str().is_none_or(str::is_empty) {
+        anyhow::bail!("URL '{}' has no host", mask_sensitive_url(url));
+    }
+
+    Ok(())
+}
+
 /// Mask sensitive environment variable values to prevent credential leaks
 ///
 /// This function checks if an environment variable key contains sensitive patterns
 /// like CLIENT_ID, CLIENT_SECRET, etc. (case insensitive, with various separators)
 /// and masks the value showing only first 3 and last 3 characters.
 pub fn mask_sensitive_env_value(key: &str, value: &str) -> String {
+    if is_sensitive_env_key(key) {
+        mask_secret(value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Mask an HTTP header value when the header carries credentials
+///
+/// Covers `Authorization`, `Proxy-Authorization` and `Cookie` as well as any
+/// name that would count as sensitive for an environment variable.
+pub fn mask_sensitive_header_value(name: &str, value: &str) -> String {
+    let credential_header = matches!(
+        name.to_lowercase().as_str(),
+        "authorization" | "proxy-authorization" | "cookie"
+    );
+    if credential_header || is_sensitive_env_key(name) {
+        mask_secret(value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn mask_secret(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() > 6 {
+        // Show first 3 and last 3 characters with asterisks in between
+        let first_part: String = chars[..3].iter().collect();
+        let last_part: String = chars[chars.len() - 3..].iter().collect();
+        let middle_length = chars.len() - 6;
+        let asterisks = "*".repeat(middle_length.max(4)); // At least 4 asterisks
+        format!("{}{}{}", first_part, asterisks, last_part)
+    } else {
+        // For very short values, just show asterisks
+        "*".repeat(chars.len().max(8))
+    }
+}
+
+/// Check whether an environment variable name looks like it holds a credential
+pub fn is_sensitive_env_key(key: &str) -> bool {
     // Convert key to lowercase and normalize separators for pattern matching
     let normalized_key = key.to_lowercase().replace(['_', '-', '.'], "");
 
@@ -117,36 +381,367 @@ pub fn mask_sensitive_env_value(key: &str, value: &str) -> String {
     ];
 
     // Check if the key contains any sensitive patterns
-    let is_sensitive = sensitive_patterns
+    sensitive_patterns
         .iter()
-        .any(|pattern| normalized_key.contains(pattern));
+        .any(|pattern| normalized_key.contains(pattern))
+}
 
-    if is_sensitive && value.len() > 6 {
-        // Show first 3 and last 3 characters with asterisks in between
-        let first_part = &value[..3];
-        let last_part = &value[value.len() - 3..];
-        let middle_length = value.len() - 6;
-        let asterisks = "*".repeat(middle_length.max(4)); // At least 4 asterisks
-        format!("{}{}{}", first_part, asterisks, last_part)
-    } else if is_sensitive {
-        // For very short values, just show asterisks
-        "*".repeat(value.len().max(8))
+/// The form two environment keys share if they differ only by case
+///
+/// Both the collision check and the variant lookup fold with this, so they
+/// agree on non-ASCII names too.
+fn fold_env_key(key: &str) -> String {
+    key.to_uppercase()
+}
+
+/// Find groups of environment keys that differ only by case
+///
+/// Windows treats environment variable names case-insensitively, so maps like
+/// `{"Api_Key": .., "API_KEY": ..}` behave differently across platforms.
+pub fn find_case_colliding_keys(env: &HashMap<String, String>) -> Vec<Vec<String>> {
+    let mut groups: HashMap<String, Vec<String>> = HashMap::new();
+    for key in env.keys() {
+        groups
+            .entry(fold_env_key(key))
+            .or_default()
+            .push(key.clone());
+    }
+
+    let mut collisions: Vec<Vec<String>> = groups
+        .into_values()
+        .filter(|keys| keys.len() > 1)
+        .map(|mut keys| {
+            keys.sort();
+            keys
+        })
+        .collect();
+    collisions.sort();
+    collisions
+}
+
+/// Find existing keys that match `key` case-insensitively but not exactly, sorted
+pub fn find_case_variant_keys(env: &HashMap<String, String>, key: &str) -> Vec<String> {
+    if env.contains_key(key) {
+        return Vec::new();
+    }
+    let folded = fold_env_key(key);
+    let mut keys: Vec<String> = env
+        .keys()
+        .filter(|k| fold_env_key(k) == folded)
+        .cloned()
+        .collect();
+    keys.sort();
+    keys
+}
+
+/// Collapse a group of case-variant keys down to the chosen one
+pub fn merge_case_variant_keys(env: &mut HashMap<String, String>, group: &[String], keep: &str) {
+    for key in group {
+        if key != keep {
+            env.remove(key);
+        }
+    }
+}
+
+/// Exit code used when the user cancels an interactive prompt (128 + SIGINT)
+pub const CANCELLED_EXIT_CODE: i32 = 130;
+
+/// Error returned by a command that ran to completion but must exit non-zero,
+/// e.g. a report that found errors
+///
+/// The command has already printed its output; main records the run and then
+/// exits with `code` instead of printing the error.
+#[derive(Debug)]
+pub struct ExitStatus {
+    pub code: i32,
+    reason: String,
+}
+
+impl ExitStatus {
+    pub fn new(code: i32, reason: impl Into<String>) -> Self {
+        Self {
+            code,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ExitStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+impl std::error::Error for ExitStatus {}
+
+/// The exit code a command asked for, if the error is an [`ExitStatus`]
+pub fn exit_status(err: &anyhow::Error) -> Option<i32> {
+    err.downcast_ref::<ExitStatus>().map(|status| status.code)
+}
+
+/// Error returned when a prompt cannot be answered because stdin is closed
+#[derive(Debug)]
+pub struct PromptCancelled;
+
+impl std::fmt::Display for PromptCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Operation cancelled.")
+    }
+}
+
+impl std::error::Error for PromptCancelled {}
+
+/// Error returned when a prompt is needed but stdin is not a terminal
+///
+/// Unlike a cancelled prompt this is a usage error: the message says how to
+/// run the command without being asked.
+#[derive(Debug)]
+pub struct NotInteractive(String);
+
+impl std::fmt::Display for NotInteractive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (stdin is not a terminal)", self.0)
+    }
+}
+
+impl std::error::Error for NotInteractive {}
+
+/// Ensure prompts can be answered; `hint` says how to do without them
+///
+/// A closed stdin (Ctrl-D, `/dev/null`) cancels like Ctrl-C does; anything
+/// else that isn't a terminal is a usage error.
+pub fn ensure_interactive(hint: &str) -> Result<()> {
+    if std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+        Ok(())
+    } else if stdin_at_eof() {
+        Err(PromptCancelled.into())
     } else {
-        // Not sensitive, return as-is
-        value.to_string()
+        Err(NotInteractive(hint.to_string()).into())
     }
 }
 
+/// Whether stdin is already at end of file, without waiting for input
+#[cfg(unix)]
+fn stdin_at_eof() -> bool {
+    let mut fd = libc::pollfd {
+        fd: libc::STDIN_FILENO,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    // SAFETY: fd points to one valid pollfd, and a zero timeout never blocks
+    let ready = unsafe { libc::poll(&mut fd, 1, 0) };
+    if ready != 1 || fd.revents & (libc::POLLIN | libc::POLLHUP) == 0 {
+        return false;
+    }
+    // Readable stdin may hold data rather than EOF; only a read can tell.
+    // The byte is lost, but the prompt fails either way.
+    let mut byte = 0u8;
+    // SAFETY: the buffer is one writable byte, and poll said the read won't block
+    let read = unsafe { libc::read(libc::STDIN_FILENO, (&mut byte as *mut u8).cast(), 1) };
+    read == 0
+}
+
+#[cfg(not(unix))]
+fn stdin_at_eof() -> bool {
+    false
+}
+
+/// Ensure a confirmation can be answered, for commands that take `--force`
+pub fn ensure_can_confirm() -> Result<()> {
+    ensure_interactive("Confirmation required; pass --force to skip it")
+}
+
+/// Whether an error means a prompt was needed but stdin is not a terminal
+pub fn is_not_interactive(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<NotInteractive>().is_some()
+}
+
+/// Whether an error means the user cancelled a prompt (Ctrl-C, Esc or EOF)
+pub fn is_prompt_cancelled(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<PromptCancelled>().is_some()
+        || matches!(
+            err.downcast_ref::<inquire::InquireError>(),
+            Some(
+                inquire::InquireError::OperationCanceled
+                    | inquire::InquireError::OperationInterrupted
+            )
+        )
+}
+
+/// Sanitize filename by removing invalid characters
+pub fn sanitize_filename(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect()
+}
+
+/// FNV-1a checksum of file content, as `fnv1a64:<hex>`
+///
+/// Stable across builds and platforms, unlike `DefaultHasher`.
+pub fn content_hash(content: &[u8]) -> String {
+    let hash = content.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("fnv1a64:{:016x}", hash)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_writable_probes_directories() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(is_writable(dir.path()));
+        assert!(is_writable(&dir.path().join("missing/config.json")));
+        // The probe file is cleaned up
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+
+        // A file where a directory should be can't be written into
+        let file = dir.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        assert!(!is_writable(&file.join("config.json")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_symlinks_follows_dangling_links() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir(root.join("dotfiles")).unwrap();
+        std::os::unix::fs::symlink("dotfiles", root.join("linked")).unwrap();
+        std::os::unix::fs::symlink("linked/config.json", root.join("config.json")).unwrap();
+
+        let target = root.join("dotfiles/config.json");
+        assert_eq!(resolve_symlinks(&root.join("config.json")), target);
+        assert_eq!(
+            resolve_symlinks(&root.join("linked/new.json")),
+            root.join("dotfiles/new.json")
+        );
+        assert_eq!(resolve_symlinks(&target), target);
+    }
+
+    #[test]
+    fn test_config_override_precedence() {
+        let flag = Some(PathBuf::from("/flag/config.json"));
+        let setting = Some(PathBuf::from("/env/config.json"));
+        assert_eq!(config_override_from(flag.clone(), setting.clone()), flag);
+        assert_eq!(config_override_from(None, setting.clone()), setting);
+        assert_eq!(config_override_from(None, Some(PathBuf::new())), None);
+        assert_eq!(config_override_from(None, None), None);
+
+        let relative = config_override_from(Some(PathBuf::from("portable.json")), None).unwrap();
+        assert!(relative.is_absolute() && relative.ends_with("portable.json"));
+    }
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(content_hash(b""), "fnv1a64:cbf29ce484222325");
+        assert_ne!(content_hash(b"servers: []"), content_hash(b"servers: [] "));
+    }
+
+    #[test]
+    fn test_prompt_cancellation_detection() {
+        assert!(is_prompt_cancelled(&PromptCancelled.into()));
+        assert!(is_prompt_cancelled(
+            &inquire::InquireError::OperationInterrupted.into()
+        ));
+        assert!(is_prompt_cancelled(
+            &anyhow::Error::from(inquire::InquireError::OperationCanceled).context("Adding server")
+        ));
+        assert!(!is_prompt_cancelled(&anyhow::anyhow!("Server not found")));
+    }
+
+    #[test]
+    fn test_not_interactive_is_not_a_cancellation() {
+        let err = anyhow::Error::from(NotInteractive("Confirmation required".to_string()));
+        assert!(is_not_interactive(&err));
+        assert!(!is_prompt_cancelled(&err));
+        assert_eq!(
+            err.to_string(),
+            "Confirmation required (stdin is not a terminal)"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(sanitize_filename("feature/new-stuff"), "feature_new-stuff");
+        assert_eq!(sanitize_filename("backup:2024"), "backup_2024");
+        assert_eq!(sanitize_filename("normal-name"), "normal-name");
+    }
+
     #[test]
     fn test_config_paths() {
         // Test that we can get config paths without errors
         assert!(get_config_dir().is_ok());
         assert!(get_claude_config_path().is_ok());
-        assert!(get_backup_dir().is_ok());
+    }
+
+    #[test]
+    fn test_case_colliding_env_keys() {
+        let mut env = HashMap::new();
+        env.insert("API_KEY".to_string(), "one".to_string());
+        env.insert("Api_Key".to_string(), "two".to_string());
+        env.insert("PORT".to_string(), "8080".to_string());
+
+        let collisions = find_case_colliding_keys(&env);
+        assert_eq!(
+            collisions,
+            vec![vec!["API_KEY".to_string(), "Api_Key".to_string()]]
+        );
+
+        merge_case_variant_keys(&mut env, &collisions[0], "Api_Key");
+        assert_eq!(env.len(), 2);
+        assert_eq!(env.get("Api_Key"), Some(&"two".to_string()));
+        assert!(find_case_colliding_keys(&env).is_empty());
+    }
+
+    #[test]
+    fn test_find_case_variant_keys() {
+        let mut env = HashMap::new();
+        env.insert("api_key".to_string(), "one".to_string());
+        env.insert("Api_Key".to_string(), "two".to_string());
+
+        assert_eq!(
+            find_case_variant_keys(&env, "API_KEY"),
+            ["Api_Key", "api_key"]
+        );
+        assert!(find_case_variant_keys(&env, "Api_Key").is_empty());
+        assert!(find_case_variant_keys(&env, "TOKEN").is_empty());
+    }
+
+    #[test]
+    fn test_case_folding_agrees_on_non_ascii_keys() {
+        let mut env = HashMap::new();
+        env.insert("ÉCOLE_ID".to_string(), "one".to_string());
+        env.insert("école_id".to_string(), "two".to_string());
+
+        assert_eq!(find_case_colliding_keys(&env).len(), 1);
+        env.remove("ÉCOLE_ID");
+        assert_eq!(find_case_variant_keys(&env, "ÉCOLE_ID"), ["école_id"]);
+    }
+
+    #[test]
+    fn test_mask_sensitive_header_value() {
+        assert_eq!(
+            mask_sensitive_header_value("Authorization", "Bearer sk-1234567890"),
+            "Bea**************890"
+        );
+        assert_eq!(
+            mask_sensitive_header_value("cookie", "session=abc"),
+            "ses*****abc"
+        );
+        assert_eq!(
+            mask_sensitive_header_value("X-Api-Key", "short"),
+            "********"
+        );
+        assert_eq!(
+            mask_sensitive_header_value("Content-Type", "application/json"),
+            "application/json"
+        );
     }
 
     #[test]
@@ -185,6 +780,11 @@ mod tests {
 
         // Test short sensitive values
         assert_eq!(mask_sensitive_env_value("SECRET", "abc"), "********");
+        // Multibyte values are cut on character boundaries
+        assert_eq!(
+            mask_sensitive_env_value("API_KEY", "ключ-секрет"),
+            "клю*****рет"
+        );
 
         // Test API_KEY pattern (13 chars: 3 + 7 + 3)
         assert_eq!(
@@ -0,0 +1,304 @@
+use anyhow::{anyhow, Result};
+
+/// One `KEY=VALUE` entry being read
+#[derive(Default)]
+struct Entry {
+    key: Option<String>,
+    text: String,
+    /// Length of `text` up to the last quoted or escaped character, which
+    /// trimming must not remove
+    literal_end: usize,
+    quoted: bool,
+    /// Byte offset of the entry in the input
+    start: usize,
+}
+
+impl Entry {
+    fn push_plain(&mut self, c: char) {
+        // Unquoted leading whitespace is dropped, as the old parser trimmed it
+        if self.text.is_empty() && !self.quoted && c.is_whitespace() {
+            return;
+        }
+        self.text.push(c);
+    }
+
+    fn push_literal(&mut self, c: char) {
+        self.text.push(c);
+        self.mark_literal();
+    }
+
+    fn mark_literal(&mut self) {
+        self.quoted = true;
+        self.literal_end = self.text.len();
+    }
+
+    /// The text read so far, without unquoted trailing whitespace
+    fn take(&mut self) -> String {
+        let kept = self.literal_end.max(self.text.trim_end().len());
+        let mut text = std::mem::take(&mut self.text);
+        text.truncate(kept);
+        self.literal_end = 0;
+        self.quoted = false;
+        text
+    }
+
+    fn is_blank(&self) -> bool {
+        self.key.is_none() && self.text.is_empty() && !self.quoted
+    }
+}
+
+/// 1-based character column of a byte offset, for error messages
+fn column(input: &str, offset: usize) -> usize {
+    input[..offset].chars().count() + 1
+}
+
+/// Parse a comma-separated list of `KEY=VALUE` assignments, e.g. `a=1,b=2`
+///
+/// Values may be quoted with `"` or `'` to hold commas, equals signs or
+/// surrounding spaces, and outside single quotes `\` escapes the next
+/// character. The key ends at the first unquoted `=`. A trailing comma is
+/// allowed; other empty entries are an error.
+pub fn parse_assignments(input: &str) -> Result<Vec<(String, String)>> {
+    let mut assignments = Vec::new();
+    let mut entry = Entry::default();
+    let mut quote: Option<(char, usize)> = None;
+    let mut chars = input.char_indices();
+
+    let finish = |entry: &mut Entry, end: usize| -> Result<(String, String)> {
+        let raw = input[entry.start..end].trim();
+        if entry.is_blank() {
+            return Err(anyhow!(
+                "Empty entry at column {} in '{}'. Remove the extra comma",
+                column(input, end),
+                input
+            ));
+        }
+        let value = entry.take();
+        match entry.key.take() {
+            Some(key) if key.is_empty() => {
+                Err(anyhow!("Missing variable name before '=' in '{}'", raw))
+            }
+            Some(key) => Ok((key, value)),
+            None => Err(anyhow!(
+                "Invalid variable format: '{}'. Use KEY=VALUE format",
+                raw
+            )),
+        }
+    };
+
+    while let Some((offset, c)) = chars.next() {
+        match (quote, c) {
+            (Some((q, _)), c) if c == q => quote = None,
+            (Some(('"', _)) | None, '\\') => match chars.next() {
+                Some((_, escaped)) => entry.push_literal(escaped),
+                None => {
+                    return Err(anyhow!(
+                        "Nothing to escape after '\\' at column {} in '{}'",
+                        column(input, offset),
+                        input
+                    ))
+                }
+            },
+            (Some(_), c) => entry.push_literal(c),
+            (None, '"' | '\'') => {
+                quote = Some((c, offset));
+                entry.mark_literal();
+            }
+            (None, ',') => {
+                assignments.push(finish(&mut entry, offset)?);
+                entry.start = offset + 1;
+            }
+            (None, '=') if entry.key.is_none() => entry.key = Some(entry.take()),
+            (None, c) => entry.push_plain(c),
+        }
+    }
+
+    if let Some((q, offset)) = quote {
+        return Err(anyhow!(
+            "Unmatched {} quote at column {} in '{}'",
+            q,
+            column(input, offset),
+            input
+        ));
+    }
+    // A single trailing comma is harmless
+    if !entry.is_blank() || assignments.is_empty() {
+        assignments.push(finish(&mut entry, input.len())?);
+    }
+    Ok(assignments)
+}
+
+/// Parse one `KEY=VALUE` assignment, as given to a repeatable flag
+///
+/// Everything after the first `=` is the value, exactly as the shell passed it.
+pub fn parse_assignment(input: &str) -> Result<(String, String)> {
+    match input.split_once('=') {
+        Some((key, _)) if key.trim().is_empty() => {
+            Err(anyhow!("Missing variable name before '=' in '{}'", input))
+        }
+        Some((key, value)) => Ok((key.trim().to_string(), value.to_string())),
+        None => Err(anyhow!(
+            "Invalid variable format: '{}'. Use KEY=VALUE format",
+            input
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Vec<(String, String)> {
+        parse_assignments(input).unwrap_or_else(|e| panic!("{:?}: {}", input, e))
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn error(input: &str) -> String {
+        parse_assignments(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_simple_form_still_works() {
+        assert_eq!(parse("a=1"), pairs(&[("a", "1")]));
+        assert_eq!(parse("a=1,b=2"), pairs(&[("a", "1"), ("b", "2")]));
+        assert_eq!(parse(" a = 1 , b=2 "), pairs(&[("a", "1"), ("b", "2")]));
+        assert_eq!(
+            parse("path=/home/user/docs"),
+            pairs(&[("path", "/home/user/docs")])
+        );
+    }
+
+    #[test]
+    fn test_equals_signs_after_the_first_belong_to_the_value() {
+        assert_eq!(
+            parse("url=http://h/?a=1&b=2"),
+            pairs(&[("url", "http://h/?a=1&b=2")])
+        );
+        assert_eq!(parse("a==b"), pairs(&[("a", "=b")]));
+    }
+
+    #[test]
+    fn test_quoted_values() {
+        assert_eq!(
+            parse(r#"dsn="postgres://u:p@h/db?a=1,b=2",mode=ro"#),
+            pairs(&[("dsn", "postgres://u:p@h/db?a=1,b=2"), ("mode", "ro")])
+        );
+        assert_eq!(parse("list='x,y,z'"), pairs(&[("list", "x,y,z")]));
+        assert_eq!(
+            parse(r#"pad="  spaced  ""#),
+            pairs(&[("pad", "  spaced  ")])
+        );
+        assert_eq!(parse(r#"q='say "hi"'"#), pairs(&[("q", r#"say "hi""#)]));
+        assert_eq!(parse(r#"q="it's""#), pairs(&[("q", "it's")]));
+        // Quotes can cover part of a value
+        assert_eq!(parse(r#"a=pre" , "post"#), pairs(&[("a", "pre , post")]));
+        assert_eq!(parse(r#""odd key"=1"#), pairs(&[("odd key", "1")]));
+    }
+
+    #[test]
+    fn test_backslash_escapes() {
+        assert_eq!(parse(r"list=x\,y"), pairs(&[("list", "x,y")]));
+        assert_eq!(parse(r"a\=b=c"), pairs(&[("a=b", "c")]));
+        assert_eq!(parse(r"path=C:\\temp"), pairs(&[("path", r"C:\temp")]));
+        assert_eq!(parse(r#"q="a\"b""#), pairs(&[("q", r#"a"b"#)]));
+        // Single quotes keep backslashes as they are
+        assert_eq!(parse(r"path='C:\temp'"), pairs(&[("path", r"C:\temp")]));
+        assert_eq!(parse(r"a=x\ "), pairs(&[("a", "x ")]));
+    }
+
+    #[test]
+    fn test_empty_values() {
+        assert_eq!(parse("a="), pairs(&[("a", "")]));
+        assert_eq!(parse("a=,b=2"), pairs(&[("a", ""), ("b", "2")]));
+        assert_eq!(parse(r#"a="""#), pairs(&[("a", "")]));
+        assert_eq!(parse("a=''"), pairs(&[("a", "")]));
+    }
+
+    #[test]
+    fn test_trailing_comma() {
+        assert_eq!(parse("a=1,"), pairs(&[("a", "1")]));
+        assert_eq!(parse("a=1, "), pairs(&[("a", "1")]));
+        assert!(error("a=1,,").starts_with("Empty entry at column 5"));
+    }
+
+    #[test]
+    fn test_empty_entries_are_errors() {
+        assert_eq!(
+            error("a=1,,b=2"),
+            "Empty entry at column 5 in 'a=1,,b=2'. Remove the extra comma"
+        );
+        assert_eq!(
+            error(",a=1"),
+            "Empty entry at column 1 in ',a=1'. Remove the extra comma"
+        );
+        assert_eq!(
+            error(","),
+            "Empty entry at column 1 in ','. Remove the extra comma"
+        );
+        assert!(error("").starts_with("Empty entry at column 1"));
+    }
+
+    #[test]
+    fn test_unmatched_quotes() {
+        assert_eq!(
+            error(r#"a=1,dsn="postgres://h"#),
+            r#"Unmatched " quote at column 9 in 'a=1,dsn="postgres://h'"#
+        );
+        assert_eq!(error("a='x"), "Unmatched ' quote at column 3 in 'a='x'");
+        assert!(error(r#"a="x\""#).starts_with("Unmatched \" quote at column 3"));
+    }
+
+    #[test]
+    fn test_dangling_backslash() {
+        assert_eq!(
+            error(r"a=x\"),
+            r"Nothing to escape after '\' at column 4 in 'a=x\'"
+        );
+    }
+
+    #[test]
+    fn test_missing_key_or_equals() {
+        assert_eq!(
+            error("a=1,oops"),
+            "Invalid variable format: 'oops'. Use KEY=VALUE format"
+        );
+        assert_eq!(error("=1"), "Missing variable name before '=' in '=1'");
+        assert_eq!(error(" =1"), "Missing variable name before '=' in '=1'");
+        // A quoted '=' doesn't end the key
+        assert!(error(r#"a"=""#).starts_with("Invalid variable format"));
+        assert_eq!(
+            error(r#"a"="b"#),
+            r#"Invalid variable format: 'a"="b'. Use KEY=VALUE format"#
+        );
+    }
+
+    #[test]
+    fn test_parse_assignment() {
+        assert_eq!(
+            parse_assignment("dsn=postgres://h/db?a=1,b=2").unwrap(),
+            ("dsn".to_string(), "postgres://h/db?a=1,b=2".to_string())
+        );
+        assert_eq!(
+            parse_assignment("note= keep spaces ").unwrap(),
+            ("note".to_string(), " keep spaces ".to_string())
+        );
+        assert_eq!(
+            parse_assignment("empty=").unwrap(),
+            ("empty".to_string(), String::new())
+        );
+        assert!(parse_assignment("novalue")
+            .unwrap_err()
+            .to_string()
+            .starts_with("Invalid variable format: 'novalue'"));
+        assert!(parse_assignment("=x")
+            .unwrap_err()
+            .to_string()
+            .starts_with("Missing variable name"));
+    }
+}
@@ -0,0 +1,182 @@
+use anyhow::{Context, Result};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// The steps of an atomic write that touch the disk
+///
+/// Split out so tests can make one of them fail part-way.
+pub trait WriteSteps {
+    /// Write `contents` to the temporary file and flush it to disk
+    fn write(&self, file: &mut File, contents: &[u8]) -> io::Result<()> {
+        file.write_all(contents)?;
+        file.sync_all()
+    }
+
+    /// Move the finished temporary file over the target
+    fn replace(&self, from: &Path, to: &Path) -> io::Result<()> {
+        replace_file(from, to)
+    }
+}
+
+/// The real filesystem
+pub struct Disk;
+
+impl WriteSteps for Disk {}
+
+/// Replace `path` with `contents` so that readers see the old file or the new
+/// one, never a partial write
+///
+/// The data goes to a temporary file in the same directory, is synced, and is
+/// then renamed over `path`. An existing file's permissions are kept. If
+/// `path` is a symlink, the file it points at is replaced and the link kept.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    write_atomic_with(&Disk, path, contents)
+}
+
+pub fn write_atomic_with(steps: &dyn WriteSteps, path: &Path, contents: &[u8]) -> Result<()> {
+    write_atomic_at(steps, &super::resolve_symlinks(path), contents)
+}
+
+/// Like [`write_atomic_with`], but a symlink at `path` is itself replaced,
+/// for callers that have already chosen whether to follow it
+pub fn write_atomic_at(steps: &dyn WriteSteps, path: &Path, contents: &[u8]) -> Result<()> {
+    let temp = temp_path(path);
+    let result = write_temp(steps, &temp, path, contents)
+        .and_then(|()| steps.replace(&temp, path))
+        .with_context(|| format!("Failed to write {}", path.display()));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    } else {
+        sync_parent(path);
+    }
+    result
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()))
+}
+
+fn write_temp(steps: &dyn WriteSteps, temp: &Path, path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(temp)?;
+    if let Ok(existing) = fs::metadata(path) {
+        file.set_permissions(existing.permissions())?;
+    }
+    steps.write(&mut file, contents)
+}
+
+/// Make the rename itself durable; not every platform can sync a directory
+fn sync_parent(path: &Path) {
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() {
+        if let Ok(dir) = File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+}
+
+#[cfg(not(windows))]
+fn replace_file(from: &Path, to: &Path) -> io::Result<()> {
+    fs::rename(from, to)
+}
+
+/// Windows refuses to replace a file another process briefly holds open
+/// (Claude Desktop, antivirus, indexers), so retry for a moment
+#[cfg(windows)]
+fn replace_file(from: &Path, to: &Path) -> io::Result<()> {
+    const ATTEMPTS: u32 = 10;
+    let mut attempt = 1;
+    loop {
+        match fs::rename(from, to) {
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied && attempt < ATTEMPTS => {
+                std::thread::sleep(std::time::Duration::from_millis(50 * u64::from(attempt)));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Writes half the data, then fails as if the disk filled up
+    struct DiskFull;
+
+    impl WriteSteps for DiskFull {
+        fn write(&self, file: &mut File, contents: &[u8]) -> io::Result<()> {
+            file.write_all(&contents[..contents.len() / 2])?;
+            Err(io::Error::other("No space left on device"))
+        }
+    }
+
+    struct RenameFails;
+
+    impl WriteSteps for RenameFails {
+        fn replace(&self, _from: &Path, _to: &Path) -> io::Result<()> {
+            Err(io::Error::other("rename failed"))
+        }
+    }
+
+    fn setup() -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(&path, "original").unwrap();
+        (dir, path)
+    }
+
+    fn leftovers(dir: &TempDir) -> Vec<String> {
+        fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name != "config.json")
+            .collect()
+    }
+
+    #[test]
+    fn test_write_atomic_replaces_the_file() {
+        let (dir, path) = setup();
+        write_atomic(&path, b"updated").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "updated");
+        assert!(leftovers(&dir).is_empty());
+
+        let new = dir.path().join("new.json");
+        write_atomic(&new, b"created").unwrap();
+        assert_eq!(fs::read_to_string(&new).unwrap(), "created");
+    }
+
+    #[test]
+    fn test_failed_writes_leave_the_original_untouched() {
+        for steps in [&DiskFull as &dyn WriteSteps, &RenameFails] {
+            let (dir, path) = setup();
+            let err = write_atomic_with(steps, &path, b"a much longer replacement").unwrap_err();
+            assert!(err.to_string().contains("Failed to write"), "{}", err);
+            assert_eq!(fs::read_to_string(&path).unwrap(), "original");
+            assert!(leftovers(&dir).is_empty(), "{:?}", leftovers(&dir));
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (_dir, path) = setup();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+        write_atomic(&path, b"updated").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
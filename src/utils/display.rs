@@ -0,0 +1,315 @@
+use crate::deletions::format_size;
+use colored::Colorize;
+use std::collections::HashMap;
+
+/// Displayed values longer than this many characters are cut short by default
+pub const DEFAULT_MAX_VALUE_CHARS: usize = 120;
+
+/// Values shorter than this are always shown, even if they look like base64
+const MIN_BLOB_CHARS: usize = 64;
+
+/// Longest value to display, set with `MCP_FORGE_MAX_VALUE_CHARS`
+pub fn max_value_chars() -> usize {
+    max_value_chars_from(std::env::var("MCP_FORGE_MAX_VALUE_CHARS").ok().as_deref())
+}
+
+fn max_value_chars_from(setting: Option<&str>) -> usize {
+    match setting.map(|value| value.trim().parse::<usize>()) {
+        None => DEFAULT_MAX_VALUE_CHARS,
+        Some(Ok(max)) if max > 0 => max,
+        Some(_) => {
+            eprintln!(
+                "⚠ MCP_FORGE_MAX_VALUE_CHARS must be a positive number; using {}",
+                DEFAULT_MAX_VALUE_CHARS
+            );
+            DEFAULT_MAX_VALUE_CHARS
+        }
+    }
+}
+
+/// Make an environment value fit on screen, masking it first if it's sensitive
+///
+/// Binary or base64 values are summarized and long values truncated, unless
+/// `full` is set.
+pub fn display_env_value(key: &str, value: &str, full: bool) -> String {
+    if full {
+        return super::mask_sensitive_env_value(key, value);
+    }
+    if let Some(summary) = summarize_blob(value) {
+        return summary;
+    }
+    truncate_value(
+        &super::mask_sensitive_env_value(key, value),
+        max_value_chars(),
+    )
+}
+
+/// Cut a value to at most `max` characters, saying how many were left out
+pub fn truncate_value(value: &str, max: usize) -> String {
+    let count = value.chars().count();
+    if count <= max {
+        return value.to_string();
+    }
+    let kept: String = value.chars().take(max).collect();
+    format!("{}… (+{} more chars)", kept, count - max)
+}
+
+/// Summarize a value that looks like binary or base64 data, e.g. `<base64, 4.2 KB>`
+pub fn summarize_blob(value: &str) -> Option<String> {
+    let kind = if looks_binary(value) {
+        "binary"
+    } else if looks_like_base64(value) {
+        "base64"
+    } else {
+        return None;
+    };
+    Some(format!("<{}, {}>", kind, format_size(value.len() as u64)))
+}
+
+/// Control characters other than line breaks and tabs, or replacement
+/// characters left by lossy decoding
+fn looks_binary(value: &str) -> bool {
+    value
+        .chars()
+        .any(|c| c == '\u{FFFD}' || (c.is_control() && !matches!(c, '\n' | '\r' | '\t')))
+}
+
+/// A long run of base64 (standard or URL-safe) characters, optionally wrapped
+/// in PEM armour lines such as `-----BEGIN CERTIFICATE-----`
+fn looks_like_base64(value: &str) -> bool {
+    let body: String = value
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+    let body = body.trim_end_matches('=');
+    body.len() >= MIN_BLOB_CHARS
+        && body
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '-' | '_'))
+        // Mixed case and digits, so long identifiers and hex digests still show
+        && body.chars().any(|c| c.is_ascii_uppercase())
+        && body.chars().any(|c| c.is_ascii_lowercase())
+        && body.chars().any(|c| c.is_ascii_digit())
+}
+
+/// Lines describing how an environment map changed, for previews and diffs
+///
+/// Keys are sorted, values are masked and shortened like `list` shows them,
+/// the old → new columns line up, and unchanged keys collapse into a count.
+/// Each line starts with `+`, `-` or `~`, except the final `(+N unchanged)`.
+pub fn render_env_diff(
+    old: &HashMap<String, String>,
+    new: &HashMap<String, String>,
+) -> Vec<String> {
+    let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let show = |key: &str, value: &str| display_env_value(key, value, false);
+    let mut changes = Vec::new();
+    let mut unchanged = 0;
+    for key in keys {
+        match (old.get(key), new.get(key)) {
+            (Some(before), Some(after)) if before == after => unchanged += 1,
+            (before, after) => changes.push((
+                key,
+                before.map(|v| show(key, v)),
+                after.map(|v| show(key, v)),
+            )),
+        }
+    }
+
+    let key_width = changes
+        .iter()
+        .map(|(k, _, _)| k.chars().count())
+        .max()
+        .unwrap_or(0);
+    let old_width = changes
+        .iter()
+        .filter(|(_, before, after)| before.is_some() && after.is_some())
+        .filter_map(|(_, before, _)| before.as_ref().map(|v| v.chars().count()))
+        .max()
+        .unwrap_or(0);
+
+    let mut lines: Vec<String> = changes
+        .into_iter()
+        .map(|(key, before, after)| match (before, after) {
+            (Some(before), Some(after)) => format!(
+                "~ {:<kw$}  {:<ow$} → {}",
+                key,
+                before,
+                after,
+                kw = key_width,
+                ow = old_width
+            ),
+            (Some(before), None) => format!("- {:<kw$}  {}", key, before, kw = key_width),
+            (None, after) => format!(
+                "+ {:<kw$}  {}",
+                key,
+                after.unwrap_or_default(),
+                kw = key_width
+            ),
+        })
+        .map(|line| line.trim_end().to_string())
+        .collect();
+    if unchanged > 0 && !lines.is_empty() {
+        lines.push(format!("(+{} unchanged)", unchanged));
+    }
+    lines
+}
+
+/// Print an environment diff from [`render_env_diff`], colored by change
+pub fn print_env_diff(indent: &str, old: &HashMap<String, String>, new: &HashMap<String, String>) {
+    for line in render_env_diff(old, new) {
+        let line = match line.chars().next() {
+            Some('+') => line.green(),
+            Some('-') => line.red(),
+            Some('~') => line.yellow(),
+            _ => line.dimmed(),
+        };
+        println!("{}{}", indent, line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CERT: &str = "-----BEGIN CERTIFICATE-----\n\
+        MIIBszCCAVmgAwIBAgIUe3bQ8H2x9Yk1m0dJZ5Gm3nq2LzswCgYIKoZIzj0EAwIw\n\
+        DzENMAsGA1UEAwwEdGVzdDAeFw0yNTAxMDEwMDAwMDBaFw0yNjAxMDEwMDAwMDBa\n\
+        -----END CERTIFICATE-----\n";
+
+    #[test]
+    fn test_truncate_value() {
+        assert_eq!(truncate_value("short", 10), "short");
+        assert_eq!(truncate_value("exactly10!", 10), "exactly10!");
+        assert_eq!(
+            truncate_value("abcdefghijkl", 10),
+            "abcdefghij… (+2 more chars)"
+        );
+        assert_eq!(truncate_value("", 10), "");
+    }
+
+    #[test]
+    fn test_truncate_value_counts_characters_not_bytes() {
+        // Cutting by bytes would split these multibyte characters
+        assert_eq!(truncate_value("ééééé", 3), "ééé… (+2 more chars)");
+        assert_eq!(
+            truncate_value("日本語テキスト", 3),
+            "日本語… (+4 more chars)"
+        );
+        assert_eq!(truncate_value("a🦀b🦀c", 2), "a🦀… (+3 more chars)");
+        assert_eq!(truncate_value("🦀🦀", 2), "🦀🦀");
+    }
+
+    #[test]
+    fn test_summarize_blob() {
+        assert_eq!(summarize_blob(CERT).as_deref(), Some("<base64, 184 B>"));
+        let key =
+            "QUJDREVGR0hJSktMTU5PUFFSU1RVVldYWVphYmNkZWZnaGlqa2xtbm9wcXJzdHV2d3h5ejAxMjM0NTY3ODk=";
+        assert_eq!(summarize_blob(key).as_deref(), Some("<base64, 84 B>"));
+        assert_eq!(
+            summarize_blob(&"Zm9vYmFy".repeat(600)).as_deref(),
+            Some("<base64, 4.7 KB>")
+        );
+        assert_eq!(
+            summarize_blob("\u{0}\u{1}PNG").as_deref(),
+            Some("<binary, 5 B>")
+        );
+        assert_eq!(
+            summarize_blob("bad\u{FFFD}utf8").as_deref(),
+            Some("<binary, 10 B>")
+        );
+    }
+
+    #[test]
+    fn test_ordinary_values_are_not_blobs() {
+        for value in [
+            "localhost",
+            "postgres://user@db.internal:5432/app?sslmode=require",
+            r#"{"retries": 3, "endpoints": ["a", "b"]}"#,
+            "line one\nline two\twith tab",
+            // Short base64 is more likely a token worth seeing
+            "dGVzdA==",
+            // Hex digests and long identifiers have no mixed case
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+            "a_very_long_lowercase_identifier_that_goes_on_and_on_and_on_forever",
+        ] {
+            assert_eq!(summarize_blob(value), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_display_env_value() {
+        let long = "x".repeat(130);
+        assert_eq!(
+            display_env_value("CONFIG", &long, false),
+            format!("{}… (+10 more chars)", "x".repeat(120))
+        );
+        assert_eq!(display_env_value("CONFIG", &long, true), long);
+        assert_eq!(display_env_value("CERT", CERT, false), "<base64, 184 B>");
+        assert_eq!(display_env_value("CERT", CERT, true), CERT);
+        // Masking still applies, and a long secret's asterisks are cut short too
+        let secret = format!("sk-{}", "a".repeat(200));
+        let shown = display_env_value("API_KEY", &secret, false);
+        assert!(shown.starts_with("sk-****"));
+        assert!(shown.ends_with("(+83 more chars)"));
+        assert_eq!(display_env_value("PORT", "8080", false), "8080");
+    }
+
+    fn env(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_render_env_diff() {
+        let old = env(&[
+            ("PORT", "8080"),
+            ("LOG_LEVEL", "info"),
+            ("API_KEY", "sk-live-1234567890"),
+            ("HOST", "localhost"),
+            ("REGION", "eu"),
+        ]);
+        let new = env(&[
+            ("PORT", "9090"),
+            ("LOG_LEVEL", "debug"),
+            ("API_KEY", "sk-live-0987654321"),
+            ("HOST", "localhost"),
+            ("DEBUG", "1"),
+        ]);
+        assert_eq!(
+            render_env_diff(&old, &new).join("\n"),
+            "\
+~ API_KEY    sk-************890 → sk-************321
++ DEBUG      1
+~ LOG_LEVEL  info               → debug
+~ PORT       8080               → 9090
+- REGION     eu
+(+1 unchanged)"
+        );
+    }
+
+    #[test]
+    fn test_render_env_diff_without_changes() {
+        let same = env(&[("PORT", "8080")]);
+        assert!(render_env_diff(&same, &same).is_empty());
+        assert!(render_env_diff(&HashMap::new(), &HashMap::new()).is_empty());
+        assert_eq!(
+            render_env_diff(&HashMap::new(), &same),
+            vec!["+ PORT  8080".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_max_value_chars_from_setting() {
+        assert_eq!(max_value_chars_from(None), 120);
+        assert_eq!(max_value_chars_from(Some("40")), 40);
+        assert_eq!(max_value_chars_from(Some("0")), DEFAULT_MAX_VALUE_CHARS);
+        assert_eq!(max_value_chars_from(Some("wide")), DEFAULT_MAX_VALUE_CHARS);
+    }
+}
@@ -0,0 +1,117 @@
+use anyhow::{anyhow, Result};
+use chrono::Duration;
+
+/// Units accepted after a number, largest first, with their length in seconds
+const UNITS: &[(char, i64)] = &[
+    ('w', 7 * 24 * 3600),
+    ('d', 24 * 3600),
+    ('h', 3600),
+    ('m', 60),
+    ('s', 1),
+];
+
+/// Parse a duration such as `30d`, `2w`, `12h`, `45m` or `90s`
+///
+/// A bare number is a number of days, so `7` means a week.
+pub fn parse_duration(value: &str) -> Result<Duration> {
+    let value = value.trim().to_lowercase();
+    let invalid = || {
+        anyhow!(
+            "Invalid duration '{}'. Use a number with a unit, like 30d, 2w, 12h, 45m or 90s",
+            value
+        )
+    };
+
+    let (number, unit_seconds) = match UNITS.iter().find(|(unit, _)| value.ends_with(*unit)) {
+        Some((_, seconds)) => (&value[..value.len() - 1], *seconds),
+        None => (value.as_str(), 24 * 3600),
+    };
+    // `u32` rejects signs and whitespace, and keeps the result within range
+    if !number.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let count: u32 = number.parse().map_err(|_| invalid())?;
+    Ok(Duration::seconds(i64::from(count) * unit_seconds))
+}
+
+/// Render a duration in the largest unit that represents it exactly, e.g. `2w` or `90s`
+///
+/// The result parses back to the same duration; sub-second parts are dropped.
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.num_seconds();
+    if seconds == 0 {
+        return "0s".to_string();
+    }
+    let (unit, length) = UNITS
+        .iter()
+        .find(|(_, length)| seconds % length == 0)
+        .copied()
+        .unwrap_or(('s', 1));
+    format!("{}{}", seconds / length, unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30d").unwrap(), Duration::days(30));
+        assert_eq!(parse_duration("2w").unwrap(), Duration::weeks(2));
+        assert_eq!(parse_duration("12h").unwrap(), Duration::hours(12));
+        assert_eq!(parse_duration("45m").unwrap(), Duration::minutes(45));
+        assert_eq!(parse_duration("90s").unwrap(), Duration::seconds(90));
+        assert_eq!(parse_duration(" 7 ").unwrap(), Duration::days(7));
+        assert_eq!(parse_duration("24H").unwrap(), Duration::hours(24));
+    }
+
+    #[test]
+    fn test_parse_duration_rejects_garbage() {
+        for garbage in [
+            "",
+            "d",
+            "3 fortnights",
+            "3 d",
+            "-5d",
+            "+5d",
+            "1.5h",
+            "10y",
+            "5dd",
+            "99999999999d",
+        ] {
+            let err = parse_duration(garbage).unwrap_err();
+            assert!(
+                err.to_string().starts_with("Invalid duration"),
+                "{:?} gave {}",
+                garbage,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_durations_round_trip() {
+        for count in [0i64, 1, 2, 6, 7, 13, 14, 59, 60, 61, 90, 365, 3600, 86_400] {
+            for (unit, length) in UNITS {
+                let duration = Duration::seconds(count * length);
+                let rendered = format_duration(duration);
+                assert_eq!(parse_duration(&rendered).unwrap(), duration, "{}", rendered);
+
+                let written = format!("{}{}", count, unit);
+                assert_eq!(
+                    format_duration(parse_duration(&written).unwrap()),
+                    format_duration(duration)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_duration_picks_largest_exact_unit() {
+        assert_eq!(format_duration(Duration::days(14)), "2w");
+        assert_eq!(format_duration(Duration::days(30)), "30d");
+        assert_eq!(format_duration(Duration::hours(36)), "36h");
+        assert_eq!(format_duration(Duration::seconds(90)), "90s");
+        assert_eq!(format_duration(Duration::zero()), "0s");
+    }
+}
@@ -0,0 +1,168 @@
+use anyhow::{anyhow, Result};
+use std::sync::OnceLock;
+
+/// Operating system names as templates list them in `platforms`
+pub const OS_NAMES: [&str; 3] = ["linux", "macos", "windows"];
+
+/// The operating system and CPU architecture mcp-forge runs on, named the
+/// way templates and their `{{os}}`/`{{arch}}` helpers name them
+#[derive(Debug, Clone, PartialEq)]
+pub struct Platform {
+    /// `linux`, `macos`, `windows` or `unknown`
+    pub os: String,
+    /// `x64`, `arm64` or `unknown`
+    pub arch: String,
+}
+
+impl Platform {
+    /// The platform this binary was built for
+    pub fn native() -> Self {
+        Self {
+            os: native_os().to_string(),
+            arch: native_arch().to_string(),
+        }
+    }
+
+    /// Parse `os` or `os/arch`, e.g. `windows` or `macos/arm64`
+    ///
+    /// Rust's own names (`x86_64`, `aarch64`) are accepted for the
+    /// architecture; a missing one is taken from the native platform.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim().to_lowercase();
+        let (os, arch) = match spec.split_once('/') {
+            Some((os, arch)) => (os, Some(arch)),
+            None => (spec.as_str(), None),
+        };
+        if !OS_NAMES.contains(&os) {
+            return Err(anyhow!(
+                "unknown operating system '{}'; expected one of {}",
+                os,
+                OS_NAMES.join(", ")
+            ));
+        }
+        let arch = match arch {
+            None => native_arch(),
+            Some("x64" | "x86_64") => "x64",
+            Some("arm64" | "aarch64") => "arm64",
+            Some(other) => {
+                return Err(anyhow!(
+                    "unknown architecture '{}'; expected x64 or arm64",
+                    other
+                ))
+            }
+        };
+        Ok(Self {
+            os: os.to_string(),
+            arch: arch.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.os, self.arch)
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    static TEST_OVERRIDE: std::cell::RefCell<Option<Platform>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Pretend to run on `platform` for the rest of the current test
+#[cfg(test)]
+pub fn set_override(platform: Option<Platform>) {
+    TEST_OVERRIDE.with(|cell| *cell.borrow_mut() = platform);
+}
+
+/// The platform to behave as: `MCP_FORGE_PLATFORM_OVERRIDE` if set, otherwise
+/// the native one
+///
+/// Template helpers, platform gating, search and diagnostics all ask here, so
+/// they agree with each other and can be exercised for any platform.
+pub fn current() -> Platform {
+    #[cfg(test)]
+    if let Some(platform) = TEST_OVERRIDE.with(|cell| cell.borrow().clone()) {
+        return platform;
+    }
+    // Read once, so a bad value is only warned about once
+    static FROM_ENV: OnceLock<Platform> = OnceLock::new();
+    FROM_ENV
+        .get_or_init(|| current_from(std::env::var("MCP_FORGE_PLATFORM_OVERRIDE").ok().as_deref()))
+        .clone()
+}
+
+fn current_from(setting: Option<&str>) -> Platform {
+    match setting.filter(|s| !s.trim().is_empty()) {
+        None => Platform::native(),
+        Some(spec) => Platform::parse(spec).unwrap_or_else(|e| {
+            let native = Platform::native();
+            eprintln!("⚠ MCP_FORGE_PLATFORM_OVERRIDE: {}; using {}", e, native);
+            native
+        }),
+    }
+}
+
+/// The current operating system: `linux`, `macos`, `windows` or `unknown`
+pub fn os() -> String {
+    current().os
+}
+
+/// The current CPU architecture: `x64`, `arm64` or `unknown`
+pub fn arch() -> String {
+    current().arch
+}
+
+fn native_os() -> &'static str {
+    #[cfg(target_os = "windows")]
+    return "windows";
+    #[cfg(target_os = "macos")]
+    return "macos";
+    #[cfg(target_os = "linux")]
+    return "linux";
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    return "unknown";
+}
+
+fn native_arch() -> &'static str {
+    #[cfg(target_arch = "x86_64")]
+    return "x64";
+    #[cfg(target_arch = "aarch64")]
+    return "arm64";
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    return "unknown";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_platform() {
+        let mac = Platform::parse("macOS/aarch64").unwrap();
+        assert_eq!((mac.os.as_str(), mac.arch.as_str()), ("macos", "arm64"));
+        assert_eq!(
+            Platform::parse("windows/x64").unwrap().to_string(),
+            "windows x64"
+        );
+        assert_eq!(Platform::parse("linux").unwrap().arch, native_arch());
+        assert!(Platform::parse("darwin").is_err());
+        assert!(Platform::parse("linux/riscv").is_err());
+    }
+
+    #[test]
+    fn test_current_from_setting() {
+        assert_eq!(current_from(None), Platform::native());
+        assert_eq!(current_from(Some(" ")), Platform::native());
+        assert_eq!(current_from(Some("windows/arm64")).os, "windows");
+        assert_eq!(current_from(Some("plan9")), Platform::native());
+    }
+
+    #[test]
+    fn test_native_names_match_template_vocabulary() {
+        let native = Platform::native();
+        assert!(OS_NAMES.contains(&native.os.as_str()) || native.os == "unknown");
+        assert!(["x64", "arm64", "unknown"].contains(&native.arch.as_str()));
+    }
+}
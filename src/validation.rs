@@ -1,8 +1,13 @@
-use crate::config::{Config, McpServer};
+use crate::claude_logs::{Failure, LogFinding};
+use crate::config::{Config, McpServer, Target};
+use crate::github::ClientOptions;
+use crate::selection::Selection;
 use crate::utils;
 use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
 use colored::Colorize;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::path::Path;
 use std::process::Command;
 
@@ -25,6 +30,16 @@ impl ValidationStatus {
         }
     }
 
+    /// ASCII severity word for plain-text reports
+    pub fn plain_label(&self) -> &'static str {
+        match self {
+            ValidationStatus::Valid => "OK",
+            ValidationStatus::Warning => "WARN",
+            ValidationStatus::Error => "ERROR",
+            ValidationStatus::RequirementsMissing => "MISSING",
+        }
+    }
+
     pub fn symbol(&self) -> &'static str {
         match self {
             ValidationStatus::Valid => "✓",
@@ -42,6 +57,12 @@ pub struct ValidationIssue {
     pub message: String,
     pub severity: ValidationStatus,
     pub fix_suggestion: Option<String>,
+    /// Concrete command to run next, shown under the fix suggestion
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_command: Option<String>,
+    /// Claude Desktop log lines the issue was found in, oldest first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub log_excerpt: Vec<String>,
 }
 
 /// Validation result for a single server
@@ -65,36 +86,169 @@ pub struct SystemDiagnostic {
     pub config_file_writable: bool,
     pub backup_directory_exists: bool,
     pub total_servers: usize,
+    /// Where the GitHub token came from and its redacted form
+    pub github_token: Option<String>,
+    /// GitHub's rate limit as of the diagnostic, when it could be checked
+    pub github_rate_limit: Option<String>,
+    /// The proxy requests go through and where it was configured
+    pub proxy: Option<String>,
+    /// Hosts that bypass the proxy
+    pub no_proxy: Option<String>,
     pub issues: Vec<ValidationIssue>,
 }
 
+/// Output format for validation and health reports
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Default,
+    Plain,
+    Json,
+}
+
+impl ReportFormat {
+    /// Parse the `--format` flag value
+    pub fn parse(format: Option<&str>) -> Result<Self> {
+        match format {
+            None | Some("default") => Ok(ReportFormat::Default),
+            Some("plain") => Ok(ReportFormat::Plain),
+            Some("json") => Ok(ReportFormat::Json),
+            Some(f) => Err(anyhow!(
+                "Unsupported format: {}. Use 'default', 'plain' or 'json'",
+                f
+            )),
+        }
+    }
+}
+
+/// Header printed at the top of plain-text reports
+#[derive(Debug, Clone)]
+pub struct ReportHeader {
+    pub title: String,
+    pub generated_at: DateTime<Utc>,
+    pub host: String,
+    pub profile: String,
+    pub config_path: String,
+}
+
+impl ReportHeader {
+    /// Build a header describing the current run
+    pub fn current(title: &str, target: &Target, profile: Option<&str>) -> Self {
+        Self {
+            title: title.to_string(),
+            generated_at: Utc::now(),
+            host: get_hostname(),
+            profile: profile.unwrap_or("default").to_string(),
+            config_path: target
+                .config_path()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|_| "unknown".to_string()),
+        }
+    }
+}
+
+/// Maximum line width for plain-text reports (mail clients wrap at ~100)
+const PLAIN_LINE_WIDTH: usize = 98;
+
 /// Handle validate command
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_validate(
     deep: bool,
     requirements: bool,
-    server_name: Option<String>,
+    servers: Selection,
+    fix: bool,
+    force: bool,
+    format: ReportFormat,
+    target: &Target,
+    github: &ClientOptions,
     profile: Option<String>,
 ) -> Result<()> {
-    let config = Config::load(profile.as_deref()).await?;
+    let mut config = Config::load(target).await?;
 
-    println!("{}", "Configuration Validation".cyan().bold());
-    println!("{}", "────────────────────────".cyan());
+    if fix {
+        let original = config.clone();
+        if normalize_env_case(&mut config, force)? > 0 {
+            // Create backup before modification
+            crate::backup::backup_before_change(target, &original).await?;
 
-    let results = if let Some(name) = server_name {
-        if let Some(server) = config.mcp_servers.get(&name) {
-            vec![validate_server(&name, server, deep, requirements).await]
-        } else {
-            return Err(anyhow!("Server '{}' not found", name));
+            crate::transaction::save_config(&config, target, profile.as_deref(), None).await?;
+            println!();
         }
-    } else {
-        let mut results = Vec::new();
+    }
+
+    if format == ReportFormat::Default {
+        println!("{}", "Configuration Validation".cyan().bold());
+        println!("{}", "────────────────────────".cyan());
+    }
+
+    let workspace = crate::workspace::discover(&config, github).await?;
+    let workspace_servers = workspace.as_ref().map_or(&[][..], |w| &w.servers[..]);
+
+    let find = |name: &str| {
+        config.mcp_servers.get(name).or_else(|| {
+            workspace_servers
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, server)| server)
+        })
+    };
+    servers.require(|name| find(name).is_some(), "")?;
+
+    let mut results = Vec::new();
+    if servers.is_all() {
         for (name, server) in &config.mcp_servers {
-            results.push(validate_server(name, server, deep, requirements).await);
+            results.push(validate_server(name, server, deep, requirements, target).await);
         }
-        results
-    };
+        for (name, server) in workspace_servers {
+            results.push(validate_server(name, server, deep, requirements, target).await);
+        }
+    } else {
+        for name in servers.names() {
+            if let Some(server) = find(name) {
+                results.push(validate_server(name, server, deep, requirements, target).await);
+            }
+        }
+    }
+    if let Some(workspace) = &workspace {
+        for result in &mut results {
+            if !config.mcp_servers.contains_key(&result.server_name) {
+                result.suggestions.push(format!(
+                    "[workspace] declared in {}; add it with 'mcp-forge workspace install'",
+                    workspace.file.display()
+                ));
+            }
+        }
+    }
 
-    display_validation_results(&results);
+    // Dependencies outside the selection are validated too, to know whether they pass
+    let metadata = crate::metadata::ServerMetadata::load(target).unwrap_or_default();
+    let mut statuses: HashMap<String, ValidationStatus> = results
+        .iter()
+        .map(|r| (r.server_name.clone(), r.status.clone()))
+        .collect();
+    let dependencies: Vec<String> = results
+        .iter()
+        .flat_map(|r| metadata.dependencies(&r.server_name).to_vec())
+        .collect();
+    for dependency in dependencies {
+        if let (false, Some(server)) = (
+            statuses.contains_key(&dependency),
+            config.mcp_servers.get(&dependency),
+        ) {
+            let status = validate_server(&dependency, server, deep, requirements, target)
+                .await
+                .status;
+            statuses.insert(dependency, status);
+        }
+    }
+    let platform = crate::utils::platform::os();
+    for result in &mut results {
+        add_dependency_issues(
+            result,
+            metadata.dependencies(&result.server_name),
+            &statuses,
+        );
+        add_platform_issues(result, metadata.get(&result.server_name), &platform);
+    }
 
     let has_errors = results
         .iter()
@@ -103,10 +257,23 @@ pub async fn handle_validate(
         .iter()
         .any(|r| matches!(r.status, ValidationStatus::Warning));
 
+    if format == ReportFormat::Plain {
+        let header =
+            ReportHeader::current("MCP-Forge Validation Report", target, profile.as_deref());
+        print!("{}", render_plain_report(&header, &results));
+        return validation_status(has_errors);
+    }
+
+    if format == ReportFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return validation_status(has_errors);
+    }
+
+    display_validation_results(&results);
+
     println!();
     if has_errors {
         println!("{}", "❌ Validation completed with errors".red().bold());
-        std::process::exit(1);
     } else if has_warnings {
         println!(
             "{}",
@@ -116,22 +283,222 @@ pub async fn handle_validate(
         println!("{}", "✅ All validations passed".green().bold());
     }
 
-    Ok(())
+    validation_status(has_errors)
+}
+
+/// Exit with status 1 once the report is printed if any server has an error
+fn validation_status(has_errors: bool) -> Result<()> {
+    if has_errors {
+        Err(utils::ExitStatus::new(1, "Validation found errors").into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Warn about dependencies, declared with `link set`, that are missing or failing
+///
+/// `statuses` holds the validation status of every configured dependency; a
+/// dependency without one is not in the config.
+fn add_dependency_issues(
+    result: &mut ValidationResult,
+    dependencies: &[String],
+    statuses: &HashMap<String, ValidationStatus>,
+) {
+    for dependency in dependencies {
+        let (issue_type, message) = match statuses.get(dependency) {
+            None => (
+                "Missing Dependency",
+                format!("Depends on '{}', which is not configured", dependency),
+            ),
+            Some(ValidationStatus::Error) => (
+                "Failing Dependency",
+                format!("Depends on '{}', which fails validation", dependency),
+            ),
+            Some(_) => continue,
+        };
+        result.issues.push(ValidationIssue {
+            issue_type: issue_type.to_string(),
+            message,
+            severity: ValidationStatus::Warning,
+            fix_suggestion: Some(format!(
+                "Add or fix '{}', or update the link with `mcp-forge link set {} --depends-on ...`",
+                dependency, result.server_name
+            )),
+            suggested_command: None,
+            log_excerpt: Vec::new(),
+        });
+        if result.status == ValidationStatus::Valid {
+            result.status = ValidationStatus::Warning;
+        }
+    }
+}
+
+/// Warn about a server added with `--ignore-platform` while it runs on an unsupported platform
+fn add_platform_issues(
+    result: &mut ValidationResult,
+    provenance: Option<&crate::metadata::ServerProvenance>,
+    platform: &str,
+) {
+    let Some((template, platform_override)) =
+        provenance.and_then(|p| Some((&p.template, p.platform_override.as_ref()?)))
+    else {
+        return;
+    };
+    if crate::templates::supports_platform(&platform_override.supported, platform) {
+        return;
+    }
+
+    result.issues.push(ValidationIssue {
+        issue_type: "Unsupported Platform".to_string(),
+        message: format!(
+            "Added with --ignore-platform: template '{}' supports {}, not {}",
+            template,
+            platform_override.supported.join(", "),
+            platform
+        ),
+        severity: ValidationStatus::Warning,
+        fix_suggestion: Some(format!(
+            "Check that the server works on {}, or replace it with a template that supports it",
+            platform
+        )),
+        suggested_command: Some(suggest("remove", &result.server_name)),
+        log_excerpt: Vec::new(),
+    });
+    if result.status == ValidationStatus::Valid {
+        result.status = ValidationStatus::Warning;
+    }
+}
+
+/// Report the failures Claude Desktop logged for the server as errors
+fn add_log_issues(result: &mut ValidationResult, findings: &[LogFinding]) {
+    for finding in findings.iter().filter(|f| f.server == result.server_name) {
+        let (issue_type, message, fix_suggestion, subcommand) = match &finding.failure {
+            Failure::NotFound { command } => (
+                "Failed To Start",
+                format!(
+                    "Claude Desktop couldn't start '{}': command not found (spawn ENOENT)",
+                    command
+                ),
+                format!(
+                    "Install {} or use its absolute path; Claude Desktop doesn't read your shell's PATH",
+                    command
+                ),
+                "edit",
+            ),
+            Failure::InvalidOutput => (
+                "Invalid Output",
+                "Claude Desktop couldn't parse the server's output as JSON-RPC".to_string(),
+                "Make the server log to stderr; stdout is reserved for JSON-RPC messages"
+                    .to_string(),
+                "smoke --only",
+            ),
+            Failure::Exited { code } => (
+                "Exited Early",
+                match code {
+                    Some(code) => format!("The server exited with code {} after starting", code),
+                    None => "The server exited soon after starting".to_string(),
+                },
+                "Run the server's command in a terminal to see why it stops".to_string(),
+                "smoke --only",
+            ),
+        };
+        result.issues.push(ValidationIssue {
+            issue_type: issue_type.to_string(),
+            message: format!("{} ({})", message, finding.file.display()),
+            severity: ValidationStatus::Error,
+            fix_suggestion: Some(fix_suggestion),
+            suggested_command: Some(suggest(subcommand, &result.server_name)),
+            log_excerpt: finding.excerpt.clone(),
+        });
+        result.status = ValidationStatus::Error;
+    }
+}
+
+/// Failures in the Claude Desktop logs, or none with a warning if they can't be read
+fn read_log_findings(target: &Target) -> Vec<LogFinding> {
+    if target.host != crate::config::Host::Claude {
+        eprintln!(
+            "{} --logs reads Claude Desktop's logs, which don't cover --target clients",
+            "⚠".yellow()
+        );
+        return Vec::new();
+    }
+    let findings = crate::claude_logs::log_dir().and_then(|dir| {
+        if !dir.is_dir() {
+            return Err(anyhow!("no logs at {}", dir.display()));
+        }
+        crate::claude_logs::recent_failures(&dir)
+    });
+    findings.unwrap_or_else(|e| {
+        eprintln!(
+            "{} Couldn't read Claude Desktop logs: {:#}",
+            "⚠".yellow(),
+            e
+        );
+        Vec::new()
+    })
 }
 
 /// Handle health check command
-pub async fn handle_health_check(profile: Option<String>) -> Result<()> {
-    let config = Config::load(profile.as_deref()).await?;
+///
+/// With `logs`, failures Claude Desktop logged for the servers are reported too.
+/// Exits with status 1 if any server has an error, whatever the format.
+pub async fn handle_health_check(
+    format: ReportFormat,
+    logs: bool,
+    target: &Target,
+    profile: Option<String>,
+) -> Result<()> {
+    if check_health(format, logs, target, profile).await? {
+        return Err(utils::ExitStatus::new(1, "Health check found errors").into());
+    }
+    Ok(())
+}
+
+/// Report the health of every server; whether any has an error
+async fn check_health(
+    format: ReportFormat,
+    logs: bool,
+    target: &Target,
+    profile: Option<String>,
+) -> Result<bool> {
+    let config = Config::load(target).await?;
+    let findings = if logs {
+        read_log_findings(target)
+    } else {
+        Vec::new()
+    };
+
+    if format != ReportFormat::Default {
+        let mut results = Vec::new();
+        for (name, server) in &config.mcp_servers {
+            let mut result = validate_server(name, server, true, true, target).await;
+            add_log_issues(&mut result, &findings);
+            results.push(result);
+        }
+        if format == ReportFormat::Json {
+            println!("{}", serde_json::to_string_pretty(&results)?);
+        } else {
+            let header =
+                ReportHeader::current("MCP-Forge Health Report", target, profile.as_deref());
+            print!("{}", render_plain_report(&header, &results));
+        }
+        return Ok(results
+            .iter()
+            .any(|r| matches!(r.status, ValidationStatus::Error)));
+    }
 
     println!("{}", "System Health Check".cyan().bold());
     println!("{}", "───────────────────".cyan());
 
     let mut health_issues = Vec::new();
     let mut healthy_count = 0;
+    let mut has_errors = false;
 
     for (name, server) in &config.mcp_servers {
         print!("Checking {} ... ", name);
-        let result = validate_server(name, server, true, true).await;
+        let mut result = validate_server(name, server, true, true, target).await;
+        add_log_issues(&mut result, &findings);
 
         match result.status {
             ValidationStatus::Valid => {
@@ -144,6 +511,7 @@ pub async fn handle_health_check(profile: Option<String>) -> Result<()> {
             }
             ValidationStatus::Error | ValidationStatus::RequirementsMissing => {
                 println!("{}", "✗ Unhealthy".red());
+                has_errors |= matches!(result.status, ValidationStatus::Error);
                 health_issues.extend(result.issues);
             }
         }
@@ -168,39 +536,75 @@ pub async fn handle_health_check(profile: Option<String>) -> Result<()> {
                 issue.issue_type.bold(),
                 issue.message
             );
+            for line in &issue.log_excerpt {
+                println!("    │ {}", line.dimmed());
+            }
             if let Some(suggestion) = &issue.fix_suggestion {
                 println!("    💡 {}", suggestion.italic());
             }
+            if let Some(command) = &issue.suggested_command {
+                println!("    ▶ run: {}", command.cyan());
+            }
+        }
+
+        let mut next_steps: Vec<&String> = health_issues
+            .iter()
+            .filter_map(|i| i.suggested_command.as_ref())
+            .collect();
+        next_steps.sort();
+        next_steps.dedup();
+
+        if !next_steps.is_empty() {
+            println!();
+            println!("Next steps:");
+            for command in next_steps {
+                println!("  {}", command.cyan());
+            }
         }
     }
 
-    Ok(())
+    Ok(has_errors)
 }
 
 /// Handle validate-all command
-pub async fn handle_validate_all(profile: Option<String>) -> Result<()> {
+pub async fn handle_validate_all(
+    target: &Target,
+    github: &ClientOptions,
+    profile: Option<String>,
+) -> Result<()> {
     println!("{}", "Comprehensive Validation".cyan().bold());
     println!("{}", "───────────────────────".cyan());
 
-    // First run health check
-    handle_health_check(profile.clone()).await?;
+    // First run health check; the validation below sets the exit status
+    check_health(ReportFormat::Default, false, target, profile.clone()).await?;
 
     println!();
     println!("{}", "Configuration Details".cyan().bold());
     println!("{}", "────────────────────".cyan());
 
     // Then run detailed validation
-    handle_validate(true, true, None, profile).await?;
+    handle_validate(
+        true,
+        true,
+        Selection::default(),
+        false,
+        false,
+        ReportFormat::Default,
+        target,
+        github,
+        profile,
+    )
+    .await?;
 
     Ok(())
 }
 
 /// Handle doctor command (system diagnostic)
-pub async fn handle_doctor(profile: Option<String>) -> Result<()> {
+pub async fn handle_doctor(target: &Target, github: &ClientOptions) -> Result<()> {
     println!("{}", "System Diagnostic".cyan().bold());
     println!("{}", "─────────────────".cyan());
 
-    let diagnostic = run_system_diagnostic(profile.as_deref()).await?;
+    let diagnostic = run_system_diagnostic(target, github).await?;
     display_diagnostic(&diagnostic);
 
     Ok(())
@@ -212,6 +616,7 @@ async fn validate_server(
     server: &McpServer,
     deep: bool,
     check_requirements: bool,
+    target: &Target,
 ) -> ValidationResult {
     let mut result = ValidationResult {
         server_name: name.to_string(),
@@ -230,9 +635,15 @@ async fn validate_server(
     // Validate environment variables
     validate_environment(server, &mut result);
 
+    // Template syntax that never got rendered, e.g. carried in by an import
+    validate_rendered(server, &mut result);
+
+    // Validate client-side timeout and autoApprove
+    validate_client_options(server, &mut result);
+
     // Check requirements if requested
     if check_requirements {
-        validate_requirements(server, &mut result).await;
+        validate_requirements(server, &mut result, target).await;
     }
 
     // Deep validation if requested
@@ -264,13 +675,22 @@ async fn validate_server(
     result
 }
 
+/// Build an `mcp-forge` command line that acts on a single server
+fn suggest(subcommand: &str, server_name: &str) -> String {
+    if server_name.contains(char::is_whitespace) {
+        format!("mcp-forge {} '{}'", subcommand, server_name)
+    } else {
+        format!("mcp-forge {} {}", subcommand, server_name)
+    }
+}
+
 /// Check if the command exists and is executable
 fn validate_command_exists(server: &McpServer, result: &mut ValidationResult) {
     // For URL servers, skip command validation
     if server.is_url_server() {
         return;
     }
-    
+
     let Some(command) = &server.command else {
         return;
     };
@@ -283,6 +703,8 @@ fn validate_command_exists(server: &McpServer, result: &mut ValidationResult) {
                 message: format!("Command path '{}' does not exist", command),
                 severity: ValidationStatus::Error,
                 fix_suggestion: Some("Verify the command path is correct".to_string()),
+                suggested_command: Some(suggest("edit", &result.server_name)),
+                log_excerpt: Vec::new(),
             });
             return;
         }
@@ -293,6 +715,8 @@ fn validate_command_exists(server: &McpServer, result: &mut ValidationResult) {
                 message: format!("Command '{}' is not executable", command),
                 severity: ValidationStatus::Error,
                 fix_suggestion: Some("Check file permissions".to_string()),
+                suggested_command: cfg!(unix).then(|| format!("chmod +x {}", command)),
+                log_excerpt: Vec::new(),
             });
         }
     } else {
@@ -303,6 +727,8 @@ fn validate_command_exists(server: &McpServer, result: &mut ValidationResult) {
                 message: format!("Command '{}' not found in PATH", command),
                 severity: ValidationStatus::Error,
                 fix_suggestion: Some(format!("Install {} or add it to your PATH", command)),
+                suggested_command: Some(suggest("validate --requirements", &result.server_name)),
+                log_excerpt: Vec::new(),
             });
         }
     }
@@ -313,7 +739,7 @@ fn validate_arguments(server: &McpServer, result: &mut ValidationResult) {
     let Some(args) = &server.args else {
         return;
     };
-    
+
     // Check for common problematic argument patterns
     for (i, arg) in args.iter().enumerate() {
         // Check for unquoted spaces in file paths
@@ -327,6 +753,8 @@ fn validate_arguments(server: &McpServer, result: &mut ValidationResult) {
                 ),
                 severity: ValidationStatus::Warning,
                 fix_suggestion: Some("Consider quoting arguments with spaces".to_string()),
+                suggested_command: Some(suggest("edit", &result.server_name)),
+                log_excerpt: Vec::new(),
             });
         }
 
@@ -341,14 +769,49 @@ fn validate_arguments(server: &McpServer, result: &mut ValidationResult) {
                 fix_suggestion: Some(
                     "Verify the path exists or will be created at runtime".to_string(),
                 ),
+                suggested_command: Some(suggest("edit", &result.server_name)),
+                log_excerpt: Vec::new(),
             });
         }
     }
 }
 
+/// Flag `{{...}}` left behind by a template that didn't fully render
+fn validate_rendered(server: &McpServer, result: &mut ValidationResult) {
+    for (field, fragment) in server.unrendered_fragments() {
+        result.issues.push(ValidationIssue {
+            issue_type: "Unrendered Template Syntax".to_string(),
+            message: format!("Template syntax left in {}: '{}'", field, fragment),
+            severity: ValidationStatus::Error,
+            fix_suggestion: Some(
+                "Replace it with the intended value; the server receives it literally".to_string(),
+            ),
+            suggested_command: Some(suggest("edit", &result.server_name)),
+            log_excerpt: Vec::new(),
+        });
+    }
+}
+
 /// Validate environment variables
 fn validate_environment(server: &McpServer, result: &mut ValidationResult) {
     if let Some(env) = &server.env {
+        for group in utils::find_case_colliding_keys(env) {
+            result.issues.push(ValidationIssue {
+                issue_type: "Case-Colliding Environment Keys".to_string(),
+                message: format!(
+                    "Environment keys {} differ only by case; behavior is platform-dependent",
+                    group.join(", ")
+                ),
+                severity: ValidationStatus::Warning,
+                fix_suggestion: Some(
+                    "Run 'mcp-forge validate --fix' or 'mcp-forge config normalize' to merge them"
+                        .to_string(),
+                ),
+                suggested_command: Some("mcp-forge config normalize".to_string()),
+                log_excerpt: Vec::new(),
+            });
+        }
+
         for (key, value) in env {
             // Check for empty values that might be problematic
             if value.is_empty() {
@@ -359,6 +822,8 @@ fn validate_environment(server: &McpServer, result: &mut ValidationResult) {
                     fix_suggestion: Some(
                         "Consider removing unused environment variables".to_string(),
                     ),
+                    suggested_command: Some(suggest("edit", &result.server_name)),
+                    log_excerpt: Vec::new(),
                 });
             }
 
@@ -379,14 +844,65 @@ fn validate_environment(server: &McpServer, result: &mut ValidationResult) {
                     fix_suggestion: Some(
                         "Verify the path exists or will be created at runtime".to_string(),
                     ),
+                    suggested_command: Some(format!(
+                        "{} --set {}=<path>",
+                        suggest("update", &result.server_name),
+                        key
+                    )),
+                    log_excerpt: Vec::new(),
                 });
             }
         }
     }
 }
 
+/// Check the client-side `timeout` and `autoApprove` fields
+fn validate_client_options(server: &McpServer, result: &mut ValidationResult) {
+    if let Some(timeout) = server.timeout {
+        if timeout <= 0 {
+            result.issues.push(ValidationIssue {
+                issue_type: "Invalid Timeout".to_string(),
+                message: format!("Timeout is {}s; clients need a positive value", timeout),
+                severity: ValidationStatus::Warning,
+                fix_suggestion: Some("Set a timeout in seconds, e.g. 60".to_string()),
+                suggested_command: Some(format!(
+                    "{} --timeout 60",
+                    suggest("update", &result.server_name)
+                )),
+                log_excerpt: Vec::new(),
+            });
+        }
+    }
+
+    if let Some(tools) = &server.auto_approve {
+        let mut seen = std::collections::HashSet::new();
+        let duplicates: Vec<&str> = tools
+            .iter()
+            .filter(|tool| !seen.insert(tool.as_str()))
+            .map(|tool| tool.as_str())
+            .collect();
+        if tools.iter().any(|tool| tool.trim().is_empty()) || !duplicates.is_empty() {
+            result.issues.push(ValidationIssue {
+                issue_type: "Invalid Auto-Approve List".to_string(),
+                message: if duplicates.is_empty() {
+                    "autoApprove contains an empty tool name".to_string()
+                } else {
+                    format!("autoApprove lists {} more than once", duplicates.join(", "))
+                },
+                severity: ValidationStatus::Warning,
+                fix_suggestion: Some("List each tool name once".to_string()),
+                suggested_command: Some(format!(
+                    "{} --auto-approve <tools>",
+                    suggest("update", &result.server_name)
+                )),
+                log_excerpt: Vec::new(),
+            });
+        }
+    }
+}
+
 /// Check system requirements for the server
-async fn validate_requirements(server: &McpServer, result: &mut ValidationResult) {
+async fn validate_requirements(server: &McpServer, result: &mut ValidationResult, target: &Target) {
     let Some(command) = &server.command else {
         return;
     };
@@ -403,6 +919,11 @@ async fn validate_requirements(server: &McpServer, result: &mut ValidationResult
                     message: "Node.js is required but not found".to_string(),
                     severity: ValidationStatus::RequirementsMissing,
                     fix_suggestion: Some("Install Node.js from https://nodejs.org/".to_string()),
+                    suggested_command: Some(suggest(
+                        "validate --requirements",
+                        &result.server_name,
+                    )),
+                    log_excerpt: Vec::new(),
                 });
             }
         }
@@ -417,21 +938,242 @@ async fn validate_requirements(server: &McpServer, result: &mut ValidationResult
                     message: "Python is required but not found".to_string(),
                     severity: ValidationStatus::RequirementsMissing,
                     fix_suggestion: Some("Install Python from https://python.org/".to_string()),
+                    suggested_command: Some(suggest(
+                        "validate --requirements",
+                        &result.server_name,
+                    )),
+                    log_excerpt: Vec::new(),
                 });
             }
         }
-        "uvx" => {
-            if !command_in_path("uvx") {
-                result.issues.push(ValidationIssue {
-                    issue_type: "Missing Requirement".to_string(),
-                    message: "uvx is required but not found".to_string(),
-                    severity: ValidationStatus::RequirementsMissing,
-                    fix_suggestion: Some("Install uvx: pip install uvx".to_string()),
+        "uvx" if !command_in_path("uvx") => {
+            result.issues.push(ValidationIssue {
+                issue_type: "Missing Requirement".to_string(),
+                message: "uvx is required but not found".to_string(),
+                severity: ValidationStatus::RequirementsMissing,
+                fix_suggestion: Some("Install uvx: pip install uvx".to_string()),
+                suggested_command: Some(suggest("validate --requirements", &result.server_name)),
+                log_excerpt: Vec::new(),
+            });
+        }
+        _ => {}
+    }
+
+    // Pinned versions are heuristics, so `meta set` can acknowledge a false positive
+    let metadata = crate::metadata::ServerMetadata::load(target).unwrap_or_default();
+    if !metadata.ignore_version_pins.contains(&result.server_name) {
+        let issues = version_pin_issues(
+            server,
+            &result.server_name,
+            &|interpreter| match interpreter {
+                Interpreter::Node => get_node_version(),
+                Interpreter::Python => get_python_version(),
+            },
+            &command_in_path,
+        );
+        result.issues.extend(issues);
+    }
+}
+
+/// Interpreter a server's command or arguments can pin a version of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Interpreter {
+    Node,
+    Python,
+}
+
+impl Interpreter {
+    fn label(self) -> &'static str {
+        match self {
+            Interpreter::Node => "Node.js",
+            Interpreter::Python => "Python",
+        }
+    }
+
+    fn install_hint(self, version: &str) -> String {
+        match self {
+            Interpreter::Node => format!(
+                "Install Node.js {} (e.g. `nvm install {}`) or update the pinned version",
+                version, version
+            ),
+            Interpreter::Python => format!(
+                "Install Python {} (e.g. `uv python install {}` or `pyenv install {}`) or update the pinned version",
+                version, version, version
+            ),
+        }
+    }
+}
+
+/// What a server runs on, e.g. `Node.js`, `docker`, or `remote` for URL servers
+pub fn runtime_label(server: &McpServer) -> String {
+    if server.is_url_server() {
+        return "remote".to_string();
+    }
+    match server.command.as_deref() {
+        Some(command) => command_interpreter(command)
+            .map(|interpreter| interpreter.label().to_string())
+            .unwrap_or_else(|| command_basename(command).to_string()),
+        None => "unknown".to_string(),
+    }
+}
+
+/// Interpreter family of a command, for `--*version*` flags that don't name one
+fn command_interpreter(command: &str) -> Option<Interpreter> {
+    match command_basename(command) {
+        "node" | "npx" => Some(Interpreter::Node),
+        "python" | "python3" | "uv" | "uvx" => Some(Interpreter::Python),
+        name => versioned_interpreter(name).map(|(interpreter, _)| interpreter),
+    }
+}
+
+/// File name of a command without directory or `.exe` suffix
+fn command_basename(command: &str) -> &str {
+    let name = command.rsplit(['/', '\\']).next().unwrap_or(command);
+    name.strip_suffix(".exe").unwrap_or(name)
+}
+
+/// A versioned interpreter command such as `python3.11` or `node18`
+fn versioned_interpreter(command: &str) -> Option<(Interpreter, String)> {
+    let name = command_basename(command);
+    if let Some(version) = name.strip_prefix("python") {
+        // Plain `python3` is what the generic check already covers
+        return (version.contains('.') && is_version_literal(version))
+            .then(|| (Interpreter::Python, version.to_string()));
+    }
+    name.strip_prefix("node")
+        .filter(|version| is_version_literal(version))
+        .map(|version| (Interpreter::Node, version.to_string()))
+}
+
+/// Whether a value looks like `18`, `3.11` or `v18.17.0`
+fn is_version_literal(value: &str) -> bool {
+    let value = value.strip_prefix('v').unwrap_or(value);
+    let parts: Vec<&str> = value.split('.').collect();
+    parts.len() <= 3
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Versions pinned by flags like `--node=18`, `--python 3.11` or `--target-version=3.12`
+fn pinned_versions(server: &McpServer) -> Vec<(Interpreter, String)> {
+    let Some(args) = &server.args else {
+        return Vec::new();
+    };
+    let fallback = server.command.as_deref().and_then(command_interpreter);
+
+    let mut pins = Vec::new();
+    for (i, arg) in args.iter().enumerate() {
+        let Some(flag) = arg.strip_prefix("--") else {
+            continue;
+        };
+        let (name, value) = match flag.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (flag, args.get(i + 1).map(String::as_str)),
+        };
+        let name = name.to_lowercase();
+        let interpreter = if name.contains("node") {
+            Some(Interpreter::Node)
+        } else if name.contains("python") {
+            Some(Interpreter::Python)
+        } else if name.contains("version") {
+            fallback
+        } else {
+            None
+        };
+        if let (Some(interpreter), Some(value)) = (interpreter, value) {
+            if is_version_literal(value) {
+                let version = value.trim_start_matches('v').to_string();
+                pins.push((interpreter, version));
+            }
+        }
+    }
+    pins
+}
+
+/// Numeric components of a version string such as `v18.17.0` or `Python 3.12.1`
+fn version_components(version: &str) -> Vec<u64> {
+    version
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .split('.')
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+/// Whether an installed version satisfies a pin, comparing only the pinned components
+fn version_matches(pinned: &str, installed: &str) -> bool {
+    let pinned = version_components(pinned);
+    let installed = version_components(installed);
+    !pinned.is_empty() && installed.starts_with(&pinned)
+}
+
+/// Warnings for interpreter versions a server expects but the system doesn't provide
+///
+/// `installed` reports the version of the default interpreter, and `in_path`
+/// probes for a command, so the heuristics can run against synthetic systems.
+fn version_pin_issues(
+    server: &McpServer,
+    server_name: &str,
+    installed: &dyn Fn(Interpreter) -> Option<String>,
+    in_path: &dyn Fn(&str) -> bool,
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let mut seen = Vec::new();
+
+    if let Some(command) = &server.command {
+        if let Some((interpreter, version)) = versioned_interpreter(command) {
+            seen.push((interpreter, version.clone()));
+            if !in_path(command) {
+                let default = installed(interpreter)
+                    .map(|v| format!("; the default interpreter is {}", v))
+                    .unwrap_or_default();
+                issues.push(ValidationIssue {
+                    issue_type: "Interpreter Version".to_string(),
+                    message: format!(
+                        "Command '{}' expects {} {}, which is not installed{}",
+                        command,
+                        interpreter.label(),
+                        version,
+                        default
+                    ),
+                    severity: ValidationStatus::Warning,
+                    fix_suggestion: Some(interpreter.install_hint(&version)),
+                    suggested_command: Some(suggest("edit", server_name)),
+                    log_excerpt: Vec::new(),
                 });
             }
         }
-        _ => {}
     }
+
+    for (interpreter, version) in pinned_versions(server) {
+        if seen.contains(&(interpreter, version.clone())) {
+            continue;
+        }
+        seen.push((interpreter, version.clone()));
+
+        // Without an installed interpreter the version can't be compared
+        let Some(current) = installed(interpreter) else {
+            continue;
+        };
+        if version_matches(&version, &current) {
+            continue;
+        }
+        issues.push(ValidationIssue {
+            issue_type: "Interpreter Version".to_string(),
+            message: format!(
+                "Arguments pin {} {}, but the installed version is {}",
+                interpreter.label(),
+                version,
+                current
+            ),
+            severity: ValidationStatus::Warning,
+            fix_suggestion: Some(interpreter.install_hint(&version)),
+            suggested_command: Some(suggest("edit", server_name)),
+            log_excerpt: Vec::new(),
+        });
+    }
+
+    issues
 }
 
 /// Perform deep validation (not network-level as per requirements)
@@ -442,14 +1184,16 @@ async fn perform_deep_validation(server: &McpServer, result: &mut ValidationResu
     if let Some(args) = &server.args {
         for arg in args {
             if let Ok(port) = arg.parse::<u16>() {
-            if port < 1024 {
-                result.issues.push(ValidationIssue {
-                    issue_type: "Privileged Port".to_string(),
-                    message: format!("Port {} requires elevated privileges", port),
-                    severity: ValidationStatus::Warning,
-                    fix_suggestion: Some("Consider using a port > 1024".to_string()),
-                });
-            }
+                if port < 1024 {
+                    result.issues.push(ValidationIssue {
+                        issue_type: "Privileged Port".to_string(),
+                        message: format!("Port {} requires elevated privileges", port),
+                        severity: ValidationStatus::Warning,
+                        fix_suggestion: Some("Consider using a port > 1024".to_string()),
+                        suggested_command: Some(suggest("edit", &result.server_name)),
+                        log_excerpt: Vec::new(),
+                    });
+                }
             }
         }
     }
@@ -467,15 +1211,20 @@ async fn perform_deep_validation(server: &McpServer, result: &mut ValidationResu
                 fix_suggestion: Some(
                     "Consider using configuration files instead of many arguments".to_string(),
                 ),
+                suggested_command: Some(suggest("edit", &result.server_name)),
+                log_excerpt: Vec::new(),
             });
         }
     }
 }
 
 /// Run comprehensive system diagnostic
-async fn run_system_diagnostic(profile: Option<&str>) -> Result<SystemDiagnostic> {
+async fn run_system_diagnostic(
+    target: &Target,
+    github: &ClientOptions,
+) -> Result<SystemDiagnostic> {
     let mut diagnostic = SystemDiagnostic {
-        platform: get_platform_info(),
+        platform: crate::utils::platform::current().to_string(),
         node_version: get_node_version(),
         python_version: get_python_version(),
         config_file_exists: false,
@@ -483,22 +1232,28 @@ async fn run_system_diagnostic(profile: Option<&str>) -> Result<SystemDiagnostic
         config_file_writable: false,
         backup_directory_exists: false,
         total_servers: 0,
+        github_token: None,
+        github_rate_limit: None,
+        proxy: None,
+        no_proxy: crate::github::no_proxy(),
         issues: Vec::new(),
     };
 
     // Check configuration file
-    match utils::get_claude_config_path() {
+    match target.config_path() {
         Ok(path) => {
             diagnostic.config_file_path = path.display().to_string();
             diagnostic.config_file_exists = path.exists();
-            diagnostic.config_file_writable = is_writable(&path);
+            diagnostic.config_file_writable = utils::is_writable(&path);
 
             if !diagnostic.config_file_exists {
                 diagnostic.issues.push(ValidationIssue {
                     issue_type: "Configuration".to_string(),
-                    message: "Claude Desktop configuration file not found".to_string(),
+                    message: format!("{} configuration file not found", target.host),
                     severity: ValidationStatus::Warning,
                     fix_suggestion: Some("Run 'mcp-forge config init' to create it".to_string()),
+                    suggested_command: Some("mcp-forge config init".to_string()),
+                    log_excerpt: Vec::new(),
                 });
             } else if !diagnostic.config_file_writable {
                 diagnostic.issues.push(ValidationIssue {
@@ -506,6 +1261,8 @@ async fn run_system_diagnostic(profile: Option<&str>) -> Result<SystemDiagnostic
                     message: "Configuration file is not writable".to_string(),
                     severity: ValidationStatus::Error,
                     fix_suggestion: Some("Check file permissions".to_string()),
+                    suggested_command: cfg!(unix).then(|| format!("chmod u+w {}", path.display())),
+                    log_excerpt: Vec::new(),
                 });
             }
         }
@@ -515,12 +1272,14 @@ async fn run_system_diagnostic(profile: Option<&str>) -> Result<SystemDiagnostic
                 message: format!("Cannot determine config file location: {}", e),
                 severity: ValidationStatus::Error,
                 fix_suggestion: None,
+                suggested_command: None,
+                log_excerpt: Vec::new(),
             });
         }
     }
 
     // Check backup directory
-    if let Ok(backup_dir) = utils::get_backup_dir() {
+    if let Ok(backup_dir) = target.backup_dir() {
         diagnostic.backup_directory_exists = backup_dir.exists();
         if !diagnostic.backup_directory_exists {
             diagnostic.issues.push(ValidationIssue {
@@ -528,15 +1287,49 @@ async fn run_system_diagnostic(profile: Option<&str>) -> Result<SystemDiagnostic
                 message: "Backup directory doesn't exist".to_string(),
                 severity: ValidationStatus::Warning,
                 fix_suggestion: Some("It will be created automatically when needed".to_string()),
+                suggested_command: Some("mcp-forge backup create".to_string()),
+                log_excerpt: Vec::new(),
             });
         }
     }
 
     // Load config to get server count
-    if let Ok(config) = Config::load(profile).await {
+    if let Ok(config) = Config::load(target).await {
         diagnostic.total_servers = config.mcp_servers.len();
     }
 
+    // A settings file that can't be read shows up below as a GitHub issue
+    diagnostic.proxy = crate::github::configured_proxy(github)
+        .ok()
+        .flatten()
+        .map(|(url, source)| format!("{} from {}", crate::github::redact_proxy(&url), source));
+
+    // Check GitHub access; the rate limit endpoint doesn't use up a request
+    let rate_limit = match crate::github::GitHubClient::try_new(github) {
+        Ok(client) => {
+            diagnostic.github_token = client
+                .token()
+                .map(|(token, source)| format!("{} from {}", token, source));
+            tokio::time::timeout(std::time::Duration::from_secs(5), client.fetch_rate_limit())
+                .await
+                .unwrap_or_else(|_| Err(anyhow!("timed out after 5s")))
+        }
+        Err(e) => Err(e),
+    };
+    match rate_limit {
+        Ok(state) => diagnostic.github_rate_limit = Some(state.describe(github.zone)),
+        Err(e) => diagnostic.issues.push(ValidationIssue {
+            issue_type: "GitHub".to_string(),
+            message: format!("Couldn't check the GitHub rate limit: {}", e),
+            severity: ValidationStatus::Warning,
+            fix_suggestion: Some(
+                "Templates can still be used from the cache with --offline".to_string(),
+            ),
+            suggested_command: None,
+            log_excerpt: Vec::new(),
+        }),
+    }
+
     Ok(diagnostic)
 }
 
@@ -562,6 +1355,9 @@ fn display_validation_results(results: &[ValidationResult]) {
             if let Some(suggestion) = &issue.fix_suggestion {
                 println!("    💡 {}", suggestion.italic());
             }
+            if let Some(command) = &issue.suggested_command {
+                println!("    ▶ run: {}", command.cyan());
+            }
         }
 
         if !result.suggestions.is_empty() {
@@ -572,6 +1368,170 @@ fn display_validation_results(results: &[ValidationResult]) {
     }
 }
 
+/// Render validation results as ASCII-only, column-aligned plain text
+pub fn render_plain_report(header: &ReportHeader, results: &[ValidationResult]) -> String {
+    let mut sorted: Vec<&ValidationResult> = results.iter().collect();
+    sorted.sort_by(|a, b| a.server_name.cmp(&b.server_name));
+
+    let mut output = String::new();
+    output.push_str(&format!("{}\n", to_ascii(&header.title)));
+    output.push_str(&format!("{}\n", "=".repeat(header.title.len())));
+    output.push_str(&format!(
+        "Generated: {}\n",
+        header.generated_at.format("%Y-%m-%d %H:%M:%S UTC")
+    ));
+    output.push_str(&format!("Host:      {}\n", to_ascii(&header.host)));
+    output.push_str(&format!("Profile:   {}\n", to_ascii(&header.profile)));
+    output.push_str(&wrap_plain(
+        &format!("Config:    {}", to_ascii(&header.config_path)),
+        "           ",
+    ));
+    output.push('\n');
+
+    if sorted.is_empty() {
+        output.push_str("No servers configured.\n");
+        return output;
+    }
+
+    let name_width = sorted
+        .iter()
+        .map(|r| r.server_name.len())
+        .max()
+        .unwrap_or(0)
+        .clamp(6, 40);
+
+    output.push_str(&format!(
+        "{:<8} {:<width$} {}\n",
+        "STATUS",
+        "SERVER",
+        "ISSUES",
+        width = name_width
+    ));
+    output.push_str(&format!(
+        "{:<8} {:<width$} {}\n",
+        "------",
+        "------",
+        "------",
+        width = name_width
+    ));
+
+    for result in &sorted {
+        output.push_str(&format!(
+            "{:<8} {:<width$} {}\n",
+            result.status.plain_label(),
+            truncate_plain(&to_ascii(&result.server_name), name_width),
+            result.issues.len(),
+            width = name_width
+        ));
+    }
+
+    let with_issues: Vec<_> = sorted.iter().filter(|r| !r.issues.is_empty()).collect();
+    if !with_issues.is_empty() {
+        output.push_str("\nDetails:\n");
+        for result in with_issues {
+            output.push_str(&format!("\n{}\n", to_ascii(&result.server_name)));
+            for issue in &result.issues {
+                output.push_str(&wrap_plain(
+                    &format!(
+                        "  - [{}] {}: {}",
+                        issue.severity.plain_label(),
+                        to_ascii(&issue.issue_type),
+                        to_ascii(&issue.message)
+                    ),
+                    "    ",
+                ));
+                for line in &issue.log_excerpt {
+                    output.push_str(&format!(
+                        "    | {}\n",
+                        truncate_plain(&to_ascii(line), PLAIN_LINE_WIDTH - 6)
+                    ));
+                }
+                if let Some(suggestion) = &issue.fix_suggestion {
+                    output.push_str(&wrap_plain(
+                        &format!("    Fix: {}", to_ascii(suggestion)),
+                        "         ",
+                    ));
+                }
+                if let Some(command) = &issue.suggested_command {
+                    output.push_str(&wrap_plain(
+                        &format!("    Run: {}", to_ascii(command)),
+                        "         ",
+                    ));
+                }
+            }
+        }
+    }
+
+    let count = |status: ValidationStatus| sorted.iter().filter(|r| r.status == status).count();
+    output.push_str(&format!(
+        "\nSummary: {} server(s), {} OK, {} WARN, {} ERROR, {} MISSING\n",
+        sorted.len(),
+        count(ValidationStatus::Valid),
+        count(ValidationStatus::Warning),
+        count(ValidationStatus::Error),
+        count(ValidationStatus::RequirementsMissing)
+    ));
+
+    output
+}
+
+/// Replace non-ASCII characters so reports survive any mail client
+fn to_ascii(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_ascii() && !c.is_ascii_control() {
+                c
+            } else {
+                '?'
+            }
+        })
+        .collect()
+}
+
+/// Truncate a column value to the given width
+fn truncate_plain(text: &str, width: usize) -> String {
+    if text.len() <= width {
+        text.to_string()
+    } else {
+        format!("{}...", &text[..width.saturating_sub(3)])
+    }
+}
+
+/// Word-wrap a line to the plain report width, indenting continuation lines
+fn wrap_plain(line: &str, indent: &str) -> String {
+    let mut output = String::new();
+    let body = line.trim_start();
+    let mut current = line[..line.len() - body.len()].to_string();
+    let mut line_start = true;
+
+    for word in body.split(' ') {
+        if !line_start && current.len() + 1 + word.len() > PLAIN_LINE_WIDTH {
+            output.push_str(current.trim_end());
+            output.push('\n');
+            current = indent.to_string();
+        } else if !line_start {
+            current.push(' ');
+        }
+        line_start = false;
+
+        // Hard-split words that can never fit on a single line
+        let mut word = word;
+        while current.len() + word.len() > PLAIN_LINE_WIDTH {
+            let room = PLAIN_LINE_WIDTH - current.len();
+            current.push_str(&word[..room]);
+            output.push_str(&current);
+            output.push('\n');
+            current = indent.to_string();
+            word = &word[room..];
+        }
+        current.push_str(word);
+    }
+
+    output.push_str(current.trim_end());
+    output.push('\n');
+    output
+}
+
 /// Display system diagnostic
 fn display_diagnostic(diagnostic: &SystemDiagnostic) {
     println!("Platform: {}", diagnostic.platform.bold());
@@ -609,6 +1569,26 @@ fn display_diagnostic(diagnostic: &SystemDiagnostic) {
     );
     println!("  Servers: {}", diagnostic.total_servers);
 
+    println!();
+    println!("GitHub:");
+    match &diagnostic.github_token {
+        Some(token) => println!("  Token: {}", token.green()),
+        None => println!(
+            "  Token: {}",
+            "not set (60 requests an hour; set GITHUB_TOKEN or GH_TOKEN for 5,000)".yellow()
+        ),
+    }
+    if let Some(rate_limit) = &diagnostic.github_rate_limit {
+        println!("  Rate limit: {}", rate_limit);
+    }
+    match &diagnostic.proxy {
+        Some(proxy) => println!("  Proxy: {}", proxy),
+        None => println!("  Proxy: none"),
+    }
+    if let Some(no_proxy) = &diagnostic.no_proxy {
+        println!("  No proxy for: {}", no_proxy);
+    }
+
     println!();
     println!(
         "Backup Directory: {}",
@@ -632,6 +1612,9 @@ fn display_diagnostic(diagnostic: &SystemDiagnostic) {
             if let Some(suggestion) = &issue.fix_suggestion {
                 println!("    💡 {}", suggestion.italic());
             }
+            if let Some(command) = &issue.suggested_command {
+                println!("    ▶ run: {}", command.cyan());
+            }
         }
     } else {
         println!();
@@ -640,8 +1623,19 @@ fn display_diagnostic(diagnostic: &SystemDiagnostic) {
 }
 
 /// Helper functions
-fn get_platform_info() -> String {
-    format!("{} {}", std::env::consts::OS, std::env::consts::ARCH)
+fn get_hostname() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .filter(|h| !h.is_empty())
+        .or_else(|| {
+            Command::new("hostname")
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .and_then(|output| String::from_utf8(output.stdout).ok())
+                .map(|s| s.trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string())
 }
 
 fn get_node_version() -> Option<String> {
@@ -697,96 +1691,290 @@ fn is_executable(path: &Path) -> bool {
         })
 }
 
-fn is_writable(path: &Path) -> bool {
-    if path.exists() {
-        std::fs::OpenOptions::new().append(true).open(path).is_ok()
-    } else {
-        // Check if parent directory is writable
-        path.parent().is_some_and(|parent| {
-            std::fs::metadata(parent).is_ok_and(|metadata| !metadata.permissions().readonly())
-        })
-    }
-}
+/// Interactively merge environment keys that differ only by case
+///
+/// With `force` the first key of each group in sorted order is kept without
+/// asking. Returns the number of collision groups that were resolved.
+pub fn normalize_env_case(config: &mut Config, force: bool) -> Result<usize> {
+    let mut names: Vec<String> = config.mcp_servers.keys().cloned().collect();
+    names.sort();
+
+    let mut resolved = 0;
+    for name in names {
+        let Some(env) = config
+            .mcp_servers
+            .get_mut(&name)
+            .and_then(|s| s.env.as_mut())
+        else {
+            continue;
+        };
 
-/// Validate configuration with options
-pub async fn validate_config(
-    deep: bool,
-    requirements: bool,
-    server: Option<String>,
-    profile: Option<String>,
-) -> Result<()> {
-    let config = crate::config::Config::load(profile.as_deref()).await?;
-
-    println!("🔍 Validating configuration...");
-
-    if let Some(server_name) = server {
-        // Validate specific server
-        if let Some(server) = config.get_server(&server_name) {
-            let result = validate_server(&server_name, server, deep, requirements).await;
-            match result.status {
-                ValidationStatus::Valid => println!("✅ Server '{}' is valid", server_name),
-                ValidationStatus::Warning => {
-                    println!("⚠️  Server '{}' has warnings", server_name);
-                    for issue in &result.issues {
-                        println!("    {}", issue.message);
-                    }
-                }
-                ValidationStatus::Error | ValidationStatus::RequirementsMissing => {
-                    println!("❌ Server '{}' has errors", server_name);
-                    for issue in &result.issues {
-                        println!("    {}", issue.message);
-                    }
-                    return Err(anyhow::anyhow!(
-                        "Validation failed for server '{}'",
-                        server_name
-                    ));
-                }
-            }
-        } else {
-            return Err(anyhow::anyhow!("Server '{}' not found", server_name));
-        }
-    } else {
-        // Validate all servers
-        let servers = config.list_servers();
-        if servers.is_empty() {
-            println!("⚠️  No servers configured to validate");
-            return Ok(());
-        }
-
-        let mut has_errors = false;
-        for (name, server) in servers {
-            let result = validate_server(&name, server, deep, requirements).await;
-            match result.status {
-                ValidationStatus::Valid => println!("✅ Server '{}' is valid", name),
-                ValidationStatus::Warning => {
-                    println!("⚠️  Server '{}' has warnings", name);
-                    for issue in &result.issues {
-                        println!("    {}", issue.message);
-                    }
-                }
-                ValidationStatus::Error | ValidationStatus::RequirementsMissing => {
-                    println!("❌ Server '{}' has errors", name);
-                    for issue in &result.issues {
-                        println!("    {}", issue.message);
-                    }
-                    has_errors = true;
-                }
+        for group in utils::find_case_colliding_keys(env) {
+            if force {
+                utils::merge_case_variant_keys(env, &group, &group[0]);
+                println!("{}", format!("✓ {}: kept {}", name, group[0]).green());
+                resolved += 1;
+                continue;
             }
+            utils::ensure_interactive(
+                "Choosing between keys that differ only by case needs a terminal; pass --force to keep the first in sorted order",
+            )?;
+            let choices: Vec<String> = group
+                .iter()
+                .map(|key| {
+                    format!(
+                        "{}={}",
+                        key,
+                        utils::mask_sensitive_env_value(key, &env[key])
+                    )
+                })
+                .collect();
+            let selected = inquire::Select::new(
+                &format!("Server '{}': which value should be kept?", name),
+                choices.clone(),
+            )
+            .prompt()?;
+            let index = choices.iter().position(|c| c == &selected).unwrap_or(0);
+
+            utils::merge_case_variant_keys(env, &group, &group[index]);
+            println!("{}", format!("✓ {}: kept {}", name, group[index]).green());
+            resolved += 1;
         }
+    }
 
-        if has_errors {
-            return Err(anyhow::anyhow!("Validation failed for one or more servers"));
-        }
+    if resolved == 0 {
+        println!("{}", "No case-colliding environment keys found.".green());
     }
 
-    println!("✅ Configuration validation completed");
-    Ok(())
+    Ok(resolved)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+
+    #[test]
+    fn test_validate_client_options() {
+        let mut server = McpServer {
+            command: Some("node".to_string()),
+            args: None,
+            url: None,
+            env: None,
+            timeout: Some(0),
+            auto_approve: Some(vec!["read".to_string(), "read".to_string()]),
+            other: HashMap::new(),
+        };
+        let mut result = ValidationResult {
+            server_name: "files".to_string(),
+            status: ValidationStatus::Valid,
+            issues: Vec::new(),
+            suggestions: Vec::new(),
+            requirements_checked: false,
+        };
+        validate_client_options(&server, &mut result);
+        let types: Vec<&str> = result
+            .issues
+            .iter()
+            .map(|i| i.issue_type.as_str())
+            .collect();
+        assert_eq!(types, ["Invalid Timeout", "Invalid Auto-Approve List"]);
+        assert!(result.issues[1].message.contains("read"));
+
+        server.timeout = Some(120);
+        server.auto_approve = Some(vec!["read".to_string(), "list".to_string()]);
+        result.issues.clear();
+        validate_client_options(&server, &mut result);
+        assert!(result.issues.is_empty());
+    }
+
+    fn launch(command: &str, args: &[&str]) -> McpServer {
+        McpServer {
+            command: Some(command.to_string()),
+            args: Some(args.iter().map(|a| a.to_string()).collect()),
+            url: None,
+            env: None,
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::new(),
+        }
+    }
+
+    /// A system with Node.js 20.11.0 and Python 3.12.1 as the defaults
+    fn installed(interpreter: Interpreter) -> Option<String> {
+        match interpreter {
+            Interpreter::Node => Some("v20.11.0".to_string()),
+            Interpreter::Python => Some("Python 3.12.1".to_string()),
+        }
+    }
+
+    fn pin_messages(server: &McpServer, in_path: &dyn Fn(&str) -> bool) -> Vec<String> {
+        version_pin_issues(server, "srv", &installed, in_path)
+            .into_iter()
+            .inspect(|issue| assert_eq!(issue.severity, ValidationStatus::Warning))
+            .map(|issue| issue.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_versioned_interpreter() {
+        assert_eq!(
+            versioned_interpreter("/usr/bin/python3.11"),
+            Some((Interpreter::Python, "3.11".to_string()))
+        );
+        assert_eq!(
+            versioned_interpreter("node18"),
+            Some((Interpreter::Node, "18".to_string()))
+        );
+        assert_eq!(versioned_interpreter("python3"), None);
+        assert_eq!(versioned_interpreter("python3-config"), None);
+        assert_eq!(versioned_interpreter("nodemon"), None);
+    }
+
+    #[test]
+    fn test_missing_versioned_interpreter_warns() {
+        let server = launch("python3.11", &["server.py"]);
+        assert_eq!(
+            pin_messages(&server, &|_| false),
+            ["Command 'python3.11' expects Python 3.11, which is not installed; the default interpreter is Python 3.12.1"]
+        );
+        assert!(pin_messages(&server, &|cmd| cmd == "python3.11").is_empty());
+
+        let issue = &version_pin_issues(&launch("node18", &[]), "srv", &installed, &|_| false)[0];
+        assert!(issue
+            .fix_suggestion
+            .as_deref()
+            .unwrap()
+            .contains("nvm install 18"));
+    }
+
+    #[test]
+    fn test_pinned_versions_in_args() {
+        let pins = pinned_versions(&launch(
+            "uvx",
+            &[
+                "--python",
+                "3.11",
+                "--node=v18",
+                "--target-version=3.12",
+                "--port",
+                "8080",
+            ],
+        ));
+        assert_eq!(
+            pins,
+            [
+                (Interpreter::Python, "3.11".to_string()),
+                (Interpreter::Node, "18".to_string()),
+                (Interpreter::Python, "3.12".to_string()),
+            ]
+        );
+
+        // Generic version flags need an interpreter to compare against
+        assert!(pinned_versions(&launch("my-server", &["--api-version=2"])).is_empty());
+        // Values that aren't plain version literals are left alone
+        assert!(
+            pinned_versions(&launch("uvx", &["--python", "pypy3", "--node-env=prod"])).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_arg_pins_compared_against_installed_version() {
+        let in_path = |_: &str| true;
+        assert_eq!(
+            pin_messages(&launch("npx", &["-y", "pkg", "--node=18"]), &in_path),
+            ["Arguments pin Node.js 18, but the installed version is v20.11.0"]
+        );
+        assert!(pin_messages(&launch("npx", &["--node=20"]), &in_path).is_empty());
+        assert!(pin_messages(&launch("uv", &["run", "--python", "3.12"]), &in_path).is_empty());
+        // The command's own pin isn't reported twice
+        assert_eq!(
+            pin_messages(
+                &launch("python3.11", &["--python-version", "3.11"]),
+                &|_| false
+            )
+            .len(),
+            1
+        );
+        // Nothing to compare against when the interpreter isn't installed
+        let none = |_: Interpreter| None;
+        assert!(
+            version_pin_issues(&launch("npx", &["--node=18"]), "srv", &none, &in_path).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_version_matches() {
+        assert!(version_matches("3.11", "Python 3.11.4"));
+        assert!(version_matches("18", "v18.17.0"));
+        assert!(!version_matches("3.1", "Python 3.11.4"));
+        assert!(!version_matches("18.2", "v18.17.0"));
+    }
+
+    #[test]
+    fn test_add_dependency_issues() {
+        let mut result = ValidationResult {
+            server_name: "gateway".to_string(),
+            status: ValidationStatus::Valid,
+            issues: Vec::new(),
+            suggestions: Vec::new(),
+            requirements_checked: false,
+        };
+        let statuses = HashMap::from([
+            ("api".to_string(), ValidationStatus::Error),
+            ("auth".to_string(), ValidationStatus::Warning),
+        ]);
+        let dependencies = ["api", "auth", "db"].map(String::from);
+
+        add_dependency_issues(&mut result, &dependencies, &statuses);
+
+        assert_eq!(result.status, ValidationStatus::Warning);
+        let types: Vec<&str> = result
+            .issues
+            .iter()
+            .map(|i| i.issue_type.as_str())
+            .collect();
+        assert_eq!(types, ["Failing Dependency", "Missing Dependency"]);
+        assert!(result.issues[1].message.contains("'db'"));
+    }
+
+    #[test]
+    fn test_add_platform_issues() {
+        let mut result = ValidationResult {
+            server_name: "win".to_string(),
+            status: ValidationStatus::Valid,
+            issues: Vec::new(),
+            suggestions: Vec::new(),
+            requirements_checked: false,
+        };
+        let provenance = crate::metadata::ServerProvenance {
+            template: "win-tools".to_string(),
+            version: "1.0.0".to_string(),
+            author: "Test".to_string(),
+            category: None,
+            tags: Vec::new(),
+            requirements: None,
+            added_at: Utc::now(),
+            notes: None,
+            variables: HashMap::new(),
+            platform_override: Some(crate::metadata::PlatformOverride {
+                platform: "linux".to_string(),
+                supported: vec!["windows".to_string()],
+            }),
+            docs_url: None,
+            pinned: None,
+        };
+
+        // Still fine once the config is used on a supported platform
+        add_platform_issues(&mut result, Some(&provenance), "windows");
+        assert!(result.issues.is_empty());
+
+        add_platform_issues(&mut result, Some(&provenance), "linux");
+        assert_eq!(result.status, ValidationStatus::Warning);
+        assert_eq!(
+            result.issues[0].message,
+            "Added with --ignore-platform: template 'win-tools' supports windows, not linux"
+        );
+    }
 
     #[test]
     fn test_validation_status_color() {
@@ -794,13 +1982,116 @@ mod tests {
         assert_eq!(ValidationStatus::Error.color(), colored::Color::Red);
     }
 
+    fn plain_header() -> ReportHeader {
+        ReportHeader {
+            title: "MCP-Forge Health Report".to_string(),
+            generated_at: DateTime::parse_from_rfc3339("2025-01-16T08:30:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            host: "buildbox".to_string(),
+            profile: "default".to_string(),
+            config_path: "/home/me/.config/claude/claude_desktop_config.json".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_plain_report_snapshot() {
+        let results = vec![
+            ValidationResult {
+                server_name: "zeta".to_string(),
+                status: ValidationStatus::Warning,
+                issues: vec![ValidationIssue {
+                    issue_type: "Unquoted Argument".to_string(),
+                    message: "Argument 1 'a b' contains spaces but isn't quoted".to_string(),
+                    severity: ValidationStatus::Warning,
+                    fix_suggestion: Some("Consider quoting arguments with spaces".to_string()),
+                    suggested_command: Some("mcp-forge edit zeta".to_string()),
+                    log_excerpt: Vec::new(),
+                }],
+                suggestions: Vec::new(),
+                requirements_checked: true,
+            },
+            ValidationResult {
+                server_name: "alpha".to_string(),
+                status: ValidationStatus::Valid,
+                issues: Vec::new(),
+                suggestions: Vec::new(),
+                requirements_checked: true,
+            },
+        ];
+
+        let expected = "\
+MCP-Forge Health Report
+=======================
+Generated: 2025-01-16 08:30:00 UTC
+Host:      buildbox
+Profile:   default
+Config:    /home/me/.config/claude/claude_desktop_config.json
+
+STATUS   SERVER ISSUES
+------   ------ ------
+OK       alpha  0
+WARN     zeta   1
+
+Details:
+
+zeta
+  - [WARN] Unquoted Argument: Argument 1 'a b' contains spaces but isn't quoted
+    Fix: Consider quoting arguments with spaces
+    Run: mcp-forge edit zeta
+
+Summary: 2 server(s), 1 OK, 1 WARN, 0 ERROR, 0 MISSING
+";
+        assert_eq!(render_plain_report(&plain_header(), &results), expected);
+    }
+
+    #[test]
+    fn test_render_plain_report_is_ascii_and_narrow() {
+        let results = vec![ValidationResult {
+            server_name: "emoji-✨".to_string(),
+            status: ValidationStatus::Error,
+            issues: vec![ValidationIssue {
+                issue_type: "Path Not Found".to_string(),
+                message: format!("Path argument '/{}' does not exist", "x".repeat(150)),
+                severity: ValidationStatus::Error,
+                fix_suggestion: None,
+                suggested_command: None,
+                log_excerpt: Vec::new(),
+            }],
+            suggestions: Vec::new(),
+            requirements_checked: false,
+        }];
+
+        let output = render_plain_report(&plain_header(), &results);
+        assert!(output.is_ascii());
+        assert!(!output.contains('\u{1b}'));
+        assert!(output.lines().all(|line| line.len() < 100));
+        assert!(output.contains("emoji-?"));
+    }
+
+    #[test]
+    fn test_report_format_parse() {
+        assert_eq!(ReportFormat::parse(None).unwrap(), ReportFormat::Default);
+        assert_eq!(
+            ReportFormat::parse(Some("plain")).unwrap(),
+            ReportFormat::Plain
+        );
+        assert_eq!(
+            ReportFormat::parse(Some("json")).unwrap(),
+            ReportFormat::Json
+        );
+        assert!(ReportFormat::parse(Some("html")).is_err());
+    }
+
     #[test]
     fn test_command_validation() {
         let server = McpServer {
             command: Some("nonexistent-command-12345".to_string()),
             args: Some(vec![]),
             url: None,
-                env: None,
+            env: None,
+            timeout: None,
+            auto_approve: None,
             other: HashMap::new(),
         };
 
@@ -817,13 +2108,134 @@ mod tests {
         assert!(matches!(result.issues[0].severity, ValidationStatus::Error));
     }
 
+    #[test]
+    fn test_common_issues_suggest_commands() {
+        let server = McpServer {
+            command: Some("/nonexistent/mcp-forge-test-binary".to_string()),
+            args: Some(vec![
+                "has space".to_string(),
+                "/nonexistent/mcp-forge-test-path".to_string(),
+            ]),
+            url: None,
+            env: Some(HashMap::from([
+                ("EMPTY".to_string(), String::new()),
+                ("DATA_DIR".to_string(), "/nonexistent/data".to_string()),
+                ("Token".to_string(), "a".to_string()),
+                ("TOKEN".to_string(), "b".to_string()),
+            ])),
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::new(),
+        };
+
+        let mut result = ValidationResult {
+            server_name: "my server".to_string(),
+            status: ValidationStatus::Valid,
+            issues: Vec::new(),
+            suggestions: Vec::new(),
+            requirements_checked: false,
+        };
+        validate_command_exists(&server, &mut result);
+        validate_arguments(&server, &mut result);
+        validate_environment(&server, &mut result);
+
+        let command_for = |issue_type: &str| {
+            result
+                .issues
+                .iter()
+                .find(|i| i.issue_type == issue_type)
+                .and_then(|i| i.suggested_command.clone())
+                .unwrap_or_else(|| panic!("no suggested command for {}", issue_type))
+        };
+
+        assert_eq!(
+            command_for("Command Not Found"),
+            "mcp-forge edit 'my server'"
+        );
+        assert_eq!(
+            command_for("Unquoted Argument"),
+            "mcp-forge edit 'my server'"
+        );
+        assert_eq!(command_for("Path Not Found"), "mcp-forge edit 'my server'");
+        assert_eq!(
+            command_for("Empty Environment Variable"),
+            "mcp-forge edit 'my server'"
+        );
+        assert_eq!(
+            command_for("Environment Path Not Found"),
+            "mcp-forge update 'my server' --set DATA_DIR=<path>"
+        );
+        assert_eq!(
+            command_for("Case-Colliding Environment Keys"),
+            "mcp-forge config normalize"
+        );
+
+        let mut result = ValidationResult {
+            server_name: "fs".to_string(),
+            status: ValidationStatus::Valid,
+            issues: Vec::new(),
+            suggestions: Vec::new(),
+            requirements_checked: false,
+        };
+        let server = McpServer {
+            command: Some("mcp-forge-test-missing-command".to_string()),
+            ..server
+        };
+        validate_command_exists(&server, &mut result);
+        assert_eq!(
+            result.issues[0].suggested_command.as_deref(),
+            Some("mcp-forge validate --requirements fs")
+        );
+
+        // Wrappers read the command from the JSON output
+        let json = serde_json::to_value(&result.issues[0]).unwrap();
+        assert_eq!(
+            json["suggested_command"],
+            "mcp-forge validate --requirements fs"
+        );
+    }
+
+    #[test]
+    fn test_case_colliding_env_keys_warning() {
+        let server = McpServer {
+            command: None,
+            args: None,
+            url: Some("https://example.com/mcp".to_string()),
+            env: Some(HashMap::from([
+                ("API_KEY".to_string(), "one".to_string()),
+                ("api_key".to_string(), "two".to_string()),
+            ])),
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::new(),
+        };
+
+        let mut result = ValidationResult {
+            server_name: "test".to_string(),
+            status: ValidationStatus::Valid,
+            issues: Vec::new(),
+            suggestions: Vec::new(),
+            requirements_checked: false,
+        };
+
+        validate_environment(&server, &mut result);
+        assert_eq!(result.issues.len(), 1);
+        assert_eq!(
+            result.issues[0].issue_type,
+            "Case-Colliding Environment Keys"
+        );
+        assert!(result.issues[0].message.contains("API_KEY, api_key"));
+    }
+
     #[test]
     fn test_argument_validation() {
         let server = McpServer {
             command: Some("test".to_string()),
             args: Some(vec!["file with spaces".to_string()]),
             url: None,
-                env: None,
+            env: None,
+            timeout: None,
+            auto_approve: None,
             other: HashMap::new(),
         };
 
@@ -0,0 +1,370 @@
+use crate::backup::{self, BackupServerVersion};
+use crate::config::{Config, McpServer, Target};
+use crate::journal::{self, JournalEntry};
+use crate::metadata::{ServerMetadata, ServerProvenance};
+use crate::profiles;
+use crate::timefmt::{self, Zone};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// Everything mcp-forge has recorded about how a server came to be configured
+#[derive(Debug, Serialize)]
+pub struct WhyReport {
+    pub server: String,
+    pub profile: Option<String>,
+    /// Whether the server is in the config right now
+    pub configured: bool,
+    pub provenance: Option<ServerProvenance>,
+    pub created: Option<TimelineEvent>,
+    pub last_modified: Option<TimelineEvent>,
+    /// Template and journal events, oldest first
+    pub timeline: Vec<TimelineEvent>,
+    /// Backups holding a copy of the server, newest first
+    pub backups: Vec<BackupHint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimelineEvent {
+    pub timestamp: DateTime<Utc>,
+    pub description: String,
+    pub success: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackupHint {
+    pub backup: String,
+    pub created_at: DateTime<Utc>,
+    pub file: PathBuf,
+    /// How the backed-up copy differs from the current server
+    pub diff: String,
+}
+
+/// Assemble the report from the three stores
+pub fn build_report(
+    name: &str,
+    profile: Option<String>,
+    current: Option<&McpServer>,
+    provenance: Option<ServerProvenance>,
+    journal: &[JournalEntry],
+    backups: Vec<BackupServerVersion>,
+) -> WhyReport {
+    let mut timeline: Vec<TimelineEvent> = journal
+        .iter()
+        .filter(|entry| entry.profile == profile && entry.servers.iter().any(|s| s == name))
+        .map(|entry| TimelineEvent {
+            timestamp: entry.timestamp,
+            description: describe_entry(entry),
+            success: entry.success,
+        })
+        .collect();
+    if let Some(provenance) = &provenance {
+        timeline.push(TimelineEvent {
+            timestamp: provenance.added_at,
+            description: format!(
+                "created from template {} v{}",
+                provenance.template, provenance.version
+            ),
+            success: true,
+        });
+    }
+    timeline.sort_by_key(|event| event.timestamp);
+
+    let created = timeline.iter().find(|event| event.success).cloned();
+    let last_modified = timeline
+        .iter()
+        .rev()
+        .find(|event| event.success)
+        .filter(|event| Some(*event) != created.as_ref())
+        .cloned();
+
+    WhyReport {
+        server: name.to_string(),
+        profile,
+        configured: current.is_some(),
+        provenance,
+        created,
+        last_modified,
+        timeline,
+        backups: backups
+            .into_iter()
+            .map(|version| BackupHint {
+                diff: diff_hint(&version.server, current),
+                backup: version.backup,
+                created_at: version.created_at,
+                file: version.file,
+            })
+            .collect(),
+    }
+}
+
+fn describe_entry(entry: &JournalEntry) -> String {
+    let mut description = format!("`{}`", entry.command);
+    if let Some(error) = &entry.error {
+        description.push_str(&format!(" failed: {}", error));
+    }
+    if let Some(backup) = &entry.backup {
+        description.push_str(&format!(" (backup: {})", backup));
+    }
+    description
+}
+
+/// One line on how a backed-up server differs from the current one
+fn diff_hint(old: &McpServer, current: Option<&McpServer>) -> String {
+    let Some(current) = current else {
+        return "server has since been removed".to_string();
+    };
+
+    let mut changes = Vec::new();
+    if old.command != current.command {
+        changes.push("command".to_string());
+    }
+    if old.args != current.args {
+        changes.push("args".to_string());
+    }
+    if old.url != current.url {
+        changes.push("url".to_string());
+    }
+
+    let empty = Default::default();
+    let old_env = old.env.as_ref().unwrap_or(&empty);
+    let new_env = current.env.as_ref().unwrap_or(&empty);
+    let keys: BTreeSet<&String> = old_env.keys().chain(new_env.keys()).collect();
+    let env_changes: Vec<String> = keys
+        .into_iter()
+        .filter_map(|key| match (old_env.get(key), new_env.get(key)) {
+            (Some(_), None) => Some(format!("-{}", key)),
+            (None, Some(_)) => Some(format!("+{}", key)),
+            (Some(a), Some(b)) if a != b => Some(format!("~{}", key)),
+            _ => None,
+        })
+        .collect();
+    if !env_changes.is_empty() {
+        changes.push(format!("env ({})", env_changes.join(" ")));
+    }
+
+    if old.timeout != current.timeout {
+        changes.push("timeout".to_string());
+    }
+    if old.auto_approve != current.auto_approve {
+        changes.push("autoApprove".to_string());
+    }
+    if old.other != current.other {
+        changes.push("other fields".to_string());
+    }
+
+    if changes.is_empty() {
+        "same as current".to_string()
+    } else {
+        format!("differs in {}", changes.join(", "))
+    }
+}
+
+/// Handle `mcp-forge why`
+pub async fn handle_why(
+    name: String,
+    json: bool,
+    target: &Target,
+    profile: Option<String>,
+    zone: Zone,
+) -> Result<()> {
+    let config = Config::load(target).await?;
+    let current = config.get_server(&name);
+    let provenance = ServerMetadata::load(target)?.get(&name).cloned();
+    let profile = profiles::effective_profile(profile.as_deref()).await?;
+    let report = build_report(
+        &name,
+        profile,
+        current,
+        provenance,
+        &journal::entries(target)?,
+        backup::server_versions(target, &name).await?,
+    );
+
+    if !report.configured
+        && report.provenance.is_none()
+        && report.timeline.is_empty()
+        && report.backups.is_empty()
+    {
+        return Err(anyhow!(
+            "Server '{}' is not configured and has no recorded history",
+            name
+        ));
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    display_report(&report, zone);
+    Ok(())
+}
+
+fn display_report(report: &WhyReport, zone: Zone) {
+    let now = Utc::now();
+    println!(
+        "{}",
+        format!("History of '{}'", report.server).cyan().bold()
+    );
+    println!("{}", "─".repeat(report.server.len() + 13).cyan());
+
+    let profile = report.profile.as_deref().unwrap_or("default");
+    if report.configured {
+        println!("Status: configured in profile {}", profile);
+    } else {
+        println!(
+            "Status: {}",
+            format!("not in the config for profile {}", profile).yellow()
+        );
+    }
+
+    if let Some(provenance) = &report.provenance {
+        println!(
+            "Template: {} v{} by {}",
+            provenance.template, provenance.version, provenance.author
+        );
+        if !provenance.variables.is_empty() {
+            let mut variables: Vec<String> = provenance
+                .variables
+                .iter()
+                .map(|(name, value)| match value {
+                    serde_json::Value::String(s) => format!("{}={}", name, s),
+                    other => format!("{}={}", name, other),
+                })
+                .collect();
+            variables.sort();
+            println!("  Variables: {}", variables.join(", "));
+        }
+    }
+
+    for (label, event) in [
+        ("Created", &report.created),
+        ("Last modified", &report.last_modified),
+    ] {
+        if let Some(event) = event {
+            println!(
+                "{}: {} by {}",
+                label,
+                timefmt::format_age(event.timestamp, now),
+                event.description
+            );
+        }
+    }
+
+    if !report.timeline.is_empty() {
+        println!();
+        println!("{}", "Timeline:".bold());
+        for event in &report.timeline {
+            let status = if event.success {
+                "✓".green()
+            } else {
+                "✗".red()
+            };
+            println!(
+                "  {} {}  {}",
+                status,
+                timefmt::format_timestamp(event.timestamp, zone).dimmed(),
+                event.description
+            );
+        }
+    }
+
+    if !report.backups.is_empty() {
+        println!();
+        println!("{}", "Backups with a copy:".bold());
+        for hint in &report.backups {
+            println!(
+                "  • {} ({}): {}",
+                hint.backup,
+                timefmt::format_timestamp(hint.created_at, zone),
+                hint.diff
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn server(args: &[&str], env: &[(&str, &str)]) -> McpServer {
+        McpServer {
+            command: Some("npx".to_string()),
+            args: Some(args.iter().map(|a| a.to_string()).collect()),
+            url: None,
+            env: Some(
+                env.iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            ),
+            timeout: None,
+            auto_approve: None,
+            other: HashMap::new(),
+        }
+    }
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn entry(time: &str, command: &str, servers: &[&str], success: bool) -> JournalEntry {
+        JournalEntry {
+            timestamp: at(time),
+            command: command.to_string(),
+            profile: None,
+            servers: servers.iter().map(|s| s.to_string()).collect(),
+            backup: None,
+            success,
+            error: (!success).then(|| "boom".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_diff_hint() {
+        let current = server(&["a"], &[("KEEP", "1"), ("TOKEN", "new"), ("ADDED", "x")]);
+        let old = server(&["b"], &[("KEEP", "1"), ("TOKEN", "old"), ("GONE", "y")]);
+        assert_eq!(
+            diff_hint(&old, Some(&current)),
+            "differs in args, env (+ADDED -GONE ~TOKEN)"
+        );
+        assert_eq!(diff_hint(&current, Some(&current)), "same as current");
+        assert_eq!(diff_hint(&old, None), "server has since been removed");
+    }
+
+    #[test]
+    fn test_build_report_orders_timeline() {
+        let journal = [
+            entry("2026-03-01T10:00:00Z", "add", &["db"], true),
+            entry("2026-03-02T10:00:00Z", "update", &["db"], false),
+            entry("2026-03-03T10:00:00Z", "update", &["db", "other"], true),
+            entry("2026-03-04T10:00:00Z", "remove", &["other"], true),
+        ];
+        let current = server(&["a"], &[]);
+        let report = build_report("db", None, Some(&current), None, &journal, Vec::new());
+
+        assert!(report.configured);
+        assert_eq!(report.timeline.len(), 3);
+        assert_eq!(report.created.unwrap().description, "`add`");
+        let modified = report.last_modified.unwrap();
+        assert_eq!(modified.timestamp, at("2026-03-03T10:00:00Z"));
+        assert_eq!(report.timeline[1].description, "`update` failed: boom");
+
+        // Entries from other profiles belong to a different server of the same name
+        let report = build_report(
+            "db",
+            Some("work".to_string()),
+            None,
+            None,
+            &journal,
+            Vec::new(),
+        );
+        assert!(report.timeline.is_empty() && report.created.is_none());
+    }
+}
@@ -0,0 +1,215 @@
+use crate::bulk::{self, BatchConfig};
+use crate::config::{Config, McpServer, Target};
+use crate::github::ClientOptions;
+use crate::templates::TemplateManager;
+use anyhow::{anyhow, Result};
+use clap::Subcommand;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// Directory, inside a project, holding its server definitions
+const WORKSPACE_DIR: &str = ".mcp-forge";
+
+/// Server definition files looked for in the workspace directory, in order
+const WORKSPACE_FILES: &[&str] = &["servers.yaml", "servers.yml"];
+
+/// How many parent directories above the current one are searched
+const MAX_DEPTH: usize = 8;
+
+#[derive(Subcommand)]
+pub enum WorkspaceCommands {
+    /// Add the servers declared in the workspace file to the configuration
+    Install {
+        /// Preview changes without applying
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show which workspace file applies to the current directory
+    Path,
+}
+
+/// Servers declared in a project's `.mcp-forge/servers.yaml`
+pub struct WorkspaceServers {
+    pub file: PathBuf,
+    pub servers: Vec<(String, McpServer)>,
+}
+
+/// Handle workspace command routing
+pub async fn handle_workspace_command(
+    action: WorkspaceCommands,
+    target: &Target,
+    github: &ClientOptions,
+    profile: Option<String>,
+) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let file = find_workspace_file(&cwd).ok_or_else(|| {
+        anyhow!(
+            "No {}/{} found in {} or up to {} directories above it",
+            WORKSPACE_DIR,
+            WORKSPACE_FILES[0],
+            cwd.display(),
+            MAX_DEPTH
+        )
+    })?;
+
+    match action {
+        WorkspaceCommands::Install { dry_run } => {
+            println!(
+                "Workspace servers from {}",
+                file.display().to_string().bold()
+            );
+            println!();
+            bulk::handle_bulk_add(
+                file.display().to_string(),
+                dry_run,
+                false,
+                None,
+                false,
+                false,
+                false,
+                bulk::FailureMode::default(),
+                target,
+                github,
+                profile,
+            )
+            .await
+        }
+        WorkspaceCommands::Path => {
+            println!("{}", file.display());
+            Ok(())
+        }
+    }
+}
+
+/// Whether read commands merge in workspace servers, set with
+/// `MCP_FORGE_WORKSPACE_DISCOVERY`
+pub fn discovery_enabled() -> bool {
+    discovery_enabled_from(
+        std::env::var("MCP_FORGE_WORKSPACE_DISCOVERY")
+            .ok()
+            .as_deref(),
+    )
+}
+
+fn discovery_enabled_from(setting: Option<&str>) -> bool {
+    matches!(
+        setting.map(|value| value.trim().to_lowercase()).as_deref(),
+        Some("1" | "true" | "yes" | "on")
+    )
+}
+
+/// The nearest workspace file at or above `start`
+pub fn find_workspace_file(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .take(MAX_DEPTH + 1)
+        .flat_map(|dir| {
+            WORKSPACE_FILES
+                .iter()
+                .map(move |name| dir.join(WORKSPACE_DIR).join(name))
+        })
+        .find(|path| path.is_file())
+}
+
+/// Workspace servers for the current directory, when discovery is turned on
+///
+/// Servers the configuration already has are left out, as the configured one
+/// is what the client runs. Entries that can't be resolved are skipped with a
+/// warning.
+pub async fn discover(config: &Config, github: &ClientOptions) -> Result<Option<WorkspaceServers>> {
+    if !discovery_enabled() {
+        return Ok(None);
+    }
+    let Some(file) = find_workspace_file(&std::env::current_dir()?) else {
+        return Ok(None);
+    };
+
+    let batch = bulk::load_batch_config(&file.display().to_string()).await?;
+    let servers = resolve_servers(&batch, config, &file, github).await?;
+    Ok(Some(WorkspaceServers { file, servers }))
+}
+
+async fn resolve_servers(
+    batch: &BatchConfig,
+    config: &Config,
+    file: &Path,
+    github: &ClientOptions,
+) -> Result<Vec<(String, McpServer)>> {
+    let needs_templates = batch.servers.iter().any(|s| s.template.is_some());
+    let template_manager = if needs_templates {
+        Some(TemplateManager::new(github)?)
+    } else {
+        None
+    };
+
+    let mut servers = Vec::new();
+    for entry in &batch.servers {
+        if config.mcp_servers.contains_key(&entry.name) {
+            eprintln!(
+                "{} Workspace server '{}' in {} is shadowed by the configured server of that name",
+                "ℹ".cyan(),
+                entry.name,
+                file.display()
+            );
+            continue;
+        }
+        let resolved = match &template_manager {
+            Some(manager) => entry.resolve(manager).await.map(|(server, _)| server),
+            None => entry.resolve_inline(),
+        };
+        match resolved {
+            Ok(server) => servers.push((entry.name.clone(), server)),
+            Err(e) => eprintln!(
+                "{} Skipping workspace server '{}': {}",
+                "⚠".yellow(),
+                entry.name,
+                e
+            ),
+        }
+    }
+    Ok(servers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_discovery_setting() {
+        assert!(!discovery_enabled_from(None));
+        assert!(discovery_enabled_from(Some("1")));
+        assert!(discovery_enabled_from(Some("true")));
+        assert!(discovery_enabled_from(Some(" On ")));
+        assert!(!discovery_enabled_from(Some("0")));
+        assert!(!discovery_enabled_from(Some("off")));
+    }
+
+    #[test]
+    fn test_find_workspace_file_walks_up_to_the_depth_limit() {
+        let root = tempfile::TempDir::new().unwrap();
+        let workspace = root.path().join(WORKSPACE_DIR);
+        fs::create_dir_all(&workspace).unwrap();
+        fs::write(workspace.join("servers.yaml"), "servers: []").unwrap();
+
+        let nested = root.path().join("a/b/c");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(
+            find_workspace_file(&nested),
+            Some(workspace.join("servers.yaml"))
+        );
+
+        // A nearer workspace wins
+        let inner = root.path().join("a").join(WORKSPACE_DIR);
+        fs::create_dir_all(&inner).unwrap();
+        fs::write(inner.join("servers.yml"), "servers: []").unwrap();
+        assert_eq!(
+            find_workspace_file(&nested),
+            Some(inner.join("servers.yml"))
+        );
+
+        let deep = root.path().join("1/2/3/4/5/6/7/8/9");
+        fs::create_dir_all(&deep).unwrap();
+        assert_eq!(find_workspace_file(&deep), None);
+    }
+}
@@ -0,0 +1,131 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const LIVE_CONFIG: &str = r#"{
+  "mcpServers": {
+    "keep": { "command": "node", "args": ["keep.js"] },
+    "drift": { "command": "node", "args": ["old.js"] },
+    "extra": { "command": "python", "args": [] }
+  }
+}"#;
+
+const DESIRED: &str = r#"
+servers:
+  - name: keep
+    command: node
+    args: ["keep.js"]
+  - name: drift
+    command: node
+    args: ["new.js"]
+  - name: fresh
+    url: https://example.com/mcp
+"#;
+
+fn setup() -> (TempDir, PathBuf) {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), LIVE_CONFIG);
+    let desired = home.path().join("servers.yaml");
+    fs::write(&desired, DESIRED).unwrap();
+    (home, desired)
+}
+
+#[test]
+fn dry_run_reports_plan_without_changing_config() {
+    let (home, desired) = setup();
+
+    mcp_forge(home.path())
+        .args(["apply", desired.to_str().unwrap(), "--dry-run", "--prune"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("NEW fresh"))
+        .stdout(predicate::str::contains("UPDATE drift"))
+        .stdout(predicate::str::contains("REMOVE extra"));
+
+    let config = read_config(home.path());
+    assert!(config["mcpServers"].get("extra").is_some());
+    assert!(config["mcpServers"].get("fresh").is_none());
+}
+
+#[test]
+fn dry_run_exit_code_signals_drift() {
+    let (home, desired) = setup();
+
+    mcp_forge(home.path())
+        .args([
+            "apply",
+            desired.to_str().unwrap(),
+            "--dry-run",
+            "--exit-code",
+        ])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn apply_converges_and_is_idempotent() {
+    let (home, desired) = setup();
+
+    mcp_forge(home.path())
+        .args(["apply", desired.to_str().unwrap(), "--prune"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 added, 1 updated, 1 removed"));
+
+    let config = read_config(home.path());
+    let servers = config["mcpServers"].as_object().unwrap();
+    assert_eq!(servers.len(), 3);
+    assert_eq!(servers["drift"]["args"][0], "new.js");
+    assert_eq!(servers["fresh"]["url"], "https://example.com/mcp");
+    assert!(!servers.contains_key("extra"));
+
+    mcp_forge(home.path())
+        .args([
+            "apply",
+            desired.to_str().unwrap(),
+            "--prune",
+            "--dry-run",
+            "--exit-code",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("already matches"));
+}
+
+#[test]
+fn apply_without_prune_keeps_undeclared_servers() {
+    let (home, desired) = setup();
+
+    mcp_forge(home.path())
+        .args(["apply", desired.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let config = read_config(home.path());
+    assert!(config["mcpServers"].get("extra").is_some());
+}
+
+#[test]
+fn duplicate_declarations_are_rejected() {
+    let (home, _) = setup();
+    let desired = home.path().join("dupes.yaml");
+    fs::write(
+        &desired,
+        "servers:\n  - name: a\n    command: node\n  - name: a\n    command: python\n",
+    )
+    .unwrap();
+
+    mcp_forge(home.path())
+        .args(["apply", desired.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("declared more than once"));
+}
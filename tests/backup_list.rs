@@ -0,0 +1,110 @@
+// These tests redirect the config directory through $HOME, which `dirs` only
+// honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Write a backup named `name` taken `days_ago` days ago, holding `servers` servers
+fn write_backup(home: &Path, name: &str, days_ago: i64, servers: usize) {
+    let created_at = chrono::Utc::now() - chrono::Duration::days(days_ago);
+    let mcp_servers: serde_json::Map<String, serde_json::Value> = (0..servers)
+        .map(|i| {
+            (
+                format!("server-{}", i),
+                serde_json::json!({ "command": "npx" }),
+            )
+        })
+        .collect();
+    let backup = serde_json::json!({
+        "metadata": {
+            "name": name,
+            "created_at": created_at,
+            "servers_count": servers,
+            "description": null,
+            "git_branch": null,
+            "git_commit": null
+        },
+        "config": { "mcpServers": mcp_servers }
+    });
+    let path = config_path(home).with_file_name(format!("backups/{}.json", name));
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, serde_json::to_string_pretty(&backup).unwrap()).unwrap();
+}
+
+fn setup(count: i64) -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    for day in 0..count {
+        write_backup(home.path(), &format!("auto_{:02}", day), day, 2);
+    }
+    home
+}
+
+fn list(home: &TempDir, args: &[&str]) -> String {
+    let output = mcp_forge(home.path())
+        .args(["backup", "list"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn filters_combine_and_keep_newest_first() {
+    let home = setup(10);
+    write_backup(home.path(), "pre-migration", 4, 7);
+
+    let stdout = list(
+        &home,
+        &["--since", "7d", "--name-contains", "AUTO", "--limit", "3"],
+    );
+    let names: Vec<&str> = stdout
+        .lines()
+        .filter_map(|line| line.strip_prefix("• "))
+        .collect();
+    assert_eq!(names, ["auto_00", "auto_01", "auto_02"]);
+    assert!(stdout.contains("Showing 3 of 11 backup(s)"));
+
+    let stdout = list(&home, &["--until", "3d", "--since", "5d", "--oneline"]);
+    assert!(stdout.contains("pre-migration"));
+    assert!(stdout.contains("7 servers"));
+    assert!(!stdout.contains("auto_02") && !stdout.contains("auto_06"));
+
+    assert!(list(&home, &["--name-contains", "nothing"]).contains("None of the 11 backup(s) match"));
+
+    mcp_forge(home.path())
+        .args(["backup", "list", "--since", "last week"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid time 'last week'"));
+}
+
+#[test]
+fn many_backups_default_to_one_line_each() {
+    let home = setup(20);
+    let stdout = list(&home, &[]);
+    assert!(stdout.contains("• auto_00"));
+    assert!(stdout.contains("  Servers: 2"));
+
+    write_backup(home.path(), "auto_20", 20, 2);
+    let stdout = list(&home, &[]);
+    assert!(!stdout.contains("• "));
+    let line = stdout.lines().find(|l| l.starts_with("auto_20")).unwrap();
+    assert!(line.contains("20 days ago") && line.contains("2 servers") && line.contains(" B"));
+
+    // --long brings the details back, and a limit can drop back under the threshold
+    assert!(list(&home, &["--long"]).contains("• auto_20"));
+    assert!(list(&home, &["--limit", "5"]).contains("• auto_04"));
+
+    mcp_forge(home.path())
+        .args(["backup", "list", "--oneline", "--long"])
+        .assert()
+        .failure();
+}
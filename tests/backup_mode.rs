@@ -0,0 +1,130 @@
+// These tests redirect the config directory through $HOME, which `dirs` only
+// honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "files": { "command": "npx", "args": [] },
+    "notes": { "command": "npx", "args": [] }
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    home
+}
+
+fn backup_dir(home: &TempDir) -> PathBuf {
+    config_path(home.path()).with_file_name("backups")
+}
+
+fn backup_names(home: &TempDir) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(backup_dir(home)) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .map(|e| {
+            e.unwrap()
+                .path()
+                .file_stem()
+                .unwrap()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn auto_backup_creates_the_directory_and_names_the_backup() {
+    let home = setup();
+    assert!(!backup_dir(&home).exists());
+
+    let output = mcp_forge(home.path())
+        .args(["update", "files", "--set", "LOG=debug"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let names = backup_names(&home);
+    assert_eq!(names.len(), 1);
+    assert!(names[0].starts_with("config_backup_"));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(&format!("Backup: {}", names[0])));
+
+    // The backup holds the config from before the change, and can be restored
+    let backup: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(backup_dir(&home).join(format!("{}.json", names[0]))).unwrap(),
+    )
+    .unwrap();
+    assert!(backup["config"]["mcpServers"]["files"]["env"].is_null());
+    mcp_forge(home.path())
+        .args(["backup", "restore", &names[0], "--server", "files"])
+        .assert()
+        .success();
+    assert!(read_config(home.path())["mcpServers"]["files"]["env"]["LOG"].is_null());
+}
+
+#[test]
+fn none_skips_the_backup() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["--backup", "none", "remove", "notes", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Backup:").not());
+
+    assert!(read_config(home.path())["mcpServers"]["notes"].is_null());
+    assert!(backup_names(&home).is_empty());
+}
+
+#[test]
+fn named_backup_uses_the_name_and_refuses_to_overwrite() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["bulk", "update", "--set", "LOG=debug", "--pattern", "files"])
+        .args(["--backup", "named:pre-migration"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Backup: pre-migration"));
+    assert_eq!(backup_names(&home), ["pre-migration"]);
+
+    mcp_forge(home.path())
+        .args(["update", "notes", "--set", "LOG=debug"])
+        .args(["--backup", "named:pre-migration"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "A backup named 'pre-migration' already exists",
+        ));
+    assert!(read_config(home.path())["mcpServers"]["notes"]["env"].is_null());
+
+    mcp_forge(home.path())
+        .args(["--backup", "named:", "list"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("needs a backup name"));
+}
+
+#[test]
+fn dry_runs_take_no_backup() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["update", "files", "--set", "LOG=debug", "--dry-run"])
+        .assert()
+        .success();
+    assert!(backup_names(&home).is_empty());
+}
@@ -0,0 +1,104 @@
+// These tests redirect the config directory through $HOME, which `dirs` only
+// honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Take a backup holding a single server named `server`
+fn backup_with(home: &TempDir, name: &str, server: &str) {
+    write_config(
+        home.path(),
+        &format!(
+            r#"{{ "mcpServers": {{ "{}": {{ "command": "npx" }} }} }}"#,
+            server
+        ),
+    );
+    mcp_forge(home.path())
+        .args(["backup", "create", "--name", name])
+        .assert()
+        .success();
+}
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    backup_with(&home, "release-2024-01", "january");
+    backup_with(&home, "release-2024-02", "february");
+    backup_with(&home, "release-2024", "year");
+    home
+}
+
+#[test]
+fn ambiguous_partial_name_lists_candidates() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["backup", "restore", "2024-0", "--preview"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'2024-0' matches 2 backups"))
+        .stderr(predicate::str::contains("release-2024-01"))
+        .stderr(predicate::str::contains("release-2024-02"))
+        .stderr(predicate::str::contains("--latest-match"));
+}
+
+#[test]
+fn latest_match_picks_the_newest_candidate() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["backup", "restore", "2024-0", "--preview", "--latest-match"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("february"))
+        .stdout(predicate::str::contains("january").not());
+
+    mcp_forge(home.path())
+        .args(["config", "restore", "2024-0", "--preview", "--latest-match"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("february"));
+}
+
+#[test]
+fn exact_name_wins_over_partial_matches() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["backup", "restore", "release-2024", "--preview"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("year"))
+        .stdout(predicate::str::contains("february").not());
+}
+
+#[test]
+fn restore_accepts_a_backup_file_path() {
+    let home = setup();
+    let copy = home.path().join("copied-backup.json");
+    fs::copy(
+        config_path(home.path()).with_file_name("backups/release-2024-01.json"),
+        &copy,
+    )
+    .unwrap();
+
+    mcp_forge(home.path())
+        .args(["backup", "restore", "--preview"])
+        .arg(&copy)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("january"));
+
+    let not_a_backup = home.path().join("servers.json");
+    fs::write(&not_a_backup, r#"{ "mcpServers": {} }"#).unwrap();
+    mcp_forge(home.path())
+        .args(["backup", "restore", "--preview"])
+        .arg(&not_a_backup)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not an mcp-forge backup"));
+}
@@ -0,0 +1,115 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Passes the pre-flight check but fails to render when applied
+const BROKEN_TEMPLATE: &str = r#"{
+  "name": "broken",
+  "version": "1.0.0",
+  "description": "Broken",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {},
+  "config": { "command": "{{undeclared}}", "args": [] }
+}"#;
+
+const BATCH: &str = "servers:
+  - name: first
+    command: node
+    args: [first.js]
+  - name: broken
+    template: broken
+  - name: third
+    command: node
+    args: [third.js]
+";
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    seed_templates(home.path(), &[("broken", BROKEN_TEMPLATE)]);
+    fs::write(home.path().join("batch.yaml"), BATCH).unwrap();
+    home
+}
+
+fn bulk_add(home: &TempDir, extra: &[&str]) -> assert_cmd::assert::Assert {
+    offline(home.path())
+        .args(["bulk", "add", "--file"])
+        .arg(home.path().join("batch.yaml"))
+        .args(extra)
+        .assert()
+}
+
+fn server_names(home: &TempDir) -> Vec<String> {
+    let mut names: Vec<String> = read_config(home.path())["mcpServers"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn continues_past_failures_by_default() {
+    let home = setup();
+
+    bulk_add(&home, &[]).success().stdout(
+        predicate::str::contains("Attempted: 3")
+            .and(predicate::str::contains("Succeeded: 2"))
+            .and(predicate::str::contains("Failed:    1"))
+            .and(predicate::str::contains("Skipped:   0")),
+    );
+    assert_eq!(server_names(&home), ["first", "third"]);
+}
+
+#[test]
+fn fail_fast_stops_without_saving() {
+    let home = setup();
+
+    bulk_add(&home, &["--fail-fast"])
+        .failure()
+        .stdout(
+            predicate::str::contains("Attempted: 2")
+                .and(predicate::str::contains("Skipped:   1"))
+                .and(predicate::str::contains("Not attempted")),
+        )
+        .stderr(
+            predicate::str::contains("Stopped at 'broken'").and(predicate::str::contains(
+                "pass --save-partial to keep the 1 server(s) added",
+            )),
+        );
+    assert!(server_names(&home).is_empty());
+}
+
+#[test]
+fn save_partial_keeps_servers_before_the_failure() {
+    let home = setup();
+
+    bulk_add(&home, &["--fail-fast", "--save-partial"])
+        .failure()
+        .stderr(predicate::str::contains(
+            "the 1 server(s) added before it were saved",
+        ));
+    assert_eq!(server_names(&home), ["first"]);
+}
+
+#[test]
+fn fail_fast_with_state_needs_save_partial() {
+    let home = setup();
+    let state = home.path().join("state.json");
+
+    bulk_add(&home, &["--fail-fast", "--state", state.to_str().unwrap()])
+        .failure()
+        .stderr(predicate::str::contains("--save-partial"));
+    assert!(server_names(&home).is_empty());
+}
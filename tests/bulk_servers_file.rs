@@ -0,0 +1,119 @@
+// These tests redirect the config directory through $HOME, which `dirs` only
+// honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "db-prod": { "command": "npx", "args": [] },
+    "db-test": { "command": "npx", "args": [] },
+    "api-prod": { "command": "npx", "args": [] },
+    "api-test": { "command": "npx", "args": [] }
+  }
+}"#;
+
+fn setup(listed: &str) -> (TempDir, std::path::PathBuf) {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    let file = home.path().join("servers.txt");
+    fs::write(&file, listed).unwrap();
+    (home, file)
+}
+
+fn env_of(home: &TempDir, name: &str) -> serde_json::Value {
+    read_config(home.path())["mcpServers"][name]["env"].clone()
+}
+
+#[test]
+fn update_targets_listed_servers_within_the_pattern() {
+    let (home, file) = setup("db-prod\napi-test\napi-prod\n");
+
+    mcp_forge(home.path())
+        .args(["bulk", "update", "--set", "LOG=debug", "--pattern", "prod"])
+        .arg("--servers-file")
+        .arg(&file)
+        .assert()
+        .success();
+
+    assert_eq!(env_of(&home, "db-prod")["LOG"], "debug");
+    assert_eq!(env_of(&home, "api-prod")["LOG"], "debug");
+    assert!(env_of(&home, "api-test").is_null());
+    assert!(env_of(&home, "db-test").is_null());
+}
+
+#[test]
+fn dry_run_previews_the_listed_servers() {
+    let (home, file) = setup(r#"["db-test", "api-test"]"#);
+
+    mcp_forge(home.path())
+        .args(["bulk", "update", "--set", "LOG=debug", "--dry-run"])
+        .arg("--servers-file")
+        .arg(&file)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("db-test"))
+        .stdout(predicate::str::contains("api-test"))
+        .stdout(predicate::str::contains("db-prod").not());
+    assert!(env_of(&home, "db-test").is_null());
+}
+
+#[test]
+fn unknown_names_warn_unless_strict() {
+    let (home, file) = setup("db-test\ndb-tset\nweb\n");
+
+    mcp_forge(home.path())
+        .args(["bulk", "update", "--set", "LOG=debug"])
+        .arg("--servers-file")
+        .arg(&file)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("'db-tset' is not configured"))
+        .stderr(predicate::str::contains("'web' is not configured"));
+    assert_eq!(env_of(&home, "db-test")["LOG"], "debug");
+
+    mcp_forge(home.path())
+        .args(["bulk", "remove", "--force", "--strict"])
+        .arg("--servers-file")
+        .arg(&file)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Servers file lists 2 unknown server(s): db-tset, web",
+        ));
+    assert_eq!(
+        read_config(home.path())["mcpServers"]
+            .as_object()
+            .unwrap()
+            .len(),
+        4
+    );
+}
+
+#[test]
+fn remove_takes_a_servers_file_instead_of_a_pattern() {
+    let (home, file) = setup("db-test\napi-test\n");
+
+    mcp_forge(home.path())
+        .args(["bulk", "remove", "--force"])
+        .arg("--servers-file")
+        .arg(&file)
+        .assert()
+        .success();
+
+    let config = read_config(home.path());
+    let mut remaining: Vec<&String> = config["mcpServers"].as_object().unwrap().keys().collect();
+    remaining.sort();
+    assert_eq!(remaining, ["api-prod", "db-prod"]);
+
+    mcp_forge(home.path())
+        .args(["bulk", "remove", "--force"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--pattern"));
+}
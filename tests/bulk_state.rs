@@ -0,0 +1,113 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Template whose command renders only once `fixed` is true
+fn template(fixed: bool) -> String {
+    let command = if fixed { "echo-mcp" } else { "{{undeclared}}" };
+    format!(
+        r#"{{
+  "name": "echo",
+  "version": "1.0.0",
+  "description": "Echo",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {{}},
+  "config": {{ "command": "{}", "args": [] }}
+}}"#,
+        command
+    )
+}
+
+const BATCH: &str = "servers:
+  - name: first
+    command: node
+    args: [first.js]
+  - name: second
+    command: node
+    args: [second.js]
+  - name: third
+    template: echo
+";
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    seed_templates(home.path(), &[("echo", &template(false))]);
+    fs::write(home.path().join("batch.yaml"), BATCH).unwrap();
+    home
+}
+
+fn bulk_add(home: &TempDir, extra: &[&str]) -> assert_cmd::assert::Assert {
+    offline(home.path())
+        .args(["bulk", "add", "--file"])
+        .arg(home.path().join("batch.yaml"))
+        .arg("--state")
+        .arg(home.path().join("state.json"))
+        .args(extra)
+        .assert()
+}
+
+fn server_names(home: &TempDir) -> Vec<String> {
+    let mut names: Vec<String> = read_config(home.path())["mcpServers"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn resume_applies_only_the_remaining_entries() {
+    let home = setup();
+
+    bulk_add(&home, &[]).success();
+    assert_eq!(server_names(&home), ["first", "second"]);
+    let state: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(home.path().join("state.json")).unwrap()).unwrap();
+    assert_eq!(state["completed"], serde_json::json!(["first", "second"]));
+
+    // Entries the state file lists as done must not be added again
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    seed_templates(home.path(), &[("echo", &template(true))]);
+
+    bulk_add(&home, &[])
+        .success()
+        .stdout(predicate::str::contains("2 of 3 server(s) already added"));
+    assert_eq!(server_names(&home), ["third"]);
+
+    bulk_add(&home, &[])
+        .success()
+        .stdout(predicate::str::contains("Nothing left to add"));
+}
+
+#[test]
+fn changed_batch_file_invalidates_state() {
+    let home = setup();
+    bulk_add(&home, &[]).success();
+
+    fs::write(
+        home.path().join("batch.yaml"),
+        BATCH.replace("first.js", "renamed.js"),
+    )
+    .unwrap();
+    bulk_add(&home, &[])
+        .failure()
+        .stderr(predicate::str::contains("--reset-state"));
+
+    bulk_add(&home, &["--reset-state"]).success();
+    assert_eq!(
+        read_config(home.path())["mcpServers"]["first"]["args"],
+        serde_json::json!(["renamed.js"])
+    );
+}
@@ -0,0 +1,92 @@
+// These tests redirect the config and cache directories through $HOME and
+// XDG_CACHE_HOME, which `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const TEMPLATE: &str = r#"{
+  "name": "demo",
+  "version": "1.0.0",
+  "description": "Demo template",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {},
+  "config": { "command": "demo", "args": [] }
+}"#;
+
+/// A home whose cache directory is blocked by a plain file, plus a private temp dir
+fn setup() -> (TempDir, TempDir, assert_cmd::Command) {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    let blocked = home.path().join("not-a-dir");
+    fs::write(&blocked, "").unwrap();
+
+    let tmp = TempDir::new().unwrap();
+    let mut cmd = mcp_forge(home.path());
+    cmd.env("XDG_CACHE_HOME", &blocked)
+        .env("TMPDIR", tmp.path());
+    (home, tmp, cmd)
+}
+
+/// The fallback cache directory in `tmp` for the user running the tests
+fn fallback_dir(tmp: &Path) -> PathBuf {
+    let uid = fs::metadata(tmp).unwrap().uid();
+    tmp.join(format!("mcp-forge-cache-{}", uid))
+}
+
+#[test]
+fn unusable_cache_dir_falls_back_to_temp_dir() {
+    let (_home, tmp, mut cmd) = setup();
+    let fallback = fallback_dir(tmp.path()).join("templates");
+    fs::create_dir_all(&fallback).unwrap();
+    fs::write(fallback.join("demo.json"), TEMPLATE).unwrap();
+
+    cmd.args(["template", "show", "demo", "--cached"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Template: demo"))
+        .stderr(predicate::str::contains("using"))
+        .stderr(predicate::str::contains("mcp-forge-cache"));
+}
+
+#[test]
+fn a_temp_cache_others_can_write_is_refused() {
+    let (_home, tmp, mut cmd) = setup();
+    let fallback = fallback_dir(tmp.path());
+    fs::create_dir_all(fallback.join("templates")).unwrap();
+    fs::write(fallback.join("templates/demo.json"), TEMPLATE).unwrap();
+    fs::set_permissions(&fallback, fs::Permissions::from_mode(0o777)).unwrap();
+
+    cmd.args(["template", "show", "demo", "--cached"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("can be written by other users"));
+}
+
+#[test]
+fn commands_without_templates_skip_the_cache() {
+    let (home, _tmp, mut cmd) = setup();
+    let batch = home.path().join("servers.yaml");
+    fs::write(&batch, "servers:\n  - name: inline\n    command: node\n").unwrap();
+
+    cmd.args(["bulk", "add", "--file"])
+        .arg(&batch)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Template cache").not());
+    assert!(read_config(home.path())["mcpServers"]["inline"].is_object());
+
+    let (_home, _tmp, mut cmd) = setup();
+    cmd.arg("list")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Template cache").not());
+}
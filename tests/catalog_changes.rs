@@ -0,0 +1,91 @@
+// These tests redirect the cache directory through $HOME, which `dirs` only
+// honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+fn catalog(entries: &[(&str, &str, &str)]) -> String {
+    let templates: serde_json::Map<String, serde_json::Value> = entries
+        .iter()
+        .map(|(name, version, category)| {
+            let entry = serde_json::json!({
+                "name": name,
+                "version": version,
+                "description": "",
+                "author": "test",
+                "tags": [],
+                "platforms": [],
+                "category": category,
+                "path": format!("templates/{}/{}.json", category, name)
+            });
+            (name.to_string(), entry)
+        })
+        .collect();
+    serde_json::json!({ "version": "1", "last_updated": "", "templates": templates }).to_string()
+}
+
+fn cache_dir(home: &TempDir) -> PathBuf {
+    let dir = template_cache_dir(home.path())
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn changes_lists_added_updated_and_removed_templates() {
+    let home = TempDir::new().unwrap();
+    let dir = cache_dir(&home);
+    fs::write(
+        dir.join("catalog.prev.json"),
+        catalog(&[
+            ("github", "1.0.0", "official"),
+            ("slack", "0.2.0", "community"),
+            ("old", "0.1.0", "experimental"),
+        ]),
+    )
+    .unwrap();
+    fs::write(
+        dir.join("catalog.json"),
+        catalog(&[
+            ("github", "1.1.0", "official"),
+            ("slack", "0.2.0", "official"),
+            ("brave", "1.0.0", "community"),
+        ]),
+    )
+    .unwrap();
+
+    mcp_forge(home.path())
+        .args(["template", "changes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("NEW brave 1.0.0 (community)"))
+        .stdout(predicate::str::contains("UPDATE github 1.0.0 → 1.1.0"))
+        .stdout(predicate::str::contains(
+            "UPDATE slack category community → official",
+        ))
+        .stdout(predicate::str::contains("REMOVE old 0.1.0"))
+        .stdout(predicate::str::contains("1 added, 2 updated, 1 removed"));
+}
+
+#[test]
+fn changes_without_an_earlier_catalog() {
+    let home = TempDir::new().unwrap();
+    let dir = cache_dir(&home);
+    fs::write(dir.join("catalog.json"), catalog(&[])).unwrap();
+
+    mcp_forge(home.path())
+        .args(["template", "changes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "No earlier catalog to compare with",
+        ));
+}
@@ -0,0 +1,151 @@
+// These tests redirect the config directory through $HOME, which `dirs` only
+// honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "filesystem": { "command": "sh", "args": [] },
+    "github": { "command": "sh", "args": [] },
+    "postgres": { "command": "sh", "args": [] },
+    "weather": { "command": "sh", "args": [] }
+  }
+}"#;
+
+fn log_dir(home: &Path) -> PathBuf {
+    #[cfg(target_os = "macos")]
+    let dir = home.join("Library/Logs/Claude");
+    #[cfg(not(target_os = "macos"))]
+    let dir = home.join(".config/claude/logs");
+    dir
+}
+
+/// A home with `CONFIG` and the log fixtures where Claude Desktop writes them
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/claude_logs");
+    let dir = log_dir(home.path());
+    fs::create_dir_all(&dir).unwrap();
+    for entry in fs::read_dir(fixtures).unwrap() {
+        let path = entry.unwrap().path();
+        fs::copy(&path, dir.join(path.file_name().unwrap())).unwrap();
+    }
+    home
+}
+
+fn issue_types(report: &serde_json::Value, server: &str) -> Vec<String> {
+    report
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|result| result["server_name"] == server)
+        .unwrap()["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| issue["issue_type"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn health_reports_failures_from_the_claude_logs() {
+    let home = setup();
+
+    let output = mcp_forge(home.path())
+        .args(["health", "--logs", "--format", "json"])
+        .output()
+        .unwrap();
+    // Servers with errors fail the check, whatever the format
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(issue_types(&report, "filesystem"), ["Failed To Start"]);
+    assert_eq!(issue_types(&report, "github"), ["Invalid Output"]);
+    assert_eq!(issue_types(&report, "postgres"), ["Exited Early"]);
+    assert!(issue_types(&report, "weather").is_empty());
+
+    let postgres = report
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|result| result["server_name"] == "postgres")
+        .unwrap();
+    assert_eq!(postgres["status"], "Error");
+    let issue = &postgres["issues"][0];
+    assert!(issue["message"]
+        .as_str()
+        .unwrap()
+        .contains("exited with code 1"));
+    assert!(issue["log_excerpt"][1]
+        .as_str()
+        .unwrap()
+        .contains("password authentication failed"));
+
+    // Servers only mcp.log mentions aren't configured, so aren't reported
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("\"memory\""));
+}
+
+#[test]
+fn health_shows_the_log_excerpt() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["health", "--logs"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "Claude Desktop couldn't start 'npx': command not found",
+        ))
+        .stdout(predicate::str::contains("│ ").and(predicate::str::contains("spawn npx ENOENT")))
+        .stdout(predicate::str::contains("mcp-forge smoke --only github"));
+    mcp_forge(home.path())
+        .args(["health", "--logs", "--format", "plain"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("spawn npx ENOENT"));
+
+    // Without --logs the logs aren't read
+    mcp_forge(home.path())
+        .arg("health")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ENOENT").not());
+}
+
+#[test]
+fn missing_logs_are_a_warning() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+
+    mcp_forge(home.path())
+        .args(["health", "--logs", "--format", "plain"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Couldn't read Claude Desktop logs",
+        ))
+        .stdout(predicate::str::contains("4 OK"));
+}
+
+#[test]
+fn health_fails_on_errors_in_the_default_format() {
+    let home = TempDir::new().unwrap();
+    write_config(
+        home.path(),
+        r#"{ "mcpServers": { "broken": { "command": "mcp-forge-no-such-binary" } } }"#,
+    );
+
+    mcp_forge(home.path())
+        .arg("health")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("broken"));
+}
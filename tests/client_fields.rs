@@ -0,0 +1,113 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Shaped like Cline's `cline_mcp_settings.json`
+const CLINE_SETTINGS: &str = r#"{
+  "mcpServers": {
+    "filesystem": {
+      "command": "npx",
+      "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"],
+      "env": {},
+      "disabled": false,
+      "autoApprove": ["read_file", "list_directory"],
+      "timeout": 120
+    },
+    "weather": {
+      "command": "node",
+      "args": ["weather.js"],
+      "disabled": true,
+      "autoApprove": [],
+      "timeout": 30
+    }
+  }
+}"#;
+
+const CONFIG: &str = r#"{ "mcpServers": { "local": { "command": "node", "args": [] } } }"#;
+
+#[test]
+fn cline_settings_round_trip_through_import_and_export() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    let file = home.path().join("cline_mcp_settings.json");
+    fs::write(&file, CLINE_SETTINGS).unwrap();
+
+    // timeout and autoApprove are modelled, so no unknown-field confirmation
+    mcp_forge(home.path())
+        .args(["import", "--replace", "--file", file.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let output = mcp_forge(home.path())
+        .args(["export", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let exported: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let original: serde_json::Value = serde_json::from_str(CLINE_SETTINGS).unwrap();
+    assert_eq!(exported["mcpServers"], original["mcpServers"]);
+}
+
+#[test]
+fn update_sets_and_clears_client_fields() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+
+    mcp_forge(home.path())
+        .args([
+            "update",
+            "local",
+            "--timeout",
+            "90",
+            "--auto-approve",
+            "read_file, list_directory,read_file",
+        ])
+        .assert()
+        .success();
+    let server = &read_config(home.path())["mcpServers"]["local"];
+    assert_eq!(server["timeout"], 90);
+    assert_eq!(
+        server["autoApprove"],
+        serde_json::json!(["read_file", "list_directory"])
+    );
+
+    mcp_forge(home.path())
+        .args(["list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Timeout: 90s"))
+        .stdout(predicate::str::contains(
+            "Auto-approve: read_file, list_directory",
+        ));
+
+    mcp_forge(home.path())
+        .args(["update", "local", "--clear-auto-approve"])
+        .assert()
+        .success();
+    let server = &read_config(home.path())["mcpServers"]["local"];
+    assert!(server.get("autoApprove").is_none());
+    assert_eq!(server["timeout"], 90);
+}
+
+#[test]
+fn update_rejects_non_positive_timeout() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+
+    mcp_forge(home.path())
+        .args(["update", "local", "--timeout", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--timeout must be a positive"));
+
+    assert!(read_config(home.path())["mcpServers"]["local"]
+        .get("timeout")
+        .is_none());
+}
@@ -0,0 +1,72 @@
+#![allow(dead_code)]
+
+use assert_cmd::Command;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn config_path(home: &Path) -> PathBuf {
+    #[cfg(target_os = "macos")]
+    let dir = home.join("Library/Application Support/Claude");
+    #[cfg(not(target_os = "macos"))]
+    let dir = home.join(".config/claude");
+    dir.join("claude_desktop_config.json")
+}
+
+pub fn write_config(home: &Path, json: &str) {
+    let path = config_path(home);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, json).unwrap();
+}
+
+pub fn read_config(home: &Path) -> serde_json::Value {
+    let content = fs::read_to_string(config_path(home)).unwrap();
+    serde_json::from_str(&content).unwrap()
+}
+
+/// Directory `TemplateManager` caches templates in
+pub fn template_cache_dir(home: &Path) -> PathBuf {
+    #[cfg(target_os = "macos")]
+    let dir = home.join("Library/Caches/mcp-forge/templates");
+    #[cfg(not(target_os = "macos"))]
+    let dir = home.join(".cache/mcp-forge/templates");
+    dir
+}
+
+/// Write `(name, json)` templates into the template cache, returning its directory
+pub fn seed_templates(home: &Path, templates: &[(&str, &str)]) -> PathBuf {
+    let dir = template_cache_dir(home);
+    fs::create_dir_all(&dir).unwrap();
+    for (name, json) in templates {
+        fs::write(dir.join(format!("{}.json", name)), json).unwrap();
+    }
+    dir
+}
+
+pub fn mcp_forge(home: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("mcp-forge").unwrap();
+    cmd.env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("XDG_CACHE_HOME", home.join(".cache"))
+        .env("NO_COLOR", "1")
+        .env_remove("MCP_FORGE_CONFIG")
+        .env_remove("MCP_FORGE_TEMPLATE_PATH")
+        .env_remove("MCP_FORGE_PLATFORM_OVERRIDE")
+        .env_remove("GITHUB_TOKEN")
+        .env_remove("GH_TOKEN")
+        .env_remove("MCP_FORGE_RECALL")
+        .env_remove("MCP_FORGE_STALE_AFTER")
+        // Offline tests fail their GitHub requests on purpose; retrying them
+        // would only add seconds of backoff
+        .env("MCP_FORGE_NO_RETRY", "1");
+    cmd
+}
+
+/// `mcp_forge` with every request routed through a proxy that refuses connections
+pub fn offline(home: &Path) -> Command {
+    let mut cmd = mcp_forge(home);
+    cmd.env("HTTPS_PROXY", "http://127.0.0.1:1")
+        .env("HTTP_PROXY", "http://127.0.0.1:1")
+        .env_remove("NO_PROXY")
+        .env_remove("no_proxy");
+    cmd
+}
@@ -0,0 +1,158 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "github": { "command": "npx", "args": [] },
+    "gitlab": { "command": "npx", "args": [] }
+  },
+  "disabledServers": {
+    "old-db": { "command": "pg-mcp" }
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    home
+}
+
+/// Run the generated bash completion for each command line, the last word
+/// being the one completed, and return the candidates offered for each
+fn complete_in_bash(home: &TempDir, lines: &[&str]) -> Vec<String> {
+    let output = mcp_forge(home.path())
+        .args(["completions", "bash"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let script = home.path().join("mcp-forge.bash");
+    fs::write(&script, output.stdout).unwrap();
+
+    let mut driver = format!("source '{}'\n", script.display());
+    for line in lines {
+        driver.push_str(&format!(
+            "COMP_WORDS=({}); COMP_CWORD=$((${{#COMP_WORDS[@]}} - 1)); COMPREPLY=(); _mcp_forge; echo \"${{COMPREPLY[*]}}\"\n",
+            line
+        ));
+    }
+
+    let bin = assert_cmd::cargo::cargo_bin("mcp-forge");
+    let path = format!(
+        "{}:{}",
+        bin.parent().unwrap().display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let output = Command::new("bash")
+        .arg("-c")
+        .arg(driver)
+        .env("PATH", path)
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join(".config"))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn bash_completes_commands_flags_and_server_names() {
+    let home = setup();
+
+    let candidates = complete_in_bash(
+        &home,
+        &[
+            "mcp-forge te",
+            "mcp-forge template ''",
+            "mcp-forge list --form",
+            "mcp-forge template list --ca",
+            "mcp-forge --profile work show g",
+            "mcp-forge enable ''",
+            "mcp-forge show github ''",
+        ],
+    );
+    assert_eq!(
+        candidates,
+        [
+            "template",
+            "list show cat search refresh changes create validate edit pin outdated repo source",
+            "--format",
+            "--cached",
+            "github gitlab",
+            "old-db",
+            "",
+        ]
+    );
+}
+
+#[test]
+fn every_supported_shell_gets_a_script() {
+    let home = setup();
+
+    for (shell, registration) in [
+        (
+            "bash",
+            "complete -o bashdefault -o default -F _mcp_forge mcp-forge",
+        ),
+        ("zsh", "compdef _mcp_forge mcp-forge"),
+        (
+            "fish",
+            "complete -c mcp-forge -n '__mcp_forge_at show' -f -a",
+        ),
+        (
+            "powershell",
+            "Register-ArgumentCompleter -Native -CommandName 'mcp-forge'",
+        ),
+    ] {
+        mcp_forge(home.path())
+            .args(["completions", shell])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(registration));
+    }
+
+    mcp_forge(home.path())
+        .args(["completions", "tcsh"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unsupported shell 'tcsh'"));
+}
+
+#[test]
+fn list_names_only_prints_bare_names() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["list", "--names-only", "--sort", "name"])
+        .assert()
+        .success()
+        .stdout("github\ngitlab\nold-db\n");
+    mcp_forge(home.path())
+        .args(["list", "--names-only", "--disabled-only", "--json"])
+        .assert()
+        .success()
+        .stdout("[\"old-db\"]\n");
+
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    mcp_forge(home.path())
+        .args(["list", "--names-only"])
+        .assert()
+        .success()
+        .stdout("");
+}
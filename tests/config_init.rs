@@ -0,0 +1,100 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const POPULATED: &str = r#"{
+  "mcpServers": {
+    "filesystem": { "command": "npx", "args": [] }
+  }
+}"#;
+
+fn backup_files(home: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let dir = config_path(home).parent().unwrap().join("backups");
+    match fs::read_dir(dir) {
+        Ok(entries) => entries.map(|e| e.unwrap().path()).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+#[test]
+fn init_creates_fresh_config() {
+    let home = TempDir::new().unwrap();
+
+    mcp_forge(home.path())
+        .args(["config", "init"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Initialized empty configuration at",
+        ))
+        .stdout(predicate::str::contains("claude_desktop_config.json"));
+
+    let config = read_config(home.path());
+    assert!(config["mcpServers"].as_object().unwrap().is_empty());
+}
+
+#[test]
+fn init_refuses_to_overwrite_populated_config() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), POPULATED);
+
+    mcp_forge(home.path())
+        .args(["config", "init"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already contains 1 server(s)"))
+        .stderr(predicate::str::contains("--force"));
+
+    assert_eq!(
+        fs::read_to_string(config_path(home.path())).unwrap(),
+        POPULATED
+    );
+}
+
+#[test]
+fn init_force_backs_up_before_overwriting() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), POPULATED);
+
+    mcp_forge(home.path())
+        .args(["config", "init", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Backed up existing configuration to",
+        ));
+
+    let config = read_config(home.path());
+    assert!(config["mcpServers"].as_object().unwrap().is_empty());
+
+    let backups = backup_files(home.path());
+    assert_eq!(backups.len(), 1);
+    let backup: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&backups[0]).unwrap()).unwrap();
+    assert!(backup["config"]["mcpServers"].get("filesystem").is_some());
+}
+
+#[test]
+fn init_if_missing_leaves_existing_config_alone() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), POPULATED);
+
+    mcp_forge(home.path())
+        .args(["config", "init", "--if-missing"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("already exists"));
+
+    assert_eq!(
+        fs::read_to_string(config_path(home.path())).unwrap(),
+        POPULATED
+    );
+    assert!(backup_files(home.path()).is_empty());
+}
@@ -0,0 +1,337 @@
+// These tests redirect the config directory through $HOME, which `dirs` only
+// honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const BASE: &str = r#"{
+  "mcpServers": {
+    "files": { "command": "files-mcp", "args": ["/shared"] },
+    "git": { "command": "git-mcp", "args": [] }
+  },
+  "globalShortcut": "Alt+Space"
+}"#;
+
+const MINE: &str = r#"{
+  "mcpServers": {
+    "files": { "command": "files-mcp", "args": ["/home/me"] },
+    "notes": { "command": "notes-mcp", "args": [] }
+  }
+}"#;
+
+/// A home whose Claude config is `BASE`, with `MINE` added as an overlay
+fn setup() -> (TempDir, PathBuf) {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), BASE);
+    let mine = home.path().join("mine.json");
+    fs::write(&mine, MINE).unwrap();
+
+    mcp_forge(home.path())
+        .args(["config", "overlay", "add"])
+        .arg(&mine)
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("as the base")
+                .and(predicate::str::contains("✓ Added overlay 'mine'"))
+                .and(predicate::str::contains(
+                    "✓ Wrote 3 server(s) from base + 1 overlay(s)",
+                ))
+                .and(predicate::str::contains("files: mine (overrides base)")),
+        );
+    (home, mine)
+}
+
+fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn base_path(home: &Path) -> PathBuf {
+    home.join(".config/mcp-forge/base.json")
+}
+
+#[test]
+fn adding_an_overlay_materializes_the_merge() {
+    let (home, _mine) = setup();
+
+    let config = read_config(home.path());
+    assert_eq!(config["mcpServers"]["files"]["args"][0], "/home/me");
+    assert_eq!(config["mcpServers"]["git"]["command"], "git-mcp");
+    assert_eq!(config["mcpServers"]["notes"]["command"], "notes-mcp");
+    assert_eq!(config["globalShortcut"], "Alt+Space");
+
+    // The base is the config as it was
+    let base = read_json(&base_path(home.path()));
+    assert_eq!(base["mcpServers"]["files"]["args"][0], "/shared");
+    assert!(base["mcpServers"].get("notes").is_none());
+
+    mcp_forge(home.path())
+        .args(["config", "overlay", "list"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("base  ")
+                .and(predicate::str::contains("mine  "))
+                .and(predicate::str::contains("Merged in this order into")),
+        );
+}
+
+#[test]
+fn list_and_show_label_servers_by_layer() {
+    let (home, _mine) = setup();
+
+    mcp_forge(home.path())
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("[layer] mine (overrides base)")
+                .and(predicate::str::contains("[layer] base"))
+                .and(predicate::str::contains("[layer] mine")),
+        );
+
+    let output = mcp_forge(home.path())
+        .args(["list", "--json"])
+        .output()
+        .unwrap();
+    let servers: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let files = servers
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["name"] == "files")
+        .unwrap();
+    assert_eq!(files["layer"]["layer"], "mine");
+    assert_eq!(files["layer"]["overrides"], serde_json::json!(["base"]));
+
+    mcp_forge(home.path())
+        .args(["show", "git"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[layer] base"));
+}
+
+#[test]
+fn changes_go_to_the_chosen_layer() {
+    let (home, mine) = setup();
+
+    // Without a terminal there is no one to ask
+    mcp_forge(home.path())
+        .args(["disable", "git"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--layer (base, mine)"));
+    mcp_forge(home.path())
+        .args(["disable", "git", "--layer", "theirs"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Unknown layer 'theirs'; layers: base, mine",
+        ));
+
+    mcp_forge(home.path())
+        .args(["disable", "git", "--layer", "base"])
+        .assert()
+        .success();
+    let base = read_json(&base_path(home.path()));
+    assert!(base["mcpServers"].get("git").is_none());
+    assert_eq!(base["disabledServers"]["git"]["command"], "git-mcp");
+    let config = read_config(home.path());
+    assert!(config["mcpServers"].get("git").is_none());
+    assert!(config["mcpServers"].get("notes").is_some());
+
+    // The overlay's version of a server is the one it edits
+    mcp_forge(home.path())
+        .args(["remove", "files", "--force", "--layer", "mine"])
+        .assert()
+        .success();
+    assert!(read_json(&mine)["mcpServers"].get("files").is_none());
+    let config = read_config(home.path());
+    assert_eq!(config["mcpServers"]["files"]["args"][0], "/shared");
+
+    mcp_forge(home.path())
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("overrides").not());
+}
+
+#[test]
+fn materialize_picks_up_edits_to_a_layer() {
+    let (home, mine) = setup();
+    fs::write(
+        &mine,
+        r#"{ "mcpServers": { "git": { "command": "my-git", "args": [] } } }"#,
+    )
+    .unwrap();
+
+    mcp_forge(home.path())
+        .args(["config", "materialize"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("✓ Wrote 2 server(s) from base + 1 overlay(s)")
+                .and(predicate::str::contains("git: mine (overrides base)")),
+        );
+    let config = read_config(home.path());
+    assert_eq!(config["mcpServers"]["git"]["command"], "my-git");
+    assert_eq!(config["mcpServers"]["files"]["args"][0], "/shared");
+    assert!(config["mcpServers"].get("notes").is_none());
+
+    fs::remove_file(&mine).unwrap();
+    mcp_forge(home.path())
+        .args(["config", "materialize"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Layer 'mine' is missing"));
+}
+
+#[test]
+fn removing_the_last_overlay_hands_the_config_back() {
+    let (home, mine) = setup();
+
+    mcp_forge(home.path())
+        .args(["config", "overlay", "remove", "mine"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("✓ Removed overlay 'mine'")
+                .and(predicate::str::contains("No overlays left")),
+        );
+    assert!(mine.exists());
+    let config = read_config(home.path());
+    assert_eq!(config["mcpServers"]["files"]["args"][0], "/shared");
+    assert!(config["mcpServers"].get("notes").is_none());
+
+    mcp_forge(home.path())
+        .args(["disable", "git"])
+        .assert()
+        .success();
+    assert!(read_config(home.path())["disabledServers"]
+        .get("git")
+        .is_some());
+    mcp_forge(home.path())
+        .args(["config", "materialize"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No overlays configured"));
+}
+
+#[test]
+fn overlay_add_checks_its_arguments() {
+    let (home, mine) = setup();
+
+    mcp_forge(home.path())
+        .args(["config", "overlay", "add"])
+        .arg(&mine)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Overlay 'mine' already exists"));
+    mcp_forge(home.path())
+        .args(["config", "overlay", "add", "--name", "base"])
+        .arg(home.path().join("other.json"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is the base config"));
+    mcp_forge(home.path())
+        .args(["config", "overlay", "add", "--base"])
+        .arg(home.path().join("b.json"))
+        .arg(home.path().join("other.json"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("A base is already configured"));
+
+    // A new file starts out as an empty overlay
+    let laptop = home.path().join("laptop.json");
+    mcp_forge(home.path())
+        .args(["config", "overlay", "add"])
+        .arg(&laptop)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created an empty overlay"));
+    assert_eq!(read_json(&laptop)["mcpServers"], serde_json::json!({}));
+}
+
+#[test]
+fn an_explicit_base_is_used_as_is() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    let base = home.path().join("team.json");
+    fs::write(&base, BASE).unwrap();
+    let mine = home.path().join("mine.json");
+    fs::write(&mine, MINE).unwrap();
+
+    mcp_forge(home.path())
+        .args(["config", "overlay", "add", "--base"])
+        .arg(&base)
+        .arg(&mine)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("as the base").not());
+    assert!(!base_path(home.path()).exists());
+    assert_eq!(
+        read_config(home.path())["mcpServers"]["git"]["command"],
+        "git-mcp"
+    );
+}
+
+#[test]
+fn other_targets_ignore_overlays() {
+    let (home, _mine) = setup();
+    let cursor = home.path().join(".cursor/mcp.json");
+    fs::create_dir_all(cursor.parent().unwrap()).unwrap();
+    fs::write(&cursor, r#"{ "mcpServers": {} }"#).unwrap();
+
+    mcp_forge(home.path())
+        .args(["--target", "cursor", "disable", "git"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not found"));
+    mcp_forge(home.path())
+        .args(["--target", "cursor", "config", "overlay", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No overlays configured"));
+}
+
+#[test]
+fn a_config_override_ignores_overlays() {
+    let (home, mine) = setup();
+    let portable = home.path().join("portable.json");
+    fs::write(
+        &portable,
+        r#"{ "mcpServers": { "scratch": { "command": "sh" } } }"#,
+    )
+    .unwrap();
+    let merged = fs::read_to_string(config_path(home.path())).unwrap();
+
+    mcp_forge(home.path())
+        .arg("--config")
+        .arg(&portable)
+        .args(["disable", "scratch"])
+        .assert()
+        .success();
+
+    let written = read_json(&portable);
+    assert!(written["mcpServers"].get("scratch").is_none());
+    assert!(written["mcpServers"].get("files").is_none());
+    assert_eq!(fs::read_to_string(&mine).unwrap(), MINE);
+    assert_eq!(
+        fs::read_to_string(config_path(home.path())).unwrap(),
+        merged
+    );
+
+    mcp_forge(home.path())
+        .arg("--config")
+        .arg(&portable)
+        .args(["config", "overlay", "remove", "mine"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--config"));
+}
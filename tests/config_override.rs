@@ -0,0 +1,110 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{ "mcpServers": { "db": { "command": "sh" } } }"#;
+
+#[test]
+fn flag_beats_env_beats_default() {
+    let home = TempDir::new().unwrap();
+    let from_env = home.path().join("env/config.json");
+    let from_flag = home.path().join("flag/config.json");
+
+    mcp_forge(home.path())
+        .args(["config", "path"])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", config_path(home.path()).display()));
+
+    mcp_forge(home.path())
+        .env("MCP_FORGE_CONFIG", &from_env)
+        .args(["config", "path"])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", from_env.display()));
+
+    mcp_forge(home.path())
+        .env("MCP_FORGE_CONFIG", &from_env)
+        .arg("--config")
+        .arg(&from_flag)
+        .args(["config", "path"])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", from_flag.display()));
+}
+
+#[test]
+fn commands_change_the_override_and_back_up_beside_it() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    let portable = home.path().join("portable/claude.json");
+    fs::create_dir_all(portable.parent().unwrap()).unwrap();
+    fs::write(&portable, CONFIG).unwrap();
+
+    mcp_forge(home.path())
+        .env("MCP_FORGE_CONFIG", &portable)
+        .args(["remove", "db", "--force"])
+        .assert()
+        .success();
+
+    let written: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&portable).unwrap()).unwrap();
+    assert!(written["mcpServers"].get("db").is_none());
+    assert!(read_config(home.path())["mcpServers"].get("db").is_some());
+
+    let backups: Vec<_> = fs::read_dir(home.path().join("portable/backups"))
+        .unwrap()
+        .collect();
+    assert_eq!(backups.len(), 1);
+}
+
+#[test]
+fn state_files_live_beside_the_override() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    let portable = home.path().join("portable/claude.json");
+    fs::create_dir_all(portable.parent().unwrap()).unwrap();
+    fs::write(&portable, CONFIG).unwrap();
+
+    mcp_forge(home.path())
+        .arg("--config")
+        .arg(&portable)
+        .args(["meta", "set", "db", "exclude_from_share", "true"])
+        .assert()
+        .success();
+
+    let dir = home.path().join("portable");
+    assert!(dir.join("server_metadata.json").exists());
+    assert!(dir.join("operations.log").exists());
+    assert!(dir.join("mcp-forge.lock").exists());
+    let claude_dir = config_path(home.path()).parent().unwrap().to_path_buf();
+    for file in ["server_metadata.json", "operations.log", "mcp-forge.lock"] {
+        assert!(!claude_dir.join(file).exists(), "{}", file);
+    }
+
+    mcp_forge(home.path())
+        .arg("--config")
+        .arg(&portable)
+        .arg("log")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("meta set"));
+}
+
+#[test]
+fn config_and_target_conflict() {
+    let home = TempDir::new().unwrap();
+
+    mcp_forge(home.path())
+        .args(["--config", "a.json", "--target", "cursor", "list"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
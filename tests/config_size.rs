@@ -0,0 +1,113 @@
+// These tests redirect the config directory through $HOME, which `dirs` only
+// honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// A home whose config has 50 small servers and one with a 40 KB env value
+fn setup() -> TempDir {
+    let mut servers = serde_json::Map::new();
+    for i in 0..50 {
+        servers.insert(
+            format!("server-{:02}", i),
+            serde_json::json!({ "command": "npx", "args": ["-y", "some-mcp"] }),
+        );
+    }
+    servers.insert(
+        "blob".to_string(),
+        serde_json::json!({
+            "command": "blob-mcp",
+            "env": { "CERT": "x".repeat(40 * 1024), "TOKEN": "secret" }
+        }),
+    );
+    let config = serde_json::json!({ "mcpServers": servers });
+
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), &serde_json::to_string_pretty(&config).unwrap());
+    home
+}
+
+#[test]
+fn stats_breaks_the_size_down_by_server() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("51 server(s), 0 disabled, 4")
+                .and(predicate::str::contains("Servers").not()),
+        );
+
+    let output = mcp_forge(home.path())
+        .args(["stats", "--size"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let servers: Vec<&str> = stdout
+        .lines()
+        .skip_while(|line| *line != "Servers")
+        .skip(1)
+        .take(2)
+        .collect();
+    assert!(servers[0].trim_start().starts_with("blob "), "{}", stdout);
+    assert!(servers[0].contains("40.1 KB"), "{}", stdout);
+    assert!(servers[1].trim_start().starts_with("server-"), "{}", stdout);
+    assert!(stdout.contains("(other)"), "{}", stdout);
+    assert!(stdout.contains("blob.CERT"), "{}", stdout);
+    // Sizes only; values are never printed
+    assert!(!stdout.contains("secret"), "{}", stdout);
+
+    let output = mcp_forge(home.path())
+        .args(["stats", "--size", "--json"])
+        .output()
+        .unwrap();
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["servers"], 51);
+    assert_eq!(report["size"]["servers"][0]["name"], "blob");
+    assert_eq!(report["size"]["env_values"][0]["key"], "CERT");
+}
+
+#[test]
+fn large_saves_warn_but_go_ahead() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .env("MCP_FORGE_SIZE_WARNING", "16KB")
+        .args(["disable", "server-01"])
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("more than the 16.0 KB Claude Desktop handles well")
+                .and(predicate::str::contains("Largest servers: blob (40."))
+                .and(predicate::str::contains("Largest env values: blob.CERT"))
+                .and(predicate::str::contains("stats --size")),
+        );
+    assert!(read_config(home.path())["disabledServers"]
+        .get("server-01")
+        .is_some());
+
+    // Below the default threshold, and with the warning off, nothing is said
+    for setting in [None, Some("off")] {
+        let mut cmd = mcp_forge(home.path());
+        if let Some(setting) = setting {
+            cmd.env("MCP_FORGE_SIZE_WARNING", setting);
+        } else {
+            cmd.env_remove("MCP_FORGE_SIZE_WARNING");
+        }
+        cmd.args(["enable", "server-01"])
+            .assert()
+            .success()
+            .stderr(predicate::str::contains("handles well").not());
+        mcp_forge(home.path())
+            .args(["disable", "server-01", "--backup", "none"])
+            .assert()
+            .success();
+    }
+}
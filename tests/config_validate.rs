@@ -0,0 +1,95 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+const GOOD: &str = r#"{ "mcpServers": {
+  "fs": { "command": "npx", "args": ["-y", "@modelcontextprotocol/server-filesystem"] },
+  "remote": { "url": "https://example.com/mcp" }
+} }"#;
+
+const BROKEN: &str = r#"{ "mcpServers": {
+  "fs": { "command": "npx", "args": "-y server" },
+  "remote": { "url": 42 }
+} }"#;
+
+const DUPLICATES: &str = r#"{ "mcpServers": {
+  "fs": { "command": "npx", "args": ["fs"] },
+  "FS": { "command": "npx", "args": ["fs"] },
+  "fs": { "command": "npx", "args": ["fs"] },
+  "both": { "command": "npx", "url": "https://example.com/mcp" }
+} }"#;
+
+fn validate(config: &str, json: bool) -> assert_cmd::assert::Assert {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), config);
+    let mut cmd = mcp_forge(home.path());
+    cmd.args(["config", "validate"]);
+    if json {
+        cmd.arg("--json");
+    }
+    cmd.assert()
+}
+
+#[test]
+fn good_config_passes_every_section() {
+    validate(GOOD, false)
+        .success()
+        .stdout(predicate::str::contains("Schema\n  ✓ No problems found"))
+        .stdout(predicate::str::contains(
+            "Duplicates and conflicts\n  ✓ No problems found",
+        ))
+        .stdout(predicate::str::contains("Servers\n  ✓ No problems found"))
+        .stdout(predicate::str::contains(
+            "Summary: 0 error(s), 0 warning(s)",
+        ));
+}
+
+#[test]
+fn structurally_broken_config_fails_the_schema_section() {
+    validate(BROKEN, false)
+        .code(1)
+        .stdout(predicate::str::contains(
+            "Server 'fs': 'args' must be an array of strings, found a string",
+        ))
+        .stdout(predicate::str::contains(
+            "Server 'remote': 'url' must be a string, found a number",
+        ))
+        .stdout(predicate::str::contains(
+            "skipped: fix the errors above first",
+        ));
+}
+
+#[test]
+fn duplicate_laden_config_reports_each_conflict_as_json() {
+    let output = validate(DUPLICATES, true).code(1).get_output().clone();
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let messages = |section: usize| -> Vec<String> {
+        report["sections"][section]["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|issue| issue["message"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(
+        messages(1),
+        [
+            "Server 'fs' is defined 2 times; only the last definition is used",
+            "Server names differ only in case: FS, fs",
+            "Servers FS, fs all run 'npx fs'",
+        ]
+    );
+    assert_eq!(
+        messages(2),
+        ["Server 'both': Server cannot have both 'url' and 'command' fields"]
+    );
+    assert_eq!(report["errors"], 2);
+    assert_eq!(report["warnings"], 2);
+}
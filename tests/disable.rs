@@ -0,0 +1,154 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use serde_json::json;
+use std::fs;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "postgres-dev": { "command": "pg-mcp", "args": ["--host", "dev"] },
+    "github": { "command": "npx", "args": [] }
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    home
+}
+
+#[test]
+fn disable_moves_the_server_aside_and_enable_restores_it() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["disable", "postgres-dev"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("mcp-forge enable postgres-dev"));
+
+    let config = read_config(home.path());
+    assert!(config["mcpServers"].get("postgres-dev").is_none());
+    assert_eq!(
+        config["disabledServers"]["postgres-dev"],
+        json!({ "command": "pg-mcp", "args": ["--host", "dev"] })
+    );
+
+    mcp_forge(home.path())
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("• postgres-dev disabled"))
+        .stdout(predicate::str::contains("• github\n"));
+    mcp_forge(home.path())
+        .args(["list", "--disabled-only"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("postgres-dev"))
+        .stdout(predicate::str::contains("github").not());
+
+    mcp_forge(home.path())
+        .args(["enable", "postgres-dev"])
+        .assert()
+        .success();
+
+    let config = read_config(home.path());
+    assert_eq!(
+        config["mcpServers"]["postgres-dev"],
+        json!({ "command": "pg-mcp", "args": ["--host", "dev"] })
+    );
+    assert!(config.get("disabledServers").is_none());
+}
+
+#[test]
+fn enabling_over_an_active_server_needs_a_decision() {
+    let home = setup();
+    mcp_forge(home.path())
+        .args(["disable", "postgres-dev"])
+        .assert()
+        .success();
+    mcp_forge(home.path())
+        .args(["add", "postgres-dev", "--preset", "npx:pg-mcp-next"])
+        .assert()
+        .success();
+
+    mcp_forge(home.path())
+        .args(["enable", "postgres-dev"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Operation cancelled"));
+    assert!(read_config(home.path())["disabledServers"]
+        .get("postgres-dev")
+        .is_some());
+
+    mcp_forge(home.path())
+        .args(["enable", "postgres-dev", "--rename", "postgres-dev-old"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("enabled as 'postgres-dev-old'"));
+
+    let config = read_config(home.path());
+    assert_eq!(config["mcpServers"]["postgres-dev"]["command"], "npx");
+    assert_eq!(
+        config["mcpServers"]["postgres-dev-old"]["command"],
+        "pg-mcp"
+    );
+}
+
+#[test]
+fn force_overwrites_the_active_server() {
+    let home = setup();
+    mcp_forge(home.path())
+        .args(["disable", "postgres-dev"])
+        .assert()
+        .success();
+    mcp_forge(home.path())
+        .args(["add", "postgres-dev", "--preset", "npx:pg-mcp-next"])
+        .assert()
+        .success();
+
+    mcp_forge(home.path())
+        .args(["enable", "postgres-dev", "--force"])
+        .assert()
+        .success();
+
+    let config = read_config(home.path());
+    assert_eq!(config["mcpServers"]["postgres-dev"]["command"], "pg-mcp");
+}
+
+#[test]
+fn disabled_servers_survive_backups_and_profile_switches() {
+    let home = setup();
+    for args in [
+        vec!["profile", "create", "work"],
+        vec!["profile", "create", "other"],
+        vec!["profile", "switch", "work"],
+        vec!["add", "postgres-dev", "--preset", "npx:pg-mcp"],
+        vec!["disable", "postgres-dev"],
+        vec!["backup", "create", "--name", "with-disabled"],
+        vec!["profile", "switch", "other"],
+    ] {
+        mcp_forge(home.path()).args(&args).assert().success();
+    }
+    assert!(read_config(home.path()).get("disabledServers").is_none());
+
+    let backup =
+        fs::read_to_string(config_path(home.path()).with_file_name("backups/with-disabled.json"))
+            .unwrap();
+    assert!(backup.contains("disabledServers"));
+
+    mcp_forge(home.path())
+        .args(["profile", "switch", "work"])
+        .assert()
+        .success();
+    assert_eq!(
+        read_config(home.path())["disabledServers"]["postgres-dev"]["command"],
+        "npx"
+    );
+}
@@ -0,0 +1,100 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const DOCS: &str = "https://github.com/modelcontextprotocol/servers/tree/main/src/filesystem";
+
+const TEMPLATE: &str = r#"{
+  "name": "filesystem",
+  "version": "1.0.0",
+  "description": "Filesystem access",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "homepage": "https://github.com/modelcontextprotocol/servers/tree/main/src/filesystem",
+  "variables": {},
+  "config": { "command": "npx", "args": ["-y", "@modelcontextprotocol/server-filesystem"] }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    seed_templates(home.path(), &[("filesystem", TEMPLATE)]);
+    home
+}
+
+#[test]
+fn added_servers_keep_their_template_docs_link() {
+    let home = setup();
+    offline(home.path())
+        .args(["add", "docs", "filesystem"])
+        .assert()
+        .success();
+
+    let metadata: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(config_path(home.path()).with_file_name("server_metadata.json"))
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(metadata["servers"]["docs"]["docs_url"], DOCS);
+
+    mcp_forge(home.path())
+        .args(["show", "docs"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!("  Docs: {}\n", DOCS)));
+
+    let output = mcp_forge(home.path())
+        .args(["list", "--json"])
+        .output()
+        .unwrap();
+    let listed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(listed[0]["docs_url"], DOCS);
+}
+
+#[test]
+fn open_is_skipped_without_an_interactive_terminal() {
+    let home = setup();
+    offline(home.path())
+        .args(["add", "docs", "filesystem"])
+        .assert()
+        .success();
+
+    for args in [
+        vec!["show", "docs", "--open"],
+        vec!["template", "show", "filesystem", "--cached", "--open"],
+    ] {
+        offline(home.path())
+            .args(&args)
+            .env("CI", "true")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(format!("Docs: {}", DOCS)))
+            .stdout(predicate::str::contains("Not opening a browser"));
+    }
+}
+
+#[test]
+fn open_says_when_there_is_no_link() {
+    let home = setup();
+    write_config(
+        home.path(),
+        r#"{ "mcpServers": { "manual": { "command": "node" } } }"#,
+    );
+
+    mcp_forge(home.path())
+        .args(["show", "manual", "--open"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Docs:").not())
+        .stdout(predicate::str::contains(
+            "No documentation link is recorded for server 'manual'",
+        ));
+}
@@ -0,0 +1,115 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Every file under `dir` with its contents
+fn snapshot(dir: &Path) -> BTreeMap<PathBuf, Vec<u8>> {
+    let mut files = BTreeMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                pending.push(path);
+            } else {
+                files.insert(path.clone(), fs::read(&path).unwrap());
+            }
+        }
+    }
+    files
+}
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    home
+}
+
+#[test]
+fn profile_delete_dry_run_changes_nothing() {
+    let home = setup();
+    mcp_forge(home.path())
+        .args(["profile", "create", "work"])
+        .assert()
+        .success();
+    let before = snapshot(home.path());
+
+    mcp_forge(home.path())
+        .args(["profile", "delete", "work", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("profile_snapshots/work.json"))
+        .stdout(predicate::str::contains("profile 'work' in"));
+
+    assert_eq!(snapshot(home.path()), before);
+}
+
+#[test]
+fn backup_clean_dry_run_changes_nothing() {
+    let home = setup();
+    let backup_dir = config_path(home.path()).with_file_name("backups");
+    fs::create_dir_all(&backup_dir).unwrap();
+    fs::write(
+        backup_dir.join("old.json"),
+        r#"{
+  "metadata": {
+    "name": "old",
+    "created_at": "2020-01-01T00:00:00Z",
+    "servers_count": 0,
+    "description": null,
+    "git_branch": null,
+    "git_commit": null
+  },
+  "config": { "mcpServers": {} }
+}"#,
+    )
+    .unwrap();
+    let before = snapshot(home.path());
+
+    mcp_forge(home.path())
+        .args(["backup", "clean", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("backups/old.json"))
+        .stdout(predicate::str::contains("Files (1,"));
+
+    assert_eq!(snapshot(home.path()), before);
+}
+
+#[test]
+fn template_refresh_clear_dry_run_changes_nothing() {
+    let home = setup();
+    seed_templates(home.path(), &[("weather", r#"{ "name": "weather" }"#)]);
+    let before = snapshot(home.path());
+
+    mcp_forge(home.path())
+        .args(["template", "refresh", "--clear", "--dry-run"])
+        .env("HTTPS_PROXY", "http://127.0.0.1:1")
+        .env("HTTP_PROXY", "http://127.0.0.1:1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("templates/weather.json"))
+        .stdout(predicate::str::contains("cached template 'weather'"));
+
+    assert_eq!(snapshot(home.path()), before);
+}
+
+#[test]
+fn template_refresh_dry_run_requires_clear() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["template", "refresh", "--dry-run"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--clear"));
+}
@@ -0,0 +1,86 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const TEMPLATE: &str = r#"{
+  "name": "search",
+  "version": "1.2.0",
+  "description": "Search",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {
+    "region": { "type": "string", "description": "Region", "required": true },
+    "api_key": { "type": "string", "description": "API key", "required": true, "secret": true }
+  },
+  "config": {
+    "command": "search-mcp",
+    "args": ["--region", "{{region}}"],
+    "env": { "SEARCH_CREDENTIAL": "{{api_key}}" }
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(
+        home.path(),
+        r#"{ "mcpServers": { "plain": { "command": "sh", "args": [] } } }"#,
+    );
+    seed_templates(home.path(), &[("search", TEMPLATE)]);
+
+    let vars = home.path().join("vars.yaml");
+    fs::write(&vars, "region: eu\napi_key: sk-live-0123456789\n").unwrap();
+    offline(home.path())
+        .args(["add", "web", "search", "--vars-file"])
+        .arg(&vars)
+        .assert()
+        .success();
+    home
+}
+
+#[test]
+fn edit_offers_to_rerun_the_template_with_previous_values() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["edit", "web", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Template: search v1.2.0 (edit can re-run it with updated variables)",
+        ))
+        .stdout(predicate::str::contains("Previous values: region=eu"))
+        .stdout(predicate::str::contains("sk-live").not());
+}
+
+#[test]
+fn edit_falls_back_to_the_raw_editor() {
+    let home = setup();
+
+    // No recorded template
+    offline(home.path())
+        .args(["edit", "plain", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "No template to re-run; edit opens the raw editor",
+        ));
+
+    // Template recorded but no longer loadable
+    fs::remove_file(template_cache_dir(home.path()).join("search.json")).unwrap();
+    offline(home.path())
+        .args(["edit", "web", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Couldn't load template 'search'"))
+        .stdout(predicate::str::contains(
+            "No template to re-run; edit opens the raw editor",
+        ));
+}
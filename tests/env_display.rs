@@ -0,0 +1,101 @@
+// These tests redirect the config directory through $HOME, which `dirs` only
+// honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn setup() -> (TempDir, String, String) {
+    let home = TempDir::new().unwrap();
+    let blob = format!(r#"{{"rules": [{}]}}"#, r#""allow-read","#.repeat(40));
+    let cert = "MIIBszCCAVmgAwIBAgIUe3bQ8H2x9Yk1m0dJZ5Gm3nq2Lzsw".repeat(4);
+    let config = serde_json::json!({
+        "mcpServers": {
+            "big": {
+                "command": "npx",
+                "args": [],
+                "env": { "POLICY": blob, "TLS_CERT": cert, "MODE": "strict" }
+            }
+        }
+    });
+    write_config(home.path(), &config.to_string());
+    (home, blob, cert)
+}
+
+#[test]
+fn list_truncates_long_values_and_summarizes_base64() {
+    let (home, blob, cert) = setup();
+    let more = blob.chars().count() - 120;
+
+    mcp_forge(home.path())
+        .arg("list")
+        .env_remove("MCP_FORGE_MAX_VALUE_CHARS")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "POLICY={}… (+{} more chars)",
+            &blob[..120],
+            more
+        )))
+        .stdout(predicate::str::contains("TLS_CERT=<base64, 192 B>"))
+        .stdout(predicate::str::contains("MODE=strict"))
+        .stdout(predicate::str::contains(cert.as_str()).not());
+}
+
+#[test]
+fn full_and_json_show_values_untouched() {
+    let (home, blob, cert) = setup();
+
+    mcp_forge(home.path())
+        .args(["list", "--full"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!("POLICY={}", blob)))
+        .stdout(predicate::str::contains(format!("TLS_CERT={}", cert)));
+
+    let output = mcp_forge(home.path())
+        .args(["list", "--json"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(&serde_json::to_string(&blob).unwrap()));
+    assert!(!stdout.contains("more chars"));
+}
+
+#[test]
+fn max_value_chars_setting_changes_the_limit() {
+    let (home, _, _) = setup();
+
+    mcp_forge(home.path())
+        .arg("list")
+        .env("MCP_FORGE_MAX_VALUE_CHARS", "20")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"POLICY={"rules": ["allow-re… (+"#,
+        ));
+}
+
+#[test]
+fn update_preview_diffs_env_in_key_order() {
+    let (home, _, _) = setup();
+
+    mcp_forge(home.path())
+        .args([
+            "update",
+            "big",
+            "--set",
+            "MODE=relaxed",
+            "--set",
+            "API_TOKEN=tok-1234567890",
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "    + API_TOKEN  tok********890\n    ~ MODE       strict → relaxed\n    (+2 unchanged)",
+        ));
+}
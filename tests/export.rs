@@ -0,0 +1,214 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "github": {
+      "command": "npx",
+      "args": ["-y", "@modelcontextprotocol/server-github"],
+      "env": { "GITHUB_TOKEN": "ghp_abcdefghijkl", "LOG_LEVEL": "debug" }
+    },
+    "remote": { "url": "https://example.com/mcp" }
+  },
+  "globalShortcut": "Ctrl+Space"
+}"#;
+
+const METADATA: &str = r#"{
+  "servers": {
+    "github": {
+      "template": "github",
+      "version": "1.2.0",
+      "author": "MCP Team",
+      "category": "official",
+      "tags": ["git", "vcs"],
+      "requirements": { "node": ">=18" },
+      "added_at": "2026-03-01T12:00:00Z",
+      "notes": "Work account\nRotate token quarterly"
+    }
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    let metadata_path = config_path(home.path()).with_file_name("server_metadata.json");
+    fs::write(metadata_path, METADATA).unwrap();
+    home
+}
+
+fn export_annotated(home: &TempDir) -> String {
+    let output = mcp_forge(home.path())
+        .args(["export", "--format", "yaml", "--annotate", "--utc"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn annotated_yaml_describes_each_server() {
+    let home = setup();
+    let yaml = export_annotated(&home);
+
+    assert!(yaml.contains("  # template: github v1.2.0 by MCP Team (official)"));
+    assert!(yaml.contains("  # added: 2026-03-01T12:00:00Z"));
+    assert!(yaml.contains("  # tags: git, vcs"));
+    assert!(yaml.contains("  # notes: Work account\n  # notes: Rotate token quarterly"));
+    assert!(yaml.contains("  # secret: GITHUB_TOKEN = ghp**********jkl"));
+    assert!(!yaml.contains("# secret: LOG_LEVEL"));
+    assert!(yaml.contains("  # template: unknown"));
+}
+
+#[test]
+fn annotated_yaml_round_trips_without_comments() {
+    let home = setup();
+    let yaml = export_annotated(&home);
+
+    let stripped: String = yaml
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    let parsed: serde_json::Value = serde_yaml::from_str(&stripped).unwrap();
+
+    assert_eq!(parsed, read_config(home.path()));
+}
+
+#[test]
+fn annotate_requires_yaml_format() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["export", "--annotate"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--format yaml"));
+}
+
+#[test]
+fn split_export_round_trips_through_directory_import() {
+    let home = setup();
+    let dir = home.path().join("servers");
+
+    mcp_forge(home.path())
+        .args(["export", "--split", "--include-secrets", "--output-dir"])
+        .arg(&dir)
+        .assert()
+        .success();
+    assert!(dir.join("github.json").exists());
+    assert!(dir.join("remote.json").exists());
+    let index: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dir.join("index.json")).unwrap()).unwrap();
+    assert_eq!(index["servers"][0]["name"], "github");
+    assert_eq!(index["secrets_masked"], false);
+
+    let other = TempDir::new().unwrap();
+    write_config(other.path(), r#"{ "mcpServers": {} }"#);
+    mcp_forge(other.path())
+        .args(["import", "--merge", "--file"])
+        .arg(&dir)
+        .assert()
+        .success();
+
+    assert_eq!(
+        read_config(other.path())["mcpServers"],
+        read_config(home.path())["mcpServers"]
+    );
+}
+
+#[test]
+fn split_export_masks_secrets_by_default() {
+    let home = setup();
+    let dir = home.path().join("servers");
+
+    mcp_forge(home.path())
+        .args(["export", "--split", "--format", "yaml", "--output-dir"])
+        .arg(&dir)
+        .assert()
+        .success();
+
+    let github = fs::read_to_string(dir.join("github.yaml")).unwrap();
+    assert!(github.contains("ghp**********jkl"));
+    assert!(!github.contains("ghp_abcdefghijkl"));
+    assert!(github.contains("LOG_LEVEL: debug"));
+
+    // Importing masked values would overwrite real secrets
+    mcp_forge(home.path())
+        .args(["import", "--merge", "--file"])
+        .arg(&dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--include-secrets"));
+}
+
+#[test]
+fn directory_import_rejects_duplicate_servers() {
+    let home = setup();
+    let dir = home.path().join("servers");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.json"), r#"{ "dup": { "command": "a" } }"#).unwrap();
+    fs::write(dir.join("b.yaml"), "dup:\n  command: b\n").unwrap();
+
+    mcp_forge(home.path())
+        .args(["import", "--merge", "--file"])
+        .arg(&dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Server 'dup' is defined in both"));
+}
+
+const MARKDOWN_SNAPSHOT: &str = "tests/snapshots/export-markdown.md";
+
+// If this fails after an intended change to the report, regenerate it with
+// `UPDATE_SNAPSHOTS=1 cargo test --test export` and review the diff.
+#[test]
+fn markdown_export_matches_snapshot() {
+    let home = setup();
+    let mut config: serde_json::Value = serde_json::from_str(CONFIG).unwrap();
+    config["mcpServers"]["db"] = serde_json::json!({
+        "command": "/usr/local/bin/python3",
+        "args": ["-m", "db_mcp", "--dsn", "postgres://db/app"],
+        "env": { "DB_PASSWORD": "hunter2hunter2" }
+    });
+    config["disabledServers"] = serde_json::json!({ "old": { "command": "old-mcp" } });
+    write_config(home.path(), &config.to_string());
+    fs::write(
+        config_path(home.path()).with_file_name("profiles.json"),
+        r#"{"current_profile": "work", "profiles": {}}"#,
+    )
+    .unwrap();
+
+    let output = mcp_forge(home.path())
+        .args(["export", "--format", "markdown", "--utc"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let markdown = String::from_utf8(output.stdout).unwrap();
+
+    // The generation time is the only thing that changes between runs
+    let (header, rest) = markdown.split_once("- Profile:").unwrap();
+    assert!(header.starts_with("# MCP server inventory\n\n- Generated: 20"));
+    let actual = format!("- Profile:{}", rest);
+    assert!(!actual.contains("ghp_abcdefghijkl") && !actual.contains("hunter2hunter2"));
+
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(MARKDOWN_SNAPSHOT);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        actual == expected,
+        "Markdown export differs from {}; rerun with UPDATE_SNAPSHOTS=1 if the change is intended:\n{}",
+        MARKDOWN_SNAPSHOT,
+        actual
+    );
+}
@@ -0,0 +1,142 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const POSTGRES: &str = r#"{
+  "name": "postgres",
+  "version": "1.0.0",
+  "description": "Postgres",
+  "author": "Official",
+  "tags": ["database"],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {
+    "host": { "type": "string", "description": "Host", "required": true },
+    "port": { "type": "number", "description": "Port", "default": 5432 }
+  },
+  "config": {
+    "command": "pg-mcp",
+    "args": ["--host", "{{host}}", "--port", "{{port}}"],
+    "env": { "PGSSLMODE": "require" }
+  }
+}"#;
+
+const READONLY: &str = r#"{
+  "name": "pg-readonly",
+  "version": "1.0.0",
+  "description": "Read-only Postgres",
+  "author": "Community",
+  "extends": "postgres",
+  "config": { "env": { "PGOPTIONS": "-c default_transaction_read_only=on" } }
+}"#;
+
+const BAD_OVERRIDE: &str = r#"{
+  "name": "pg-bad",
+  "version": "1.0.0",
+  "description": "Postgres with a string port",
+  "author": "Community",
+  "extends": "pg-readonly",
+  "variables": { "port": { "type": "string", "description": "Port" } }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    seed_templates(
+        home.path(),
+        &[
+            ("postgres", POSTGRES),
+            ("pg-readonly", READONLY),
+            ("pg-bad", BAD_OVERRIDE),
+        ],
+    );
+    home
+}
+
+#[test]
+fn add_merges_the_parent_template() {
+    let home = setup();
+
+    offline(home.path())
+        .args([
+            "add",
+            "db",
+            "pg-readonly",
+            "--vars",
+            "host=replica,port=6432",
+        ])
+        .assert()
+        .success();
+
+    let server = &read_config(home.path())["mcpServers"]["db"];
+    assert_eq!(server["command"], "pg-mcp");
+    assert_eq!(
+        server["args"],
+        serde_json::json!(["--host", "replica", "--port", "6432"])
+    );
+    assert_eq!(server["env"]["PGSSLMODE"], "require");
+    assert_eq!(
+        server["env"]["PGOPTIONS"],
+        "-c default_transaction_read_only=on"
+    );
+}
+
+#[test]
+fn show_names_the_inheritance_chain() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["template", "show", "pg-bad", "--cached"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Extends: pg-bad → pg-readonly → postgres",
+        ))
+        .stdout(predicate::str::contains("Tags: database"));
+}
+
+#[test]
+fn cat_flags_a_variable_overridden_with_another_type() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["template", "cat", "pg-bad", "--cached"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Variable 'port' is a string here but a number in 'pg-readonly', which it extends",
+        ));
+    offline(home.path())
+        .args(["template", "cat", "pg-readonly", "--cached"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Variable").not());
+}
+
+#[test]
+fn a_cycle_is_reported() {
+    let home = setup();
+    let cache = template_cache_dir(home.path());
+    fs::write(
+        cache.join("postgres.json"),
+        POSTGRES.replace(
+            r#""author": "Official","#,
+            r#""author": "Official", "extends": "pg-readonly","#,
+        ),
+    )
+    .unwrap();
+
+    offline(home.path())
+        .args(["template", "show", "pg-readonly", "--cached"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Template inheritance cycle: pg-readonly → postgres → pg-readonly",
+        ));
+}
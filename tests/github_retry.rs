@@ -0,0 +1,37 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// `offline` with the retries the other tests turn off
+fn with_retries(home: &Path) -> assert_cmd::Command {
+    let mut cmd = offline(home);
+    cmd.env_remove("MCP_FORGE_NO_RETRY");
+    cmd
+}
+
+#[test]
+fn connection_errors_are_retried_unless_no_retry() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+
+    with_retries(home.path())
+        .args(["--verbose", "template", "refresh"])
+        .assert()
+        .stderr(
+            predicate::str::contains("(attempt 1 of 3); retrying in")
+                .and(predicate::str::contains("(attempt 2 of 3); retrying in"))
+                .and(predicate::str::contains("after 3 attempts")),
+        );
+
+    with_retries(home.path())
+        .args(["--verbose", "--no-retry", "template", "refresh"])
+        .assert()
+        .stderr(predicate::str::contains("retrying").not());
+}
@@ -0,0 +1,49 @@
+// These tests redirect the config directory through $HOME, which `dirs` only
+// honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn doctor_reports_the_token_without_revealing_it() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+
+    offline(home.path())
+        .arg("doctor")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Token: not set"))
+        .stdout(predicate::str::contains(
+            "Couldn't check the GitHub rate limit",
+        ));
+
+    let settings = home.path().join(".config/mcp-forge/settings.json");
+    fs::create_dir_all(settings.parent().unwrap()).unwrap();
+    fs::write(
+        &settings,
+        r#"{ "github_token": "github_pat_fromsettings" }"#,
+    )
+    .unwrap();
+    offline(home.path())
+        .arg("doctor")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Token: github_pat_**** from settings.json",
+        ))
+        .stdout(predicate::str::contains("fromsettings").not());
+
+    offline(home.path())
+        .env("GH_TOKEN", "ghp_fromenv")
+        .arg("doctor")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Token: ghp_**** from GH_TOKEN"))
+        .stdout(predicate::str::contains("fromenv").not());
+}
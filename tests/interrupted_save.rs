@@ -0,0 +1,91 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const BEFORE: &str = r#"{"mcpServers":{"api":{"command":"api-mcp"}}}"#;
+const AFTER: &str = r#"{"mcpServers":{"api":{"command":"api-mcp"},"db":{"command":"db-mcp"}}}"#;
+const METADATA: &str = r#"{"servers":{},"presets":{"db":"npx:db-mcp"}}"#;
+
+fn sidecar(home: &Path, name: &str) -> PathBuf {
+    config_path(home).with_file_name(name)
+}
+
+/// Leave things as if mcp-forge died while adding `db`, with the config
+/// either written or not
+fn interrupted_add(config_written: bool) -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), if config_written { AFTER } else { BEFORE });
+    let journal = json!({
+        "started_at": "2026-03-01T12:00:00Z",
+        "writes": [
+            { "path": config_path(home.path()), "before": BEFORE, "after": AFTER },
+            { "path": sidecar(home.path(), "server_metadata.json"), "before": null, "after": METADATA },
+        ]
+    });
+    fs::write(
+        sidecar(home.path(), "pending_writes.json"),
+        journal.to_string(),
+    )
+    .unwrap();
+    home
+}
+
+#[test]
+fn next_run_finishes_a_change_whose_config_was_written() {
+    let home = interrupted_add(true);
+
+    mcp_forge(home.path())
+        .args(["list", "--names-only"])
+        .assert()
+        .success()
+        .stdout("api\ndb\n")
+        .stderr(predicate::str::contains(
+            "was interrupted; finished it (1 file written)",
+        ));
+
+    let metadata = fs::read_to_string(sidecar(home.path(), "server_metadata.json")).unwrap();
+    assert_eq!(metadata, METADATA);
+    assert!(!sidecar(home.path(), "pending_writes.json").exists());
+}
+
+#[test]
+fn next_run_undoes_a_change_whose_config_was_not_written() {
+    let home = interrupted_add(false);
+    fs::write(sidecar(home.path(), "server_metadata.json"), METADATA).unwrap();
+
+    mcp_forge(home.path())
+        .args(["list", "--names-only"])
+        .assert()
+        .success()
+        .stdout("api\n")
+        .stderr(predicate::str::contains(
+            "was interrupted; undid it (1 file restored)",
+        ));
+
+    assert!(!sidecar(home.path(), "server_metadata.json").exists());
+    assert!(!sidecar(home.path(), "pending_writes.json").exists());
+}
+
+#[test]
+fn saves_leave_no_journal_behind() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), BEFORE);
+
+    mcp_forge(home.path())
+        .args(["rename", "api", "api2"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("interrupted").not());
+
+    assert!(read_config(home.path())["mcpServers"]["api2"].is_object());
+    assert!(!sidecar(home.path(), "pending_writes.json").exists());
+}
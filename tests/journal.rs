@@ -0,0 +1,116 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{ "mcpServers": { "local": { "command": "node", "args": [] } } }"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    fs::create_dir_all(config_path(home.path()).with_file_name("backups")).unwrap();
+    home
+}
+
+fn journal(home: &TempDir) -> Vec<serde_json::Value> {
+    let output = mcp_forge(home.path())
+        .args(["log", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn mutating_commands_are_journaled_newest_first() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["update", "local", "--set", "API_KEY=secret-value"])
+        .assert()
+        .success();
+    mcp_forge(home.path())
+        .args(["update", "missing", "--set", "A=1"])
+        .assert()
+        .failure();
+    // Read-only commands are not journaled
+    mcp_forge(home.path()).args(["list"]).assert().success();
+
+    let entries = journal(&home);
+    assert_eq!(entries.len(), 2);
+
+    assert_eq!(entries[0]["command"], "update");
+    assert_eq!(entries[0]["success"], false);
+    assert!(entries[0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("Server 'missing' not found"));
+
+    assert_eq!(entries[1]["success"], true);
+    assert_eq!(entries[1]["servers"], serde_json::json!(["local"]));
+    assert!(entries[1]["backup"]
+        .as_str()
+        .unwrap()
+        .contains("config_backup_"));
+
+    let log =
+        fs::read_to_string(config_path(home.path()).with_file_name("operations.log")).unwrap();
+    assert!(!log.contains("secret-value"));
+}
+
+#[test]
+fn log_renders_relative_times_and_respects_limit() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["log"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No operations recorded yet."));
+
+    for key in ["A=1", "B=2"] {
+        mcp_forge(home.path())
+            .args(["update", "local", "--set", key])
+            .assert()
+            .success();
+    }
+
+    mcp_forge(home.path())
+        .args(["log", "--limit", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ update just now"))
+        .stdout(predicate::str::contains("Servers: local"))
+        .stdout(predicate::str::contains("update").count(1));
+}
+
+#[test]
+fn validate_fix_is_journaled_when_errors_remain() {
+    let home = setup();
+    write_config(
+        home.path(),
+        r#"{ "mcpServers": { "api": {
+            "command": "mcp-forge-no-such-binary",
+            "env": { "api_key": "one", "API_KEY": "two" }
+        } } }"#,
+    );
+
+    mcp_forge(home.path())
+        .args(["validate", "--fix", "--force"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("Validation completed with errors"))
+        .stdout(predicate::str::contains("Backup: config_backup_"));
+
+    let entries = journal(&home);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["command"], "validate");
+    assert_eq!(entries[0]["servers"], serde_json::json!(["api"]));
+    assert_eq!(entries[0]["error"], "Validation found errors");
+}
@@ -0,0 +1,99 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{ "mcpServers": {
+  "gateway": { "command": "sh", "args": [] },
+  "api": { "command": "sh", "args": [] },
+  "db": { "command": "sh", "args": [] },
+  "solo": { "command": "sh", "args": [] }
+} }"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    for (server, depends_on) in [("gateway", "api"), ("api", "db")] {
+        mcp_forge(home.path())
+            .args(["link", "set", server, "--depends-on", depends_on])
+            .assert()
+            .success();
+    }
+    home
+}
+
+#[test]
+fn link_set_rejects_cycles_and_unknown_servers() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["link", "set", "db", "--depends-on", "gateway"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "dependency cycle: db → gateway → api → db",
+        ));
+    mcp_forge(home.path())
+        .args(["link", "set", "solo", "--depends-on", "ghost"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Server(s) not found: ghost"));
+}
+
+#[test]
+fn list_tree_renders_the_dependency_graph() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["list", "--tree"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "gateway\n└── api\n    └── db\nsolo",
+        ));
+}
+
+#[test]
+fn remove_warns_about_dependents_and_validate_reports_them() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["remove", "db", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "'api' depends on a server being removed",
+        ))
+        .stdout(predicate::str::contains("'gateway' depends on"));
+
+    mcp_forge(home.path())
+        .args(["validate", "api", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Depends on 'db', which is not configured",
+        ));
+}
+
+#[test]
+fn remove_cascade_takes_dependents_along() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["remove", "api", "--force", "--cascade"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Also removing dependent server(s): gateway",
+        ));
+
+    let servers = read_config(home.path())["mcpServers"].clone();
+    let mut names: Vec<&String> = servers.as_object().unwrap().keys().collect();
+    names.sort();
+    assert_eq!(names, ["db", "solo"]);
+}
@@ -0,0 +1,104 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn template(author: &str) -> String {
+    format!(
+        r#"{{
+  "name": "internal",
+  "version": "1.0.0",
+  "description": "Internal tools",
+  "author": "{}",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {{
+    "port": {{ "type": "number", "description": "Port", "default": 8080 }}
+  }},
+  "config": {{ "command": "internal-mcp", "args": ["--port", "{{{{port}}}}"] }}
+}}"#,
+        author
+    )
+}
+
+/// A home with a team template directory and nothing cached
+fn setup() -> (TempDir, TempDir) {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    let team = TempDir::new().unwrap();
+    fs::write(team.path().join("internal.json"), template("Team")).unwrap();
+    (home, team)
+}
+
+/// `offline` with `team` as the local template directory
+fn with_team(home: &Path, team: &Path) -> assert_cmd::Command {
+    let mut cmd = offline(home);
+    cmd.env("MCP_FORGE_TEMPLATE_PATH", team);
+    cmd
+}
+
+#[test]
+fn add_and_show_work_offline_with_a_local_template() {
+    let (home, team) = setup();
+
+    with_team(home.path(), team.path())
+        .args(["template", "show", "internal"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Author: Team").and(predicate::str::contains(format!(
+                "Source: local file {}",
+                team.path().join("internal.json").display()
+            ))),
+        );
+
+    with_team(home.path(), team.path())
+        .args(["add", "tools", "internal", "--var", "port=9000"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(local)"));
+    assert_eq!(
+        read_config(home.path())["mcpServers"]["tools"]["args"],
+        serde_json::json!(["--port", "9000"])
+    );
+}
+
+#[test]
+fn list_shows_local_templates_when_the_catalog_is_unreachable() {
+    let (home, team) = setup();
+
+    with_team(home.path(), team.path())
+        .args(["template", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("internal (local)"))
+        .stderr(predicate::str::contains("listing local templates only"));
+}
+
+#[test]
+fn template_path_takes_precedence_over_the_config_directory() {
+    let (home, team) = setup();
+    let personal = home.path().join(".config/mcp-forge/templates");
+    fs::create_dir_all(&personal).unwrap();
+    fs::write(personal.join("internal.json"), template("Me")).unwrap();
+    fs::write(personal.join("mine.json"), template("Me")).unwrap();
+
+    with_team(home.path(), team.path())
+        .args(["template", "show", "internal"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Author: Team"));
+
+    with_team(home.path(), team.path())
+        .args(["template", "list", "--offline"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("internal").and(predicate::str::contains("mine")));
+}
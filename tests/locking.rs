@@ -0,0 +1,98 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use fs4::FileExt;
+use predicates::prelude::*;
+use std::fs::File;
+use std::path::Path;
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{"mcpServers": {}}"#);
+    home
+}
+
+/// Hold the config lock the way another mcp-forge process would
+fn hold_lock(home: &Path) -> File {
+    let file = File::create(config_path(home).with_file_name("mcp-forge.lock")).unwrap();
+    file.lock_exclusive().unwrap();
+    file
+}
+
+#[test]
+fn concurrent_adds_all_survive() {
+    let home = setup();
+
+    let children: Vec<_> = (0..6)
+        .map(|i| {
+            let mut cmd = std::process::Command::new(env!("CARGO_BIN_EXE_mcp-forge"));
+            cmd.env("HOME", home.path())
+                .env("XDG_CONFIG_HOME", home.path().join(".config"))
+                .env("NO_COLOR", "1")
+                .args(["add", &format!("server-{}", i), "--preset"])
+                .arg(format!("npx:pkg-{}", i))
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .spawn()
+                .unwrap()
+        })
+        .collect();
+    for mut child in children {
+        assert!(child.wait().unwrap().success());
+    }
+
+    let servers = read_config(home.path())["mcpServers"].clone();
+    for i in 0..6 {
+        assert!(
+            servers[format!("server-{}", i)].is_object(),
+            "server-{} was lost: {}",
+            i,
+            servers
+        );
+    }
+}
+
+#[test]
+fn gives_up_when_another_process_holds_the_lock() {
+    let home = setup();
+    let _lock = hold_lock(home.path());
+
+    mcp_forge(home.path())
+        .env("MCP_FORGE_LOCK_TIMEOUT", "1s")
+        .args(["add", "api", "--preset", "npx:api"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "is changing the configuration; gave up after 1s",
+        ))
+        .stderr(predicate::str::contains("--wait"));
+    assert!(read_config(home.path())["mcpServers"]["api"].is_null());
+
+    // Reading doesn't need the lock
+    mcp_forge(home.path()).arg("list").assert().success();
+}
+
+#[test]
+fn wait_blocks_until_the_lock_is_released() {
+    let home = setup();
+    let lock = hold_lock(home.path());
+    let release = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(1500));
+        drop(lock);
+    });
+
+    mcp_forge(home.path())
+        .env("MCP_FORGE_LOCK_TIMEOUT", "1s")
+        .args(["--wait", "add", "api", "--preset", "npx:api"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Waiting for"));
+    release.join().unwrap();
+    assert!(read_config(home.path())["mcpServers"]["api"].is_object());
+}
@@ -0,0 +1,174 @@
+// These tests redirect the config directory through $HOME, which `dirs` only
+// honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+/// A home whose config is the named fixture from `tests/fixtures/malformed_servers`
+fn setup(fixture: &str) -> TempDir {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/malformed_servers")
+        .join(fixture);
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), &fs::read_to_string(path).unwrap());
+    home
+}
+
+#[test]
+fn null_servers_need_force_without_a_terminal() {
+    let home = setup("null.json");
+    let before = fs::read_to_string(config_path(home.path())).unwrap();
+
+    mcp_forge(home.path())
+        .arg("list")
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("'mcpServers' in")
+                .and(predicate::str::contains("is null"))
+                .and(predicate::str::contains("config repair"))
+                .and(predicate::str::contains("--force")),
+        );
+    assert_eq!(
+        fs::read_to_string(config_path(home.path())).unwrap(),
+        before
+    );
+
+    mcp_forge(home.path())
+        .args(["--force", "list"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("treating it as empty"));
+}
+
+#[test]
+fn forced_save_replaces_a_list_and_keeps_other_keys() {
+    let home = setup("empty_list.json");
+    let file = home.path().join("servers.json");
+    fs::write(
+        &file,
+        r#"{ "mcpServers": { "web": { "command": "web-mcp", "args": [] } } }"#,
+    )
+    .unwrap();
+
+    mcp_forge(home.path())
+        .args(["--force", "import", "--merge", "--file"])
+        .arg(&file)
+        .assert()
+        .success();
+    assert_eq!(
+        read_config(home.path())["mcpServers"]["web"]["command"],
+        "web-mcp"
+    );
+
+    let home = setup("null.json");
+    mcp_forge(home.path())
+        .args(["--force", "import", "--merge", "--file"])
+        .arg(&file)
+        .assert()
+        .success();
+    let config = read_config(home.path());
+    assert_eq!(config["globalShortcut"], "Alt+Space");
+    assert!(config["mcpServers"].get("web").is_some());
+}
+
+#[test]
+fn misspelled_key_suggests_repair() {
+    let home = setup("misspelled.json");
+
+    mcp_forge(home.path())
+        .args(["--force", "list"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "has 'mcp_servers' but no 'mcpServers' — did you mean mcpServers?",
+        ))
+        .stderr(predicate::str::contains("mcp-forge config repair"));
+
+    mcp_forge(home.path())
+        .args(["config", "repair", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "renamed 'mcp_servers' to 'mcpServers'",
+        ));
+    assert!(read_config(home.path()).get("mcp_servers").is_some());
+
+    mcp_forge(home.path())
+        .args(["config", "repair"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Configuration repaired"));
+    let config = read_config(home.path());
+    assert!(config.get("mcp_servers").is_none());
+    assert_eq!(config["mcpServers"]["files"]["command"], "npx");
+    assert_eq!(config["globalShortcut"], "Alt+Space");
+
+    mcp_forge(home.path())
+        .args(["list", "--names-only"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("files"));
+    mcp_forge(home.path())
+        .args(["config", "repair"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing to repair"));
+}
+
+#[test]
+fn repair_fixes_null_but_not_a_list_of_servers() {
+    let home = setup("null.json");
+    mcp_forge(home.path())
+        .args(["config", "repair"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "replaced null 'mcpServers' with {}",
+        ));
+    assert_eq!(
+        read_config(home.path())["mcpServers"],
+        serde_json::json!({})
+    );
+
+    let home = setup("list.json");
+    mcp_forge(home.path())
+        .args(["config", "repair"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("a list of 1 item(s)"));
+    mcp_forge(home.path())
+        .args(["--force", "list"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn other_types_get_a_targeted_error_even_with_force() {
+    let home = setup("string.json");
+
+    mcp_forge(home.path())
+        .args(["--force", "list"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "is a string, not an object of servers",
+        ));
+}
+
+#[test]
+fn missing_servers_key_means_no_servers() {
+    let home = setup("missing.json");
+
+    mcp_forge(home.path())
+        .arg("list")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("mcpServers").not());
+}
@@ -0,0 +1,72 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{ "mcpServers": {
+  "api": { "command": "node", "env": { "api_key": "one", "Api_Key": "two", "API_KEY": "three" } }
+} }"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    home
+}
+
+fn env_keys(home: &TempDir) -> Vec<String> {
+    let config = read_config(home.path());
+    let mut keys: Vec<String> = config["mcpServers"]["api"]["env"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn normalize_without_a_terminal_needs_force() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["config", "normalize"])
+        .write_stdin("y\n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("pass --force"));
+    assert_eq!(env_keys(&home).len(), 3);
+
+    mcp_forge(home.path())
+        .args(["config", "normalize", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("api: kept API_KEY"));
+    assert_eq!(env_keys(&home), ["API_KEY"]);
+    assert_eq!(
+        read_config(home.path())["mcpServers"]["api"]["env"]["API_KEY"],
+        "three"
+    );
+}
+
+#[test]
+fn validate_fix_force_keeps_the_upper_case_key() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["validate", "--fix", "--force"])
+        .assert()
+        .stdout(predicate::str::contains("api: kept API_KEY"));
+    assert_eq!(env_keys(&home), ["API_KEY"]);
+
+    mcp_forge(home.path())
+        .args(["validate", "--force"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--fix"));
+}
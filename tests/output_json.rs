@@ -0,0 +1,181 @@
+// These tests redirect the config directory through $HOME, which `dirs` only
+// honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "git": { "command": "git-mcp", "args": [] },
+    "notes": { "command": "notes-mcp", "args": [], "env": { "API_KEY": "sk-live-1234567890" } }
+  }
+}"#;
+
+const TEMPLATE: &str = r#"{
+  "name": "internal",
+  "version": "1.0.0",
+  "description": "Internal tools",
+  "author": "Team",
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {
+    "token": { "type": "string", "description": "Token", "required": true, "secret": true }
+  },
+  "config": { "command": "internal-mcp", "args": ["--token", "{{token}}"] }
+}"#;
+
+/// Parse stdout, which must hold nothing but the JSON document
+fn document(output: &std::process::Output) -> serde_json::Value {
+    serde_json::from_slice(&output.stdout).unwrap_or_else(|e| {
+        panic!(
+            "stdout is not JSON ({}):\n{}",
+            e,
+            String::from_utf8_lossy(&output.stdout)
+        )
+    })
+}
+
+#[test]
+fn add_prints_the_new_server() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+
+    let output = mcp_forge(home.path())
+        .args([
+            "add",
+            "files",
+            "--preset",
+            "npx:server-files",
+            "--output",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("✓ Server 'files' added"));
+
+    let doc = document(&output);
+    assert_eq!(doc["operation"], "add");
+    assert_eq!(doc["name"], "files");
+    assert_eq!(doc["server"]["command"], "npx");
+    assert_eq!(
+        doc["server"]["args"],
+        serde_json::json!(["-y", "server-files"])
+    );
+    assert!(doc["backup"]
+        .as_str()
+        .unwrap()
+        .starts_with("config_backup_"));
+    assert_eq!(
+        doc["config_path"].as_str().unwrap(),
+        config_path(home.path()).to_str().unwrap()
+    );
+}
+
+#[test]
+fn add_from_a_template_masks_secrets_and_includes_provenance() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    let team = TempDir::new().unwrap();
+    fs::write(team.path().join("internal.json"), TEMPLATE).unwrap();
+
+    let output = mcp_forge(home.path())
+        .env("MCP_FORGE_TEMPLATE_PATH", team.path())
+        .args(["add", "tools", "internal", "--output", "json"])
+        .args(["--var", "token=hunter2-secret"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let doc = document(&output);
+    assert_eq!(
+        doc["server"]["args"],
+        serde_json::json!(["--token", "********"])
+    );
+    assert_eq!(doc["provenance"]["template"], "internal");
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("hunter2"));
+    // The saved server keeps the real value
+    assert_eq!(
+        read_config(home.path())["mcpServers"]["tools"]["args"][1],
+        "hunter2-secret"
+    );
+}
+
+#[test]
+fn update_prints_the_changed_server_with_credentials_masked() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+
+    let output = mcp_forge(home.path())
+        .args(["update", "notes", "--set", "MODE=fast", "--output", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let doc = document(&output);
+    assert_eq!(doc["operation"], "update");
+    assert_eq!(doc["server"]["env"]["MODE"], "fast");
+    assert_ne!(doc["server"]["env"]["API_KEY"], "sk-live-1234567890");
+    assert!(doc.get("provenance").is_none());
+}
+
+#[test]
+fn remove_prints_a_null_server() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+
+    let output = mcp_forge(home.path())
+        .args(["remove", "git", "--force", "--output", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let doc = document(&output);
+    assert_eq!(doc["operation"], "remove");
+    assert_eq!(doc["name"], "git");
+    assert!(doc["server"].is_null());
+    assert!(doc["backup"].is_string());
+    assert!(read_config(home.path())["mcpServers"].get("git").is_none());
+
+    mcp_forge(home.path())
+        .args(["remove", "notes", "git", "--force", "--output", "json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("name exactly one"));
+}
+
+#[test]
+fn failures_are_reported_as_json() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+
+    let output = mcp_forge(home.path())
+        .args(["remove", "missing", "--force", "--output", "json"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let doc = document(&output);
+    assert_eq!(doc["operation"], "remove");
+    assert_eq!(doc["name"], "missing");
+    assert_eq!(doc["error"]["kind"], "error");
+    assert!(doc["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("not found"));
+
+    // Replacing a server needs a confirmation no one is there to give
+    let output = mcp_forge(home.path())
+        .args(["add", "git", "--preset", "npx:other", "--output", "json"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert_eq!(document(&output)["error"]["kind"], "cancelled");
+    assert_eq!(
+        read_config(home.path())["mcpServers"]["git"]["command"],
+        "git-mcp"
+    );
+}
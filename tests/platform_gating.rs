@@ -0,0 +1,117 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms, so a windows-only template is
+// always a mismatch here.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const WINDOWS_ONLY: &str = r#"{
+  "name": "win-tools",
+  "version": "1.0.0",
+  "description": "Windows tools",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["windows"],
+  "variables": {},
+  "config": { "command": "win-tools.exe", "args": [] }
+}"#;
+
+const CATALOG: &str = r#"{
+  "version": "1",
+  "last_updated": "2025-01-01",
+  "templates": {
+    "win-tools": {
+      "name": "win-tools", "version": "1.0.0", "description": "Windows tools",
+      "author": "Test", "tags": [], "platforms": ["windows"],
+      "category": "community", "path": "templates/win-tools.json"
+    },
+    "anywhere": {
+      "name": "anywhere", "version": "1.0.0", "description": "Portable tools",
+      "author": "Test", "tags": [], "platforms": ["linux", "macos", "windows"],
+      "category": "community", "path": "templates/anywhere.json"
+    }
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    let cache = seed_templates(home.path(), &[("win-tools", WINDOWS_ONLY)]);
+    fs::write(cache.parent().unwrap().join("catalog.json"), CATALOG).unwrap();
+    home
+}
+
+#[test]
+fn add_refuses_a_template_for_another_platform() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["add", "win", "win-tools"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Template 'win-tools' supports windows only, not ",
+        ))
+        .stderr(predicate::str::contains("--ignore-platform"));
+    assert!(read_config(home.path())["mcpServers"]
+        .as_object()
+        .unwrap()
+        .is_empty());
+
+    let batch = home.path().join("batch.yaml");
+    fs::write(&batch, "servers:\n  - name: win\n    template: win-tools\n").unwrap();
+    offline(home.path())
+        .args(["bulk", "add", "--file"])
+        .arg(&batch)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("supports windows only"));
+}
+
+#[test]
+fn ignore_platform_adds_and_validate_keeps_warning() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["add", "win", "win-tools", "--ignore-platform"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("adding it anyway"));
+
+    let metadata = config_path(home.path()).with_file_name("server_metadata.json");
+    let metadata: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(metadata).unwrap()).unwrap();
+    assert_eq!(
+        metadata["servers"]["win"]["platform_override"]["supported"],
+        serde_json::json!(["windows"])
+    );
+
+    offline(home.path())
+        .args(["validate", "win"])
+        .assert()
+        .stdout(predicate::str::contains(
+            "Added with --ignore-platform: template 'win-tools' supports windows",
+        ));
+}
+
+#[test]
+fn template_list_compatible_hides_other_platforms() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["template", "list", "--cached"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("win-tools"));
+    offline(home.path())
+        .args(["template", "list", "--cached", "--compatible"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("anywhere"))
+        .stdout(predicate::str::contains("win-tools").not());
+}
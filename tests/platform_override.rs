@@ -0,0 +1,108 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const PROBE: &str = r#"{
+  "name": "probe",
+  "version": "1.0.0",
+  "description": "Reports the platform",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["windows"],
+  "variables": {},
+  "config": { "command": "probe", "args": ["{{os}}", "{{arch}}"] }
+}"#;
+
+const CATALOG: &str = r#"{
+  "version": "1",
+  "last_updated": "2025-01-01",
+  "templates": {
+    "probe": {
+      "name": "probe", "version": "1.0.0", "description": "Reports the platform",
+      "author": "Test", "tags": [], "platforms": ["windows"],
+      "category": "community", "path": "templates/probe.json"
+    },
+    "penguin": {
+      "name": "penguin", "version": "1.0.0", "description": "Linux only",
+      "author": "Test", "tags": [], "platforms": ["linux"],
+      "category": "community", "path": "templates/penguin.json"
+    }
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    let cache = seed_templates(home.path(), &[("probe", PROBE)]);
+    fs::write(cache.parent().unwrap().join("catalog.json"), CATALOG).unwrap();
+    home
+}
+
+/// Behave as `platform`, with every request routed through a proxy that
+/// refuses connections
+fn as_platform(home: &TempDir, platform: &str) -> assert_cmd::Command {
+    let mut cmd = offline(home.path());
+    cmd.env("MCP_FORGE_PLATFORM_OVERRIDE", platform);
+    cmd
+}
+
+#[test]
+fn every_platform_check_follows_the_override() {
+    let home = setup();
+
+    // Gating and the template helpers
+    as_platform(&home, "windows/arm64")
+        .args(["add", "probe", "probe"])
+        .assert()
+        .success();
+    assert_eq!(
+        read_config(home.path())["mcpServers"]["probe"]["args"],
+        serde_json::json!(["windows", "arm64"])
+    );
+
+    // Compatibility filtering
+    as_platform(&home, "windows/arm64")
+        .args(["template", "list", "--offline", "--compatible"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("probe").and(predicate::str::contains("penguin").not()));
+
+    // Diagnostics
+    as_platform(&home, "windows/arm64")
+        .arg("doctor")
+        .assert()
+        .stdout(predicate::str::contains("Platform: windows arm64"));
+}
+
+#[test]
+fn overridden_platform_gates_like_a_real_one() {
+    let home = setup();
+
+    as_platform(&home, "macos")
+        .args(["add", "probe", "probe"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Template 'probe' supports windows only, not macos",
+        ));
+}
+
+#[test]
+fn unknown_override_warns_and_uses_the_real_platform() {
+    let home = setup();
+
+    as_platform(&home, "plan9")
+        .args(["template", "list", "--offline", "--compatible"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "MCP_FORGE_PLATFORM_OVERRIDE: unknown operating system 'plan9'",
+        ));
+}
@@ -0,0 +1,88 @@
+// These tests redirect the config directory through $HOME, which `dirs` only
+// honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    home
+}
+
+#[test]
+fn preset_adds_a_server_with_passthrough_args() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args([
+            "add",
+            "files",
+            "--preset",
+            "npx:@modelcontextprotocol/server-filesystem",
+            "--",
+            "/srv/docs",
+            "--read-only",
+        ])
+        .assert()
+        .success();
+
+    let server = &read_config(home.path())["mcpServers"]["files"];
+    assert_eq!(server["command"], "npx");
+    assert_eq!(
+        server["args"],
+        serde_json::json!([
+            "-y",
+            "@modelcontextprotocol/server-filesystem",
+            "/srv/docs",
+            "--read-only"
+        ])
+    );
+
+    let metadata_path = config_path(home.path()).with_file_name("server_metadata.json");
+    let metadata: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(metadata_path).unwrap()).unwrap();
+    assert_eq!(
+        metadata["presets"]["files"],
+        "npx:@modelcontextprotocol/server-filesystem"
+    );
+}
+
+#[test]
+fn preset_dry_run_leaves_the_config_alone() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["add", "git", "--preset", "uvx:mcp-server-git", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("NEW git"))
+        .stdout(predicate::str::contains("uvx"));
+    assert!(read_config(home.path())["mcpServers"]["git"].is_null());
+}
+
+#[test]
+fn preset_conflicts_with_a_template_and_vars() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["add", "x", "filesystem", "--preset", "npx:pkg"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+    mcp_forge(home.path())
+        .args(["add", "x", "--preset", "npx:pkg", "--vars", "a=b"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+    mcp_forge(home.path())
+        .args(["add", "x", "--preset", "brew:pkg"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown preset runtime 'brew'"));
+}
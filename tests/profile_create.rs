@@ -0,0 +1,141 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "filesystem": { "command": "npx", "args": [] },
+    "github": { "command": "npx", "args": [] }
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    home
+}
+
+fn snapshot_path(home: &TempDir, profile: &str) -> PathBuf {
+    config_path(home.path()).with_file_name(format!("profile_snapshots/{}.json", profile))
+}
+
+fn read_snapshot(home: &TempDir, profile: &str) -> serde_json::Value {
+    serde_json::from_str(&fs::read_to_string(snapshot_path(home, profile)).unwrap()).unwrap()
+}
+
+fn profiles(home: &TempDir) -> serde_json::Value {
+    let path = config_path(home.path()).with_file_name("profiles.json");
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap(),
+        Err(_) => serde_json::json!({ "profiles": {} }),
+    }
+}
+
+#[test]
+fn from_current_copies_the_live_config() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["profile", "create", "experiments", "--from", "current"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Seeded from current with 2 servers",
+        ))
+        .stdout(predicate::str::contains("• filesystem"))
+        .stdout(predicate::str::contains("• github"));
+
+    let snapshot = read_snapshot(&home, "experiments");
+    assert_eq!(
+        snapshot["mcpServers"],
+        read_config(home.path())["mcpServers"]
+    );
+    assert_eq!(
+        profiles(&home)["profiles"]["experiments"]["server_count"],
+        2
+    );
+}
+
+#[test]
+fn from_another_profile_copies_its_snapshot() {
+    let home = setup();
+    mcp_forge(home.path())
+        .args(["profile", "create", "work", "--from", "current"])
+        .assert()
+        .success();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+
+    mcp_forge(home.path())
+        .args(["profile", "create", "work-copy", "--from", "work"])
+        .assert()
+        .success();
+
+    assert_eq!(
+        read_snapshot(&home, "work-copy"),
+        read_snapshot(&home, "work")
+    );
+    assert_eq!(profiles(&home)["profiles"]["work-copy"]["server_count"], 2);
+}
+
+#[test]
+fn from_backup_copies_the_backed_up_config() {
+    let home = setup();
+    mcp_forge(home.path())
+        .args(["backup", "create", "--name", "before-cleanup"])
+        .assert()
+        .success();
+    write_config(
+        home.path(),
+        r#"{ "mcpServers": { "only": { "command": "npx" } } }"#,
+    );
+
+    mcp_forge(home.path())
+        .args([
+            "profile",
+            "create",
+            "restored",
+            "--from",
+            "backup:before-cleanup",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("• filesystem"));
+
+    let snapshot = read_snapshot(&home, "restored");
+    assert!(snapshot["mcpServers"].get("filesystem").is_some());
+    assert!(snapshot["mcpServers"].get("only").is_none());
+}
+
+#[test]
+fn bad_sources_leave_no_profile_behind() {
+    let home = setup();
+
+    for (source, error) in [
+        ("nope", "Profile 'nope' does not exist"),
+        ("backup:nope", "Backup 'nope' not found"),
+    ] {
+        mcp_forge(home.path())
+            .args(["profile", "create", "half-made", "--from", source])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(error));
+    }
+
+    write_config(home.path(), "{ not json");
+    mcp_forge(home.path())
+        .args(["profile", "create", "half-made", "--from", "current"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to parse config file"));
+
+    assert!(profiles(&home)["profiles"].get("half-made").is_none());
+    assert!(!snapshot_path(&home, "half-made").exists());
+}
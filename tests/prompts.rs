@@ -0,0 +1,93 @@
+// These tests redirect the config and cache directories through $HOME and
+// XDG variables, which `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "existing": { "command": "node", "args": ["server.js"] }
+  }
+}"#;
+
+const TEMPLATE: &str = r#"{
+  "name": "demo",
+  "version": "1.0.0",
+  "description": "Demo template",
+  "author": "tests",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {
+    "path": { "type": "string", "description": "Directory", "required": true }
+  },
+  "config": { "command": "demo", "args": ["{{path}}"] }
+}"#;
+
+/// Seed a config and a cached template so `add` never touches the network
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    seed_templates(home.path(), &[("demo", TEMPLATE)]);
+    home
+}
+
+#[test]
+fn eof_during_add_prompts_cancels_cleanly() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["add", "fresh", "demo"])
+        .write_stdin("")
+        .assert()
+        .code(130)
+        .stderr(predicate::str::contains("Operation cancelled."))
+        .stderr(predicate::str::contains("Error").not());
+
+    let content = fs::read_to_string(config_path(home.path())).unwrap();
+    assert_eq!(content, CONFIG);
+}
+
+#[test]
+fn eof_during_remove_confirmation_keeps_server() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["remove", "existing"])
+        .write_stdin("")
+        .assert()
+        .code(130);
+
+    let content = fs::read_to_string(config_path(home.path())).unwrap();
+    assert_eq!(content, CONFIG);
+}
+
+#[test]
+fn piped_input_is_not_taken_as_an_answer() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["add", "fresh", "demo"])
+        .write_stdin("/tmp\n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "pass it with --var path=<value> (stdin is not a terminal)",
+        ));
+
+    mcp_forge(home.path())
+        .args(["remove", "existing"])
+        .write_stdin("y\n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Confirmation required; pass --force to skip it",
+        ));
+
+    let content = fs::read_to_string(config_path(home.path())).unwrap();
+    assert_eq!(content, CONFIG);
+}
@@ -0,0 +1,96 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "one": { "command": "node", "args": [] },
+    "two": { "command": "node", "args": [] }
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    home
+}
+
+fn footer(home: &TempDir, profile: &str) -> String {
+    format!(
+        "profile: {} → {}",
+        profile,
+        config_path(home.path()).display()
+    )
+}
+
+#[test]
+fn mutating_command_names_default_target() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["remove", "one", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::ends_with(format!(
+            "{}\n",
+            footer(&home, "default")
+        )));
+}
+
+#[test]
+fn mutating_command_names_explicit_profile() {
+    let home = setup();
+    mcp_forge(home.path())
+        .args(["profile", "create", "work"])
+        .assert()
+        .success();
+
+    mcp_forge(home.path())
+        .args(["--profile", "work", "remove", "one", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(footer(&home, "work")));
+}
+
+#[test]
+fn mutating_command_names_current_profile() {
+    let home = setup();
+    mcp_forge(home.path())
+        .args(["profile", "create", "work"])
+        .assert()
+        .success();
+    mcp_forge(home.path())
+        .args(["profile", "switch", "work"])
+        .assert()
+        .success();
+    // Switching loads the new profile's (empty) snapshot
+    write_config(home.path(), CONFIG);
+
+    mcp_forge(home.path())
+        .args(["remove", "two", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(footer(&home, "work")));
+}
+
+#[test]
+fn read_only_and_dry_run_commands_have_no_footer() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("profile:").not());
+    mcp_forge(home.path())
+        .args(["remove", "one", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("profile:").not());
+}
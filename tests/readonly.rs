@@ -0,0 +1,115 @@
+// These tests redirect the config directory through $HOME and rely on Unix
+// permission bits to simulate a locked-down machine.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "filesystem": { "command": "npx", "args": [] }
+  }
+}"#;
+
+fn set_mode(path: &Path, mode: u32) {
+    fs::set_permissions(path, fs::Permissions::from_mode(mode)).unwrap();
+}
+
+/// Config with its directory made read-only; permissions are restored on drop
+struct ReadOnlyHome {
+    home: TempDir,
+}
+
+impl ReadOnlyHome {
+    fn new() -> Self {
+        let home = TempDir::new().unwrap();
+        write_config(home.path(), CONFIG);
+        set_mode(config_path(home.path()).parent().unwrap(), 0o555);
+        Self { home }
+    }
+
+    fn path(&self) -> &Path {
+        self.home.path()
+    }
+
+    /// Whether the mode actually blocks writes; it doesn't when running as root
+    fn is_enforced(&self) -> bool {
+        let probe = config_path(self.path()).parent().unwrap().join(".probe");
+        let writable = fs::write(&probe, "").is_ok();
+        let _ = fs::remove_file(&probe);
+        !writable
+    }
+}
+
+impl Drop for ReadOnlyHome {
+    fn drop(&mut self) {
+        set_mode(config_path(self.home.path()).parent().unwrap(), 0o755);
+    }
+}
+
+#[test]
+fn mutating_command_fails_fast_naming_the_directory() {
+    let home = ReadOnlyHome::new();
+    if !home.is_enforced() {
+        return;
+    }
+    let config_dir = config_path(home.path()).parent().unwrap().to_path_buf();
+
+    // The template doesn't exist, so reaching the template fetch would fail differently
+    mcp_forge(home.path())
+        .args(["add", "new-server", "no-such-template"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(format!(
+            "Cannot write to {}",
+            config_dir.display()
+        )))
+        .stderr(predicate::str::contains("mode 555"))
+        .stderr(predicate::str::contains("chmod u+w"));
+}
+
+#[test]
+fn read_only_config_file_is_reported() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    set_mode(&config_path(home.path()), 0o444);
+
+    mcp_forge(home.path())
+        .args(["remove", "filesystem", "--force"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("claude_desktop_config.json"))
+        .stderr(predicate::str::contains("mode 444"));
+
+    assert_eq!(
+        fs::read_to_string(config_path(home.path())).unwrap(),
+        CONFIG
+    );
+}
+
+#[test]
+fn read_only_commands_are_unaffected() {
+    let home = ReadOnlyHome::new();
+
+    mcp_forge(home.path())
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("filesystem"));
+
+    mcp_forge(home.path())
+        .args(["remove", "filesystem", "--dry-run"])
+        .assert()
+        .success();
+
+    mcp_forge(home.path())
+        .args(["config", "show"])
+        .assert()
+        .success();
+}
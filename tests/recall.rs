@@ -0,0 +1,124 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use std::fs;
+use tempfile::TempDir;
+
+const TEMPLATE: &str = r#"{
+  "name": "postgres",
+  "version": "1.0.0",
+  "description": "Postgres",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {
+    "host": { "type": "string", "description": "Host", "default": "db" },
+    "port": { "type": "string", "description": "Port", "default": "5432" },
+    "password": { "type": "string", "description": "Password", "secret": true }
+  },
+  "config": {
+    "command": "pg-mcp",
+    "args": ["--host", "{{host}}", "--port", "{{port}}"],
+    "env": { "PGPASSWORD": "{{password}}" }
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    seed_templates(home.path(), &[("postgres", TEMPLATE)]);
+    home
+}
+
+fn answers_path(home: &TempDir) -> std::path::PathBuf {
+    config_path(home.path()).with_file_name("template_answers.json")
+}
+
+#[test]
+fn add_records_non_secret_answers() {
+    let home = setup();
+
+    offline(home.path())
+        .args([
+            "add",
+            "db1",
+            "postgres",
+            "--var",
+            "host=localhost",
+            "--var",
+            "port=5432",
+            "--var",
+            "password=hunter2",
+        ])
+        .assert()
+        .success();
+
+    let answers = fs::read_to_string(answers_path(&home)).unwrap();
+    assert!(!answers.contains("hunter2"));
+    let answers: serde_json::Value = serde_json::from_str(&answers).unwrap();
+    assert_eq!(
+        answers["templates"]["postgres"],
+        serde_json::json!({ "host": "localhost", "port": "5432" })
+    );
+}
+
+#[test]
+fn explicit_vars_beat_recalled_values() {
+    let home = setup();
+
+    offline(home.path())
+        .args([
+            "add",
+            "db1",
+            "postgres",
+            "--vars",
+            "host=localhost,port=5432,password=x",
+        ])
+        .assert()
+        .success();
+    offline(home.path())
+        .args([
+            "add",
+            "db2",
+            "postgres",
+            "--vars",
+            "host=remote,port=6543,password=x",
+        ])
+        .assert()
+        .success();
+
+    let config = read_config(home.path());
+    assert_eq!(
+        config["mcpServers"]["db2"]["args"],
+        serde_json::json!(["--host", "remote", "--port", "6543"])
+    );
+    let answers: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(answers_path(&home)).unwrap()).unwrap();
+    assert_eq!(
+        answers["templates"]["postgres"],
+        serde_json::json!({ "host": "remote", "port": "6543" })
+    );
+}
+
+#[test]
+fn recall_setting_turns_recording_off() {
+    let home = setup();
+
+    offline(home.path())
+        .env("MCP_FORGE_RECALL", "0")
+        .args([
+            "add",
+            "db1",
+            "postgres",
+            "--vars",
+            "host=localhost,port=5432,password=x",
+        ])
+        .assert()
+        .success();
+
+    assert!(!answers_path(&home).exists());
+}
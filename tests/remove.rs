@@ -0,0 +1,196 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "filesystem": { "command": "npx", "args": [] },
+    "github": { "command": "npx", "args": [] }
+  }
+}"#;
+
+/// Every kind of record kept for `filesystem`, plus a link to it from `github`
+const METADATA: &str = r#"{
+  "servers": {
+    "filesystem": {
+      "template": "filesystem", "version": "1.0.0", "author": "Test",
+      "added_at": "2025-01-01T00:00:00Z", "notes": "shared docs"
+    },
+    "github": {
+      "template": "github", "version": "1.0.0", "author": "Test",
+      "added_at": "2025-01-01T00:00:00Z"
+    }
+  },
+  "depends_on": { "filesystem": ["github"], "github": ["filesystem"] },
+  "exclude_from_share": ["filesystem"],
+  "ignore_version_pins": ["filesystem"]
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    home
+}
+
+fn setup_with_metadata() -> TempDir {
+    let home = setup();
+    fs::write(metadata_path(&home), METADATA).unwrap();
+    home
+}
+
+fn metadata_path(home: &TempDir) -> std::path::PathBuf {
+    config_path(home.path()).with_file_name("server_metadata.json")
+}
+
+fn read_metadata(home: &TempDir) -> serde_json::Value {
+    serde_json::from_str(&fs::read_to_string(metadata_path(home)).unwrap()).unwrap()
+}
+
+/// Every place the metadata file could still mention `name`
+fn mentions(metadata: &serde_json::Value, name: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    for key in ["servers", "depends_on"] {
+        if metadata[key].get(name).is_some() {
+            found.push(key.to_string());
+        }
+    }
+    for key in ["exclude_from_share", "ignore_version_pins"] {
+        if metadata[key]
+            .as_array()
+            .is_some_and(|names| names.iter().any(|n| n == name))
+        {
+            found.push(key.to_string());
+        }
+    }
+    found
+}
+
+#[test]
+fn unmatched_pattern_lists_existing_servers() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["remove", "--pattern", "old-", "--force"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "No servers found matching pattern: old-",
+        ))
+        .stderr(predicate::str::contains(
+            "Existing servers: filesystem, github",
+        ));
+}
+
+#[test]
+fn unknown_name_lists_existing_servers() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["remove", "filesytem", "--force"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Server 'filesytem' not found"))
+        .stderr(predicate::str::contains(
+            "Existing servers: filesystem, github",
+        ));
+}
+
+#[test]
+fn ignore_missing_turns_no_match_into_warning() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["remove", "--pattern", "old-", "--force", "--ignore-missing"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("nothing to remove"));
+
+    mcp_forge(home.path())
+        .args(["remove", "missing", "--force", "--ignore-missing"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("nothing to remove"));
+
+    let config = read_config(home.path());
+    assert_eq!(config["mcpServers"].as_object().unwrap().len(), 2);
+}
+
+#[test]
+fn remove_cleans_up_the_servers_metadata() {
+    let home = setup_with_metadata();
+
+    mcp_forge(home.path())
+        .args(["remove", "filesystem", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "filesystem: template provenance, notes, dependency links, exclude_from_share, ignore_version_pins",
+        ));
+    assert_eq!(mentions(&read_metadata(&home), "filesystem").len(), 4);
+
+    mcp_forge(home.path())
+        .args(["remove", "filesystem", "--force"])
+        .assert()
+        .success();
+
+    let metadata = read_metadata(&home);
+    assert!(mentions(&metadata, "filesystem").is_empty());
+    // The other server keeps its records, including its link to the removed one
+    assert_eq!(metadata["servers"]["github"]["template"], "github");
+    assert_eq!(
+        metadata["depends_on"]["github"],
+        serde_json::json!(["filesystem"])
+    );
+}
+
+#[test]
+fn bulk_remove_cleans_up_the_servers_metadata() {
+    let home = setup_with_metadata();
+
+    mcp_forge(home.path())
+        .args(["bulk", "remove", "--pattern", "file", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Also removing recorded metadata"));
+
+    let metadata = read_metadata(&home);
+    assert!(mentions(&metadata, "filesystem").is_empty());
+    assert!(metadata["servers"].get("github").is_some());
+}
+
+#[test]
+fn keep_metadata_leaves_records_in_place() {
+    let home = setup_with_metadata();
+
+    mcp_forge(home.path())
+        .args(["remove", "filesystem", "--force", "--keep-metadata"])
+        .assert()
+        .success();
+    mcp_forge(home.path())
+        .args([
+            "bulk",
+            "remove",
+            "--pattern",
+            "git",
+            "--force",
+            "--keep-metadata",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Also removing").not());
+
+    let metadata = read_metadata(&home);
+    assert_eq!(mentions(&metadata, "filesystem").len(), 4);
+    assert_eq!(mentions(&metadata, "github").len(), 2);
+    assert!(read_config(home.path())["mcpServers"]
+        .as_object()
+        .unwrap()
+        .is_empty());
+}
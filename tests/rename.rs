@@ -0,0 +1,126 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use serde_json::json;
+use std::fs;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "postgres": {
+      "command": "pg-mcp",
+      "args": ["--host", "db"],
+      "env": { "PGUSER": "app" },
+      "cwd": "/srv",
+      "x-team": { "owner": "data" }
+    },
+    "api": { "command": "api-mcp" }
+  }
+}"#;
+
+const METADATA: &str = r#"{
+  "servers": {
+    "postgres": {
+      "template": "postgres", "version": "1.0.0", "author": "Test",
+      "added_at": "2025-01-01T00:00:00Z", "notes": "main database"
+    }
+  },
+  "depends_on": { "api": ["postgres"] }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    fs::write(metadata_path(&home), METADATA).unwrap();
+    home
+}
+
+fn metadata_path(home: &TempDir) -> std::path::PathBuf {
+    config_path(home.path()).with_file_name("server_metadata.json")
+}
+
+fn original() -> serde_json::Value {
+    serde_json::from_str::<serde_json::Value>(CONFIG).unwrap()["mcpServers"]["postgres"].clone()
+}
+
+#[test]
+fn rename_keeps_the_server_and_moves_its_metadata() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["rename", "postgres", "db-main"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Renamed 'postgres' to 'db-main'"));
+
+    let config = read_config(home.path());
+    assert_eq!(config["mcpServers"]["db-main"], original());
+    assert!(config["mcpServers"].get("postgres").is_none());
+
+    let metadata: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(metadata_path(&home)).unwrap()).unwrap();
+    assert_eq!(metadata["servers"]["db-main"]["notes"], "main database");
+    assert!(metadata["servers"].get("postgres").is_none());
+    assert_eq!(metadata["depends_on"]["api"], json!(["db-main"]));
+}
+
+#[test]
+fn existing_name_needs_force() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["rename", "postgres", "api"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--force"));
+    assert_eq!(
+        read_config(home.path())["mcpServers"]["api"]["command"],
+        "api-mcp"
+    );
+
+    mcp_forge(home.path())
+        .args(["rename", "postgres", "api", "--force"])
+        .assert()
+        .success();
+    let config = read_config(home.path());
+    assert_eq!(config["mcpServers"]["api"], original());
+    assert_eq!(config["mcpServers"].as_object().unwrap().len(), 1);
+}
+
+#[test]
+fn dry_run_shows_the_change_without_making_it() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["rename", "postgres", "db-main", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("REMOVE postgres"))
+        .stdout(predicate::str::contains("NEW db-main"))
+        .stdout(predicate::str::contains("template provenance, notes"));
+
+    assert_eq!(
+        read_config(home.path()),
+        serde_json::from_str::<serde_json::Value>(CONFIG).unwrap()
+    );
+}
+
+#[test]
+fn keep_old_leaves_a_disabled_copy() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["rename", "postgres", "db-main", "--keep-old"])
+        .assert()
+        .success();
+
+    let config = read_config(home.path());
+    assert_eq!(config["mcpServers"]["db-main"], original());
+    assert!(config["mcpServers"].get("postgres").is_none());
+    assert_eq!(config["disabledServers"]["postgres"], original());
+}
@@ -0,0 +1,57 @@
+// Snapshot of the CLI surface. If this fails after an intended CLI change,
+// regenerate the snapshot with `UPDATE_SNAPSHOTS=1 cargo test --test schema`
+// and review the diff alongside the code.
+
+mod common;
+
+use common::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const SNAPSHOT: &str = "tests/snapshots/cli-schema.json";
+
+#[test]
+fn cli_schema_matches_snapshot() {
+    let home = TempDir::new().unwrap();
+    let output = mcp_forge(home.path())
+        .args(["schema", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // The version changes every release; the surface shouldn't
+    let mut schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema["version"], env!("CARGO_PKG_VERSION"));
+    schema["version"] = serde_json::Value::from("<version>");
+    let actual = serde_json::to_string_pretty(&schema).unwrap() + "\n";
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SNAPSHOT);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        actual == expected,
+        "CLI surface differs from {}; rerun with UPDATE_SNAPSHOTS=1 if the change is intended",
+        SNAPSHOT
+    );
+}
+
+#[test]
+fn schema_renders_markdown_reference() {
+    let home = TempDir::new().unwrap();
+    let output = mcp_forge(home.path())
+        .args(["schema", "--format", "markdown"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let markdown = String::from_utf8(output.stdout).unwrap();
+    assert!(markdown.starts_with("# mcp-forge command reference\n"));
+    assert!(markdown.contains("\n## `mcp-forge profile delete`\n"));
+    assert!(markdown.contains("| `--limit <LIMIT>` | integer | 20 | Number of entries to show |"));
+    // The schema command itself is hidden
+    assert!(!markdown.contains("## `mcp-forge schema`"));
+}
@@ -0,0 +1,124 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const TEMPLATE: &str = r#"{
+  "name": "search",
+  "version": "1.0.0",
+  "description": "Search",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {
+    "region": { "type": "string", "description": "Region", "required": true },
+    "api_key": { "type": "string", "description": "API key", "required": true, "secret": true }
+  },
+  "config": {
+    "command": "search-mcp",
+    "args": ["--region", "{{region}}", "--auth={{api_key}}"],
+    "env": { "SEARCH_CREDENTIAL": "{{api_key}}" }
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    seed_templates(home.path(), &[("search", TEMPLATE)]);
+    home
+}
+
+#[test]
+fn secret_variables_are_not_recorded_in_metadata() {
+    let home = setup();
+    let file = home.path().join("vars.yaml");
+    fs::write(&file, "region: eu\napi_key: sk-live-0123456789\n").unwrap();
+
+    offline(home.path())
+        .args(["add", "web", "search", "--vars-file"])
+        .arg(&file)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("shell history").not());
+
+    // The server itself still needs the key
+    let server = &read_config(home.path())["mcpServers"]["web"];
+    assert_eq!(server["env"]["SEARCH_CREDENTIAL"], "sk-live-0123456789");
+
+    let metadata_path = config_path(home.path()).with_file_name("server_metadata.json");
+    let metadata = fs::read_to_string(metadata_path).unwrap();
+    assert!(!metadata.contains("sk-live-0123456789"));
+    let metadata: serde_json::Value = serde_json::from_str(&metadata).unwrap();
+    assert_eq!(
+        metadata["servers"]["web"]["variables"],
+        serde_json::json!({ "region": "eu" })
+    );
+}
+
+#[test]
+fn secrets_on_the_command_line_warn_and_are_masked_in_previews() {
+    let home = setup();
+
+    offline(home.path())
+        .args([
+            "add",
+            "web",
+            "search",
+            "--vars",
+            "region=eu,api_key=sk-live-0123456789",
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "api_key is secret; values passed with --vars are saved in your shell history",
+        ))
+        .stdout(predicate::str::contains("--auth=********"))
+        .stdout(predicate::str::contains("SEARCH_CREDENTIAL=********"))
+        .stdout(predicate::str::contains("sk-live").not());
+}
+
+#[test]
+fn vars_stdin_keeps_secrets_off_the_command_line() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["add", "web", "search", "--vars-stdin", "--var", "region=us"])
+        .write_stdin("# credentials\nregion=eu\napi_key=sk-live-0123456789\n")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("shell history").not());
+    let server = &read_config(home.path())["mcpServers"]["web"];
+    assert_eq!(server["env"]["SEARCH_CREDENTIAL"], "sk-live-0123456789");
+    // --var takes precedence over stdin
+    assert_eq!(server["args"][1], "us");
+
+    offline(home.path())
+        .args(["add", "api", "search", "--vars-stdin"])
+        .write_stdin(r#"{ "region": "eu", "api_key": "sk-live-json" }"#)
+        .assert()
+        .success();
+    let server = &read_config(home.path())["mcpServers"]["api"];
+    assert_eq!(server["env"]["SEARCH_CREDENTIAL"], "sk-live-json");
+}
+
+#[test]
+fn missing_secret_without_a_terminal_fails_as_before() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["add", "web", "search", "--var", "region=eu"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("api_key"));
+    assert!(read_config(home.path())["mcpServers"]
+        .as_object()
+        .unwrap()
+        .is_empty());
+}
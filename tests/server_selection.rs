@@ -0,0 +1,173 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "api": { "command": "sh" },
+    "db": { "command": "sh" },
+    "old-cache": { "command": "sh" },
+    "old-queue": { "command": "sh" },
+    "web": { "url": "https://example.com/mcp" }
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    home
+}
+
+fn server_names(home: &TempDir) -> Vec<String> {
+    let mut names: Vec<String> = read_config(home.path())["mcpServers"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn validate_checks_each_named_server_once() {
+    let home = setup();
+
+    let output = mcp_forge(home.path())
+        .args(["validate", "db", "api", "db", "--format", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let results: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let names: Vec<&str> = results
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["server_name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["db", "api"]);
+}
+
+#[test]
+fn one_unknown_of_three_fails_naming_it() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["validate", "api", "dbb", "web"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Server 'dbb' not found"));
+
+    mcp_forge(home.path())
+        .args(["remove", "api", "dbb", "webb", "--force"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Servers 'dbb', 'webb' not found"))
+        .stderr(predicate::str::contains("Existing servers:"));
+    assert_eq!(server_names(&home).len(), 5);
+
+    mcp_forge(home.path())
+        .args(["export", "--server", "api", "--server", "dbb"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Server 'dbb' not found"));
+}
+
+#[test]
+fn remove_combines_names_with_pattern_matches() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args([
+            "remove",
+            "api",
+            "old-cache",
+            "api",
+            "--pattern",
+            "old-",
+            "--force",
+        ])
+        .assert()
+        .success();
+    assert_eq!(server_names(&home), ["db", "web"]);
+}
+
+#[test]
+fn remove_ignore_missing_removes_the_rest() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["remove", "api", "dbb", "--force", "--ignore-missing"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Server 'dbb' not found; removing the rest.",
+        ));
+    assert_eq!(server_names(&home), ["db", "old-cache", "old-queue", "web"]);
+}
+
+#[test]
+fn export_only_includes_selected_servers() {
+    let home = setup();
+
+    let output = mcp_forge(home.path())
+        .args([
+            "export", "--server", "web", "--server", "api", "--server", "web",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let exported: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let mut names: Vec<&String> = exported["mcpServers"].as_object().unwrap().keys().collect();
+    names.sort();
+    assert_eq!(names, ["api", "web"]);
+}
+
+#[test]
+fn backup_restore_brings_back_several_servers() {
+    let home = setup();
+    mcp_forge(home.path())
+        .args(["backup", "create", "--name", "before"])
+        .assert()
+        .success();
+    mcp_forge(home.path())
+        .args(["remove", "--pattern", "old-", "--force"])
+        .assert()
+        .success();
+
+    mcp_forge(home.path())
+        .args(["backup", "restore", "before", "--server", "old-cache"])
+        .args(["--server", "nope", "--server", "old-queue"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Server 'nope' not found in backup 'before'",
+        ));
+
+    mcp_forge(home.path())
+        .args(["backup", "restore", "before", "--server", "old-cache"])
+        .args(["--server", "old-queue", "--fields", "command"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("one server at a time"));
+
+    mcp_forge(home.path())
+        .args(["backup", "restore", "before", "--server", "old-cache"])
+        .args(["--server", "old-queue", "--server", "old-cache"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Servers restored successfully: old-cache, old-queue",
+        ));
+    assert_eq!(server_names(&home).len(), 5);
+}
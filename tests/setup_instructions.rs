@@ -0,0 +1,101 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const TEMPLATE: &str = r#"{
+  "name": "files",
+  "version": "1.0.0",
+  "description": "Files",
+  "author": "Team",
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {
+    "paths": { "type": "string", "description": "Paths to serve", "required": true },
+    "node_major": { "type": "number", "description": "Node major version", "default": 20 }
+  },
+  "config": { "command": "files-mcp", "args": ["{{paths}}"] },
+  "requirements": { "node": "{{node_major}}.x", "python": "{{python_version}}" },
+  "setup_instructions": "Grant access to {{paths}} and install Node {{node_major}}"
+}"#;
+
+/// A home with `TEMPLATE` as a local template and nothing cached
+fn setup() -> (TempDir, TempDir) {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    let team = TempDir::new().unwrap();
+    fs::write(team.path().join("files.json"), TEMPLATE).unwrap();
+    (home, team)
+}
+
+/// `offline` with `team` as the local template directory
+fn with_team(home: &Path, team: &Path) -> assert_cmd::Command {
+    let mut cmd = offline(home);
+    cmd.env("MCP_FORGE_TEMPLATE_PATH", team);
+    cmd
+}
+
+#[test]
+fn add_renders_setup_instructions_and_requirements() {
+    let (home, team) = setup();
+
+    with_team(home.path(), team.path())
+        .args(["add", "docs", "files", "--var", "paths=/srv/docs"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Setup Instructions:\nGrant access to /srv/docs and install Node 20",
+        ))
+        // The requirement naming an undeclared variable is kept as written
+        .stderr(
+            predicate::str::contains("failed to render requirement 'python'")
+                .and(predicate::str::contains("Leaving it as written")),
+        );
+
+    with_team(home.path(), team.path())
+        .args(["list", "--show-requirements"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("node: 20.x")
+                .and(predicate::str::contains("python: {{python_version}}")),
+        );
+
+    // The template keeps its placeholders
+    with_team(home.path(), team.path())
+        .args(["template", "show", "files"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Grant access to {{paths}}")
+                .and(predicate::str::contains("node: {{node_major}}.x")),
+        );
+}
+
+#[test]
+fn dry_run_shows_instructions_with_the_values_given() {
+    let (home, team) = setup();
+
+    with_team(home.path(), team.path())
+        .args([
+            "add",
+            "docs",
+            "files",
+            "--var",
+            "paths=/srv/docs",
+            "--var",
+            "node_major=22",
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Grant access to /srv/docs and install Node 22",
+        ));
+}
@@ -0,0 +1,153 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{ "mcpServers": {
+  "shared": { "command": "sh", "args": [] },
+  "scratch": { "command": "sh", "args": ["experiment"] }
+} }"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    mcp_forge(home.path())
+        .args(["meta", "set", "scratch", "exclude_from_share", "true"])
+        .assert()
+        .success();
+    home
+}
+
+fn server_names(config: &serde_json::Value) -> Vec<String> {
+    let mut names: Vec<String> = config["mcpServers"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn meta_set_rejects_unknown_keys_and_servers() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["meta", "set", "scratch", "colour", "blue"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Unknown metadata key 'colour'. Supported keys: exclude_from_share",
+        ));
+    mcp_forge(home.path())
+        .args(["meta", "set", "ghost", "exclude_from_share", "true"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Server 'ghost' not found"));
+}
+
+#[test]
+fn export_withholds_excluded_servers() {
+    let home = setup();
+
+    let output = mcp_forge(home.path()).arg("export").output().unwrap();
+    assert!(output.status.success());
+    let exported: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(server_names(&exported), ["shared"]);
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Withheld 1 server(s) marked exclude_from_share: scratch"));
+
+    let output = mcp_forge(home.path())
+        .args(["export", "--include-excluded"])
+        .output()
+        .unwrap();
+    let exported: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(server_names(&exported), ["scratch", "shared"]);
+
+    // Unsetting the flag shares the server again
+    mcp_forge(home.path())
+        .args(["meta", "set", "scratch", "exclude_from_share", "false"])
+        .assert()
+        .success();
+    let output = mcp_forge(home.path()).arg("export").output().unwrap();
+    let exported: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(server_names(&exported), ["scratch", "shared"]);
+}
+
+#[test]
+fn split_export_withholds_excluded_servers() {
+    let home = setup();
+    let dir = home.path().join("split");
+
+    mcp_forge(home.path())
+        .args(["export", "--split", "--output-dir"])
+        .arg(&dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Exported 1 server(s)"))
+        .stdout(predicate::str::contains("Withheld 1 server(s)"));
+    assert!(dir.join("shared.json").exists());
+    assert!(!dir.join("scratch.json").exists());
+}
+
+#[test]
+fn profile_sync_withholds_excluded_servers() {
+    let home = setup();
+    mcp_forge(home.path())
+        .args(["profile", "create", "work"])
+        .assert()
+        .success();
+
+    mcp_forge(home.path())
+        .args(["profile", "sync", "default", "work", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("NEW shared"))
+        .stdout(predicate::str::contains("scratch").count(1))
+        .stdout(predicate::str::contains("Withheld 1 server(s)"));
+
+    mcp_forge(home.path())
+        .args(["profile", "sync", "default", "work"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Servers copied: 1"));
+    let snapshot = config_path(home.path()).with_file_name("profile_snapshots/work.json");
+    let synced: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&snapshot).unwrap()).unwrap();
+    assert_eq!(server_names(&synced), ["shared"]);
+
+    mcp_forge(home.path())
+        .args(["profile", "sync", "default", "work", "--include-excluded"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Servers copied: 2"));
+}
+
+#[test]
+fn backup_create_keeps_excluded_servers_and_marks_them() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["backup", "create", "--name", "local"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Includes 1 server(s) marked exclude_from_share: scratch",
+        ));
+
+    let backup = config_path(home.path()).with_file_name("backups/local.json");
+    let backup: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(backup).unwrap()).unwrap();
+    assert_eq!(server_names(&backup["config"]), ["scratch", "shared"]);
+    assert_eq!(
+        backup["metadata"]["excluded_from_share"],
+        serde_json::json!(["scratch"])
+    );
+}
@@ -0,0 +1,101 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "github": {
+      "command": "npx",
+      "args": ["-y", "@modelcontextprotocol/server-github"],
+      "env": { "LOG_LEVEL": "info", "GITHUB_TOKEN": "ghp_abcdefghijklmnop" },
+      "cwd": "/srv/github"
+    },
+    "remote": {
+      "url": "https://mcp.example.com/sse?api_key=sk-1234567890",
+      "headers": { "Authorization": "Bearer sk-1234567890" }
+    }
+  }
+}"#;
+
+const METADATA: &str = r#"{
+  "servers": {
+    "github": {
+      "template": "github", "version": "1.2.0", "author": "Test",
+      "added_at": "2025-01-01T00:00:00Z", "notes": "org-wide token"
+    }
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    fs::write(
+        config_path(home.path()).with_file_name("server_metadata.json"),
+        METADATA,
+    )
+    .unwrap();
+    home
+}
+
+#[test]
+fn shows_one_server_with_secrets_masked() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["show", "github"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "  Args: -y @modelcontextprotocol/server-github",
+        ))
+        .stdout(predicate::str::contains(
+            "    GITHUB_TOKEN=ghp**************nop\n    LOG_LEVEL=info",
+        ))
+        .stdout(predicate::str::contains("    cwd: \"/srv/github\""))
+        .stdout(predicate::str::contains("  Template: github v1.2.0"))
+        .stdout(predicate::str::contains("  Notes: org-wide token"))
+        .stdout(predicate::str::contains("remote").not());
+}
+
+#[test]
+fn json_output_is_masked_unless_secrets_are_revealed() {
+    let home = setup();
+
+    let output = mcp_forge(home.path())
+        .args(["show", "remote", "--json"])
+        .output()
+        .unwrap();
+    let shown: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let url = shown["remote"]["url"].as_str().unwrap();
+    let header = shown["remote"]["headers"]["Authorization"]
+        .as_str()
+        .unwrap();
+    assert!(!url.contains("sk-1234567890"), "{}", url);
+    assert!(!header.contains("sk-1234567890"), "{}", header);
+
+    let output = mcp_forge(home.path())
+        .args(["show", "remote", "--json", "--reveal-secrets"])
+        .output()
+        .unwrap();
+    let shown: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let config = read_config(home.path());
+    assert_eq!(shown["remote"], config["mcpServers"]["remote"]);
+}
+
+#[test]
+fn unknown_server_fails() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["show", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Server 'missing' not found"));
+}
@@ -0,0 +1,137 @@
+// These tests use shell scripts as dummy servers and redirect the config
+// directory through $HOME, which `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const GOOD: &str = r#"#!/bin/sh
+read request
+echo "starting up"
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2024-11-05","capabilities":{},"serverInfo":{"name":"dummy","version":"1.0.0"}}}'
+read rest
+"#;
+
+const CRASH: &str = r#"#!/bin/sh
+echo "error: API_KEY is not set" >&2
+exit 3
+"#;
+
+const HANG: &str = r#"#!/bin/sh
+exec sleep 30
+"#;
+
+fn script(dir: &Path, name: &str, body: &str) -> PathBuf {
+    let path = dir.join(name);
+    fs::write(&path, body).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
+fn setup(servers: serde_json::Value) -> TempDir {
+    let home = TempDir::new().unwrap();
+    let config = serde_json::json!({ "mcpServers": servers });
+    write_config(home.path(), &config.to_string());
+    home
+}
+
+fn command(path: &Path) -> serde_json::Value {
+    serde_json::json!({ "command": path, "args": [] })
+}
+
+fn smoke(home: &TempDir) -> assert_cmd::Command {
+    let mut cmd = mcp_forge(home.path());
+    cmd.arg("smoke")
+        .env_remove("HTTP_PROXY")
+        .env_remove("http_proxy")
+        .env_remove("ALL_PROXY");
+    cmd
+}
+
+#[test]
+fn reports_pass_and_failures_with_stderr() {
+    let bin = TempDir::new().unwrap();
+    let home = setup(serde_json::json!({
+        "good": command(&script(bin.path(), "good.sh", GOOD)),
+        "crash": command(&script(bin.path(), "crash.sh", CRASH)),
+        "hang": command(&script(bin.path(), "hang.sh", HANG)),
+    }));
+
+    smoke(&home)
+        .args(["--timeout", "1"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("initialized (dummy)"))
+        .stdout(predicate::str::contains("exited before responding"))
+        .stdout(predicate::str::contains("error: API_KEY is not set"))
+        .stdout(predicate::str::contains("no response within 1s"))
+        .stdout(predicate::str::contains("Summary: 1 passed, 2 failed"))
+        .stderr(predicate::str::contains("2 of 3 server(s) failed to start"));
+}
+
+#[test]
+fn only_scopes_the_run_and_succeeds_when_all_pass() {
+    let bin = TempDir::new().unwrap();
+    let home = setup(serde_json::json!({
+        "good": command(&script(bin.path(), "good.sh", GOOD)),
+        "crash": command(&script(bin.path(), "crash.sh", CRASH)),
+    }));
+
+    smoke(&home)
+        .args(["--only", "goo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("crash").not());
+}
+
+#[test]
+fn disabled_servers_are_skipped() {
+    let bin = TempDir::new().unwrap();
+    let mut crash = command(&script(bin.path(), "crash.sh", CRASH));
+    crash["disabled"] = serde_json::json!(true);
+    let home = setup(serde_json::json!({ "crash": crash }));
+
+    smoke(&home)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No enabled servers to test."));
+}
+
+#[test]
+fn json_output_and_url_probe() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    std::thread::spawn(move || {
+        if let Ok((mut stream, _)) = listener.accept() {
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf);
+            let _ = stream.write_all(
+                b"HTTP/1.1 405 Method Not Allowed\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            );
+        }
+    });
+
+    let home = setup(serde_json::json!({
+        "up": { "url": format!("http://127.0.0.1:{}/mcp", port) },
+        "down": { "url": "http://127.0.0.1:1/mcp" },
+    }));
+
+    let output = smoke(&home).args(["--json"]).output().unwrap();
+    assert!(!output.status.success());
+
+    let outcomes: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(outcomes[0]["name"], "down");
+    assert_eq!(outcomes[0]["passed"], false);
+    assert_eq!(outcomes[1]["name"], "up");
+    assert_eq!(outcomes[1]["kind"], "url");
+    assert_eq!(outcomes[1]["passed"], true);
+    assert_eq!(outcomes[1]["detail"], "reachable (HTTP 405)");
+}
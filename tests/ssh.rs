@@ -0,0 +1,189 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms, and put a shell-script `ssh` on PATH.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Runs the remote command locally with $HOME set to the fake remote home.
+/// Hosts containing "denied" fail the way ssh does on bad credentials.
+const SSH_SHIM: &str = r#"#!/bin/sh
+printf '%s\n' "$*" >> "$SSH_LOG"
+if [ "$1" = "-p" ]; then shift 2; fi
+if [ "$1" = "--" ]; then shift; fi
+case "$1" in
+  *denied*) echo "$1: Permission denied (publickey)." >&2; exit 255 ;;
+esac
+shift
+HOME="$REMOTE_HOME" exec sh -c "$1"
+"#;
+
+const LOCAL: &str = r#"{ "mcpServers": { "local": { "command": "node", "args": [] } } }"#;
+const REMOTE: &str = r#"{ "mcpServers": { "remote": { "command": "uvx", "args": ["tool"] } } }"#;
+
+struct Machines {
+    home: TempDir,
+    remote: TempDir,
+    bin: TempDir,
+}
+
+impl Machines {
+    fn new() -> Self {
+        let home = TempDir::new().unwrap();
+        write_config(home.path(), LOCAL);
+        let remote = TempDir::new().unwrap();
+
+        let bin = TempDir::new().unwrap();
+        let shim = bin.path().join("ssh");
+        fs::write(&shim, SSH_SHIM).unwrap();
+        fs::set_permissions(&shim, fs::Permissions::from_mode(0o755)).unwrap();
+
+        Self { home, remote, bin }
+    }
+
+    fn remote_config(&self) -> PathBuf {
+        self.remote
+            .path()
+            .join(".config/claude/claude_desktop_config.json")
+    }
+
+    fn seed_remote(&self) {
+        fs::create_dir_all(self.remote_config().parent().unwrap()).unwrap();
+        fs::write(self.remote_config(), REMOTE).unwrap();
+    }
+
+    fn ssh_log(&self) -> String {
+        fs::read_to_string(self.bin.path().join("log")).unwrap_or_default()
+    }
+
+    fn cmd(&self) -> assert_cmd::Command {
+        let path = format!(
+            "{}:{}",
+            self.bin.path().display(),
+            std::env::var("PATH").unwrap_or_default()
+        );
+        let mut cmd = mcp_forge(self.home.path());
+        cmd.env("PATH", path)
+            .env("REMOTE_HOME", self.remote.path())
+            .env("SSH_LOG", self.bin.path().join("log"));
+        cmd
+    }
+}
+
+fn servers(path: &Path) -> serde_json::Value {
+    let content = fs::read_to_string(path).unwrap();
+    serde_json::from_str::<serde_json::Value>(&content).unwrap()["mcpServers"].clone()
+}
+
+#[test]
+fn import_from_ssh_merges_remote_servers() {
+    let machines = Machines::new();
+    machines.seed_remote();
+
+    machines
+        .cmd()
+        .args(["import", "--from", "ssh://me@laptop:2222", "--merge"])
+        .assert()
+        .success();
+
+    let imported = servers(&config_path(machines.home.path()));
+    assert!(imported["local"].is_object());
+    assert_eq!(imported["remote"]["command"], "uvx");
+    assert!(machines.ssh_log().starts_with("-p 2222 -- me@laptop "));
+}
+
+#[test]
+fn import_from_ssh_reports_missing_remote_config() {
+    let machines = Machines::new();
+
+    machines
+        .cmd()
+        .args(["import", "--from", "ssh://me@laptop", "--merge"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Could not read the Claude config on me@laptop",
+        ))
+        .stderr(predicate::str::contains("--remote-path"));
+
+    machines
+        .cmd()
+        .args(["import", "--from", "ssh://me@laptop", "--merge"])
+        .args(["--remote-path", "~/elsewhere.json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("elsewhere.json"));
+}
+
+#[test]
+fn import_from_ssh_reports_connection_failures() {
+    let machines = Machines::new();
+
+    machines
+        .cmd()
+        .args(["import", "--from", "ssh://me@denied-host", "--merge"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "SSH connection to me@denied-host failed",
+        ))
+        .stderr(predicate::str::contains("Permission denied"));
+}
+
+#[test]
+fn export_to_ssh_overwrites_remote_and_keeps_backup() {
+    let machines = Machines::new();
+    machines.seed_remote();
+
+    machines
+        .cmd()
+        .args(["export", "--to", "ssh://me@laptop", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("kept as"));
+
+    assert!(servers(&machines.remote_config())["local"].is_object());
+    let backup = machines.remote_config().with_extension("json.bak");
+    assert_eq!(fs::read_to_string(backup).unwrap(), REMOTE);
+    assert!(!machines.remote_config().with_extension("json.tmp").exists());
+}
+
+#[test]
+fn export_to_ssh_writes_custom_remote_path() {
+    let machines = Machines::new();
+
+    machines
+        .cmd()
+        .args(["export", "--to", "ssh://laptop", "--force"])
+        .args(["--remote-path", "~/new dir/config.json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("kept as").not());
+
+    let written = machines.remote.path().join("new dir/config.json");
+    assert!(servers(&written)["local"].is_object());
+}
+
+#[test]
+fn export_to_ssh_requires_confirmation() {
+    let machines = Machines::new();
+    machines.seed_remote();
+
+    machines
+        .cmd()
+        .args(["export", "--to", "ssh://me@laptop"])
+        .assert()
+        .failure();
+
+    assert_eq!(
+        fs::read_to_string(machines.remote_config()).unwrap(),
+        REMOTE
+    );
+    assert!(machines.ssh_log().is_empty());
+}
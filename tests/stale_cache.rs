@@ -0,0 +1,103 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const TEMPLATE: &str = r#"{
+  "name": "demo",
+  "version": "1.0.0",
+  "description": "Demo template",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {},
+  "config": { "command": "demo", "args": [] }
+}"#;
+
+const CATALOG: &str = r#"{
+  "version": "1",
+  "last_updated": "2025-01-01",
+  "templates": {
+    "demo": {
+      "name": "demo", "version": "1.0.0", "description": "Demo template",
+      "author": "Test", "tags": [], "platforms": ["linux", "macos", "windows"],
+      "category": "official", "path": "templates/demo.json"
+    }
+  }
+}"#;
+
+const BANNER: &str = "template data was last refreshed 41 days ago";
+
+/// A cache last refreshed `days_ago`, which hasn't expired yet
+fn setup(days_ago: i64) -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    let templates = seed_templates(home.path(), &[("demo", TEMPLATE)]);
+
+    let cache = templates.parent().unwrap();
+    fs::write(cache.join("catalog.json"), CATALOG).unwrap();
+    let now = chrono::Utc::now();
+    let metadata = serde_json::json!({
+        "last_refresh": now - chrono::Duration::days(days_ago),
+        "etag": null,
+        "catalog_etag": null,
+        "expires_at": now + chrono::Duration::days(30),
+    });
+    fs::write(cache.join("metadata.json"), metadata.to_string()).unwrap();
+    home
+}
+
+#[test]
+fn old_cache_prints_refresh_banner() {
+    let home = setup(41);
+
+    for args in [
+        &["template", "list"][..],
+        &["template", "list", "--cached"],
+        &["template", "search", "demo"],
+        &["add", "demo", "demo", "--dry-run"],
+    ] {
+        offline(home.path())
+            .args(args)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(BANNER).not())
+            .stderr(predicate::str::contains(BANNER))
+            .stderr(predicate::str::contains("mcp-forge template refresh"));
+    }
+}
+
+#[test]
+fn recent_cache_has_no_banner() {
+    let home = setup(3);
+
+    offline(home.path())
+        .args(["template", "search", "demo"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("last refreshed").not());
+}
+
+#[test]
+fn stale_after_setting_moves_or_disables_the_threshold() {
+    let home = setup(41);
+
+    offline(home.path())
+        .env("MCP_FORGE_STALE_AFTER", "60d")
+        .args(["template", "list"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("last refreshed").not());
+    offline(home.path())
+        .env("MCP_FORGE_STALE_AFTER", "off")
+        .args(["template", "list"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("last refreshed").not());
+}
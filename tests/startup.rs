@@ -0,0 +1,37 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "files": { "command": "files-mcp", "args": ["/srv"] }
+  }
+}"#;
+
+#[test]
+fn commands_without_templates_leave_the_cache_alone() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    let cache = template_cache_dir(home.path());
+
+    for args in [
+        vec!["list"],
+        vec!["list", "--json"],
+        vec!["config", "path"],
+        vec!["config", "show"],
+        vec!["validate"],
+    ] {
+        // validate may find issues and exit non-zero; only the cache matters here
+        mcp_forge(home.path()).args(&args).output().unwrap();
+        assert!(
+            !cache.parent().unwrap().exists(),
+            "`{}` created the template cache",
+            args.join(" ")
+        );
+    }
+}
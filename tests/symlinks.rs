@@ -0,0 +1,145 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::os::unix::fs::{symlink, PermissionsExt};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const CONFIG: &str =
+    r#"{ "mcpServers": { "api": { "command": "sh" }, "db": { "command": "sh" } } }"#;
+
+/// A dotfiles checkout holding the real config, with the Claude config linked into it
+fn setup() -> (TempDir, PathBuf) {
+    let home = TempDir::new().unwrap();
+    let real = home.path().join("dotfiles/claude.json");
+    fs::create_dir_all(real.parent().unwrap()).unwrap();
+    fs::write(&real, CONFIG).unwrap();
+    let link = config_path(home.path());
+    fs::create_dir_all(link.parent().unwrap()).unwrap();
+    symlink(&real, &link).unwrap();
+    (home, real)
+}
+
+fn servers(path: &Path) -> Vec<String> {
+    let config: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+    config["mcpServers"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn saves_write_through_a_symlinked_config() {
+    let (home, real) = setup();
+
+    mcp_forge(home.path())
+        .args(["remove", "db", "--force"])
+        .assert()
+        .success();
+
+    let link = config_path(home.path());
+    assert!(fs::symlink_metadata(&link)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert_eq!(servers(&real), ["api"]);
+
+    let backups = link.parent().unwrap().join("backups");
+    let backup = fs::read_dir(backups)
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let backup: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(backup).unwrap()).unwrap();
+    assert_eq!(
+        backup["metadata"]["config_file"],
+        real.canonicalize().unwrap().display().to_string()
+    );
+}
+
+#[test]
+fn force_replace_symlink_swaps_the_link_for_a_file() {
+    let (home, real) = setup();
+
+    mcp_forge(home.path())
+        .args(["--force-replace-symlink", "remove", "db", "--force"])
+        .assert()
+        .success();
+
+    let link = config_path(home.path());
+    assert!(fs::symlink_metadata(&link).unwrap().is_file());
+    assert_eq!(servers(&link), ["api"]);
+    assert_eq!(fs::read_to_string(&real).unwrap(), CONFIG);
+}
+
+#[test]
+fn read_only_link_target_suggests_replacing_the_link() {
+    let (home, real) = setup();
+    fs::set_permissions(&real, fs::Permissions::from_mode(0o444)).unwrap();
+
+    mcp_forge(home.path())
+        .args(["remove", "db", "--force"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--force-replace-symlink"));
+    assert_eq!(fs::read_to_string(&real).unwrap(), CONFIG);
+}
+
+#[test]
+fn symlinked_config_directory_is_followed() {
+    let home = TempDir::new().unwrap();
+    let real_dir = home.path().join("dotfiles/claude");
+    fs::create_dir_all(&real_dir).unwrap();
+    fs::write(real_dir.join("claude_desktop_config.json"), CONFIG).unwrap();
+    let link = config_path(home.path());
+    let link_dir = link.parent().unwrap();
+    fs::create_dir_all(link_dir.parent().unwrap()).unwrap();
+    symlink(&real_dir, link_dir).unwrap();
+
+    mcp_forge(home.path())
+        .args(["remove", "db", "--force"])
+        .assert()
+        .success();
+
+    assert!(fs::symlink_metadata(link_dir)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert_eq!(
+        servers(&real_dir.join("claude_desktop_config.json")),
+        ["api"]
+    );
+    assert!(real_dir.join("backups").is_dir());
+}
+
+#[test]
+fn dangling_link_creates_its_target() {
+    let home = TempDir::new().unwrap();
+    let real = home.path().join("dotfiles/claude.json");
+    fs::create_dir_all(real.parent().unwrap()).unwrap();
+    let link = config_path(home.path());
+    fs::create_dir_all(link.parent().unwrap()).unwrap();
+    symlink(&real, &link).unwrap();
+
+    mcp_forge(home.path())
+        .args(["config", "init"])
+        .assert()
+        .success();
+
+    assert!(fs::symlink_metadata(&link)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert!(real.is_file());
+}
@@ -0,0 +1,163 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const TEMPLATE: &str = r#"{
+  "name": "filesystem",
+  "version": "1.0.0",
+  "description": "Filesystem",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {
+    "paths": { "type": "string", "description": "Allowed path", "required": true }
+  },
+  "config": {
+    "command": "npx",
+    "args": ["-y", "@modelcontextprotocol/server-filesystem", "{{paths}}"]
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    seed_templates(home.path(), &[("filesystem", TEMPLATE)]);
+    home
+}
+
+fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn cursor_add_writes_the_cursor_config() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["--target", "cursor", "add", "fs", "filesystem"])
+        .args(["--vars", "paths=/tmp"])
+        .assert()
+        .success();
+
+    let cursor = read_json(&home.path().join(".cursor/mcp.json"));
+    assert_eq!(cursor["mcpServers"]["fs"]["command"], "npx");
+    assert_eq!(cursor["mcpServers"]["fs"]["args"][2], "/tmp");
+    assert!(!config_path(home.path()).exists());
+
+    offline(home.path())
+        .args(["--target", "cursor", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("fs"));
+}
+
+#[test]
+fn each_client_keeps_its_own_metadata_backups_and_log() {
+    let home = setup();
+    let claude_dir = config_path(home.path()).parent().unwrap().to_path_buf();
+    let cursor_dir = claude_dir.join("targets/cursor");
+
+    offline(home.path())
+        .args(["--target", "cursor", "add", "fs", "filesystem"])
+        .args(["--vars", "paths=/tmp"])
+        .assert()
+        .success();
+    offline(home.path())
+        .args(["--target", "cursor", "remove", "fs", "--force"])
+        .assert()
+        .success();
+
+    let metadata = read_json(&cursor_dir.join("server_metadata.json"));
+    assert!(metadata["servers"].get("fs").is_none());
+    assert!(fs::read_dir(cursor_dir.join("backups")).unwrap().count() > 0);
+    assert!(!claude_dir.join("server_metadata.json").exists());
+    assert!(!claude_dir.join("backups").exists());
+    assert!(!claude_dir.join("operations.log").exists());
+
+    offline(home.path())
+        .args(["--target", "cursor", "log"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("remove"));
+    offline(home.path())
+        .arg("log")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No operations recorded yet."));
+}
+
+#[test]
+fn vscode_config_keeps_its_own_shape() {
+    let home = setup();
+    let file = home.path().join(".config/Code/User/mcp.json");
+    fs::create_dir_all(file.parent().unwrap()).unwrap();
+    fs::write(
+        &file,
+        r#"{ "servers": { "web": { "type": "http", "url": "https://example.com/mcp" } }, "inputs": [] }"#,
+    )
+    .unwrap();
+
+    offline(home.path())
+        .args(["--target", "vscode", "add", "fs", "filesystem"])
+        .args(["--vars", "paths=/tmp"])
+        .assert()
+        .success();
+
+    let vscode = read_json(&file);
+    assert!(vscode.get("mcpServers").is_none());
+    assert_eq!(vscode["servers"]["fs"]["type"], "stdio");
+    assert_eq!(vscode["servers"]["web"]["url"], "https://example.com/mcp");
+    assert_eq!(vscode["inputs"], serde_json::json!([]));
+
+    offline(home.path())
+        .args(["--target", "vscode", "remove", "web", "--force"])
+        .assert()
+        .success();
+    assert!(read_json(&file)["servers"].get("web").is_none());
+}
+
+#[test]
+fn custom_target_uses_the_given_file() {
+    let home = setup();
+    let file = home.path().join("project/mcp.json");
+    fs::create_dir_all(file.parent().unwrap()).unwrap();
+    fs::write(&file, r#"{ "mcpServers": { "db": { "command": "sh" } } }"#).unwrap();
+    let target = format!("custom:{}", file.display());
+
+    mcp_forge(home.path())
+        .args(["--target", &target, "config", "path"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("project/mcp.json"));
+
+    mcp_forge(home.path())
+        .args(["--target", &target, "validate", "db"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn profiles_only_apply_to_claude_desktop() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["--target", "windsurf", "profile", "list"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Profiles only manage the Claude Desktop config",
+        ));
+
+    mcp_forge(home.path())
+        .args(["--target", "zed", "list"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("custom:<path>"));
+}
@@ -0,0 +1,60 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(
+        home.path(),
+        r#"{ "mcpServers": { "github": { "command": "npx", "args": ["github-mcp"] } } }"#,
+    );
+    home
+}
+
+#[test]
+fn wizard_needs_a_terminal() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .current_dir(home.path())
+        .args(["template", "create", "github", "--from-server", "github"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("needs a terminal"));
+    assert!(!home.path().join("github.json").exists());
+}
+
+#[test]
+fn unknown_server_is_reported_before_prompting() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["template", "create", "gh", "--from-server", "gitlab"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Server 'gitlab' not found"))
+        .stderr(predicate::str::contains("Existing servers: github"));
+}
+
+#[test]
+fn existing_file_needs_force() {
+    let home = setup();
+    let dir = home.path().join("templates");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("github.json"), "{}").unwrap();
+
+    mcp_forge(home.path())
+        .args(["template", "create", "github", "--dir"])
+        .arg(&dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("pass --force to overwrite it"));
+    assert_eq!(fs::read_to_string(dir.join("github.json")).unwrap(), "{}");
+}
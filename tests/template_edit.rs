@@ -0,0 +1,89 @@
+// These tests redirect the config directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const TEMPLATE: &str = r#"{
+    "name": "postgres",
+    "version": "1.0.0",
+    "description": "PostgreSQL",
+    "author": "Test",
+    "variables": {
+        "port": { "type": "number", "description": "Port", "default": 5432 },
+        "host": { "type": "string", "description": "Host" }
+    },
+    "config": { "command": "npx", "args": ["pg-mcp", "{{host}}:{{port}}"] }
+}"#;
+
+fn setup() -> (TempDir, PathBuf) {
+    let home = TempDir::new().unwrap();
+    let file = home.path().join("postgres.json");
+    fs::write(&file, TEMPLATE).unwrap();
+    (home, file)
+}
+
+#[test]
+fn set_var_updates_the_file_in_place() {
+    let (home, file) = setup();
+
+    mcp_forge(home.path())
+        .args(["template", "edit"])
+        .arg(&file)
+        .args(["--set-var", "port.default=5433", "--set", "version=1.0.1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Updated"));
+
+    let written = fs::read_to_string(&file).unwrap();
+    let template: serde_json::Value = serde_json::from_str(&written).unwrap();
+    assert_eq!(template["variables"]["port"]["default"], 5433);
+    assert_eq!(template["version"], "1.0.1");
+    // Fields stay where they were written
+    assert!(written.find("\"port\"").unwrap() < written.find("\"host\"").unwrap());
+    assert!(written.find("\"author\"").unwrap() < written.find("\"variables\"").unwrap());
+}
+
+#[test]
+fn invalid_result_leaves_the_file_alone() {
+    let (home, file) = setup();
+
+    mcp_forge(home.path())
+        .args(["template", "edit"])
+        .arg(&file)
+        .args(["--remove-var", "host"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("was not changed"))
+        .stderr(predicate::str::contains(
+            "arg 1 uses 'host', which is not a variable",
+        ));
+    assert_eq!(fs::read_to_string(&file).unwrap(), TEMPLATE);
+
+    mcp_forge(home.path())
+        .args(["template", "edit"])
+        .arg(&file)
+        .args(["--set-var", "port.default=high"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must be a number"));
+    assert_eq!(fs::read_to_string(&file).unwrap(), TEMPLATE);
+}
+
+#[test]
+fn menu_needs_a_terminal() {
+    let (home, file) = setup();
+
+    mcp_forge(home.path())
+        .args(["template", "edit"])
+        .arg(&file)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--set-var"));
+}
@@ -0,0 +1,106 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const TEMPLATE: &str = r#"{
+  "name": "demo",
+  "version": "1.0.0",
+  "description": "Demo template",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {},
+  "config": { "command": "demo", "args": [] }
+}"#;
+
+/// Seed a template cached from the default repository
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    seed_templates(home.path(), &[("demo", TEMPLATE)]);
+    home
+}
+
+#[test]
+fn set_saves_the_repository_and_get_reports_it() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["template", "repo", "get"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "AndyCross/mcp-forge-templates@master (default)",
+        ));
+
+    offline(home.path())
+        .args(["template", "repo", "set", "acme/templates@dev"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Templates now come from acme/templates@dev",
+        ));
+    let settings = fs::read_to_string(home.path().join(".config/mcp-forge/settings.json")).unwrap();
+    assert!(settings.contains(r#""template_repo": "acme/templates@dev""#));
+
+    offline(home.path())
+        .args(["template", "repo", "get"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("acme/templates@dev (set in"));
+    offline(home.path())
+        .args(["template", "--repo", "other/fork", "repo", "get"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("other/fork@master (--repo)"));
+}
+
+#[test]
+fn set_rejects_a_malformed_repository() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["template", "repo", "set", "not-a-repo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected owner/repo"));
+    assert!(!home.path().join(".config/mcp-forge/settings.json").exists());
+}
+
+#[test]
+fn cache_from_another_repository_is_not_used() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["template", "show", "demo", "--cached"])
+        .assert()
+        .success();
+
+    offline(home.path())
+        .args([
+            "template",
+            "--repo",
+            "acme/templates",
+            "show",
+            "demo",
+            "--cached",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Template 'demo' is not cached"));
+
+    offline(home.path())
+        .args(["template", "repo", "set", "acme/templates"])
+        .assert()
+        .success();
+    offline(home.path())
+        .args(["template", "show", "demo"])
+        .assert()
+        .failure();
+}
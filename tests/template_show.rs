@@ -0,0 +1,131 @@
+// These tests redirect the cache directory through $HOME, which `dirs`
+// only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const TEMPLATE: &str = r#"{
+  "name": "demo",
+  "version": "1.0.0",
+  "description": "Demo template",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {},
+  "config": { "command": "demo", "args": [] }
+}"#;
+
+/// Seed a cached template whose cache metadata has expired
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    let cache = seed_templates(home.path(), &[("demo", TEMPLATE)]);
+    fs::write(
+        cache.parent().unwrap().join("metadata.json"),
+        r#"{
+          "last_refresh": "2020-01-01T00:00:00Z",
+          "etag": null,
+          "catalog_etag": null,
+          "expires_at": "2020-01-31T00:00:00Z"
+        }"#,
+    )
+    .unwrap();
+    home
+}
+
+#[test]
+fn show_falls_back_to_expired_cache_when_offline() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["template", "show", "demo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Template: demo"))
+        .stderr(predicate::str::contains("using the cached copy"));
+}
+
+#[test]
+fn show_offline_reads_cache_only() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["template", "show", "demo", "--offline"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Template: demo"))
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+fn show_cached_lists_cached_names_when_missing() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["template", "show", "missing", "--cached"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Template 'missing' is not cached. Cached templates: demo",
+        ));
+}
+
+#[test]
+fn show_raw_prints_the_cached_file_verbatim() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["template", "show", "demo", "--raw", "--cached"])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", TEMPLATE))
+        .stderr(predicate::str::contains("Source: cache ("));
+}
+
+#[test]
+fn show_raw_falls_back_to_expired_cache_when_offline() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["template", "show", "demo", "--raw"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(TEMPLATE))
+        .stderr(predicate::str::contains("using the cached copy"))
+        .stderr(predicate::str::contains("Source: cache ("));
+}
+
+#[test]
+fn cat_writes_a_local_copy_and_lints_it() {
+    let home = setup();
+    let risky = home.path().join("risky.json");
+    fs::write(
+        &risky,
+        TEMPLATE.replace(
+            r#""variables": {},"#,
+            r#""variables": { "api_key": { "type": "string", "description": "Key" } },"#,
+        ),
+    )
+    .unwrap();
+
+    let copy = home.path().join("fork.json");
+    offline(home.path())
+        .args(["template", "cat"])
+        .arg(&risky)
+        .arg("--output")
+        .arg(&copy)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Source: local file"))
+        .stderr(predicate::str::contains(
+            "Variable 'api_key' looks like a credential but isn't marked secret",
+        ));
+    assert_eq!(
+        fs::read_to_string(&copy).unwrap(),
+        fs::read_to_string(&risky).unwrap()
+    );
+}
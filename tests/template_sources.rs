@@ -0,0 +1,188 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn template(name: &str, author: &str) -> String {
+    format!(
+        r#"{{
+  "name": "{}",
+  "version": "1.0.0",
+  "description": "A {} template",
+  "author": "{}",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {{}},
+  "config": {{ "command": "{}-mcp", "args": [] }}
+}}"#,
+        name, name, author, name
+    )
+}
+
+const CATALOG: &str = r#"{
+  "version": "1",
+  "last_updated": "2025-01-01",
+  "templates": {
+    "demo": {
+      "name": "demo", "version": "1.0.0", "description": "A demo template",
+      "author": "Upstream", "tags": [], "platforms": ["linux", "macos", "windows"],
+      "category": "official", "path": "templates/demo.json"
+    }
+  }
+}"#;
+
+/// A home with `demo` cached from the repository, and a team directory that
+/// has its own `demo` plus `internal`
+fn setup() -> (TempDir, TempDir) {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    let cache = seed_templates(home.path(), &[("demo", &template("demo", "Upstream"))]);
+    fs::write(cache.parent().unwrap().join("catalog.json"), CATALOG).unwrap();
+
+    let team = TempDir::new().unwrap();
+    fs::write(team.path().join("demo.json"), template("demo", "Team")).unwrap();
+    fs::write(
+        team.path().join("internal.json"),
+        template("internal", "Team"),
+    )
+    .unwrap();
+    (home, team)
+}
+
+fn add_team_source(home: &Path, team: &Path) {
+    offline(home)
+        .args(["template", "source", "add", "team"])
+        .arg(team)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added template source 'team'"));
+}
+
+#[test]
+fn sources_are_added_listed_and_removed() {
+    let (home, team) = setup();
+    add_team_source(home.path(), team.path());
+
+    offline(home.path())
+        .args(["template", "source", "list"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("default  github AndyCross/mcp-forge-templates@master")
+                .and(predicate::str::contains("team     dir ")),
+        );
+    let settings = fs::read_to_string(home.path().join(".config/mcp-forge/settings.json")).unwrap();
+    assert!(settings.contains(r#""name": "team""#));
+
+    offline(home.path())
+        .args(["template", "source", "add", "team", "acme/templates"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+    offline(home.path())
+        .args(["template", "source", "remove", "default"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("can't be removed"));
+
+    offline(home.path())
+        .args(["template", "source", "remove", "team"])
+        .assert()
+        .success();
+    offline(home.path())
+        .args(["template", "source", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("team").not());
+}
+
+#[test]
+fn list_merges_sources_and_the_first_one_wins() {
+    let (home, team) = setup();
+    add_team_source(home.path(), team.path());
+
+    offline(home.path())
+        .args(["template", "list", "--cached"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("demo - A demo template")
+                .and(predicate::str::contains("Source: default"))
+                .and(predicate::str::contains("internal - A internal template"))
+                .and(predicate::str::contains("Source: team")),
+        );
+
+    offline(home.path())
+        .args(["template", "show", "demo", "--offline"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Author: Upstream")
+                .and(predicate::str::contains("Source: default")),
+        );
+    offline(home.path())
+        .args(["template", "show", "demo", "--offline", "--source", "team"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Author: Team").and(predicate::str::contains("Source: team")),
+        );
+    offline(home.path())
+        .args(["template", "show", "demo", "--source", "nowhere"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Unknown template source 'nowhere'; sources: default, team",
+        ));
+}
+
+#[test]
+fn add_loads_from_the_named_source() {
+    let (home, team) = setup();
+    add_team_source(home.path(), team.path());
+
+    offline(home.path())
+        .args(["add", "tools", "demo", "--source", "team"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("from source 'team'"));
+    assert_eq!(
+        read_config(home.path())["mcpServers"]["tools"]["command"],
+        "demo-mcp"
+    );
+
+    // Without --source, a template only the team has is still found
+    offline(home.path())
+        .args(["add", "internal", "internal"])
+        .assert()
+        .success();
+    assert_eq!(
+        read_config(home.path())["mcpServers"]["internal"]["command"],
+        "internal-mcp"
+    );
+}
+
+#[test]
+fn refresh_reports_each_source() {
+    let (home, team) = setup();
+    add_team_source(home.path(), team.path());
+
+    offline(home.path())
+        .args(["template", "refresh"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("✗ default")
+                .and(predicate::str::contains("✓ team: 2 template(s)"))
+                .and(predicate::str::contains(
+                    "Refreshed 1 of 2 template sources",
+                )),
+        );
+}
@@ -0,0 +1,135 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn template(version: &str) -> String {
+    format!(
+        r#"{{
+  "name": "files",
+  "version": "{}",
+  "description": "Files",
+  "author": "Team",
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {{}},
+  "config": {{ "command": "files-mcp", "args": [] }}
+}}"#,
+        version
+    )
+}
+
+/// `offline` with `team` as the local template directory
+fn with_team(home: &Path, team: &Path) -> assert_cmd::Command {
+    let mut cmd = offline(home);
+    cmd.env("MCP_FORGE_TEMPLATE_PATH", team);
+    cmd
+}
+
+/// A home with `docs` added from v1.0.0 of a local template that is now at
+/// `version`
+fn setup(version: &str) -> (TempDir, TempDir) {
+    let home = TempDir::new().unwrap();
+    write_config(
+        home.path(),
+        r#"{ "mcpServers": { "git": { "command": "git-mcp", "args": [] } } }"#,
+    );
+    let team = TempDir::new().unwrap();
+    fs::write(team.path().join("files.json"), template("1.0.0")).unwrap();
+    with_team(home.path(), team.path())
+        .args(["add", "docs", "files"])
+        .assert()
+        .success();
+    fs::write(team.path().join("files.json"), template(version)).unwrap();
+    (home, team)
+}
+
+fn outdated_json(home: &Path, team: &Path) -> serde_json::Value {
+    let output = with_team(home, team)
+        .args(["template", "outdated", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn outdated_lists_servers_behind_the_latest_version() {
+    let (home, team) = setup("1.1.0");
+
+    let rows = outdated_json(home.path(), team.path());
+    assert_eq!(
+        rows,
+        serde_json::json!([{
+            "server": "docs",
+            "template": "files",
+            "version": "1.0.0",
+            "latest": "1.1.0",
+            "pinned": null,
+            "status": "outdated"
+        }])
+    );
+
+    with_team(home.path(), team.path())
+        .args(["template", "outdated"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("docs - files v1.0.0 → v1.1.0"))
+        .stdout(predicate::str::contains("1 outdated, 0 pinned"));
+}
+
+#[test]
+fn pinned_servers_are_measured_against_their_pin() {
+    let (home, team) = setup("2.0.0");
+
+    with_team(home.path(), team.path())
+        .args(["template", "pin", "docs", "1.0.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pinned docs to files v1.0.0"));
+    let rows = outdated_json(home.path(), team.path());
+    assert_eq!(rows[0]["pinned"], "1.0.0");
+    assert_eq!(rows[0]["latest"], "2.0.0");
+    assert_eq!(rows[0]["status"], "pinned");
+
+    with_team(home.path(), team.path())
+        .args(["show", "docs"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pinned to: v1.0.0"));
+
+    with_team(home.path(), team.path())
+        .args(["template", "pin", "docs", "--unpin"])
+        .assert()
+        .success();
+    assert_eq!(
+        outdated_json(home.path(), team.path())[0]["status"],
+        "outdated"
+    );
+}
+
+#[test]
+fn pin_needs_a_template_server_and_a_version() {
+    let (home, team) = setup("1.0.0");
+
+    with_team(home.path(), team.path())
+        .args(["template", "pin", "git", "1.0.0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("wasn't added from a template"));
+    with_team(home.path(), team.path())
+        .args(["template", "pin", "docs", "newest"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'newest' is not a version"));
+    with_team(home.path(), team.path())
+        .args(["template", "pin", "docs"])
+        .assert()
+        .failure();
+}
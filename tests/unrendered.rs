@@ -0,0 +1,96 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+// `\{{readonly}}` is handlebars for literal braces, so strict mode lets it through
+const BROKEN_TEMPLATE: &str = r#"{
+  "name": "files",
+  "version": "1.0.0",
+  "description": "Files",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {
+    "root": { "type": "string", "description": "Root", "required": true }
+  },
+  "config": {
+    "command": "files-mcp",
+    "args": ["{{root}}"],
+    "env": { "READONLY": "\\{{readonly}}" }
+  }
+}"#;
+
+const DAMAGED_CONFIG: &str = r#"{
+  "mcpServers": {
+    "imported": {
+      "command": "sh",
+      "args": ["-c", "true", "--mode={{#if readonly}}ro{{/if}}"],
+      "env": { "SETTINGS": "{\"nested\": {\"ok\": true}}" }
+    }
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    seed_templates(home.path(), &[("files", BROKEN_TEMPLATE)]);
+    home
+}
+
+#[test]
+fn add_refuses_leftover_handlebars_syntax() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["add", "docs", "files", "--vars", "root=/srv"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Template 'files' left handlebars syntax in the server:\n  env var 'READONLY': {{readonly}}",
+        ))
+        .stderr(predicate::str::contains("--allow-unrendered"));
+    assert!(read_config(home.path())["mcpServers"]["docs"].is_null());
+}
+
+#[test]
+fn allow_unrendered_adds_with_a_warning() {
+    let home = setup();
+
+    offline(home.path())
+        .args([
+            "add",
+            "docs",
+            "files",
+            "--vars",
+            "root=/srv",
+            "--allow-unrendered",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("env var 'READONLY': {{readonly}}"));
+    assert_eq!(
+        read_config(home.path())["mcpServers"]["docs"]["env"]["READONLY"],
+        "{{readonly}}"
+    );
+}
+
+#[test]
+fn validate_reports_damage_in_an_existing_config() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), DAMAGED_CONFIG);
+
+    mcp_forge(home.path())
+        .args(["validate", "imported", "--format", "json"])
+        .assert()
+        .stdout(predicate::str::contains(
+            "Template syntax left in argument 3: '{{#if readonly}}'",
+        ))
+        // Nested JSON in an env value is not template syntax
+        .stdout(predicate::str::contains("SETTINGS").not());
+}
@@ -0,0 +1,167 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const V1: &str = r#"{
+  "name": "db",
+  "version": "1.0.0",
+  "description": "Database",
+  "author": "Team",
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {
+    "host": { "type": "string", "description": "Host", "required": true },
+    "token": { "type": "string", "description": "Token", "required": true, "secret": true }
+  },
+  "config": {
+    "command": "db-mcp",
+    "args": ["--host", "{{host}}"],
+    "env": { "DB_TOKEN": "{{token}}" }
+  }
+}"#;
+
+const V2: &str = r#"{
+  "name": "db",
+  "version": "1.1.0",
+  "description": "Database",
+  "author": "Team",
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {
+    "host": { "type": "string", "description": "Host", "required": true },
+    "token": { "type": "string", "description": "Token", "required": true, "secret": true },
+    "region": { "type": "string", "description": "Region", "default": "eu-west-1" }
+  },
+  "config": {
+    "command": "db-mcp",
+    "args": ["--host", "{{host}}", "--region", "{{region}}"],
+    "env": { "DB_TOKEN": "{{token}}" }
+  }
+}"#;
+
+/// `offline` with `team` as the local template directory
+fn with_team(home: &Path, team: &Path) -> assert_cmd::Command {
+    let mut cmd = offline(home);
+    cmd.env("MCP_FORGE_TEMPLATE_PATH", team);
+    cmd
+}
+
+/// A home with `orders` and `users` added from v1 of the template, which is
+/// now at v2
+fn setup() -> (TempDir, TempDir) {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    let team = TempDir::new().unwrap();
+    fs::write(team.path().join("db.json"), V1).unwrap();
+    for (name, host) in [("orders", "orders.db"), ("users", "users.db")] {
+        with_team(home.path(), team.path())
+            .args(["add", name, "db", "--var", &format!("host={}", host)])
+            .args(["--var", "token=s3cret-token"])
+            .assert()
+            .success();
+    }
+    fs::write(team.path().join("db.json"), V2).unwrap();
+    (home, team)
+}
+
+fn backup_count(home: &Path) -> usize {
+    let dir = config_path(home).parent().unwrap().join("backups");
+    fs::read_dir(dir)
+        .map(|entries| entries.count())
+        .unwrap_or(0)
+}
+
+#[test]
+fn upgrade_rebuilds_the_server_keeping_its_values() {
+    let (home, team) = setup();
+    let backups = backup_count(home.path());
+
+    with_team(home.path(), team.path())
+        .args(["upgrade", "orders", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("orders: db v1.0.0 → v1.1.0"))
+        .stdout(predicate::str::contains("--region eu-west-1"))
+        .stdout(predicate::str::contains("s3cret-token").not())
+        .stdout(predicate::str::contains("✓ Upgraded 'orders' to db v1.1.0"));
+
+    let server = &read_config(home.path())["mcpServers"]["orders"];
+    assert_eq!(
+        server["args"],
+        serde_json::json!(["--host", "orders.db", "--region", "eu-west-1"])
+    );
+    // The secret was never recorded, but is read back from the config
+    assert_eq!(server["env"]["DB_TOKEN"], "s3cret-token");
+    assert_eq!(backup_count(home.path()), backups + 1);
+
+    with_team(home.path(), team.path())
+        .args(["show", "orders"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Template: db v1.1.0"));
+    with_team(home.path(), team.path())
+        .args(["upgrade", "orders"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("'orders' is already on db v1.1.0"));
+}
+
+#[test]
+fn dry_run_and_all() {
+    let (home, team) = setup();
+    let before = read_config(home.path());
+
+    with_team(home.path(), team.path())
+        .args(["upgrade", "--all", "--dry-run", "--var", "region=us-east-1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("orders: db v1.0.0 → v1.1.0"))
+        .stdout(predicate::str::contains("users: db v1.0.0 → v1.1.0"))
+        .stdout(predicate::str::contains("--region us-east-1"))
+        .stdout(predicate::str::contains("nothing was changed"));
+    assert_eq!(read_config(home.path()), before);
+
+    with_team(home.path(), team.path())
+        .args(["template", "pin", "users", "1.0.0"])
+        .assert()
+        .success();
+    with_team(home.path(), team.path())
+        .args(["upgrade", "--all", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Skipping 'users', pinned to db v1.0.0",
+        ))
+        .stdout(predicate::str::contains("✓ Upgraded 'orders'"));
+    let config = read_config(home.path());
+    assert_eq!(config["mcpServers"]["orders"]["args"][3], "eu-west-1");
+    assert_eq!(config["mcpServers"]["users"], before["mcpServers"]["users"]);
+
+    with_team(home.path(), team.path())
+        .args(["upgrade", "users"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is pinned to db v1.0.0"));
+}
+
+#[test]
+fn a_missing_template_is_an_error() {
+    let (home, team) = setup();
+    fs::remove_file(team.path().join("db.json")).unwrap();
+    let before = read_config(home.path());
+
+    with_team(home.path(), team.path())
+        .args(["upgrade", "orders", "--force"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Couldn't load template 'db', which 'orders' was created from",
+        ));
+    assert_eq!(read_config(home.path()), before);
+}
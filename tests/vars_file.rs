@@ -0,0 +1,154 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const TEMPLATE: &str = r#"{
+  "name": "postgres",
+  "version": "1.0.0",
+  "description": "Postgres",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {
+    "host": { "type": "string", "description": "Database host", "required": true },
+    "port": { "type": "number", "description": "Database port", "required": true },
+    "ssl": { "type": "boolean", "description": "Use TLS" }
+  },
+  "config": {
+    "command": "pg-mcp",
+    "args": ["--host", "{{host}}", "--port", "{{port}}", "{{#if ssl}}--ssl{{/if}}"]
+  }
+}"#;
+
+const VARS: &str = "host: db.internal\nport: 5432\nssl: false\n";
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    seed_templates(home.path(), &[("postgres", TEMPLATE)]);
+    home
+}
+
+fn args_of(home: &TempDir, name: &str) -> serde_json::Value {
+    read_config(home.path())["mcpServers"][name]["args"].clone()
+}
+
+#[test]
+fn explicit_vars_override_the_file() {
+    let home = setup();
+    let file = home.path().join("pg.yaml");
+    fs::write(&file, VARS).unwrap();
+
+    offline(home.path())
+        .args(["add", "db", "postgres", "--vars-file"])
+        .arg(&file)
+        .args(["--vars", "host=override.local"])
+        .assert()
+        .success();
+
+    assert_eq!(
+        args_of(&home, "db"),
+        serde_json::json!(["--host", "override.local", "--port", "5432", ""])
+    );
+}
+
+#[test]
+fn vars_file_can_be_read_from_stdin() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["add", "db", "postgres", "--vars-file", "-"])
+        .write_stdin(r#"{ "host": "db.internal", "port": "6543", "ssl": "true" }"#)
+        .assert()
+        .success();
+
+    assert_eq!(
+        args_of(&home, "db"),
+        serde_json::json!(["--host", "db.internal", "--port", "6543", "--ssl"])
+    );
+}
+
+#[test]
+fn mistyped_values_name_the_variable_and_type() {
+    let home = setup();
+    let file = home.path().join("pg.json");
+    fs::write(&file, r#"{ "host": "db.internal", "port": "lots" }"#).unwrap();
+
+    offline(home.path())
+        .args(["add", "db", "postgres", "--vars-file"])
+        .arg(&file)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Variable 'port' expects a number (Database port): 'lots' is not a number",
+        ));
+}
+
+#[test]
+fn bulk_vars_file_is_relative_to_the_batch_file() {
+    let home = setup();
+    let batch_dir = home.path().join("batch");
+    fs::create_dir_all(batch_dir.join("vars")).unwrap();
+    fs::write(batch_dir.join("vars/pg.yaml"), VARS).unwrap();
+    fs::write(
+        batch_dir.join("servers.yaml"),
+        "servers:\n  - name: db\n    template: postgres\n    vars_file: vars/pg.yaml\n    vars:\n      port: \"7000\"\n",
+    )
+    .unwrap();
+
+    offline(home.path())
+        .args(["bulk", "add", "--file"])
+        .arg(batch_dir.join("servers.yaml"))
+        .assert()
+        .success();
+
+    assert_eq!(
+        args_of(&home, "db"),
+        serde_json::json!(["--host", "db.internal", "--port", "7000", ""])
+    );
+}
+
+#[test]
+fn quoted_vars_and_repeated_var_keep_commas_and_equals_signs() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["add", "db", "postgres"])
+        .args(["--vars", r#"host="h?a=1,b=2",port=5432"#])
+        .assert()
+        .success();
+    assert_eq!(
+        args_of(&home, "db"),
+        serde_json::json!(["--host", "h?a=1,b=2", "--port", "5432", ""])
+    );
+
+    offline(home.path())
+        .args(["add", "db2", "postgres", "--vars", "host=ignored,port=1"])
+        .args(["--var", "host=h?a=1,b=2", "--var", "port=5432"])
+        .assert()
+        .success();
+    assert_eq!(
+        args_of(&home, "db2"),
+        serde_json::json!(["--host", "h?a=1,b=2", "--port", "5432", ""])
+    );
+}
+
+#[test]
+fn unmatched_quote_in_vars_is_reported() {
+    let home = setup();
+
+    offline(home.path())
+        .args(["add", "db", "postgres", "--vars", r#"host="h,port=5432"#])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            r#"Unmatched " quote at column 6 in 'host="h,port=5432'"#,
+        ));
+}
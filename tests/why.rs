@@ -0,0 +1,101 @@
+// These tests redirect the config and cache directories through $HOME, which
+// `dirs` only honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const TEMPLATE: &str = r#"{
+  "name": "search",
+  "version": "1.2.0",
+  "description": "Search",
+  "author": "Test",
+  "tags": [],
+  "platforms": ["linux", "macos", "windows"],
+  "variables": {
+    "region": { "type": "string", "description": "Region", "required": true },
+    "api_key": { "type": "string", "description": "API key", "required": true, "secret": true }
+  },
+  "config": {
+    "command": "search-mcp",
+    "args": ["--region", "{{region}}"],
+    "env": { "SEARCH_CREDENTIAL": "{{api_key}}" }
+  }
+}"#;
+
+fn setup() -> TempDir {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), r#"{ "mcpServers": {} }"#);
+    fs::create_dir_all(config_path(home.path()).with_file_name("backups")).unwrap();
+    seed_templates(home.path(), &[("search", TEMPLATE)]);
+    home
+}
+
+#[test]
+fn why_combines_metadata_journal_and_backups() {
+    let home = setup();
+    let vars = home.path().join("vars.yaml");
+    fs::write(&vars, "region: eu\napi_key: sk-live-0123456789\n").unwrap();
+
+    offline(home.path())
+        .args(["add", "web", "search", "--vars-file"])
+        .arg(&vars)
+        .assert()
+        .success();
+    mcp_forge(home.path())
+        .args(["update", "web", "--set", "LOG_LEVEL=debug"])
+        .assert()
+        .success();
+
+    let output = mcp_forge(home.path())
+        .args(["why", "web", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(report["configured"], true);
+    assert_eq!(report["provenance"]["template"], "search");
+    assert_eq!(
+        report["provenance"]["variables"],
+        serde_json::json!({ "region": "eu" })
+    );
+    let commands: Vec<&str> = report["timeline"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|event| event["description"].as_str().unwrap())
+        .filter(|description| description.starts_with('`'))
+        .collect();
+    assert_eq!(commands.len(), 2);
+    assert!(commands[0].starts_with("`add`"));
+    assert!(commands[1].starts_with("`update`"));
+    assert!(report["last_modified"]["description"]
+        .as_str()
+        .unwrap()
+        .starts_with("`update`"));
+
+    let backups = report["backups"].as_array().unwrap();
+    assert_eq!(backups.len(), 1);
+    assert_eq!(backups[0]["diff"], "differs in env (+LOG_LEVEL)");
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("sk-live-0123456789"));
+}
+
+#[test]
+fn why_reports_unknown_servers() {
+    let home = setup();
+
+    mcp_forge(home.path())
+        .args(["why", "ghost"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Server 'ghost' is not configured and has no recorded history",
+        ));
+}
@@ -0,0 +1,145 @@
+// These tests redirect the config directory through $HOME, which `dirs` only
+// honours on Unix-like platforms.
+#![cfg(unix)]
+
+mod common;
+
+use common::*;
+use predicates::prelude::*;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"{
+  "mcpServers": {
+    "global": { "command": "global-mcp", "args": [] },
+    "shared": { "command": "configured-mcp", "args": [] }
+  }
+}"#;
+
+const WORKSPACE: &str = r#"
+servers:
+  - name: project-db
+    command: db-mcp
+    args: ["--root", "./data"]
+  - name: shared
+    command: workspace-mcp
+"#;
+
+/// A home with a global config and a project declaring its own servers
+fn setup() -> (TempDir, PathBuf) {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+    let project = home.path().join("project");
+    fs::create_dir_all(project.join(".mcp-forge")).unwrap();
+    fs::write(project.join(".mcp-forge/servers.yaml"), WORKSPACE).unwrap();
+    fs::create_dir_all(project.join("src/nested")).unwrap();
+    (home, project)
+}
+
+fn in_project(home: &TempDir, dir: &std::path::Path) -> assert_cmd::Command {
+    let mut cmd = mcp_forge(home.path());
+    cmd.current_dir(dir)
+        .env("MCP_FORGE_WORKSPACE_DISCOVERY", "1");
+    cmd
+}
+
+#[test]
+fn discovery_is_opt_in() {
+    let (home, project) = setup();
+
+    mcp_forge(home.path())
+        .current_dir(&project)
+        .env_remove("MCP_FORGE_WORKSPACE_DISCOVERY")
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("global"))
+        .stdout(predicate::str::contains("project-db").not());
+}
+
+#[test]
+fn list_labels_workspace_servers_and_prefers_the_config() {
+    let (home, project) = setup();
+    let file = project.join(".mcp-forge/servers.yaml");
+
+    in_project(&home, &project.join("src/nested"))
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "• project-db\n  [workspace] {}",
+            file.display()
+        )))
+        .stdout(predicate::str::contains("configured-mcp"))
+        .stdout(predicate::str::contains("workspace-mcp").not())
+        .stderr(predicate::str::contains("Workspace server 'shared'"));
+
+    let output = in_project(&home, &project)
+        .args(["list", "--json"])
+        .output()
+        .unwrap();
+    let servers: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let workspace_file = |name: &str| {
+        let server = servers.iter().find(|s| s["name"] == name).unwrap();
+        server["workspace_file"].clone()
+    };
+    assert_eq!(workspace_file("project-db"), file.display().to_string());
+    assert!(workspace_file("global").is_null());
+    assert!(workspace_file("shared").is_null());
+}
+
+#[test]
+fn validate_includes_workspace_servers() {
+    let (home, project) = setup();
+
+    in_project(&home, &project)
+        .args(["validate", "project-db", "--format", "json"])
+        .assert()
+        .stdout(predicate::str::contains("\"server_name\": \"project-db\""))
+        .stdout(predicate::str::contains("[workspace] declared in"));
+}
+
+#[test]
+fn install_adds_workspace_servers_without_touching_the_file() {
+    let (home, project) = setup();
+    let file = project.join(".mcp-forge/servers.yaml");
+
+    mcp_forge(home.path())
+        .current_dir(project.join("src"))
+        .args(["workspace", "install", "--dry-run"])
+        .assert()
+        .success();
+    assert!(read_config(home.path())["mcpServers"]["project-db"].is_null());
+
+    mcp_forge(home.path())
+        .current_dir(project.join("src"))
+        .args(["workspace", "install"])
+        .assert()
+        .success();
+
+    let config = read_config(home.path());
+    assert_eq!(config["mcpServers"]["project-db"]["command"], "db-mcp");
+    assert_eq!(config["mcpServers"]["shared"]["command"], "workspace-mcp");
+    assert_eq!(fs::read_to_string(&file).unwrap(), WORKSPACE);
+
+    // Once installed, the configured server is listed without the badge
+    in_project(&home, &project)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[workspace]").not());
+}
+
+#[test]
+fn install_outside_a_workspace_fails() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), CONFIG);
+
+    mcp_forge(home.path())
+        .current_dir(home.path())
+        .args(["workspace", "install"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No .mcp-forge/servers.yaml found"));
+}